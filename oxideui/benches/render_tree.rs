@@ -0,0 +1,75 @@
+//! Per-frame cost of building and discarding a render tree, with and
+//! without recycling the previous frame's allocations.
+//!
+//! Run with `cargo bench --bench render_tree`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use oxideui::core::{Matrix, Rect};
+use oxideui::{Color, RenderObject};
+
+const ROWS: usize = 200;
+const COLUMNS: usize = 20;
+
+fn cell() -> RenderObject {
+    RenderObject::rect(Rect::new(0.0, 0.0, 40.0, 20.0), Color::WHITE)
+}
+
+/// A table-like frame built the way widgets did before pooling
+fn build_allocating() -> RenderObject {
+    let mut rows = Vec::with_capacity(ROWS);
+    for row in 0..ROWS {
+        let mut cells = Vec::with_capacity(COLUMNS);
+        for column in 0..COLUMNS {
+            cells.push(RenderObject::Transform {
+                matrix: Matrix::translate(column as f32 * 40.0, 0.0),
+                child: Box::new(RenderObject::Clip {
+                    rect: Rect::new(0.0, 0.0, 40.0, 20.0),
+                    child: Box::new(cell()),
+                }),
+            });
+        }
+        rows.push(RenderObject::Transform {
+            matrix: Matrix::translate(0.0, row as f32 * 20.0),
+            child: Box::new(RenderObject::group(cells)),
+        });
+    }
+    RenderObject::group(rows)
+}
+
+/// The same frame built from pooled buffers
+fn build_pooled() -> RenderObject {
+    let mut rows = RenderObject::child_vec(ROWS);
+    for row in 0..ROWS {
+        let mut cells = RenderObject::child_vec(COLUMNS);
+        for column in 0..COLUMNS {
+            cells.push(RenderObject::transform(
+                Matrix::translate(column as f32 * 40.0, 0.0),
+                RenderObject::clip(Rect::new(0.0, 0.0, 40.0, 20.0), cell()),
+            ));
+        }
+        rows.push(RenderObject::transform(
+            Matrix::translate(0.0, row as f32 * 20.0),
+            RenderObject::group(cells),
+        ));
+    }
+    RenderObject::group(rows)
+}
+
+fn render_tree(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render_tree_frame");
+
+    group.bench_function("allocate_and_drop", |b| {
+        b.iter(|| drop(black_box(build_allocating())));
+    });
+
+    // Warm the pool with one frame, as the runtime has after its first frame
+    build_pooled().recycle();
+    group.bench_function("pooled_and_recycled", |b| {
+        b.iter(|| black_box(build_pooled()).recycle());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, render_tree);
+criterion_main!(benches);
@@ -1,5 +1,6 @@
 //! Complete animation system with curves, springs, and keyframes
-use std::time::{Duration, Instant};
+use std::time::Duration;
+use web_time::Instant;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -9,6 +10,18 @@ pub struct AnimationId(u64);
 
 static ANIMATION_ID_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
 
+static REDUCED_MOTION: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Make every animation jump straight to its end value, and stop repeating
+/// (decorative) ones. The runtime sets this from the OS preference.
+pub fn set_reduced_motion(reduced: bool) {
+    REDUCED_MOTION.store(reduced, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 impl AnimationId {
     pub fn new() -> Self {
         AnimationId(ANIMATION_ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst))
@@ -132,6 +145,61 @@ impl Interpolate for crate::core::Color {
     }
 }
 
+impl Interpolate for crate::core::Theme {
+    /// Colors and metrics blend; fonts, `is_dark` and `forced_colors` switch
+    /// at the midpoint
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        let snap = if t < 0.5 { self } else { other };
+        Self {
+            background: self.background.interpolate(&other.background, t),
+            foreground: self.foreground.interpolate(&other.foreground, t),
+            card: self.card.interpolate(&other.card, t),
+            card_foreground: self.card_foreground.interpolate(&other.card_foreground, t),
+            popover: self.popover.interpolate(&other.popover, t),
+            popover_foreground: self.popover_foreground.interpolate(&other.popover_foreground, t),
+            primary: self.primary.interpolate(&other.primary, t),
+            primary_foreground: self.primary_foreground.interpolate(&other.primary_foreground, t),
+            secondary: self.secondary.interpolate(&other.secondary, t),
+            secondary_foreground: self.secondary_foreground.interpolate(&other.secondary_foreground, t),
+            muted: self.muted.interpolate(&other.muted, t),
+            muted_foreground: self.muted_foreground.interpolate(&other.muted_foreground, t),
+            accent: self.accent.interpolate(&other.accent, t),
+            accent_foreground: self.accent_foreground.interpolate(&other.accent_foreground, t),
+            destructive: self.destructive.interpolate(&other.destructive, t),
+            destructive_foreground: self.destructive_foreground.interpolate(&other.destructive_foreground, t),
+            border: self.border.interpolate(&other.border, t),
+            input: self.input.interpolate(&other.input, t),
+            ring: self.ring.interpolate(&other.ring, t),
+            sidebar: self.sidebar.interpolate(&other.sidebar, t),
+            sidebar_foreground: self.sidebar_foreground.interpolate(&other.sidebar_foreground, t),
+            sidebar_primary: self.sidebar_primary.interpolate(&other.sidebar_primary, t),
+            sidebar_primary_foreground: self.sidebar_primary_foreground.interpolate(&other.sidebar_primary_foreground, t),
+            sidebar_accent: self.sidebar_accent.interpolate(&other.sidebar_accent, t),
+            sidebar_accent_foreground: self.sidebar_accent_foreground.interpolate(&other.sidebar_accent_foreground, t),
+            sidebar_border: self.sidebar_border.interpolate(&other.sidebar_border, t),
+            sidebar_ring: self.sidebar_ring.interpolate(&other.sidebar_ring, t),
+            font_sans: snap.font_sans.clone(),
+            font_mono: snap.font_mono.clone(),
+            radius: self.radius.interpolate(&other.radius, t),
+            is_dark: snap.is_dark,
+            shadow_x: self.shadow_x.interpolate(&other.shadow_x, t),
+            shadow_y: self.shadow_y.interpolate(&other.shadow_y, t),
+            shadow_blur: self.shadow_blur.interpolate(&other.shadow_blur, t),
+            shadow_spread: self.shadow_spread.interpolate(&other.shadow_spread, t),
+            shadow_opacity: self.shadow_opacity.interpolate(&other.shadow_opacity, t),
+            chart_1: self.chart_1.interpolate(&other.chart_1, t),
+            chart_2: self.chart_2.interpolate(&other.chart_2, t),
+            chart_3: self.chart_3.interpolate(&other.chart_3, t),
+            chart_4: self.chart_4.interpolate(&other.chart_4, t),
+            chart_5: self.chart_5.interpolate(&other.chart_5, t),
+            tokens: self.tokens.interpolate(&other.tokens, t),
+            typography: snap.typography,
+            density: snap.density,
+            forced_colors: snap.forced_colors,
+        }
+    }
+}
+
 /// Animation state
 #[derive(Clone)]
 pub struct Animation<T: Interpolate> {
@@ -184,13 +252,19 @@ impl<T: Interpolate> Animation<T> {
     }
 
     pub fn update(&mut self) -> bool {
-        let elapsed = self.start_time.elapsed();
-        let t = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        let reduced = reduced_motion();
+        let t = if reduced {
+            1.0
+        } else {
+            (self.start_time.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
         let eased_t = self.curve.evaluate(t);
         self.value.update(eased_t);
 
         if t >= 1.0 {
-            match self.repeat {
+            // Repeats only decorate, so they finish after the first pass
+            let repeat = if reduced { AnimationRepeat::Once } else { self.repeat };
+            match repeat {
                 AnimationRepeat::Once => {
                     if let Some(callback) = &self.on_complete {
                         callback();
@@ -312,8 +386,11 @@ impl<T: Interpolate + Clone> KeyframeAnimation<T> {
     }
 
     pub fn update(&mut self) -> bool {
-        let elapsed = self.start_time.elapsed();
-        let t = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        let t = if reduced_motion() {
+            1.0
+        } else {
+            (self.start_time.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
 
         // Find surrounding keyframes
         let mut prev_kf = &self.keyframes[0];
@@ -2,5 +2,6 @@ mod animations;
 
 pub use animations::{
     Animation, AnimationController, EasingCurve,
-    AnimationId, AnimationRepeat, Interpolate
+    AnimationId, AnimationRepeat, Interpolate,
+    reduced_motion, set_reduced_motion,
 };
\ No newline at end of file
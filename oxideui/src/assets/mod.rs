@@ -0,0 +1,281 @@
+//! Bundled assets addressed by logical path
+//!
+//! Fonts, images, icons and theme files are registered once, either
+//! embedded in the binary with `embed_assets!` or read from an assets
+//! directory with `Assets::load_dir`, and then looked up by their path
+//! relative to that directory, e.g. `Image::asset("logo.png")`.
+//!
+//! In debug builds the runtime watches files loaded from disk and reloads
+//! them when they change; images update on the next frame and
+//! `Assets::on_reload` listeners can re-apply fonts or themes.
+//!
+//! ```rust,no_run
+//! use oxideui::assets::Assets;
+//!
+//! oxideui::embed_assets!("assets" => ["logo.png", "fonts/Inter-Regular.ttf"]);
+//! let assets = Assets::global();
+//! assets.load_dir("assets/themes")?;
+//! assets.register_font("Inter", "fonts/Inter-Regular.ttf")?;
+//! let theme = assets.theme("ocean.json")?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use parking_lot::RwLock;
+
+use crate::core::render_object::ImageData;
+use crate::render::text::FontManager;
+use crate::theming::ThemeConfig;
+use crate::widgets::element_widgets::icon::{IconData, IconPack};
+
+/// Register files from a directory under the crate root in the binary, by
+/// their path relative to it:
+/// `embed_assets!("assets" => ["logo.png", "icons/home.svg"])`
+#[macro_export]
+macro_rules! embed_assets {
+    ($dir:literal => [$($file:literal),* $(,)?]) => {
+        $(
+            $crate::assets::Assets::global().register(
+                $file,
+                &include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $dir, "/", $file))[..],
+            );
+        )*
+    };
+}
+
+struct Asset {
+    data: Arc<[u8]>,
+    /// The same bytes as an image, so the renderer decodes them once
+    image: ImageData,
+    /// File the asset was read from, for hot reload
+    origin: Option<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Asset {
+    fn new(data: Arc<[u8]>, origin: Option<(PathBuf, Option<SystemTime>)>) -> Self {
+        Self {
+            image: ImageData::from_encoded(data.clone()),
+            data,
+            origin,
+        }
+    }
+}
+
+type ReloadListener = Arc<dyn Fn(&[String]) + Send + Sync>;
+
+/// The app's asset registry
+#[derive(Default)]
+pub struct Assets {
+    assets: RwLock<HashMap<String, Asset>>,
+    listeners: RwLock<Vec<ReloadListener>>,
+}
+
+impl Assets {
+    pub fn global() -> &'static Assets {
+        static ASSETS: OnceLock<Assets> = OnceLock::new();
+        ASSETS.get_or_init(Assets::default)
+    }
+
+    /// Register `data` under `path`, replacing any earlier asset there
+    pub fn register(&self, path: impl Into<String>, data: impl Into<Arc<[u8]>>) {
+        self.assets.write().insert(normalize(&path.into()), Asset::new(data.into(), None));
+    }
+
+    /// Register every file under `dir` by its path relative to `dir`, using
+    /// `/` separators on every platform; returns how many were registered
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<usize> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+        collect_files(dir, &mut files).with_context(|| format!("Failed to read assets from {}", dir.display()))?;
+
+        let mut assets = self.assets.write();
+        for file in &files {
+            let data = std::fs::read(file).with_context(|| format!("Failed to read asset {}", file.display()))?;
+            let relative = file.strip_prefix(dir).unwrap_or(file).to_string_lossy();
+            assets.insert(normalize(&relative), Asset::new(data.into(), Some((file.clone(), modified(file)))));
+        }
+        Ok(files.len())
+    }
+
+    pub fn get(&self, path: &str) -> Option<Arc<[u8]>> {
+        self.assets.read().get(&normalize(path)).map(|asset| asset.data.clone())
+    }
+
+    pub fn get_str(&self, path: &str) -> Result<String> {
+        let data = self.get(path).ok_or_else(|| anyhow!("No asset at \"{}\"", path))?;
+        String::from_utf8(data.to_vec()).with_context(|| format!("Asset \"{}\" is not UTF-8", path))
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.assets.read().contains_key(&normalize(path))
+    }
+
+    /// Every registered path, sorted
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.assets.read().keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    pub(crate) fn image(&self, path: &str) -> Option<ImageData> {
+        self.assets.read().get(&normalize(path)).map(|asset| asset.image.clone())
+    }
+
+    /// Make the font at `path` available as `family`
+    pub fn register_font(&self, family: impl Into<String>, path: &str) -> Result<()> {
+        let data = self.get(path).ok_or_else(|| anyhow!("No font asset at \"{}\"", path))?;
+        FontManager::register_font(family, data.to_vec())
+    }
+
+    /// Parse the theme JSON at `path`
+    pub fn theme(&self, path: &str) -> Result<ThemeConfig> {
+        ThemeConfig::from_json(&self.get_str(path)?).with_context(|| format!("Failed to load theme asset \"{}\"", path))
+    }
+
+    /// An icon pack named `name` holding every SVG under `dir`, named by
+    /// file stem: `icons/home.svg` becomes `home`
+    pub fn icon_pack(&self, name: impl Into<String>, dir: &str) -> IconPack {
+        let prefix = format!("{}/", normalize(dir).trim_end_matches('/'));
+        let assets = self.assets.read();
+        let mut pack = IconPack::new(name);
+        for (path, asset) in assets.iter() {
+            let Some(file) = path.strip_prefix(&prefix).and_then(|file| file.strip_suffix(".svg")) else {
+                continue;
+            };
+            let icon = std::str::from_utf8(&asset.data).ok().and_then(IconData::from_svg);
+            match icon {
+                Some(icon) => pack = pack.with_icon(file, icon),
+                None => eprintln!("⚠️ Skipping icon asset \"{}\": no SVG paths", path),
+            }
+        }
+        pack
+    }
+
+    /// Call `listener` on the UI thread with the paths reloaded from disk
+    pub fn on_reload<F>(&self, listener: F)
+    where
+        F: Fn(&[String]) + Send + Sync + 'static,
+    {
+        self.listeners.write().push(Arc::new(listener));
+    }
+
+    /// Re-read file-backed assets whose modification time changed and
+    /// return their paths
+    pub fn reload_changed(&self) -> Vec<String> {
+        let mut assets = self.assets.write();
+        let mut reloaded = Vec::new();
+        for (path, asset) in assets.iter_mut() {
+            let Some((file, last_modified)) = &asset.origin else {
+                continue;
+            };
+            let modified = modified(file);
+            if modified == *last_modified {
+                continue;
+            }
+            // A file mid-write or briefly missing is retried on the next poll
+            let Ok(data) = std::fs::read(file) else {
+                continue;
+            };
+            *asset = Asset::new(data.into(), Some((file.clone(), modified)));
+            reloaded.push(path.clone());
+        }
+        reloaded.sort();
+        reloaded
+    }
+
+    /// Poll file-backed assets on a background thread, reloading changed
+    /// ones and running the reload listeners and a frame through `ui`
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub(crate) fn watch(&'static self, ui: crate::runtime::UiHandle) {
+        const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+        static WATCHING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+        if WATCHING.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+        let spawned = std::thread::Builder::new().name("oxideui-assets".into()).spawn(move || loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let reloaded = self.reload_changed();
+            if reloaded.is_empty() {
+                continue;
+            }
+            let notified = ui.run_on_ui(move || {
+                let listeners = self.listeners.read().clone();
+                for listener in listeners {
+                    listener(&reloaded);
+                }
+            });
+            if notified.is_err() {
+                break;
+            }
+        });
+        if let Err(e) = spawned {
+            eprintln!("⚠️ Asset hot reload disabled: {}", e);
+        }
+    }
+}
+
+/// `./icons\home.svg` → `icons/home.svg`
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").trim_start_matches('/').to_string()
+}
+
+fn modified(file: &Path) -> Option<SystemTime> {
+    std::fs::metadata(file).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assets_resolve_by_logical_path_and_reload() {
+        let dir = std::env::temp_dir().join(format!("oxideui-assets-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("icons")).unwrap();
+        std::fs::write(dir.join("greeting.txt"), "hello").unwrap();
+        std::fs::write(
+            dir.join("icons/dot.svg"),
+            r#"<svg viewBox="0 0 16 16" fill="none" stroke-width="1.5"><path d="M8 8h0"/></svg>"#,
+        )
+        .unwrap();
+
+        let assets = Assets::default();
+        assets.register("./embedded\\logo.png", &b"png"[..]);
+        assert_eq!(assets.load_dir(&dir).unwrap(), 2);
+        assert_eq!(assets.paths(), vec!["embedded/logo.png", "greeting.txt", "icons/dot.svg"]);
+        assert_eq!(assets.get_str("greeting.txt").unwrap(), "hello");
+        assert!(assets.get_str("missing.txt").is_err());
+
+        let dot = assets.icon_pack("test", "icons").get("dot").cloned().unwrap();
+        assert_eq!(dot.view_box, 16.0);
+        assert_eq!(dot.style, crate::widgets::element_widgets::icon::IconStyle::Stroke(1.5));
+
+        // Only assets whose file changed are reloaded
+        assert!(assets.reload_changed().is_empty());
+        std::fs::write(dir.join("greeting.txt"), "hi").unwrap();
+        // Don't rely on the filesystem's timestamp resolution
+        let file = std::fs::File::options().write(true).open(dir.join("greeting.txt")).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(assets.reload_changed(), vec!["greeting.txt"]);
+        assert_eq!(assets.get_str("greeting.txt").unwrap(), "hi");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,354 @@
+//! Color parsing, conversion and manipulation
+//!
+//! Conversions between sRGB, HSL and OKLCH, CSS-style parsing, and the
+//! lighten/darken/mix helpers theming uses to derive interaction shades.
+//! HSL hue and OKLCH hue are in degrees; saturation, lightness and amounts
+//! are fractions in `0.0..=1.0`.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::core::render_object::Color;
+
+impl Color {
+    /// Parse a CSS color: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`,
+    /// `rgb()`/`rgba()`, `hsl()`/`hsla()`, `oklch()`, `white`, `black` or
+    /// `transparent`. Functions accept comma or space separated arguments
+    /// with an optional `/ alpha`.
+    pub fn parse(source: &str) -> Result<Color> {
+        let source = source.trim().to_ascii_lowercase();
+
+        if let Some(hex) = source.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| anyhow!("Invalid hex color \"{}\"", source));
+        }
+
+        match source.as_str() {
+            "transparent" => return Ok(Color::TRANSPARENT),
+            "white" => return Ok(Color::WHITE),
+            "black" => return Ok(Color::BLACK),
+            _ => {}
+        }
+
+        let (name, args) = source
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or_else(|| anyhow!("Unrecognized color \"{}\"", source))?;
+        let args: Vec<&str> = args
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|a| !a.is_empty())
+            .collect();
+        if args.len() != 3 && args.len() != 4 {
+            bail!("Expected 3 or 4 components in \"{}\"", source);
+        }
+        let alpha = match args.get(3) {
+            Some(a) => (parse_fraction(a)? * 255.0).round().clamp(0.0, 255.0) as u8,
+            None => 255,
+        };
+
+        let color = match name {
+            "rgb" | "rgba" => {
+                let channel = |arg: &str| -> Result<u8> {
+                    let value = match arg.strip_suffix('%') {
+                        Some(pct) => parse_number(pct)? * 2.55,
+                        None => parse_number(arg)?,
+                    };
+                    Ok(value.round().clamp(0.0, 255.0) as u8)
+                };
+                Color::rgb(channel(args[0])?, channel(args[1])?, channel(args[2])?)
+            }
+            "hsl" | "hsla" => Color::from_hsl(
+                parse_angle(args[0])?,
+                parse_percent(args[1])?,
+                parse_percent(args[2])?,
+            ),
+            "oklch" => Color::from_oklch(
+                parse_fraction(args[0])?,
+                parse_number(args[1])?,
+                parse_angle(args[2])?,
+            ),
+            _ => bail!("Unknown color function \"{}\"", name),
+        };
+        Ok(color.with_alpha(alpha))
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` when not fully opaque
+    pub fn to_hex_string(&self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        let h = hue.rem_euclid(360.0) / 60.0;
+        let s = saturation.clamp(0.0, 1.0);
+        let l = lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        Color::rgb(to_u8(r + m), to_u8(g + m), to_u8(b + m))
+    }
+
+    /// `(hue, saturation, lightness)`
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b) = (self.r as f32 / 255.0, self.g as f32 / 255.0, self.b as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (h, s, l)
+    }
+
+    /// From OKLCH; out-of-gamut colors are clipped to sRGB
+    pub fn from_oklch(lightness: f32, chroma: f32, hue: f32) -> Color {
+        let (a, b) = (
+            chroma * hue.to_radians().cos(),
+            chroma * hue.to_radians().sin(),
+        );
+        let l_ = lightness + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = lightness - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = lightness - 0.089_484_18 * a - 1.291_485_5 * b;
+        let (l, m, s) = (l_.powi(3), m_.powi(3), s_.powi(3));
+
+        let r = 4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s;
+        let g = -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s;
+        let b = -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s;
+        Color::rgb(
+            to_u8(linear_to_srgb(r)),
+            to_u8(linear_to_srgb(g)),
+            to_u8(linear_to_srgb(b)),
+        )
+    }
+
+    /// `(lightness, chroma, hue)`
+    pub fn to_oklch(&self) -> (f32, f32, f32) {
+        let r = srgb_to_linear(self.r as f32 / 255.0);
+        let g = srgb_to_linear(self.g as f32 / 255.0);
+        let b = srgb_to_linear(self.b as f32 / 255.0);
+
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+        let lightness = 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s;
+        let a = 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s;
+        let b = 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s;
+        let hue = b.atan2(a).to_degrees().rem_euclid(360.0);
+        (lightness, (a * a + b * b).sqrt(), hue)
+    }
+
+    /// Raise HSL lightness by `amount`
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s, l + amount).with_alpha(self.a)
+    }
+
+    /// Lower HSL lightness by `amount`
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Raise HSL saturation by `amount`
+    pub fn saturate(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsl(h, s + amount, l).with_alpha(self.a)
+    }
+
+    /// Lower HSL saturation by `amount`
+    pub fn desaturate(&self, amount: f32) -> Color {
+        self.saturate(-amount)
+    }
+
+    /// Blend toward `other`; `t = 0.0` is `self`, `1.0` is `other`
+    pub fn mix(&self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color::rgba(
+            lerp(self.r, other.r),
+            lerp(self.g, other.g),
+            lerp(self.b, other.b),
+            lerp(self.a, other.a),
+        )
+    }
+
+    /// WCAG relative luminance, `0.0` (black) to `1.0` (white)
+    pub fn relative_luminance(&self) -> f32 {
+        0.2126 * srgb_to_linear(self.r as f32 / 255.0)
+            + 0.7152 * srgb_to_linear(self.g as f32 / 255.0)
+            + 0.0722 * srgb_to_linear(self.b as f32 / 255.0)
+    }
+
+    /// WCAG contrast ratio, from `1.0` to `21.0`; 4.5 is the AA minimum for
+    /// body text
+    pub fn contrast_ratio(&self, other: Color) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.relative_luminance() < 0.18
+    }
+
+    /// Black or white, whichever reads better on this color
+    pub fn contrasting_text(&self) -> Color {
+        if self.contrast_ratio(Color::WHITE) >= self.contrast_ratio(Color::BLACK) {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        }
+    }
+
+    /// Shade for hover: lighter on dark colors, darker on light ones
+    pub fn hover_shade(&self) -> Color {
+        self.shift_lightness(0.06)
+    }
+
+    /// Shade for pressed, one step beyond `hover_shade`
+    pub fn pressed_shade(&self) -> Color {
+        self.shift_lightness(0.12)
+    }
+
+    fn shift_lightness(&self, amount: f32) -> Color {
+        if self.is_dark() {
+            self.lighten(amount)
+        } else {
+            self.darken(amount)
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Color::parse(s)
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<_>>()?;
+    match digits.as_slice() {
+        [r, g, b] => Some(Color::rgb(r * 17, g * 17, b * 17)),
+        [r, g, b, a] => Some(Color::rgba(r * 17, g * 17, b * 17, a * 17)),
+        [r1, r2, g1, g2, b1, b2] => Some(Color::rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+        [r1, r2, g1, g2, b1, b2, a1, a2] => Some(Color::rgba(
+            r1 * 16 + r2,
+            g1 * 16 + g2,
+            b1 * 16 + b2,
+            a1 * 16 + a2,
+        )),
+        _ => None,
+    }
+}
+
+fn parse_number(value: &str) -> Result<f32> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Invalid number \"{}\" in color", value))
+}
+
+/// `40%` or `0.4`
+fn parse_fraction(value: &str) -> Result<f32> {
+    match value.strip_suffix('%') {
+        Some(pct) => Ok(parse_number(pct)? / 100.0),
+        None => parse_number(value),
+    }
+}
+
+/// `40%` or `40`, both meaning 0.4
+fn parse_percent(value: &str) -> Result<f32> {
+    Ok(parse_number(value.trim_end_matches('%'))? / 100.0)
+}
+
+/// Degrees, with or without a `deg` suffix
+fn parse_angle(value: &str) -> Result<f32> {
+    parse_number(value.trim_end_matches("deg"))
+}
+
+fn to_u8(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formats() {
+        let expected = Color::rgb(0x21, 0x96, 0xf3);
+        assert_eq!(Color::parse("#2196F3").unwrap(), expected);
+        assert_eq!(Color::parse("rgb(33, 150, 243)").unwrap(), expected);
+        assert_eq!(Color::parse("#fff8").unwrap(), Color::rgba(255, 255, 255, 136));
+        assert_eq!(Color::parse("hsl(0 100% 50%)").unwrap(), Color::RED);
+        assert_eq!(Color::parse("hsl(120deg, 100%, 50% / 50%)").unwrap(), Color::rgba(0, 255, 0, 128));
+        assert!(Color::parse("#12345").is_err());
+        assert!(Color::parse("cmyk(0, 0, 0, 0)").is_err());
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let color = Color::rgb(216, 121, 67);
+
+        let (h, s, l) = color.to_hsl();
+        assert_eq!(Color::from_hsl(h, s, l), color);
+
+        let (l, c, h) = color.to_oklch();
+        assert_eq!(Color::from_oklch(l, c, h), color);
+        assert_eq!(Color::parse("oklch(100% 0 0)").unwrap(), Color::WHITE);
+    }
+
+    #[test]
+    fn test_contrast_and_shades() {
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 0.01);
+        assert_eq!(Color::WHITE.contrast_ratio(Color::WHITE), 1.0);
+        assert_eq!(Color::from_hex(0x111827).contrasting_text(), Color::WHITE);
+
+        let light = Color::from_hex(0xF3F4F6);
+        assert!(light.hover_shade().relative_luminance() < light.relative_luminance());
+        let dark = Color::from_hex(0x121113);
+        assert!(dark.pressed_shade().relative_luminance() > dark.hover_shade().relative_luminance());
+    }
+}
@@ -1,11 +1,35 @@
 //! Build context - safe access to element tree during widget building
 
 use std::any::TypeId;
+use std::panic::Location;
 use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
 use crate::Color;
+use crate::core::render_object::{RenderObject, TextStyle};
 use crate::core::element::{ElementId, SharedElementTree};
-use crate::layout::constraints::Constraints;
-use crate::theming::ThemeConfig;
+use crate::core::state_driven::{BuildDependencies, ReactiveState, Trackable};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::i18n::{FluentArgs, LocaleFormat, Localizer};
+use crate::layout::constraints::{Constraints, EdgeInsets};
+use crate::layout::{debug_hash, Direction, LayoutCache, LayoutKey, Size};
+use crate::platform::window::WindowController;
+use crate::runtime::{TimerHandle, TimerScheduler, UiHandle};
+#[cfg(feature = "async")]
+use crate::runtime::{TaskHandle, TaskSpawner};
+#[cfg(feature = "skia")]
+use crate::render::RgbaImage;
+#[cfg(feature = "skia")]
+use anyhow::{Context, Result};
+use crate::theming::{
+    ButtonStyle, CardStyle, ColorRole, Density, DesignTokens, InputStyle, StyleProperties, StyleState, Stylesheet,
+    SystemColors, TextVariant, ThemeConfig, ThemeController, Typography, WidgetStyles,
+};
+
+/// Elements with fewer children than this build them serially; below it
+/// the cost of handing work to the pool outweighs building the children
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_CHILDREN: usize = 8;
 
 /// Theme data with Radix UI inspired colors
 #[derive(Clone, Debug)]
@@ -51,13 +75,20 @@ pub struct Theme {
     pub chart_3: Color,
     pub chart_4: Color,
     pub chart_5: Color,
+    /// Spacing, radii, font sizes and semantic colors derived from this palette
+    pub tokens: DesignTokens,
+    pub typography: Typography,
+    pub density: Density,
+    /// Colors come from the system palette; widgets draw borders instead of
+    /// relying on subtle fills
+    pub forced_colors: bool,
 }
 
 impl Theme {
     pub fn from_config(config: &ThemeConfig, is_dark: bool) -> Self {
         let colors = if is_dark { &config.dark } else { &config.light };
 
-        Self {
+        let mut theme = Self {
             background: colors.get_color("background"),
             foreground: colors.get_color("foreground"),
             card: colors.get_color("card"),
@@ -99,6 +130,47 @@ impl Theme {
             chart_3: colors.get_color("chart_3"),
             chart_4: colors.get_color("chart_4"),
             chart_5: colors.get_color("chart_5"),
+            tokens: DesignTokens::empty(),
+            typography: config.typography,
+            density: config.density,
+            forced_colors: false,
+        };
+        theme.tokens = DesignTokens::derive(&theme, &config.tokens);
+        theme
+    }
+
+    /// `config`'s fonts and metrics with every color taken from `colors`,
+    /// for forced-colors mode
+    pub fn forced_colors(config: &ThemeConfig, colors: &SystemColors) -> Self {
+        let mut theme = Theme::from_config(&config.with_system_colors(colors), colors.is_dark());
+        theme.forced_colors = true;
+        theme
+    }
+
+    /// Color for a semantic role, e.g. `ColorRole::SurfaceHover`
+    pub fn role(&self, role: ColorRole) -> Color {
+        self.tokens.color(role)
+    }
+
+    /// Size scale for controls, rows and paddings
+    pub fn density(&self) -> Density {
+        self.density
+    }
+
+    /// Text style for a typography role in the theme's foreground color
+    pub fn text_style(&self, variant: TextVariant) -> TextStyle {
+        let type_style = self.typography.get(variant);
+        TextStyle {
+            font_family: match variant {
+                TextVariant::Code => self.font_mono.clone(),
+                _ => self.font_sans.clone(),
+            },
+            font_size: type_style.size,
+            color: self.foreground,
+            bold: type_style.is_bold(),
+            italic: false,
+            line_height: Some(type_style.line_height),
+            ..Default::default()
         }
     }
 }
@@ -110,6 +182,42 @@ impl Default for Theme {
     }
 }
 
+/// Text properties inherited by descendant text widgets; unset fields fall
+/// through to the nearest ancestor that sets them, then to the theme
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InheritedTextStyle {
+    pub font_family: Option<String>,
+    pub font_size: Option<f32>,
+    pub color: Option<Color>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+}
+
+impl InheritedTextStyle {
+    /// `self` layered over an ancestor's style
+    pub fn inherit(&self, parent: &InheritedTextStyle) -> InheritedTextStyle {
+        InheritedTextStyle {
+            font_family: self.font_family.clone().or_else(|| parent.font_family.clone()),
+            font_size: self.font_size.or(parent.font_size),
+            color: self.color.or(parent.color),
+            bold: self.bold.or(parent.bold),
+            italic: self.italic.or(parent.italic),
+        }
+    }
+
+    /// Fill the unset fields from `base`
+    pub fn resolve(&self, base: &TextStyle) -> TextStyle {
+        TextStyle {
+            font_family: self.font_family.clone().unwrap_or_else(|| base.font_family.clone()),
+            font_size: self.font_size.unwrap_or(base.font_size),
+            color: self.color.unwrap_or(base.color),
+            bold: self.bold.unwrap_or(base.bold),
+            italic: self.italic.unwrap_or(base.italic),
+            ..base.clone()
+        }
+    }
+}
+
 /// Theme provider trait for widgets
 pub trait ThemeProvider {
     fn theme(&self) -> &Theme;
@@ -131,6 +239,59 @@ pub struct BuildContext {
 
     /// Current theme
     pub theme: Arc<Theme>,
+
+    /// State reads recorded during this element's build
+    dependencies: Arc<BuildDependencies>,
+
+    /// Native window handle; None when building headless
+    window: Option<WindowController>,
+
+    /// Runtime theme switching; None when building headless
+    theme_controller: Option<ThemeController>,
+
+    /// App stylesheet for class-based styling
+    stylesheet: Option<Arc<Stylesheet>>,
+
+    /// App-wide widget style defaults
+    widget_styles: Option<Arc<WidgetStyles>>,
+
+    /// Text style cascaded from `DefaultTextStyle` ancestors
+    text_style: Arc<InheritedTextStyle>,
+
+    /// Screen edges covered by notches and system bars, in logical pixels
+    safe_area: EdgeInsets,
+
+    /// Logical size of the window being built for; None when headless
+    window_size: Option<Size>,
+
+    /// The user asked the OS (or the app) to minimize motion
+    reduced_motion: bool,
+
+    /// Translations for `tr`; None when the app isn't localized
+    localizer: Option<Localizer>,
+
+    /// Reading direction; RTL mirrors horizontal layout
+    direction: Direction,
+
+    /// Previous layouts reused by `build_child`; None disables caching
+    layout_cache: Option<LayoutCache>,
+
+    /// Built only to measure a natural size, see `measuring_context`
+    measuring: bool,
+
+    /// Build the children of large elements on the rayon pool
+    #[cfg(feature = "parallel")]
+    parallel: bool,
+
+    /// Route back onto the UI thread; None when building headless
+    ui_handle: Option<UiHandle>,
+
+    /// Runs `after` and `every` callbacks; None when building headless
+    timers: Option<TimerScheduler>,
+
+    /// Runs `spawn`ed futures; None when building headless
+    #[cfg(feature = "async")]
+    tasks: Option<TaskSpawner>,
 }
 
 impl BuildContext {
@@ -141,12 +302,381 @@ impl BuildContext {
         constraints: Constraints,
         theme: Arc<Theme>,
     ) -> Self {
+        let tree_id = element_tree.read().id();
         Self {
             element_id,
             element_tree,
             constraints,
             theme,
+            dependencies: Arc::new(BuildDependencies::new(tree_id, element_id)),
+            window: None,
+            theme_controller: None,
+            stylesheet: None,
+            widget_styles: None,
+            text_style: Arc::new(InheritedTextStyle::default()),
+            safe_area: EdgeInsets::zero(),
+            window_size: None,
+            reduced_motion: false,
+            localizer: None,
+            direction: Direction::Ltr,
+            layout_cache: None,
+            measuring: false,
+            #[cfg(feature = "parallel")]
+            parallel: false,
+            ui_handle: None,
+            timers: None,
+            #[cfg(feature = "async")]
+            tasks: None,
+        }
+    }
+
+    /// Attach the window controller exposed through `window()`
+    pub fn with_window(mut self, window: WindowController) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    pub fn with_safe_area(mut self, insets: EdgeInsets) -> Self {
+        self.safe_area = insets;
+        self
+    }
+
+    pub fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.reduced_motion = reduced_motion;
+        self
+    }
+
+    pub fn with_window_size(mut self, size: Size) -> Self {
+        self.window_size = Some(size);
+        self
+    }
+
+    /// Whether to skip non-essential motion. `Animation`s already jump to
+    /// their end value when set; check this for hand-rolled effects.
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
+    /// Attach translations; the direction follows the active locale
+    pub fn with_localizer(mut self, localizer: Localizer) -> Self {
+        self.direction = Direction::for_locale(&localizer.locale());
+        self.localizer = Some(localizer);
+        self
+    }
+
+    /// Override the reading direction, e.g. to keep a code editor LTR
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn with_ui_handle(mut self, handle: UiHandle) -> Self {
+        self.ui_handle = Some(handle);
+        self
+    }
+
+    /// Handle for updating state or posting events from other threads
+    pub fn ui_handle(&self) -> Option<UiHandle> {
+        self.ui_handle.clone()
+    }
+
+    pub(crate) fn with_timers(mut self, timers: TimerScheduler) -> Self {
+        self.timers = Some(timers);
+        self
+    }
+
+    /// Call `callback` on the UI thread once `delay` from now, then rebuild.
+    ///
+    /// Identified by this element and the calling line, so calling it again
+    /// on every build keeps the original deadline and fires once; the timer
+    /// goes away when the element unmounts. None when building headless.
+    #[track_caller]
+    pub fn after<F>(&self, delay: Duration, callback: F) -> Option<TimerHandle>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let timers = self.timers.as_ref()?;
+        Some(timers.register(self.element_id, Location::caller(), delay, None, Arc::new(callback), Instant::now()))
+    }
+
+    /// Call `callback` on the UI thread every `interval`, rebuilding after
+    /// each call, until cancelled or the element unmounts. Like `after`,
+    /// repeated calls from the same line refer to the same timer.
+    #[track_caller]
+    pub fn every<F>(&self, interval: Duration, callback: F) -> Option<TimerHandle>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let timers = self.timers.as_ref()?;
+        Some(timers.register(
+            self.element_id,
+            Location::caller(),
+            interval,
+            Some(interval),
+            Arc::new(callback),
+            Instant::now(),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn with_task_spawner(mut self, tasks: TaskSpawner) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
+    /// Run `future` on the runtime's tokio pool. It is cancelled when this
+    /// element unmounts; use `ui_handle` to send its result back.
+    ///
+    /// Builds run every frame, so spawn in response to a state change rather
+    /// than unconditionally. None when building headless.
+    #[cfg(feature = "async")]
+    pub fn spawn<F>(&self, future: F) -> Option<TaskHandle>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        Some(self.tasks.as_ref()?.spawn(self.element_id, future))
+    }
+
+    pub fn with_layout_cache(mut self, cache: LayoutCache) -> Self {
+        self.layout_cache = Some(cache);
+        self
+    }
+
+    /// Build children of large elements in parallel; see
+    /// `WidgetBuilder::with_parallel`
+    #[cfg(feature = "parallel")]
+    pub(crate) fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Build `child` as the next child element of this one, under this
+    /// context's constraints. Never returns a `Container`: its children are
+    /// built as elements below the child's.
+    pub fn build_child(&self, child: &dyn Widget) -> WidgetNode {
+        self.mount_child(child).build_element(child)
+    }
+
+    /// Mount `child` as the next child element of this one and return the
+    /// context to build it in, for layouts that build a child more than
+    /// once, e.g. to measure it first
+    pub fn mount_child(&self, child: &dyn Widget) -> BuildContext {
+        let id = self.element_tree.write().mount(Some(self.element_id), child);
+        self.child_context(id, self.constraints)
+    }
+
+    /// Mount item `index` of a lazily built list, keyed by its index unless
+    /// it has a key of its own, so scrolling keeps each item's element
+    pub fn mount_item(&self, index: usize, child: &dyn Widget) -> BuildContext {
+        let key = child.key().unwrap_or(WidgetKey::U64(index as u64));
+        let id = self.element_tree.write().mount_keyed(Some(self.element_id), child, Some(key));
+        self.child_context(id, self.constraints)
+    }
+
+    /// Build `widget` as this context's element and mark the output with
+    /// it, so the layout pass records where the element was drawn.
+    ///
+    /// Widgets that opt in through `Widget::layout_hash` reuse their
+    /// previous output, children included, when neither their configuration
+    /// nor the constraints changed. So do widgets whose `should_rebuild`
+    /// says no against the widget they replace.
+    pub fn build_element(&self, widget: &dyn Widget) -> WidgetNode {
+        let _span = span!(TRACE, "widget", widget = widget.debug_name());
+        // Building again at another size mounts the same children
+        self.element_tree.write().restart_children(self.element_id);
+
+        // Only widgets that would skip rebuilding against themselves can
+        // ever reuse their output, so only they keep it
+        let skippable = !widget.should_rebuild(widget);
+        let inherited = skippable.then(|| {
+            debug_hash(&(Arc::as_ptr(&self.theme) as usize, self.direction, &*self.text_style, self.reduced_motion, self.measuring))
+        });
+        if let Some(inherited) = inherited {
+            let mut tree = self.element_tree.write();
+            let reusable = tree.get_mut(self.element_id).and_then(|element| {
+                let previous = element.previous_widget.take()?;
+                let (constraints, hash, output) = element.built.as_ref()?;
+                (*constraints == self.constraints && *hash == inherited && !widget.should_rebuild(previous.as_ref()))
+                    .then(|| output.clone())
+            });
+            if let Some(render_object) = reusable {
+                tree.retain_subtree(self.element_id);
+                return WidgetNode::Leaf(RenderObject::element(self.element_id, render_object));
+            }
+        }
+
+        let key = self.layout_cache.as_ref().and_then(|_| widget.layout_hash()).map(|config| {
+            let context = debug_hash(&(self.element_id, self.direction, &*self.text_style, self.reduced_motion, self.measuring));
+            LayoutKey::new(Widget::type_id(widget), config, context, self.constraints)
+        });
+        if let (Some(cache), Some(key)) = (&self.layout_cache, &key) {
+            if let Some(render_object) = cache.get(key) {
+                self.element_tree.write().retain_subtree(self.element_id);
+                return WidgetNode::Leaf(RenderObject::element(self.element_id, render_object));
+            }
         }
+
+        self.dependencies.mark_built();
+        let render_object = match widget.build(self) {
+            WidgetNode::Leaf(render_object) => render_object,
+            WidgetNode::Container { children } => RenderObject::group(self.build_children(&children)),
+            WidgetNode::None => return WidgetNode::None,
+        };
+        if let (Some(cache), Some(key)) = (&self.layout_cache, key) {
+            cache.insert(key, render_object.clone());
+        }
+        if let Some(inherited) = inherited {
+            if let Some(element) = self.element_tree.write().get_mut(self.element_id) {
+                element.built = Some((self.constraints, inherited, render_object.clone()));
+            }
+        }
+        WidgetNode::Leaf(RenderObject::element(self.element_id, render_object))
+    }
+
+    /// Build `children` as child elements of this one, in order. Large
+    /// lists build on the rayon pool with the `parallel` feature; the
+    /// result is the same as a serial build.
+    pub fn build_children(&self, children: &[Box<dyn Widget>]) -> Vec<RenderObject> {
+        // Mounted up front so slots follow declaration order
+        let mounted: Vec<BuildContext> = children.iter().map(|child| self.mount_child(child.as_ref())).collect();
+        let build = |(ctx, child): (&BuildContext, &Box<dyn Widget>)| match ctx.build_element(child.as_ref()) {
+            WidgetNode::Leaf(render_object) => render_object,
+            _ => RenderObject::None,
+        };
+
+        #[cfg(feature = "parallel")]
+        if self.parallel && children.len() >= PARALLEL_MIN_CHILDREN {
+            use rayon::prelude::*;
+
+            // Indexed collect keeps declaration order, so paint order and
+            // hit testing don't depend on which thread finished first
+            return mounted.par_iter().zip(children.par_iter()).map(build).collect();
+        }
+
+        let mut objects = RenderObject::child_vec(children.len());
+        objects.extend(mounted.iter().zip(children).map(build));
+        objects
+    }
+
+    /// Handle for switching the locale later, e.g. from an event callback
+    pub fn localizer(&self) -> Option<&Localizer> {
+        self.localizer.as_ref()
+    }
+
+    /// Translate `key` in the active locale, or return it unchanged when the
+    /// app has no localizer
+    pub fn tr(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        match &self.localizer {
+            Some(localizer) => localizer.tr(key, args),
+            None => key.to_string(),
+        }
+    }
+
+    /// Number and date conventions of the active locale (US English without a localizer)
+    pub fn locale_format(&self) -> LocaleFormat {
+        self.localizer.as_ref().map_or_else(LocaleFormat::default, |localizer| LocaleFormat::new(localizer.locale()))
+    }
+
+    /// Insets to keep content clear of notches, rounded corners and system
+    /// bars; zero on desktop
+    pub fn safe_area(&self) -> EdgeInsets {
+        self.safe_area
+    }
+
+    /// Logical size of the window, for keeping popups inside it
+    pub fn window_size(&self) -> Option<Size> {
+        self.window_size
+    }
+
+    /// Control the native window, if there is one
+    pub fn window(&self) -> Option<&WindowController> {
+        self.window.as_ref()
+    }
+
+    /// Attach the controller behind `set_theme` and `toggle_dark_mode`
+    pub fn with_theme_controller(mut self, controller: ThemeController) -> Self {
+        self.theme_controller = Some(controller);
+        self
+    }
+
+    /// Handle for changing the theme later, e.g. from an event callback
+    pub fn theme_controller(&self) -> Option<&ThemeController> {
+        self.theme_controller.as_ref()
+    }
+
+    /// Swap the app theme; every element rebuilds with it on the next frame
+    pub fn set_theme(&self, config: ThemeConfig) {
+        if let Some(controller) = &self.theme_controller {
+            controller.set_theme(config);
+        }
+    }
+
+    /// Switch between the light and dark palettes of the current theme
+    pub fn toggle_dark_mode(&self) {
+        if let Some(controller) = &self.theme_controller {
+            controller.toggle_dark_mode();
+        }
+    }
+
+    pub fn with_stylesheet(mut self, stylesheet: Arc<Stylesheet>) -> Self {
+        self.stylesheet = Some(stylesheet);
+        self
+    }
+
+    /// Style for a widget of type `widget` with `classes` in `state`;
+    /// empty when no stylesheet is installed
+    pub fn resolve_style(&self, widget: &str, classes: &[String], state: StyleState) -> StyleProperties {
+        self.stylesheet
+            .as_ref()
+            .map(|sheet| sheet.resolve(widget, classes, state))
+            .unwrap_or_default()
+    }
+
+    pub fn with_widget_styles(mut self, styles: Arc<WidgetStyles>) -> Self {
+        self.widget_styles = Some(styles);
+        self
+    }
+
+    /// Registered default button style, or one derived from the theme
+    pub fn button_style(&self) -> ButtonStyle {
+        self.widget_styles
+            .as_ref()
+            .and_then(|styles| styles.button.clone())
+            .unwrap_or_else(|| ButtonStyle::from_theme(&self.theme))
+    }
+
+    /// Registered default input style, or one derived from the theme
+    pub fn input_style(&self) -> InputStyle {
+        self.widget_styles
+            .as_ref()
+            .and_then(|styles| styles.input.clone())
+            .unwrap_or_else(|| InputStyle::from_theme(&self.theme))
+    }
+
+    /// Registered default card style, if any; cards otherwise derive
+    /// theirs from the theme and their variant
+    pub fn card_style(&self) -> Option<CardStyle> {
+        self.widget_styles.as_ref().and_then(|styles| styles.card.clone())
+    }
+
+    /// Layer `style` over the inherited text style for this subtree
+    pub fn with_text_style(mut self, style: &InheritedTextStyle) -> Self {
+        self.text_style = Arc::new(style.inherit(&self.text_style));
+        self
+    }
+
+    /// The inherited text style as set by ancestors
+    pub fn inherited_text_style(&self) -> &InheritedTextStyle {
+        &self.text_style
+    }
+
+    /// Effective text style here: inherited values over the theme's body text
+    pub fn text_style(&self) -> TextStyle {
+        self.text_style.resolve(&self.theme.text_style(TextVariant::Body))
     }
 
     /// Get the parent element ID
@@ -166,18 +696,90 @@ impl BuildContext {
             .find_ancestor(self.element_id, TypeId::of::<W>())
     }
 
+    /// Screenshot of an element as of the last presented frame.
+    ///
+    /// Fails if the element has not been laid out or there is no window.
+    #[cfg(feature = "skia")]
+    pub fn capture_widget(&self, element_id: ElementId) -> Result<RgbaImage> {
+        let bounds = self
+            .element_tree
+            .read()
+            .get(element_id)
+            .and_then(|element| element.bounds)
+            .context("Element has no layout bounds to capture")?;
+        self.window
+            .as_ref()
+            .context("Capturing a widget needs a window")?
+            .capture_region(bounds)
+    }
+
     /// Mark the current element as dirty (needs rebuilding)
     pub fn mark_dirty(&self) {
         self.element_tree.write().mark_dirty(self.element_id);
     }
 
+    /// Read a reactive state and subscribe this element to it
+    ///
+    /// The subscription is dropped automatically if a later build of this
+    /// element no longer reads the state.
+    pub fn watch<T: Clone + Send + Sync + 'static>(&self, state: &ReactiveState<T>) -> T {
+        self.dependencies.record(state);
+        state.get()
+    }
+
+    /// Record a dependency on any trackable state without reading it
+    pub fn depend_on(&self, state: &dyn Trackable) {
+        self.dependencies.record(state);
+    }
+
+    /// Context to build this element in only to learn its natural size.
+    /// Layouts that measure their children first can skip their own
+    /// measuring pass inside one, as only the size is kept.
+    pub(crate) fn measuring_context(&self, constraints: Constraints) -> BuildContext {
+        BuildContext {
+            measuring: true,
+            ..self.child_context(self.element_id, constraints)
+        }
+    }
+
+    /// Whether this build is only measuring, see `measuring_context`
+    pub(crate) fn is_measuring(&self) -> bool {
+        self.measuring
+    }
+
     /// Create a child context
     pub fn child_context(&self, child_id: ElementId, constraints: Constraints) -> BuildContext {
+        // A child built under the same element shares its dependency set
+        let dependencies = if child_id == self.dependencies.element() {
+            self.dependencies.clone()
+        } else {
+            Arc::new(BuildDependencies::new(self.dependencies.tree(), child_id))
+        };
+
         BuildContext {
             element_id: child_id,
             element_tree: self.element_tree.clone(),
             constraints,
             theme: self.theme.clone(),
+            dependencies,
+            window: self.window.clone(),
+            theme_controller: self.theme_controller.clone(),
+            stylesheet: self.stylesheet.clone(),
+            widget_styles: self.widget_styles.clone(),
+            text_style: self.text_style.clone(),
+            safe_area: self.safe_area,
+            window_size: self.window_size,
+            reduced_motion: self.reduced_motion,
+            localizer: self.localizer.clone(),
+            direction: self.direction,
+            layout_cache: self.layout_cache.clone(),
+            measuring: self.measuring,
+            #[cfg(feature = "parallel")]
+            parallel: self.parallel,
+            ui_handle: self.ui_handle.clone(),
+            timers: self.timers.clone(),
+            #[cfg(feature = "async")]
+            tasks: self.tasks.clone(),
         }
     }
 }
@@ -195,6 +797,102 @@ impl Clone for BuildContext {
             element_tree: self.element_tree.clone(),
             constraints: self.constraints,
             theme: self.theme.clone(),
+            dependencies: self.dependencies.clone(),
+            window: self.window.clone(),
+            theme_controller: self.theme_controller.clone(),
+            stylesheet: self.stylesheet.clone(),
+            widget_styles: self.widget_styles.clone(),
+            text_style: self.text_style.clone(),
+            safe_area: self.safe_area,
+            window_size: self.window_size,
+            reduced_motion: self.reduced_motion,
+            localizer: self.localizer.clone(),
+            direction: self.direction,
+            layout_cache: self.layout_cache.clone(),
+            measuring: self.measuring,
+            #[cfg(feature = "parallel")]
+            parallel: self.parallel,
+            ui_handle: self.ui_handle.clone(),
+            timers: self.timers.clone(),
+            #[cfg(feature = "async")]
+            tasks: self.tasks.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_style_cascades_through_ancestors() {
+        let tree = crate::core::element::new_shared_element_tree();
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            tree,
+            Constraints::new(0.0, 100.0, 0.0, 100.0),
+            Arc::new(Theme::default()),
+        );
+
+        let outer = ctx.with_text_style(&InheritedTextStyle {
+            font_size: Some(20.0),
+            color: Some(Color::RED),
+            ..Default::default()
+        });
+        let inner = outer.clone().with_text_style(&InheritedTextStyle {
+            color: Some(Color::BLUE),
+            ..Default::default()
+        });
+
+        let style = inner.text_style();
+        assert_eq!(style.font_size, 20.0);
+        assert_eq!(style.color, Color::BLUE);
+        assert_eq!(style.font_family, inner.theme.font_sans);
+        assert_eq!(outer.text_style().color, Color::RED);
+    }
+
+    /// Records whether it was built on a pool thread, and draws its index
+    #[cfg(feature = "parallel")]
+    #[derive(Clone)]
+    struct Probe(usize, Arc<parking_lot::Mutex<Vec<bool>>>);
+
+    #[cfg(feature = "parallel")]
+    impl Widget for Probe {
+        fn build(&self, _ctx: &BuildContext) -> WidgetNode {
+            self.1.lock().push(rayon::current_thread_index().is_some());
+            let rect = crate::core::render_object::Rect::new(0.0, 0.0, self.0 as f32, 1.0);
+            WidgetNode::Leaf(RenderObject::rect(rect, Color::RED))
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
         }
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_large_child_lists_build_on_the_pool() {
+        let build = |count: usize| {
+            let pooled = Arc::new(parking_lot::Mutex::new(Vec::new()));
+            let children: Vec<Box<dyn Widget>> =
+                (0..count).map(|i| Box::new(Probe(i, pooled.clone())) as Box<dyn Widget>).collect();
+            let tree = crate::core::element::new_shared_element_tree();
+            let root = tree.write().create_element(&Probe(0, pooled.clone()), None, 0);
+            let ctx = BuildContext::new(root, tree, Constraints::loose(crate::layout::Size::new(100.0, 100.0)), Arc::new(Theme::default()))
+                .with_parallel(true);
+            let widths: Vec<f32> =
+                ctx.build_children(&children).iter().map(|obj| obj.layout_size().width).collect();
+            // Declaration order whichever thread built each child
+            assert_eq!(widths, (0..count).map(|i| i as f32).collect::<Vec<_>>());
+            let on_pool = pooled.lock().clone();
+            on_pool
+        };
+
+        assert!(build(PARALLEL_MIN_CHILDREN - 1).iter().all(|pooled| !pooled));
+        assert!(build(PARALLEL_MIN_CHILDREN).iter().all(|pooled| *pooled));
+    }
+}
@@ -6,9 +6,12 @@
 use parking_lot::RwLock;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use crate::core::render_object::RenderObject;
+use crate::core::render_object::{sticky_offset, Matrix, Point, Rect, RenderObject};
+use crate::core::semantics::SemanticsData;
+use crate::core::state_driven::release_tree;
 use crate::core::widget::{Widget, WidgetKey};
 use crate::layout::constraints::{Constraints, Size};
 
@@ -26,6 +29,18 @@ impl ElementId {
     }
 }
 
+/// Identifies an element tree. Element ids restart in every tree, so state
+/// kept outside a tree is keyed by both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TreeId(u64);
+
+impl TreeId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        TreeId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 /// An element in the element tree - the runtime representation of a widget
 pub struct Element {
     /// Unique identifier for this element
@@ -60,10 +75,51 @@ pub struct Element {
 
     /// Computed size after layout
     pub size: Size,
+
+    /// Maps this element's local coordinates into window coordinates
+    pub transform: Matrix,
+
+    /// Global bounds computed during layout (None until laid out)
+    pub bounds: Option<Rect>,
+
+    /// The widget this element was last built from
+    pub widget: Option<Box<dyn Widget>>,
+
+    /// Accessibility information declared by the widget
+    pub semantics: Option<SemanticsData>,
+
+    /// Placed inside an overlay of an ancestor, so it can be hit outside
+    /// its parent's bounds
+    pub(crate) in_overlay: bool,
+
+    /// Build pass that last mounted this element
+    pub(crate) generation: u64,
+
+    /// The widget this element was built from before `widget`, for
+    /// `Widget::should_rebuild`
+    pub(crate) previous_widget: Option<Box<dyn Widget>>,
+
+    /// Output of the last build, with the constraints and a hash of the
+    /// inherited context it was built under; kept only for widgets that can
+    /// skip rebuilds
+    pub(crate) built: Option<(Constraints, u64, RenderObject)>,
+
+    /// Position among its parent's unkeyed children, which are matched up
+    /// between builds by type and this position
+    pub(crate) unkeyed_index: Option<usize>,
+}
+
+/// Children of one parent mounted so far in the current build pass
+#[derive(Default)]
+struct MountCursor {
+    mounted: Vec<ElementId>,
+    unkeyed: usize,
 }
 
 /// The element tree - manages all elements and their relationships
 pub struct ElementTree {
+    id: TreeId,
+
     /// All elements indexed by ID
     elements: HashMap<ElementId, Element>,
 
@@ -72,18 +128,36 @@ pub struct ElementTree {
 
     /// Next available element ID
     next_id: u64,
+
+    /// Elements removed since the runtime last checked, so it can cancel
+    /// the work they started
+    unmounted: Vec<ElementId>,
+
+    /// Current build pass; `end_build` removes elements it didn't mount
+    generation: u64,
+
+    /// Children mounted in this pass, per parent (None for the root)
+    cursors: HashMap<Option<ElementId>, MountCursor>,
 }
 
 impl ElementTree {
     /// Create a new empty element tree
     pub fn new() -> Self {
         Self {
+            id: TreeId::next(),
             elements: HashMap::new(),
             root: None,
             next_id: 1,
+            unmounted: Vec::new(),
+            generation: 0,
+            cursors: HashMap::new(),
         }
     }
 
+    pub fn id(&self) -> TreeId {
+        self.id
+    }
+
     /// Create a new element
     pub fn create_element(
         &mut self,
@@ -91,6 +165,17 @@ impl ElementTree {
         parent: Option<ElementId>,
         slot_index: usize,
     ) -> ElementId {
+        let id = self.insert_element(widget, parent, slot_index);
+
+        // Set as root if this is the first element
+        if self.root.is_none() {
+            self.root = Some(id);
+        }
+
+        id
+    }
+
+    fn insert_element(&mut self, widget: &dyn Widget, parent: Option<ElementId>, slot_index: usize) -> ElementId {
         let id = ElementId(self.next_id);
         self.next_id += 1;
 
@@ -106,6 +191,15 @@ impl ElementTree {
             render_object: None,
             constraints: Constraints::default(),
             size: Size::default(),
+            transform: Matrix::identity(),
+            bounds: None,
+            widget: Some(widget.clone_box()),
+            semantics: widget.semantics(),
+            in_overlay: false,
+            generation: self.generation,
+            unkeyed_index: None,
+            previous_widget: None,
+            built: None,
         };
 
         self.elements.insert(id, element);
@@ -117,14 +211,113 @@ impl ElementTree {
             }
         }
 
-        // Set as root if this is the first element
-        if self.root.is_none() {
-            self.root = Some(id);
-        }
+        id
+    }
+
+    /// Start a build pass. Elements survive `end_build` only if the pass
+    /// mounts them again or keeps them with `retain_subtree`.
+    pub fn begin_build(&mut self) {
+        self.generation += 1;
+        self.cursors.clear();
+        self.clear_dirty();
+    }
 
+    /// Mount `widget` as the next child of `parent`, or as the root when
+    /// `parent` is None. The element from the previous build is reused when
+    /// its key matches, or for unkeyed widgets its type and position among
+    /// the unkeyed siblings, so ids and state carry over.
+    pub fn mount(&mut self, parent: Option<ElementId>, widget: &dyn Widget) -> ElementId {
+        self.mount_keyed(parent, widget, widget.key())
+    }
+
+    /// `mount` matching on `key` in place of the widget's own key, for
+    /// lazily built items that are only sometimes built
+    pub fn mount_keyed(&mut self, parent: Option<ElementId>, widget: &dyn Widget, key: Option<WidgetKey>) -> ElementId {
+        let widget_type = widget.type_id();
+        let cursor = self.cursors.entry(parent).or_default();
+        let slot = cursor.mounted.len();
+        let unkeyed_index = key.is_none().then(|| {
+            cursor.unkeyed += 1;
+            cursor.unkeyed - 1
+        });
+
+        let candidates = match parent {
+            Some(parent) => self.get_children(parent),
+            None => self.root.into_iter().collect(),
+        };
+        let claimed = &self.cursors[&parent].mounted;
+        let existing = candidates.into_iter().find(|id| {
+            !claimed.contains(id)
+                && self.elements.get(id).is_some_and(|element| {
+                    element.widget_type == widget_type && element.key == key && element.unkeyed_index == unkeyed_index
+                })
+        });
+
+        let id = match existing {
+            Some(id) => {
+                let element = self.elements.get_mut(&id).expect("candidates are in the tree");
+                element.previous_widget = element.widget.replace(widget.clone_box());
+                element.semantics = widget.semantics();
+                element.slot_index = slot;
+                id
+            }
+            None => {
+                let id = self.insert_element(widget, parent, slot);
+                let element = self.elements.get_mut(&id).expect("just inserted");
+                element.dirty = false;
+                element.key = key;
+                if parent.is_none() {
+                    self.root = Some(id);
+                }
+                id
+            }
+        };
+        let generation = self.generation;
+        let element = self.elements.get_mut(&id).expect("mounted");
+        element.generation = generation;
+        element.unkeyed_index = unkeyed_index;
+        self.cursors.entry(parent).or_default().mounted.push(id);
         id
     }
 
+    /// Mount `parent`'s children from the start again, for a second build
+    /// of the same element, e.g. at another size
+    pub fn restart_children(&mut self, parent: ElementId) {
+        self.cursors.remove(&Some(parent));
+    }
+
+    /// Keep `id`'s descendants from the previous pass, for an element whose
+    /// earlier output is reused without building its children
+    pub fn retain_subtree(&mut self, id: ElementId) {
+        let generation = self.generation;
+        for child in self.get_children(id) {
+            if let Some(element) = self.elements.get_mut(&child) {
+                element.generation = generation;
+            }
+            self.retain_subtree(child);
+        }
+    }
+
+    /// Finish a build pass: remove the elements it didn't mount, and order
+    /// each rebuilt element's children as they were mounted
+    pub fn end_build(&mut self) {
+        let stale: Vec<ElementId> = self
+            .elements
+            .values()
+            .filter(|element| element.generation != self.generation)
+            .map(|element| element.id)
+            .collect();
+        for id in stale {
+            self.remove_element(id);
+        }
+
+        for (parent, cursor) in std::mem::take(&mut self.cursors) {
+            if let Some(element) = parent.and_then(|parent| self.elements.get_mut(&parent)) {
+                element.children = cursor.mounted;
+            }
+        }
+    }
+
     /// Get an element by ID
     pub fn get(&self, id: ElementId) -> Option<&Element> {
         self.elements.get(&id)
@@ -157,6 +350,13 @@ impl ElementTree {
         }
     }
 
+    /// Mark every element dirty, e.g. after the theme changes
+    pub fn mark_all_dirty(&mut self) {
+        for element in self.elements.values_mut() {
+            element.dirty = true;
+        }
+    }
+
     /// Remove an element and all its children
     pub fn remove_element(&mut self, id: ElementId) {
         // FIX: Collect children IDs FIRST before removing anything
@@ -173,6 +373,7 @@ impl ElementTree {
 
         // Now remove the element itself
         if let Some(element) = self.elements.remove(&id) {
+            self.unmounted.push(id);
             // Remove from parent's child list
             if let Some(parent_id) = element.parent {
                 if let Some(parent) = self.elements.get_mut(&parent_id) {
@@ -187,6 +388,127 @@ impl ElementTree {
         }
     }
 
+    /// Record the layout result for an element.
+    ///
+    /// `local_transform` positions the element inside its parent (usually a
+    /// translation by the child offset). The global transform and bounds are
+    /// derived from the parent's, so parents must be laid out first.
+    pub fn set_layout(&mut self, id: ElementId, local_transform: Matrix, size: Size) {
+        let parent_transform = self
+            .get_parent(id)
+            .and_then(|parent| self.elements.get(&parent))
+            .map(|parent| parent.transform)
+            .unwrap_or_default();
+
+        self.place(id, parent_transform.concat(&local_transform), size);
+    }
+
+    /// Record an element's window-space transform and size
+    fn place(&mut self, id: ElementId, transform: Matrix, size: Size) {
+        if let Some(element) = self.elements.get_mut(&id) {
+            element.size = size;
+            element.transform = transform;
+            element.bounds = Some(transform.map_rect(Rect::new(0.0, 0.0, size.width, size.height)));
+        }
+    }
+
+    /// Lay out every element from the frame `root`, which was built from
+    /// this tree: each `RenderObject::Element` marks where an element's
+    /// output was placed in the window. Elements missing from the frame
+    /// lose their bounds, so they can't be hit.
+    pub fn record_layout(&mut self, root: &RenderObject) {
+        for element in self.elements.values_mut() {
+            element.bounds = None;
+            element.render_object = None;
+            element.in_overlay = false;
+        }
+        self.record_layout_recursive(root, Matrix::identity(), None, false, None);
+    }
+
+    /// Walk `obj`, drawn with the window-space `transform` inside element
+    /// `owner`, returning what it paints in its own coordinates like
+    /// `RenderObject::bounds`. `viewport_top` is the window-space top of
+    /// the innermost scrolling viewport, where sticky headers pin.
+    fn record_layout_recursive(
+        &mut self,
+        obj: &RenderObject,
+        transform: Matrix,
+        owner: Option<ElementId>,
+        in_overlay: bool,
+        viewport_top: Option<f32>,
+    ) -> Option<Rect> {
+        match obj {
+            RenderObject::Element { id, child } => {
+                // Overlays below are recorded relative to this transform
+                if let Some(element) = self.elements.get_mut(id) {
+                    element.transform = transform;
+                    element.in_overlay = in_overlay;
+                }
+                let bounds = self.record_layout_recursive(child, transform, Some(*id), false, viewport_top);
+                let size = bounds.map_or(Size::zero(), |bounds| {
+                    Size::new((bounds.x + bounds.width).max(0.0), (bounds.y + bounds.height).max(0.0))
+                });
+                self.place(*id, transform, size);
+                bounds
+            }
+            RenderObject::Transform { matrix, child } => self
+                .record_layout_recursive(child, transform.concat(matrix), owner, in_overlay, viewport_top)
+                .map(|bounds| matrix.map_rect(bounds)),
+            RenderObject::Group { children } => children
+                .iter()
+                .filter_map(|child| self.record_layout_recursive(child, transform, owner, in_overlay, viewport_top))
+                .reduce(|a, b| a.union(&b)),
+            RenderObject::Clip { rect, child } => self
+                .record_layout_recursive(child, transform, owner, in_overlay, viewport_top)?
+                .intersect(rect),
+            RenderObject::Viewport { rect, child } => {
+                let top = transform.map_point(Point::new(rect.x, rect.y)).y;
+                self.record_layout_recursive(child, transform, owner, in_overlay, Some(top))?.intersect(rect)
+            }
+            RenderObject::ClipShape { shape, child } => self
+                .record_layout_recursive(child, transform, owner, in_overlay, viewport_top)?
+                .intersect(&shape.bounds()),
+            RenderObject::Backdrop { rect, child, .. } => {
+                let bounds = self.record_layout_recursive(child, transform, owner, in_overlay, viewport_top);
+                Some(bounds.map_or(*rect, |bounds| bounds.union(rect)))
+            }
+            // Pinned headers are hit where they're painted
+            RenderObject::Sticky { section, child } => {
+                let offset = match (viewport_top, transform.invert()) {
+                    (Some(top), Some(inverse)) => sticky_offset(*section, child, inverse.map_point(Point::new(0.0, top)).y),
+                    _ => 0.0,
+                };
+                let matrix = Matrix::translate(0.0, offset);
+                self.record_layout_recursive(child, transform.concat(&matrix), owner, in_overlay, viewport_top)
+                    .map(|bounds| matrix.map_rect(bounds))
+            }
+            RenderObject::Opacity { child, .. } | RenderObject::Effect { child, .. } => {
+                self.record_layout_recursive(child, transform, owner, in_overlay, viewport_top)
+            }
+            // Drawn above everything else, so it takes no room in its
+            // element but is hit-tested before the rest of the tree
+            RenderObject::Overlay { child } => {
+                self.record_layout_recursive(child, transform, owner, true, None);
+                if let Some(element) = owner.and_then(|owner| self.elements.get_mut(&owner)) {
+                    let local = element.transform.invert().unwrap_or_default().concat(&transform);
+                    let overlay = RenderObject::transform(local, obj.clone());
+                    element.render_object = Some(match element.render_object.take() {
+                        Some(previous) => RenderObject::group(vec![previous, overlay]),
+                        None => overlay,
+                    });
+                }
+                None
+            }
+            _ => obj.bounds(),
+        }
+    }
+
+    /// Map a window-space point into an element's local coordinates
+    pub fn to_local(&self, id: ElementId, point: Point) -> Option<Point> {
+        let element = self.elements.get(&id)?;
+        element.transform.invert().map(|inverse| inverse.map_point(point))
+    }
+
     /// Get parent of an element
     pub fn get_parent(&self, id: ElementId) -> Option<ElementId> {
         self.elements.get(&id).and_then(|e| e.parent)
@@ -218,6 +540,33 @@ impl ElementTree {
         None
     }
 
+    /// Elements whose widgets are focusable, in tree order: the order Tab
+    /// visits them
+    pub fn focusable_elements(&self) -> Vec<ElementId> {
+        let mut order = Vec::new();
+        let mut stack: Vec<ElementId> = self.root().into_iter().collect();
+        while let Some(id) = stack.pop() {
+            let Some(element) = self.elements.get(&id) else {
+                continue;
+            };
+            if element.widget.as_ref().is_some_and(|widget| widget.is_focusable()) {
+                order.push(id);
+            }
+            stack.extend(element.children.iter().rev());
+        }
+        order
+    }
+
+    /// All elements, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &Element> {
+        self.elements.values()
+    }
+
+    /// Elements removed since the last call
+    pub(crate) fn take_unmounted(&mut self) -> Vec<ElementId> {
+        std::mem::take(&mut self.unmounted)
+    }
+
     /// Collect all dirty elements
     pub fn collect_dirty(&self) -> Vec<ElementId> {
         self.elements
@@ -251,6 +600,13 @@ impl Default for ElementTree {
     }
 }
 
+impl Drop for ElementTree {
+    fn drop(&mut self) {
+        // Offscreen and capture trees are dropped without unmounting
+        release_tree(self.id);
+    }
+}
+
 /// Thread-safe wrapper around ElementTree
 pub type SharedElementTree = Arc<RwLock<ElementTree>>;
 
@@ -258,3 +614,83 @@ pub type SharedElementTree = Arc<RwLock<ElementTree>>;
 pub fn new_shared_element_tree() -> SharedElementTree {
     Arc::new(RwLock::new(ElementTree::new()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::Color;
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_record_layout_places_elements_where_they_were_drawn() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Text::new("root"), None, 0);
+        let child = tree.create_element(&Text::new("child"), Some(root), 0);
+        let menu = tree.create_element(&Text::new("menu"), Some(child), 0);
+        let gone = tree.create_element(&Text::new("gone"), Some(root), 1);
+        tree.set_layout(gone, Matrix::identity(), Size::new(10.0, 10.0));
+
+        let frame = RenderObject::element(
+            root,
+            RenderObject::group(vec![
+                RenderObject::rect(Rect::new(0.0, 0.0, 400.0, 300.0), Color::WHITE),
+                RenderObject::transform(
+                    Matrix::translate(20.0, 30.0),
+                    RenderObject::element(
+                        child,
+                        RenderObject::group(vec![
+                            RenderObject::rect(Rect::new(0.0, 0.0, 100.0, 40.0), Color::BLACK),
+                            RenderObject::overlay(RenderObject::transform(
+                                Matrix::translate(0.0, 40.0),
+                                RenderObject::element(menu, RenderObject::rect(Rect::new(0.0, 0.0, 100.0, 200.0), Color::BLACK)),
+                            )),
+                        ]),
+                    ),
+                ),
+            ]),
+        );
+        tree.record_layout(&frame);
+
+        assert_eq!(tree.get(root).unwrap().bounds, Some(Rect::new(0.0, 0.0, 400.0, 300.0)));
+        // The overlay takes no room in its element
+        assert_eq!(tree.get(child).unwrap().bounds, Some(Rect::new(20.0, 30.0, 100.0, 40.0)));
+        assert_eq!(tree.to_local(child, Point::new(25.0, 35.0)), Some(Point::new(5.0, 5.0)));
+        assert_eq!(tree.get(menu).unwrap().bounds, Some(Rect::new(20.0, 70.0, 100.0, 200.0)));
+        assert!(tree.get(menu).unwrap().in_overlay);
+        assert!(tree.get(child).unwrap().render_object.is_some());
+        assert_eq!(tree.get(gone).unwrap().bounds, None);
+    }
+
+    #[test]
+    fn test_pinned_headers_are_placed_where_they_are_painted() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Text::new("root"), None, 0);
+        let section = tree.create_element(&Text::new("section"), Some(root), 0);
+        let header = tree.create_element(&Text::new("header"), Some(section), 0);
+
+        // A 400px section scrolled 150px up a 100px viewport
+        let frame = RenderObject::element(
+            root,
+            RenderObject::viewport(
+                Rect::new(0.0, 0.0, 200.0, 100.0),
+                RenderObject::transform(
+                    Matrix::translate(0.0, -150.0),
+                    RenderObject::element(
+                        section,
+                        RenderObject::group(vec![
+                            RenderObject::rect(Rect::new(0.0, 0.0, 200.0, 400.0), Color::WHITE),
+                            RenderObject::sticky(
+                                Rect::new(0.0, 0.0, 200.0, 400.0),
+                                RenderObject::element(header, RenderObject::rect(Rect::new(0.0, 0.0, 200.0, 20.0), Color::BLACK)),
+                            ),
+                        ]),
+                    ),
+                ),
+            ),
+        );
+        tree.record_layout(&frame);
+
+        assert_eq!(tree.get(header).unwrap().bounds, Some(Rect::new(0.0, 0.0, 200.0, 20.0)));
+        assert_eq!(tree.to_local(header, Point::new(10.0, 10.0)), Some(Point::new(10.0, 10.0)));
+    }
+}
@@ -1,10 +1,12 @@
 use crate::core::element::ElementId;
-use crate::core::render_object::Point;
+use crate::core::render_object::{Matrix, Point};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use winit::event::MouseButton as WinitMouseButton;
-use winit::keyboard::{KeyCode, ModifiersState};
+use winit::keyboard::{Key, KeyCode, ModifiersState};
 
 /// Mouse button types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MouseButton {
     Left,
     Right,
@@ -28,7 +30,7 @@ impl From<WinitMouseButton> for MouseButton {
 }
 
 /// 2D vector for mouse deltas
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
@@ -43,7 +45,7 @@ impl Vector2 {
 }
 
 /// Keyboard modifiers state
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
 pub struct Modifiers {
     pub shift: bool,
     pub ctrl: bool,
@@ -81,24 +83,73 @@ pub enum UiEvent {
         position: Point,
         delta: Vector2,
     },
+    /// A press ended without a release to act on: it was held into a long
+    /// press, or the system took the touch away. Widgets drop their
+    /// pressed state without clicking.
+    PointerCancel {
+        id: u64,
+        position: Point,
+    },
     Scroll {
         position: Point,
         delta: Vector2,
     },
+    /// The pointer moved onto the element. Sent to it alone, without
+    /// capturing or bubbling.
+    PointerEnter,
+    /// The pointer moved off the element
+    PointerLeave,
+    /// The primary button was held still on the element; sent by the
+    /// dispatcher after the press, at the press position
+    LongPress {
+        position: Point,
+    },
+    /// Second primary press close in time and place to the first; sent by
+    /// the dispatcher right after the second `PointerDown`
+    DoubleClick {
+        position: Point,
+    },
+    /// `key` is the physical key (position on a US layout) and
+    /// `logical_key` what it produces in the active layout. Match
+    /// shortcuts with `Shortcut`, which checks both.
     KeyDown {
         key: KeyCode,
+        logical_key: Key,
         modifiers: Modifiers,
         repeat: bool,
     },
     KeyUp {
         key: KeyCode,
+        logical_key: Key,
         modifiers: Modifiers,
     },
     TextInput {
         character: char,
     },
+    /// The input method's in-progress composition changed; empty `text`
+    /// ends it. `cursor` is a byte range within `text`.
+    ImePreedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// The input method finished composing `text`
+    ImeCommit {
+        text: String,
+    },
     Focus,
     Blur,
+    /// Files dragged from the OS are over the window
+    FileHover {
+        position: Point,
+        paths: Vec<PathBuf>,
+    },
+    /// Files dragged from the OS were dropped on the window
+    FileDrop {
+        position: Point,
+        paths: Vec<PathBuf>,
+    },
+    /// The drag left the window or was cancelled without dropping
+    FileHoverCancelled,
     Custom {
         name: String,
         data: Box<dyn std::any::Any + Send + Sync>,
@@ -111,21 +162,83 @@ impl UiEvent {
             UiEvent::PointerDown { position, .. }
             | UiEvent::PointerUp { position, .. }
             | UiEvent::PointerMove { position, .. }
-            | UiEvent::Scroll { position, .. } => Some(*position),
+            | UiEvent::PointerCancel { position, .. }
+            | UiEvent::Scroll { position, .. }
+            | UiEvent::LongPress { position }
+            | UiEvent::DoubleClick { position }
+            | UiEvent::FileHover { position, .. }
+            | UiEvent::FileDrop { position, .. } => Some(*position),
             _ => None,
         }
     }
 
+    /// Copy of a positioned event with its position, and pointer deltas,
+    /// mapped through `matrix`; `None` for events without a position
+    pub fn transformed(&self, matrix: &Matrix) -> Option<UiEvent> {
+        let map_delta = |delta: &Vector2| {
+            let (x, y) = matrix.map_vector(delta.x, delta.y);
+            Vector2 { x, y }
+        };
+        Some(match self {
+            UiEvent::PointerDown { id, position, button } => UiEvent::PointerDown {
+                id: *id,
+                position: matrix.map_point(*position),
+                button: *button,
+            },
+            UiEvent::PointerUp { id, position, button } => UiEvent::PointerUp {
+                id: *id,
+                position: matrix.map_point(*position),
+                button: *button,
+            },
+            UiEvent::PointerMove { id, position, delta } => UiEvent::PointerMove {
+                id: *id,
+                position: matrix.map_point(*position),
+                delta: map_delta(delta),
+            },
+            UiEvent::PointerCancel { id, position } => UiEvent::PointerCancel {
+                id: *id,
+                position: matrix.map_point(*position),
+            },
+            UiEvent::Scroll { position, delta } => UiEvent::Scroll {
+                position: matrix.map_point(*position),
+                delta: map_delta(delta),
+            },
+            UiEvent::LongPress { position } => UiEvent::LongPress {
+                position: matrix.map_point(*position),
+            },
+            UiEvent::DoubleClick { position } => UiEvent::DoubleClick {
+                position: matrix.map_point(*position),
+            },
+            UiEvent::FileHover { position, paths } => UiEvent::FileHover {
+                position: matrix.map_point(*position),
+                paths: paths.clone(),
+            },
+            UiEvent::FileDrop { position, paths } => UiEvent::FileDrop {
+                position: matrix.map_point(*position),
+                paths: paths.clone(),
+            },
+            _ => return None,
+        })
+    }
+
     pub fn is_pointer_event(&self) -> bool {
         matches!(
             self,
             UiEvent::PointerDown { .. }
                 | UiEvent::PointerUp { .. }
                 | UiEvent::PointerMove { .. }
+                | UiEvent::PointerCancel { .. }
                 | UiEvent::Scroll { .. }
         )
     }
 
+    pub fn is_file_drag_event(&self) -> bool {
+        matches!(
+            self,
+            UiEvent::FileHover { .. } | UiEvent::FileDrop { .. } | UiEvent::FileHoverCancelled
+        )
+    }
+
     pub fn is_keyboard_event(&self) -> bool {
         matches!(
             self,
@@ -142,6 +255,13 @@ pub enum EventPhase {
     Bubbling,
 }
 
+/// A change to pointer capture requested by an event handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerCaptureRequest {
+    Capture(ElementId),
+    Release,
+}
+
 /// Event context passed to event handlers
 pub struct EventContext {
     pub target: ElementId,
@@ -149,6 +269,13 @@ pub struct EventContext {
     pub phase: EventPhase,
     pub handled: bool,
     pub default_prevented: bool,
+    /// Element currently holding pointer capture, if any
+    pub pointer_capture: Option<ElementId>,
+    /// Modifier keys held when the event was sent, for Ctrl+click and the like
+    pub modifiers: Modifiers,
+    capture_request: Option<PointerCaptureRequest>,
+    focus_requested: bool,
+    dirty: bool,
 }
 
 impl EventContext {
@@ -159,6 +286,11 @@ impl EventContext {
             phase,
             handled: false,
             default_prevented: false,
+            pointer_capture: None,
+            modifiers: Modifiers::default(),
+            capture_request: None,
+            focus_requested: false,
+            dirty: false,
         }
     }
 
@@ -173,6 +305,47 @@ impl EventContext {
     pub fn is_at_target(&self) -> bool {
         self.target == self.current_target
     }
+
+    /// Route all pointer events to `element_id` until released.
+    ///
+    /// Capture is released automatically on the next `PointerUp`.
+    pub fn capture_pointer(&mut self, element_id: ElementId) {
+        self.capture_request = Some(PointerCaptureRequest::Capture(element_id));
+    }
+
+    /// Release pointer capture, resuming normal hit-testing
+    pub fn release_pointer_capture(&mut self) {
+        self.capture_request = Some(PointerCaptureRequest::Release);
+    }
+
+    /// Whether the element handling this event holds pointer capture
+    pub fn has_pointer_capture(&self) -> bool {
+        self.pointer_capture == Some(self.current_target)
+    }
+
+    pub(crate) fn take_capture_request(&mut self) -> Option<PointerCaptureRequest> {
+        self.capture_request.take()
+    }
+
+    /// Move keyboard focus to the element handling this event, so key
+    /// presses are routed to it. Widgets call this on pointer down.
+    pub fn request_focus(&mut self) {
+        self.focus_requested = true;
+    }
+
+    pub(crate) fn take_focus_request(&mut self) -> bool {
+        std::mem::take(&mut self.focus_requested)
+    }
+
+    /// Rebuild the element handling this event and draw a new frame, for
+    /// handlers that change how the widget looks, like hover and press
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub(crate) fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
 }
 
 /// Event path through the element tree
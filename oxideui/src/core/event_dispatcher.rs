@@ -1,23 +1,56 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use web_time::Instant;
 
 use crate::core::element::{ElementId, ElementTree};
-use crate::core::event::{EventContext, EventPath, EventPhase, EventResult, UiEvent};
-use crate::core::render_object::Point;
+use crate::core::event::{
+    EventContext, EventPath, EventPhase, EventResult, Modifiers, MouseButton, PointerCaptureRequest, UiEvent,
+};
+use crate::core::event_system::{FocusManager, GestureRecognizer, GestureType};
+use crate::core::render_object::{Point, Rect, RenderObject};
 use crate::core::widget::Widget;
+use winit::keyboard::KeyCode;
+
+/// How an element came to be focused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusOrigin {
+    Pointer,
+    Keyboard,
+    /// Focused from code; shows a ring only if the user was last on the keyboard
+    Programmatic,
+}
 
 /// Event dispatcher handles routing events through the widget tree
 pub struct EventDispatcher {
     /// Currently focused element
     focused_element: Option<ElementId>,
+    focus_origin: FocusOrigin,
+    /// Tab order of elements that have taken focus
+    focus_manager: FocusManager,
+    /// Whether the most recent press came from the keyboard rather than a pointer
+    keyboard_modality: bool,
 
     /// Element currently under the pointer
     hovered_element: Option<ElementId>,
+    /// Element under an OS file drag, so it hears about a cancel
+    file_hover_target: Option<ElementId>,
 
     /// Current pointer position
     pointer_position: Option<Point>,
 
+    /// Turns primary presses into `LongPress` and `DoubleClick` events
+    gestures: GestureRecognizer,
+
+    /// Element receiving all pointer events regardless of hit-testing
+    pointer_capture: Option<ElementId>,
+
+    /// Modifier keys currently held
+    modifiers: Modifiers,
+
+    /// Elements whose handlers called `EventContext::mark_dirty`
+    dirty_elements: Mutex<Vec<ElementId>>,
+
     /// Widget registry - maps ElementId to Widget for event handling
     /// CRITICAL: This is needed to actually call widget.handle_event()
     widget_handlers: Arc<RwLock<HashMap<ElementId, Box<dyn Widget>>>>,
@@ -27,8 +60,16 @@ impl EventDispatcher {
     pub fn new() -> Self {
         Self {
             focused_element: None,
+            focus_origin: FocusOrigin::Programmatic,
+            focus_manager: FocusManager::new(),
+            keyboard_modality: false,
             hovered_element: None,
+            file_hover_target: None,
             pointer_position: None,
+            gestures: GestureRecognizer::new(),
+            pointer_capture: None,
+            modifiers: Modifiers::default(),
+            dirty_elements: Mutex::new(Vec::new()),
             widget_handlers: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -44,23 +85,79 @@ impl EventDispatcher {
         self.widget_handlers.write().remove(&element_id);
     }
 
+    /// Record the modifier keys held, passed to handlers with each event
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
     /// Dispatch an event through the element tree
     pub fn dispatch_event(&mut self, event: &UiEvent, element_tree: &ElementTree) -> EventResult {
+        let _span = span!(DEBUG, "dispatch_event", event = ?event);
+        // Sent from outside when the pointer leaves the window: everything
+        // hovered loses hover
+        if matches!(event, UiEvent::PointerLeave) {
+            self.pointer_position = None;
+            self.update_hover_state(None, element_tree);
+            return EventResult::Handled;
+        }
         // Update pointer position for pointer events
         if let Some(pos) = event.position() {
             self.pointer_position = Some(pos);
         }
+        // Letting go of a long press is not a click
+        if let UiEvent::PointerUp { id, position, button: MouseButton::Left } = event {
+            let long_pressed = self.gestures.get_gesture(*id).map(|gesture| gesture.gesture_type);
+            if long_pressed == Some(GestureType::LongPress) {
+                self.gestures.handle_pointer_up(*id);
+                return self.dispatch_event(&UiEvent::PointerCancel { id: *id, position: *position }, element_tree);
+            }
+        }
+        match event {
+            UiEvent::PointerDown { .. } => self.keyboard_modality = false,
+            UiEvent::KeyDown { .. } => self.keyboard_modality = true,
+            _ => {}
+        }
 
         // Determine target element
         let target_id = match event {
+            UiEvent::PointerDown { .. }
+            | UiEvent::PointerUp { .. }
+            | UiEvent::PointerMove { .. }
+            | UiEvent::PointerCancel { .. }
+                if self.captured_target(element_tree).is_some() =>
+            {
+                // A captured pointer bypasses hit-testing entirely
+                self.captured_target(element_tree)
+            }
             UiEvent::PointerDown { position, .. }
             | UiEvent::PointerUp { position, .. }
             | UiEvent::PointerMove { position, .. }
+            | UiEvent::PointerCancel { position, .. }
             | UiEvent::Scroll { position, .. } => {
                 // Hit test to find which element was clicked/touched
                 self.hit_test(*position, element_tree)
             }
-            UiEvent::KeyDown { .. } | UiEvent::KeyUp { .. } | UiEvent::TextInput { .. } => {
+            UiEvent::FileHover { position, .. } | UiEvent::FileDrop { position, .. } => {
+                let target = self.hit_test(*position, element_tree);
+                // Moving off a drop target cancels the hover there
+                if self.file_hover_target.is_some() && self.file_hover_target != target {
+                    self.dispatch_file_hover_cancelled(element_tree);
+                }
+                self.file_hover_target = match event {
+                    UiEvent::FileHover { .. } => target,
+                    _ => None,
+                };
+                target
+            }
+            UiEvent::FileHoverCancelled => self
+                .file_hover_target
+                .take()
+                .filter(|id| element_tree.get(*id).is_some()),
+            UiEvent::KeyDown { .. }
+            | UiEvent::KeyUp { .. }
+            | UiEvent::TextInput { .. }
+            | UiEvent::ImePreedit { .. }
+            | UiEvent::ImeCommit { .. } => {
                 // Keyboard events go to focused element
                 self.focused_element
             }
@@ -68,6 +165,10 @@ impl EventDispatcher {
                 // Focus events target the focused element
                 self.focused_element
             }
+            UiEvent::PointerEnter | UiEvent::PointerLeave => self.hovered_element,
+            UiEvent::LongPress { position } | UiEvent::DoubleClick { position } => self
+                .captured_target(element_tree)
+                .or_else(|| self.hit_test(*position, element_tree)),
             UiEvent::Custom { .. } => {
                 // Custom events go to focused element by default
                 self.focused_element
@@ -75,20 +176,146 @@ impl EventDispatcher {
         };
 
         let Some(target_id) = target_id else {
+            // Moving onto empty space unhovers what was under the pointer
+            if matches!(event, UiEvent::PointerMove { .. }) {
+                self.update_hover_state(None, element_tree);
+            }
             // No target found, event is unhandled
+            self.recognize_gesture(event, element_tree);
+            self.handle_tab(event, EventResult::Unhandled);
             return EventResult::Unhandled;
         };
 
         // Update hover state for pointer events
         if event.is_pointer_event() {
-            self.update_hover_state(target_id, element_tree);
+            self.update_hover_state(Some(target_id), element_tree);
         }
 
         // Build event path (root → target → root)
         let event_path = self.build_event_path(target_id, element_tree);
 
         // Execute event propagation
-        self.propagate_event(event, &event_path, element_tree)
+        let mut capture_requests = Vec::new();
+        let mut focus_request = None;
+        let result = self.propagate_event(event, &event_path, element_tree, &mut capture_requests, &mut focus_request);
+
+        if let Some(element_id) = focus_request {
+            let origin = if event.is_pointer_event() { FocusOrigin::Pointer } else { FocusOrigin::Keyboard };
+            self.focus_manager.register_focusable(element_id);
+            self.set_focus_with_origin(Some(element_id), origin);
+        }
+
+        self.handle_tab(event, result);
+
+        for request in capture_requests {
+            match request {
+                PointerCaptureRequest::Capture(element_id) => self.pointer_capture = Some(element_id),
+                PointerCaptureRequest::Release => self.pointer_capture = None,
+            }
+        }
+
+        // Capture never outlives the press that started it
+        if matches!(event, UiEvent::PointerUp { .. } | UiEvent::PointerCancel { .. }) {
+            self.pointer_capture = None;
+        }
+
+        self.recognize_gesture(event, element_tree);
+        result
+    }
+
+    /// Tab moves between focusable elements unless the focused one used it
+    fn handle_tab(&mut self, event: &UiEvent, result: EventResult) {
+        if let UiEvent::KeyDown { key: KeyCode::Tab, modifiers, .. } = event {
+            if !result.is_handled() {
+                if modifiers.shift {
+                    self.focus_manager.focus_previous();
+                } else {
+                    self.focus_manager.focus_next();
+                }
+                let next = self.focus_manager.get_focused();
+                self.set_focus_with_origin(next, FocusOrigin::Keyboard);
+            }
+        }
+    }
+
+    /// Follow primary presses, sending `DoubleClick` after a second press
+    /// and `LongPress` when a press moves onto its long-press time
+    fn recognize_gesture(&mut self, event: &UiEvent, element_tree: &ElementTree) {
+        let gesture = match event {
+            UiEvent::PointerDown { id, position, button: MouseButton::Left } => {
+                self.gestures.handle_pointer_down(*id, *position).map(|gesture| (gesture, *position))
+            }
+            UiEvent::PointerMove { id, position, .. } => self
+                .gestures
+                .handle_pointer_move(*id, *position)
+                .and_then(|gesture| Some((gesture, self.gestures.get_gesture(*id)?.start_position))),
+            UiEvent::PointerUp { id, button: MouseButton::Left, .. } | UiEvent::PointerCancel { id, .. } => {
+                self.gestures.handle_pointer_up(*id);
+                None
+            }
+            _ => None,
+        };
+        match gesture {
+            Some((GestureType::DoubleTap, position)) => {
+                self.dispatch_event(&UiEvent::DoubleClick { position }, element_tree);
+            }
+            Some((GestureType::LongPress, position)) => {
+                self.dispatch_event(&UiEvent::LongPress { position }, element_tree);
+            }
+            _ => {}
+        }
+    }
+
+    /// Send `LongPress` for a press held still past its time. Call on each
+    /// wakeup; `long_press_deadline` says when the next one is due.
+    pub fn poll_long_press(&mut self, element_tree: &ElementTree) -> EventResult {
+        match self.gestures.poll_long_press() {
+            Some((_, position)) => self.dispatch_event(&UiEvent::LongPress { position }, element_tree),
+            None => EventResult::Unhandled,
+        }
+    }
+
+    /// When a press still held becomes a long press
+    pub fn long_press_deadline(&self) -> Option<Instant> {
+        self.gestures.long_press_deadline()
+    }
+
+    fn dispatch_file_hover_cancelled(&mut self, element_tree: &ElementTree) {
+        let Some(target_id) = self.file_hover_target.take() else {
+            return;
+        };
+        if element_tree.get(target_id).is_none() {
+            return;
+        }
+        let event_path = self.build_event_path(target_id, element_tree);
+        let mut capture_requests = Vec::new();
+        let mut focus_request = None;
+        self.propagate_event(
+            &UiEvent::FileHoverCancelled,
+            &event_path,
+            element_tree,
+            &mut capture_requests,
+            &mut focus_request,
+        );
+    }
+
+    /// Route pointer events to `element_id` until released or the next `PointerUp`
+    pub fn capture_pointer(&mut self, element_id: ElementId) {
+        self.pointer_capture = Some(element_id);
+    }
+
+    pub fn release_pointer_capture(&mut self) {
+        self.pointer_capture = None;
+    }
+
+    /// Element currently holding pointer capture
+    pub fn pointer_capture(&self) -> Option<ElementId> {
+        self.pointer_capture
+    }
+
+    /// The capturing element, if it is still mounted
+    fn captured_target(&self, element_tree: &ElementTree) -> Option<ElementId> {
+        self.pointer_capture.filter(|id| element_tree.get(*id).is_some())
     }
 
     /// Hit test to find which element is at the given position
@@ -96,7 +323,50 @@ impl EventDispatcher {
         // Start from root and traverse down
         let root_id = element_tree.root()?;
 
-        self.hit_test_recursive(position, root_id, element_tree)
+        // Overlays paint above everything and outside their element's
+        // bounds, so open menus and their barriers are tested first
+        self.hit_test_overlays(position, root_id, element_tree)
+            .or_else(|| self.hit_test_recursive(position, root_id, element_tree))
+    }
+
+    /// Deepest element whose overlay content is under `position`
+    fn hit_test_overlays(&self, position: Point, element_id: ElementId, element_tree: &ElementTree) -> Option<ElementId> {
+        let element = element_tree.get(element_id)?;
+        for &child_id in element.children.iter().rev() {
+            if let Some(hit) = self.hit_test_overlays(position, child_id, element_tree) {
+                return Some(hit);
+            }
+        }
+        // Elements built inside an overlay have bounds of their own, outside
+        // those of their parent
+        if element.in_overlay {
+            return self.hit_test_recursive(position, element_id, element_tree);
+        }
+        let local = element.transform.invert()?.map_point(position);
+        let render_obj = element.render_object.as_ref()?;
+        self.point_in_overlay(local, render_obj).then_some(element_id)
+    }
+
+    /// Whether `point` hits an overlay within `render_obj`. Overlays ignore
+    /// ancestor clips, so only transforms are followed on the way down.
+    fn point_in_overlay(&self, point: Point, render_obj: &RenderObject) -> bool {
+        match render_obj {
+            RenderObject::Overlay { child } => self.point_in_render_object(point, child),
+            RenderObject::Group { children } => children.iter().any(|child| self.point_in_overlay(point, child)),
+            RenderObject::Transform { child, matrix } => match matrix.invert() {
+                Some(inverse) => self.point_in_overlay(inverse.map_point(point), child),
+                None => false,
+            },
+            RenderObject::Clip { child, .. }
+            | RenderObject::ClipShape { child, .. }
+            | RenderObject::Viewport { child, .. }
+            | RenderObject::Opacity { child, .. }
+            | RenderObject::Effect { child, .. }
+            | RenderObject::Backdrop { child, .. }
+            | RenderObject::Sticky { child, .. }
+            | RenderObject::Element { child, .. } => self.point_in_overlay(point, child),
+            _ => false,
+        }
     }
 
     /// Recursive hit testing
@@ -108,9 +378,25 @@ impl EventDispatcher {
     ) -> Option<ElementId> {
         let element = element_tree.get(element_id)?;
 
-        // Check if point is within this element's bounds
-        if let Some(render_obj) = &element.render_object {
-            if !self.point_in_render_object(position, render_obj) {
+        // Hit-test in the element's own coordinate space so scaled, rotated
+        // and translated subtrees resolve correctly
+        let local = match element.transform.invert() {
+            Some(inverse) => inverse.map_point(position),
+            // A singular transform collapses the element to nothing
+            None => return None,
+        };
+
+        // Prefer the laid-out geometry; fall back to the render object for
+        // elements that haven't been through layout yet. Elements with
+        // neither weren't part of the last frame.
+        if element.bounds.is_some() {
+            let rect = Rect::new(0.0, 0.0, element.size.width, element.size.height);
+            if !rect.contains(local.x, local.y) {
+                return None;
+            }
+        } else {
+            let render_obj = element.render_object.as_ref()?;
+            if !self.point_in_render_object(local, render_obj) {
                 return None;
             }
         }
@@ -127,25 +413,15 @@ impl EventDispatcher {
     }
 
     /// Check if a point is within a render object's bounds
-    fn point_in_render_object(
-        &self,
-        point: Point,
-        render_obj: &crate::core::render_object::RenderObject,
-    ) -> bool {
-        use crate::core::render_object::RenderObject;
-
+    fn point_in_render_object(&self, point: Point, render_obj: &RenderObject) -> bool {
         match render_obj {
-            RenderObject::Rect { rect, .. } => {
-                point.x >= rect.x
-                    && point.x <= rect.x + rect.width
-                    && point.y >= rect.y
-                    && point.y <= rect.y + rect.height
-            }
-            RenderObject::Text { position, .. } => {
-                // Simplified: just check if point is near text position
-                // TODO: Proper text bounds checking with actual text layout
-                let margin = 20.0;
-                (point.x - position.x).abs() < margin && (point.y - position.y).abs() < margin
+            RenderObject::Rect { rect, .. } => rect.contains(point.x, point.y),
+            RenderObject::Text { content, style, position, width } => {
+                // Approximate the laid-out paragraph box: text is painted with
+                // its top-left corner at `position`
+                let width = width.unwrap_or(content.chars().count() as f32 * style.font_size * 0.6);
+                let height = style.font_size * 1.2;
+                Rect::new(position.x, position.y, width, height).contains(point.x, point.y)
             }
             RenderObject::Group { children } => {
                 // Check any child
@@ -153,24 +429,32 @@ impl EventDispatcher {
                     .iter()
                     .any(|child| self.point_in_render_object(point, child))
             }
-            RenderObject::Transform { child, matrix: _ } => {
-                // TODO: Transform point by inverse matrix
-                // For now, just check child directly
-                self.point_in_render_object(point, child)
+            RenderObject::Transform { child, matrix } => match matrix.invert() {
+                Some(inverse) => self.point_in_render_object(inverse.map_point(point), child),
+                None => false,
+            },
+            RenderObject::Clip { rect, child } | RenderObject::Viewport { rect, child } => {
+                rect.contains(point.x, point.y) && self.point_in_render_object(point, child)
             }
-            RenderObject::Clip { rect, child } => {
-                // Check if point is in clip rect, then check child
-                let in_clip = point.x >= rect.x
-                    && point.x <= rect.x + rect.width
-                    && point.y >= rect.y
-                    && point.y <= rect.y + rect.height;
-
-                in_clip && self.point_in_render_object(point, child)
+            RenderObject::ClipShape { shape, child } => {
+                shape.contains(point) && self.point_in_render_object(point, child)
+            }
+            RenderObject::Image { size, .. } => {
+                Rect::new(0.0, 0.0, size.width, size.height).contains(point.x, point.y)
             }
-            RenderObject::Image { .. } => {
-                // TODO: Proper image bounds
-                false
+            RenderObject::Path { bounds, .. } => bounds.contains(point.x, point.y),
+            // The frosted panel itself is hit, not just what's drawn on it
+            RenderObject::Backdrop { rect, child, .. } => {
+                rect.contains(point.x, point.y) || self.point_in_render_object(point, child)
             }
+            // Fully transparent content still takes clicks, as in CSS
+            RenderObject::Opacity { child, .. }
+            | RenderObject::Effect { child, .. }
+            | RenderObject::Overlay { child }
+            | RenderObject::Element { child, .. } => self.point_in_render_object(point, child),
+            // Hit where the header is laid out; a pinned header is painted
+            // over content that still takes the hit
+            RenderObject::Sticky { child, .. } => self.point_in_render_object(point, child),
             RenderObject::None => false,
         }
     }
@@ -199,6 +483,8 @@ impl EventDispatcher {
         event: &UiEvent,
         path: &EventPath,
         element_tree: &ElementTree,
+        capture_requests: &mut Vec<PointerCaptureRequest>,
+        focus_request: &mut Option<ElementId>,
     ) -> EventResult {
         // Phase 1: Capturing (root → target)
         for &element_id in &path.capturing {
@@ -209,7 +495,7 @@ impl EventDispatcher {
             let mut context = EventContext::new(path.target, element_id, EventPhase::Capturing);
 
             if let Some(result) =
-                self.dispatch_to_element(event, element_id, &mut context, element_tree)
+                self.dispatch_to_element(event, element_id, element_tree, &mut context, capture_requests, focus_request)
             {
                 if result.should_stop() {
                     return result;
@@ -220,7 +506,7 @@ impl EventDispatcher {
         // Phase 2: At Target
         let mut context = EventContext::new(path.target, path.target, EventPhase::AtTarget);
         if let Some(result) =
-            self.dispatch_to_element(event, path.target, &mut context, element_tree)
+            self.dispatch_to_element(event, path.target, element_tree, &mut context, capture_requests, focus_request)
         {
             if result.should_stop() {
                 return result;
@@ -236,7 +522,7 @@ impl EventDispatcher {
             let mut context = EventContext::new(path.target, element_id, EventPhase::Bubbling);
 
             if let Some(result) =
-                self.dispatch_to_element(event, element_id, &mut context, element_tree)
+                self.dispatch_to_element(event, element_id, element_tree, &mut context, capture_requests, focus_request)
             {
                 if result.should_stop() {
                     return result;
@@ -253,40 +539,85 @@ impl EventDispatcher {
         &self,
         event: &UiEvent,
         element_id: ElementId,
+        element_tree: &ElementTree,
         context: &mut EventContext,
-        _element_tree: &ElementTree,
+        capture_requests: &mut Vec<PointerCaptureRequest>,
+        focus_request: &mut Option<ElementId>,
     ) -> Option<EventResult> {
         // Get the widget for this element
+        // A registered handler stands in for the widget the element was
+        // built from
         let handlers = self.widget_handlers.read();
-        let widget = handlers.get(&element_id)?;
+        let widget = match handlers.get(&element_id) {
+            Some(widget) => widget.as_ref(),
+            None => element_tree.get(element_id)?.widget.as_deref()?,
+        };
 
         // Call the widget's event handler
-        Some(widget.handle_event(event, context))
+        context.pointer_capture = self.pointer_capture;
+        context.modifiers = self.modifiers;
+        let result = widget.handle_event(event, context);
+        capture_requests.extend(context.take_capture_request());
+        if context.take_focus_request() {
+            *focus_request = Some(element_id);
+        }
+        if context.take_dirty() {
+            self.dirty_elements.lock().push(element_id);
+        }
+        Some(result)
     }
 
-    /// Update hover state when pointer moves
-    fn update_hover_state(&mut self, new_target: ElementId, element_tree: &ElementTree) {
-        if self.hovered_element == Some(new_target) {
+    /// Update hover state when pointer moves. An element is hovered while
+    /// the pointer is over it or any of its descendants, so wrappers such
+    /// as tooltips see the pointer arrive and leave.
+    fn update_hover_state(&mut self, new_target: Option<ElementId>, element_tree: &ElementTree) {
+        if self.hovered_element == new_target {
             return; // No change
         }
-
-        // Element lost hover
-        if let Some(old_target) = self.hovered_element {
-            if element_tree.get(old_target).is_some() {
-                // TODO: Trigger hover leave event
+        let ancestors = |target: Option<ElementId>| {
+            let mut chain = Vec::new();
+            let mut current = target;
+            while let Some(element) = current.and_then(|id| element_tree.get(id)) {
+                chain.push(element.id);
+                current = element.parent;
             }
+            chain
+        };
+        let old_chain = ancestors(self.hovered_element);
+        let new_chain = ancestors(new_target);
+
+        // Innermost first for elements losing hover, outermost first for
+        // those gaining it
+        for id in old_chain.iter().filter(|id| !new_chain.contains(id)) {
+            self.dispatch_at_target(&UiEvent::PointerLeave, *id, element_tree);
+        }
+        self.hovered_element = new_target;
+        for id in new_chain.iter().rev().filter(|id| !old_chain.contains(id)) {
+            self.dispatch_at_target(&UiEvent::PointerEnter, *id, element_tree);
         }
+    }
 
-        // Element gained hover
-        self.hovered_element = Some(new_target);
-        // TODO: Trigger hover enter event
+    /// Send `event` to `element_id` alone, for events that don't bubble
+    fn dispatch_at_target(&self, event: &UiEvent, element_id: ElementId, element_tree: &ElementTree) {
+        let mut context = EventContext::new(element_id, element_id, EventPhase::AtTarget);
+        let mut capture_requests = Vec::new();
+        let mut focus_request = None;
+        self.dispatch_to_element(event, element_id, element_tree, &mut context, &mut capture_requests, &mut focus_request);
     }
 
     /// Set the focused element
     pub fn set_focus(&mut self, element_id: Option<ElementId>) {
+        self.set_focus_with_origin(element_id, FocusOrigin::Programmatic);
+    }
+
+    /// Set the focused element, recording whether a pointer or the keyboard
+    /// moved it there
+    pub fn set_focus_with_origin(&mut self, element_id: Option<ElementId>, origin: FocusOrigin) {
+        self.focus_origin = origin;
         if self.focused_element == element_id {
             return;
         }
+        self.focus_manager.set_focus(element_id);
 
         // TODO: Dispatch blur event to old focused element
         // TODO: Dispatch focus event to new focused element
@@ -299,11 +630,42 @@ impl EventDispatcher {
         self.focused_element
     }
 
+    /// Tab through the focusable elements of the latest build, and drop
+    /// focus held by an element in `unmounted`. Call after each build.
+    pub fn update_focusables(&mut self, element_tree: &ElementTree, unmounted: &[ElementId]) {
+        self.focus_manager.set_tab_order(element_tree.focusable_elements());
+        if self.focused_element.is_some_and(|id| unmounted.contains(&id)) {
+            self.set_focus(None);
+        }
+    }
+
+    /// Tab order and focus history
+    pub fn focus_manager(&mut self) -> &mut FocusManager {
+        &mut self.focus_manager
+    }
+
+    /// Whether the focused element should show a focus ring, following the
+    /// CSS `:focus-visible` heuristic
+    pub fn focus_visible(&self) -> bool {
+        self.focused_element.is_some()
+            && match self.focus_origin {
+                FocusOrigin::Keyboard => true,
+                FocusOrigin::Pointer => false,
+                FocusOrigin::Programmatic => self.keyboard_modality,
+            }
+    }
+
     /// Get element under pointer
     pub fn hovered_element(&self) -> Option<ElementId> {
         self.hovered_element
     }
 
+    /// Elements that asked to be rebuilt while handling events since the
+    /// last call
+    pub fn take_dirty_elements(&mut self) -> Vec<ElementId> {
+        std::mem::take(&mut *self.dirty_elements.lock())
+    }
+
     /// Get current pointer position
     pub fn pointer_position(&self) -> Option<Point> {
         self.pointer_position
@@ -319,4 +681,217 @@ impl Default for EventDispatcher {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::{Color, Matrix};
+    use crate::core::widget::{WidgetKey, WidgetNode};
+    use crate::layout::constraints::Size;
+    use std::any::Any;
+
+    #[derive(Clone)]
+    struct Dummy;
+
+    impl Widget for Dummy {
+        fn build(&self, _ctx: &crate::core::context::BuildContext) -> WidgetNode {
+            WidgetNode::None
+        }
+        fn key(&self) -> Option<WidgetKey> {
+            None
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_hit_test_uses_transformed_layout_bounds() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Dummy, None, 0);
+        let child = tree.create_element(&Dummy, Some(root), 0);
+
+        tree.set_layout(root, Matrix::identity(), Size::new(400.0, 400.0));
+        // Child is offset by (100, 100) and drawn at 2x scale
+        tree.set_layout(
+            child,
+            Matrix::translate(100.0, 100.0).concat(&Matrix::scale(2.0, 2.0)),
+            Size::new(50.0, 50.0),
+        );
+
+        let dispatcher = EventDispatcher::new();
+        assert_eq!(dispatcher.hit_test(Point::new(190.0, 190.0), &tree), Some(child));
+        assert_eq!(dispatcher.hit_test(Point::new(210.0, 150.0), &tree), Some(root));
+        assert_eq!(dispatcher.hit_test(Point::new(500.0, 10.0), &tree), None);
+    }
+
+    #[test]
+    fn test_overlays_are_hit_outside_their_element() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Dummy, None, 0);
+        let select = tree.create_element(&Dummy, Some(root), 0);
+        let below = tree.create_element(&Dummy, Some(root), 1);
+        tree.set_layout(root, Matrix::identity(), Size::new(400.0, 400.0));
+        tree.set_layout(select, Matrix::translate(10.0, 10.0), Size::new(100.0, 30.0));
+        tree.set_layout(below, Matrix::translate(10.0, 50.0), Size::new(100.0, 30.0));
+
+        // The open menu hangs below the select, over its sibling
+        let menu = RenderObject::overlay(RenderObject::rect(Rect::new(0.0, 30.0, 100.0, 120.0), Color::BLACK));
+        tree.get_mut(select).unwrap().render_object = Some(menu);
+
+        let dispatcher = EventDispatcher::new();
+        assert_eq!(dispatcher.hit_test(Point::new(50.0, 60.0), &tree), Some(select));
+        assert_eq!(dispatcher.hit_test(Point::new(50.0, 200.0), &tree), Some(root));
+    }
+
+    /// Records the events it receives at target
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<RwLock<Vec<&'static str>>>);
+
+    impl Widget for Recorder {
+        fn build(&self, _ctx: &crate::core::context::BuildContext) -> WidgetNode {
+            WidgetNode::None
+        }
+        fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+            if context.phase == EventPhase::AtTarget {
+                self.0.write().push(match event {
+                    UiEvent::PointerDown { .. } => "down",
+                    UiEvent::PointerUp { .. } => "up",
+                    UiEvent::PointerCancel { .. } => "cancel",
+                    UiEvent::LongPress { .. } => "long-press",
+                    UiEvent::DoubleClick { .. } => "double-click",
+                    _ => "other",
+                });
+            }
+            EventResult::Unhandled
+        }
+        fn key(&self) -> Option<WidgetKey> {
+            None
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_second_click_sends_double_click() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Dummy, None, 0);
+        tree.set_layout(root, Matrix::identity(), Size::new(400.0, 400.0));
+        let recorder = Recorder::default();
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.register_widget(root, Box::new(recorder.clone()));
+
+        let position = Point::new(50.0, 50.0);
+        for _ in 0..2 {
+            dispatcher.dispatch_event(&UiEvent::PointerDown { id: 0, position, button: MouseButton::Left }, &tree);
+            dispatcher.dispatch_event(&UiEvent::PointerUp { id: 0, position, button: MouseButton::Left }, &tree);
+        }
+        let received: Vec<_> = recorder.0.read().iter().copied().filter(|name| *name != "other").collect();
+        assert_eq!(received, ["down", "up", "down", "double-click", "up"]);
+        assert_eq!(dispatcher.long_press_deadline(), None);
+    }
+
+    #[test]
+    fn test_hover_ends_off_element_and_outside_the_window() {
+        use crate::core::event::Vector2;
+        use crate::widgets::Button;
+
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Dummy, None, 0);
+        let button = Button::new("Save");
+        let element = tree.create_element(&button, Some(root), 0);
+        tree.set_layout(root, Matrix::identity(), Size::new(200.0, 200.0));
+        tree.set_layout(element, Matrix::translate(10.0, 10.0), Size::new(100.0, 30.0));
+        let mut dispatcher = EventDispatcher::new();
+        let move_to = |dispatcher: &mut EventDispatcher, x: f32, y: f32| {
+            let event = UiEvent::PointerMove { id: 0, position: Point::new(x, y), delta: Vector2::ZERO };
+            dispatcher.dispatch_event(&event, &tree);
+        };
+
+        // Hovering restyles the button, so it asks for a rebuild
+        move_to(&mut dispatcher, 20.0, 20.0);
+        assert!(button.style_state().hover);
+        assert_eq!(dispatcher.take_dirty_elements(), vec![element]);
+
+        // Past the root's bounds nothing is hit, which still ends the hover
+        move_to(&mut dispatcher, 300.0, 20.0);
+        assert!(!button.style_state().hover);
+        assert_eq!(dispatcher.hovered_element(), None);
+        assert_eq!(dispatcher.take_dirty_elements(), vec![element]);
+
+        // Leaving the window straight from the button
+        move_to(&mut dispatcher, 20.0, 20.0);
+        dispatcher.dispatch_event(&UiEvent::PointerLeave, &tree);
+        assert!(!button.style_state().hover);
+        assert_eq!(dispatcher.pointer_position(), None);
+    }
+
+    #[test]
+    fn test_release_after_long_press_cancels_instead_of_clicking() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Dummy, None, 0);
+        tree.set_layout(root, Matrix::identity(), Size::new(400.0, 400.0));
+        let recorder = Recorder::default();
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.register_widget(root, Box::new(recorder.clone()));
+        dispatcher.gestures = GestureRecognizer::new().with_long_press_duration(std::time::Duration::ZERO);
+
+        let position = Point::new(50.0, 50.0);
+        dispatcher.dispatch_event(&UiEvent::PointerDown { id: 0, position, button: MouseButton::Left }, &tree);
+        dispatcher.poll_long_press(&tree);
+        dispatcher.dispatch_event(&UiEvent::PointerUp { id: 0, position, button: MouseButton::Left }, &tree);
+        let received: Vec<_> = recorder.0.read().iter().copied().filter(|name| *name != "other").collect();
+        assert_eq!(received, ["down", "long-press", "cancel"]);
+    }
+
+    #[test]
+    fn test_tab_visits_built_focusable_elements_in_order() {
+        use crate::widgets::basic::{Column, Text};
+        use crate::widgets::Button;
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+        use crate::runtime::widget_builder::WidgetBuilder;
+        use winit::keyboard::{Key, NamedKey};
+
+        let root: Box<dyn Widget> = Box::new(Column::new().with_children(vec![
+            Box::new(Button::new("First")),
+            Box::new(Text::new("Not focusable")),
+            Box::new(Button::new("Disabled").disabled(true)),
+            Box::new(Button::new("Last")),
+        ]));
+        let tree = new_shared_element_tree();
+        let builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(tree.clone());
+        builder.build_widget_tree(&root, Constraints::new(0.0, 400.0, 0.0, 400.0));
+        let mut dispatcher = EventDispatcher::new();
+        let unmounted = tree.write().take_unmounted();
+        dispatcher.update_focusables(&tree.read(), &unmounted);
+
+        let label = |dispatcher: &EventDispatcher| {
+            let tree = tree.read();
+            let widget = tree.get(dispatcher.focused_element()?)?.widget.as_ref()?;
+            Some(widget.as_any().downcast_ref::<Button>()?.label.clone())
+        };
+        let tab = UiEvent::KeyDown {
+            key: KeyCode::Tab,
+            logical_key: Key::Named(NamedKey::Tab),
+            modifiers: Modifiers::default(),
+            repeat: false,
+        };
+        let mut visited = Vec::new();
+        for _ in 0..3 {
+            dispatcher.dispatch_event(&tab, &tree.read());
+            visited.push(label(&dispatcher));
+        }
+        let expected = ["First", "Last", "First"].map(|label| Some(label.to_string()));
+        assert_eq!(visited, expected);
+    }
+}
@@ -1,11 +1,13 @@
 // File: ./oxideui/src/core/event_system.rs
 //! Complete event dispatching with gesture recognition and focus management
 
-use std::collections::HashMap;
-use std::time::{Duration, Instant};
-use crate::core::element::ElementId;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use web_time::Instant;
+use crate::core::element::{ElementId, ElementTree};
 use crate::core::event::{Vector2};
 use crate::core::render_object::Point;
+use crate::core::semantics::{LiveRegion, SemanticsData};
 
 /// Gesture recognizer for touch/mouse gestures
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -78,13 +80,20 @@ impl GestureRecognizer {
     pub fn new() -> Self {
         Self {
             active_gestures: HashMap::new(),
-            tap_threshold: 10.0,
+            // Fingers wobble more than mice; phones get a larger tap slop
+            tap_threshold: if cfg!(any(target_os = "android", target_os = "ios")) { 18.0 } else { 10.0 },
             long_press_duration: Duration::from_millis(500),
             double_tap_duration: Duration::from_millis(300),
             last_tap: None,
         }
     }
 
+    /// How long a press must be held still to become a long press
+    pub fn with_long_press_duration(mut self, duration: Duration) -> Self {
+        self.long_press_duration = duration;
+        self
+    }
+
     pub fn handle_pointer_down(&mut self, id: u64, position: Point) -> Option<GestureType> {
         // Check for double tap
         if let Some((last_time, last_pos)) = self.last_tap {
@@ -133,6 +142,26 @@ impl GestureRecognizer {
         None
     }
 
+    /// Turn presses held still past the long-press duration into long
+    /// presses, returning the pointer id and press position of the first
+    pub fn poll_long_press(&mut self) -> Option<(u64, Point)> {
+        let long_press_duration = self.long_press_duration;
+        let (id, gesture) = self.active_gestures.iter_mut().find(|(_, gesture)| {
+            gesture.gesture_type == GestureType::Tap && gesture.duration() >= long_press_duration
+        })?;
+        gesture.gesture_type = GestureType::LongPress;
+        Some((*id, gesture.start_position))
+    }
+
+    /// When the earliest press still held becomes a long press
+    pub fn long_press_deadline(&self) -> Option<Instant> {
+        self.active_gestures
+            .values()
+            .filter(|gesture| gesture.gesture_type == GestureType::Tap)
+            .map(|gesture| gesture.start_time + self.long_press_duration)
+            .min()
+    }
+
     pub fn get_gesture(&self, id: u64) -> Option<&GestureState> {
         self.active_gestures.get(&id)
     }
@@ -205,11 +234,10 @@ impl FocusManager {
             return;
         }
 
-        let current_index = self.focused
+        // Without focus, Tab starts at the first element
+        let next_index = self.focused
             .and_then(|f| self.tab_order.iter().position(|&e| e == f))
-            .unwrap_or(0);
-
-        let next_index = (current_index + 1) % self.tab_order.len();
+            .map_or(0, |current_index| (current_index + 1) % self.tab_order.len());
         self.set_focus(Some(self.tab_order[next_index]));
     }
 
@@ -218,14 +246,9 @@ impl FocusManager {
             return;
         }
 
-        let current_index = self.focused
-            .and_then(|f| self.tab_order.iter().position(|&e| e == f))
-            .unwrap_or(0);
-
-        let prev_index = if current_index == 0 {
-            self.tab_order.len() - 1
-        } else {
-            current_index - 1
+        let prev_index = match self.focused.and_then(|f| self.tab_order.iter().position(|&e| e == f)) {
+            Some(current_index) if current_index > 0 => current_index - 1,
+            _ => self.tab_order.len() - 1,
         };
 
         self.set_focus(Some(self.tab_order[prev_index]));
@@ -237,6 +260,12 @@ impl FocusManager {
         }
     }
 
+    /// Replace the tab order, e.g. with the focusable elements of the
+    /// latest build
+    pub fn set_tab_order(&mut self, order: Vec<ElementId>) {
+        self.tab_order = order;
+    }
+
     pub fn unregister_focusable(&mut self, element: ElementId) {
         self.tab_order.retain(|&e| e != element);
         if self.focused == Some(element) {
@@ -305,6 +334,16 @@ impl InputMethodManager {
     pub fn get_active_input(&self) -> Option<ElementId> {
         self.active_input
     }
+
+    /// Route the composition to `element`, the field receiving IME events
+    pub fn set_active_input(&mut self, element: Option<ElementId>) {
+        self.active_input = element;
+    }
+
+    /// Cursor or selection within the composition, as a byte range
+    pub fn get_composition_range(&self) -> Option<(usize, usize)> {
+        self.composition_range
+    }
 }
 
 impl Default for InputMethodManager {
@@ -317,6 +356,12 @@ impl Default for InputMethodManager {
 pub struct AccessibilityManager {
     labels: HashMap<ElementId, String>,
     roles: HashMap<ElementId, AccessibilityRole>,
+    values: HashMap<ElementId, String>,
+    hints: HashMap<ElementId, String>,
+    /// Live regions and what was last announced for each
+    live_regions: HashMap<ElementId, (LiveRegion, String)>,
+    /// Elements whose entries came from `sync_from_tree`
+    declared: HashSet<ElementId>,
     screen_reader_enabled: bool,
 }
 
@@ -333,6 +378,7 @@ pub enum AccessibilityRole {
     List,
     ListItem,
     Heading,
+    Table,
 }
 
 impl AccessibilityManager {
@@ -340,6 +386,10 @@ impl AccessibilityManager {
         Self {
             labels: HashMap::new(),
             roles: HashMap::new(),
+            values: HashMap::new(),
+            hints: HashMap::new(),
+            live_regions: HashMap::new(),
+            declared: HashSet::new(),
             screen_reader_enabled: Self::detect_screen_reader(),
         }
     }
@@ -385,6 +435,80 @@ impl AccessibilityManager {
         self.roles.get(&element).copied()
     }
 
+    pub fn set_value(&mut self, element: ElementId, value: String) {
+        self.values.insert(element, value);
+    }
+
+    pub fn get_value(&self, element: ElementId) -> Option<&str> {
+        self.values.get(&element).map(|s| s.as_str())
+    }
+
+    pub fn set_hint(&mut self, element: ElementId, hint: String) {
+        self.hints.insert(element, hint);
+    }
+
+    pub fn get_hint(&self, element: ElementId) -> Option<&str> {
+        self.hints.get(&element).map(|s| s.as_str())
+    }
+
+    pub fn get_live_region(&self, element: ElementId) -> Option<LiveRegion> {
+        self.live_regions.get(&element).map(|(region, _)| *region)
+    }
+
+    /// Import `Semantics` annotations from the element tree, replacing what
+    /// the previous sync declared. Live regions whose text changed since the
+    /// last sync are announced; entries set imperatively are left alone.
+    pub fn sync_from_tree(&mut self, tree: &ElementTree) {
+        let mut declared = HashSet::new();
+        let mut announcements = Vec::new();
+        for element in tree.iter() {
+            let Some(semantics) = &element.semantics else {
+                continue;
+            };
+            declared.insert(element.id);
+            self.apply(element.id, semantics, &mut announcements);
+        }
+
+        for stale in self.declared.difference(&declared) {
+            self.labels.remove(stale);
+            self.roles.remove(stale);
+            self.values.remove(stale);
+            self.hints.remove(stale);
+            self.live_regions.remove(stale);
+        }
+        self.declared = declared;
+
+        // Assertive regions go first
+        announcements.sort_by_key(|(region, _)| *region != LiveRegion::Assertive);
+        for (_, message) in announcements {
+            self.announce(&message);
+        }
+    }
+
+    fn apply(&mut self, id: ElementId, semantics: &SemanticsData, announcements: &mut Vec<(LiveRegion, String)>) {
+        fn update<T>(map: &mut HashMap<ElementId, T>, id: ElementId, value: Option<T>) {
+            match value {
+                Some(value) => map.insert(id, value),
+                None => map.remove(&id),
+            };
+        }
+        update(&mut self.labels, id, semantics.label.clone());
+        update(&mut self.roles, id, semantics.role);
+        update(&mut self.values, id, semantics.value.clone());
+        update(&mut self.hints, id, semantics.hint.clone());
+
+        let Some(region) = semantics.live_region else {
+            self.live_regions.remove(&id);
+            return;
+        };
+        let text = semantics.announcement();
+        let previous = self.live_regions.insert(id, (region, text.clone()));
+        // The first sync only records the initial text
+        if previous.is_some_and(|(_, spoken)| spoken != text) && !text.is_empty() {
+            announcements.push((region, text));
+        }
+    }
+
     pub fn is_screen_reader_enabled(&self) -> bool {
         self.screen_reader_enabled
     }
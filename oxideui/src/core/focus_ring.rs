@@ -0,0 +1,122 @@
+//! Focus ring drawn around the focused element
+//!
+//! Follows the `:focus-visible` convention: the runtime only draws the ring
+//! when focus arrived by keyboard (see `EventDispatcher::focus_visible`), so
+//! clicking a button doesn't leave a ring behind.
+
+use crate::core::context::Theme;
+use crate::core::render_object::{Color, Rect, RenderObject};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusRing {
+    /// Ring color; `None` uses the theme's `ring` color
+    pub color: Option<Color>,
+    pub width: f32,
+    /// Gap between the element's bounds and the inside of the ring
+    pub offset: f32,
+    /// Corner radius of the ring's outer edge
+    pub radius: f32,
+}
+
+impl FocusRing {
+    pub fn new() -> Self {
+        Self {
+            color: None,
+            width: 2.0,
+            offset: 2.0,
+            radius: 6.0,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// The ring around `bounds` (window coordinates), drawn in the overlay
+    /// pass so ancestor clips don't cut it off
+    pub fn render(&self, bounds: Rect, theme: &Theme) -> RenderObject {
+        // Stroke along the middle of the ring so its inner edge sits at `offset`
+        let inset = self.offset + self.width / 2.0;
+        let rect = Rect::new(
+            bounds.x - inset,
+            bounds.y - inset,
+            bounds.width + inset * 2.0,
+            bounds.height + inset * 2.0,
+        );
+        let radius = (self.radius - self.width / 2.0).clamp(0.0, rect.width.min(rect.height) / 2.0);
+        let outer = Rect::new(
+            rect.x - self.width / 2.0,
+            rect.y - self.width / 2.0,
+            rect.width + self.width,
+            rect.height + self.width,
+        );
+        RenderObject::overlay(RenderObject::stroke_path(
+            rounded_rect_path(rect, radius),
+            outer,
+            self.color.unwrap_or(theme.ring),
+            self.width,
+        ))
+    }
+}
+
+impl Default for FocusRing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn rounded_rect_path(rect: Rect, r: f32) -> String {
+    let (x, y, right, bottom) = (rect.x, rect.y, rect.x + rect.width, rect.y + rect.height);
+    format!(
+        "M{} {}H{}A{r} {r} 0 0 1 {} {}V{}A{r} {r} 0 0 1 {} {}H{}A{r} {r} 0 0 1 {} {}V{}A{r} {r} 0 0 1 {} {}Z",
+        x + r,
+        y,
+        right - r,
+        right,
+        y + r,
+        bottom - r,
+        right - r,
+        bottom,
+        x + r,
+        x,
+        bottom - r,
+        y + r,
+        x + r,
+        y,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_surrounds_bounds() {
+        let ring = FocusRing::new().with_width(2.0).with_offset(3.0).with_color(Color::BLACK);
+        let RenderObject::Overlay { child } = ring.render(Rect::new(10.0, 10.0, 100.0, 40.0), &Theme::default()) else {
+            panic!("focus ring should draw in the overlay pass");
+        };
+        let RenderObject::Path { bounds, paint, stroke, .. } = *child else {
+            panic!("focus ring should be a path");
+        };
+        assert!(stroke);
+        assert_eq!(paint.color, Color::BLACK);
+        assert_eq!(bounds, Rect::new(5.0, 5.0, 110.0, 50.0));
+    }
+}
@@ -1,10 +1,15 @@
+mod color;
 pub mod context;
 pub mod element;
 pub mod event;
 pub mod event_dispatcher;
 mod event_system;
+mod focus_ring;
 pub mod reconcile;
 pub mod render_object;
+mod render_pool;
+mod semantics;
+mod shortcut;
 pub(crate) mod state_driven;
 pub mod widget;
 
@@ -14,13 +19,18 @@ pub use crate::core::event_system::{
 };
 pub use crate::core::state_driven::{
     DerivedState, EffectRunner, ReactiveState, StateBatch, StateChange, StateToken, StateTracker,
+    Trackable,
 };
-pub use context::{BuildContext, Theme};
+pub use context::{BuildContext, InheritedTextStyle, Theme};
 pub use element::{Element, ElementId, ElementTree, SharedElementTree, new_shared_element_tree};
 pub use event::{
-    EventContext, EventPath, EventPhase, EventResult, Modifiers, MouseButton, UiEvent, Vector2,
+    EventContext, EventPath, EventPhase, EventResult, Modifiers, MouseButton, PointerCaptureRequest,
+    UiEvent, Vector2,
 };
-pub use event_dispatcher::EventDispatcher;
+pub use event_dispatcher::{EventDispatcher, FocusOrigin};
+pub use focus_ring::FocusRing;
 pub use reconcile::Reconciler;
-pub use render_object::{Color, Matrix, Paint, Point, Rect, RenderObject, TextStyle};
+pub use semantics::{LiveRegion, SemanticsData};
+pub use shortcut::{Shortcut, ShortcutKey};
+pub use render_object::{ClipShape, Color, ImageData, ImageFit, Matrix, Paint, Point, Rect, RenderEffect, RenderObject, TextAlign, TextDecoration, TextStyle};
 pub use widget::{StatefulWidget, StatelessWidget, Widget, WidgetKey, WidgetNode, WidgetState};
@@ -4,6 +4,7 @@
 //! the element tree by reusing elements where possible.
 
 use std::any::TypeId;
+use std::collections::HashMap;
 
 use crate::core::element::{ElementId, ElementTree};
 use crate::core::widget::{Widget, WidgetKey};
@@ -24,6 +25,7 @@ impl Reconciler {
         slot_index: usize,
         _theme: Arc<crate::core::context::Theme>,
     ) -> ElementId {
+        let _span = span!(DEBUG, "reconcile", widget = new_widget.debug_name());
         // Try to find an existing element we can reuse
         let existing = Self::find_reusable_element(element_tree, &*new_widget, parent, slot_index);
 
@@ -53,31 +55,34 @@ impl Reconciler {
     }
 
     /// Find an existing element that can be reused for this widget
+    ///
+    /// Keyed widgets match a sibling with the same key anywhere under the
+    /// parent; unkeyed widgets match the element at the same slot.
     fn find_reusable_element(
         element_tree: &ElementTree,
         new_widget: &dyn Widget,
         parent: Option<ElementId>,
         slot_index: usize,
     ) -> Option<ElementId> {
-        // If there's a parent, look through its children at the slot index
-        if let Some(parent_id) = parent {
-            if let Some(parent_element) = element_tree.get(parent_id) {
-                if slot_index < parent_element.children.len() {
-                    let child_id = parent_element.children[slot_index];
-                    if let Some(child_element) = element_tree.get(child_id) {
-                        if Self::can_update(
-                            child_element.widget_type,
-                            &child_element.key,
-                            new_widget,
-                        ) {
-                            return Some(child_id);
-                        }
-                    }
-                }
-            }
+        let parent_element = element_tree.get(parent?)?;
+
+        if let Some(key) = new_widget.key() {
+            return parent_element.children.iter().copied().find(|child_id| {
+                element_tree.get(*child_id).map_or(false, |child| {
+                    child.key.as_ref() == Some(&key)
+                        && Self::can_update(child.widget_type, &child.key, new_widget)
+                })
+            });
         }
 
-        None
+        // If there's a parent, look through its children at the slot index
+        let child_id = *parent_element.children.get(slot_index)?;
+        let child_element = element_tree.get(child_id)?;
+        if Self::can_update(child_element.widget_type, &child_element.key, new_widget) {
+            Some(child_id)
+        } else {
+            None
+        }
     }
 
     /// Update an existing element with a new widget
@@ -87,12 +92,30 @@ impl Reconciler {
         new_widget: Box<dyn Widget>,
     ) {
         if let Some(element) = element_tree.get_mut(element_id) {
-            element.dirty = true;
+            let needs_rebuild = match &element.widget {
+                Some(old_widget) => new_widget.should_rebuild(&**old_widget),
+                None => true,
+            };
+            if needs_rebuild {
+                element.dirty = true;
+            }
             element.widget_type = new_widget.type_id();
+            element.semantics = new_widget.semantics();
+            element.widget = Some(new_widget);
             // State is preserved automatically
         }
     }
 
+    /// Move a reused element to a new slot under its parent
+    fn move_element(element_tree: &mut ElementTree, element_id: ElementId, slot_index: usize) {
+        if let Some(element) = element_tree.get_mut(element_id) {
+            if element.slot_index != slot_index {
+                element.slot_index = slot_index;
+                element.dirty = true;
+            }
+        }
+    }
+
     /// Mount a new element for a widget
     fn mount_element(
         element_tree: &mut ElementTree,
@@ -125,25 +148,59 @@ impl Reconciler {
     }
 
     /// Reconcile a list of children
+    ///
+    /// Keyed children are matched by key regardless of position, so a
+    /// reordered keyed child keeps its element (and with it state, focus and
+    /// scroll position). Unkeyed children are matched by slot among the old
+    /// unkeyed children.
     pub fn reconcile_children(
         element_tree: &mut ElementTree,
         parent_id: ElementId,
         new_children: Vec<Box<dyn Widget>>,
-        theme: Arc<crate::core::context::Theme>,
+        _theme: Arc<crate::core::context::Theme>,
     ) {
         // Get current children
         let old_children = element_tree.get_children(parent_id);
 
+        // Index old keyed children; unkeyed ones match in order among
+        // themselves, so keyed siblings moving around don't shift them
+        let mut old_keyed: HashMap<WidgetKey, ElementId> = HashMap::new();
+        let mut old_unkeyed: Vec<ElementId> = Vec::with_capacity(old_children.len());
+        for &child_id in &old_children {
+            match element_tree.get(child_id).and_then(|e| e.key.clone()) {
+                Some(key) => {
+                    old_keyed.insert(key, child_id);
+                }
+                None => old_unkeyed.push(child_id),
+            }
+        }
+
         // Reconcile each new child
-        let mut new_child_ids = Vec::new();
+        let mut new_child_ids = Vec::with_capacity(new_children.len());
+        let mut unkeyed_index = 0;
         for (index, child_widget) in new_children.into_iter().enumerate() {
-            let child_id = Self::reconcile(
-                element_tree,
-                child_widget,
-                Some(parent_id),
-                index,
-                theme.clone(),
-            );
+            let candidate = match child_widget.key() {
+                Some(key) => old_keyed.remove(&key),
+                None => {
+                    unkeyed_index += 1;
+                    old_unkeyed.get(unkeyed_index - 1).copied()
+                }
+            };
+
+            let reusable = candidate.filter(|id| {
+                element_tree.get(*id).map_or(false, |e| {
+                    Self::can_update(e.widget_type, &e.key, &*child_widget)
+                })
+            });
+
+            let child_id = match reusable {
+                Some(element_id) => {
+                    Self::move_element(element_tree, element_id, index);
+                    Self::update_element(element_tree, element_id, child_widget);
+                    element_id
+                }
+                None => Self::mount_element(element_tree, child_widget, Some(parent_id), index),
+            };
             new_child_ids.push(child_id);
         }
 
@@ -219,4 +276,63 @@ mod tests {
             &widget2
         ));
     }
+
+    #[test]
+    fn test_keyed_reorder_preserves_elements() {
+        let mut tree = ElementTree::new();
+        let theme = Arc::new(crate::core::context::Theme::default());
+        let parent = tree.create_element(&TestWidget { key: None }, None, 0);
+
+        let keyed = |k: &str| -> Box<dyn Widget> {
+            Box::new(TestWidget {
+                key: Some(WidgetKey::string(k)),
+            })
+        };
+
+        Reconciler::reconcile_children(&mut tree, parent, vec![keyed("a"), keyed("b"), keyed("c")], theme.clone());
+        let before = tree.get_children(parent);
+
+        Reconciler::reconcile_children(&mut tree, parent, vec![keyed("c"), keyed("a"), keyed("b")], theme.clone());
+        let after = tree.get_children(parent);
+
+        assert_eq!(after, vec![before[2], before[0], before[1]]);
+        assert_eq!(tree.get(before[2]).unwrap().slot_index, 0);
+
+        Reconciler::reconcile_children(&mut tree, parent, vec![keyed("a")], theme);
+        assert_eq!(tree.get_children(parent), vec![before[0]]);
+        assert!(tree.get(before[1]).is_none());
+    }
+
+    #[test]
+    fn test_unkeyed_children_match_in_order_among_keyed_ones() {
+        let mut tree = ElementTree::new();
+        let theme = Arc::new(crate::core::context::Theme::default());
+        let parent = tree.create_element(&TestWidget { key: None }, None, 0);
+
+        let keyed = |k: &str| -> Box<dyn Widget> {
+            Box::new(TestWidget {
+                key: Some(WidgetKey::string(k)),
+            })
+        };
+        let unkeyed = || -> Box<dyn Widget> { Box::new(TestWidget { key: None }) };
+
+        Reconciler::reconcile_children(&mut tree, parent, vec![keyed("a"), unkeyed(), unkeyed()], theme.clone());
+        let before = tree.get_children(parent);
+
+        // A keyed child inserted first shifts every slot, but the unkeyed
+        // children are still the first and second unkeyed ones
+        Reconciler::reconcile_children(
+            &mut tree,
+            parent,
+            vec![keyed("b"), keyed("a"), unkeyed(), unkeyed()],
+            theme.clone(),
+        );
+        let after = tree.get_children(parent);
+        assert_eq!(&after[1..], &[before[0], before[1], before[2]]);
+
+        // Dropping the keyed children keeps the unkeyed ones
+        Reconciler::reconcile_children(&mut tree, parent, vec![unkeyed(), unkeyed()], theme);
+        assert_eq!(tree.get_children(parent), vec![before[1], before[2]]);
+        assert!(tree.get(before[0]).is_none());
+    }
 }
@@ -1,4 +1,10 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::core::element::ElementId;
+use crate::core::render_pool;
 use crate::layout::constraints::Size;
+use serde::{Deserialize, Serialize};
 
 /// A color in RGBA format
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -40,7 +46,7 @@ impl Color {
 }
 
 /// 2D point
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -81,12 +87,82 @@ impl Rect {
         x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
     }
 
+    /// Smallest rect containing both
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    /// Overlap of both, or `None` if they don't meet
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        (right >= x && bottom >= y).then(|| Rect::new(x, y, right - x, bottom - y))
+    }
+
     /// Convert to skia_safe::Rect
+    #[cfg(feature = "skia")]
     pub fn to_skia_rect(&self) -> skia_safe::Rect {
         skia_safe::Rect::from_xywh(self.x, self.y, self.width, self.height)
     }
 }
 
+/// Horizontal alignment of lines within a text block
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch every line but the last to the full width
+    Justify,
+}
+
+/// Lines drawn through or under text
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub strikethrough: bool,
+    /// Defaults to the text color
+    pub color: Option<Color>,
+}
+
+impl TextDecoration {
+    pub const NONE: TextDecoration = TextDecoration {
+        underline: false,
+        strikethrough: false,
+        color: None,
+    };
+
+    pub const fn underline() -> Self {
+        Self {
+            underline: true,
+            ..Self::NONE
+        }
+    }
+
+    pub const fn strikethrough() -> Self {
+        Self {
+            strikethrough: true,
+            ..Self::NONE
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn is_none(&self) -> bool {
+        !self.underline && !self.strikethrough
+    }
+}
+
 /// Text style configuration
 #[derive(Clone, Debug, PartialEq)]
 pub struct TextStyle {
@@ -95,6 +171,12 @@ pub struct TextStyle {
     pub color: Color,
     pub bold: bool,
     pub italic: bool,
+    /// Line height as a multiple of `font_size`; `None` uses 1.2
+    pub line_height: Option<f32>,
+    /// Extra space after each character, in logical pixels
+    pub letter_spacing: f32,
+    pub decoration: TextDecoration,
+    pub align: TextAlign,
 }
 
 impl Default for TextStyle {
@@ -105,6 +187,10 @@ impl Default for TextStyle {
             color: Color::BLACK,
             bold: false,
             italic: false,
+            line_height: None,
+            letter_spacing: 0.0,
+            decoration: TextDecoration::NONE,
+            align: TextAlign::Left,
         }
     }
 }
@@ -151,6 +237,105 @@ impl Matrix {
             values: [[sx, 0.0, 0.0], [0.0, sy, 0.0], [0.0, 0.0, 1.0]],
         }
     }
+
+    /// Counter-clockwise rotation by `radians` in a y-down space, which
+    /// turns clockwise on screen
+    pub fn rotate(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self {
+            values: [[cos, -sin, 0.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /// Shear by the tangents of the angles `x` and `y`, in radians
+    pub fn skew(x: f32, y: f32) -> Self {
+        Self {
+            values: [[1.0, x.tan(), 0.0], [y.tan(), 1.0, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    /// This transform applied around `origin` instead of (0, 0)
+    pub fn around(&self, origin: Point) -> Matrix {
+        Matrix::translate(origin.x, origin.y)
+            .concat(self)
+            .concat(&Matrix::translate(-origin.x, -origin.y))
+    }
+
+    /// `self * other` - applies `other` first, then `self`
+    pub fn concat(&self, other: &Matrix) -> Matrix {
+        let mut values = [[0.0; 3]; 3];
+        for (row, out) in values.iter_mut().enumerate() {
+            for (col, value) in out.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.values[row][k] * other.values[k][col]).sum();
+            }
+        }
+        Matrix { values }
+    }
+
+    /// Inverse matrix, or `None` if the matrix is singular
+    pub fn invert(&self) -> Option<Matrix> {
+        let m = &self.values;
+        let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let inv = 1.0 / det;
+        Some(Matrix {
+            values: [
+                [
+                    (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv,
+                    (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv,
+                    (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv,
+                ],
+                [
+                    (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv,
+                    (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv,
+                    (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv,
+                ],
+                [
+                    (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv,
+                    (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv,
+                    (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv,
+                ],
+            ],
+        })
+    }
+
+    pub fn map_point(&self, point: Point) -> Point {
+        let m = &self.values;
+        let x = m[0][0] * point.x + m[0][1] * point.y + m[0][2];
+        let y = m[1][0] * point.x + m[1][1] * point.y + m[1][2];
+        let w = m[2][0] * point.x + m[2][1] * point.y + m[2][2];
+        if w != 0.0 && w != 1.0 {
+            Point::new(x / w, y / w)
+        } else {
+            Point::new(x, y)
+        }
+    }
+
+    /// Map a direction or distance: the linear part only, no translation
+    pub fn map_vector(&self, x: f32, y: f32) -> (f32, f32) {
+        let m = &self.values;
+        (m[0][0] * x + m[0][1] * y, m[1][0] * x + m[1][1] * y)
+    }
+
+    /// Axis-aligned bounds of `rect` after mapping
+    pub fn map_rect(&self, rect: Rect) -> Rect {
+        let corners = [
+            self.map_point(Point::new(rect.x, rect.y)),
+            self.map_point(Point::new(rect.x + rect.width, rect.y)),
+            self.map_point(Point::new(rect.x, rect.y + rect.height)),
+            self.map_point(Point::new(rect.x + rect.width, rect.y + rect.height)),
+        ];
+        let min_x = corners.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+        let min_y = corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+        let max_x = corners.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+        let max_y = corners.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+        Rect::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
 }
 
 impl Default for Matrix {
@@ -159,15 +344,264 @@ impl Default for Matrix {
     }
 }
 
+/// Encoded image file contents (PNG, JPEG, WebP, ...), decoded by the backend
+/// when drawn. Clones share the bytes and compare equal.
+#[derive(Clone)]
+pub struct ImageData {
+    id: u64,
+    bytes: Arc<[u8]>,
+}
+
+impl ImageData {
+    pub fn from_encoded(bytes: impl Into<Arc<[u8]>>) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Identifies these bytes for backends caching the decoded image
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl PartialEq for ImageData {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl std::fmt::Debug for ImageData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ImageData(#{}, {} bytes)", self.id, self.bytes.len())
+    }
+}
+
+/// How an image is scaled into its box
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ImageFit {
+    /// Stretch to the box, ignoring the aspect ratio
+    Fill,
+    /// Scale to fit inside the box
+    #[default]
+    Contain,
+    /// Scale to cover the box, cropping the overflow
+    Cover,
+    /// Like `Contain`, but never scale up
+    ScaleDown,
+}
+
+/// Filters applied to a rendered subtree as a whole
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderEffect {
+    /// Gaussian blur sigma in logical pixels; 0 for none
+    pub blur: f32,
+    /// 0 is grayscale, 1 unchanged, above 1 oversaturated
+    pub saturation: f32,
+    /// Multiplies each color channel; 1 is unchanged
+    pub brightness: f32,
+    /// Blended over the content where it is opaque, by the tint's alpha
+    pub tint: Option<Color>,
+}
+
+impl Default for RenderEffect {
+    fn default() -> Self {
+        Self {
+            blur: 0.0,
+            saturation: 1.0,
+            brightness: 1.0,
+            tint: None,
+        }
+    }
+}
+
+impl RenderEffect {
+    pub fn blur(sigma: f32) -> Self {
+        Self::default().with_blur(sigma)
+    }
+
+    pub fn grayscale() -> Self {
+        Self::default().with_saturation(0.0)
+    }
+
+    pub fn tint(color: Color) -> Self {
+        Self::default().with_tint(color)
+    }
+
+    pub fn with_blur(mut self, sigma: f32) -> Self {
+        self.blur = sigma.max(0.0);
+        self
+    }
+
+    pub fn with_saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation.max(0.0);
+        self
+    }
+
+    pub fn with_brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness.max(0.0);
+        self
+    }
+
+    pub fn with_tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+
+    /// True when drawing through the effect changes nothing
+    pub fn is_identity(&self) -> bool {
+        self.blur == 0.0
+            && self.saturation == 1.0
+            && self.brightness == 1.0
+            && self.tint.map_or(true, |tint| tint.a == 0)
+    }
+
+    /// Saturation and brightness as a row-major 4x5 color matrix over
+    /// normalized RGBA, or `None` when they leave colors unchanged
+    pub fn color_matrix(&self) -> Option<[f32; 20]> {
+        if self.saturation == 1.0 && self.brightness == 1.0 {
+            return None;
+        }
+        // Rec. 709 luma weights
+        const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+        let s = self.saturation;
+        let b = self.brightness;
+        let mut matrix = [0.0; 20];
+        for row in 0..3 {
+            for column in 0..3 {
+                let identity = if row == column { s } else { 0.0 };
+                matrix[row * 5 + column] = (LUMA[column] * (1.0 - s) + identity) * b;
+            }
+        }
+        matrix[18] = 1.0;
+        Some(matrix)
+    }
+}
+
+/// Non-rectangular clip region, in the clipped content's coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClipShape {
+    /// Corner radii clockwise from the top left
+    RoundedRect { rect: Rect, radii: [f32; 4] },
+    /// Ellipse inscribed in `rect`
+    Oval(Rect),
+    /// SVG path data, filled with the non-zero rule; `bounds` is its extent
+    Path { data: Arc<str>, bounds: Rect },
+}
+
+impl ClipShape {
+    pub fn rounded(rect: Rect, radius: f32) -> Self {
+        ClipShape::RoundedRect { rect, radii: [radius; 4] }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        match self {
+            ClipShape::RoundedRect { rect, .. } | ClipShape::Oval(rect) => *rect,
+            ClipShape::Path { bounds, .. } => *bounds,
+        }
+    }
+
+    /// Whether `point` is inside the shape. Paths are tested precisely with
+    /// Skia and against their bounds otherwise.
+    pub fn contains(&self, point: Point) -> bool {
+        match self {
+            ClipShape::RoundedRect { rect, radii } => {
+                if !rect.contains(point.x, point.y) {
+                    return false;
+                }
+                // Radii can't exceed half the shorter side
+                let limit = rect.width.min(rect.height) / 2.0;
+                let right = point.x > rect.x + rect.width / 2.0;
+                let bottom = point.y > rect.y + rect.height / 2.0;
+                let radius = match (right, bottom) {
+                    (false, false) => radii[0],
+                    (true, false) => radii[1],
+                    (true, true) => radii[2],
+                    (false, true) => radii[3],
+                }
+                .clamp(0.0, limit);
+                if radius == 0.0 {
+                    return true;
+                }
+                // Outside a corner square the point is inside; within one it
+                // must be inside the corner's circle
+                let cx = if right { rect.x + rect.width - radius } else { rect.x + radius };
+                let cy = if bottom { rect.y + rect.height - radius } else { rect.y + radius };
+                let dx = if right { (point.x - cx).max(0.0) } else { (cx - point.x).max(0.0) };
+                let dy = if bottom { (point.y - cy).max(0.0) } else { (cy - point.y).max(0.0) };
+                dx * dx + dy * dy <= radius * radius
+            }
+            ClipShape::Oval(rect) => {
+                if rect.width <= 0.0 || rect.height <= 0.0 {
+                    return false;
+                }
+                let dx = (point.x - (rect.x + rect.width / 2.0)) / (rect.width / 2.0);
+                let dy = (point.y - (rect.y + rect.height / 2.0)) / (rect.height / 2.0);
+                dx * dx + dy * dy <= 1.0
+            }
+            ClipShape::Path { data, bounds } => {
+                if !bounds.contains(point.x, point.y) {
+                    return false;
+                }
+                #[cfg(feature = "skia")]
+                if let Some(path) = skia_safe::Path::from_svg(data) {
+                    return path.contains((point.x, point.y));
+                }
+                #[cfg(not(feature = "skia"))]
+                let _ = data;
+                true
+            }
+        }
+    }
+}
+
 /// Backend-agnostic rendering primitives
 #[derive(Clone, Debug, PartialEq)]
 pub enum RenderObject {
     Rect { rect: Rect, paint: Paint },
-    Text { content: String, style: TextStyle, position: Point },
-    Image { size: Size },
+    /// `width` is the box the text is aligned within per `style.align`;
+    /// None aligns lines against the widest one
+    Text { content: String, style: TextStyle, position: Point, width: Option<f32> },
+    /// `data` drawn into `size` by `fit`; a placeholder box when `None`
+    Image { size: Size, data: Option<ImageData>, fit: ImageFit },
     Clip { rect: Rect, child: Box<RenderObject> },
+    /// `child` clipped to a rounded rectangle, oval or path, anti-aliased
+    ClipShape { shape: ClipShape, child: Box<RenderObject> },
     Transform { matrix: Matrix, child: Box<RenderObject> },
     Group { children: Vec<RenderObject> },
+    /// Vector shape in SVG path syntax (`"M4 12h16"`), stroked with
+    /// `paint.stroke_width` or filled; `bounds` is its extent for culling
+    /// and hit-testing
+    Path { data: Arc<str>, bounds: Rect, paint: Paint, stroke: bool },
+    /// `child` composited as a whole at `alpha` (0 to 1), so overlapping
+    /// children don't show through each other
+    Opacity { alpha: f32, child: Box<RenderObject> },
+    /// `child` drawn into a layer filtered by `effect`; backends without
+    /// image filters draw the child unfiltered
+    Effect { effect: RenderEffect, child: Box<RenderObject> },
+    /// Content already painted under `rect` is filtered by `filter` (frosted
+    /// glass) before `child` draws on top. Backends without backdrop
+    /// filters fill `rect` with `fallback` instead.
+    Backdrop { rect: Rect, filter: RenderEffect, fallback: Color, child: Box<RenderObject> },
+    /// Drawn in the overlay pass above all other content, outside any
+    /// ancestor clip but still positioned by ancestor transforms
+    Overlay { child: Box<RenderObject> },
+    /// Scrolled content clipped to `rect`; `Sticky` descendants pin to the
+    /// top of the nearest viewport
+    Viewport { rect: Rect, child: Box<RenderObject> },
+    /// Header laid out at the top of `section` that stays at the top of the
+    /// viewport while the section is in view, until the section's bottom
+    /// pushes it out
+    Sticky { section: Rect, child: Box<RenderObject> },
+    /// What element `id` built. Paints `child` unchanged; the layout pass
+    /// reads it to record where the element ended up in the window.
+    Element { id: ElementId, child: Box<RenderObject> },
     None,
 }
 
@@ -183,24 +617,202 @@ impl RenderObject {
     }
 
     pub fn text(content: String, style: TextStyle, position: Point) -> Self {
-        RenderObject::Text { content, style, position }
+        RenderObject::Text { content, style, position, width: None }
+    }
+
+    /// Text aligned per `style.align` within a box `width` wide
+    pub fn aligned_text(content: String, style: TextStyle, position: Point, width: f32) -> Self {
+        RenderObject::Text { content, style, position, width: Some(width) }
     }
 
     pub fn transform(matrix: Matrix, child: RenderObject) -> Self {
         RenderObject::Transform {
             matrix,
-            child: Box::new(child),
+            child: render_pool::take_box(child),
         }
     }
 
     pub fn clip(rect: Rect, child: RenderObject) -> Self {
         RenderObject::Clip {
             rect,
-            child: Box::new(child),
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn clip_shape(shape: ClipShape, child: RenderObject) -> Self {
+        RenderObject::ClipShape {
+            shape,
+            child: render_pool::take_box(child),
         }
     }
 
     pub fn group(children: Vec<RenderObject>) -> Self {
         RenderObject::Group { children }
     }
-}
\ No newline at end of file
+
+    /// Approximate extent of what this object paints, in its own
+    /// coordinates, or `None` if it paints nothing. Text is estimated from
+    /// its length and font size; overlays don't count, as they are drawn
+    /// outside the layout.
+    pub fn bounds(&self) -> Option<Rect> {
+        match self {
+            RenderObject::Rect { rect, .. } => Some(*rect),
+            RenderObject::Text { content, style, position, width } => Some(Rect::new(
+                position.x,
+                position.y,
+                width.unwrap_or(content.chars().count() as f32 * style.font_size * 0.6),
+                style.font_size * 1.2,
+            )),
+            RenderObject::Image { size, .. } => Some(Rect::from_size(*size)),
+            RenderObject::Path { bounds, .. } => Some(*bounds),
+            RenderObject::Group { children } => children
+                .iter()
+                .filter_map(RenderObject::bounds)
+                .reduce(|a, b| a.union(&b)),
+            RenderObject::Transform { matrix, child } => child.bounds().map(|rect| matrix.map_rect(rect)),
+            RenderObject::Clip { rect, child } | RenderObject::Viewport { rect, child } => {
+                child.bounds()?.intersect(rect)
+            }
+            RenderObject::ClipShape { shape, child } => child.bounds()?.intersect(&shape.bounds()),
+            RenderObject::Backdrop { rect, child, .. } => {
+                Some(child.bounds().map_or(*rect, |bounds| bounds.union(rect)))
+            }
+            RenderObject::Opacity { child, .. }
+            | RenderObject::Effect { child, .. }
+            | RenderObject::Sticky { child, .. }
+            | RenderObject::Element { child, .. } => child.bounds(),
+            RenderObject::Overlay { .. } | RenderObject::None => None,
+        }
+    }
+
+    /// Baseline of the first line of text in this subtree, in its
+    /// coordinates, for aligning rows of mixed font sizes
+    pub fn baseline(&self) -> Option<f32> {
+        match self {
+            RenderObject::Text { style, position, .. } => Some(position.y + crate::render::text::baseline(style)),
+            RenderObject::Group { children } => children.iter().find_map(RenderObject::baseline),
+            RenderObject::Transform { matrix, child } => {
+                child.baseline().map(|baseline| matrix.map_point(Point::new(0.0, baseline)).y)
+            }
+            RenderObject::Clip { child, .. }
+            | RenderObject::ClipShape { child, .. }
+            | RenderObject::Backdrop { child, .. }
+            | RenderObject::Opacity { child, .. }
+            | RenderObject::Effect { child, .. }
+            | RenderObject::Viewport { child, .. }
+            | RenderObject::Sticky { child, .. }
+            | RenderObject::Element { child, .. } => child.baseline(),
+            _ => None,
+        }
+    }
+
+    /// Size a parent should reserve for this object placed at its origin:
+    /// from (0, 0) to the far edges of `bounds`
+    pub fn layout_size(&self) -> Size {
+        self.bounds().map_or(Size::zero(), |bounds| {
+            Size::new((bounds.x + bounds.width).max(0.0), (bounds.y + bounds.height).max(0.0))
+        })
+    }
+
+    /// Empty child list for `group`, reusing a buffer from a recycled frame
+    pub fn child_vec(capacity: usize) -> Vec<RenderObject> {
+        render_pool::take_vec(capacity)
+    }
+
+    /// Return this tree's buffers to the current thread's pool so the next
+    /// build can reuse them instead of allocating
+    pub fn recycle(self) {
+        render_pool::recycle(self)
+    }
+
+    pub fn stroke_path(data: impl Into<Arc<str>>, bounds: Rect, color: Color, width: f32) -> Self {
+        RenderObject::Path {
+            data: data.into(),
+            bounds,
+            paint: Paint {
+                color,
+                stroke_width: width,
+                anti_alias: true,
+            },
+            stroke: true,
+        }
+    }
+
+    pub fn fill_path(data: impl Into<Arc<str>>, bounds: Rect, color: Color) -> Self {
+        RenderObject::Path {
+            data: data.into(),
+            bounds,
+            paint: Paint {
+                color,
+                ..Default::default()
+            },
+            stroke: false,
+        }
+    }
+
+    pub fn opacity(alpha: f32, child: RenderObject) -> Self {
+        RenderObject::Opacity {
+            alpha: alpha.clamp(0.0, 1.0),
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn effect(effect: RenderEffect, child: RenderObject) -> Self {
+        RenderObject::Effect {
+            effect,
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn backdrop(rect: Rect, filter: RenderEffect, fallback: Color, child: RenderObject) -> Self {
+        RenderObject::Backdrop {
+            rect,
+            filter,
+            fallback,
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn overlay(child: RenderObject) -> Self {
+        RenderObject::Overlay {
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn viewport(rect: Rect, child: RenderObject) -> Self {
+        RenderObject::Viewport {
+            rect,
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn sticky(section: Rect, child: RenderObject) -> Self {
+        RenderObject::Sticky {
+            section,
+            child: render_pool::take_box(child),
+        }
+    }
+
+    pub fn element(id: ElementId, child: RenderObject) -> Self {
+        RenderObject::Element {
+            id,
+            child: render_pool::take_box(child),
+        }
+    }
+
+    /// `self` without the element markers around it
+    pub fn unmarked(&self) -> &RenderObject {
+        match self {
+            RenderObject::Element { child, .. } => child.unmarked(),
+            other => other,
+        }
+    }
+}
+
+/// How far the `header` of a `Sticky` moves down to stay at `viewport_top`,
+/// both in the header's coordinates: not at all while the top of `section`
+/// is in view, and never past the section's bottom
+pub fn sticky_offset(section: Rect, header: &RenderObject, viewport_top: f32) -> f32 {
+    let header_height = header.bounds().map_or(0.0, |bounds| bounds.height);
+    (viewport_top - section.y).min(section.height - header_height).max(0.0)
+}
@@ -0,0 +1,116 @@
+//! Per-thread free lists for render tree allocations
+//!
+//! Every frame rebuilds the render tree, which means thousands of small
+//! `Vec` and `Box` allocations freed again one frame later. The runtime hands
+//! the previous frame's tree to `RenderObject::recycle`, which keeps the
+//! emptied buffers here for `RenderObject::child_vec`, `transform` and `clip`
+//! to reuse during the next build.
+
+use std::cell::RefCell;
+
+use crate::core::render_object::RenderObject;
+
+/// Buffers of each kind kept per thread; covers a frame of several thousand
+/// widgets without hoarding memory after a one-off huge frame
+const MAX_POOLED: usize = 16384;
+
+/// Child lists bigger than this are freed rather than pooled
+const MAX_POOLED_CAPACITY: usize = 256;
+
+#[derive(Default)]
+struct Pool {
+    vecs: Vec<Vec<RenderObject>>,
+    /// Boxes holding `RenderObject::None`
+    boxes: Vec<Box<RenderObject>>,
+}
+
+thread_local! {
+    static POOL: RefCell<Pool> = RefCell::new(Pool::default());
+}
+
+pub(crate) fn take_vec(capacity: usize) -> Vec<RenderObject> {
+    match POOL.with(|pool| pool.borrow_mut().vecs.pop()) {
+        Some(mut children) => {
+            children.reserve(capacity);
+            children
+        }
+        None => Vec::with_capacity(capacity),
+    }
+}
+
+pub(crate) fn take_box(value: RenderObject) -> Box<RenderObject> {
+    match POOL.with(|pool| pool.borrow_mut().boxes.pop()) {
+        Some(mut boxed) => {
+            *boxed = value;
+            boxed
+        }
+        None => Box::new(value),
+    }
+}
+
+pub(crate) fn recycle(object: RenderObject) {
+    POOL.with(|pool| pool.borrow_mut().recycle(object));
+}
+
+impl Pool {
+    fn recycle(&mut self, object: RenderObject) {
+        match object {
+            RenderObject::Group { mut children } => {
+                for child in children.drain(..) {
+                    self.recycle(child);
+                }
+                if self.vecs.len() < MAX_POOLED && children.capacity() <= MAX_POOLED_CAPACITY {
+                    self.vecs.push(children);
+                }
+            }
+            RenderObject::Clip { child, .. }
+            | RenderObject::ClipShape { child, .. }
+            | RenderObject::Transform { child, .. }
+            | RenderObject::Opacity { child, .. }
+            | RenderObject::Effect { child, .. }
+            | RenderObject::Backdrop { child, .. }
+            | RenderObject::Overlay { child }
+            | RenderObject::Viewport { child, .. }
+            | RenderObject::Sticky { child, .. }
+            | RenderObject::Element { child, .. } => self.recycle_box(child),
+            _ => {}
+        }
+    }
+
+    fn recycle_box(&mut self, mut boxed: Box<RenderObject>) {
+        let child = std::mem::replace(&mut *boxed, RenderObject::None);
+        self.recycle(child);
+        if self.boxes.len() < MAX_POOLED {
+            self.boxes.push(boxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::render_object::{Color, Matrix, Rect, RenderObject};
+
+    #[test]
+    fn test_recycled_buffers_are_reused() {
+        let mut children = RenderObject::child_vec(4);
+        children.push(RenderObject::rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::WHITE));
+        let children_ptr = children.as_ptr();
+        let transformed = RenderObject::transform(Matrix::translate(5.0, 0.0), RenderObject::group(children));
+        let RenderObject::Transform { child, .. } = &transformed else {
+            unreachable!()
+        };
+        let box_ptr = &**child as *const RenderObject;
+
+        transformed.recycle();
+
+        let reused = RenderObject::transform(Matrix::identity(), RenderObject::None);
+        let RenderObject::Transform { child, .. } = &reused else {
+            unreachable!()
+        };
+        assert_eq!(&**child as *const RenderObject, box_ptr);
+
+        let reused_children = RenderObject::child_vec(1);
+        assert!(reused_children.is_empty());
+        assert_eq!(reused_children.as_ptr(), children_ptr);
+    }
+}
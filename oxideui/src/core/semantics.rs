@@ -0,0 +1,71 @@
+//! Declarative accessibility information
+//!
+//! Widgets describe themselves to assistive technology by returning
+//! `SemanticsData` from `Widget::semantics`, usually by wrapping a subtree in
+//! the `Semantics` widget. The element tree keeps the data on each element
+//! and `AccessibilityManager::sync_from_tree` exports it, so apps no longer
+//! have to look up element ids and call `set_label` by hand.
+
+use crate::core::event_system::AccessibilityRole;
+
+/// How eagerly screen readers announce changes to a live region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveRegion {
+    /// Announce when the user is idle
+    Polite,
+    /// Interrupt whatever is being read
+    Assertive,
+}
+
+/// What assistive technology should know about an element
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SemanticsData {
+    pub label: Option<String>,
+    pub role: Option<AccessibilityRole>,
+    /// Current value of sliders, progress bars and text fields
+    pub value: Option<String>,
+    /// What happens on activation, e.g. "Opens the settings dialog"
+    pub hint: Option<String>,
+    /// Announce label and value changes without moving focus
+    pub live_region: Option<LiveRegion>,
+}
+
+impl SemanticsData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_role(mut self, role: AccessibilityRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn with_live_region(mut self, live_region: LiveRegion) -> Self {
+        self.live_region = Some(live_region);
+        self
+    }
+
+    /// Text a screen reader speaks for this element
+    pub fn announcement(&self) -> String {
+        match (&self.label, &self.value) {
+            (Some(label), Some(value)) => format!("{}: {}", label, value),
+            (Some(text), None) | (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
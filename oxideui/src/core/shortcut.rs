@@ -0,0 +1,224 @@
+//! Keyboard shortcuts that work on any keyboard layout
+//!
+//! A character shortcut such as `Ctrl+Z` matches the key that types `z` in
+//! the active layout, so it follows the label on AZERTY and Dvorak keyboards.
+//! When the layout does not type Latin characters (Cyrillic, Greek, ...) it
+//! falls back to the physical key in the US position, as native apps do.
+//! Named keys (`Escape`, `F5`, arrows) always match the physical key.
+
+use std::fmt;
+
+use anyhow::{anyhow, bail, Result};
+use winit::keyboard::{Key, KeyCode};
+
+use crate::core::event::{Modifiers, UiEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShortcutKey {
+    /// A printable key, lowercase
+    Character(char),
+    /// A named or positional key
+    Code(KeyCode),
+}
+
+/// A key plus exact modifiers, parsed from strings like `"Ctrl+Shift+Z"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: ShortcutKey,
+}
+
+impl Shortcut {
+    pub fn new(modifiers: Modifiers, key: ShortcutKey) -> Self {
+        let key = match key {
+            ShortcutKey::Character(c) => ShortcutKey::Character(c.to_ascii_lowercase()),
+            other => other,
+        };
+        Self { modifiers, key }
+    }
+
+    /// Parse `"Ctrl+Z"`, `"CmdOrCtrl+Shift+K"`, `"Alt+F4"` or `"Escape"`.
+    ///
+    /// `CmdOrCtrl` is Cmd on macOS and Ctrl elsewhere.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut key = None;
+        for part in text.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" | "option" => modifiers.alt = true,
+                "cmd" | "meta" | "super" | "win" => modifiers.meta = true,
+                "cmdorctrl" | "commandorcontrol" => {
+                    if cfg!(target_os = "macos") {
+                        modifiers.meta = true;
+                    } else {
+                        modifiers.ctrl = true;
+                    }
+                }
+                _ if key.is_some() => bail!("Shortcut {:?} has more than one key", text),
+                _ => key = Some(parse_key(part).ok_or_else(|| anyhow!("Unknown key {:?} in shortcut {:?}", part, text))?),
+            }
+        }
+        let key = key.ok_or_else(|| anyhow!("Shortcut {:?} has no key", text))?;
+        Ok(Self::new(modifiers, key))
+    }
+
+    /// Whether `event` is a key press of this shortcut
+    pub fn matches(&self, event: &UiEvent) -> bool {
+        let UiEvent::KeyDown { key, logical_key, modifiers, .. } = event else {
+            return false;
+        };
+        if *modifiers != self.modifiers {
+            return false;
+        }
+        match self.key {
+            ShortcutKey::Code(code) => *key == code,
+            ShortcutKey::Character(c) => match logical_key {
+                Key::Character(typed) if typed.is_ascii() => {
+                    typed.chars().count() == 1 && typed.eq_ignore_ascii_case(c.encode_utf8(&mut [0; 4]))
+                }
+                _ => us_layout_code(c) == Some(*key),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in [
+            (self.modifiers.ctrl, "Ctrl"),
+            (self.modifiers.alt, "Alt"),
+            (self.modifiers.shift, "Shift"),
+            (self.modifiers.meta, if cfg!(target_os = "macos") { "Cmd" } else { "Super" }),
+        ] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match self.key {
+            ShortcutKey::Character(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            ShortcutKey::Code(code) => write!(f, "{:?}", code),
+        }
+    }
+}
+
+fn parse_key(name: &str) -> Option<ShortcutKey> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(ShortcutKey::Character(c.to_ascii_lowercase()));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "escape" | "esc" => KeyCode::Escape,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "space" => KeyCode::Space,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::ArrowUp,
+        "down" => KeyCode::ArrowDown,
+        "left" => KeyCode::ArrowLeft,
+        "right" => KeyCode::ArrowRight,
+        "f1" => KeyCode::F1,
+        "f2" => KeyCode::F2,
+        "f3" => KeyCode::F3,
+        "f4" => KeyCode::F4,
+        "f5" => KeyCode::F5,
+        "f6" => KeyCode::F6,
+        "f7" => KeyCode::F7,
+        "f8" => KeyCode::F8,
+        "f9" => KeyCode::F9,
+        "f10" => KeyCode::F10,
+        "f11" => KeyCode::F11,
+        "f12" => KeyCode::F12,
+        _ => return None,
+    };
+    Some(ShortcutKey::Code(code))
+}
+
+/// The physical key typing `c` on a US QWERTY keyboard
+fn us_layout_code(c: char) -> Option<KeyCode> {
+    Some(match c {
+        'a' => KeyCode::KeyA,
+        'b' => KeyCode::KeyB,
+        'c' => KeyCode::KeyC,
+        'd' => KeyCode::KeyD,
+        'e' => KeyCode::KeyE,
+        'f' => KeyCode::KeyF,
+        'g' => KeyCode::KeyG,
+        'h' => KeyCode::KeyH,
+        'i' => KeyCode::KeyI,
+        'j' => KeyCode::KeyJ,
+        'k' => KeyCode::KeyK,
+        'l' => KeyCode::KeyL,
+        'm' => KeyCode::KeyM,
+        'n' => KeyCode::KeyN,
+        'o' => KeyCode::KeyO,
+        'p' => KeyCode::KeyP,
+        'q' => KeyCode::KeyQ,
+        'r' => KeyCode::KeyR,
+        's' => KeyCode::KeyS,
+        't' => KeyCode::KeyT,
+        'u' => KeyCode::KeyU,
+        'v' => KeyCode::KeyV,
+        'w' => KeyCode::KeyW,
+        'x' => KeyCode::KeyX,
+        'y' => KeyCode::KeyY,
+        'z' => KeyCode::KeyZ,
+        '0' => KeyCode::Digit0,
+        '1' => KeyCode::Digit1,
+        '2' => KeyCode::Digit2,
+        '3' => KeyCode::Digit3,
+        '4' => KeyCode::Digit4,
+        '5' => KeyCode::Digit5,
+        '6' => KeyCode::Digit6,
+        '7' => KeyCode::Digit7,
+        '8' => KeyCode::Digit8,
+        '9' => KeyCode::Digit9,
+        '-' => KeyCode::Minus,
+        '=' => KeyCode::Equal,
+        '[' => KeyCode::BracketLeft,
+        ']' => KeyCode::BracketRight,
+        ';' => KeyCode::Semicolon,
+        '\'' => KeyCode::Quote,
+        ',' => KeyCode::Comma,
+        '.' => KeyCode::Period,
+        '/' => KeyCode::Slash,
+        '\\' => KeyCode::Backslash,
+        '`' => KeyCode::Backquote,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key: KeyCode, typed: &str) -> UiEvent {
+        UiEvent::KeyDown {
+            key,
+            logical_key: Key::Character(typed.into()),
+            modifiers: Modifiers { ctrl: true, ..Default::default() },
+            repeat: false,
+        }
+    }
+
+    #[test]
+    fn test_shortcut_follows_layout() {
+        let undo = Shortcut::parse("Ctrl+Z").unwrap();
+
+        // QWERTY, and AZERTY where the Z label sits on the W key
+        assert!(undo.matches(&press(KeyCode::KeyZ, "z")));
+        assert!(undo.matches(&press(KeyCode::KeyW, "z")));
+        assert!(!undo.matches(&press(KeyCode::KeyZ, "w")));
+
+        // Cyrillic layouts fall back to the physical position
+        assert!(undo.matches(&press(KeyCode::KeyZ, "я")));
+        assert!(!Shortcut::parse("Ctrl+Shift+Z").unwrap().matches(&press(KeyCode::KeyZ, "z")));
+    }
+}
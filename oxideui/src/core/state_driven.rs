@@ -2,9 +2,10 @@
 //! State-driven rebuild system with granular updates
 
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
-use parking_lot::RwLock;
-use crate::core::element::ElementId;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
+use parking_lot::{Mutex, RwLock};
+use crate::core::element::{ElementId, TreeId};
 
 /// State subscription token
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -62,6 +63,16 @@ impl StateTracker {
             .insert(token);
     }
 
+    /// Unsubscribe an element from a single state
+    pub fn unsubscribe(&self, element: ElementId, token: StateToken) {
+        if let Some(elements) = self.subscriptions.write().get_mut(&token) {
+            elements.remove(&element);
+        }
+        if let Some(tokens) = self.dependencies.write().get_mut(&element) {
+            tokens.remove(&token);
+        }
+    }
+
     /// Unsubscribe an element from all states
     pub fn unsubscribe_all(&self, element: ElementId) {
         let tokens = {
@@ -177,6 +188,16 @@ impl<T: Clone + Send + Sync + 'static> ReactiveState<T> {
     }
 }
 
+impl<T: Clone + Send + Sync + 'static> Trackable for ReactiveState<T> {
+    fn state_token(&self) -> StateToken {
+        self.token
+    }
+
+    fn state_tracker(&self) -> Arc<StateTracker> {
+        self.tracker.clone()
+    }
+}
+
 impl<T: Clone + Send + Sync + 'static> Clone for ReactiveState<T> {
     fn clone(&self) -> Self {
         Self {
@@ -232,6 +253,16 @@ impl<T: Clone + Send + Sync + 'static> DerivedState<T> {
     }
 }
 
+impl<T: Clone + Send + Sync + 'static> Trackable for DerivedState<T> {
+    fn state_token(&self) -> StateToken {
+        self.token
+    }
+
+    fn state_tracker(&self) -> Arc<StateTracker> {
+        self.tracker.clone()
+    }
+}
+
 /// Effect runner for side effects
 pub struct EffectRunner {
     effects: Arc<RwLock<Vec<Box<dyn Fn() + Send + Sync>>>>,
@@ -297,4 +328,228 @@ impl StateBatch {
             self.tracker.notify_change(token);
         }
     }
-}
\ No newline at end of file
+}
+
+/// State that can be observed by an element
+pub trait Trackable {
+    fn state_token(&self) -> StateToken;
+    fn state_tracker(&self) -> Arc<StateTracker>;
+}
+
+type Subscriptions = HashMap<(TreeId, ElementId), Vec<(Weak<StateTracker>, StateToken)>>;
+
+/// Auto-subscriptions made by each element's previous build, per tree
+fn auto_subscriptions() -> &'static Mutex<Subscriptions> {
+    static AUTO_SUBSCRIPTIONS: OnceLock<Mutex<Subscriptions>> = OnceLock::new();
+    AUTO_SUBSCRIPTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Unsubscribe `(tree, element)` from `token`, unless the element with the
+/// same id in another tree still reads it: trackers only know element ids
+fn unsubscribe(registry: &Subscriptions, (tree, element): (TreeId, ElementId), tracker: &StateTracker, token: StateToken) {
+    let shared = registry
+        .iter()
+        .any(|((other, id), reads)| *other != tree && *id == element && reads.iter().any(|(_, t)| *t == token));
+    if !shared {
+        tracker.unsubscribe(element, token);
+    }
+}
+
+/// Drop the auto-subscriptions of an element that was unmounted from `tree`
+pub(crate) fn release_subscriptions(tree: TreeId, element: ElementId) {
+    let mut registry = auto_subscriptions().lock();
+    let Some(previous) = registry.remove(&(tree, element)) else {
+        return;
+    };
+    for (tracker, token) in previous {
+        if let Some(tracker) = tracker.upgrade() {
+            unsubscribe(&registry, (tree, element), &tracker, token);
+        }
+    }
+}
+
+/// Drop the auto-subscriptions of every element of a tree going away
+pub(crate) fn release_tree(tree: TreeId) {
+    let mut registry = auto_subscriptions().lock();
+    let owners: Vec<_> = registry.keys().filter(|(owner, _)| *owner == tree).copied().collect();
+    for owner in owners {
+        for (tracker, token) in registry.remove(&owner).unwrap_or_default() {
+            if let Some(tracker) = tracker.upgrade() {
+                unsubscribe(&registry, owner, &tracker, token);
+            }
+        }
+    }
+}
+
+/// State reads recorded while an element builds
+///
+/// Reads subscribe the element immediately. When the last handle is dropped
+/// after the element's widget was built, subscriptions from the previous
+/// build that were not read again are removed. Contexts that never built the
+/// widget (measuring, layout cache hits) only add to them.
+pub(crate) struct BuildDependencies {
+    tree: TreeId,
+    element: ElementId,
+    reads: Mutex<Vec<(Arc<StateTracker>, StateToken)>>,
+    built: AtomicBool,
+}
+
+impl BuildDependencies {
+    pub(crate) fn new(tree: TreeId, element: ElementId) -> Self {
+        Self {
+            tree,
+            element,
+            reads: Mutex::new(Vec::new()),
+            built: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn tree(&self) -> TreeId {
+        self.tree
+    }
+
+    pub(crate) fn element(&self) -> ElementId {
+        self.element
+    }
+
+    /// The element's widget is being built with these dependencies, so
+    /// they replace the previous build's
+    pub(crate) fn mark_built(&self) {
+        self.built.store(true, Ordering::Relaxed);
+    }
+
+    /// Record a read and subscribe the building element
+    pub(crate) fn record(&self, state: &dyn Trackable) {
+        let token = state.state_token();
+        let mut reads = self.reads.lock();
+        if reads.iter().any(|(_, t)| *t == token) {
+            return;
+        }
+        let tracker = state.state_tracker();
+        tracker.subscribe(self.element, token);
+        reads.push((tracker, token));
+    }
+}
+
+impl Drop for BuildDependencies {
+    fn drop(&mut self) {
+        let reads = std::mem::take(self.reads.get_mut());
+        let owner = (self.tree, self.element);
+        let mut registry = auto_subscriptions().lock();
+
+        if !*self.built.get_mut() {
+            if !reads.is_empty() {
+                let previous = registry.entry(owner).or_default();
+                for (tracker, token) in &reads {
+                    if !previous.iter().any(|(_, t)| t == token) {
+                        previous.push((Arc::downgrade(tracker), *token));
+                    }
+                }
+            }
+            return;
+        }
+        if reads.is_empty() && !registry.contains_key(&owner) {
+            return;
+        }
+
+        let previous = registry.remove(&owner).unwrap_or_default();
+        for (tracker, token) in previous {
+            if reads.iter().any(|(_, t)| *t == token) {
+                continue;
+            }
+            if let Some(tracker) = tracker.upgrade() {
+                unsubscribe(&registry, owner, &tracker, token);
+            }
+        }
+
+        if !reads.is_empty() {
+            registry.insert(
+                owner,
+                reads.iter().map(|(tracker, token)| (Arc::downgrade(tracker), *token)).collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::ElementTree;
+
+    #[test]
+    fn test_stale_auto_subscriptions_are_dropped() {
+        let tracker = Arc::new(StateTracker::new());
+        let a = ReactiveState::new(1, tracker.clone());
+        let b = ReactiveState::new(2, tracker.clone());
+        let (tree, element) = (ElementTree::new().id(), ElementId::new(4242));
+
+        let deps = BuildDependencies::new(tree, element);
+        deps.mark_built();
+        deps.record(&a);
+        deps.record(&b);
+        drop(deps);
+
+        // Second build only reads `a`
+        let deps = BuildDependencies::new(tree, element);
+        deps.mark_built();
+        deps.record(&a);
+        drop(deps);
+
+        b.set(3);
+        assert!(!tracker.get_dirty_elements().contains(&element));
+        a.set(5);
+        assert!(tracker.get_dirty_elements().contains(&element));
+    }
+
+    #[test]
+    fn test_contexts_that_did_not_build_keep_subscriptions() {
+        let tracker = Arc::new(StateTracker::new());
+        let a = ReactiveState::new(1, tracker.clone());
+        let (tree, element) = (ElementTree::new().id(), ElementId::new(4343));
+
+        let deps = BuildDependencies::new(tree, element);
+        deps.mark_built();
+        deps.record(&a);
+        drop(deps);
+
+        // A context for the same element that was only used to measure, or
+        // whose output came from the layout cache
+        drop(BuildDependencies::new(tree, element));
+        a.set(2);
+        assert!(tracker.get_dirty_elements().contains(&element));
+
+        // Unmounting drops them
+        tracker.clear_dirty();
+        release_subscriptions(tree, element);
+        a.set(3);
+        assert!(!tracker.get_dirty_elements().contains(&element));
+        assert!(!auto_subscriptions().lock().contains_key(&(tree, element)));
+    }
+
+    #[test]
+    fn test_trees_keep_their_own_subscriptions() {
+        let tracker = Arc::new(StateTracker::new());
+        let a = ReactiveState::new(1, tracker.clone());
+        let b = ReactiveState::new(2, tracker.clone());
+        let (main, offscreen) = (ElementTree::new().id(), ElementTree::new().id());
+        let element = ElementId::new(4444);
+
+        let deps = BuildDependencies::new(main, element);
+        deps.mark_built();
+        deps.record(&a);
+        drop(deps);
+
+        // The same id in another tree reads something else, then goes away
+        let deps = BuildDependencies::new(offscreen, element);
+        deps.mark_built();
+        deps.record(&b);
+        deps.record(&a);
+        drop(deps);
+        release_subscriptions(offscreen, element);
+
+        a.set(2);
+        assert!(tracker.get_dirty_elements().contains(&element));
+        assert!(auto_subscriptions().lock().contains_key(&(main, element)));
+        release_subscriptions(main, element);
+    }
+}
@@ -11,6 +11,7 @@ use std::fmt;
 use crate::core::context::BuildContext;
 use crate::core::event::{EventContext, EventResult, UiEvent};
 use crate::core::render_object::RenderObject;
+use crate::core::semantics::SemanticsData;
 
 /// Unique identifier for widgets to aid in reconciliation
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -62,6 +63,42 @@ pub trait Widget: Send + Sync + 'static {
         EventResult::Unhandled // Default: don't handle events
     }
 
+    /// Whether this widget needs rebuilding when it replaces `old`
+    ///
+    /// Return false when the widget's inputs are unchanged so the element
+    /// keeps its previous output, children included, instead of rebuilding.
+    /// Only consulted while the constraints and inherited context are the
+    /// same as in the previous build.
+    fn should_rebuild(&self, _old: &dyn Widget) -> bool {
+        true
+    }
+
+    /// Hash of everything that affects this widget's layout and paint, so
+    /// `BuildContext::build_child` can reuse its previous render object.
+    ///
+    /// Return None (the default) for widgets that read state, animate, or
+    /// hold children that don't opt in themselves.
+    fn layout_hash(&self) -> Option<u64> {
+        None
+    }
+
+    /// Whether Tab can move keyboard focus to this widget. Focusable
+    /// elements are visited in tree order.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Accessibility information for this widget's element, exported by
+    /// `AccessibilityManager::sync_from_tree`
+    fn semantics(&self) -> Option<SemanticsData> {
+        None
+    }
+
+    /// Name shown for this widget in profiling spans and diagnostics
+    fn debug_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     /// Optional key for reconciliation (helps preserve state during updates)
     fn key(&self) -> Option<WidgetKey> {
         None
@@ -0,0 +1,116 @@
+use unic_langid::LanguageIdentifier;
+
+/// Number and date conventions of a locale, for widgets that format values
+/// themselves (DatePicker, Slider labels, Table cells).
+///
+/// Covers the common European and Asian conventions without pulling in CLDR
+/// data; apps that need exact formatting can pass preformatted strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocaleFormat {
+    locale: LanguageIdentifier,
+}
+
+impl LocaleFormat {
+    pub fn new(locale: LanguageIdentifier) -> Self {
+        Self { locale }
+    }
+
+    pub fn locale(&self) -> &LanguageIdentifier {
+        &self.locale
+    }
+
+    fn language(&self) -> &str {
+        self.locale.language.as_str()
+    }
+
+    fn region(&self) -> Option<&str> {
+        self.locale.region.as_ref().map(|region| region.as_str())
+    }
+
+    pub fn decimal_separator(&self) -> char {
+        match self.language() {
+            "de" | "fr" | "es" | "it" | "pt" | "nl" | "ru" | "pl" | "sv" | "da" | "nb" | "fi" | "cs" | "tr" | "uk" => ',',
+            _ => '.',
+        }
+    }
+
+    pub fn grouping_separator(&self) -> char {
+        match self.language() {
+            "fr" | "ru" | "pl" | "sv" | "nb" | "fi" | "cs" | "uk" => '\u{a0}',
+            "de" | "es" | "it" | "pt" | "nl" | "da" | "tr" => '.',
+            _ => ',',
+        }
+    }
+
+    /// `value` rounded to `decimals` places with locale separators
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+
+        let mut result = String::new();
+        if value.is_sign_negative() && formatted.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            result.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                result.push(self.grouping_separator());
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator());
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// 0 for Sunday, 1 for Monday
+    pub fn first_day_of_week(&self) -> u32 {
+        match (self.language(), self.region()) {
+            (_, Some("US" | "CA" | "MX" | "BR" | "JP" | "KR" | "IL" | "PH")) => 0,
+            ("en" | "ja" | "ko" | "he", None) => 0,
+            _ => 1,
+        }
+    }
+
+    /// Short numeric date pattern using `%Y`, `%m` and `%d`
+    pub fn date_pattern(&self) -> &'static str {
+        match (self.language(), self.region()) {
+            ("en", Some("US") | None) => "%m/%d/%Y",
+            ("de" | "ru" | "pl" | "fi" | "nb" | "cs" | "tr" | "uk", _) => "%d.%m.%Y",
+            ("ja" | "zh" | "ko" | "sv" | "lt" | "hu", _) => "%Y-%m-%d",
+            ("nl" | "da", _) => "%d-%m-%Y",
+            _ => "%d/%m/%Y",
+        }
+    }
+}
+
+/// US English, the conventions widgets used before localization
+impl Default for LocaleFormat {
+    fn default() -> Self {
+        Self::new("en-US".parse().expect("en-US is a valid locale"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(locale: &str) -> LocaleFormat {
+        LocaleFormat::new(locale.parse().unwrap())
+    }
+
+    #[test]
+    fn test_locale_conventions() {
+        assert_eq!(format("en-US").format_number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(format("de-DE").format_number(-1234.5, 1), "-1.234,5");
+        assert_eq!(format("en-US").format_number(-0.001, 2), "0.00");
+        assert_eq!(format("en-US").first_day_of_week(), 0);
+        assert_eq!(format("fr-FR").first_day_of_week(), 1);
+        assert_eq!(format("en-GB").date_pattern(), "%d/%m/%Y");
+        assert_eq!(format("de-AT").date_pattern(), "%d.%m.%Y");
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use parking_lot::RwLock;
+use unic_langid::LanguageIdentifier;
+
+/// Translations for every loaded locale plus the active one.
+///
+/// Cheap to clone; clones share bundles and the active locale, so a clone
+/// captured in an event callback can switch the locale for the whole app.
+#[derive(Clone)]
+pub struct Localizer {
+    inner: Arc<RwLock<LocalizerState>>,
+}
+
+struct LocalizerState {
+    locale: LanguageIdentifier,
+    /// Used for keys the active locale doesn't translate
+    fallback: LanguageIdentifier,
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+    /// Keys already reported missing, so each is logged once
+    missing: HashSet<String>,
+    /// The locale changed since the runtime last rebuilt
+    changed: bool,
+}
+
+impl Localizer {
+    /// A localizer that starts in, and falls back to, `fallback` (e.g. `"en-US"`)
+    pub fn new(fallback: &str) -> Result<Self> {
+        let fallback = parse_locale(fallback)?;
+        Ok(Self {
+            inner: Arc::new(RwLock::new(LocalizerState {
+                locale: fallback.clone(),
+                fallback,
+                bundles: HashMap::new(),
+                missing: HashSet::new(),
+                changed: false,
+            })),
+        })
+    }
+
+    /// Add Fluent source for `locale`. Later messages don't override earlier
+    /// ones with the same id.
+    pub fn add_ftl(&self, locale: &str, source: impl Into<String>) -> Result<()> {
+        let locale = parse_locale(locale)?;
+        let resource = FluentResource::try_new(source.into()).map_err(|(_, errors)| {
+            anyhow!("Failed to parse Fluent source for {}: {:?}", locale, errors)
+        })?;
+
+        let mut state = self.inner.write();
+        let bundle = state.bundles.entry(locale.clone()).or_insert_with(|| {
+            let mut bundle = FluentBundle::new_concurrent(vec![locale.clone()]);
+            // Bidi isolation marks render as boxes in fonts without them
+            bundle.set_use_isolating(false);
+            bundle
+        });
+        bundle
+            .add_resource(resource)
+            .map_err(|errors| anyhow!("Duplicate messages in {}: {:?}", locale, errors))
+    }
+
+    /// Load every `<dir>/<locale>/*.ftl` file, e.g. `locales/fr-FR/main.ftl`
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir).with_context(|| format!("Failed to read locale directory {:?}", dir))?;
+        for entry in entries {
+            let locale_dir = entry?.path();
+            let Some(locale) = locale_dir.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !locale_dir.is_dir() {
+                continue;
+            }
+            for file in std::fs::read_dir(&locale_dir)? {
+                let path = file?.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("ftl") {
+                    continue;
+                }
+                let source =
+                    std::fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+                self.add_ftl(locale, source).with_context(|| format!("Failed to load {:?}", path))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Switch the active locale; the runtime rebuilds every widget on the next frame
+    pub fn set_locale(&self, locale: &str) -> Result<()> {
+        let locale = parse_locale(locale)?;
+        let mut state = self.inner.write();
+        if state.locale != locale {
+            state.locale = locale;
+            state.changed = true;
+        }
+        Ok(())
+    }
+
+    pub fn locale(&self) -> LanguageIdentifier {
+        self.inner.read().locale.clone()
+    }
+
+    /// Locales with at least one loaded resource
+    pub fn available_locales(&self) -> Vec<LanguageIdentifier> {
+        self.inner.read().bundles.keys().cloned().collect()
+    }
+
+    /// Translate `key` (or `key.attribute`) in the active locale.
+    ///
+    /// Falls back to the same language in another region (`fr` for
+    /// `fr-CA`), then to the fallback locale, then to the key itself.
+    pub fn tr(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        let state = self.inner.read();
+        let (id, attribute) = match key.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (key, None),
+        };
+
+        for bundle in state.candidates() {
+            let Some(message) = bundle.get_message(id) else {
+                continue;
+            };
+            let pattern = match attribute {
+                Some(attribute) => message.get_attribute(attribute).map(|attr| attr.value()),
+                None => message.value(),
+            };
+            if let Some(pattern) = pattern {
+                let mut errors = Vec::new();
+                let text = bundle.format_pattern(pattern, args, &mut errors).into_owned();
+                if !errors.is_empty() {
+                    eprintln!("⚠️ Translation errors in {:?}: {:?}", key, errors);
+                }
+                return text;
+            }
+        }
+
+        drop(state);
+        let mut state = self.inner.write();
+        if state.missing.insert(key.to_string()) {
+            eprintln!("⚠️ Missing translation for {:?} in {}", key, state.locale);
+        }
+        key.to_string()
+    }
+
+    pub(crate) fn take_changed(&self) -> bool {
+        std::mem::take(&mut self.inner.write().changed)
+    }
+}
+
+impl LocalizerState {
+    /// Bundles to search, best match first
+    fn candidates(&self) -> impl Iterator<Item = &FluentBundle<FluentResource>> {
+        let exact = self.bundles.get(&self.locale);
+        let same_language = self
+            .bundles
+            .iter()
+            .filter(|(locale, _)| **locale != self.locale && locale.language == self.locale.language)
+            .map(|(_, bundle)| bundle);
+        let fallback = self.bundles.get(&self.fallback);
+        exact.into_iter().chain(same_language).chain(fallback)
+    }
+}
+
+fn parse_locale(locale: &str) -> Result<LanguageIdentifier> {
+    locale.parse().map_err(|e| anyhow!("Invalid locale {:?}: {}", locale, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translation_fallback() {
+        let localizer = Localizer::new("en-US").unwrap();
+        localizer
+            .add_ftl("en-US", "hello = Hello, { $name }!\nquit = Quit\nsave = Save\n    .tooltip = Save the file")
+            .unwrap();
+        localizer.add_ftl("fr", "hello = Bonjour, { $name } !").unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Ada");
+        assert_eq!(localizer.tr("hello", Some(&args)), "Hello, Ada!");
+        assert_eq!(localizer.tr("save.tooltip", None), "Save the file");
+
+        localizer.set_locale("fr-CA").unwrap();
+        assert!(localizer.take_changed());
+        assert_eq!(localizer.tr("hello", Some(&args)), "Bonjour, Ada !");
+        assert_eq!(localizer.tr("quit", None), "Quit");
+        assert_eq!(localizer.tr("missing-key", None), "missing-key");
+    }
+}
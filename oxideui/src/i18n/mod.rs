@@ -0,0 +1,27 @@
+//! Localization with Project Fluent
+//!
+//! Load `.ftl` files into a `Localizer`, hand it to `Runtime::with_localizer`
+//! and translate in builds with `ctx.tr("key", None)`. Switching the locale
+//! with `Localizer::set_locale` rebuilds every widget on the next frame;
+//! keys the active locale lacks fall back to the fallback locale, then to
+//! the key itself.
+//!
+//! ```rust,no_run
+//! use oxideui::i18n::{FluentArgs, Localizer};
+//!
+//! let localizer = Localizer::new("en-US")?;
+//! localizer.load_dir("locales")?;
+//!
+//! let mut args = FluentArgs::new();
+//! args.set("count", 3);
+//! let title = localizer.tr("unread-messages", Some(&args));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+mod format;
+mod localizer;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+pub use format::LocaleFormat;
+pub use localizer::Localizer;
+pub use unic_langid::LanguageIdentifier;
@@ -13,6 +13,90 @@ pub struct LayoutNode {
     pub position: (f32, f32),
     pub children: Vec<LayoutNode>,
     pub layout_type: LayoutType,
+    /// Distance from the top to the first line of text, if any
+    pub baseline: Option<f32>,
+    /// How a `Flex` node arranges its children
+    pub flex: FlexLayout,
+    /// How this node grows and shrinks inside a `Flex` parent
+    pub flex_item: FlexItem,
+    /// How a `Grid` node arranges its children
+    pub grid: GridLayout,
+    /// Where this node sits inside a `Grid` parent
+    pub grid_item: GridItem,
+}
+
+impl LayoutNode {
+    pub fn new(id: u64, layout_type: LayoutType, constraints: Constraints) -> Self {
+        Self {
+            id,
+            constraints,
+            size: Size::zero(),
+            position: (0.0, 0.0),
+            children: Vec::new(),
+            layout_type,
+            baseline: None,
+            flex: FlexLayout::default(),
+            flex_item: FlexItem::default(),
+            grid: GridLayout::default(),
+            grid_item: GridItem::default(),
+        }
+    }
+
+    /// A child whose content measured `size`
+    pub fn sized(id: u64, size: Size) -> Self {
+        Self {
+            size,
+            ..Self::new(id, LayoutType::Absolute, Constraints::unbounded())
+        }
+    }
+
+    pub fn with_flex(mut self, flex: FlexLayout) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    pub fn with_flex_item(mut self, flex_item: FlexItem) -> Self {
+        self.flex_item = flex_item;
+        self
+    }
+
+    pub fn with_grid(mut self, grid: GridLayout) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    pub fn with_grid_item(mut self, grid_item: GridItem) -> Self {
+        self.grid_item = grid_item;
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<LayoutNode>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn with_baseline(mut self, baseline: Option<f32>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Cross-axis alignment inside a flex line
+    fn align(&self, align_items: AlignItems) -> AlignItems {
+        self.flex_item.align_self.unwrap_or(align_items)
+    }
+
+    /// Baseline for alignment; boxes without text sit on their bottom edge
+    fn baseline(&self) -> f32 {
+        self.baseline.unwrap_or(self.size.height)
+    }
+}
+
+/// Children of one flex line, by index
+struct FlexLine {
+    start: usize,
+    end: usize,
+    main: f32,
+    cross: f32,
 }
 
 /// Layout algorithm type
@@ -35,6 +119,25 @@ pub struct FlexLayout {
     pub gap: f32,
 }
 
+impl Default for FlexLayout {
+    fn default() -> Self {
+        Self {
+            direction: FlexDirection::Row,
+            justify_content: JustifyContent::FlexStart,
+            align_items: AlignItems::Stretch,
+            align_content: AlignContent::Stretch,
+            wrap: FlexWrap::NoWrap,
+            gap: 0.0,
+        }
+    }
+}
+
+impl FlexDirection {
+    pub fn is_row(self) -> bool {
+        matches!(self, FlexDirection::Row | FlexDirection::RowReverse)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FlexDirection {
     Row,
@@ -53,6 +156,24 @@ pub enum JustifyContent {
     SpaceEvenly,
 }
 
+impl JustifyContent {
+    /// Offset of the first of `count` items and the gap after each, given
+    /// `free` space left over with `gap` between items
+    pub fn distribute(self, free: f32, count: usize, gap: f32) -> (f32, f32) {
+        let free = free.max(0.0);
+        let count = count.max(1) as f32;
+        match self {
+            JustifyContent::FlexStart => (0.0, gap),
+            JustifyContent::FlexEnd => (free, gap),
+            JustifyContent::Center => (free / 2.0, gap),
+            JustifyContent::SpaceBetween if count > 1.0 => (0.0, gap + free / (count - 1.0)),
+            JustifyContent::SpaceBetween => (0.0, gap),
+            JustifyContent::SpaceAround => (free / count / 2.0, gap + free / count),
+            JustifyContent::SpaceEvenly => (free / (count + 1.0), gap + free / (count + 1.0)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AlignItems {
     FlexStart,
@@ -80,7 +201,7 @@ pub enum FlexWrap {
 }
 
 /// Flex item properties
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FlexItem {
     pub flex_grow: f32,
     pub flex_shrink: f32,
@@ -99,6 +220,45 @@ impl Default for FlexItem {
     }
 }
 
+impl FlexItem {
+    /// Keeps its natural size: neither grows nor shrinks
+    pub fn rigid() -> Self {
+        Self {
+            flex_shrink: 0.0,
+            ..Self::default()
+        }
+    }
+}
+
+/// Main-axis sizes for items with natural sizes `natural` sharing
+/// `available` space: free space goes to items by `flex_grow`, and overflow
+/// is taken from items by `flex_shrink` weighted by their basis, as in CSS.
+/// Each size is clamped to `limits` (min, max).
+pub fn resolve_flexible_lengths(items: &[(FlexItem, f32, (f32, f32))], available: f32) -> Vec<f32> {
+    let basis: Vec<f32> = items
+        .iter()
+        .map(|(item, natural, _)| item.flex_basis.unwrap_or(*natural))
+        .collect();
+    let free = if available.is_finite() { available - basis.iter().sum::<f32>() } else { 0.0 };
+    let total_grow: f32 = items.iter().map(|(item, _, _)| item.flex_grow).sum();
+    let total_shrink: f32 = items.iter().zip(&basis).map(|((item, _, _), basis)| item.flex_shrink * basis).sum();
+
+    items
+        .iter()
+        .zip(&basis)
+        .map(|((item, _, (min, max)), &basis)| {
+            let size = if free > 0.0 && total_grow > 0.0 {
+                basis + free * item.flex_grow / total_grow
+            } else if free < 0.0 && total_shrink > 0.0 {
+                basis + free * item.flex_shrink * basis / total_shrink
+            } else {
+                basis
+            };
+            size.max(0.0).clamp(*min, max.max(*min))
+        })
+        .collect()
+}
+
 /// Grid layout properties
 #[derive(Debug, Clone)]
 pub struct GridLayout {
@@ -107,11 +267,182 @@ pub struct GridLayout {
     pub column_gap: f32,
     pub row_gap: f32,
     pub auto_flow: GridAutoFlow,
+    /// Named regions, one string of space-separated cell names per row, as
+    /// in `grid-template-areas`; `.` leaves a cell unnamed
+    pub areas: Vec<String>,
 }
 
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            column_gap: 0.0,
+            row_gap: 0.0,
+            auto_flow: GridAutoFlow::Row,
+            areas: Vec::new(),
+        }
+    }
+}
+
+impl GridLayout {
+    /// Cells covered by the area `name`: the bounding box of every cell
+    /// carrying that name in `areas`
+    pub fn area(&self, name: &str) -> Option<GridItem> {
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (row, line) in self.areas.iter().enumerate() {
+            for (column, _) in line.split_whitespace().enumerate().filter(|(_, cell)| *cell == name) {
+                bounds = Some(match bounds {
+                    Some((left, right, top, bottom)) => {
+                        (left.min(column), right.max(column + 1), top.min(row), bottom.max(row + 1))
+                    }
+                    None => (column, column + 1, row, row + 1),
+                });
+            }
+        }
+        bounds.map(|(left, right, top, bottom)| GridItem {
+            column_start: Some(left),
+            column_end: Some(right),
+            row_start: Some(top),
+            row_end: Some(bottom),
+            ..GridItem::default()
+        })
+    }
+
+    /// Columns the template defines, counting those only named in `areas`
+    fn template_columns(&self) -> usize {
+        let named = self.areas.iter().map(|line| line.split_whitespace().count()).max().unwrap_or(0);
+        self.columns.len().max(named)
+    }
+
+    fn template_rows(&self) -> usize {
+        self.rows.len().max(self.areas.len())
+    }
+
+    /// Cells of each item, placed as CSS grid does: items placed on both
+    /// axes take their cells first, then items with only a row (a column,
+    /// flowing by column) take the first free cells in it, then the rest
+    /// flow into free cells after the previous one. Dense flows look for
+    /// a free cell from the start every time, filling earlier holes.
+    fn place(&self, items: &[GridItem]) -> Vec<GridCell> {
+        // Placed in row-flow terms, where the number of columns is fixed and
+        // rows are added as needed; column flows swap the axes
+        let column_flow = matches!(self.auto_flow, GridAutoFlow::Column | GridAutoFlow::ColumnDense);
+        let dense = matches!(self.auto_flow, GridAutoFlow::RowDense | GridAutoFlow::ColumnDense);
+        let axes: Vec<_> = items
+            .iter()
+            .map(|item| if column_flow { (item.rows(), item.columns()) } else { (item.columns(), item.rows()) })
+            .collect();
+        let template = if column_flow { self.template_rows() } else { self.template_columns() };
+        let width = axes
+            .iter()
+            .map(|((start, span), _)| start.unwrap_or(0) + span)
+            .fold(template, usize::max)
+            .max(1);
+
+        let mut occupancy = Occupancy { columns: width, taken: Vec::new() };
+        let mut cells: Vec<Option<GridCell>> = vec![None; items.len()];
+        for (slot, &((column, columns), (row, rows))) in cells.iter_mut().zip(&axes) {
+            if let (Some(column), Some(row)) = (column, row) {
+                let cell = GridCell { column, row, columns, rows };
+                occupancy.take(cell);
+                *slot = Some(cell);
+            }
+        }
+        for (slot, &((column, columns), (row, rows))) in cells.iter_mut().zip(&axes) {
+            if let (None, Some(row)) = (column, row) {
+                let cell = (0..=width - columns)
+                    .map(|column| GridCell { column, row, columns, rows })
+                    .find(|&cell| occupancy.is_free(cell))
+                    .unwrap_or(GridCell { column: 0, row, columns, rows });
+                occupancy.take(cell);
+                *slot = Some(cell);
+            }
+        }
+
+        // (row, column) after the last auto-placed item
+        let mut cursor = (0, 0);
+        for (slot, &((column, columns), (_, rows))) in cells.iter_mut().zip(&axes) {
+            if slot.is_some() {
+                continue;
+            }
+            if dense {
+                cursor = (0, 0);
+            }
+            let cell = match column {
+                // Fixed column: the first free row at or after the cursor
+                Some(column) => {
+                    let mut row = if column < cursor.1 { cursor.0 + 1 } else { cursor.0 };
+                    while !occupancy.is_free(GridCell { column, row, columns, rows }) {
+                        row += 1;
+                    }
+                    GridCell { column, row, columns, rows }
+                }
+                None => loop {
+                    let (row, column) = cursor;
+                    if column + columns > width {
+                        cursor = (row + 1, 0);
+                        continue;
+                    }
+                    let cell = GridCell { column, row, columns, rows };
+                    if occupancy.is_free(cell) {
+                        break cell;
+                    }
+                    cursor.1 += 1;
+                },
+            };
+            occupancy.take(cell);
+            cursor = (cell.row, cell.column + cell.columns);
+            *slot = Some(cell);
+        }
+
+        cells
+            .into_iter()
+            .flatten()
+            .map(|cell| if column_flow { cell.transposed() } else { cell })
+            .collect()
+    }
+
+    /// Width and height of each item's cells along the axes where every
+    /// track it spans is `Fixed`, so its content can't change them
+    pub fn fixed_cell_sizes(&self, items: &[GridItem]) -> Vec<(Option<f32>, Option<f32>)> {
+        let cells = self.place(items);
+        let column_count = cells.iter().map(|cell| cell.column + cell.columns).fold(self.template_columns().max(1), usize::max);
+        let row_count = cells.iter().map(|cell| cell.row + cell.rows).fold(self.template_rows(), usize::max);
+        let columns = GridLayout::tracks(&self.columns, column_count);
+        let rows = GridLayout::tracks(&self.rows, row_count);
+        let fixed = |tracks: &[GridTrack], gap: f32| {
+            let sizes: Option<Vec<f32>> = tracks
+                .iter()
+                .map(|track| if let GridTrack::Fixed(size) = track { Some(*size) } else { None })
+                .collect();
+            sizes.map(|sizes| track_extent(&sizes, gap))
+        };
+        cells
+            .iter()
+            .map(|cell| {
+                (
+                    fixed(&columns[cell.column..cell.column + cell.columns], self.column_gap),
+                    fixed(&rows[cell.row..cell.row + cell.rows], self.row_gap),
+                )
+            })
+            .collect()
+    }
+
+    /// The first `count` tracks of `template`; tracks past its end size to
+    /// their content
+    fn tracks(template: &[GridTrack], count: usize) -> Vec<GridTrack> {
+        (0..count).map(|i| template.get(i).copied().unwrap_or(GridTrack::Auto)).collect()
+    }
+}
+
+/// Size of a grid track. The engine measures one natural size per item,
+/// so `MinContent` and `MaxContent` tracks both fit their largest item;
+/// unlike `Auto` tracks they never take leftover space.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GridTrack {
     Fixed(f32),
+    /// A share of the space left after the other tracks, like CSS `fr`
     Flex(f32),
     Auto,
     MinContent,
@@ -126,13 +457,199 @@ pub enum GridAutoFlow {
     ColumnDense,
 }
 
-/// Grid item placement
-#[derive(Debug, Clone, Copy)]
+/// Grid item placement, as zero-based track indices with exclusive ends.
+/// Missing starts are found by auto-placement; the spans apply where an
+/// end is missing.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GridItem {
     pub column_start: Option<usize>,
     pub column_end: Option<usize>,
     pub row_start: Option<usize>,
     pub row_end: Option<usize>,
+    pub column_span: usize,
+    pub row_span: usize,
+}
+
+impl Default for GridItem {
+    fn default() -> Self {
+        Self {
+            column_start: None,
+            column_end: None,
+            row_start: None,
+            row_end: None,
+            column_span: 1,
+            row_span: 1,
+        }
+    }
+}
+
+impl GridItem {
+    /// Auto-placed over `columns` by `rows` cells
+    pub fn span(columns: usize, rows: usize) -> Self {
+        Self {
+            column_span: columns,
+            row_span: rows,
+            ..Self::default()
+        }
+    }
+
+    /// One cell at `column`, `row`
+    pub fn at(column: usize, row: usize) -> Self {
+        Self {
+            column_start: Some(column),
+            row_start: Some(row),
+            ..Self::default()
+        }
+    }
+
+    /// First column, if placed, and the number of columns spanned
+    fn columns(&self) -> (Option<usize>, usize) {
+        Self::resolve(self.column_start, self.column_end, self.column_span)
+    }
+
+    fn rows(&self) -> (Option<usize>, usize) {
+        Self::resolve(self.row_start, self.row_end, self.row_span)
+    }
+
+    fn resolve(start: Option<usize>, end: Option<usize>, span: usize) -> (Option<usize>, usize) {
+        let span = span.max(1);
+        match (start, end) {
+            (Some(start), Some(end)) => (Some(start), end.saturating_sub(start).max(1)),
+            (Some(start), None) => (Some(start), span),
+            (None, Some(end)) => {
+                let start = end.saturating_sub(span);
+                (Some(start), (end - start).max(1))
+            }
+            (None, None) => (None, span),
+        }
+    }
+}
+
+/// Cells occupied by one grid item
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GridCell {
+    column: usize,
+    row: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl GridCell {
+    fn transposed(self) -> Self {
+        Self {
+            column: self.row,
+            row: self.column,
+            columns: self.rows,
+            rows: self.columns,
+        }
+    }
+}
+
+/// Which cells of a grid `columns` wide are taken, row by row
+struct Occupancy {
+    columns: usize,
+    taken: Vec<bool>,
+}
+
+impl Occupancy {
+    fn indices(&self, cell: GridCell) -> impl Iterator<Item = usize> + '_ {
+        (cell.row..cell.row + cell.rows)
+            .flat_map(move |row| (cell.column..cell.column + cell.columns).map(move |column| row * self.columns + column))
+    }
+
+    fn is_free(&self, cell: GridCell) -> bool {
+        self.indices(cell).all(|i| !self.taken.get(i).copied().unwrap_or(false))
+    }
+
+    fn take(&mut self, cell: GridCell) {
+        for i in self.indices(cell).collect::<Vec<_>>() {
+            if i >= self.taken.len() {
+                self.taken.resize(i + 1, false);
+            }
+            self.taken[i] = true;
+        }
+    }
+}
+
+/// Total extent of `sizes` laid end to end with `gap` between them
+fn track_extent(sizes: &[f32], gap: f32) -> f32 {
+    sizes.iter().sum::<f32>() + gap * sizes.len().saturating_sub(1) as f32
+}
+
+/// What each of `tracks` needs to fit its items, given the (first track,
+/// track count, natural extent) of each item. Items spanning several
+/// tracks spread what doesn't fit evenly over the spanned tracks that size
+/// to content.
+fn grid_content(tracks: &[GridTrack], items: &[(usize, usize, f32)], gap: f32) -> Vec<f32> {
+    let mut content = vec![0.0; tracks.len()];
+    for &(start, _, size) in items.iter().filter(|(_, span, _)| *span == 1) {
+        content[start] = f32::max(content[start], size);
+    }
+    for &(start, span, size) in items.iter().filter(|(_, span, _)| *span > 1) {
+        let range = start..start + span;
+        let covered: Vec<f32> = tracks[range.clone()]
+            .iter()
+            .zip(&content[range.clone()])
+            .map(|(track, &content)| if let GridTrack::Fixed(size) = track { *size } else { content })
+            .collect();
+        let growable: Vec<usize> = range.filter(|&i| !matches!(tracks[i], GridTrack::Fixed(_))).collect();
+        let missing = size - track_extent(&covered, gap);
+        if missing > 0.0 && !growable.is_empty() {
+            for i in &growable {
+                content[*i] += missing / growable.len() as f32;
+            }
+        }
+    }
+    content
+}
+
+/// Sizes of grid `tracks` sharing `available` space with `gap` between
+/// them, where `content` is what each track's items need. `Flex` tracks
+/// share what the others leave, but never shrink below their content;
+/// with no `Flex` tracks, `Auto` tracks stretch to share the leftover
+/// instead, as with CSS `align-content: normal`.
+/// Unbounded, every track but `Fixed` fits its content.
+fn resolve_tracks(tracks: &[GridTrack], content: &[f32], available: f32, gap: f32) -> Vec<f32> {
+    let mut sizes: Vec<f32> = tracks
+        .iter()
+        .zip(content)
+        .map(|(track, &content)| match track {
+            GridTrack::Fixed(size) => *size,
+            _ => content,
+        })
+        .collect();
+    if !available.is_finite() {
+        return sizes;
+    }
+
+    // Flex tracks whose share is smaller than their content keep the
+    // content and drop out of the sharing
+    let mut flexing: Vec<usize> = (0..tracks.len()).filter(|&i| matches!(tracks[i], GridTrack::Flex(_))).collect();
+    while !flexing.is_empty() {
+        let rigid: f32 = (0..tracks.len()).filter(|i| !flexing.contains(i)).map(|i| sizes[i]).sum();
+        let gaps = gap * tracks.len().saturating_sub(1) as f32;
+        let factor = |i: usize| if let GridTrack::Flex(factor) = tracks[i] { factor } else { 0.0 };
+        let total: f32 = flexing.iter().map(|&i| factor(i)).sum();
+        let per_fraction = if total > 0.0 { (available - gaps - rigid).max(0.0) / total } else { 0.0 };
+        let (too_small, sharing): (Vec<usize>, Vec<usize>) =
+            flexing.iter().copied().partition(|&i| factor(i) * per_fraction < content[i]);
+        if too_small.is_empty() {
+            for i in sharing {
+                sizes[i] = factor(i) * per_fraction;
+            }
+            return sizes;
+        }
+        flexing = sharing;
+    }
+
+    let free = available - track_extent(&sizes, gap);
+    let auto: Vec<usize> = (0..tracks.len()).filter(|&i| tracks[i] == GridTrack::Auto).collect();
+    if free > 0.0 && !auto.is_empty() {
+        for i in &auto {
+            sizes[*i] += free / auto.len() as f32;
+        }
+    }
+    sizes
 }
 
 /// Layout engine
@@ -149,6 +666,7 @@ impl LayoutEngine {
 
     /// Layout a node and its children
     pub fn layout(&mut self, node: &mut LayoutNode) {
+        let _span = span!(DEBUG, "layout");
         match node.layout_type {
             LayoutType::Flex => self.layout_flex(node),
             LayoutType::Grid => self.layout_grid(node),
@@ -157,57 +675,207 @@ impl LayoutEngine {
         }
     }
 
-    /// Flexbox layout algorithm
+    /// Flexbox layout algorithm. Children arrive with `size` set to their
+    /// natural size (and `baseline` when they have text) and leave with
+    /// their final size and position.
     fn layout_flex(&self, node: &mut LayoutNode) {
-        let is_row = true;
-        let mut position = 0.0;
-
-        for child in &mut node.children {
-            let child_size = child.constraints.biggest();
+        let flex = node.flex;
+        let is_row = flex.direction.is_row();
+        let reverse = matches!(flex.direction, FlexDirection::RowReverse | FlexDirection::ColumnReverse);
+        // (main, cross) extents of a size
+        let axes = |size: Size| if is_row { (size.width, size.height) } else { (size.height, size.width) };
+        let (available, _) = axes(Size::new(node.constraints.max_width, node.constraints.max_height));
+        let gap = flex.gap;
 
-            if is_row {
-                child.position = (position, 0.0);
-                position += child_size.width;
-            } else {
-                child.position = (0.0, position);
-                position += child_size.height;
+        // Break into lines by hypothetical main size
+        let mut lines: Vec<FlexLine> = Vec::new();
+        for (i, child) in node.children.iter().enumerate() {
+            let basis = child.flex_item.flex_basis.unwrap_or(axes(child.size).0);
+            match lines.last_mut() {
+                Some(line)
+                    if flex.wrap == FlexWrap::NoWrap || line.main + gap + basis <= available =>
+                {
+                    line.end = i + 1;
+                    line.main += gap + basis;
+                }
+                _ => lines.push(FlexLine { start: i, end: i + 1, main: basis, cross: 0.0 }),
             }
+        }
+
+        // Share out each line's free space, then measure its cross extent
+        for line in &mut lines {
+            let children = &mut node.children[line.start..line.end];
+            let items: Vec<_> = children
+                .iter()
+                .map(|child| {
+                    let (min, _) = axes(child.constraints.smallest());
+                    let (max, _) = axes(Size::new(child.constraints.max_width, child.constraints.max_height));
+                    (child.flex_item, axes(child.size).0, (min, max))
+                })
+                .collect();
+            let gaps = gap * (children.len() - 1) as f32;
+            let main_sizes = resolve_flexible_lengths(&items, available - gaps);
+            line.main = main_sizes.iter().sum::<f32>() + gaps;
 
-            child.size = child_size;
+            let mut ascent: f32 = 0.0;
+            for (child, main) in children.iter_mut().zip(main_sizes) {
+                if is_row {
+                    child.size.width = main;
+                } else {
+                    child.size.height = main;
+                }
+                line.cross = line.cross.max(axes(child.size).1);
+                if is_row && child.align(flex.align_items) == AlignItems::Baseline {
+                    ascent = ascent.max(child.baseline());
+                }
+            }
+            // Baseline-aligned children hang below the line's tallest ascent
+            for child in children.iter().filter(|child| is_row && child.align(flex.align_items) == AlignItems::Baseline) {
+                line.cross = line.cross.max(ascent - child.baseline() + child.size.height);
+            }
         }
 
-        node.size = if is_row {
-            Size::new(position, node.constraints.max_height)
+        // Bounded containers fill their main axis; unbounded ones wrap content
+        let content_main = lines.iter().map(|line| line.main).fold(0.0, f32::max);
+        let content_cross = lines.iter().map(|line| line.cross).sum::<f32>() + gap * lines.len().saturating_sub(1) as f32;
+        let main = if available.is_finite() { available } else { content_main };
+        node.size = node.constraints.constrain(if is_row {
+            Size::new(main, content_cross)
         } else {
-            Size::new(node.constraints.max_width, position)
+            Size::new(content_cross, main)
+        });
+        let (container_main, container_cross) = axes(node.size);
+
+        // A single unwrapped line spans the container's cross axis;
+        // wrapped lines share leftover cross space by `align_content`
+        let (mut cross_offset, line_gap) = if flex.wrap == FlexWrap::NoWrap {
+            if let Some(line) = lines.first_mut() {
+                line.cross = container_cross;
+            }
+            (0.0, gap)
+        } else {
+            let free = container_cross - content_cross;
+            match flex.align_content {
+                AlignContent::Stretch if free > 0.0 => {
+                    let extra = free / lines.len().max(1) as f32;
+                    lines.iter_mut().for_each(|line| line.cross += extra);
+                    (0.0, gap)
+                }
+                AlignContent::Stretch | AlignContent::FlexStart => (0.0, gap),
+                AlignContent::FlexEnd => JustifyContent::FlexEnd.distribute(free, lines.len(), gap),
+                AlignContent::Center => JustifyContent::Center.distribute(free, lines.len(), gap),
+                AlignContent::SpaceBetween => JustifyContent::SpaceBetween.distribute(free, lines.len(), gap),
+                AlignContent::SpaceAround => JustifyContent::SpaceAround.distribute(free, lines.len(), gap),
+            }
         };
-    }
 
-    /// Grid layout algorithm - FIXED TYPE ANNOTATIONS
-    fn layout_grid(&self, node: &mut LayoutNode) {
-        let columns = 3;
-        let gap = 10.0;
+        for line in &lines {
+            let children = &mut node.children[line.start..line.end];
+            let (mut main_offset, item_gap) =
+                flex.justify_content.distribute(container_main - line.main, children.len(), gap);
+            let ascent = children
+                .iter()
+                .filter(|child| child.align(flex.align_items) == AlignItems::Baseline)
+                .map(LayoutNode::baseline)
+                .fold(0.0, f32::max);
 
-        let available_width = node.constraints.max_width - (gap * (columns - 1) as f32);
-        let cell_width = available_width / columns as f32;
-        let cell_height = 100.0;
+            for child in children.iter_mut() {
+                let (child_main, mut child_cross) = axes(child.size);
+                let within = match child.align(flex.align_items) {
+                    AlignItems::FlexStart => 0.0,
+                    AlignItems::FlexEnd => line.cross - child_cross,
+                    AlignItems::Center => (line.cross - child_cross) / 2.0,
+                    AlignItems::Baseline if is_row => ascent - child.baseline(),
+                    AlignItems::Baseline => 0.0,
+                    AlignItems::Stretch => {
+                        let (_, max) = axes(Size::new(child.constraints.max_width, child.constraints.max_height));
+                        child_cross = line.cross.min(max);
+                        0.0
+                    }
+                };
+                // Reversed directions and wrap-reverse lay out from the far end
+                let main_pos = if reverse { container_main - main_offset - child_main } else { main_offset };
+                let cross_pos = if flex.wrap == FlexWrap::WrapReverse {
+                    container_cross - cross_offset - line.cross + within
+                } else {
+                    cross_offset + within
+                };
+                if is_row {
+                    child.size.height = child_cross;
+                    child.position = (main_pos, cross_pos);
+                } else {
+                    child.size.width = child_cross;
+                    child.position = (cross_pos, main_pos);
+                }
+                main_offset += child_main + item_gap;
+            }
+            cross_offset += line.cross + line_gap;
+        }
+    }
 
-        for (i, child) in node.children.iter_mut().enumerate() {
-            let col = i % columns;
-            let row = i / columns;
+    /// Grid layout algorithm. Children arrive with `size` set to their
+    /// natural size and leave filling the cells they occupy.
+    fn layout_grid(&self, node: &mut LayoutNode) {
+        let (cells, columns, rows) = self.grid_tracks(node, node.constraints.max_width, node.constraints.max_height);
+        let (column_gap, row_gap) = (node.grid.column_gap, node.grid.row_gap);
+        let offsets = |sizes: &[f32], gap: f32| -> Vec<f32> {
+            sizes
+                .iter()
+                .scan(0.0, |offset, size| {
+                    let start = *offset;
+                    *offset += size + gap;
+                    Some(start)
+                })
+                .collect()
+        };
+        let (column_offsets, row_offsets) = (offsets(&columns, column_gap), offsets(&rows, row_gap));
 
-            child.position = (
-                col as f32 * (cell_width + gap),
-                row as f32 * (cell_height + gap),
+        for (child, cell) in node.children.iter_mut().zip(cells) {
+            child.position = (column_offsets[cell.column], row_offsets[cell.row]);
+            child.size = Size::new(
+                track_extent(&columns[cell.column..cell.column + cell.columns], column_gap),
+                track_extent(&rows[cell.row..cell.row + cell.rows], row_gap),
             );
-            child.size = Size::new(cell_width, cell_height);
         }
+        node.size = node
+            .constraints
+            .constrain(Size::new(track_extent(&columns, column_gap), track_extent(&rows, row_gap)));
+    }
+
+    /// Cells of each of a grid's children, and the sizes of its column and
+    /// row tracks within `max_width` by `max_height`. Tracks past the
+    /// template are added as placement needs them and size to their content.
+    fn grid_tracks(&self, node: &LayoutNode, max_width: f32, max_height: f32) -> (Vec<GridCell>, Vec<f32>, Vec<f32>) {
+        let grid = &node.grid;
+        let items: Vec<GridItem> = node.children.iter().map(|child| child.grid_item).collect();
+        let cells = grid.place(&items);
+        let column_count = cells
+            .iter()
+            .map(|cell| cell.column + cell.columns)
+            .fold(grid.template_columns().max(1), usize::max);
+        let row_count = cells.iter().map(|cell| cell.row + cell.rows).fold(grid.template_rows(), usize::max);
+        let columns = GridLayout::tracks(&grid.columns, column_count);
+        let rows = GridLayout::tracks(&grid.rows, row_count);
 
-        let rows = (node.children.len() + columns - 1) / columns;
-        node.size = Size::new(
-            node.constraints.max_width,
-            rows as f32 * cell_height + (rows - 1) as f32 * gap,
+        let natural = |child: &LayoutNode| self.measure_intrinsic(child);
+        let widths: Vec<_> = cells
+            .iter()
+            .zip(&node.children)
+            .map(|(cell, child)| (cell.column, cell.columns, natural(child).width))
+            .collect();
+        let heights: Vec<_> = cells
+            .iter()
+            .zip(&node.children)
+            .map(|(cell, child)| (cell.row, cell.rows, natural(child).height))
+            .collect();
+        let column_sizes = resolve_tracks(
+            &columns,
+            &grid_content(&columns, &widths, grid.column_gap),
+            max_width,
+            grid.column_gap,
         );
+        let row_sizes = resolve_tracks(&rows, &grid_content(&rows, &heights, grid.row_gap), max_height, grid.row_gap);
+        (cells, column_sizes, row_sizes)
     }
 
     fn layout_absolute(&self, node: &mut LayoutNode) {
@@ -232,18 +900,21 @@ impl LayoutEngine {
         node.size = Size::new(max_width, max_height);
     }
 
+    /// Size `node` would take with unlimited space: its content laid out
+    /// without flexing or wrapping. Leaves report their natural `size`.
     pub fn measure_intrinsic(&self, node: &LayoutNode) -> Size {
         match node.layout_type {
             LayoutType::Flex => self.measure_flex_intrinsic(node),
             LayoutType::Grid => self.measure_grid_intrinsic(node),
+            _ if node.children.is_empty() => node.constraints.constrain(node.size),
             _ => node.constraints.smallest(),
         }
     }
 
     fn measure_flex_intrinsic(&self, node: &LayoutNode) -> Size {
-        let is_row = true;
+        let is_row = node.flex.direction.is_row();
         let mut total_width = 0.0;
-        let mut total_height: f32 = 0.0; // FIX: Explicit type annotation
+        let mut total_height: f32 = 0.0;
 
         for child in &node.children {
             let child_size = self.measure_intrinsic(child);
@@ -257,17 +928,21 @@ impl LayoutEngine {
             }
         }
 
-        Size::new(total_width, total_height)
+        let gaps = node.flex.gap * node.children.len().saturating_sub(1) as f32;
+        let size = if is_row {
+            Size::new(total_width + gaps, total_height)
+        } else {
+            Size::new(total_width, total_height + gaps)
+        };
+        node.constraints.constrain(size)
     }
 
     fn measure_grid_intrinsic(&self, node: &LayoutNode) -> Size {
-        let columns = 3;
-        let rows = (node.children.len() + columns - 1) / columns;
-
-        Size::new(
-            300.0 * columns as f32,
-            100.0 * rows as f32,
-        )
+        let (_, columns, rows) = self.grid_tracks(node, f32::INFINITY, f32::INFINITY);
+        node.constraints.constrain(Size::new(
+            track_extent(&columns, node.grid.column_gap),
+            track_extent(&rows, node.grid.row_gap),
+        ))
     }
 }
 
@@ -277,54 +952,116 @@ impl Default for LayoutEngine {
     }
 }
 
-pub struct LayoutSolver {
-    variables: HashMap<String, f32>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl LayoutSolver {
-    pub fn new() -> Self {
-        Self {
-            variables: HashMap::new(),
-        }
+    fn flex_node(flex: FlexLayout, sizes: &[(f32, f32)]) -> LayoutNode {
+        let children = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &(width, height))| LayoutNode::sized(i as u64, Size::new(width, height)).with_flex_item(FlexItem::rigid()))
+            .collect();
+        LayoutNode::new(0, LayoutType::Flex, Constraints::loose(Size::new(100.0, 100.0)))
+            .with_flex(flex)
+            .with_children(children)
     }
 
-    pub fn solve(&mut self, constraints: &[LayoutConstraint]) -> bool {
-        for constraint in constraints {
-            match constraint {
-                LayoutConstraint::Equal(var, value) => {
-                    self.variables.insert(var.clone(), *value);
-                }
-                LayoutConstraint::GreaterThan(var, value) => {
-                    let current = self.variables.get(var).copied().unwrap_or(0.0);
-                    if current < *value {
-                        self.variables.insert(var.clone(), *value);
-                    }
-                }
-                LayoutConstraint::LessThan(var, value) => {
-                    let current = self.variables.get(var).copied().unwrap_or(f32::INFINITY);
-                    if current > *value {
-                        self.variables.insert(var.clone(), *value);
-                    }
-                }
-            }
-        }
-        true
+    #[test]
+    fn test_flex_wraps_justifies_and_aligns() {
+        let mut engine = LayoutEngine::new();
+        let mut node = flex_node(
+            FlexLayout {
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                wrap: FlexWrap::Wrap,
+                gap: 10.0,
+                ..FlexLayout::default()
+            },
+            &[(40.0, 20.0), (40.0, 10.0), (40.0, 20.0)],
+        );
+        engine.layout(&mut node);
+        let positions: Vec<_> = node.children.iter().map(|child| child.position).collect();
+        // Two fit on the first line with the spare 10px between them; the
+        // shorter one is centered; the third wraps below the gap
+        assert_eq!(positions, vec![(0.0, 0.0), (60.0, 5.0), (0.0, 30.0)]);
+        assert_eq!(node.size, Size::new(100.0, 50.0));
+
+        let mut reversed = flex_node(
+            FlexLayout {
+                direction: FlexDirection::RowReverse,
+                gap: 10.0,
+                ..FlexLayout::default()
+            },
+            &[(40.0, 20.0), (40.0, 20.0)],
+        );
+        engine.layout(&mut reversed);
+        let positions: Vec<_> = reversed.children.iter().map(|child| child.position.0).collect();
+        assert_eq!(positions, vec![60.0, 10.0]);
     }
 
-    pub fn get_value(&self, var: &str) -> Option<f32> {
-        self.variables.get(var).copied()
+    #[test]
+    fn test_grid_sizes_tracks_to_content_and_fractions() {
+        let mut engine = LayoutEngine::new();
+        let grid = GridLayout {
+            columns: vec![GridTrack::Auto, GridTrack::Flex(1.0), GridTrack::Fixed(30.0)],
+            column_gap: 10.0,
+            row_gap: 5.0,
+            ..GridLayout::default()
+        };
+        let children = [(25.0, 10.0), (80.0, 20.0), (10.0, 10.0), (15.0, 40.0)]
+            .iter()
+            .enumerate()
+            .map(|(i, &(width, height))| LayoutNode::sized(i as u64, Size::new(width, height)))
+            .collect();
+        let mut node = LayoutNode::new(0, LayoutType::Grid, Constraints::new(0.0, 200.0, 0.0, f32::INFINITY))
+            .with_grid(grid)
+            .with_children(children);
+        engine.layout(&mut node);
+
+        // The auto column fits its widest item, the fixed one keeps 30 and
+        // the fraction takes the rest: 200 - 25 - 30 - 2 * 10 = 125. The
+        // fourth child wraps onto a second row as tall as it is.
+        let cells: Vec<_> = node.children.iter().map(|child| (child.position, child.size)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                ((0.0, 0.0), Size::new(25.0, 20.0)),
+                ((35.0, 0.0), Size::new(125.0, 20.0)),
+                ((170.0, 0.0), Size::new(30.0, 20.0)),
+                ((0.0, 25.0), Size::new(25.0, 40.0)),
+            ]
+        );
+        assert_eq!(node.size, Size::new(200.0, 65.0));
+        // Unbounded, the fraction fits its content instead
+        assert_eq!(engine.measure_intrinsic(&node), Size::new(25.0 + 80.0 + 30.0 + 20.0, 65.0));
     }
-}
 
-#[derive(Debug, Clone)]
-pub enum LayoutConstraint {
-    Equal(String, f32),
-    GreaterThan(String, f32),
-    LessThan(String, f32),
-}
+    #[test]
+    fn test_grid_flows_around_placed_items() {
+        let items = [GridItem::at(1, 0), GridItem::span(2, 1), GridItem::default(), GridItem::span(1, 2)];
+        let cells = |auto_flow: GridAutoFlow| -> Vec<(usize, usize, usize, usize)> {
+            let grid = GridLayout {
+                columns: vec![GridTrack::Flex(1.0); 3],
+                auto_flow,
+                ..GridLayout::default()
+            };
+            grid.place(&items)
+                .into_iter()
+                .map(|cell| (cell.column, cell.row, cell.columns, cell.rows))
+                .collect()
+        };
 
-impl Default for LayoutSolver {
-    fn default() -> Self {
-        Self::new()
+        // The two-column item doesn't fit either side of the placed one, so
+        // it starts the second row and leaves holes in the first
+        assert_eq!(
+            cells(GridAutoFlow::Row),
+            vec![(1, 0, 1, 1), (0, 1, 2, 1), (2, 1, 1, 1), (0, 2, 1, 2)]
+        );
+        // Dense packing goes back to fill them
+        assert_eq!(
+            cells(GridAutoFlow::RowDense),
+            vec![(1, 0, 1, 1), (0, 1, 2, 1), (0, 0, 1, 1), (2, 0, 1, 2)]
+        );
     }
-}
\ No newline at end of file
+}
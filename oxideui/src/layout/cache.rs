@@ -0,0 +1,211 @@
+//! Layout result caching
+//!
+//! Widgets opt in by returning a hash of their configuration from
+//! `Widget::layout_hash`. When a parent builds them through
+//! `BuildContext::build_child`, the render object from the previous frame is
+//! reused as long as that hash and the incoming constraints are unchanged, so
+//! resizing one panel only lays out that panel again.
+
+use std::any::TypeId;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Write};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::core::{RenderObject, Theme, Widget};
+use crate::layout::Constraints;
+
+/// Frames an entry survives without being used. Children of a reused parent
+/// aren't visited, so entries must outlive a single frame to still be there
+/// when the parent changes.
+const MAX_IDLE_FRAMES: u64 = 60;
+
+/// Render objects of cacheable widgets from recent frames.
+///
+/// Cheap to clone; clones share entries.
+#[derive(Clone, Default)]
+pub struct LayoutCache {
+    inner: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<LayoutKey, CacheEntry>,
+    frame: u64,
+    /// Theme the entries were built with; held so a new theme is never
+    /// mistaken for the old one by address
+    theme: Option<Arc<Theme>>,
+    hits: u64,
+    misses: u64,
+}
+
+struct CacheEntry {
+    render_object: RenderObject,
+    last_used: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct LayoutKey {
+    widget: TypeId,
+    config: u64,
+    /// Inherited context the widget reads: direction, text style, motion
+    context: u64,
+    constraints: [u32; 4],
+}
+
+impl LayoutKey {
+    pub(crate) fn new(widget: TypeId, config: u64, context: u64, constraints: Constraints) -> Self {
+        Self {
+            widget,
+            config,
+            context,
+            constraints: [
+                constraints.min_width.to_bits(),
+                constraints.max_width.to_bits(),
+                constraints.min_height.to_bits(),
+                constraints.max_height.to_bits(),
+            ],
+        }
+    }
+}
+
+/// Cache counters since the cache was created
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LayoutCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl LayoutCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn get(&self, key: &LayoutKey) -> Option<RenderObject> {
+        let mut state = self.inner.lock();
+        let frame = state.frame;
+        match state.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = frame;
+                let render_object = entry.render_object.clone();
+                state.hits += 1;
+                Some(render_object)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert(&self, key: LayoutKey, render_object: RenderObject) {
+        let mut state = self.inner.lock();
+        let last_used = state.frame;
+        state.entries.insert(key, CacheEntry { render_object, last_used });
+    }
+
+    /// Start a frame built with `theme`; a different theme drops every entry
+    pub(crate) fn begin_frame(&self, theme: &Arc<Theme>) {
+        let mut state = self.inner.lock();
+        if !state.theme.as_ref().is_some_and(|cached| Arc::ptr_eq(cached, theme)) {
+            state.entries.clear();
+            state.theme = Some(theme.clone());
+        }
+    }
+
+    /// Drop entries that haven't been used for a while, so widgets that left
+    /// the tree don't keep their render objects alive
+    pub(crate) fn end_frame(&self) {
+        let mut state = self.inner.lock();
+        let frame = state.frame;
+        state.entries.retain(|_, entry| frame - entry.last_used < MAX_IDLE_FRAMES);
+        state.frame += 1;
+    }
+
+    /// Forget every entry, e.g. after the locale or stylesheet changed
+    pub fn clear(&self) {
+        self.inner.lock().entries.clear();
+    }
+
+    pub fn stats(&self) -> LayoutCacheStats {
+        let state = self.inner.lock();
+        LayoutCacheStats {
+            entries: state.entries.len(),
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+}
+
+/// Hash of `value`'s `Debug` output, for configurations holding floats that
+/// can't derive `Hash`
+pub fn debug_hash<T: Debug + ?Sized>(value: &T) -> u64 {
+    struct HashWriter(DefaultHasher);
+
+    impl Write for HashWriter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.write(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    let mut writer = HashWriter(DefaultHasher::new());
+    let _ = write!(writer, "{:?}", value);
+    writer.0.finish()
+}
+
+/// Combined layout hash of `children`, or None if any of them opts out
+pub fn children_hash<'a>(children: impl IntoIterator<Item = &'a dyn Widget>) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    for child in children {
+        Widget::type_id(child).hash(&mut hasher);
+        child.layout_hash()?.hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::BuildContext;
+    use crate::layout::Size;
+    use crate::widgets::basic::{Row, Text};
+
+    #[test]
+    fn test_layout_reused_until_constraints_change() {
+        let theme = Arc::new(Theme::default());
+        let cache = LayoutCache::new();
+        let row = Row::new().with_children(vec![Box::new(Text::new("Name")), Box::new(Text::new("Value"))]);
+        let frame = |width: f32| {
+            cache.begin_frame(&theme);
+            BuildContext::new(
+                ElementId::new(0),
+                new_shared_element_tree(),
+                Constraints::loose(Size::new(width, 40.0)),
+                theme.clone(),
+            )
+            .with_layout_cache(cache.clone())
+            .build_child(&row);
+            cache.end_frame();
+        };
+
+        // The row and both texts are laid out once
+        frame(300.0);
+        assert_eq!((cache.stats().hits, cache.stats().misses), (0, 3));
+
+        // Unchanged: the row is reused without visiting its children
+        frame(300.0);
+        assert_eq!((cache.stats().hits, cache.stats().misses), (1, 3));
+
+        // Narrower: everything lays out again, and the old size stays cached
+        frame(200.0);
+        assert_eq!((cache.stats().hits, cache.stats().misses), (1, 6));
+        frame(300.0);
+        assert_eq!(cache.stats(), LayoutCacheStats { entries: 6, hits: 2, misses: 6 });
+    }
+}
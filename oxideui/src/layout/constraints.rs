@@ -3,6 +3,8 @@
 //! Implements a constraint-based layout model similar to Flutter's BoxConstraints.
 //! Parent passes constraints down, child measures itself, returns size up.
 
+use serde::{Deserialize, Serialize};
+
 /// Size in logical pixels
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Size {
@@ -186,6 +188,17 @@ impl Constraints {
         }
     }
 
+    /// `self` clamped to fit within `parent`, so an extra constraint can
+    /// narrow the parent's range but never escape it
+    pub fn enforce(&self, parent: &Constraints) -> Self {
+        Self {
+            min_width: self.min_width.clamp(parent.min_width, parent.max_width),
+            max_width: self.max_width.clamp(parent.min_width, parent.max_width),
+            min_height: self.min_height.clamp(parent.min_height, parent.max_height),
+            max_height: self.max_height.clamp(parent.min_height, parent.max_height),
+        }
+    }
+
     /// Tighten to a specific size
     pub fn tighten(&self, size: Size) -> Self {
         Self::tight(size)
@@ -259,6 +272,15 @@ impl Default for EdgeInsets {
     }
 }
 
+/// Direction children are laid out along
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
 /// Alignment options
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Alignment {
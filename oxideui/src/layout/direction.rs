@@ -0,0 +1,118 @@
+//! Right-to-left layout
+//!
+//! `BuildContext::direction()` is RTL when the active locale writes right to
+//! left (Arabic, Hebrew, Persian, Urdu, ...). Layout widgets then place
+//! children from the right, padding declared with `EdgeInsetsDirectional`
+//! swaps its start and end, and `Alignment`s mirror horizontally.
+
+use unic_langid::{CharacterDirection, LanguageIdentifier};
+
+use crate::layout::constraints::{Alignment, EdgeInsets, Size};
+
+/// Horizontal reading direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// The direction `locale` is written in
+    pub fn for_locale(locale: &LanguageIdentifier) -> Self {
+        match locale.character_direction() {
+            CharacterDirection::RTL => Direction::Rtl,
+            _ => Direction::Ltr,
+        }
+    }
+
+    pub fn is_rtl(self) -> bool {
+        self == Direction::Rtl
+    }
+
+    /// Left edge of a child of width `width` placed `offset` from the start
+    /// of a parent of width `parent_width`. Unbounded parents have no right
+    /// edge to start from, so they lay out LTR.
+    pub fn place(self, offset: f32, width: f32, parent_width: f32) -> f32 {
+        match self {
+            Direction::Rtl if parent_width.is_finite() => parent_width - offset - width,
+            _ => offset,
+        }
+    }
+}
+
+/// Insets whose horizontal sides follow the reading direction
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct EdgeInsetsDirectional {
+    pub start: f32,
+    pub top: f32,
+    pub end: f32,
+    pub bottom: f32,
+}
+
+impl EdgeInsetsDirectional {
+    pub const fn only(start: f32, top: f32, end: f32, bottom: f32) -> Self {
+        Self { start, top, end, bottom }
+    }
+
+    /// Physical insets for `direction`
+    pub fn resolve(&self, direction: Direction) -> EdgeInsets {
+        match direction {
+            Direction::Ltr => EdgeInsets::only(self.start, self.top, self.end, self.bottom),
+            Direction::Rtl => EdgeInsets::only(self.end, self.top, self.start, self.bottom),
+        }
+    }
+}
+
+impl EdgeInsets {
+    /// Left and right swapped
+    pub fn mirrored(&self) -> Self {
+        Self::only(self.right, self.top, self.left, self.bottom)
+    }
+}
+
+impl Alignment {
+    /// Left and right swapped
+    pub fn mirrored(&self) -> Self {
+        match self {
+            Alignment::TopLeft => Alignment::TopRight,
+            Alignment::TopRight => Alignment::TopLeft,
+            Alignment::CenterLeft => Alignment::CenterRight,
+            Alignment::CenterRight => Alignment::CenterLeft,
+            Alignment::BottomLeft => Alignment::BottomRight,
+            Alignment::BottomRight => Alignment::BottomLeft,
+            centered => *centered,
+        }
+    }
+
+    /// Like `align`, treating left as the start of the line so RTL layouts mirror
+    pub fn align_directional(&self, size: Size, container_size: Size, direction: Direction) -> (f32, f32) {
+        match direction {
+            Direction::Ltr => self.align(size, container_size),
+            Direction::Rtl => self.mirrored().align(size, container_size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtl_mirroring() {
+        assert_eq!(Direction::for_locale(&"ar-EG".parse().unwrap()), Direction::Rtl);
+        assert_eq!(Direction::for_locale(&"he".parse().unwrap()), Direction::Rtl);
+        assert_eq!(Direction::for_locale(&"en-US".parse().unwrap()), Direction::Ltr);
+
+        let padding = EdgeInsetsDirectional::only(16.0, 4.0, 8.0, 4.0);
+        assert_eq!(padding.resolve(Direction::Rtl), EdgeInsets::only(8.0, 4.0, 16.0, 4.0));
+
+        let (x, _) = Alignment::CenterLeft.align_directional(
+            Size::new(20.0, 10.0),
+            Size::new(100.0, 10.0),
+            Direction::Rtl,
+        );
+        assert_eq!(x, 80.0);
+        assert_eq!(Direction::Rtl.place(10.0, 30.0, 100.0), 60.0);
+    }
+}
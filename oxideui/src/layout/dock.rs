@@ -0,0 +1,398 @@
+//! Panel arrangements for docking and split-pane layouts
+//!
+//! A `DockLayout` is a tree of splits with panels at the leaves, each split
+//! dividing its rect between two children by a ratio. Apps build the default
+//! arrangement, lay panels out with `panel_rects`, and persist whatever the
+//! user arranged with `serialize_layout`:
+//!
+//! ```rust,ignore
+//! let mut dock = DockLayout::new(DockNode::split(
+//!     Axis::Horizontal,
+//!     0.25,
+//!     DockNode::panel("files"),
+//!     DockNode::split(Axis::Vertical, 0.7, DockNode::panel("editor"), DockNode::panel("terminal")),
+//! ));
+//! if let Some(saved) = settings.get("dock") {
+//!     dock.restore_layout(saved.clone())?;
+//! }
+//! settings.insert("dock", dock.serialize_layout());
+//! ```
+//!
+//! The panels in the default arrangement are the ones that exist. Restoring
+//! drops saved panels that no longer exist, giving their space to their
+//! sibling, and panels the saved layout leaves out stay closed until
+//! `open_panel` brings them back. `DockView` renders a layout as split panes
+//! with draggable dividers.
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::render_object::Rect;
+use crate::layout::constraints::Axis;
+
+/// Format version written by `serialize_layout`
+const LAYOUT_VERSION: u32 = 1;
+/// Splits always leave some room for both sides
+const MIN_RATIO: f32 = 0.05;
+
+/// A panel, or a split between two subtrees
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DockNode {
+    Panel {
+        id: String,
+    },
+    Split {
+        axis: Axis,
+        /// Share of the split's extent along `axis` given to `first`
+        ratio: f32,
+        first: Box<DockNode>,
+        second: Box<DockNode>,
+    },
+}
+
+impl DockNode {
+    pub fn panel(id: impl Into<String>) -> Self {
+        DockNode::Panel { id: id.into() }
+    }
+
+    pub fn split(axis: Axis, ratio: f32, first: DockNode, second: DockNode) -> Self {
+        DockNode::Split {
+            axis,
+            ratio: ratio.clamp(MIN_RATIO, 1.0 - MIN_RATIO),
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        let mut panels = Vec::new();
+        self.collect_panels(&mut panels);
+        panels.contains(&id)
+    }
+
+    fn collect_panels<'a>(&'a self, panels: &mut Vec<&'a str>) {
+        match self {
+            DockNode::Panel { id } => panels.push(id),
+            DockNode::Split { first, second, .. } => {
+                first.collect_panels(panels);
+                second.collect_panels(panels);
+            }
+        }
+    }
+
+    /// This subtree without panels outside `known` or seen before; a split
+    /// left with one side collapses into it
+    fn prune(self, known: &BTreeSet<String>, seen: &mut BTreeSet<String>) -> Option<DockNode> {
+        match self {
+            DockNode::Panel { id } => (known.contains(&id) && seen.insert(id.clone())).then_some(DockNode::Panel { id }),
+            DockNode::Split { axis, ratio, first, second } => {
+                match (first.prune(known, seen), second.prune(known, seen)) {
+                    (Some(first), Some(second)) => Some(DockNode::split(axis, ratio, first, second)),
+                    (Some(only), None) | (None, Some(only)) => Some(only),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+
+    /// This subtree with `target` split in two, `id` taking the second half
+    fn insert(self, id: &str, target: &str, axis: Axis) -> DockNode {
+        match self {
+            DockNode::Panel { id: panel } if panel == target => {
+                DockNode::split(axis, 0.5, DockNode::Panel { id: panel }, DockNode::panel(id))
+            }
+            DockNode::Panel { .. } => self,
+            DockNode::Split { axis: split_axis, ratio, first, second } => DockNode::Split {
+                axis: split_axis,
+                ratio,
+                first: Box::new(first.insert(id, target, axis)),
+                second: Box::new(second.insert(id, target, axis)),
+            },
+        }
+    }
+
+    fn layout(&self, bounds: Rect, rects: &mut Vec<(String, Rect)>, splits: &mut Vec<DockSplit>) {
+        match self {
+            DockNode::Panel { id } => rects.push((id.clone(), bounds)),
+            DockNode::Split { axis, ratio, first, second } => {
+                let (a, b) = match axis {
+                    Axis::Horizontal => {
+                        let width = bounds.width * ratio;
+                        (
+                            Rect::new(bounds.x, bounds.y, width, bounds.height),
+                            Rect::new(bounds.x + width, bounds.y, bounds.width - width, bounds.height),
+                        )
+                    }
+                    Axis::Vertical => {
+                        let height = bounds.height * ratio;
+                        (
+                            Rect::new(bounds.x, bounds.y, bounds.width, height),
+                            Rect::new(bounds.x, bounds.y + height, bounds.width, bounds.height - height),
+                        )
+                    }
+                };
+                splits.push(DockSplit { axis: *axis, ratio: *ratio, bounds });
+                first.layout(a, rects, splits);
+                second.layout(b, rects, splits);
+            }
+        }
+    }
+
+    /// Set the ratio of the `index`th split in pre-order, counting from
+    /// `*index`; returns whether it was found
+    fn set_ratio(&mut self, index: &mut usize, value: f32) -> bool {
+        match self {
+            DockNode::Panel { .. } => false,
+            DockNode::Split { ratio, first, second, .. } => {
+                if *index == 0 {
+                    *ratio = value.clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+                    return true;
+                }
+                *index -= 1;
+                first.set_ratio(index, value) || second.set_ratio(index, value)
+            }
+        }
+    }
+}
+
+/// A split as laid out, for drawing and dragging its divider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DockSplit {
+    pub axis: Axis,
+    pub ratio: f32,
+    /// The rect shared by both sides
+    pub bounds: Rect,
+}
+
+impl DockSplit {
+    /// The divider between the two sides, `thickness` wide and centered on
+    /// the boundary
+    pub fn divider(&self, thickness: f32) -> Rect {
+        let b = self.bounds;
+        match self.axis {
+            Axis::Horizontal => Rect::new(b.x + b.width * self.ratio - thickness / 2.0, b.y, thickness, b.height),
+            Axis::Vertical => Rect::new(b.x, b.y + b.height * self.ratio - thickness / 2.0, b.width, thickness),
+        }
+    }
+
+    /// The ratio that puts the boundary at `(x, y)`
+    pub fn ratio_at(&self, x: f32, y: f32) -> f32 {
+        let b = self.bounds;
+        let ratio = match self.axis {
+            Axis::Horizontal => (x - b.x) / b.width.max(1.0),
+            Axis::Vertical => (y - b.y) / b.height.max(1.0),
+        };
+        ratio.clamp(MIN_RATIO, 1.0 - MIN_RATIO)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedLayout {
+    version: u32,
+    root: Option<DockNode>,
+}
+
+/// The current arrangement of an app's dockable panels
+#[derive(Debug, Clone, PartialEq)]
+pub struct DockLayout {
+    root: Option<DockNode>,
+    /// Every panel the app has, from the default arrangement
+    known: BTreeSet<String>,
+}
+
+impl DockLayout {
+    /// Start from `default`, whose panels are all the panels that exist
+    pub fn new(default: DockNode) -> Self {
+        let mut panels = Vec::new();
+        default.collect_panels(&mut panels);
+        let known = panels.into_iter().map(String::from).collect();
+        Self {
+            root: Some(default),
+            known,
+        }
+    }
+
+    pub fn root(&self) -> Option<&DockNode> {
+        self.root.as_ref()
+    }
+
+    /// Open panels, first to last
+    pub fn panels(&self) -> Vec<&str> {
+        let mut panels = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect_panels(&mut panels);
+        }
+        panels
+    }
+
+    /// Rect of each open panel within `bounds`
+    pub fn panel_rects(&self, bounds: Rect) -> Vec<(String, Rect)> {
+        self.layout(bounds).0
+    }
+
+    /// Every split within `bounds`, in the order `set_ratio` counts them
+    pub fn splits(&self, bounds: Rect) -> Vec<DockSplit> {
+        self.layout(bounds).1
+    }
+
+    fn layout(&self, bounds: Rect) -> (Vec<(String, Rect)>, Vec<DockSplit>) {
+        let (mut rects, mut splits) = (Vec::new(), Vec::new());
+        if let Some(root) = &self.root {
+            root.layout(bounds, &mut rects, &mut splits);
+        }
+        (rects, splits)
+    }
+
+    /// Move the divider of the `index`th split, as returned by `splits`
+    pub fn set_ratio(&mut self, index: usize, ratio: f32) {
+        if let Some(root) = &mut self.root {
+            let mut index = index;
+            root.set_ratio(&mut index, ratio);
+        }
+    }
+
+    /// Whether `id` is open
+    pub fn is_open(&self, id: &str) -> bool {
+        self.panels().contains(&id)
+    }
+
+    /// Open a closed panel by splitting `next_to` along `axis`, the new
+    /// panel taking the second half. With nothing open it fills the layout,
+    /// and an open panel stays where it is.
+    pub fn open_panel(&mut self, id: &str, next_to: &str, axis: Axis) -> Result<()> {
+        if !self.known.contains(id) {
+            bail!("Unknown dock panel '{}'", id);
+        }
+        if self.is_open(id) {
+            return Ok(());
+        }
+        self.root = match self.root.take() {
+            None => Some(DockNode::panel(id)),
+            Some(root) if root.contains(next_to) => Some(root.insert(id, next_to, axis)),
+            Some(root) => {
+                self.root = Some(root);
+                bail!("Dock panel '{}' is not open", next_to);
+            }
+        };
+        Ok(())
+    }
+
+    /// Close `id`, giving its space to its sibling
+    pub fn close_panel(&mut self, id: &str) {
+        let known = self.known.iter().filter(|panel| *panel != id).cloned().collect();
+        self.root = self.root.take().and_then(|root| root.prune(&known, &mut BTreeSet::new()));
+    }
+
+    /// Replace the arrangement, e.g. after the user drags a panel or a
+    /// divider; unknown and repeated panels are dropped
+    pub fn set_root(&mut self, root: DockNode) {
+        self.root = root.prune(&self.known, &mut BTreeSet::new());
+    }
+
+    /// The arrangement as JSON, for saving between sessions
+    pub fn serialize_layout(&self) -> serde_json::Value {
+        let saved = SavedLayout {
+            version: LAYOUT_VERSION,
+            root: self.root.clone(),
+        };
+        serde_json::to_value(saved).expect("dock layouts serialize to JSON")
+    }
+
+    /// Restore an arrangement saved by `serialize_layout`. Panels that no
+    /// longer exist are dropped. On error the current arrangement is kept.
+    pub fn restore_layout(&mut self, value: serde_json::Value) -> Result<()> {
+        let saved: SavedLayout = serde_json::from_value(value).context("Invalid dock layout")?;
+        if saved.version > LAYOUT_VERSION {
+            bail!("Dock layout version {} is newer than supported", saved.version);
+        }
+        self.root = saved.root.and_then(|root| root.prune(&self.known, &mut BTreeSet::new()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_round_trips_and_drops_missing_panels() {
+        let default = || {
+            DockNode::split(
+                Axis::Horizontal,
+                0.25,
+                DockNode::panel("files"),
+                DockNode::split(Axis::Vertical, 0.7, DockNode::panel("editor"), DockNode::panel("terminal")),
+            )
+        };
+        let mut dock = DockLayout::new(default());
+        dock.set_root(DockNode::split(
+            Axis::Vertical,
+            0.5,
+            DockNode::panel("editor"),
+            DockNode::split(Axis::Horizontal, 0.5, DockNode::panel("files"), DockNode::panel("terminal")),
+        ));
+        let saved = dock.serialize_layout();
+
+        let mut restored = DockLayout::new(default());
+        restored.restore_layout(saved.clone()).unwrap();
+        assert_eq!(restored, dock);
+        let rects = restored.panel_rects(Rect::new(0.0, 0.0, 800.0, 600.0));
+        assert_eq!(rects[1], ("files".to_string(), Rect::new(0.0, 300.0, 400.0, 300.0)));
+
+        // A later version without a terminal gives its space to its sibling
+        let mut smaller = DockLayout::new(DockNode::split(
+            Axis::Horizontal,
+            0.25,
+            DockNode::panel("files"),
+            DockNode::panel("editor"),
+        ));
+        smaller.restore_layout(saved).unwrap();
+        assert_eq!(smaller.panels(), ["editor", "files"]);
+        assert_eq!(
+            smaller.panel_rects(Rect::new(0.0, 0.0, 800.0, 600.0))[1],
+            ("files".to_string(), Rect::new(0.0, 300.0, 800.0, 300.0))
+        );
+
+        assert!(smaller.restore_layout(serde_json::json!({ "version": 1, "root": 5 })).is_err());
+        assert_eq!(smaller.panels(), ["editor", "files"]);
+    }
+
+    #[test]
+    fn test_closed_panels_reopen_and_dividers_move() {
+        let mut dock = DockLayout::new(DockNode::split(
+            Axis::Horizontal,
+            0.25,
+            DockNode::panel("files"),
+            DockNode::split(Axis::Vertical, 0.7, DockNode::panel("editor"), DockNode::panel("terminal")),
+        ));
+        dock.close_panel("terminal");
+        assert_eq!(dock.panels(), ["files", "editor"]);
+
+        assert!(dock.open_panel("search", "editor", Axis::Vertical).is_err());
+        assert!(dock.open_panel("terminal", "search", Axis::Vertical).is_err());
+        dock.open_panel("terminal", "editor", Axis::Vertical).unwrap();
+        dock.open_panel("terminal", "files", Axis::Vertical).unwrap();
+        assert_eq!(dock.panels(), ["files", "editor", "terminal"]);
+        let bounds = Rect::new(0.0, 0.0, 800.0, 600.0);
+        assert_eq!(dock.panel_rects(bounds)[2], ("terminal".to_string(), Rect::new(200.0, 300.0, 600.0, 300.0)));
+
+        // Splits are counted outermost first
+        let splits = dock.splits(bounds);
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].divider(4.0), Rect::new(198.0, 0.0, 4.0, 600.0));
+        assert_eq!(splits[1].ratio_at(500.0, 150.0), 0.25);
+        dock.set_ratio(1, splits[1].ratio_at(500.0, 150.0));
+        assert_eq!(dock.panel_rects(bounds)[1], ("editor".to_string(), Rect::new(200.0, 0.0, 600.0, 150.0)));
+
+        // With everything closed the panel fills the layout
+        for panel in ["files", "editor", "terminal"] {
+            dock.close_panel(panel);
+        }
+        assert!(dock.root().is_none());
+        dock.open_panel("editor", "files", Axis::Horizontal).unwrap();
+        assert_eq!(dock.panel_rects(bounds), vec![("editor".to_string(), bounds)]);
+    }
+}
@@ -1,8 +1,19 @@
 mod advanced;
+mod cache;
 pub mod constraints;
+mod direction;
+mod dock;
+mod popup;
+mod solver;
 
 pub use crate::layout::advanced::{
-    AlignContent, AlignItems, FlexDirection, FlexLayout, FlexWrap, GridLayout, GridTrack,
-    JustifyContent, LayoutEngine,
+    resolve_flexible_lengths, AlignContent, AlignItems, FlexDirection, FlexItem, FlexLayout, FlexWrap,
+    GridAutoFlow, GridItem, GridLayout, GridTrack, JustifyContent, LayoutEngine, LayoutNode, LayoutType,
 };
-pub use constraints::{Alignment, Constraints, EdgeInsets, Size};
+pub use cache::{children_hash, debug_hash, LayoutCache, LayoutCacheStats};
+pub(crate) use cache::LayoutKey;
+pub use constraints::{Alignment, Axis, Constraints, EdgeInsets, Size};
+pub use direction::{Direction, EdgeInsetsDirectional};
+pub use dock::{DockLayout, DockNode, DockSplit};
+pub use popup::{popup_window, PopupAlign, PopupPlacement, PopupSide};
+pub use solver::{Expression, LayoutConstraint, LayoutSolver, Relation, Strength};
@@ -0,0 +1,169 @@
+//! Placing popups beside their anchor
+//!
+//! A `PopupPlacement` puts a popup of measured size on the preferred side of
+//! an anchor rect. If the popup would overflow the window on that side, it
+//! flips to the opposite side when there is more room there. Then it is
+//! clamped inside the window. Menus, tooltips and calendars all use it, so
+//! they behave the same near window edges. Widgets place their popups on
+//! every build, so the popups follow their anchors when the window is
+//! resized or the content is scrolled.
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Point, Rect};
+use crate::layout::constraints::Size;
+
+/// Side of the anchor a popup opens on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopupSide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl PopupSide {
+    pub fn opposite(self) -> Self {
+        match self {
+            PopupSide::Top => PopupSide::Bottom,
+            PopupSide::Bottom => PopupSide::Top,
+            PopupSide::Left => PopupSide::Right,
+            PopupSide::Right => PopupSide::Left,
+        }
+    }
+}
+
+/// How a popup lines up with its anchor along the side it opens on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopupAlign {
+    Start,
+    Center,
+    End,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PopupPlacement {
+    pub side: PopupSide,
+    pub align: PopupAlign,
+    /// Gap between the anchor and the popup
+    pub offset: f32,
+    /// Space kept clear at the window edges
+    pub margin: f32,
+}
+
+impl PopupPlacement {
+    pub fn new(side: PopupSide) -> Self {
+        Self {
+            side,
+            align: PopupAlign::Start,
+            offset: 4.0,
+            margin: 8.0,
+        }
+    }
+
+    pub fn with_align(mut self, align: PopupAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Where a popup of `size` goes beside `anchor` within `window`, and
+    /// the side it ended up on
+    pub fn place(&self, anchor: Rect, size: Size, window: Rect) -> (Rect, PopupSide) {
+        let room = |side: PopupSide| match side {
+            PopupSide::Top => anchor.y - self.offset - (window.y + self.margin),
+            PopupSide::Bottom => window.y + window.height - self.margin - (anchor.y + anchor.height + self.offset),
+            PopupSide::Left => anchor.x - self.offset - (window.x + self.margin),
+            PopupSide::Right => window.x + window.width - self.margin - (anchor.x + anchor.width + self.offset),
+        };
+        let needed = match self.side {
+            PopupSide::Top | PopupSide::Bottom => size.height,
+            PopupSide::Left | PopupSide::Right => size.width,
+        };
+        let opposite = self.side.opposite();
+        let side = if room(self.side) < needed && room(opposite) > room(self.side) {
+            opposite
+        } else {
+            self.side
+        };
+
+        let align = |start: f32, anchor_length: f32, length: f32| match self.align {
+            PopupAlign::Start => start,
+            PopupAlign::Center => start + (anchor_length - length) / 2.0,
+            PopupAlign::End => start + anchor_length - length,
+        };
+        let (x, y) = match side {
+            PopupSide::Top => (align(anchor.x, anchor.width, size.width), anchor.y - self.offset - size.height),
+            PopupSide::Bottom => (align(anchor.x, anchor.width, size.width), anchor.y + anchor.height + self.offset),
+            PopupSide::Left => (anchor.x - self.offset - size.width, align(anchor.y, anchor.height, size.height)),
+            PopupSide::Right => (anchor.x + anchor.width + self.offset, align(anchor.y, anchor.height, size.height)),
+        };
+
+        // Popups bigger than the window keep their start edge in view
+        let clamp = |value: f32, length: f32, start: f32, extent: f32| {
+            value.min(start + extent - self.margin - length).max(start + self.margin)
+        };
+        let rect = Rect::new(
+            clamp(x, size.width, window.x, window.width),
+            clamp(y, size.height, window.y, window.height),
+            size.width,
+            size.height,
+        );
+        (rect, side)
+    }
+
+    /// Place a popup of `size` beside the element being built, which is
+    /// `anchor_size` at its origin, in the element's local coordinates
+    pub fn place_for(&self, ctx: &BuildContext, anchor_size: Size, size: Size) -> (Rect, PopupSide) {
+        self.place(Rect::from_size(anchor_size), size, popup_window(ctx))
+    }
+}
+
+/// The window in the local coordinates of the element being built, from
+/// where the last frame laid the element out. Before its first layout, or
+/// when headless, there is nothing to flip or clamp against.
+pub fn popup_window(ctx: &BuildContext) -> Rect {
+    let origin = {
+        let tree = ctx.element_tree.read();
+        tree.get(ctx.element_id)
+            .filter(|element| element.bounds.is_some())
+            .map(|element| element.transform.map_point(Point::new(0.0, 0.0)))
+    };
+    match (origin, ctx.window_size()) {
+        (Some(origin), Some(window)) => Rect::new(-origin.x, -origin.y, window.width, window.height),
+        _ => Rect::new(f32::MIN / 4.0, f32::MIN / 4.0, f32::MAX / 2.0, f32::MAX / 2.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_popup_flips_and_clamps() {
+        let window = Rect::new(0.0, 0.0, 800.0, 600.0);
+        let menu = PopupPlacement::new(PopupSide::Bottom);
+        let size = Size::new(200.0, 150.0);
+
+        // Room below: opens below, aligned to the anchor's start
+        let (rect, side) = menu.place(Rect::new(100.0, 100.0, 120.0, 30.0), size, window);
+        assert_eq!((rect, side), (Rect::new(100.0, 134.0, 200.0, 150.0), PopupSide::Bottom));
+
+        // Near the bottom edge it flips above, and is pulled in from the right
+        let (rect, side) = menu.place(Rect::new(700.0, 500.0, 120.0, 30.0), size, window);
+        assert_eq!((rect, side), (Rect::new(592.0, 346.0, 200.0, 150.0), PopupSide::Top));
+
+        // No room either side: stays on the roomier side, clamped in the window
+        let tall = Size::new(200.0, 500.0);
+        let (rect, side) = menu.place(Rect::new(100.0, 250.0, 120.0, 30.0), tall, window);
+        assert_eq!((rect, side), (Rect::new(100.0, 92.0, 200.0, 500.0), PopupSide::Bottom));
+    }
+}
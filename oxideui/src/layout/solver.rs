@@ -0,0 +1,542 @@
+//! Linear constraint solving
+//!
+//! `LayoutSolver` is an incremental Cassowary solver: it finds values for
+//! named variables that satisfy every required constraint and as many of
+//! the weaker ones as their strengths allow. Constraints relate linear
+//! expressions of variables:
+//!
+//! ```rust,ignore
+//! let mut solver = LayoutSolver::new();
+//! solver.add_constraint(LayoutConstraint::equal(Expression::var("left") + Expression::var("width"), 300.0))?;
+//! solver.add_constraint(LayoutConstraint::at_least(Expression::var("width"), 100.0))?;
+//! solver.add_constraint(LayoutConstraint::equal(Expression::var("left"), 250.0).with_strength(Strength::WEAK))?;
+//! assert_eq!(solver.get_value("left"), Some(200.0));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use anyhow::{bail, Result};
+
+const EPSILON: f64 = 1.0e-8;
+
+fn near_zero(value: f64) -> bool {
+    value.abs() < EPSILON
+}
+
+/// How hard the solver tries to satisfy a constraint. A required
+/// constraint always holds; weaker ones give way to stronger ones.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Strength(f64);
+
+impl Strength {
+    pub const REQUIRED: Strength = Strength(1_001_001_000.0);
+    pub const STRONG: Strength = Strength(1_000_000.0);
+    pub const MEDIUM: Strength = Strength(1_000.0);
+    pub const WEAK: Strength = Strength(1.0);
+}
+
+/// A linear combination of variables plus a constant
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expression {
+    pub terms: Vec<(String, f32)>,
+    pub constant: f32,
+}
+
+impl Expression {
+    pub fn var(name: impl Into<String>) -> Self {
+        Self {
+            terms: vec![(name.into(), 1.0)],
+            constant: 0.0,
+        }
+    }
+
+    pub fn constant(constant: f32) -> Self {
+        Self {
+            terms: Vec::new(),
+            constant,
+        }
+    }
+}
+
+impl From<f32> for Expression {
+    fn from(constant: f32) -> Self {
+        Self::constant(constant)
+    }
+}
+
+impl From<&str> for Expression {
+    fn from(name: &str) -> Self {
+        Self::var(name)
+    }
+}
+
+impl<E: Into<Expression>> Add<E> for Expression {
+    type Output = Expression;
+
+    fn add(mut self, other: E) -> Expression {
+        let other = other.into();
+        self.terms.extend(other.terms);
+        self.constant += other.constant;
+        self
+    }
+}
+
+impl<E: Into<Expression>> Sub<E> for Expression {
+    type Output = Expression;
+
+    fn sub(self, other: E) -> Expression {
+        self + -other.into()
+    }
+}
+
+impl Mul<f32> for Expression {
+    type Output = Expression;
+
+    fn mul(mut self, factor: f32) -> Expression {
+        self.terms.iter_mut().for_each(|(_, coefficient)| *coefficient *= factor);
+        self.constant *= factor;
+        self
+    }
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        self * -1.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+/// `expression <relation> 0`, held with `strength`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutConstraint {
+    pub expression: Expression,
+    pub relation: Relation,
+    pub strength: Strength,
+}
+
+impl LayoutConstraint {
+    fn new(lhs: impl Into<Expression>, relation: Relation, rhs: impl Into<Expression>) -> Self {
+        Self {
+            expression: lhs.into() - rhs,
+            relation,
+            strength: Strength::REQUIRED,
+        }
+    }
+
+    /// `lhs == rhs`
+    pub fn equal(lhs: impl Into<Expression>, rhs: impl Into<Expression>) -> Self {
+        Self::new(lhs, Relation::Equal, rhs)
+    }
+
+    /// `lhs >= rhs`
+    pub fn at_least(lhs: impl Into<Expression>, rhs: impl Into<Expression>) -> Self {
+        Self::new(lhs, Relation::GreaterOrEqual, rhs)
+    }
+
+    /// `lhs <= rhs`
+    pub fn at_most(lhs: impl Into<Expression>, rhs: impl Into<Expression>) -> Self {
+        Self::new(lhs, Relation::LessOrEqual, rhs)
+    }
+
+    pub fn with_strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SymbolKind {
+    /// A user variable
+    External,
+    /// Turns an inequality into an equality
+    Slack,
+    /// How far a non-required constraint is from holding
+    Error,
+    /// Marks a required equality
+    Dummy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Symbol {
+    id: usize,
+    kind: SymbolKind,
+}
+
+/// `constant + sum(coefficient * symbol)`; as a tableau row, the value of
+/// its basic symbol
+#[derive(Debug, Clone, Default)]
+struct Row {
+    cells: BTreeMap<Symbol, f64>,
+    constant: f64,
+}
+
+impl Row {
+    fn new(constant: f64) -> Self {
+        Self {
+            cells: BTreeMap::new(),
+            constant,
+        }
+    }
+
+    fn coefficient(&self, symbol: Symbol) -> f64 {
+        self.cells.get(&symbol).copied().unwrap_or(0.0)
+    }
+
+    fn insert_symbol(&mut self, symbol: Symbol, coefficient: f64) {
+        let value = self.cells.entry(symbol).or_insert(0.0);
+        *value += coefficient;
+        if near_zero(*value) {
+            self.cells.remove(&symbol);
+        }
+    }
+
+    fn insert_row(&mut self, row: &Row, coefficient: f64) {
+        self.constant += row.constant * coefficient;
+        for (&symbol, &value) in &row.cells {
+            self.insert_symbol(symbol, value * coefficient);
+        }
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+        self.cells.values_mut().for_each(|value| *value = -*value);
+    }
+
+    /// Rearrange `0 = row` into `symbol = row'`
+    fn solve_for(&mut self, symbol: Symbol) {
+        let coefficient = -1.0 / self.cells.remove(&symbol).unwrap_or(1.0);
+        self.constant *= coefficient;
+        self.cells.values_mut().for_each(|value| *value *= coefficient);
+    }
+
+    /// Rearrange `lhs = row` into `rhs = row'`
+    fn solve_for_symbols(&mut self, lhs: Symbol, rhs: Symbol) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for(rhs);
+    }
+
+    /// Replace `symbol` with `row`
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        if let Some(coefficient) = self.cells.remove(&symbol) {
+            self.insert_row(row, coefficient);
+        }
+    }
+}
+
+/// Symbols a constraint added, to tell which to pivot on
+#[derive(Debug, Clone, Copy)]
+struct Tag {
+    marker: Symbol,
+    other: Option<Symbol>,
+}
+
+/// Cassowary constraint solver over named variables
+#[derive(Debug, Clone, Default)]
+pub struct LayoutSolver {
+    variables: HashMap<String, Symbol>,
+    rows: BTreeMap<Symbol, Row>,
+    objective: Row,
+    /// Objective while looking for a feasible start for a new constraint
+    artificial: Option<Row>,
+    next_id: usize,
+}
+
+impl LayoutSolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop every constraint and variable, then add `constraints`. Returns
+    /// false if any required constraint couldn't be satisfied; the others
+    /// still hold.
+    pub fn solve(&mut self, constraints: &[LayoutConstraint]) -> bool {
+        *self = Self::new();
+        constraints
+            .iter()
+            .fold(true, |satisfied, constraint| self.add_constraint(constraint.clone()).is_ok() && satisfied)
+    }
+
+    /// Add a constraint and re-solve. A required constraint that conflicts
+    /// with those already added is rejected, leaving the solver unchanged.
+    pub fn add_constraint(&mut self, constraint: LayoutConstraint) -> Result<()> {
+        let (row, tag) = self.create_row(&constraint);
+        let added = self.insert_row(row, tag, &constraint);
+        if added.is_err() {
+            self.remove_tag(tag, constraint.strength);
+        }
+        added
+    }
+
+    /// Current value of `name`; variables in no constraint have none
+    pub fn get_value(&self, name: &str) -> Option<f32> {
+        let symbol = self.variables.get(name)?;
+        Some(self.rows.get(symbol).map_or(0.0, |row| row.constant) as f32)
+    }
+
+    fn insert_row(&mut self, mut row: Row, tag: Tag, constraint: &LayoutConstraint) -> Result<()> {
+        let subject = match Self::choose_subject(&row, tag) {
+            Some(subject) => Some(subject),
+            None if row.cells.keys().all(|symbol| symbol.kind == SymbolKind::Dummy) => {
+                if !near_zero(row.constant) {
+                    bail!("unsatisfiable constraint: {:?}", constraint.expression);
+                }
+                Some(tag.marker)
+            }
+            None => None,
+        };
+
+        match subject {
+            Some(subject) => {
+                row.solve_for(subject);
+                self.substitute(subject, &row);
+                self.rows.insert(subject, row);
+            }
+            None => {
+                if !self.add_with_artificial_variable(row) {
+                    bail!("unsatisfiable constraint: {:?}", constraint.expression);
+                }
+            }
+        }
+        self.optimize(false)
+    }
+
+    /// Take back whatever a rejected constraint left in the tableau: its
+    /// errors' weight in the objective, then the row its marker is in
+    fn remove_tag(&mut self, tag: Tag, strength: Strength) {
+        self.artificial = None;
+        for error in std::iter::once(tag.marker).chain(tag.other).filter(|symbol| symbol.kind == SymbolKind::Error) {
+            match self.rows.get(&error) {
+                Some(row) => self.objective.insert_row(&row.clone(), -strength.0),
+                None => self.objective.insert_symbol(error, -strength.0),
+            }
+        }
+
+        let marker = tag.marker;
+        if self.rows.remove(&marker).is_none() {
+            if let Some(leaving) = self.marker_leaving_row(marker) {
+                let mut row = self.rows.remove(&leaving).unwrap_or_default();
+                row.solve_for_symbols(leaving, marker);
+                self.substitute(marker, &row);
+            }
+        }
+        let _ = self.optimize(false);
+    }
+
+    /// Row to pivot a non-basic marker into before dropping it: the
+    /// restricted row it limits most, else any row it appears in
+    fn marker_leaving_row(&self, marker: Symbol) -> Option<Symbol> {
+        let ratio = |row: &Row| row.constant / row.coefficient(marker);
+        let containing = || self.rows.iter().filter(|(_, row)| row.coefficient(marker) != 0.0);
+        let restricted = |negative: bool| {
+            containing()
+                .filter(|(symbol, row)| symbol.kind != SymbolKind::External && (row.coefficient(marker) < 0.0) == negative)
+                .map(|(&symbol, row)| (symbol, ratio(row).abs()))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(symbol, _)| symbol)
+        };
+        restricted(true)
+            .or_else(|| restricted(false))
+            .or_else(|| containing().map(|(&symbol, _)| symbol).next())
+    }
+
+    fn symbol(&mut self, kind: SymbolKind) -> Symbol {
+        self.next_id += 1;
+        Symbol { id: self.next_id, kind }
+    }
+
+    fn variable(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.variables.get(name) {
+            return symbol;
+        }
+        let symbol = self.symbol(SymbolKind::External);
+        self.variables.insert(name.to_string(), symbol);
+        symbol
+    }
+
+    /// The constraint as a row in terms of the current non-basic symbols,
+    /// with slack and error symbols for inequalities and weak constraints
+    fn create_row(&mut self, constraint: &LayoutConstraint) -> (Row, Tag) {
+        let mut row = Row::new(constraint.expression.constant as f64);
+        for (name, coefficient) in &constraint.expression.terms {
+            let coefficient = *coefficient as f64;
+            if near_zero(coefficient) {
+                continue;
+            }
+            let symbol = self.variable(name);
+            match self.rows.get(&symbol) {
+                Some(basic) => row.insert_row(&basic.clone(), coefficient),
+                None => row.insert_symbol(symbol, coefficient),
+            }
+        }
+
+        let strength = constraint.strength.0;
+        let required = constraint.strength >= Strength::REQUIRED;
+        let tag = match constraint.relation {
+            Relation::LessOrEqual | Relation::GreaterOrEqual => {
+                let sign = if constraint.relation == Relation::LessOrEqual { 1.0 } else { -1.0 };
+                let slack = self.symbol(SymbolKind::Slack);
+                row.insert_symbol(slack, sign);
+                let other = (!required).then(|| {
+                    let error = self.symbol(SymbolKind::Error);
+                    row.insert_symbol(error, -sign);
+                    self.objective.insert_symbol(error, strength);
+                    error
+                });
+                Tag { marker: slack, other }
+            }
+            Relation::Equal if required => {
+                let dummy = self.symbol(SymbolKind::Dummy);
+                row.insert_symbol(dummy, 1.0);
+                Tag { marker: dummy, other: None }
+            }
+            Relation::Equal => {
+                let plus = self.symbol(SymbolKind::Error);
+                let minus = self.symbol(SymbolKind::Error);
+                row.insert_symbol(plus, -1.0);
+                row.insert_symbol(minus, 1.0);
+                self.objective.insert_symbol(plus, strength);
+                self.objective.insert_symbol(minus, strength);
+                Tag { marker: plus, other: Some(minus) }
+            }
+        };
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+        (row, tag)
+    }
+
+    /// Symbol to make basic for a new row: any variable, otherwise a new
+    /// slack or error symbol with a negative coefficient
+    fn choose_subject(row: &Row, tag: Tag) -> Option<Symbol> {
+        if let Some(&symbol) = row.cells.keys().find(|symbol| symbol.kind == SymbolKind::External) {
+            return Some(symbol);
+        }
+        std::iter::once(tag.marker)
+            .chain(tag.other)
+            .filter(|symbol| matches!(symbol.kind, SymbolKind::Slack | SymbolKind::Error))
+            .find(|&symbol| row.coefficient(symbol) < 0.0)
+    }
+
+    /// Find a feasible basis for `row` by minimizing an artificial variable
+    /// standing in for it. False if it can't reach zero.
+    fn add_with_artificial_variable(&mut self, row: Row) -> bool {
+        let artificial = self.symbol(SymbolKind::Slack);
+        self.rows.insert(artificial, row.clone());
+        self.artificial = Some(row);
+        let optimized = self.optimize(true).is_ok();
+        let satisfied = self.artificial.take().is_some_and(|row| optimized && near_zero(row.constant));
+
+        if let Some(mut row) = self.rows.remove(&artificial) {
+            if row.cells.is_empty() {
+                return satisfied;
+            }
+            let Some(&entering) = row.cells.keys().find(|symbol| matches!(symbol.kind, SymbolKind::Slack | SymbolKind::Error))
+            else {
+                return false;
+            };
+            row.solve_for_symbols(artificial, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+        for row in self.rows.values_mut() {
+            row.cells.remove(&artificial);
+        }
+        self.objective.cells.remove(&artificial);
+        satisfied
+    }
+
+    /// Replace `symbol` with `row` throughout the tableau
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        for basic in self.rows.values_mut() {
+            basic.substitute(symbol, row);
+        }
+        self.objective.substitute(symbol, row);
+        if let Some(artificial) = &mut self.artificial {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    /// Pivot until the objective (or the artificial one) can't decrease
+    fn optimize(&mut self, artificial: bool) -> Result<()> {
+        loop {
+            let objective = if artificial { self.artificial.as_ref() } else { Some(&self.objective) };
+            let entering = objective.and_then(|objective| {
+                objective
+                    .cells
+                    .iter()
+                    .find(|(symbol, &value)| symbol.kind != SymbolKind::Dummy && value < 0.0)
+                    .map(|(&symbol, _)| symbol)
+            });
+            let Some(entering) = entering else {
+                return Ok(());
+            };
+
+            // The row that limits how far `entering` can grow
+            let leaving = self
+                .rows
+                .iter()
+                .filter(|(symbol, row)| symbol.kind != SymbolKind::External && row.coefficient(entering) < 0.0)
+                .map(|(&symbol, row)| (symbol, -row.constant / row.coefficient(entering)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(symbol, _)| symbol);
+            let Some(leaving) = leaving else {
+                bail!("the objective is unbounded");
+            };
+
+            let mut row = self.rows.remove(&leaving).unwrap_or_default();
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weaker_constraints_give_way() {
+        let mut solver = LayoutSolver::new();
+        let width = || Expression::var("width");
+        let left = || Expression::var("left");
+        solver.add_constraint(LayoutConstraint::equal(left() + width(), 300.0)).unwrap();
+        solver.add_constraint(LayoutConstraint::at_least(width(), 100.0)).unwrap();
+        solver
+            .add_constraint(LayoutConstraint::equal(left(), 250.0).with_strength(Strength::WEAK))
+            .unwrap();
+        // The weak preference moves left as far as the minimum width allows
+        assert_eq!(solver.get_value("left"), Some(200.0));
+        assert_eq!(solver.get_value("width"), Some(100.0));
+
+        // A stronger preference wins over the weak one
+        solver
+            .add_constraint(LayoutConstraint::equal(width(), 180.0).with_strength(Strength::STRONG))
+            .unwrap();
+        assert_eq!(solver.get_value("left"), Some(120.0));
+
+        // Conflicting requirements are rejected and change nothing
+        assert!(solver.add_constraint(LayoutConstraint::at_most(width(), 50.0)).is_err());
+        assert_eq!(solver.get_value("width"), Some(180.0));
+        // ...and leave nothing behind to hold back later preferences
+        solver
+            .add_constraint(LayoutConstraint::equal(width(), 250.0).with_strength(Strength::REQUIRED))
+            .unwrap();
+        assert_eq!(solver.get_value("left"), Some(50.0));
+        assert!(!solver.solve(&[
+            LayoutConstraint::equal("x", 1.0),
+            LayoutConstraint::equal("x", 2.0),
+        ]));
+        assert_eq!(solver.get_value("x"), Some(1.0));
+    }
+}
@@ -111,6 +111,9 @@
 //! // Subscribe element to state
 //! state.subscribe(element_id);
 //!
+//! // Or read through the build context to subscribe automatically
+//! let value = ctx.watch(&state);
+//!
 //! // Update triggers automatic rebuild
 //! state.set(42);
 //! state.update(|val| *val += 1);
@@ -167,7 +170,23 @@
 //! a11y.set_role(element, AccessibilityRole::Button);
 //! a11y.announce("Form submitted successfully");
 //! ```
+//!
+//! Or declare it alongside the widgets with `Semantics`, which the runtime
+//! exports each frame:
+//! ```rust,no_run
+//! use oxideui::core::AccessibilityRole;
+//! use oxideui::widgets::{Button, Semantics};
+//!
+//! let submit = Semantics::new(Button::new("Submit"))
+//!     .with_role(AccessibilityRole::Button)
+//!     .with_hint("Sends the form");
+//! ```
+#[macro_use]
+mod profiling;
+
+pub mod assets;
 pub mod core;
+pub mod i18n;
 pub mod layout;
 pub mod platform;
 pub mod render;
@@ -178,24 +197,57 @@ pub mod theming;
 pub mod animation;
 pub mod production;
 
+// Asset re-exports
+pub use assets::Assets;
 // Core re-exports
-pub use core::{BuildContext, Color, RenderObject, Theme};
+pub use core::{BuildContext, Color, RenderEffect, RenderObject, TextAlign, TextDecoration, Theme};
 pub use core::context::ThemeProvider;
 pub use core::{StatefulWidget, StatelessWidget, Widget, WidgetKey, WidgetNode, WidgetState};
 pub use core::event::{UiEvent, EventResult, MouseButton, Vector2, Modifiers};
+pub use core::{FocusRing, Shortcut, ShortcutKey};
 // Layout re-exports
-pub use layout::{Alignment, Constraints, EdgeInsets, Size};
+pub use layout::{Alignment, Axis, Constraints, Direction, EdgeInsets, EdgeInsetsDirectional, Size};
+pub use render::TextOverflow;
+#[cfg(feature = "skia")]
+pub use render::RgbaImage;
 // Runtime re-exports
-pub use runtime::Runtime;
+pub use runtime::{EventSender, Runtime, RuntimeProxy, UiHandle};
+#[cfg(feature = "skia")]
+pub use runtime::{EmbeddedUi, OffscreenRenderer};
+#[cfg(feature = "devtools")]
+pub use runtime::DevTools;
+// Platform re-exports
+pub use platform::{FullscreenMode, WindowConfig, WindowController, WindowIcon};
+#[cfg(feature = "global-hotkeys")]
+pub use platform::{HotkeyAction, HotkeyBinding};
+#[cfg(feature = "notifications")]
+pub use platform::{Notification, NotificationResponse, Notifications};
+#[cfg(feature = "tray")]
+pub use platform::{TrayConfig, TrayEvent, TrayMenuEntry};
 // Theming re-exports
-pub use theming::{ThemeConfig, ThemeColors, load_theme_from_file};
+pub use theming::{
+    ButtonStyle, ButtonVariant, CardStyle, ColorRole, Density, DesignTokens, InputStyle, StateColors, StyleProperties, StyleState, Stylesheet,
+    TextVariant, ThemeConfig, ThemeColors, ThemeController, ThemeManager, ThemeMode, TypeStyle, Typography,
+    WidgetStyles, load_theme_from_file,
+};
 // Widget re-exports
-pub use widgets::basic::{Container, Text, Column, Row, Center};
+pub use widgets::basic::{Container, DefaultTextStyle, Text, Column, Row, Center};
 pub use widgets::element_widgets::*;
 pub use widgets::layout_widgets::*;
 pub use widgets::complex_widgets::*;
+pub use widgets::memo::Memo;
+pub use widgets::error_boundary::{BoundaryError, ErrorBoundary};
+pub use widgets::suspense::Suspense;
+pub use widgets::clip::{ClipOval, ClipPath, ClipRRect};
+pub use widgets::effects::{BackdropFilter, Filtered, Opacity};
+pub use widgets::portal::Portal;
+pub use widgets::title_bar::{CustomTitleBar, WindowFrame};
+pub use widgets::transform::Transform;
 // State re-exports
 pub use state_management::state::State;
+pub use state_management::bindable::Binding;
+pub use state_management::collections::{ListChange, ReactiveVec};
+pub use state_management::async_state::{AsyncState, AsyncValue};
 // Production system re-exports (conditionally compiled)
 #[cfg(feature = "production")]
 pub use production::{ProductionRuntime, ProductionRuntimeBuilder};
@@ -0,0 +1,128 @@
+//! OS accessibility preferences
+//!
+//! Read from GNOME's settings on Linux, the Universal Access defaults on
+//! macOS, `SystemParametersInfo` on Windows and media queries in the
+//! browser. Anything unreadable counts as "no preference".
+
+use crate::theming::SystemColors;
+
+/// Read `prefers_reduced_motion` off the calling thread and hand the result
+/// to `done`, as it may start a settings process. The browser has no
+/// threads, but answers right away.
+pub fn query_reduced_motion<F>(done: F)
+where
+    F: FnOnce(bool) + Send + 'static,
+{
+    #[cfg(target_arch = "wasm32")]
+    done(prefers_reduced_motion());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::spawn(move || done(prefers_reduced_motion()));
+}
+
+/// Whether the user asked the OS to minimize animation. Starts `gsettings`
+/// or `defaults` on Linux and macOS; see `query_reduced_motion`.
+pub fn prefers_reduced_motion() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        read_command("gsettings", &["get", "org.gnome.desktop.interface", "enable-animations"])
+            .is_some_and(|value| value == "false")
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        read_command("defaults", &["read", "com.apple.universalaccess", "reduceMotion"]).is_some_and(|value| value == "1")
+    }
+
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION};
+
+        let mut animations: i32 = 1;
+        let ok = unsafe { SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, &mut animations as *mut i32 as *mut _, 0) };
+        ok != 0 && animations == 0
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+            .is_some_and(|query| query.matches())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows, target_arch = "wasm32")))]
+    {
+        false
+    }
+}
+
+/// The system palette when the OS forces colors (Windows High Contrast).
+/// Other platforms have no such mode; apps there offer a manual toggle.
+pub fn system_forced_colors() -> Option<SystemColors> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::Graphics::Gdi::{
+            GetSysColor, COLOR_GRAYTEXT, COLOR_HIGHLIGHT, COLOR_HIGHLIGHTTEXT, COLOR_HOTLIGHT, COLOR_WINDOW,
+            COLOR_WINDOWTEXT,
+        };
+        use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+        use windows_sys::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETHIGHCONTRAST};
+
+        let mut contrast: HIGHCONTRASTW = unsafe { std::mem::zeroed() };
+        contrast.cbSize = std::mem::size_of::<HIGHCONTRASTW>() as u32;
+        let ok = unsafe {
+            SystemParametersInfoW(SPI_GETHIGHCONTRAST, contrast.cbSize, &mut contrast as *mut HIGHCONTRASTW as *mut _, 0)
+        };
+        if ok == 0 || contrast.dwFlags & HCF_HIGHCONTRASTON == 0 {
+            return None;
+        }
+
+        // COLORREF is 0x00BBGGRR
+        let color = |index| {
+            let value = unsafe { GetSysColor(index) };
+            crate::core::render_object::Color::rgb(value as u8, (value >> 8) as u8, (value >> 16) as u8)
+        };
+        Some(SystemColors {
+            canvas: color(COLOR_WINDOW),
+            canvas_text: color(COLOR_WINDOWTEXT),
+            highlight: color(COLOR_HIGHLIGHT),
+            highlight_text: color(COLOR_HIGHLIGHTTEXT),
+            gray_text: color(COLOR_GRAYTEXT),
+            link_text: color(COLOR_HOTLIGHT),
+        })
+    }
+
+    #[cfg(not(windows))]
+    {
+        None
+    }
+}
+
+/// Trimmed stdout of a settings query, if it ran successfully
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn read_command(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reduced_motion_is_read_off_the_calling_thread() {
+        let (sender, receiver) = mpsc::channel();
+        let caller = std::thread::current().id();
+        query_reduced_motion(move |reduced| {
+            sender.send((std::thread::current().id(), reduced)).unwrap();
+        });
+        let (thread, reduced) = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_ne!(thread, caller);
+        assert_eq!(reduced, prefers_reduced_motion());
+    }
+}
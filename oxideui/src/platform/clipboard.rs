@@ -0,0 +1,77 @@
+//! Copying text to the system clipboard
+//!
+//! Pipes the text to `wl-copy` on Wayland or `xclip` on X11, `pbcopy` on
+//! macOS and `clip` on Windows, the same tools users have at the shell.
+//! The tool is fed and waited for on a background thread, so a slow or stuck
+//! one never holds up the UI.
+
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Put `text` on the system clipboard
+pub fn set_clipboard_text(text: &str) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    let command = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Command::new("wl-copy")
+    } else {
+        let mut command = Command::new("xclip");
+        command.args(["-selection", "clipboard"]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let command = Command::new("pbcopy");
+    #[cfg(windows)]
+    let command = Command::new("clip");
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    bail!("Copying to the clipboard is not supported on this platform");
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    pipe_to(command, text)
+}
+
+/// Start `command` and hand `text` to it on a background thread. Failing to
+/// start is reported here; later failures are logged.
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+fn pipe_to(mut command: Command, text: &str) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start the clipboard tool")?;
+    let mut stdin = child.stdin.take().context("Clipboard tool has no input")?;
+    let text = text.to_string();
+    std::thread::spawn(move || {
+        let result = stdin.write_all(text.as_bytes()).context("Failed to write to the clipboard").and_then(|()| {
+            // Closing the pipe tells the tool the text is complete
+            drop(stdin);
+            if !child.wait()?.success() {
+                bail!("Clipboard tool failed");
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            eprintln!("⚠️ Copy failed: {:#}", e);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "macos")))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_stuck_tools_do_not_block_the_caller() {
+        // Never reads its input and takes its time to exit
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        let started = Instant::now();
+        pipe_to(command, "copied").unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        assert!(pipe_to(Command::new("oxideui-no-such-clipboard-tool"), "copied").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! System-wide keyboard shortcuts
+//!
+//! Enabled with the `global-hotkeys` feature. Hotkeys fire even while the
+//! window is unfocused or hidden, which is what "show/hide the app" style
+//! bindings need. Backends: `RegisterHotKey` on Windows, Carbon hotkeys on
+//! macOS and `XGrabKey` on X11. Wayland has no global grab protocol, so
+//! registration fails there under a pure Wayland session.
+//!
+//! Accelerators are written like `"Ctrl+Shift+Space"`, `"Alt+F1"` or
+//! `"CmdOrCtrl+K"`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+/// What happens when a hotkey is pressed
+#[derive(Clone)]
+pub enum HotkeyAction {
+    /// Hide the window if it is visible, otherwise show and focus it
+    ToggleWindow,
+    /// Show and focus the window
+    ShowWindow,
+    /// Run a callback on the UI thread
+    Callback(Arc<dyn Fn() + Send + Sync>),
+}
+
+impl HotkeyAction {
+    pub fn callback<F>(callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        HotkeyAction::Callback(Arc::new(callback))
+    }
+}
+
+/// A hotkey requested by the app, registered once the runtime starts
+#[derive(Clone)]
+pub struct HotkeyBinding {
+    pub accelerator: String,
+    pub action: HotkeyAction,
+}
+
+/// Identifies a registered hotkey in `RuntimeEvent::Hotkey`
+pub type HotkeyId = u32;
+
+/// Hotkeys registered with the OS for the lifetime of the app
+pub(crate) struct GlobalHotkeys {
+    manager: GlobalHotKeyManager,
+    bindings: HashMap<HotkeyId, (String, HotKey, HotkeyAction)>,
+}
+
+impl GlobalHotkeys {
+    /// Create the platform backend and forward key presses through `send`.
+    ///
+    /// Must run on the main thread.
+    pub(crate) fn new<F>(send: F) -> Result<Self>
+    where
+        F: Fn(HotkeyId) + Send + Sync + 'static,
+    {
+        let manager = GlobalHotKeyManager::new().context("Global hotkeys are not available on this platform")?;
+        GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+            if event.state == HotKeyState::Pressed {
+                send(event.id);
+            }
+        }));
+        Ok(Self {
+            manager,
+            bindings: HashMap::new(),
+        })
+    }
+
+    /// Register `binding`, failing if the accelerator is malformed or already
+    /// taken by this app or another one
+    pub(crate) fn register(&mut self, binding: &HotkeyBinding) -> Result<HotkeyId> {
+        let hotkey = parse_accelerator(&binding.accelerator)?;
+        if let Some((existing, ..)) = self.bindings.get(&hotkey.id()) {
+            return Err(anyhow!(
+                "Hotkey \"{}\" conflicts with \"{}\", which is already registered",
+                binding.accelerator,
+                existing
+            ));
+        }
+
+        self.manager.register(hotkey).map_err(|e| match e {
+            global_hotkey::Error::AlreadyRegistered(_) => anyhow!(
+                "Hotkey \"{}\" is already registered by another application",
+                binding.accelerator
+            ),
+            e => anyhow!(
+                "Failed to register hotkey \"{}\"; it may be in use by another application: {}",
+                binding.accelerator,
+                e
+            ),
+        })?;
+
+        self.bindings
+            .insert(hotkey.id(), (binding.accelerator.clone(), hotkey, binding.action.clone()));
+        Ok(hotkey.id())
+    }
+
+    pub(crate) fn action(&self, id: HotkeyId) -> Option<&HotkeyAction> {
+        self.bindings.get(&id).map(|(_, _, action)| action)
+    }
+}
+
+impl Drop for GlobalHotkeys {
+    fn drop(&mut self) {
+        GlobalHotKeyEvent::set_event_handler(None::<fn(GlobalHotKeyEvent)>);
+        let hotkeys: Vec<HotKey> = self.bindings.values().map(|(_, hotkey, _)| *hotkey).collect();
+        let _ = self.manager.unregister_all(&hotkeys);
+    }
+}
+
+/// Parse `"Ctrl+Shift+Space"` style accelerators
+pub fn parse_accelerator(accelerator: &str) -> Result<HotKey> {
+    let normalized: Vec<String> = accelerator
+        .split('+')
+        .map(|part| part.trim().to_string())
+        .collect();
+    if normalized.iter().any(|part| part.is_empty()) {
+        return Err(anyhow!("Invalid hotkey \"{}\"", accelerator));
+    }
+    normalized
+        .join("+")
+        .parse::<HotKey>()
+        .map_err(|e| anyhow!("Invalid hotkey \"{}\": {}", accelerator, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_accelerator() {
+        assert_eq!(
+            parse_accelerator("Ctrl + Shift + Space").unwrap(),
+            parse_accelerator("ctrl+shift+space").unwrap()
+        );
+        assert!(parse_accelerator("Ctrl++").is_err());
+        assert!(parse_accelerator("Ctrl+NotAKey").is_err());
+    }
+}
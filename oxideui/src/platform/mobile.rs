@@ -0,0 +1,94 @@
+//! Android and iOS integration
+//!
+//! The runtime uses these for safe-area insets (notches, rounded corners,
+//! system bars) and for showing the software keyboard. On desktop they are
+//! no-ops, so callers need no cfg of their own.
+//!
+//! On Android the app is started from `android_main` with
+//! `Runtime::new_android`; on iOS `Runtime::new` works as is.
+
+use winit::window::Window;
+
+use crate::layout::EdgeInsets;
+
+#[cfg(target_os = "android")]
+use std::sync::OnceLock;
+#[cfg(target_os = "android")]
+use winit::platform::android::activity::AndroidApp;
+
+/// Whether the app is in the foreground
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppLifecycle {
+    /// Back on screen; the drawing surface has been recreated
+    Resumed,
+    /// Sent to the background. Mobile OSes may kill a paused app without
+    /// further notice, so save state here.
+    Paused,
+}
+
+#[cfg(target_os = "android")]
+fn android_app_slot() -> &'static OnceLock<AndroidApp> {
+    static APP: OnceLock<AndroidApp> = OnceLock::new();
+    &APP
+}
+
+#[cfg(target_os = "android")]
+pub(crate) fn set_android_app(app: AndroidApp) {
+    let _ = android_app_slot().set(app);
+}
+
+/// Insets of the area not covered by system UI, in physical pixels
+pub(crate) fn safe_area(window: &Window) -> EdgeInsets {
+    #[cfg(target_os = "android")]
+    {
+        let Some(app) = android_app_slot().get() else {
+            return EdgeInsets::zero();
+        };
+        let content = app.content_rect();
+        let size = window.inner_size();
+        EdgeInsets::only(
+            content.left.max(0) as f32,
+            content.top.max(0) as f32,
+            (size.width as i32 - content.right).max(0) as f32,
+            (size.height as i32 - content.bottom).max(0) as f32,
+        )
+    }
+
+    // winit reports the safe area as the inner rect on iOS
+    #[cfg(target_os = "ios")]
+    {
+        let (Ok(inner), Ok(outer)) = (window.inner_position(), window.outer_position()) else {
+            return EdgeInsets::zero();
+        };
+        let inner_size = window.inner_size();
+        let outer_size = window.outer_size();
+        let left = (inner.x - outer.x).max(0);
+        let top = (inner.y - outer.y).max(0);
+        EdgeInsets::only(
+            left as f32,
+            top as f32,
+            (outer_size.width as i32 - inner_size.width as i32 - left).max(0) as f32,
+            (outer_size.height as i32 - inner_size.height as i32 - top).max(0) as f32,
+        )
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        let _ = window;
+        EdgeInsets::zero()
+    }
+}
+
+/// Show or hide the software keyboard; on desktop this toggles IME input
+pub(crate) fn set_soft_keyboard(window: &Window, visible: bool) {
+    window.set_ime_allowed(visible);
+
+    #[cfg(target_os = "android")]
+    if let Some(app) = android_app_slot().get() {
+        if visible {
+            app.show_soft_input(true);
+        } else {
+            app.hide_soft_input(false);
+        }
+    }
+}
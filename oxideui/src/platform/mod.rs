@@ -0,0 +1,31 @@
+//! Platform integration: native windows and desktop services
+
+pub mod accessibility;
+pub mod clipboard;
+#[cfg(feature = "global-hotkeys")]
+pub mod hotkeys;
+pub mod mobile;
+#[cfg(feature = "notifications")]
+pub mod notifications;
+pub mod opener;
+pub mod power;
+#[cfg(feature = "tray")]
+pub mod tray;
+#[cfg(target_arch = "wasm32")]
+pub mod web;
+pub mod window;
+pub mod window_state;
+
+#[cfg(feature = "global-hotkeys")]
+pub use hotkeys::{HotkeyAction, HotkeyBinding};
+#[cfg(feature = "notifications")]
+pub use notifications::{Notification, NotificationResponse, Notifications};
+pub use accessibility::{prefers_reduced_motion, query_reduced_motion, system_forced_colors};
+pub use clipboard::set_clipboard_text;
+pub use mobile::AppLifecycle;
+pub use opener::open_url;
+pub use power::ScreenWakeLock;
+#[cfg(feature = "tray")]
+pub use tray::{TrayConfig, TrayEvent, TrayMenuEntry};
+pub use window::{CursorShape, FullscreenMode, ResizeEdge, WindowCommand, WindowConfig, WindowController, WindowIcon};
+pub use window_state::PersistedWindowState;
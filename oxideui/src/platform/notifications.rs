@@ -0,0 +1,290 @@
+//! Native desktop notifications
+//!
+//! Enabled with the `notifications` feature. Backends: the freedesktop
+//! notification service over DBus on Linux (what `notify-send` talks to),
+//! `NSUserNotification` on macOS and WinRT toasts on Windows.
+//!
+//! Get a handle from `Runtime::notifications()`. Action buttons and clicks
+//! are delivered back to the event loop, so their callbacks run on the UI
+//! thread like any other event handler.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use winit::event_loop::EventLoopProxy;
+
+use crate::runtime::RuntimeEvent;
+
+type Callback = Arc<dyn Fn() + Send + Sync>;
+
+/// A notification with optional action buttons
+#[derive(Clone)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    pub icon: Option<PathBuf>,
+    pub actions: Vec<(String, Callback)>,
+    pub on_click: Option<Callback>,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            icon: None,
+            actions: Vec::new(),
+            on_click: None,
+        }
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<PathBuf>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Add a button; platforms may cap how many are shown
+    pub fn with_action<F>(mut self, label: impl Into<String>, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.actions.push((label.into(), Arc::new(callback)));
+        self
+    }
+
+    /// Called when the notification body is clicked
+    pub fn with_on_click<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_click = Some(Arc::new(callback));
+        self
+    }
+}
+
+/// How the user responded to a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationResponse {
+    /// The notification body was clicked
+    Clicked,
+    /// An action button was chosen, by index into `Notification::actions`
+    Action(usize),
+    /// Closed or expired without a choice
+    Dismissed,
+}
+
+/// Response to a notification, sent back to the event loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationEvent {
+    pub id: u64,
+    pub response: NotificationResponse,
+}
+
+struct PendingNotification {
+    actions: Vec<Callback>,
+    on_click: Option<Callback>,
+}
+
+/// Cloneable handle for showing notifications
+#[derive(Clone)]
+pub struct Notifications {
+    proxy: EventLoopProxy<RuntimeEvent>,
+    pending: Arc<Mutex<HashMap<u64, PendingNotification>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Notifications {
+    pub(crate) fn new(proxy: EventLoopProxy<RuntimeEvent>) -> Self {
+        Self {
+            proxy,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Show a plain notification
+    pub fn send(&self, title: &str, body: &str, icon: Option<PathBuf>) -> Result<u64> {
+        let mut notification = Notification::new(title, body);
+        notification.icon = icon;
+        self.show(notification)
+    }
+
+    /// Show a notification, returning an id that appears in its events
+    pub fn show(&self, notification: Notification) -> Result<u64> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        if !notification.actions.is_empty() || notification.on_click.is_some() {
+            self.pending.lock().insert(
+                id,
+                PendingNotification {
+                    actions: notification.actions.iter().map(|(_, callback)| callback.clone()).collect(),
+                    on_click: notification.on_click.clone(),
+                },
+            );
+        }
+
+        let proxy = self.proxy.clone();
+        let respond = move |response: NotificationResponse| {
+            let _ = proxy.send_event(RuntimeEvent::Notification(NotificationEvent { id, response }));
+        };
+        if let Err(e) = backend::show(&notification, respond) {
+            self.pending.lock().remove(&id);
+            return Err(e);
+        }
+        Ok(id)
+    }
+
+    /// Run the callback for a response; called on the UI thread
+    pub(crate) fn handle_event(&self, event: NotificationEvent) {
+        let Some(pending) = self.pending.lock().remove(&event.id) else {
+            return;
+        };
+        let callback = match event.response {
+            NotificationResponse::Action(index) => pending.actions.get(index).cloned(),
+            NotificationResponse::Clicked => pending.on_click,
+            NotificationResponse::Dismissed => None,
+        };
+        if let Some(callback) = callback {
+            callback();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod backend {
+    use super::{Notification, NotificationResponse};
+    use anyhow::{Context, Result};
+
+    pub fn show<F>(notification: &Notification, respond: F) -> Result<()>
+    where
+        F: Fn(NotificationResponse) + Send + 'static,
+    {
+        let mut native = notify_rust::Notification::new();
+        native.summary(&notification.title).body(&notification.body);
+        if let Some(icon) = &notification.icon {
+            native.icon(&icon.to_string_lossy());
+        }
+        native.action("default", "Open");
+        for (index, (label, _)) in notification.actions.iter().enumerate() {
+            native.action(&index.to_string(), label);
+        }
+
+        let handle = native.show().context("Failed to show notification")?;
+        std::thread::Builder::new()
+            .name("oxideui-notification".to_string())
+            .spawn(move || {
+                handle.wait_for_action(|action| match action {
+                    "default" => respond(NotificationResponse::Clicked),
+                    // "__closed" and anything unexpected are dismissals
+                    action => respond(
+                        action
+                            .parse()
+                            .map_or(NotificationResponse::Dismissed, NotificationResponse::Action),
+                    ),
+                })
+            })
+            .context("Failed to spawn notification thread")?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod backend {
+    use super::{Notification, NotificationResponse};
+    use anyhow::{Context, Result};
+    use mac_notification_sys::{MainButton, Notification as MacNotification, NotificationResponse as MacResponse};
+
+    pub fn show<F>(notification: &Notification, respond: F) -> Result<()>
+    where
+        F: Fn(NotificationResponse) + Send + 'static,
+    {
+        let title = notification.title.clone();
+        let body = notification.body.clone();
+        let icon = notification.icon.as_ref().map(|p| p.to_string_lossy().into_owned());
+        let labels: Vec<String> = notification.actions.iter().map(|(label, _)| label.clone()).collect();
+
+        // Delivery blocks until the user responds when buttons are present
+        std::thread::Builder::new()
+            .name("oxideui-notification".to_string())
+            .spawn(move || {
+                let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+                let mut options = MacNotification::new();
+                if let Some(icon) = &icon {
+                    options.app_icon(icon);
+                }
+                match label_refs.as_slice() {
+                    [] => {}
+                    [single] => {
+                        options.main_button(MainButton::SingleAction(single));
+                    }
+                    many => {
+                        options.main_button(MainButton::DropdownActions("Actions", many));
+                    }
+                }
+                match mac_notification_sys::send_notification(&title, None, &body, Some(&options)) {
+                    Ok(MacResponse::ActionButton(label)) => respond(
+                        labels
+                            .iter()
+                            .position(|l| *l == label)
+                            .map_or(NotificationResponse::Dismissed, NotificationResponse::Action),
+                    ),
+                    Ok(MacResponse::Click) => respond(NotificationResponse::Clicked),
+                    Ok(_) => respond(NotificationResponse::Dismissed),
+                    Err(e) => {
+                        eprintln!("❌ Notification error: {}", e);
+                        respond(NotificationResponse::Dismissed);
+                    }
+                }
+            })
+            .context("Failed to spawn notification thread")?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod backend {
+    use super::{Notification, NotificationResponse};
+    use anyhow::{anyhow, Result};
+    use tauri_winrt_notification::{IconCrop, Toast};
+
+    pub fn show<F>(notification: &Notification, respond: F) -> Result<()>
+    where
+        F: Fn(NotificationResponse) + Send + 'static,
+    {
+        let mut toast = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title(&notification.title)
+            .text1(&notification.body);
+        if let Some(icon) = &notification.icon {
+            toast = toast.icon(icon, IconCrop::Square, "");
+        }
+        for (index, (label, _)) in notification.actions.iter().enumerate() {
+            toast = toast.add_button(label, &index.to_string());
+        }
+        toast
+            .on_activated(move |action| {
+                respond(match action.and_then(|a| a.parse().ok()) {
+                    Some(index) => NotificationResponse::Action(index),
+                    None => NotificationResponse::Clicked,
+                });
+                Ok(())
+            })
+            .show()
+            .map_err(|e| anyhow!("Failed to show notification: {}", e))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod backend {
+    use super::{Notification, NotificationResponse};
+    use anyhow::{bail, Result};
+
+    pub fn show<F>(_notification: &Notification, _respond: F) -> Result<()>
+    where
+        F: Fn(NotificationResponse) + Send + 'static,
+    {
+        bail!("Desktop notifications are not supported on this platform")
+    }
+}
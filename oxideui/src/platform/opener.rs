@@ -0,0 +1,43 @@
+//! Opening URLs in the user's default handler
+//!
+//! Hands the URL to `xdg-open` on Linux, `open` on macOS and the URL protocol
+//! handler on Windows, so links open in the browser or mail client the
+//! user has chosen. In the browser they open in a new tab.
+
+use anyhow::{bail, Context, Result};
+use std::process::{Command, Stdio};
+
+/// Open `url` with the platform's default handler
+pub fn open_url(url: &str) -> Result<()> {
+    if url.trim().is_empty() {
+        bail!("Cannot open an empty URL");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    return crate::platform::web::open_url(url);
+
+    #[cfg(target_os = "linux")]
+    let mut command = Command::new("xdg-open");
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(windows)]
+    let mut command = {
+        // Not `cmd /C start`, which would interpret `&` in query strings
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows, target_arch = "wasm32")))]
+    bail!("Opening URLs is not supported on this platform");
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    {
+        command
+            .arg(url)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to open {}", url))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Keeping the display awake
+//!
+//! `ScreenWakeLock` stops the screen from dimming or locking while held -
+//! for kiosks, presentations and video playback. It is released on drop.
+
+use anyhow::Result;
+
+/// Holds the display awake until dropped
+pub struct ScreenWakeLock {
+    #[cfg(not(windows))]
+    inhibitor: std::process::Child,
+}
+
+impl ScreenWakeLock {
+    /// Ask the OS to keep the display on. The lock is tied to the calling
+    /// thread, which should be the UI thread: if it exits, the lock goes
+    /// with it.
+    #[cfg(target_os = "linux")]
+    pub fn acquire(reason: &str) -> Result<Self> {
+        use anyhow::Context;
+
+        // systemd-inhibit holds an idle inhibitor for as long as the child
+        // runs, and the child runs until we exit
+        let inhibitor = orphan_proof(
+            std::process::Command::new("systemd-inhibit")
+                .args(["--what=idle:sleep", "--who=OxideUI", "--mode=block"])
+                .arg(format!("--why={}", reason))
+                .arg("tail")
+                .arg(format!("--pid={}", std::process::id()))
+                .args(["-f", "/dev/null"]),
+        )
+        .spawn()
+        .context("Failed to start systemd-inhibit")?;
+        Ok(Self { inhibitor })
+    }
+
+    /// Ask the OS to keep the display on
+    #[cfg(target_os = "macos")]
+    pub fn acquire(_reason: &str) -> Result<Self> {
+        use anyhow::Context;
+        use std::process::{Command, Stdio};
+
+        // -w ties the assertion to our pid so it can't outlive a crash
+        let inhibitor = Command::new("caffeinate")
+            .args(["-d", "-i", "-w"])
+            .arg(std::process::id().to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start caffeinate")?;
+        Ok(Self { inhibitor })
+    }
+
+    /// Ask the OS to keep the display on
+    #[cfg(windows)]
+    pub fn acquire(_reason: &str) -> Result<Self> {
+        use windows_sys::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+        };
+
+        // The state is per-thread; acquire and drop happen on the UI thread
+        let previous = unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_DISPLAY_REQUIRED | ES_SYSTEM_REQUIRED) };
+        if previous == 0 {
+            anyhow::bail!("SetThreadExecutionState failed");
+        }
+        Ok(Self {})
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    pub fn acquire(_reason: &str) -> Result<Self> {
+        anyhow::bail!("Keeping the screen awake is not supported on this platform")
+    }
+}
+
+/// Have the kernel stop `command` when we die, so a crash can't leave the
+/// display inhibited
+#[cfg(target_os = "linux")]
+fn orphan_proof(command: &mut std::process::Command) -> &mut std::process::Command {
+    use std::os::unix::process::CommandExt;
+    use std::process::Stdio;
+
+    let parent = std::process::id();
+    command.stdout(Stdio::null()).stderr(Stdio::null());
+    // SAFETY: only async-signal-safe calls between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // We may have died before the signal was armed
+            if libc::getppid() as u32 != parent {
+                return Err(std::io::Error::other("parent exited"));
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Drop for ScreenWakeLock {
+    fn drop(&mut self) {
+        #[cfg(not(windows))]
+        {
+            let _ = self.inhibitor.kill();
+            let _ = self.inhibitor.wait();
+        }
+
+        #[cfg(windows)]
+        unsafe {
+            use windows_sys::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS};
+            SetThreadExecutionState(ES_CONTINUOUS);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_children_are_stopped_when_their_parent_dies() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::Command;
+        use std::time::{Duration, Instant};
+
+        // A short-lived thread stands in for the parent, as the signal
+        // follows the thread that spawned the child
+        let mut child = std::thread::spawn(|| super::orphan_proof(Command::new("sleep").arg("30")).spawn().unwrap())
+            .join()
+            .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let status = loop {
+            if let Some(status) = child.try_wait().unwrap() {
+                break status;
+            }
+            assert!(Instant::now() < deadline, "the child outlived its parent");
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+    }
+}
@@ -0,0 +1,192 @@
+//! System tray icon and menu
+//!
+//! Enabled with the `tray` feature. Configure a `TrayConfig` and pass it to
+//! `Runtime::with_tray`; the tray is created once the event loop is running
+//! and its clicks are delivered back to the UI thread, where menu callbacks
+//! run.
+//!
+//! On Linux the tray lives on its own GTK thread, as required by
+//! libappindicator.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tray_icon::menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem};
+use tray_icon::{Icon, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::platform::window::WindowIcon;
+
+const MENU_ID_PREFIX: &str = "oxideui-tray-";
+
+/// One entry in the tray menu
+#[derive(Clone)]
+pub enum TrayMenuEntry {
+    Item {
+        label: String,
+        enabled: bool,
+        on_click: Arc<dyn Fn() + Send + Sync>,
+    },
+    /// Exits the application
+    Quit { label: String },
+    Separator,
+}
+
+/// Tray icon settings
+#[derive(Clone)]
+pub struct TrayConfig {
+    pub icon: WindowIcon,
+    pub tooltip: Option<String>,
+    pub menu: Vec<TrayMenuEntry>,
+    /// Closing the window hides it to the tray instead of exiting
+    pub hide_on_close: bool,
+    /// Left-clicking the icon shows and focuses the window
+    pub restore_on_click: bool,
+}
+
+impl TrayConfig {
+    pub fn new(icon: WindowIcon) -> Self {
+        Self {
+            icon,
+            tooltip: None,
+            menu: Vec::new(),
+            hide_on_close: false,
+            restore_on_click: true,
+        }
+    }
+
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    pub fn with_item<F>(mut self, label: impl Into<String>, on_click: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.menu.push(TrayMenuEntry::Item {
+            label: label.into(),
+            enabled: true,
+            on_click: Arc::new(on_click),
+        });
+        self
+    }
+
+    pub fn with_separator(mut self) -> Self {
+        self.menu.push(TrayMenuEntry::Separator);
+        self
+    }
+
+    pub fn with_quit_item(mut self, label: impl Into<String>) -> Self {
+        self.menu.push(TrayMenuEntry::Quit { label: label.into() });
+        self
+    }
+
+    pub fn with_hide_on_close(mut self, hide_on_close: bool) -> Self {
+        self.hide_on_close = hide_on_close;
+        self
+    }
+
+    pub fn with_restore_on_click(mut self, restore_on_click: bool) -> Self {
+        self.restore_on_click = restore_on_click;
+        self
+    }
+
+    fn build_menu(&self) -> Result<Menu> {
+        let menu = Menu::new();
+        for (index, entry) in self.menu.iter().enumerate() {
+            let id = MenuId::new(format!("{}{}", MENU_ID_PREFIX, index));
+            match entry {
+                TrayMenuEntry::Item { label, enabled, .. } => {
+                    menu.append(&MenuItem::with_id(id, label, *enabled, None))?;
+                }
+                TrayMenuEntry::Quit { label } => {
+                    menu.append(&MenuItem::with_id(id, label, true, None))?;
+                }
+                TrayMenuEntry::Separator => menu.append(&PredefinedMenuItem::separator())?,
+            }
+        }
+        Ok(menu)
+    }
+
+    fn build(&self) -> Result<TrayIcon> {
+        let icon = Icon::from_rgba(self.icon.rgba.clone(), self.icon.width, self.icon.height)
+            .context("Invalid tray icon")?;
+        let mut builder = TrayIconBuilder::new()
+            .with_icon(icon)
+            .with_menu(Box::new(self.build_menu()?))
+            .with_menu_on_left_click(false);
+        if let Some(tooltip) = &self.tooltip {
+            builder = builder.with_tooltip(tooltip);
+        }
+        builder.build().context("Failed to create tray icon")
+    }
+}
+
+/// Tray interaction forwarded to the UI thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The icon itself was left-clicked
+    Activated,
+    /// A menu entry was chosen, by index into `TrayConfig::menu`
+    MenuItem(usize),
+}
+
+/// Keeps the tray icon alive
+pub(crate) struct TrayHandle {
+    #[cfg(not(target_os = "linux"))]
+    _tray: TrayIcon,
+}
+
+/// Create the tray and route its events through `send`
+pub(crate) fn install<F>(config: &TrayConfig, send: F) -> Result<TrayHandle>
+where
+    F: Fn(TrayEvent) + Send + Sync + Clone + 'static,
+{
+    let on_menu = send.clone();
+    MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+        if let Some(index) = event.id.0.strip_prefix(MENU_ID_PREFIX).and_then(|i| i.parse().ok()) {
+            on_menu(TrayEvent::MenuItem(index));
+        }
+    }));
+    TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+        if let TrayIconEvent::Click {
+            button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
+            ..
+        } = event
+        {
+            send(TrayEvent::Activated);
+        }
+    }));
+
+    #[cfg(target_os = "linux")]
+    {
+        let config = config.clone();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("oxideui-tray".to_string())
+            .spawn(move || {
+                if let Err(e) = gtk::init() {
+                    let _ = ready_tx.send(Err(anyhow::anyhow!("Failed to initialize GTK: {}", e)));
+                    return;
+                }
+                match config.build() {
+                    Ok(_tray) => {
+                        let _ = ready_tx.send(Ok(()));
+                        gtk::main();
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                    }
+                }
+            })
+            .context("Failed to spawn tray thread")?;
+        ready_rx.recv().context("Tray thread exited")??;
+        Ok(TrayHandle {})
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(TrayHandle { _tray: config.build()? })
+    }
+}
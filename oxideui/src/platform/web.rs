@@ -0,0 +1,36 @@
+//! Browser integration for the wasm32 target
+//!
+//! winit's web backend turns DOM input into the usual window events and
+//! paces redraws with `requestAnimationFrame`, and softbuffer draws through
+//! a canvas 2D context, so the runtime works unchanged. This module covers
+//! what the browser does differently: locating the canvas and opening URLs.
+//!
+//! Build with `--no-default-features` (Skia does not target wasm32) and
+//! start the app from the page's entry point:
+//!
+//! ```ignore
+//! #[wasm_bindgen(start)]
+//! pub fn start() {
+//!     wasm_bindgen_futures::spawn_local(async {
+//!         Runtime::new(Box::new(App::new())).with_canvas("app").run().await.unwrap();
+//!     });
+//! }
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlCanvasElement;
+
+/// The `<canvas>` element with id `id`, if the page has one
+pub fn find_canvas(id: &str) -> Option<HtmlCanvasElement> {
+    web_sys::window()?.document()?.get_element_by_id(id)?.dyn_into().ok()
+}
+
+/// Open `url` in a new browser tab
+pub fn open_url(url: &str) -> Result<()> {
+    let window = web_sys::window().context("No browser window")?;
+    window
+        .open_with_url_and_target(url, "_blank")
+        .map_err(|e| anyhow!("Failed to open {}: {:?}", url, e))?;
+    Ok(())
+}
@@ -0,0 +1,539 @@
+//! Window configuration and control
+//!
+//! `WindowConfig` collects everything needed to create the native window.
+//! `Runtime` exposes it through `with_*` builders and applies it in
+//! `resumed()` when the window is created.
+//!
+//! `WindowController` lets widgets drive the window (drag, resize,
+//! minimize, ...). It is reached through `BuildContext::window()`; commands
+//! are queued and applied by the runtime after event dispatch.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use parking_lot::{Mutex, MutexGuard};
+use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition};
+use winit::window::{CursorIcon, Fullscreen, Icon, ResizeDirection, Window, WindowAttributes, WindowLevel};
+
+use crate::core::element::{ElementId, ElementTree};
+#[cfg(feature = "skia")]
+use crate::core::render_object::Color;
+use crate::core::render_object::{Rect, RenderObject};
+use crate::core::InputMethodManager;
+use crate::platform::power::ScreenWakeLock;
+#[cfg(feature = "skia")]
+use crate::render::{rasterize, RgbaImage};
+
+/// RGBA8 pixel data for the window icon
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowIcon {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowIcon {
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        Self { rgba, width, height }
+    }
+
+    fn to_winit(&self) -> Result<Icon> {
+        Icon::from_rgba(self.rgba.clone(), self.width, self.height).context("Invalid window icon")
+    }
+}
+
+/// Native window settings, in logical pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowConfig {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub min_size: Option<(u32, u32)>,
+    pub max_size: Option<(u32, u32)>,
+    pub position: Option<(i32, i32)>,
+    pub icon: Option<WindowIcon>,
+    pub resizable: bool,
+    pub decorations: bool,
+    pub always_on_top: bool,
+    pub maximized: bool,
+    /// Let the desktop show through wherever the UI draws nothing
+    pub transparent: bool,
+    /// Web only: id of the `<canvas>` to draw into; a new canvas is
+    /// appended to the page body when unset or not found
+    pub canvas_id: Option<String>,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "OxideUI Application".to_string(),
+            width: 800,
+            height: 600,
+            min_size: None,
+            max_size: None,
+            position: None,
+            icon: None,
+            resizable: true,
+            decorations: true,
+            always_on_top: false,
+            maximized: false,
+            transparent: false,
+            canvas_id: None,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// Build winit attributes; an invalid icon is reported and skipped
+    pub fn to_attributes(&self) -> WindowAttributes {
+        let mut attributes = WindowAttributes::default()
+            .with_title(&self.title)
+            .with_inner_size(LogicalSize::new(self.width, self.height))
+            .with_visible(true)
+            .with_resizable(self.resizable)
+            .with_decorations(self.decorations)
+            .with_transparent(self.transparent)
+            .with_maximized(self.maximized);
+
+        if let Some((width, height)) = self.min_size {
+            attributes = attributes.with_min_inner_size(LogicalSize::new(width, height));
+        }
+        if let Some((width, height)) = self.max_size {
+            attributes = attributes.with_max_inner_size(LogicalSize::new(width, height));
+        }
+        if let Some((x, y)) = self.position {
+            attributes = attributes.with_position(LogicalPosition::new(x, y));
+        }
+        if self.always_on_top {
+            attributes = attributes.with_window_level(WindowLevel::AlwaysOnTop);
+        }
+        if let Some(icon) = &self.icon {
+            match icon.to_winit() {
+                Ok(icon) => attributes = attributes.with_window_icon(Some(icon)),
+                Err(e) => eprintln!("⚠️ {:#}", e),
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::WindowAttributesExtWebSys;
+            attributes = match self.canvas_id.as_deref().and_then(crate::platform::web::find_canvas) {
+                Some(canvas) => attributes.with_canvas(Some(canvas)),
+                None => attributes.with_append(true),
+            };
+        }
+
+        attributes
+    }
+}
+
+/// Window edge or corner grabbed for an interactive resize
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeEdge {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl From<ResizeEdge> for ResizeDirection {
+    fn from(edge: ResizeEdge) -> Self {
+        match edge {
+            ResizeEdge::North => ResizeDirection::North,
+            ResizeEdge::South => ResizeDirection::South,
+            ResizeEdge::East => ResizeDirection::East,
+            ResizeEdge::West => ResizeDirection::West,
+            ResizeEdge::NorthEast => ResizeDirection::NorthEast,
+            ResizeEdge::NorthWest => ResizeDirection::NorthWest,
+            ResizeEdge::SouthEast => ResizeDirection::SouthEast,
+            ResizeEdge::SouthWest => ResizeDirection::SouthWest,
+        }
+    }
+}
+
+/// Pointer shapes widgets can ask for while hovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    #[default]
+    Default,
+    /// Hand, for links and other clickable text
+    Pointer,
+    Text,
+    Grab,
+    Grabbing,
+    NotAllowed,
+    ColumnResize,
+    RowResize,
+    Wait,
+}
+
+impl From<CursorShape> for CursorIcon {
+    fn from(shape: CursorShape) -> Self {
+        match shape {
+            CursorShape::Default => CursorIcon::Default,
+            CursorShape::Pointer => CursorIcon::Pointer,
+            CursorShape::Text => CursorIcon::Text,
+            CursorShape::Grab => CursorIcon::Grab,
+            CursorShape::Grabbing => CursorIcon::Grabbing,
+            CursorShape::NotAllowed => CursorIcon::NotAllowed,
+            CursorShape::ColumnResize => CursorIcon::ColResize,
+            CursorShape::RowResize => CursorIcon::RowResize,
+            CursorShape::Wait => CursorIcon::Wait,
+        }
+    }
+}
+
+/// How the window covers the screen in fullscreen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullscreenMode {
+    /// Borderless window sized to the current monitor; fast to toggle
+    Borderless,
+    /// Takes over the display at its current video mode
+    Exclusive,
+}
+
+/// A request queued by `WindowController`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowCommand {
+    /// Begin an OS-driven move; must be issued while the button is held
+    StartDrag,
+    StartResize(ResizeEdge),
+    Minimize,
+    ToggleMaximize,
+    Close,
+    SetFullscreen(Option<FullscreenMode>),
+    SetCursorVisible(bool),
+    SetKeepAwake(bool),
+    SetVisible(bool),
+    SetSoftKeyboard(bool),
+}
+
+/// Handle widgets use to control the native window
+#[derive(Clone, Default)]
+pub struct WindowController {
+    commands: Arc<Mutex<Vec<WindowCommand>>>,
+    maximized: Arc<AtomicBool>,
+    fullscreen: Arc<Mutex<Option<FullscreenMode>>>,
+    cursor_hidden: Arc<AtomicBool>,
+    /// Shape asked for during the current pointer move, applied after dispatch
+    cursor_request: Arc<Mutex<Option<CursorShape>>>,
+    cursor: Arc<Mutex<CursorShape>>,
+    close_requested: Arc<AtomicBool>,
+    wake_lock: Arc<Mutex<Option<ScreenWakeLock>>>,
+    /// Composition state fed by the window's IME events
+    ime: Arc<Mutex<InputMethodManager>>,
+    /// Where the IME candidate window should go, in logical pixels
+    ime_cursor_area: Arc<Mutex<Option<Rect>>>,
+    /// Caret of the composing field in its own coordinates, placed in the
+    /// window once the field has been laid out
+    ime_caret: Arc<Mutex<Option<(ElementId, Rect)>>>,
+    /// Most recently presented frame, kept for screenshots
+    last_frame: Arc<Mutex<Option<Arc<PresentedFrame>>>>,
+}
+
+/// A frame as handed to the renderer, in physical pixels. Without Skia
+/// only the tree is kept, to recycle it.
+pub(crate) struct PresentedFrame {
+    pub root: RenderObject,
+    #[cfg(feature = "skia")]
+    pub width: u32,
+    #[cfg(feature = "skia")]
+    pub height: u32,
+    #[cfg(feature = "skia")]
+    pub scale_factor: f32,
+    #[cfg(feature = "skia")]
+    pub background: Color,
+}
+
+impl WindowController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the window with the pointer (call on PointerDown).
+    ///
+    /// Uses the native move loop so OS snapping (Aero Snap, tiling) works.
+    pub fn start_drag(&self) {
+        self.push(WindowCommand::StartDrag);
+    }
+
+    /// Resize from an edge with the pointer (call on PointerDown)
+    pub fn start_resize(&self, edge: ResizeEdge) {
+        self.push(WindowCommand::StartResize(edge));
+    }
+
+    pub fn minimize(&self) {
+        self.push(WindowCommand::Minimize);
+    }
+
+    pub fn toggle_maximize(&self) {
+        self.push(WindowCommand::ToggleMaximize);
+    }
+
+    pub fn close(&self) {
+        self.push(WindowCommand::Close);
+    }
+
+    /// Enter fullscreen, or leave it with `None`
+    pub fn set_fullscreen(&self, mode: Option<FullscreenMode>) {
+        self.push(WindowCommand::SetFullscreen(mode));
+    }
+
+    /// Switch between windowed and `mode`
+    pub fn toggle_fullscreen(&self, mode: FullscreenMode) {
+        let next = if self.fullscreen().is_some() { None } else { Some(mode) };
+        self.set_fullscreen(next);
+    }
+
+    /// Hide the pointer while it is over the window (media playback, kiosks)
+    pub fn set_cursor_visible(&self, visible: bool) {
+        self.push(WindowCommand::SetCursorVisible(visible));
+    }
+
+    /// Show `shape` while the pointer is over the calling widget.
+    ///
+    /// Call it on every `PointerMove` the widget receives; the cursor goes
+    /// back to the default on the first move no widget claims.
+    pub fn set_cursor(&self, shape: CursorShape) {
+        *self.cursor_request.lock() = Some(shape);
+    }
+
+    /// Forget the shape requested for the previous pointer position
+    pub(crate) fn reset_cursor_request(&self) {
+        *self.cursor_request.lock() = Some(CursorShape::Default);
+    }
+
+    pub fn cursor(&self) -> CursorShape {
+        *self.cursor.lock()
+    }
+
+    /// Adopt the requested cursor shape; returns it if the cursor changed
+    pub(crate) fn commit_cursor(&self) -> Option<CursorShape> {
+        let shape = self.cursor_request.lock().take()?;
+        let mut cursor = self.cursor.lock();
+        if *cursor == shape {
+            return None;
+        }
+        *cursor = shape;
+        Some(shape)
+    }
+
+    /// Keep the display from dimming or locking while `true`
+    pub fn set_keep_awake(&self, keep_awake: bool) {
+        self.push(WindowCommand::SetKeepAwake(keep_awake));
+    }
+
+    /// Show or hide the window; a hidden window can be restored from the tray
+    pub fn set_visible(&self, visible: bool) {
+        self.push(WindowCommand::SetVisible(visible));
+    }
+
+    /// Show the software keyboard on touch devices (text fields do this
+    /// when focused); enables IME input on desktop
+    pub fn show_soft_keyboard(&self) {
+        self.push(WindowCommand::SetSoftKeyboard(true));
+    }
+
+    pub fn hide_soft_keyboard(&self) {
+        self.push(WindowCommand::SetSoftKeyboard(false));
+    }
+
+    /// Input method composition shared by the runtime and text fields
+    pub fn ime(&self) -> MutexGuard<'_, InputMethodManager> {
+        self.ime.lock()
+    }
+
+    /// Place the IME candidate window next to `area` (the text caret), in
+    /// logical window coordinates
+    pub fn set_ime_cursor_area(&self, area: Rect) {
+        *self.ime_cursor_area.lock() = Some(area);
+    }
+
+    /// Place the IME candidate window next to `area`, in `element`'s local
+    /// coordinates; for use during build, before the element is laid out
+    pub fn set_ime_caret(&self, element: ElementId, area: Rect) {
+        *self.ime_caret.lock() = Some((element, area));
+    }
+
+    /// Map the caret set during build into the window, now that `tree`
+    /// has been laid out
+    pub(crate) fn place_ime_caret(&self, tree: &ElementTree) {
+        let Some((element, area)) = self.ime_caret.lock().take() else {
+            return;
+        };
+        if let Some(element) = tree.get(element).filter(|element| element.bounds.is_some()) {
+            self.set_ime_cursor_area(element.transform.map_rect(area));
+        }
+    }
+
+    /// Whether the window was maximized as of the last frame
+    pub fn is_maximized(&self) -> bool {
+        self.maximized.load(Ordering::Relaxed)
+    }
+
+    /// Current fullscreen mode as of the last frame
+    pub fn fullscreen(&self) -> Option<FullscreenMode> {
+        *self.fullscreen.lock()
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        !self.cursor_hidden.load(Ordering::Relaxed)
+    }
+
+    pub fn is_keeping_awake(&self) -> bool {
+        self.wake_lock.lock().is_some()
+    }
+
+    /// Re-render the last presented frame offscreen and return its pixels.
+    ///
+    /// The image is in physical pixels; fails before the first frame.
+    #[cfg(feature = "skia")]
+    pub fn capture_frame(&self) -> Result<RgbaImage> {
+        let frame = self.last_frame.lock().clone().context("No frame has been rendered yet")?;
+        rasterize(&frame.root, frame.width, frame.height, frame.background)
+    }
+
+    /// Capture the part of the last frame inside `rect`, given in logical pixels
+    #[cfg(feature = "skia")]
+    pub fn capture_region(&self, rect: Rect) -> Result<RgbaImage> {
+        let scale = self.last_frame.lock().as_ref().map_or(1.0, |frame| frame.scale_factor);
+        let image = self.capture_frame()?;
+        Ok(image.crop(Rect::new(rect.x * scale, rect.y * scale, rect.width * scale, rect.height * scale)))
+    }
+
+    /// Keep `frame` for screenshots. Returns the tree it replaces, unless a
+    /// screenshot in progress still holds it.
+    pub(crate) fn store_frame(&self, frame: PresentedFrame) -> Option<RenderObject> {
+        let previous = self.last_frame.lock().replace(Arc::new(frame))?;
+        Arc::try_unwrap(previous).ok().map(|frame| frame.root)
+    }
+
+    fn push(&self, command: WindowCommand) {
+        self.commands.lock().push(command);
+    }
+
+    /// Apply queued commands to `window`; returns true if close was requested
+    pub(crate) fn apply(&self, window: &Window) -> bool {
+        let commands = std::mem::take(&mut *self.commands.lock());
+        for command in commands {
+            let result = match command {
+                WindowCommand::StartDrag => window.drag_window().map_err(|e| e.to_string()),
+                WindowCommand::StartResize(edge) => {
+                    window.drag_resize_window(edge.into()).map_err(|e| e.to_string())
+                }
+                WindowCommand::Minimize => {
+                    window.set_minimized(true);
+                    Ok(())
+                }
+                WindowCommand::ToggleMaximize => {
+                    window.set_maximized(!window.is_maximized());
+                    Ok(())
+                }
+                WindowCommand::Close => {
+                    self.close_requested.store(true, Ordering::Relaxed);
+                    Ok(())
+                }
+                WindowCommand::SetFullscreen(mode) => {
+                    window.set_fullscreen(mode.and_then(|mode| to_winit_fullscreen(window, mode)));
+                    *self.fullscreen.lock() = mode;
+                    Ok(())
+                }
+                WindowCommand::SetCursorVisible(visible) => {
+                    window.set_cursor_visible(visible);
+                    self.cursor_hidden.store(!visible, Ordering::Relaxed);
+                    Ok(())
+                }
+                WindowCommand::SetVisible(visible) => {
+                    window.set_visible(visible);
+                    if visible {
+                        window.focus_window();
+                    }
+                    Ok(())
+                }
+                WindowCommand::SetSoftKeyboard(visible) => {
+                    crate::platform::mobile::set_soft_keyboard(window, visible);
+                    Ok(())
+                }
+                WindowCommand::SetKeepAwake(keep_awake) => {
+                    let mut wake_lock = self.wake_lock.lock();
+                    match (keep_awake, wake_lock.is_some()) {
+                        (true, false) => ScreenWakeLock::acquire("Application requested the screen stay on")
+                            .map(|lock| *wake_lock = Some(lock))
+                            .map_err(|e| e.to_string()),
+                        (false, true) => {
+                            *wake_lock = None;
+                            Ok(())
+                        }
+                        _ => Ok(()),
+                    }
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("⚠️ Window command {:?} failed: {}", command, e);
+            }
+        }
+        if let Some(area) = self.ime_cursor_area.lock().take() {
+            window.set_ime_cursor_area(
+                LogicalPosition::new(area.x, area.y),
+                LogicalSize::new(area.width, area.height),
+            );
+        }
+        if let Some(shape) = self.commit_cursor() {
+            window.set_cursor(CursorIcon::from(shape));
+        }
+        self.maximized.store(window.is_maximized(), Ordering::Relaxed);
+        if window.fullscreen().is_none() {
+            // The user may have left fullscreen through the OS
+            *self.fullscreen.lock() = None;
+        }
+        self.close_requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Where the pointer is over `window`, in physical pixels, asked of the OS
+/// rather than taken from events. winit sends no cursor moves during a file
+/// drag on Windows; elsewhere it does, and this returns None.
+pub(crate) fn pointer_position(window: &Window) -> Option<PhysicalPosition<f64>> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::Foundation::POINT;
+        use windows_sys::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+        let mut point = POINT { x: 0, y: 0 };
+        if unsafe { GetCursorPos(&mut point) } == 0 {
+            return None;
+        }
+        let origin = window.inner_position().ok()?;
+        Some(PhysicalPosition::new((point.x - origin.x) as f64, (point.y - origin.y) as f64))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = window;
+        None
+    }
+}
+
+fn to_winit_fullscreen(window: &Window, mode: FullscreenMode) -> Option<Fullscreen> {
+    match mode {
+        FullscreenMode::Borderless => Some(Fullscreen::Borderless(window.current_monitor())),
+        FullscreenMode::Exclusive => {
+            let monitor = window.current_monitor()?;
+            // Prefer the mode matching the desktop; fall back to the largest
+            let size = monitor.size();
+            let video_mode = monitor
+                .video_modes()
+                .filter(|mode| mode.size() == size)
+                .max_by_key(|mode| mode.refresh_rate_millihertz())
+                .or_else(|| monitor.video_modes().max_by_key(|mode| mode.size().width * mode.size().height));
+            match video_mode {
+                Some(video_mode) => Some(Fullscreen::Exclusive(video_mode)),
+                None => Some(Fullscreen::Borderless(Some(monitor))),
+            }
+        }
+    }
+}
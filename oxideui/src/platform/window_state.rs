@@ -0,0 +1,145 @@
+//! Window state persistence
+//!
+//! Saves the window's position, size, maximized flag and monitor to the
+//! user's config directory on exit and restores them on the next launch.
+//! Geometry is stored in physical pixels for the monitor it was on.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Window geometry as saved between launches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedWindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    /// Name of the monitor the window was on, if known
+    pub monitor: Option<String>,
+}
+
+/// A connected monitor's work area, in physical pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorArea {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl MonitorArea {
+    fn overlap(&self, state: &PersistedWindowState) -> i64 {
+        let left = self.x.max(state.x) as i64;
+        let top = self.y.max(state.y) as i64;
+        let right = (self.x as i64 + self.width as i64).min(state.x as i64 + state.width as i64);
+        let bottom = (self.y as i64 + self.height as i64).min(state.y as i64 + state.height as i64);
+        (right - left).max(0) * (bottom - top).max(0)
+    }
+}
+
+/// Where the state for `app_id` lives, following platform conventions
+pub fn state_path(app_id: &str) -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|base| base.join(app_id).join("window-state.json"))
+}
+
+impl PersistedWindowState {
+    pub fn load(app_id: &str) -> Result<Option<Self>> {
+        let Some(path) = state_path(app_id) else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let state = serde_json::from_str(&json).with_context(|| format!("Invalid window state in {}", path.display()))?;
+        Ok(Some(state))
+    }
+
+    pub fn save(&self, app_id: &str) -> Result<()> {
+        let path = state_path(app_id).context("No config directory for window state")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Fit the saved state to the monitors connected now.
+    ///
+    /// If the saved monitor is gone or the window would be mostly off
+    /// screen, it is moved onto the best remaining monitor (or the first
+    /// one) and shrunk to fit. Returns `None` when there are no monitors.
+    pub fn fit_to_monitors(&self, monitors: &[MonitorArea]) -> Option<Self> {
+        let same_monitor = self
+            .monitor
+            .as_ref()
+            .and_then(|name| monitors.iter().find(|m| m.name.as_ref() == Some(name)));
+        let target = same_monitor
+            .or_else(|| monitors.iter().filter(|m| m.overlap(self) > 0).max_by_key(|m| m.overlap(self)))
+            .or_else(|| monitors.first())?;
+
+        let mut fitted = self.clone();
+        fitted.width = self.width.min(target.width);
+        fitted.height = self.height.min(target.height);
+        fitted.monitor = target.name.clone();
+
+        // Require at least half the window to be visible on the target
+        let visible = target.overlap(&fitted);
+        if visible * 2 < fitted.width as i64 * fitted.height as i64 {
+            fitted.x = target.x + (target.width - fitted.width) as i32 / 2;
+            fitted.y = target.y + (target.height - fitted.height) as i32 / 2;
+        }
+
+        Some(fitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32) -> MonitorArea {
+        MonitorArea {
+            name: Some(name.to_string()),
+            x,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        }
+    }
+
+    #[test]
+    fn test_window_on_missing_monitor_is_recentered() {
+        let state = PersistedWindowState {
+            x: 2200,
+            y: 100,
+            width: 800,
+            height: 600,
+            maximized: false,
+            monitor: Some("DP-2".to_string()),
+        };
+
+        // Second monitor unplugged: only the primary remains
+        let fitted = state.fit_to_monitors(&[monitor("DP-1", 0)]).unwrap();
+        assert_eq!((fitted.x, fitted.y), (560, 240));
+        assert_eq!(fitted.monitor.as_deref(), Some("DP-1"));
+
+        // Still connected: position is kept
+        let kept = state.fit_to_monitors(&[monitor("DP-1", 0), monitor("DP-2", 1920)]).unwrap();
+        assert_eq!((kept.x, kept.y), (2200, 100));
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use web_time::Instant;
 use crate::core::state_driven::StateTracker;
 use crate::widgets::scrolling::ScrollController;
 
@@ -0,0 +1,31 @@
+//! `tracing` spans around the frame pipeline
+//!
+//! With the `tracing` feature, each frame is recorded as a `frame` span
+//! containing `build` (one `widget` span per widget, named by type),
+//! `layout`, `reconcile`, `paint` and `present`, plus `dispatch_event` and
+//! `shape_text` wherever they run. Attach any subscriber to see them, e.g.
+//! `tracing_subscriber::fmt().with_span_events(FmtSpan::CLOSE)`, or
+//! `tracing-tracy` / `tracing-perfetto` for a timeline. Per-widget spans are
+//! at TRACE level; filter them out to see only the phases.
+//!
+//! Without the feature the spans compile to nothing.
+
+/// Enter a span for the rest of the enclosing scope:
+/// `let _span = span!(INFO, "paint", width = size.width);`
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($level:ident, $name:literal $(, $($fields:tt)*)?) => {
+        tracing::span!(tracing::Level::$level, $name $(, $($fields)*)?).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! span {
+    ($level:ident, $name:literal $(, $($fields:tt)*)?) => {
+        $crate::profiling::NoSpan
+    };
+}
+
+/// Stands in for an entered span when tracing is compiled out
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
@@ -0,0 +1,121 @@
+//! Rasterizing render trees into pixel buffers
+//!
+//! Used for screenshots of the running app (`WindowController::capture_frame`,
+//! `BuildContext::capture_widget`) and works on any render tree, with or
+//! without a window.
+
+use anyhow::{anyhow, bail, Context, Result};
+use skia_safe::{AlphaType, ColorType, Data, EncodedImageFormat, ISize, ImageInfo};
+use std::path::Path;
+
+use crate::core::render_object::{Color, Rect, RenderObject};
+use crate::render::rendering_impl::SkiaRenderer;
+
+/// Straight (non-premultiplied) RGBA8 pixels, row-major from the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            bail!("Expected {} bytes for a {}x{} image, got {}", width as usize * height as usize * 4, width, height, pixels.len());
+        }
+        Ok(Self { width, height, pixels })
+    }
+
+    /// Color at (x, y), or None outside the image
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let p = &self.pixels[i..i + 4];
+        Some(Color::rgba(p[0], p[1], p[2], p[3]))
+    }
+
+    /// The part of the image inside `rect` (in pixels), clamped to the image
+    pub fn crop(&self, rect: Rect) -> RgbaImage {
+        let x0 = (rect.x.max(0.0).floor() as u32).min(self.width);
+        let y0 = (rect.y.max(0.0).floor() as u32).min(self.height);
+        let x1 = ((rect.x + rect.width).ceil().max(0.0) as u32).clamp(x0, self.width);
+        let y1 = ((rect.y + rect.height).ceil().max(0.0) as u32).clamp(y0, self.height);
+
+        let row_bytes = self.width as usize * 4;
+        let mut pixels = Vec::with_capacity((x1 - x0) as usize * (y1 - y0) as usize * 4);
+        for y in y0..y1 {
+            let start = y as usize * row_bytes + x0 as usize * 4;
+            pixels.extend_from_slice(&self.pixels[start..start + (x1 - x0) as usize * 4]);
+        }
+        RgbaImage {
+            width: x1 - x0,
+            height: y1 - y0,
+            pixels,
+        }
+    }
+
+    /// Encode as PNG
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let info = image_info(self.width, self.height);
+        let image = skia_safe::images::raster_from_data(&info, Data::new_copy(&self.pixels), self.width as usize * 4)
+            .ok_or_else(|| anyhow!("Failed to wrap {}x{} pixels as an image", self.width, self.height))?;
+        let data = image
+            .encode(None, EncodedImageFormat::PNG, None)
+            .ok_or_else(|| anyhow!("Failed to encode PNG"))?;
+        Ok(data.as_bytes().to_vec())
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_png()?).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn image_info(width: u32, height: u32) -> ImageInfo {
+    ImageInfo::new(
+        ISize::new(width as i32, height as i32),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    )
+}
+
+/// Draw `render_obj` on a `background`-filled raster surface of the given
+/// pixel size and read the result back
+pub fn rasterize(render_obj: &RenderObject, width: u32, height: u32, background: Color) -> Result<RgbaImage> {
+    if width == 0 || height == 0 {
+        bail!("Cannot capture an empty {}x{} frame", width, height);
+    }
+    let info = image_info(width, height);
+    let mut surface = skia_safe::surfaces::raster(&info.with_alpha_type(AlphaType::Premul), None, None)
+        .ok_or_else(|| anyhow!("Failed to create a {}x{} capture surface", width, height))?;
+
+    let mut renderer = SkiaRenderer::new();
+    renderer.clear(surface.canvas(), background);
+    renderer.render(surface.canvas(), render_obj);
+
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    if !surface.read_pixels(&info, &mut pixels, width as usize * 4, (0, 0)) {
+        bail!("Failed to read back the captured frame");
+    }
+    RgbaImage::new(width, height, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crop() {
+        let pixels = (0..4 * 3 * 4).map(|i| i as u8).collect();
+        let image = RgbaImage::new(4, 3, pixels).unwrap();
+
+        let cropped = image.crop(Rect::new(1.0, 1.0, 2.0, 5.0));
+        assert_eq!((cropped.width, cropped.height), (2, 2));
+        assert_eq!(cropped.pixel(0, 0), image.pixel(1, 1));
+        assert_eq!(cropped.pixel(1, 1), image.pixel(2, 2));
+    }
+}
@@ -1,16 +1,26 @@
 //! Rendering backend abstractions for OxideUI
 
+#[cfg(feature = "skia-opengl")]
 pub mod skia_opengl;
+#[cfg(feature = "skia")]
 pub mod skia_cpu;
 pub mod softbuffer;
+#[cfg(feature = "skia")]
 pub mod rendering_impl;
 mod pipeline;
+#[cfg(feature = "skia")]
+mod capture;
 pub mod text;
+mod system_fonts;
+mod svg_path;
 
-pub use crate::render::text::{FontManager, TextLayout, TextCache, FontDescriptor, FontWeight, FontStyle};
+pub use crate::render::text::{FontManager, TextLayout, TextCache, TextCacheStats, FontDescriptor, FontFace, FontWeight, FontStyle, RegisteredFont, TextOverflow};
+pub use crate::render::system_fonts::SystemFont;
+#[cfg(feature = "skia")]
+pub use crate::render::capture::{rasterize, RgbaImage};
 
 use anyhow::Result;
-use crate::core::RenderObject;
+use crate::core::{Color, RenderObject};
 
 /// Core trait for all rendering backends
 pub trait RenderBackend: Send {
@@ -32,6 +42,10 @@ pub trait RenderBackend: Send {
     /// Clean up rendering resources
     fn cleanup(&mut self);
 
+    /// Color the surface is cleared to each frame (transparent windows use
+    /// `Color::TRANSPARENT`)
+    fn set_clear_color(&mut self, _color: Color) {}
+
     /// Get backend name for debugging
     fn name(&self) -> &str {
         "Unknown"
@@ -51,6 +65,12 @@ pub enum BackendType {
 
 /// Select the best available rendering backend
 pub fn select_backend() -> BackendType {
+    // The browser build draws through softbuffer's canvas 2D path
+    if cfg!(target_arch = "wasm32") {
+        println!("[Backend] Web: Softbuffer (canvas)");
+        return BackendType::Softbuffer;
+    }
+
     match std::env::var("OXIDEUI_RENDERER") {
         Ok(val) => match val.to_lowercase().as_str() {
             "skia" | "skia-opengl" | "opengl" | "gpu" => {
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use crate::core::{ElementId, Rect, RenderObject};
+use crate::core::render_object::{sticky_offset, Point};
 
 /// Damage region tracking for efficient partial redraws
 #[derive(Debug, Clone)]
@@ -86,6 +87,11 @@ pub struct RenderPipeline {
     pub display_list: DisplayList,
     pub layer_cache: HashMap<ElementId, RenderObject>,
     pub viewport: Rect,
+    /// Overlay subtrees deferred until the main tree has been listed
+    overlay_queue: Vec<(RenderObject, crate::core::render_object::Matrix, f32)>,
+    /// Device-space top of each enclosing viewport, innermost last, for
+    /// pinning sticky headers
+    viewport_tops: Vec<f32>,
 }
 
 impl RenderPipeline {
@@ -95,6 +101,8 @@ impl RenderPipeline {
             display_list: DisplayList::new(),
             layer_cache: HashMap::new(),
             viewport,
+            overlay_queue: Vec::new(),
+            viewport_tops: Vec::new(),
         }
     }
 
@@ -113,6 +121,12 @@ impl RenderPipeline {
             1.0,
             None,
         );
+        // Overlays are listed last (drawn on top) and ignore ancestor clips
+        while !self.overlay_queue.is_empty() {
+            for (obj, transform, opacity) in std::mem::take(&mut self.overlay_queue) {
+                self.build_display_list_recursive(&obj, transform, opacity, None);
+            }
+        }
         self.display_list.cull(self.viewport);
     }
 
@@ -138,6 +152,42 @@ impl RenderPipeline {
                 let new_clip = Some(self.transform_rect(*rect, &transform));
                 self.build_display_list_recursive(child, transform, opacity, new_clip);
             }
+            // Culled by the shape's bounds; the renderer clips precisely
+            RenderObject::ClipShape { shape, child } => {
+                let new_clip = Some(self.transform_rect(shape.bounds(), &transform));
+                self.build_display_list_recursive(child, transform, opacity, new_clip);
+            }
+            // Filters are applied by the renderer; the children are culled
+            // and hit-tested as usual
+            RenderObject::Opacity { alpha, child } => {
+                if *alpha > 0.0 {
+                    self.build_display_list_recursive(child, transform, opacity * alpha, clip);
+                }
+            }
+            RenderObject::Effect { child, .. }
+            | RenderObject::Backdrop { child, .. }
+            | RenderObject::Element { child, .. } => {
+                self.build_display_list_recursive(child, transform, opacity, clip);
+            }
+            RenderObject::Overlay { child } => {
+                self.overlay_queue.push(((**child).clone(), transform, opacity));
+            }
+            RenderObject::Viewport { rect, child } => {
+                let new_clip = Some(self.transform_rect(*rect, &transform));
+                self.viewport_tops.push(transform.map_point(Point::new(rect.x, rect.y)).y);
+                self.build_display_list_recursive(child, transform, opacity, new_clip);
+                self.viewport_tops.pop();
+            }
+            RenderObject::Sticky { section, child } => {
+                let offset = match (self.viewport_tops.last(), transform.invert()) {
+                    (Some(top), Some(inverse)) => {
+                        sticky_offset(*section, child, inverse.map_point(Point::new(0.0, *top)).y)
+                    }
+                    _ => 0.0,
+                };
+                let new_transform = transform.concat(&crate::core::render_object::Matrix::translate(0.0, offset));
+                self.build_display_list_recursive(child, new_transform, opacity, clip);
+            }
             _ => {
                 // Add to display list
                 let bounds = self.calculate_bounds(obj, &transform);
@@ -170,19 +220,8 @@ impl RenderPipeline {
     }
 
     fn transform_rect(&self, rect: Rect, matrix: &crate::core::render_object::Matrix) -> Rect {
-        // Transform rect corners
-        let x1 = rect.x * matrix.values[0][0] + rect.y * matrix.values[0][1] + matrix.values[0][2];
-        let y1 = rect.x * matrix.values[1][0] + rect.y * matrix.values[1][1] + matrix.values[1][2];
-
-        let x2 = (rect.x + rect.width) * matrix.values[0][0] + (rect.y + rect.height) * matrix.values[0][1] + matrix.values[0][2];
-        let y2 = (rect.x + rect.width) * matrix.values[1][0] + (rect.y + rect.height) * matrix.values[1][1] + matrix.values[1][2];
-
-        Rect::new(
-            x1.min(x2),
-            y1.min(y2),
-            (x2 - x1).abs(),
-            (y2 - y1).abs(),
-        )
+        // All four corners, so rotated and skewed content is bounded too
+        matrix.map_rect(rect)
     }
 
     fn calculate_bounds(&self, obj: &RenderObject, transform: &crate::core::render_object::Matrix) -> Rect {
@@ -192,9 +231,10 @@ impl RenderPipeline {
                 // Approximate text bounds
                 self.transform_rect(Rect::new(position.x, position.y, 100.0, 20.0), transform)
             }
-            RenderObject::Image { size } => {
+            RenderObject::Image { size, .. } => {
                 self.transform_rect(Rect::from_size(*size), transform)
             }
+            RenderObject::Path { bounds, .. } => self.transform_rect(*bounds, transform),
             _ => Rect::new(0.0, 0.0, 0.0, 0.0),
         }
     }
@@ -210,4 +250,42 @@ impl RenderPipeline {
     pub fn clear_damage(&mut self) {
         self.damage.clear();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::{Color, Matrix};
+
+    #[test]
+    fn test_sticky_headers_pin_and_push_out() {
+        // Two 150px sections, each with a 20px header, in a 100px viewport
+        let section = |top: f32, color: Color| {
+            RenderObject::transform(
+                Matrix::translate(0.0, top),
+                RenderObject::sticky(
+                    Rect::new(0.0, 0.0, 200.0, 150.0),
+                    RenderObject::rect(Rect::new(0.0, 0.0, 200.0, 20.0), color),
+                ),
+            )
+        };
+        let header_tops = |scroll: f32| {
+            let root = RenderObject::viewport(
+                Rect::new(0.0, 0.0, 200.0, 100.0),
+                RenderObject::transform(
+                    Matrix::translate(0.0, -scroll),
+                    RenderObject::group(vec![section(0.0, Color::RED), section(150.0, Color::BLUE)]),
+                ),
+            );
+            let mut pipeline = RenderPipeline::new(Rect::new(0.0, -500.0, 200.0, 1000.0));
+            pipeline.build_display_list(&root);
+            pipeline.display_list.items.iter().map(|item| item.bounds.y).collect::<Vec<_>>()
+        };
+
+        // Pinned while its section fills the top of the viewport
+        assert_eq!(header_tops(50.0), vec![0.0, 100.0]);
+        // Pushed up by the end of its section as the next header arrives
+        assert_eq!(header_tops(140.0), vec![-10.0, 10.0]);
+        // Out of view; the second header has taken its place
+        assert_eq!(header_tops(200.0), vec![-70.0, 0.0]);
+    }
+}
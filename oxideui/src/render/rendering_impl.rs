@@ -1,11 +1,45 @@
-use crate::core::render_object::{Color as OxColor, Matrix, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{
+    sticky_offset, ClipShape, Color as OxColor, ImageData, ImageFit, Matrix, Point, Rect, RenderEffect, RenderObject,
+    TextAlign, TextStyle,
+};
 use skia_safe::{Canvas, Color as SkColor, FontMgr, FontStyle, Paint, PaintStyle, Typeface};
-use skia_safe::textlayout::{FontCollection, ParagraphBuilder, ParagraphStyle, TextStyle as SkTextStyle};
+use skia_safe::textlayout::{
+    FontCollection, ParagraphBuilder, ParagraphStyle, TextAlign as SkTextAlign, TextDecoration as SkTextDecoration,
+    TextStyle as SkTextStyle, TypefaceFontProvider,
+};
+use crate::render::text::FontManager;
+use crate::layout::Size;
+use anyhow::{Context, Result};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+/// Typefaces kept by `get_or_create_typeface`; apps rarely use more than a
+/// few families in a few styles
+const TYPEFACE_CACHE_SIZE: usize = 64;
+
+/// Decoded images kept by `draw_image`, by `ImageData::id`
+const DECODED_IMAGE_CACHE_SIZE: usize = 128;
 
 pub struct SkiaRenderer {
-    font_cache: std::collections::HashMap<String, Typeface>,
+    font_cache: LruCache<String, Typeface>,
+    /// `None` records bytes that failed to decode so they aren't retried
+    image_cache: LruCache<u64, Option<skia_safe::Image>>,
     font_mgr: FontMgr,
     font_collection: FontCollection,
+    /// Faces registered with `FontManager::register_font`
+    font_provider: TypefaceFontProvider,
+    registered_font_count: usize,
+    /// Overlay content deferred to after the main pass, with its device matrix
+    overlays: Vec<(skia_safe::Matrix, RenderObject)>,
+    /// Device-space top of each enclosing viewport, innermost last
+    viewport_tops: Vec<f32>,
+    /// Background used by the backends when clearing each frame
+    pub clear_color: OxColor,
+    /// Apply `RenderObject::Backdrop` filters; they read back the surface,
+    /// which is only cheap on the GPU, so raster backends leave this off and
+    /// draw the fallback fill
+    pub backdrop_filters: bool,
 }
 
 impl SkiaRenderer {
@@ -14,40 +48,155 @@ impl SkiaRenderer {
         font_collection.set_default_font_manager(FontMgr::new(), None);
 
         Self {
-            font_cache: std::collections::HashMap::new(),
+            font_cache: LruCache::new(NonZeroUsize::new(TYPEFACE_CACHE_SIZE).unwrap()),
+            image_cache: LruCache::new(NonZeroUsize::new(DECODED_IMAGE_CACHE_SIZE).unwrap()),
             font_mgr: FontMgr::new(),
             font_collection,
+            font_provider: TypefaceFontProvider::new(),
+            registered_font_count: 0,
+            overlays: Vec::new(),
+            viewport_tops: Vec::new(),
+            clear_color: OxColor::WHITE,
+            backdrop_filters: false,
         }
     }
 
     pub fn render(&mut self, canvas: &Canvas, render_obj: &RenderObject) {
+        self.load_registered_fonts();
+        self.render_node(canvas, render_obj);
+
+        // Overlay pass: portal content draws on top, free of ancestor clips
+        while !self.overlays.is_empty() {
+            for (matrix, child) in std::mem::take(&mut self.overlays) {
+                canvas.save();
+                canvas.reset_matrix();
+                canvas.concat(&matrix);
+                self.render_node(canvas, &child);
+                canvas.restore();
+            }
+        }
+    }
+
+    fn render_node(&mut self, canvas: &Canvas, render_obj: &RenderObject) {
         match render_obj {
+            // Transparent rects only take up space or catch clicks, like a
+            // popup's dismiss barrier
+            RenderObject::Rect { paint, .. } if paint.color.a == 0 => {}
             RenderObject::Rect { rect, paint } => {
                 self.draw_rect(canvas, rect, &paint.color);
             }
-            RenderObject::Text { content, style, position } => {
-                self.draw_text(canvas, content, style, position);
-            }
-            RenderObject::Image { size } => {
-                self.draw_image_placeholder(canvas, *size);
+            RenderObject::Text { content, style, position, width } => {
+                self.draw_text(canvas, content, style, position, *width);
             }
+            RenderObject::Image { size, data, fit } => match data {
+                Some(data) => self.draw_image(canvas, data, *size, *fit),
+                None => self.draw_image_placeholder(canvas, *size),
+            },
             RenderObject::Clip { rect, child } => {
                 canvas.save();
                 canvas.clip_rect(rect.to_skia_rect(), None, None);
-                self.render(canvas, child);
+                self.render_node(canvas, child);
+                canvas.restore();
+            }
+            RenderObject::ClipShape { shape, child } => {
+                canvas.save();
+                match shape {
+                    ClipShape::RoundedRect { rect, radii } => {
+                        let radii = radii.map(|radius| skia_safe::Vector::new(radius, radius));
+                        canvas.clip_rrect(skia_safe::RRect::new_rect_radii(rect.to_skia_rect(), &radii), None, true);
+                    }
+                    ClipShape::Oval(rect) => {
+                        canvas.clip_rrect(skia_safe::RRect::new_oval(rect.to_skia_rect()), None, true);
+                    }
+                    ClipShape::Path { data, bounds } => match skia_safe::Path::from_svg(data) {
+                        Some(path) => {
+                            canvas.clip_path(&path, None, true);
+                        }
+                        None => {
+                            canvas.clip_rect(bounds.to_skia_rect(), None, true);
+                        }
+                    },
+                }
+                self.render_node(canvas, child);
                 canvas.restore();
             }
             RenderObject::Transform { matrix, child } => {
                 canvas.save();
                 canvas.concat(&self.matrix_to_skia(matrix));
-                self.render(canvas, child);
+                self.render_node(canvas, child);
                 canvas.restore();
             }
             RenderObject::Group { children } => {
                 for child in children {
-                    self.render(canvas, child);
+                    self.render_node(canvas, child);
+                }
+            }
+            RenderObject::Path { data, paint, stroke, .. } => {
+                self.draw_path(canvas, data, paint, *stroke);
+            }
+            RenderObject::Opacity { alpha, child } => {
+                if *alpha >= 1.0 {
+                    self.render_node(canvas, child);
+                } else if *alpha > 0.0 {
+                    canvas.save_layer_alpha_f(None, *alpha);
+                    self.render_node(canvas, child);
+                    canvas.restore();
+                }
+            }
+            RenderObject::Effect { effect, child } => {
+                let Some(filter) = effect_filter(effect) else {
+                    self.render_node(canvas, child);
+                    return;
+                };
+                let mut paint = Paint::default();
+                paint.set_image_filter(filter);
+                canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&paint));
+                self.render_node(canvas, child);
+                canvas.restore();
+            }
+            RenderObject::Backdrop { rect, filter, fallback, child } => {
+                let backdrop = effect_filter(filter).filter(|_| self.backdrop_filters);
+                canvas.save();
+                canvas.clip_rect(rect.to_skia_rect(), None, true);
+                match backdrop {
+                    Some(backdrop) => {
+                        canvas.save_layer(
+                            &skia_safe::canvas::SaveLayerRec::default()
+                                .bounds(&rect.to_skia_rect())
+                                .backdrop(&backdrop),
+                        );
+                        canvas.restore();
+                    }
+                    None => self.draw_rect(canvas, rect, fallback),
                 }
+                self.render_node(canvas, child);
+                canvas.restore();
+            }
+            RenderObject::Overlay { child } => {
+                self.overlays.push((canvas.local_to_device_as_3x3(), (**child).clone()));
             }
+            RenderObject::Viewport { rect, child } => {
+                canvas.save();
+                canvas.clip_rect(rect.to_skia_rect(), None, None);
+                let top = canvas.local_to_device_as_3x3().map_point((rect.x, rect.y)).y;
+                self.viewport_tops.push(top);
+                self.render_node(canvas, child);
+                self.viewport_tops.pop();
+                canvas.restore();
+            }
+            RenderObject::Sticky { section, child } => {
+                let local_top = self.viewport_tops.last().and_then(|top| {
+                    let inverse = canvas.local_to_device_as_3x3().invert()?;
+                    Some(inverse.map_point((0.0, *top)).y)
+                });
+                canvas.save();
+                if let Some(local_top) = local_top {
+                    canvas.translate((0.0, sticky_offset(*section, child, local_top)));
+                }
+                self.render_node(canvas, child);
+                canvas.restore();
+            }
+            RenderObject::Element { child, .. } => self.render_node(canvas, child),
             RenderObject::None => {}
         }
     }
@@ -60,12 +209,37 @@ impl SkiaRenderer {
         canvas.draw_rect(rect.to_skia_rect(), &paint);
     }
 
-    fn draw_text(&mut self, canvas: &Canvas, content: &str, style: &TextStyle, position: &Point) {
-        let paragraph_style = ParagraphStyle::new();
+    fn draw_path(&self, canvas: &Canvas, data: &str, paint: &crate::core::render_object::Paint, stroke: bool) {
+        let Some(path) = skia_safe::Path::from_svg(data) else {
+            return;
+        };
+        let mut sk_paint = Paint::default();
+        sk_paint.set_color(SkColor::from_argb(paint.color.a, paint.color.r, paint.color.g, paint.color.b));
+        sk_paint.set_anti_alias(paint.anti_alias);
+        if stroke {
+            sk_paint.set_style(PaintStyle::Stroke);
+            sk_paint.set_stroke_width(paint.stroke_width);
+            sk_paint.set_stroke_cap(skia_safe::paint::Cap::Round);
+            sk_paint.set_stroke_join(skia_safe::paint::Join::Round);
+        } else {
+            sk_paint.set_style(PaintStyle::Fill);
+        }
+        canvas.draw_path(&path, &sk_paint);
+    }
+
+    fn draw_text(&mut self, canvas: &Canvas, content: &str, style: &TextStyle, position: &Point, width: Option<f32>) {
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style.set_text_align(match style.align {
+            TextAlign::Left => SkTextAlign::Left,
+            TextAlign::Center => SkTextAlign::Center,
+            TextAlign::Right => SkTextAlign::Right,
+            TextAlign::Justify => SkTextAlign::Justify,
+        });
         let mut text_style = SkTextStyle::new();
 
         let typeface = self.get_or_create_typeface(&style.font_family, style.bold, style.italic);
         text_style.set_typeface(Some(typeface));
+        text_style.set_font_families(&[style.font_family.as_str()]);
         text_style.set_font_size(style.font_size);
         text_style.set_color(SkColor::from_argb(
             style.color.a,
@@ -73,6 +247,23 @@ impl SkiaRenderer {
             style.color.g,
             style.color.b,
         ));
+        if let Some(line_height) = style.line_height {
+            text_style.set_height(line_height);
+            text_style.set_height_override(true);
+        }
+        text_style.set_letter_spacing(style.letter_spacing);
+        if !style.decoration.is_none() {
+            let mut decoration = SkTextDecoration::NO_DECORATION;
+            if style.decoration.underline {
+                decoration |= SkTextDecoration::UNDERLINE;
+            }
+            if style.decoration.strikethrough {
+                decoration |= SkTextDecoration::LINE_THROUGH;
+            }
+            let color = style.decoration.color.unwrap_or(style.color);
+            text_style.set_decoration_type(decoration);
+            text_style.set_decoration_color(SkColor::from_argb(color.a, color.r, color.g, color.b));
+        }
 
         let mut paragraph_builder = ParagraphBuilder::new(&paragraph_style, self.font_collection.clone());
         paragraph_builder.push_style(&text_style);
@@ -80,6 +271,13 @@ impl SkiaRenderer {
 
         let mut paragraph = paragraph_builder.build();
         paragraph.layout(f32::INFINITY);
+        if style.align != TextAlign::Left || width.is_some() {
+            // Aligned within the box the text was laid out in, else lines of
+            // a multi-line string align against the widest one. Text wider
+            // than its box overflows rather than wrapping again.
+            let natural = paragraph.max_intrinsic_width().ceil();
+            paragraph.layout(width.map_or(natural, |width| width.max(natural)));
+        }
 
         // Draw text using the paragraph's draw method instead of text_blob
         canvas.save();
@@ -88,6 +286,28 @@ impl SkiaRenderer {
         canvas.restore();
     }
 
+    /// Pick up fonts registered since the last frame
+    fn load_registered_fonts(&mut self) {
+        let fonts = FontManager::registered_fonts_since(self.registered_font_count);
+        if fonts.is_empty() {
+            return;
+        }
+
+        for font in &fonts {
+            match self.font_mgr.new_from_data(&font.data, None) {
+                Some(typeface) => {
+                    self.font_provider.register_typeface(typeface, Some(font.family.as_str()));
+                }
+                None => eprintln!("⚠️ Failed to load registered font \"{}\"", font.family),
+            }
+        }
+        self.registered_font_count += fonts.len();
+        self.font_collection
+            .set_asset_font_manager(Some(FontMgr::from(self.font_provider.clone())));
+        // Families may now resolve to a registered face instead of a fallback
+        self.font_cache.clear();
+    }
+
     fn get_or_create_typeface(&mut self, family: &str, bold: bool, italic: bool) -> Typeface {
         let cache_key = format!(
             "{}_{}{}",
@@ -107,9 +327,9 @@ impl SkiaRenderer {
             (false, false) => FontStyle::normal(),
         };
 
-        let typeface = self
-            .font_mgr
+        let typeface = FontMgr::from(self.font_provider.clone())
             .match_family_style(family, font_style)
+            .or_else(|| self.font_mgr.match_family_style(family, font_style))
             .or_else(|| self.font_mgr.match_family_style("sans-serif", font_style))
             .or_else(|| self.font_mgr.match_family_style("", font_style))
             .unwrap_or_else(|| {
@@ -118,10 +338,32 @@ impl SkiaRenderer {
                     .expect("Failed to create any typeface")
             });
 
-        self.font_cache.insert(cache_key.clone(), typeface.clone());
+        self.font_cache.put(cache_key, typeface.clone());
         typeface
     }
 
+    fn draw_image(&mut self, canvas: &Canvas, data: &ImageData, size: Size, fit: ImageFit) {
+        let image = self
+            .image_cache
+            .get_or_insert(data.id(), || skia_safe::Image::from_encoded(skia_safe::Data::new_copy(data.bytes())))
+            .clone();
+        let Some(image) = image else {
+            self.draw_image_placeholder(canvas, size);
+            return;
+        };
+
+        let (src, dst) = fit_image(Size::new(image.width() as f32, image.height() as f32), size, fit);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        canvas.draw_image_rect_with_sampling_options(
+            &image,
+            Some((&src.to_skia_rect(), skia_safe::canvas::SrcRectConstraint::Fast)),
+            dst.to_skia_rect(),
+            skia_safe::SamplingOptions::new(skia_safe::FilterMode::Linear, skia_safe::MipmapMode::Linear),
+            &paint,
+        );
+    }
+
     fn draw_image_placeholder(&self, canvas: &Canvas, size: crate::layout::Size) {
         let mut paint = Paint::default();
         paint.set_color(SkColor::from_rgb(200, 200, 200));
@@ -153,10 +395,96 @@ impl SkiaRenderer {
     pub fn clear(&mut self, canvas: &Canvas, color: OxColor) {
         canvas.clear(SkColor::from_argb(color.a, color.r, color.g, color.b));
     }
+
+    /// Write `render_obj` to `path` as an SVG document of the given size
+    ///
+    /// Shapes and text stay vector; images are embedded as PNG.
+    pub fn export_svg(&mut self, render_obj: &RenderObject, size: Size, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bounds = skia_safe::Rect::from_wh(size.width, size.height);
+        let canvas = skia_safe::svg::Canvas::new(bounds, None);
+        self.export_page(&canvas, render_obj);
+        let data = canvas.end();
+
+        std::fs::write(path, data.as_bytes())
+            .with_context(|| format!("Failed to write SVG to {}", path.display()))
+    }
+
+    /// Write `render_obj` to `path` as a single-page PDF of the given size
+    pub fn export_pdf(&mut self, render_obj: &RenderObject, size: Size, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut output = Vec::new();
+        {
+            let document = skia_safe::pdf::new_document(&mut output, None);
+            let mut page = document.begin_page((size.width, size.height), None);
+            self.export_page(page.canvas(), render_obj);
+            page.end_page().close();
+        }
+
+        std::fs::write(path, &output)
+            .with_context(|| format!("Failed to write PDF to {}", path.display()))
+    }
+
+    fn export_page(&mut self, canvas: &Canvas, render_obj: &RenderObject) {
+        if self.clear_color.a > 0 {
+            self.clear(canvas, self.clear_color);
+        }
+        self.render(canvas, render_obj);
+    }
 }
 
 impl Default for SkiaRenderer {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Skia filter chain for `effect`: color adjustments, then tint, then blur
+fn effect_filter(effect: &RenderEffect) -> Option<skia_safe::ImageFilter> {
+    if effect.is_identity() {
+        return None;
+    }
+    let mut filter = None;
+    if let Some(matrix) = effect.color_matrix() {
+        let color = skia_safe::color_filters::matrix_row_major(&matrix, None);
+        filter = skia_safe::image_filters::color_filter(color, filter, None);
+    }
+    if let Some(tint) = effect.tint.filter(|tint| tint.a > 0) {
+        let color = skia_safe::color_filters::blend(
+            SkColor::from_argb(tint.a, tint.r, tint.g, tint.b),
+            skia_safe::BlendMode::SrcATop,
+        )?;
+        filter = skia_safe::image_filters::color_filter(color, filter, None);
+    }
+    if effect.blur > 0.0 {
+        filter = skia_safe::image_filters::blur((effect.blur, effect.blur), skia_safe::TileMode::Decal, filter, None);
+    }
+    filter
+}
+
+/// Source rect within an image of `image` size and destination rect within
+/// a box of `size`, for drawing the image by `fit` centred in the box
+fn fit_image(image: Size, size: Size, fit: ImageFit) -> (Rect, Rect) {
+    let full = Rect::new(0.0, 0.0, image.width, image.height);
+    if image.width <= 0.0 || image.height <= 0.0 {
+        return (full, Rect::from_size(size));
+    }
+    let scale_x = size.width / image.width;
+    let scale_y = size.height / image.height;
+    let scale = match fit {
+        ImageFit::Fill => return (full, Rect::from_size(size)),
+        ImageFit::Contain => scale_x.min(scale_y),
+        ImageFit::ScaleDown => scale_x.min(scale_y).min(1.0),
+        ImageFit::Cover => {
+            // Crop the source to the box's aspect ratio
+            let scale = scale_x.max(scale_y);
+            let src_width = size.width / scale;
+            let src_height = size.height / scale;
+            let src = Rect::new((image.width - src_width) / 2.0, (image.height - src_height) / 2.0, src_width, src_height);
+            return (src, Rect::from_size(size));
+        }
+    };
+    let width = image.width * scale;
+    let height = image.height * scale;
+    (full, Rect::new((size.width - width) / 2.0, (size.height - height) / 2.0, width, height))
+}
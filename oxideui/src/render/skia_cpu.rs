@@ -67,7 +67,7 @@ impl RenderBackend for SkiaCPURenderer {
         let canvas = self.surface.canvas();
 
         // Clear with background
-        self.skia_renderer.clear(canvas, crate::core::Color::from_hex(0xFFFFFF));
+        self.skia_renderer.clear(canvas, self.skia_renderer.clear_color);
 
         Ok(())
     }
@@ -81,7 +81,7 @@ impl RenderBackend for SkiaCPURenderer {
         let canvas = self.surface.canvas();
 
         // Clear canvas
-        self.skia_renderer.clear(canvas, crate::core::Color::from_hex(0xFFFFFF));
+        self.skia_renderer.clear(canvas, self.skia_renderer.clear_color);
 
         // Actually render the widget tree!
         self.skia_renderer.render(canvas, render_obj);
@@ -155,6 +155,10 @@ impl RenderBackend for SkiaCPURenderer {
         Ok(())
     }
 
+    fn set_clear_color(&mut self, color: crate::core::Color) {
+        self.skia_renderer.clear_color = color;
+    }
+
     fn cleanup(&mut self) {
         println!("[Skia CPU] Cleaning up renderer");
     }
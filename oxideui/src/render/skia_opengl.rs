@@ -152,12 +152,15 @@ impl SkiaOpenGLRenderer {
             None => return Err(anyhow::anyhow!("Window is required")),
         };
 
+        let mut skia_renderer = SkiaRenderer::new();
+        skia_renderer.backdrop_filters = true;
+
         Ok(Self {
             gl_context,
             gl_surface,
             skia_context,
             skia_surface: None,
-            skia_renderer: SkiaRenderer::new(),
+            skia_renderer,
             width: width as u32,
             height: height as u32,
             window: actual_window.into(), // Convert Window to Arc<Window>
@@ -215,7 +218,7 @@ impl RenderBackend for SkiaOpenGLRenderer {
 
         if let Some(ref mut surface) = self.skia_surface {
             let canvas = surface.canvas();
-            self.skia_renderer.clear(canvas, crate::core::Color::from_hex(0xFFFFFF));
+            self.skia_renderer.clear(canvas, self.skia_renderer.clear_color);
             self.skia_context.flush_and_submit();
         }
 
@@ -233,7 +236,7 @@ impl RenderBackend for SkiaOpenGLRenderer {
 
         if let Some(ref mut surface) = self.skia_surface {
             let canvas = surface.canvas();
-            self.skia_renderer.clear(canvas, crate::core::Color::from_hex(0xFFFFFF));
+            self.skia_renderer.clear(canvas, self.skia_renderer.clear_color);
             self.skia_renderer.render(canvas, render_obj);
             self.skia_context.flush_and_submit();
         }
@@ -269,6 +272,10 @@ impl RenderBackend for SkiaOpenGLRenderer {
         Ok(())
     }
 
+    fn set_clear_color(&mut self, color: crate::core::Color) {
+        self.skia_renderer.clear_color = color;
+    }
+
     fn cleanup(&mut self) {
         println!("[Skia OpenGL] Cleaning up renderer");
         self.skia_surface = None;
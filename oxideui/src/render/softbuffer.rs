@@ -3,7 +3,9 @@ use softbuffer::{Context, Surface};
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use winit::window::Window;
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{sticky_offset, Color, Matrix, Point, Rect, RenderObject, TextAlign, TextStyle};
+use crate::render::text::{baseline, text_width};
+use super::svg_path::{self, Polyline};
 use super::RenderBackend;
 
 pub struct SoftbufferRenderer {
@@ -12,6 +14,7 @@ pub struct SoftbufferRenderer {
     width: u32,
     height: u32,
     window: Arc<Window>,
+    clear_color: u32,
 }
 
 impl SoftbufferRenderer {
@@ -29,6 +32,7 @@ impl SoftbufferRenderer {
             width: 0,
             height: 0,
             window,
+            clear_color: 0xFFFFFFFF,
         })
     }
 
@@ -57,26 +61,120 @@ impl SoftbufferRenderer {
     fn render_object_to_buffer(
         buffer: &mut [u32],
         obj: &RenderObject,
+        matrix: &Matrix,
         width: u32,
         height: u32,
+        overlays: &mut Vec<(Matrix, RenderObject)>,
+        viewport_top: Option<f32>,
     ) {
         match obj {
             RenderObject::Rect { rect, paint } => {
-                Self::draw_rect_to_buffer(buffer, rect, paint.color, width, height);
+                Self::draw_rect_to_buffer(buffer, &matrix.map_rect(*rect), paint.color, width, height);
             }
-            RenderObject::Text { content, style, position } => {
-                Self::draw_text_to_buffer(buffer, content, style, position, width, height);
+            RenderObject::Text { content, style, position, width: box_width } => {
+                // Only axis-aligned scale and translation are supported here
+                let style = TextStyle {
+                    font_size: style.font_size * matrix.values[1][1].abs(),
+                    letter_spacing: style.letter_spacing * matrix.values[0][0].abs(),
+                    ..style.clone()
+                };
+                let mut position = matrix.map_point(*position);
+                let text_width = text_width(content, &style);
+                if let Some(box_width) = box_width {
+                    let slack = (box_width * matrix.values[0][0].abs() - text_width).max(0.0);
+                    position.x += match style.align {
+                        TextAlign::Left | TextAlign::Justify => 0.0,
+                        TextAlign::Center => slack / 2.0,
+                        TextAlign::Right => slack,
+                    };
+                }
+                Self::draw_text_to_buffer(buffer, content, &style, &position, width, height);
+
+                let color = style.decoration.color.unwrap_or(style.color);
+                let thickness = (style.font_size / 14.0).max(1.0);
+                if style.decoration.underline {
+                    let y = position.y + baseline(&style) + thickness;
+                    let line = Rect::new(position.x, y, text_width, thickness);
+                    Self::draw_rect_to_buffer(buffer, &line, color, width, height);
+                }
+                if style.decoration.strikethrough {
+                    let y = position.y + baseline(&style) - style.font_size * 0.3;
+                    let line = Rect::new(position.x, y, text_width, thickness);
+                    Self::draw_rect_to_buffer(buffer, &line, color, width, height);
+                }
             }
             RenderObject::Group { children } => {
                 for child in children {
-                    Self::render_object_to_buffer(buffer, child, width, height);
+                    Self::render_object_to_buffer(buffer, child, matrix, width, height, overlays, viewport_top);
+                }
+            }
+            RenderObject::Transform { child, matrix: local } => {
+                Self::render_object_to_buffer(buffer, child, &matrix.concat(local), width, height, overlays, viewport_top);
+            }
+            // No image filters on the CPU fallback: effects draw unfiltered
+            RenderObject::Clip { child, .. }
+            | RenderObject::ClipShape { child, .. }
+            | RenderObject::Effect { child, .. }
+            | RenderObject::Element { child, .. } => {
+                Self::render_object_to_buffer(buffer, child, matrix, width, height, overlays, viewport_top);
+            }
+            RenderObject::Opacity { alpha, child } if *alpha >= 1.0 => {
+                Self::render_object_to_buffer(buffer, child, matrix, width, height, overlays, viewport_top);
+            }
+            // Drawn as one layer: the subtree over a copy of what's beneath,
+            // mixed back in at `alpha`, so overlapping children don't show
+            // through each other
+            RenderObject::Opacity { alpha, child } => {
+                if *alpha > 0.0 {
+                    let mut layer = buffer.to_vec();
+                    Self::render_object_to_buffer(&mut layer, child, matrix, width, height, overlays, viewport_top);
+                    let weight = (alpha * 255.0).round() as u32;
+                    for (pixel, drawn) in buffer.iter_mut().zip(layer) {
+                        if *pixel != drawn {
+                            *pixel = mix(*pixel, drawn, weight);
+                        }
+                    }
                 }
             }
-            RenderObject::Transform { child, .. } => {
-                Self::render_object_to_buffer(buffer, child, width, height);
+            RenderObject::Backdrop { rect, fallback, child, .. } => {
+                Self::draw_rect_to_buffer(buffer, &matrix.map_rect(*rect), *fallback, width, height);
+                Self::render_object_to_buffer(buffer, child, matrix, width, height, overlays, viewport_top);
             }
-            RenderObject::Clip { child, .. } => {
-                Self::render_object_to_buffer(buffer, child, width, height);
+            RenderObject::Overlay { child } => {
+                // Drawn after the main pass so it stays on top
+                overlays.push((*matrix, (**child).clone()));
+            }
+            RenderObject::Viewport { rect, child } => {
+                let top = matrix.map_point(Point::new(rect.x, rect.y)).y;
+                Self::render_object_to_buffer(buffer, child, matrix, width, height, overlays, Some(top));
+            }
+            RenderObject::Sticky { section, child } => {
+                let offset = match (viewport_top, matrix.invert()) {
+                    (Some(top), Some(inverse)) => {
+                        sticky_offset(*section, child, inverse.map_point(Point::new(0.0, top)).y)
+                    }
+                    _ => 0.0,
+                };
+                let matrix = matrix.concat(&Matrix::translate(0.0, offset));
+                Self::render_object_to_buffer(buffer, child, &matrix, width, height, overlays, viewport_top);
+            }
+            RenderObject::Path { data, paint, stroke, .. } => {
+                let subpaths: Vec<Polyline> = svg_path::flatten(data)
+                    .into_iter()
+                    .map(|mut subpath| {
+                        subpath.points.iter_mut().for_each(|point| *point = matrix.map_point(*point));
+                        subpath
+                    })
+                    .collect();
+                if *stroke {
+                    // Widths scale with the transform like everything else
+                    let values = matrix.values;
+                    let scale = (values[0][0] * values[1][1] - values[0][1] * values[1][0]).abs().sqrt();
+                    let half_width = (paint.stroke_width * scale / 2.0).max(0.5);
+                    Self::stroke_path_to_buffer(buffer, &subpaths, half_width, paint.color, width, height);
+                } else {
+                    Self::fill_path_to_buffer(buffer, &subpaths, paint.color, width, height);
+                }
             }
             _ => {}
         }
@@ -89,21 +187,121 @@ impl SoftbufferRenderer {
         width: u32,
         height: u32,
     ) {
+        // Nothing to blend, e.g. a box that only reserves space
+        if color.a == 0 {
+            return;
+        }
         let x1 = rect.x.max(0.0).min(width as f32) as u32;
         let y1 = rect.y.max(0.0).min(height as f32) as u32;
         let x2 = ((rect.x + rect.width).max(0.0).min(width as f32)) as u32;
         let y2 = ((rect.y + rect.height).max(0.0).min(height as f32)) as u32;
 
-        let color_u32 = ((color.a as u32) << 24)
-            | ((color.r as u32) << 16)
-            | ((color.g as u32) << 8)
-            | (color.b as u32);
-
         for y in y1..y2 {
             for x in x1..x2 {
                 let idx = (y * width + x) as usize;
                 if idx < buffer.len() {
-                    buffer[idx] = color_u32;
+                    buffer[idx] = blend(buffer[idx], color);
+                }
+            }
+        }
+    }
+
+    /// Fill with the nonzero winding rule, sampling each pixel's center
+    fn fill_path_to_buffer(buffer: &mut [u32], subpaths: &[Polyline], color: Color, width: u32, height: u32) {
+        if color.a == 0 {
+            return;
+        }
+        let color_u32 = pack(color);
+        let edges: Vec<(Point, Point)> = subpaths
+            .iter()
+            .flat_map(|subpath| {
+                // Fills always close their subpaths
+                let points = &subpath.points;
+                points.iter().zip(points.iter().skip(1).chain(points.first())).map(|(a, b)| (*a, *b))
+            })
+            .filter(|(a, b)| a.y != b.y)
+            .collect();
+        let Some((top, bottom)) = edges
+            .iter()
+            .flat_map(|(a, b)| [a.y, b.y])
+            .fold(None, |range: Option<(f32, f32)>, y| Some(range.map_or((y, y), |(lo, hi)| (lo.min(y), hi.max(y)))))
+        else {
+            return;
+        };
+
+        let first_row = top.max(0.0) as u32;
+        let last_row = (bottom.ceil().max(0.0) as u32).min(height);
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for y in first_row..last_row {
+            let sample_y = y as f32 + 0.5;
+            crossings.clear();
+            for (a, b) in &edges {
+                let (lo, hi, winding) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+                if sample_y >= lo.y && sample_y < hi.y {
+                    let t = (sample_y - lo.y) / (hi.y - lo.y);
+                    crossings.push((lo.x + t * (hi.x - lo.x), winding));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding == 0 {
+                    continue;
+                }
+                // Pixels whose centers lie between the two crossings
+                let x1 = (pair[0].0 - 0.5).ceil().clamp(0.0, width as f32) as u32;
+                let x2 = (pair[1].0 - 0.5).ceil().clamp(0.0, width as f32) as u32;
+                let row = (y * width) as usize;
+                for x in x1..x2 {
+                    if let Some(pixel) = buffer.get_mut(row + x as usize) {
+                        *pixel = color_u32;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Paint every pixel whose center is within `half_width` of a segment
+    fn stroke_path_to_buffer(
+        buffer: &mut [u32],
+        subpaths: &[Polyline],
+        half_width: f32,
+        color: Color,
+        width: u32,
+        height: u32,
+    ) {
+        if color.a == 0 {
+            return;
+        }
+        let color_u32 = pack(color);
+        for subpath in subpaths {
+            let points = &subpath.points;
+            let closing = subpath.closed.then(|| (points[points.len() - 1], points[0]));
+            let segments = points.windows(2).map(|pair| (pair[0], pair[1])).chain(closing);
+            for (a, b) in segments {
+                let x1 = (a.x.min(b.x) - half_width).floor().clamp(0.0, width as f32) as u32;
+                let x2 = (a.x.max(b.x) + half_width).ceil().clamp(0.0, width as f32) as u32;
+                let y1 = (a.y.min(b.y) - half_width).floor().clamp(0.0, height as f32) as u32;
+                let y2 = (a.y.max(b.y) + half_width).ceil().clamp(0.0, height as f32) as u32;
+                let (dx, dy) = (b.x - a.x, b.y - a.y);
+                let length_squared = dx * dx + dy * dy;
+                for y in y1..y2 {
+                    for x in x1..x2 {
+                        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                        let t = if length_squared > 0.0 {
+                            (((px - a.x) * dx + (py - a.y) * dy) / length_squared).clamp(0.0, 1.0)
+                        } else {
+                            0.0
+                        };
+                        let (ex, ey) = (px - (a.x + t * dx), py - (a.y + t * dy));
+                        if ex * ex + ey * ey <= half_width * half_width {
+                            if let Some(pixel) = buffer.get_mut((y * width + x) as usize) {
+                                *pixel = color_u32;
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -121,13 +319,14 @@ impl SoftbufferRenderer {
         let y = position.y.max(0.0) as u32;
         let char_width = (style.font_size * 0.6) as u32;
         let char_height = (style.font_size * 1.2) as u32;
+        let advance = (style.font_size * 0.6 + style.letter_spacing).max(1.0) as u32;
         let color_u32 = ((style.color.a as u32) << 24)
             | ((style.color.r as u32) << 16)
             | ((style.color.g as u32) << 8)
             | (style.color.b as u32);
 
         for (i, ch) in text.chars().enumerate() {
-            let char_x = x + (i as u32 * char_width);
+            let char_x = x + (i as u32 * advance);
             if char_x >= width || y >= height {
                 break;
             }
@@ -150,20 +349,44 @@ impl SoftbufferRenderer {
     }
 }
 
+fn pack(color: Color) -> u32 {
+    ((color.a as u32) << 24) | ((color.r as u32) << 16) | ((color.g as u32) << 8) | (color.b as u32)
+}
+
+/// `color` drawn over the packed pixel `under`
+fn blend(under: u32, color: Color) -> u32 {
+    let alpha = color.a as u32;
+    if alpha == 255 {
+        return pack(color);
+    }
+    let mix = |over: u8, shift: u32| (over as u32 * alpha + ((under >> shift) & 0xFF) * (255 - alpha) + 127) / 255;
+    let a = alpha + ((under >> 24) * (255 - alpha) + 127) / 255;
+    (a << 24) | (mix(color.r, 16) << 16) | (mix(color.g, 8) << 8) | mix(color.b, 0)
+}
+
+/// Packed pixels `under` and `over` mixed channel by channel, `weight` out
+/// of 255 towards `over`
+fn mix(under: u32, over: u32, weight: u32) -> u32 {
+    (0..4).fold(0, |mixed, channel| {
+        let shift = channel * 8;
+        let (a, b) = ((under >> shift) & 0xFF, (over >> shift) & 0xFF);
+        mixed | (((b * weight + a * (255 - weight) + 127) / 255) << shift)
+    })
+}
+
 impl RenderBackend for SoftbufferRenderer {
     fn draw(&mut self, width: u32, height: u32) -> Result<()> {
         if width != self.width || height != self.height {
             self.resize(width, height)?;
         }
 
+        let clear_color = self.clear_color;
         let surface = self.ensure_surface()?;
         let mut buffer = surface.buffer_mut()
             .map_err(|e| anyhow!("Failed to get buffer: {}", e))?;
 
-        // Clear with white background
-        for pixel in buffer.iter_mut() {
-            *pixel = 0xFFFFFFFF;
-        }
+        // Clear with the background color
+        buffer.fill(clear_color);
 
         buffer.present()
             .map_err(|e| anyhow!("Failed to present buffer: {}", e))?;
@@ -176,16 +399,21 @@ impl RenderBackend for SoftbufferRenderer {
             self.resize(width, height)?;
         }
 
+        let clear_color = self.clear_color;
         let surface = self.ensure_surface()?;
         let mut buffer = surface.buffer_mut()
             .map_err(|e| anyhow!("Failed to get buffer: {}", e))?;
 
-        // Clear with white background
-        for pixel in buffer.iter_mut() {
-            *pixel = 0xFFFFFFFF;
-        }
+        // Clear with the background color
+        buffer.fill(clear_color);
 
-        Self::render_object_to_buffer(&mut buffer, render_obj, width, height);
+        let mut overlays = Vec::new();
+        Self::render_object_to_buffer(&mut buffer, render_obj, &Matrix::identity(), width, height, &mut overlays, None);
+        while !overlays.is_empty() {
+            for (matrix, overlay) in std::mem::take(&mut overlays) {
+                Self::render_object_to_buffer(&mut buffer, &overlay, &matrix, width, height, &mut overlays, None);
+            }
+        }
 
         buffer.present()
             .map_err(|e| anyhow!("Failed to present buffer: {}", e))?;
@@ -214,6 +442,13 @@ impl RenderBackend for SoftbufferRenderer {
         Ok(())
     }
 
+    fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = ((color.a as u32) << 24)
+            | ((color.r as u32) << 16)
+            | ((color.g as u32) << 8)
+            | (color.b as u32);
+    }
+
     fn cleanup(&mut self) {
         println!("[Softbuffer] Cleaning up renderer");
         self.surface = None;
@@ -223,4 +458,128 @@ impl RenderBackend for SoftbufferRenderer {
     fn name(&self) -> &str {
         "Softbuffer"
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::RenderEffect;
+    use crate::widgets::DismissBarrier;
+
+    #[test]
+    fn test_transparent_fills_leave_the_background() {
+        let (width, height) = (40, 20);
+        let mut buffer = vec![0xFFFFFFFF; (width * height) as usize];
+        // A widget's reserved space around a smaller red box, and a popup
+        // over its window-sized dismiss barrier
+        let frame = RenderObject::group(vec![
+            RenderObject::rect(Rect::new(0.0, 0.0, 40.0, 20.0), Color::TRANSPARENT),
+            RenderObject::rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED),
+            DismissBarrier::overlay(RenderObject::rect(Rect::new(20.0, 0.0, 10.0, 10.0), Color::BLUE)),
+        ]);
+        let mut overlays = Vec::new();
+        SoftbufferRenderer::render_object_to_buffer(&mut buffer, &frame, &Matrix::identity(), width, height, &mut overlays, None);
+        for (matrix, overlay) in overlays {
+            SoftbufferRenderer::render_object_to_buffer(&mut buffer, &overlay, &matrix, width, height, &mut Vec::new(), None);
+        }
+
+        assert_eq!(buffer[0], 0xFFFF0000);
+        assert_eq!(buffer[20], 0xFF0000FF);
+        assert_eq!(buffer[(15 * width + 30) as usize], 0xFFFFFFFF);
+    }
+
+    #[test]
+    fn test_paths_are_filled_and_stroked() {
+        let (width, height) = (40, 40);
+        let mut buffer = vec![0xFFFFFFFF; (width * height) as usize];
+        let pixel = |buffer: &[u32], x: u32, y: u32| buffer[(y * width + x) as usize];
+        // A filled triangle, and a focus-ring-like stroked rounded square
+        // moved by its parent's transform
+        let frame = RenderObject::group(vec![
+            RenderObject::fill_path("M0 0L10 0L0 10Z", Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED),
+            RenderObject::transform(
+                Matrix::translate(20.0, 20.0),
+                RenderObject::stroke_path(
+                    "M4 0H12A4 4 0 0 1 16 4V12A4 4 0 0 1 12 16H4A4 4 0 0 1 0 12V4A4 4 0 0 1 4 0Z",
+                    Rect::new(0.0, 0.0, 16.0, 16.0),
+                    Color::BLUE,
+                    2.0,
+                ),
+            ),
+        ]);
+        SoftbufferRenderer::render_object_to_buffer(&mut buffer, &frame, &Matrix::identity(), width, height, &mut Vec::new(), None);
+
+        assert_eq!(pixel(&buffer, 2, 2), 0xFFFF0000);
+        assert_eq!(pixel(&buffer, 8, 8), 0xFFFFFFFF);
+        // The ring's edges are drawn, its inside is not
+        assert_eq!(pixel(&buffer, 28, 20), 0xFF0000FF);
+        assert_eq!(pixel(&buffer, 20, 28), 0xFF0000FF);
+        assert_eq!(pixel(&buffer, 35, 28), 0xFF0000FF);
+        assert_eq!(pixel(&buffer, 28, 28), 0xFFFFFFFF);
+    }
+
+    #[test]
+    fn test_translucent_fills_blend_with_what_is_beneath() {
+        let (width, height) = (10, 10);
+        let mut buffer = vec![0xFFFFFFFF; (width * height) as usize];
+        // A frosted panel's fallback over white, then over black
+        let frame = RenderObject::group(vec![
+            RenderObject::rect(Rect::new(5.0, 0.0, 5.0, 10.0), Color::BLACK),
+            RenderObject::backdrop(
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                RenderEffect::blur(16.0),
+                Color::BLACK.with_alpha(128),
+                RenderObject::None,
+            ),
+        ]);
+        SoftbufferRenderer::render_object_to_buffer(&mut buffer, &frame, &Matrix::identity(), width, height, &mut Vec::new(), None);
+
+        assert_eq!(buffer[0], 0xFF7F7F7F);
+        assert_eq!(buffer[9], 0xFF000000);
+    }
+
+    #[test]
+    fn test_opacity_fades_its_subtree_as_one_layer() {
+        let (width, height) = (10, 10);
+        let mut buffer = vec![0xFFFFFFFF; (width * height) as usize];
+        // Two overlapping red boxes at half opacity over white
+        let frame = RenderObject::opacity(
+            0.5,
+            RenderObject::group(vec![
+                RenderObject::rect(Rect::new(0.0, 0.0, 6.0, 10.0), Color::RED),
+                RenderObject::rect(Rect::new(4.0, 0.0, 6.0, 10.0), Color::RED),
+            ]),
+        );
+        SoftbufferRenderer::render_object_to_buffer(&mut buffer, &frame, &Matrix::identity(), width, height, &mut Vec::new(), None);
+
+        // The overlap is no darker than the rest
+        assert_eq!(buffer[0], 0xFFFF7F7F);
+        assert_eq!(buffer[5], 0xFFFF7F7F);
+        assert_eq!(buffer[9], 0xFFFF7F7F);
+    }
+
+    #[test]
+    fn test_text_is_aligned_in_its_box_and_decorated() {
+        let (width, height) = (60, 20);
+        let mut buffer = vec![0xFFFFFFFF; (width * height) as usize];
+        let pixel = |buffer: &[u32], x: u32, y: u32| buffer[(y * width + x) as usize];
+        // Two 6px characters at the right of a 60px box, underlined in red
+        let style = TextStyle {
+            font_size: 10.0,
+            align: TextAlign::Right,
+            decoration: crate::core::render_object::TextDecoration {
+                underline: true,
+                strikethrough: false,
+                color: Some(Color::RED),
+            },
+            ..Default::default()
+        };
+        let text = RenderObject::aligned_text("ab".into(), style, Point::ZERO, 60.0);
+        SoftbufferRenderer::render_object_to_buffer(&mut buffer, &text, &Matrix::identity(), width, height, &mut Vec::new(), None);
+
+        assert_eq!(pixel(&buffer, 2, 2), 0xFFFFFFFF);
+        assert_eq!(pixel(&buffer, 49, 2), 0xFF000000);
+        // The underline runs under the gap between the glyphs
+        assert!((0..height).any(|y| pixel(&buffer, 52, y) == 0xFFFF0000));
+    }
+}
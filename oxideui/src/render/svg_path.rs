@@ -0,0 +1,319 @@
+//! SVG path data flattened into polylines
+//!
+//! The CPU renderer has no path support of its own, so `RenderObject::Path`
+//! data is parsed here and curves and arcs are cut into short lines. All
+//! commands of the SVG path grammar are understood; malformed data keeps the
+//! subpaths parsed before the error, like browsers do.
+
+use std::f32::consts::PI;
+
+use crate::core::render_object::Point;
+
+/// Lines per curve; enough for icon- and control-sized shapes
+const CURVE_SEGMENTS: usize = 16;
+
+/// One subpath: its points in order, and whether it was closed with `Z`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Polyline {
+    pub points: Vec<Point>,
+    pub closed: bool,
+}
+
+/// Flatten `data` into subpaths in the path's own coordinates
+pub(crate) fn flatten(data: &str) -> Vec<Polyline> {
+    let mut parser = Parser { bytes: data.as_bytes(), pos: 0 };
+    let mut subpaths = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    let mut pen = Point::new(0.0, 0.0);
+    let mut start = pen;
+    // Reflected control point for S and T
+    let mut last_control: Option<(u8, Point)> = None;
+    let mut command = 0u8;
+
+    let finish = |current: &mut Vec<Point>, subpaths: &mut Vec<Polyline>, closed: bool| {
+        if current.len() > 1 {
+            subpaths.push(Polyline { points: std::mem::take(current), closed });
+        }
+        current.clear();
+    };
+
+    loop {
+        parser.skip_separators();
+        let Some(&next) = parser.bytes.get(parser.pos) else {
+            break;
+        };
+        if next.is_ascii_alphabetic() {
+            command = next;
+            parser.pos += 1;
+        } else if command == 0 {
+            break;
+        }
+        let relative = command.is_ascii_lowercase();
+        let origin = if relative { pen } else { Point::new(0.0, 0.0) };
+        let offset = |p: Point| Point::new(origin.x + p.x, origin.y + p.y);
+
+        let parsed = match command.to_ascii_uppercase() {
+            b'Z' => {
+                finish(&mut current, &mut subpaths, true);
+                pen = start;
+                last_control = None;
+                // Z takes no arguments; a following number is an error
+                command = 0;
+                Some(())
+            }
+            b'M' => parser.point().map(|p| {
+                finish(&mut current, &mut subpaths, false);
+                pen = offset(p);
+                start = pen;
+                current.push(pen);
+                // Further pairs are implicit line-tos
+                command = if relative { b'l' } else { b'L' };
+                last_control = None;
+            }),
+            b'L' => parser.point().map(|p| {
+                pen = offset(p);
+                line_to(&mut current, start, pen);
+                last_control = None;
+            }),
+            b'H' => parser.number().map(|x| {
+                pen = Point::new(if relative { pen.x + x } else { x }, pen.y);
+                line_to(&mut current, start, pen);
+                last_control = None;
+            }),
+            b'V' => parser.number().map(|y| {
+                pen = Point::new(pen.x, if relative { pen.y + y } else { y });
+                line_to(&mut current, start, pen);
+                last_control = None;
+            }),
+            b'C' | b'S' => {
+                let first = if command.eq_ignore_ascii_case(&b'C') {
+                    parser.point().map(offset)
+                } else {
+                    Some(reflect(last_control, b'C', pen))
+                };
+                first.zip(parser.point()).zip(parser.point()).map(|((c1, c2), p)| {
+                    let (c2, p) = (offset(c2), offset(p));
+                    if current.is_empty() {
+                        current.push(start);
+                    }
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = i as f32 / CURVE_SEGMENTS as f32;
+                        let u = 1.0 - t;
+                        let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                        current.push(Point::new(
+                            a * pen.x + b * c1.x + c * c2.x + d * p.x,
+                            a * pen.y + b * c1.y + c * c2.y + d * p.y,
+                        ));
+                    }
+                    last_control = Some((b'C', c2));
+                    pen = p;
+                })
+            }
+            b'Q' | b'T' => {
+                let control = if command.eq_ignore_ascii_case(&b'Q') {
+                    parser.point().map(offset)
+                } else {
+                    Some(reflect(last_control, b'Q', pen))
+                };
+                control.zip(parser.point()).map(|(c, p)| {
+                    let p = offset(p);
+                    if current.is_empty() {
+                        current.push(start);
+                    }
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = i as f32 / CURVE_SEGMENTS as f32;
+                        let u = 1.0 - t;
+                        current.push(Point::new(
+                            u * u * pen.x + 2.0 * u * t * c.x + t * t * p.x,
+                            u * u * pen.y + 2.0 * u * t * c.y + t * t * p.y,
+                        ));
+                    }
+                    last_control = Some((b'Q', c));
+                    pen = p;
+                })
+            }
+            b'A' => parser.arc().map(|(rx, ry, rotation, large, sweep, p)| {
+                let p = offset(p);
+                if current.is_empty() {
+                    current.push(start);
+                }
+                arc_to(&mut current, pen, rx, ry, rotation, large, sweep, p);
+                last_control = None;
+                pen = p;
+            }),
+            _ => None,
+        };
+        if parsed.is_none() {
+            break;
+        }
+    }
+    finish(&mut current, &mut subpaths, false);
+    subpaths
+}
+
+fn line_to(current: &mut Vec<Point>, start: Point, to: Point) {
+    if current.is_empty() {
+        current.push(start);
+    }
+    current.push(to);
+}
+
+/// The control point mirrored through `pen`, if the previous segment was
+/// of the same kind, else `pen` itself
+fn reflect(last: Option<(u8, Point)>, kind: u8, pen: Point) -> Point {
+    match last {
+        Some((last_kind, control)) if last_kind == kind => Point::new(2.0 * pen.x - control.x, 2.0 * pen.y - control.y),
+        _ => pen,
+    }
+}
+
+/// Elliptical arc from `from` to `to`, converted to center form as in the
+/// SVG specification's implementation notes
+#[allow(clippy::too_many_arguments)]
+fn arc_to(current: &mut Vec<Point>, from: Point, rx: f32, ry: f32, rotation: f32, large: bool, sweep: bool, to: Point) {
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 || (from.x == to.x && from.y == to.y) {
+        current.push(to);
+        return;
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let dx = (from.x - to.x) / 2.0;
+    let dy = (from.y - to.y) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    // Radii too small to reach are scaled up until they just do
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut scale = (numerator / denominator).max(0.0).sqrt();
+    if large == sweep {
+        scale = -scale;
+    }
+    let cx1 = scale * rx * y1 / ry;
+    let cy1 = -scale * ry * x1 / rx;
+    let cx = cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0;
+
+    let angle = |ux: f32, uy: f32| uy.atan2(ux);
+    let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    let steps = ((delta.abs() / (PI / 2.0)) * CURVE_SEGMENTS as f32 / 2.0).ceil().max(1.0) as usize;
+    for i in 1..=steps {
+        let theta = start + delta * i as f32 / steps as f32;
+        let (ex, ey) = (rx * theta.cos(), ry * theta.sin());
+        current.push(Point::new(cos * ex - sin * ey + cx, sin * ex + cos * ey + cy));
+    }
+    // Land exactly on the endpoint despite rounding
+    if let Some(last) = current.last_mut() {
+        *last = to;
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_separators(&mut self) {
+        while self.bytes.get(self.pos).is_some_and(|b| b.is_ascii_whitespace() || *b == b',') {
+            self.pos += 1;
+        }
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        let peek = |parser: &Self| parser.bytes.get(parser.pos).copied();
+        if matches!(peek(self), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut seen_dot = false;
+        while let Some(b) = peek(self) {
+            match b {
+                b'0'..=b'9' => self.pos += 1,
+                // A second dot starts the next number, as in "0.5.5"
+                b'.' if !seen_dot => {
+                    seen_dot = true;
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+        if matches!(peek(self), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(peek(self), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !peek(self).is_some_and(|b| b.is_ascii_digit()) {
+                self.pos = mark;
+            }
+            while peek(self).is_some_and(|b| b.is_ascii_digit()) {
+                self.pos += 1;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()
+    }
+
+    fn point(&mut self) -> Option<Point> {
+        Some(Point::new(self.number()?, self.number()?))
+    }
+
+    /// Arc flags are single digits and may run into the next number
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.bytes.get(self.pos)? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+
+    fn arc(&mut self) -> Option<(f32, f32, f32, bool, bool, Point)> {
+        Some((self.number()?, self.number()?, self.number()?, self.flag()?, self.flag()?, self.point()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_flatten_to_their_outline() {
+        // Lines, implicit line-tos, relative moves and a close
+        let square = flatten("M0 0H10V10h-10Z m20,0 l5 5 5-5");
+        assert_eq!(square.len(), 2);
+        assert!(square[0].closed);
+        assert_eq!(
+            square[0].points,
+            vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0), Point::new(10.0, 10.0), Point::new(0.0, 10.0)]
+        );
+        assert_eq!(square[1].points, vec![Point::new(20.0, 0.0), Point::new(25.0, 5.0), Point::new(30.0, 0.0)]);
+
+        // A half circle with compact flags ends where it should and bulges
+        // out to its radius
+        let arc = &flatten("M0 0a5 5 0 015 5")[0];
+        assert_eq!(*arc.points.last().unwrap(), Point::new(5.0, 5.0));
+        let half = &flatten("M0 5A5 5 0 0 1 10 5")[0];
+        let top = half.points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+        assert!(top.abs() < 0.1, "reaches y={}", top);
+
+        // Everything up to an error is kept
+        assert_eq!(flatten("M0 0L1 1L2 x")[0].points.len(), 2);
+        assert!(flatten("").is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+//! Installed font discovery
+//!
+//! Resolves a family, weight and style to a font file through the platform's
+//! own font service: fontconfig on Linux, DirectWrite on Windows and CoreText
+//! on macOS. Other platforms report no installed fonts, and so does Linux
+//! when libfontconfig can't be loaded, as it is opened at first use.
+
+use std::path::PathBuf;
+
+use crate::render::text::{FontDescriptor, FontStyle};
+
+/// A font file on disk and the face within it (collections hold several)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemFont {
+    pub family: String,
+    pub path: PathBuf,
+    pub index: u32,
+}
+
+/// Families installed on this machine, sorted and deduplicated
+pub fn families() -> Vec<String> {
+    let mut families = backend::families();
+    families.sort_by_key(|f| f.to_lowercase());
+    families.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    families
+}
+
+/// The installed face closest to `descriptor`, if its family is installed
+pub fn find(descriptor: &FontDescriptor) -> Option<SystemFont> {
+    backend::find(descriptor)
+}
+
+/// Generic names the platform maps to a concrete family
+fn is_generic_family(family: &str) -> bool {
+    matches!(
+        family.to_ascii_lowercase().as_str(),
+        "sans-serif" | "serif" | "monospace" | "cursive" | "fantasy" | "system-ui"
+    )
+}
+
+#[cfg(target_os = "linux")]
+mod backend {
+    use super::{is_generic_family, SystemFont};
+    use crate::render::text::{FontDescriptor, FontStyle, FontWeight};
+    use fontconfig::{Fontconfig, ObjectSet, Pattern, FC_FAMILY, FC_SLANT, FC_WEIGHT};
+    use std::ffi::CString;
+
+    pub fn families() -> Vec<String> {
+        let Some(fc) = Fontconfig::new() else {
+            return Vec::new();
+        };
+        let pattern = Pattern::new(&fc);
+        let mut objects = ObjectSet::new(&fc);
+        objects.add(FC_FAMILY);
+        let families: Vec<String> = fontconfig::list_fonts(&pattern, Some(&objects))
+            .iter()
+            .filter_map(|font| font.get_string(FC_FAMILY).map(str::to_string))
+            .collect();
+        families
+    }
+
+    pub fn find(descriptor: &FontDescriptor) -> Option<SystemFont> {
+        let fc = Fontconfig::new()?;
+        let family = CString::new(descriptor.family.as_str()).ok()?;
+        let mut pattern = Pattern::new(&fc);
+        pattern.add_string(FC_FAMILY, &family);
+        pattern.add_integer(FC_WEIGHT, fc_weight(descriptor.weight));
+        pattern.add_integer(FC_SLANT, fc_slant(descriptor.style));
+
+        // fontconfig always returns something; reject substitutes for named families
+        let font = pattern.font_match();
+        let matched = font.get_string(FC_FAMILY)?;
+        if !matched.eq_ignore_ascii_case(&descriptor.family) && !is_generic_family(&descriptor.family) {
+            return None;
+        }
+        Some(SystemFont {
+            family: matched.to_string(),
+            path: font.filename()?.into(),
+            index: font.face_index().unwrap_or(0).max(0) as u32,
+        })
+    }
+
+    fn fc_weight(weight: FontWeight) -> i32 {
+        match weight {
+            FontWeight::Thin => 0,
+            FontWeight::ExtraLight => 40,
+            FontWeight::Light => 50,
+            FontWeight::Regular => 80,
+            FontWeight::Medium => 100,
+            FontWeight::SemiBold => 180,
+            FontWeight::Bold => 200,
+            FontWeight::ExtraBold => 205,
+            FontWeight::Black => 210,
+        }
+    }
+
+    fn fc_slant(style: FontStyle) -> i32 {
+        match style {
+            FontStyle::Normal => 0,
+            FontStyle::Italic => 100,
+            FontStyle::Oblique => 110,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod backend {
+    use super::{is_generic_family, SystemFont};
+    use crate::render::text::{FontDescriptor, FontStyle};
+    use dwrote::{FontCollection, FontStretch, FontStyle as DwStyle, FontWeight as DwWeight};
+
+    pub fn families() -> Vec<String> {
+        FontCollection::system().families_iter().map(|family| family.name()).collect()
+    }
+
+    pub fn find(descriptor: &FontDescriptor) -> Option<SystemFont> {
+        let name = match descriptor.family.to_ascii_lowercase().as_str() {
+            "monospace" => "Consolas",
+            "serif" => "Times New Roman",
+            family if is_generic_family(family) => "Segoe UI",
+            _ => descriptor.family.as_str(),
+        };
+        let family = FontCollection::system().get_font_family_by_name(name)?;
+        let style = match descriptor.style {
+            FontStyle::Normal => DwStyle::Normal,
+            FontStyle::Italic => DwStyle::Italic,
+            FontStyle::Oblique => DwStyle::Oblique,
+        };
+        let font = family.get_first_matching_font(
+            DwWeight::from_u32(descriptor.weight as u32),
+            FontStretch::Normal,
+            style,
+        );
+        let face = font.create_font_face();
+        let path = face.get_files().first()?.get_font_file_path()?;
+        Some(SystemFont {
+            family: family.name(),
+            path,
+            index: face.get_index(),
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod backend {
+    use super::{is_generic_family, SystemFont};
+    use crate::render::text::{FontDescriptor, FontStyle};
+    use core_text::font_descriptor::SymbolicTraitAccessors;
+
+    pub fn families() -> Vec<String> {
+        core_text::font_manager::copy_available_font_family_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    pub fn find(descriptor: &FontDescriptor) -> Option<SystemFont> {
+        let name = match descriptor.family.to_ascii_lowercase().as_str() {
+            "monospace" => "Menlo",
+            "serif" => "Times",
+            family if is_generic_family(family) => "Helvetica Neue",
+            _ => descriptor.family.as_str(),
+        };
+        let italic = descriptor.style != FontStyle::Normal;
+        let weight = descriptor.weight as u32 as f64;
+
+        // Score each face in the family by distance from the requested weight and slant
+        let faces = core_text::font_collection::create_for_family(name)?.get_descriptors()?;
+        let best = faces.iter().min_by_key(|face| {
+            let traits = face.traits();
+            // CoreText weights run -1.0..1.0 with regular at 0.0 and bold near 0.4
+            let css_weight = 400.0 + traits.normalized_weight() * 750.0;
+            let slant_penalty = if traits.symbolic_traits().is_italic() == italic { 0 } else { 1000 };
+            (css_weight - weight).abs() as i64 + slant_penalty
+        })?;
+        Some(SystemFont {
+            family: best.family_name(),
+            path: best.font_path()?,
+            index: 0,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod backend {
+    use super::SystemFont;
+    use crate::render::text::FontDescriptor;
+
+    pub fn families() -> Vec<String> {
+        Vec::new()
+    }
+
+    pub fn find(_descriptor: &FontDescriptor) -> Option<SystemFont> {
+        None
+    }
+}
+
+/// Weight and slant a descriptor asks for, for diagnostics
+pub(crate) fn describe(descriptor: &FontDescriptor) -> String {
+    let style = match descriptor.style {
+        FontStyle::Normal => "",
+        FontStyle::Italic => " italic",
+        FontStyle::Oblique => " oblique",
+    };
+    format!("{} {}{}", descriptor.family, descriptor.weight as u32, style)
+}
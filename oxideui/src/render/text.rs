@@ -1,8 +1,11 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use std::collections::HashMap;
-use std::sync::Arc;
-use parking_lot::RwLock;
-use crate::core::render_object::{TextStyle};
+use std::fs;
+use std::sync::{Arc, OnceLock};
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+use crate::core::render_object::{TextAlign, TextStyle};
+use crate::render::system_fonts::{self, SystemFont};
 
 /// Font weight enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -88,110 +91,312 @@ pub struct TextMetrics {
     pub line_gap: f32,
 }
 
+/// How text that doesn't fit its constraints is cut off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextOverflow {
+    /// Cut at the edge
+    #[default]
+    Clip,
+    /// Replace the tail with "…"
+    Ellipsis,
+    /// Fade the last line out towards the edge
+    Fade,
+}
+
+/// Lines of text fitted to a width, and whether any text was cut off
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedText {
+    pub lines: Vec<String>,
+    pub truncated: bool,
+}
+
+/// Estimated advance of `text`; the same approximation `measure_text` uses
+pub fn text_width(text: &str, style: &TextStyle) -> f32 {
+    text.chars().count() as f32 * (style.font_size * 0.6 + style.letter_spacing)
+}
+
+/// Height of one line of `style`
+pub fn line_height(style: &TextStyle) -> f32 {
+    style.font_size * style.line_height.unwrap_or(1.2)
+}
+
+/// Distance from the top of a line of `style` to its baseline, as the
+/// renderer lays it out. Without Skia, or a face to measure, it's estimated
+/// from typical Latin metrics.
+pub fn baseline(style: &TextStyle) -> f32 {
+    #[cfg(feature = "skia")]
+    if let Some((ascent, descent)) = face_metrics(style) {
+        return match style.line_height {
+            // A set height is spread over the ascent and descent in proportion
+            Some(height) => style.font_size * height * ascent / (ascent + descent),
+            None => style.font_size * ascent,
+        };
+    }
+    (line_height(style) - style.font_size) / 2.0 + style.font_size * 0.8
+}
+
+/// Ascent and descent of the face `style` is drawn with, per pixel of font
+/// size. Faces registered with `FontManager::register_font` come first, as
+/// in the renderer.
+#[cfg(feature = "skia")]
+fn face_metrics(style: &TextStyle) -> Option<(f32, f32)> {
+    use std::cell::RefCell;
+
+    use skia_safe::{Font, FontMgr, FontStyle as SkFontStyle};
+
+    type MetricsKey = (String, bool, bool, usize);
+    thread_local! {
+        static FONT_MGR: FontMgr = FontMgr::new();
+        static METRICS: RefCell<HashMap<MetricsKey, Option<(f32, f32)>>> = RefCell::new(HashMap::new());
+    }
+
+    // Registering a font may change which face a family resolves to
+    let registered = FontManager::registered_fonts();
+    let key = (style.font_family.clone(), style.bold, style.italic, registered.len());
+    if let Some(metrics) = METRICS.with(|metrics| metrics.borrow().get(&key).copied()) {
+        return metrics;
+    }
+
+    let font_style = match (style.bold, style.italic) {
+        (true, true) => SkFontStyle::bold_italic(),
+        (true, false) => SkFontStyle::bold(),
+        (false, true) => SkFontStyle::italic(),
+        (false, false) => SkFontStyle::normal(),
+    };
+    let metrics = FONT_MGR.with(|font_mgr| {
+        let typeface = registered
+            .iter()
+            .filter(|font| font.family.eq_ignore_ascii_case(&style.font_family))
+            .find_map(|font| font_mgr.new_from_data(&font.data, None))
+            .or_else(|| font_mgr.match_family_style(&style.font_family, font_style))
+            .or_else(|| font_mgr.match_family_style("sans-serif", font_style))?;
+        let (_, metrics) = Font::from_typeface(typeface, 100.0).metrics();
+        // Skia's ascent points up, so it's negative
+        let (ascent, descent) = (-metrics.ascent / 100.0, metrics.descent / 100.0);
+        (ascent > 0.0 && ascent + descent > 0.0).then_some((ascent, descent))
+    });
+    METRICS.with(|cache| cache.borrow_mut().insert(key, metrics));
+    metrics
+}
+
+/// Break `text` into at most `max_lines` lines of `max_width`, wrapping at
+/// spaces. With `Ellipsis`, a line that doesn't fit, the last one or one
+/// holding a word too long to wrap, is shortened to end in "…".
+pub fn fit_text(
+    text: &str,
+    style: &TextStyle,
+    max_width: f32,
+    max_lines: usize,
+    overflow: TextOverflow,
+) -> FittedText {
+    let _span = span!(TRACE, "shape_text", len = text.len(), max_lines);
+    let max_lines = max_lines.max(1);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (i, word) in words.iter().enumerate() {
+        if lines.len() + 1 == max_lines {
+            // The last line takes everything left and is truncated below
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(&words[i..].join(" "));
+            break;
+        }
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if current.is_empty() || text_width(&candidate, style) <= max_width {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    let ellipsis_width = text_width("…", style);
+    let mut truncated = false;
+    for line in lines.iter_mut().filter(|line| text_width(line, style) > max_width) {
+        truncated = true;
+        if overflow == TextOverflow::Ellipsis {
+            while !line.is_empty() && text_width(line, style) + ellipsis_width > max_width {
+                line.pop();
+            }
+            line.truncate(line.trim_end().len());
+            line.push('…');
+        }
+    }
+
+    FittedText { lines, truncated }
+}
+
+/// A font face shipped with the app rather than installed on the system
+#[derive(Debug, Clone)]
+pub struct RegisteredFont {
+    pub family: String,
+    pub data: Arc<[u8]>,
+    /// Weight class read from the face, 100 to 900
+    pub weight: u16,
+    pub italic: bool,
+}
+
+/// Fonts registered with `FontManager::register_font`, in registration order
+fn registered_font_store() -> &'static RwLock<Vec<RegisteredFont>> {
+    static FONTS: OnceLock<RwLock<Vec<RegisteredFont>>> = OnceLock::new();
+    FONTS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Weight class and slant from a face's OS/2 table, or from its head table's
+/// style bits without one; regular and upright when neither can be read.
+/// Collections describe their first face.
+fn face_style(data: &[u8]) -> (u16, bool) {
+    let u16_at = |at: usize| data.get(at..at + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+    let u32_at = |at: usize| data.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    let start = if data.starts_with(b"ttcf") { u32_at(12).unwrap_or(0) as usize } else { 0 };
+    let tables = u16_at(start + 4).unwrap_or(0) as usize;
+    let table = |tag: &[u8; 4]| {
+        (0..tables)
+            .map(|i| start + 12 + i * 16)
+            .find(|record| data.get(*record..*record + 4) == Some(tag.as_slice()))
+            .and_then(|record| u32_at(record + 8))
+            .map(|offset| offset as usize)
+    };
+    if let Some(os2) = table(b"OS/2") {
+        if let (Some(weight), Some(selection)) = (u16_at(os2 + 4), u16_at(os2 + 62)) {
+            return (weight.clamp(100, 900), selection & 1 != 0);
+        }
+    }
+    match table(b"head").and_then(|head| u16_at(head + 44)) {
+        Some(mac_style) => (if mac_style & 1 != 0 { 700 } else { 400 }, mac_style & 2 != 0),
+        None => (400, false),
+    }
+}
+
+/// A loaded font file and the face to use within it; collections
+/// (`.ttc`) hold several faces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontFace {
+    pub data: Vec<u8>,
+    pub index: u32,
+}
+
 /// Font manager for loading and caching fonts
 pub struct FontManager {
-    font_cache: Arc<RwLock<HashMap<FontDescriptor, Vec<u8>>>>,
-    system_fonts: Vec<String>,
+    font_cache: Arc<RwLock<HashMap<FontDescriptor, FontFace>>>,
+    /// Listed on first use; enumerating every installed font is slow
+    system_fonts: OnceLock<Vec<String>>,
 }
 
 impl FontManager {
     pub fn new() -> Self {
         Self {
             font_cache: Arc::new(RwLock::new(HashMap::new())),
-            system_fonts: Self::enumerate_system_fonts(),
+            system_fonts: OnceLock::new(),
         }
     }
 
-    fn enumerate_system_fonts() -> Vec<String> {
-        // Platform-specific font enumeration
-        #[cfg(target_os = "linux")]
-        {
-            vec![
-                "DejaVu Sans".to_string(),
-                "Liberation Sans".to_string(),
-                "Ubuntu".to_string(),
-                "Noto Sans".to_string(),
-            ]
+    /// Make a TTF/OTF face available to every renderer under `family`, e.g.
+    /// `FontManager::register_font("Inter", include_bytes!("Inter-Regular.ttf"))`.
+    ///
+    /// Register each weight and style file of a family under the same name;
+    /// the renderer picks the closest face for bold and italic text.
+    pub fn register_font(family: impl Into<String>, data: impl Into<Vec<u8>>) -> Result<()> {
+        let family = family.into();
+        let data = data.into();
+        let is_font = matches!(data.get(..4), Some(b"\x00\x01\x00\x00" | b"OTTO" | b"true" | b"ttcf"));
+        if !is_font {
+            bail!("Font \"{}\" is not TrueType or OpenType data", family);
         }
 
-        #[cfg(target_os = "macos")]
-        {
-            vec![
-                "SF Pro Display".to_string(),
-                "Helvetica Neue".to_string(),
-                "Arial".to_string(),
-            ]
-        }
+        let (weight, italic) = face_style(&data);
+        registered_font_store().write().push(RegisteredFont {
+            family,
+            data: data.into(),
+            weight,
+            italic,
+        });
+        Ok(())
+    }
 
-        #[cfg(target_os = "windows")]
-        {
-            vec![
-                "Segoe UI".to_string(),
-                "Arial".to_string(),
-                "Tahoma".to_string(),
-            ]
-        }
+    /// Every face registered with `register_font`, oldest first
+    pub fn registered_fonts() -> Vec<RegisteredFont> {
+        registered_font_store().read().clone()
+    }
 
-        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-        {
-            vec!["sans-serif".to_string()]
-        }
+    /// Faces registered after the first `start`, for renderers that load
+    /// them as they arrive
+    pub(crate) fn registered_fonts_since(start: usize) -> Vec<RegisteredFont> {
+        registered_font_store().read().get(start..).map_or_else(Vec::new, <[RegisteredFont]>::to_vec)
+    }
+
+    /// Font families installed on this machine
+    pub fn system_families(&self) -> &[String] {
+        self.system_fonts.get_or_init(system_fonts::families)
     }
 
+    /// The installed file for the face closest to `descriptor`
+    pub fn find_system_font(&self, descriptor: &FontDescriptor) -> Option<SystemFont> {
+        system_fonts::find(descriptor)
+    }
+
+    /// The whole font file for `descriptor`; see `load_face` for which
+    /// face of a collection to use
     pub fn load_font(&self, descriptor: &FontDescriptor) -> Result<Vec<u8>> {
+        self.load_face(descriptor).map(|face| face.data)
+    }
+
+    pub fn load_face(&self, descriptor: &FontDescriptor) -> Result<FontFace> {
         // Check cache first
         {
             let cache = self.font_cache.read();
-            if let Some(data) = cache.get(descriptor) {
-                return Ok(data.clone());
+            if let Some(face) = cache.get(descriptor) {
+                return Ok(face.clone());
             }
         }
 
-        // Try to load from system
-        let data = self.load_system_font(descriptor)?;
+        // Registered fonts take precedence over installed ones. Of a
+        // family's faces, the right slant wins, then the closest weight.
+        let italic = descriptor.style != FontStyle::Normal;
+        let registered = registered_font_store()
+            .read()
+            .iter()
+            .filter(|font| font.family.eq_ignore_ascii_case(&descriptor.family))
+            .min_by_key(|font| (font.italic != italic, font.weight.abs_diff(descriptor.weight as u16)))
+            .map(|font| FontFace { data: font.data.to_vec(), index: 0 });
+        let face = match registered {
+            Some(face) => face,
+            None => {
+                let font = self
+                    .find_system_font(descriptor)
+                    .ok_or_else(|| anyhow!("Font not found: {}", system_fonts::describe(descriptor)))?;
+                read_system_font(&font)?
+            }
+        };
 
         // Cache it
-        self.font_cache.write().insert(descriptor.clone(), data.clone());
-
-        Ok(data)
-    }
-
-    fn load_system_font(&self, descriptor: &FontDescriptor) -> Result<Vec<u8>> {
-        // Platform-specific font loading
-        #[cfg(target_os = "linux")]
-        {
-            use std::fs;
-            let paths = vec![
-                format!("/usr/share/fonts/truetype/{}.ttf", descriptor.family.to_lowercase().replace(" ", "-")),
-                format!("/usr/share/fonts/TTF/{}.ttf", descriptor.family),
-                format!("/usr/local/share/fonts/{}.ttf", descriptor.family),
-            ];
-
-            for path in paths {
-                if let Ok(data) = fs::read(&path) {
-                    return Ok(data);
-                }
-            }
-        }
+        self.font_cache.write().insert(descriptor.clone(), face.clone());
 
-        Err(anyhow!("Font not found: {}", descriptor.family))
+        Ok(face)
     }
 
     pub fn measure_text(&self, text: &str, style: &TextStyle) -> Result<TextMetrics> {
         // Simplified measurement - in production, use HarfBuzz or similar
-        let char_count = text.chars().count();
-        let avg_char_width = style.font_size * 0.6;
-
         Ok(TextMetrics {
-            width: avg_char_width * char_count as f32,
-            height: style.font_size * 1.2,
+            width: text_width(text, style),
+            height: line_height(style),
             ascent: style.font_size * 0.8,
             descent: style.font_size * 0.2,
-            line_gap: style.font_size * 0.2,
+            line_gap: line_height(style) - style.font_size,
         })
     }
 
     pub fn shape_text(&self, text: &str, style: &TextStyle) -> Result<ShapedText> {
+        let _span = span!(TRACE, "shape_text", len = text.len());
         // Simplified shaping - production should use HarfBuzz
         let metrics = self.measure_text(text, style)?;
 
@@ -224,6 +429,11 @@ impl Default for FontManager {
     }
 }
 
+fn read_system_font(font: &SystemFont) -> Result<FontFace> {
+    let data = fs::read(&font.path).with_context(|| format!("Failed to read font file: {}", font.path.display()))?;
+    Ok(FontFace { data, index: font.index })
+}
+
 /// Text layout engine for multi-line text
 pub struct TextLayout {
     font_manager: Arc<FontManager>,
@@ -253,7 +463,7 @@ impl TextLayout {
         style: &TextStyle,
         max_width: f32,
     ) -> Result<Vec<ShapedText>> {
-        let mut lines = Vec::new();
+        let mut line_texts = Vec::new();
         let mut current_line = String::new();
         let mut current_width = 0.0;
 
@@ -262,8 +472,7 @@ impl TextLayout {
 
             if current_width + word_metrics.width > max_width && !current_line.is_empty() {
                 // Start new line
-                lines.push(self.font_manager.shape_text(&current_line, style)?);
-                current_line.clear();
+                line_texts.push(std::mem::take(&mut current_line));
                 current_width = 0.0;
             }
 
@@ -277,22 +486,151 @@ impl TextLayout {
         }
 
         if !current_line.is_empty() {
-            lines.push(self.font_manager.shape_text(&current_line, style)?);
+            line_texts.push(current_line);
+        }
+
+        let last = line_texts.len().saturating_sub(1);
+        let mut lines = Vec::with_capacity(line_texts.len());
+        for (i, line_text) in line_texts.iter().enumerate() {
+            let mut line = self.font_manager.shape_text(line_text, style)?;
+            align_line(&mut line, line_text, style.align, max_width, i == last);
+            lines.push(line);
         }
 
         Ok(lines)
     }
 }
 
-/// Text rendering cache for performance
+/// Shift or spread the glyphs of `text`, shaped as `line`, to honor `align` within `max_width`
+fn align_line(line: &mut ShapedText, text: &str, align: TextAlign, max_width: f32, is_last: bool) {
+    let slack = max_width - line.width;
+    if slack <= 0.0 || !slack.is_finite() {
+        return;
+    }
+    match align {
+        TextAlign::Left => {}
+        TextAlign::Center | TextAlign::Right => {
+            let shift = if align == TextAlign::Center { slack / 2.0 } else { slack };
+            for glyph in &mut line.glyphs {
+                glyph.x_offset += shift;
+            }
+        }
+        // The last line of a justified paragraph stays left-aligned
+        TextAlign::Justify if is_last => {}
+        TextAlign::Justify => {
+            // Glyphs map one-to-one to chars; spread the slack over the spaces
+            let spaces = text.chars().filter(|c| *c == ' ').count();
+            if spaces == 0 {
+                return;
+            }
+            let extra = slack / spaces as f32;
+            let mut shift = 0.0;
+            for (glyph, ch) in line.glyphs.iter_mut().zip(text.chars()) {
+                glyph.x_offset += shift;
+                if ch == ' ' {
+                    shift += extra;
+                }
+            }
+            line.width = max_width;
+        }
+    }
+}
+
+/// Default `TextCache` entry limit
+const TEXT_CACHE_MAX_ENTRIES: usize = 4096;
+/// Default `TextCache` memory limit, counting strings and glyph runs
+const TEXT_CACHE_MAX_BYTES: usize = 8 * 1024 * 1024;
+
+/// Everything that changes how a string is shaped or painted
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TextCacheKey {
+    text: String,
+    family: String,
+    font_size: u32,
+    bold: bool,
+    italic: bool,
+    letter_spacing: u32,
+    line_height: Option<u32>,
+    color: [u8; 4],
+}
+
+impl TextCacheKey {
+    fn new(text: &str, style: &TextStyle) -> Self {
+        Self {
+            text: text.to_string(),
+            family: style.font_family.clone(),
+            font_size: style.font_size.to_bits(),
+            bold: style.bold,
+            italic: style.italic,
+            letter_spacing: style.letter_spacing.to_bits(),
+            line_height: style.line_height.map(f32::to_bits),
+            color: [style.color.r, style.color.g, style.color.b, style.color.a],
+        }
+    }
+
+    /// Approximate heap and inline size of an entry
+    fn entry_bytes(&self, shaped: &ShapedText) -> usize {
+        std::mem::size_of::<Self>()
+            + std::mem::size_of::<ShapedText>()
+            + self.text.len()
+            + self.family.len()
+            + shaped.glyphs.len() * std::mem::size_of::<GlyphInfo>()
+    }
+}
+
+/// Hit, miss and eviction counts since the cache was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextCacheStats {
+    pub entries: usize,
+    pub bytes: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// Shaped text cache, bounded by entry count and memory.
+///
+/// Evicts the least recently used strings first, and drops everything when
+/// fonts are registered or the scale factor changes, since either can
+/// change the shaping of every cached string.
 pub struct TextCache {
-    cache: Arc<RwLock<HashMap<String, ShapedText>>>,
+    state: Arc<Mutex<TextCacheState>>,
+}
+
+struct TextCacheState {
+    entries: LruCache<TextCacheKey, ShapedText>,
+    bytes: usize,
+    max_entries: usize,
+    max_bytes: usize,
+    scale_factor: f32,
+    /// `FontManager::registered_fonts().len()` when the entries were shaped
+    font_count: usize,
+    stats: TextCacheStats,
 }
 
 impl TextCache {
     pub fn new() -> Self {
+        Self::with_limits(TEXT_CACHE_MAX_ENTRIES, TEXT_CACHE_MAX_BYTES)
+    }
+
+    /// The app's shared cache, kept at the window's scale factor by the runtime
+    pub fn global() -> &'static TextCache {
+        static CACHE: OnceLock<TextCache> = OnceLock::new();
+        CACHE.get_or_init(TextCache::new)
+    }
+
+    /// A cache holding at most `max_entries` strings and about `max_bytes`
+    pub fn with_limits(max_entries: usize, max_bytes: usize) -> Self {
         Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
+            state: Arc::new(Mutex::new(TextCacheState {
+                entries: LruCache::unbounded(),
+                bytes: 0,
+                max_entries: max_entries.max(1),
+                max_bytes,
+                scale_factor: 1.0,
+                font_count: registered_font_store().read().len(),
+                stats: TextCacheStats::default(),
+            })),
         }
     }
 
@@ -302,25 +640,73 @@ impl TextCache {
         style: &TextStyle,
         font_manager: &FontManager,
     ) -> Result<ShapedText> {
-        let cache_key = format!("{}:{}:{}", text, style.font_family, style.font_size);
+        let key = TextCacheKey::new(text, style);
 
-        // Check cache
         {
-            let cache = self.cache.read();
-            if let Some(shaped) = cache.get(&cache_key) {
-                return Ok(shaped.clone());
+            let mut state = self.state.lock();
+            let font_count = registered_font_store().read().len();
+            if font_count != state.font_count {
+                state.font_count = font_count;
+                state.clear();
             }
+            if let Some(shaped) = state.entries.get(&key).cloned() {
+                state.stats.hits += 1;
+                return Ok(shaped);
+            }
+            state.stats.misses += 1;
         }
 
-        // Shape and cache
+        // Shape without holding the lock so other threads can hit meanwhile
         let shaped = font_manager.shape_text(text, style)?;
-        self.cache.write().insert(cache_key, shaped.clone());
+        self.state.lock().insert(key, shaped.clone());
 
         Ok(shaped)
     }
 
+    /// Drop every entry if `scale_factor` differs from the one they were shaped at
+    pub fn set_scale_factor(&self, scale_factor: f32) {
+        let mut state = self.state.lock();
+        if state.scale_factor != scale_factor {
+            state.scale_factor = scale_factor;
+            state.clear();
+        }
+    }
+
     pub fn clear(&self) {
-        self.cache.write().clear();
+        self.state.lock().clear();
+    }
+
+    pub fn stats(&self) -> TextCacheStats {
+        let state = self.state.lock();
+        TextCacheStats {
+            entries: state.entries.len(),
+            bytes: state.bytes,
+            ..state.stats
+        }
+    }
+}
+
+impl TextCacheState {
+    fn insert(&mut self, key: TextCacheKey, shaped: ShapedText) {
+        let bytes = key.entry_bytes(&shaped);
+        if let Some(replaced) = self.entries.put(key.clone(), shaped) {
+            self.bytes -= key.entry_bytes(&replaced);
+        }
+        self.bytes += bytes;
+
+        // Keep at least the newest entry, even when it alone exceeds the limit
+        while self.entries.len() > 1 && (self.entries.len() > self.max_entries || self.bytes > self.max_bytes) {
+            let Some((evicted, shaped)) = self.entries.pop_lru() else {
+                break;
+            };
+            self.bytes -= evicted.entry_bytes(&shaped);
+            self.stats.evictions += 1;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
     }
 }
 
@@ -328,4 +714,139 @@ impl Default for TextCache {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_text_ellipsis() {
+        let style = TextStyle {
+            font_family: "sans-serif".to_string(),
+            font_size: 10.0,
+            color: crate::core::Color::BLACK,
+            bold: false,
+            italic: false,
+            ..Default::default()
+        };
+        // 6px per character
+        let fitted = fit_text("one two three four", &style, 54.0, 2, TextOverflow::Ellipsis);
+        assert_eq!(fitted.lines, vec!["one two".to_string(), "three fo…".to_string()]);
+        assert!(fitted.truncated);
+
+        let fitted = fit_text("short", &style, 60.0, 1, TextOverflow::Ellipsis);
+        assert_eq!(fitted.lines, vec!["short".to_string()]);
+        assert!(!fitted.truncated);
+
+        // A word too long to wrap is cut on its own line, not just the last
+        let fitted = fit_text("a verylongword b", &style, 30.0, 3, TextOverflow::Ellipsis);
+        assert_eq!(fitted.lines, vec!["a".to_string(), "very…".to_string(), "b".to_string()]);
+        assert!(fitted.truncated);
+    }
+
+    #[test]
+    fn test_baseline_sits_within_the_line() {
+        let style = |line_height| TextStyle { font_size: 20.0, line_height, ..Default::default() };
+        for height in [None, Some(1.0), Some(2.0)] {
+            let style = style(height);
+            let baseline = baseline(&style);
+            assert!(baseline > style.font_size / 2.0 && baseline < line_height(&style), "{:?}", height);
+        }
+        // A taller line moves the baseline down with it
+        assert!(baseline(&style(Some(2.0))) > baseline(&style(Some(1.0))));
+    }
+
+    #[test]
+    fn test_register_font() {
+        let mut data = vec![0x00, 0x01, 0x00, 0x00];
+        data.extend_from_slice(&[0; 12]);
+        FontManager::register_font("Test Embedded Sans", data.clone()).unwrap();
+        assert!(FontManager::register_font("Not A Font", b"<html>".to_vec()).is_err());
+
+        let loaded = FontManager::new().load_font(&FontDescriptor::new("test embedded sans")).unwrap();
+        assert_eq!(loaded, data);
+        assert!(FontManager::registered_fonts().iter().all(|f| f.family != "Not A Font"));
+    }
+
+    #[test]
+    fn test_registered_faces_match_weight_and_style() {
+        // One table directory entry pointing at an OS/2 table
+        let face = |weight: u16, italic: bool| {
+            let mut data = vec![0x00, 0x01, 0x00, 0x00, 0x00, 0x01];
+            data.resize(12, 0);
+            data.extend_from_slice(b"OS/2\0\0\0\0");
+            data.extend_from_slice(&28u32.to_be_bytes());
+            data.extend_from_slice(&78u32.to_be_bytes());
+            let mut os2 = vec![0; 78];
+            os2[4..6].copy_from_slice(&weight.to_be_bytes());
+            os2[62..64].copy_from_slice(&(italic as u16).to_be_bytes());
+            data.extend_from_slice(&os2);
+            data
+        };
+        let (regular, bold, italic) = (face(400, false), face(700, false), face(400, true));
+        for data in [&regular, &bold, &italic] {
+            FontManager::register_font("Test Styled Sans", data.clone()).unwrap();
+        }
+
+        let fonts = FontManager::new();
+        let load = |descriptor: FontDescriptor| fonts.load_font(&descriptor).unwrap();
+        assert_eq!(load(FontDescriptor::new("Test Styled Sans")), regular);
+        assert_eq!(load(FontDescriptor::new("Test Styled Sans").bold()), bold);
+        assert_eq!(load(FontDescriptor::new("Test Styled Sans").weight(FontWeight::Black)), bold);
+        assert_eq!(load(FontDescriptor::new("Test Styled Sans").italic()), italic);
+        assert_eq!(load(FontDescriptor::new("Test Styled Sans").bold().italic()), italic);
+    }
+
+    #[test]
+    fn test_text_cache_evicts_least_recently_used() {
+        let style = TextStyle::default();
+        let fonts = FontManager::new();
+        let cache = TextCache::with_limits(2, usize::MAX);
+
+        cache.get_or_shape("one", &style, &fonts).unwrap();
+        cache.get_or_shape("two", &style, &fonts).unwrap();
+        cache.get_or_shape("one", &style, &fonts).unwrap();
+        // "two" is the least recently used, so it makes room for "three"
+        cache.get_or_shape("three", &style, &fonts).unwrap();
+        cache.get_or_shape("one", &style, &fonts).unwrap();
+
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses, stats.evictions), (2, 2, 3, 1));
+
+        // Color is part of the key
+        let red = TextStyle {
+            color: crate::core::Color::rgb(255, 0, 0),
+            ..TextStyle::default()
+        };
+        cache.get_or_shape("one", &red, &fonts).unwrap();
+        assert_eq!(cache.stats().misses, 4);
+
+        cache.set_scale_factor(2.0);
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().bytes, 0);
+    }
+
+    #[test]
+    fn test_system_faces_keep_their_collection_index() {
+        let path = std::env::temp_dir().join(format!("oxideui-test-{}.ttc", std::process::id()));
+        let data = b"ttcf\x00\x02\x00\x00".to_vec();
+        fs::write(&path, &data).unwrap();
+        let font = SystemFont { family: "Test Collection".into(), path: path.clone(), index: 2 };
+
+        let face = read_system_font(&font).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(face, FontFace { data, index: 2 });
+        assert!(read_system_font(&font).is_err());
+    }
+
+    #[test]
+    fn test_system_families_are_listed_on_first_use() {
+        let fonts = FontManager::new();
+        assert!(fonts.system_fonts.get().is_none());
+        // Sorted case-insensitively, whether or not fontconfig is installed
+        let families = fonts.system_families();
+        assert!(families.windows(2).all(|pair| pair[0].to_lowercase() <= pair[1].to_lowercase()));
+        assert!(fonts.system_fonts.get().is_some());
+    }
+}
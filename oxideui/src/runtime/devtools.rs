@@ -0,0 +1,306 @@
+//! Remote inspection of a running app
+//!
+//! With the `devtools` feature, `Runtime::with_devtools` serves the element
+//! tree, layout rects, watched state values and frame timings over a
+//! WebSocket on localhost, so an external inspector can attach. Each text
+//! message is a JSON request, answered with the same `id`:
+//!
+//! ```text
+//! → {"id": 1, "method": "getElement", "params": {"id": 7}}
+//! ← {"id": 1, "result": {"id": 7, "widget": "oxideui::widgets::Button", ...}}
+//! ```
+//!
+//! The methods are `getElementTree`, `getElement`, `getStates` and
+//! `getPerformance`. Requests are answered on the UI thread between frames,
+//! so answers never see a half-built tree.
+//!
+//! Each run picks a random token, printed with the server's address;
+//! inspectors connect to `ws://127.0.0.1:{port}/?token={token}`. Handshakes
+//! from web pages, which always carry an `Origin` header, are refused, so a
+//! site open in the browser can't read the app's state.
+
+use std::collections::hash_map::RandomState;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::thread;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::Message;
+use winit::event_loop::EventLoopProxy;
+
+use crate::core::element::{ElementId, ElementTree};
+use crate::core::render_object::Rect;
+use crate::layout::LayoutCacheStats;
+use crate::runtime::{FrameTimings, RuntimeEvent};
+use crate::state_management::State;
+
+type StateProbe = Box<dyn Fn() -> String + Send + Sync>;
+
+/// Debug server settings, passed to `Runtime::with_devtools`
+pub struct DevTools {
+    port: u16,
+    states: Vec<(String, StateProbe)>,
+}
+
+impl DevTools {
+    /// Serve on `ws://127.0.0.1:{port}`
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            states: Vec::new(),
+        }
+    }
+
+    /// Show `state` to the inspector under `name`, formatted with `Debug`
+    pub fn watch<T>(mut self, name: impl Into<String>, state: &State<T>) -> Self
+    where
+        T: Clone + Debug + Send + Sync + 'static,
+    {
+        let state = state.clone();
+        self.states.push((name.into(), Box::new(move || format!("{:?}", state.get()))));
+        self
+    }
+}
+
+/// A request waiting for the UI thread to answer it
+pub(crate) struct DevToolsRequest {
+    method: String,
+    params: Value,
+    reply: oneshot::Sender<Result<Value, String>>,
+}
+
+/// The UI thread's side of the debug server
+pub(crate) struct DevToolsHost {
+    states: Vec<(String, StateProbe)>,
+    frames: u64,
+    last_frame: FrameTimings,
+}
+
+impl DevToolsHost {
+    /// Listen for inspectors; each connection gets its own thread, which
+    /// forwards requests to the event loop through `proxy`
+    pub(crate) fn start(devtools: DevTools, proxy: EventLoopProxy<RuntimeEvent>) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, devtools.port))
+            .with_context(|| format!("Failed to listen for DevTools on port {}", devtools.port))?;
+        let token = random_token();
+        println!("🔍 DevTools listening on ws://127.0.0.1:{}/?token={}", devtools.port, token);
+        thread::Builder::new()
+            .name("oxideui-devtools".into())
+            .spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let proxy = proxy.clone();
+                    let token = token.clone();
+                    thread::spawn(move || {
+                        if let Err(e) = serve_client(stream, &token, proxy) {
+                            eprintln!("⚠️ DevTools connection closed: {:#}", e);
+                        }
+                    });
+                }
+            })
+            .context("Failed to start the DevTools thread")?;
+        Ok(Self {
+            states: devtools.states,
+            frames: 0,
+            last_frame: FrameTimings::default(),
+        })
+    }
+
+    pub(crate) fn record_frame(&mut self, timings: FrameTimings) {
+        self.frames += 1;
+        self.last_frame = timings;
+    }
+
+    pub(crate) fn answer(&self, request: DevToolsRequest, tree: &ElementTree, layout_cache: LayoutCacheStats) {
+        let result = match request.method.as_str() {
+            "getElementTree" => Ok(tree.root().map_or(Value::Null, |root| element_json(tree, root, true))),
+            "getElement" => request
+                .params
+                .get("id")
+                .and_then(Value::as_u64)
+                .map(ElementId::new)
+                .filter(|id| tree.get(*id).is_some())
+                .map(|id| element_json(tree, id, false))
+                .ok_or_else(|| "No element with that id".to_string()),
+            "getStates" => Ok(self.states.iter().map(|(name, probe)| (name.clone(), Value::from(probe()))).collect()),
+            "getPerformance" => {
+                let ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+                Ok(json!({
+                    "frames": self.frames,
+                    "lastFrameMs": {
+                        "events": ms(self.last_frame.events),
+                        "build": ms(self.last_frame.build),
+                        "paint": ms(self.last_frame.paint),
+                        "present": ms(self.last_frame.present),
+                        "total": ms(self.last_frame.total()),
+                    },
+                    "elements": tree.len(),
+                    "layoutCache": {
+                        "entries": layout_cache.entries,
+                        "hits": layout_cache.hits,
+                        "misses": layout_cache.misses,
+                    },
+                }))
+            }
+            other => Err(format!("Unknown method \"{}\"", other)),
+        };
+        // The client may have gone away meanwhile
+        let _ = request.reply.send(result);
+    }
+}
+
+/// 128 bits from the standard library's per-process random hash keys
+fn random_token() -> String {
+    (0..2)
+        .map(|_| format!("{:016x}", RandomState::new().build_hasher().finish()))
+        .collect()
+}
+
+/// Accept a handshake only from outside a browser, and with this run's
+/// token in its query string
+fn authorize(request: &Request, token: &str) -> Result<(), StatusCode> {
+    if request.headers().contains_key("origin") {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let presented = request
+        .uri()
+        .query()
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|pair| pair.strip_prefix("token="));
+    if presented != Some(token) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(())
+}
+
+/// Answer one inspector's requests until it disconnects
+fn serve_client(stream: TcpStream, token: &str, proxy: EventLoopProxy<RuntimeEvent>) -> Result<()> {
+    let check = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        authorize(request, token).map(|()| response).map_err(|status| {
+            let mut refusal = ErrorResponse::new(Some(status.to_string()));
+            *refusal.status_mut() = status;
+            refusal
+        })
+    };
+    let mut socket = tungstenite::accept_hdr(stream, check).context("WebSocket handshake failed")?;
+    loop {
+        let text = match socket.read()? {
+            Message::Text(text) => text.to_string(),
+            Message::Close(_) => return Ok(()),
+            _ => continue,
+        };
+        let response = match serde_json::from_str::<Value>(&text) {
+            Ok(request) => {
+                let id = request.get("id").cloned().unwrap_or(Value::Null);
+                let (reply, answer) = oneshot::channel();
+                let request = DevToolsRequest {
+                    method: request.get("method").and_then(Value::as_str).unwrap_or_default().to_string(),
+                    params: request.get("params").cloned().unwrap_or(Value::Null),
+                    reply,
+                };
+                proxy
+                    .send_event(RuntimeEvent::DevTools(request))
+                    .map_err(|_| anyhow::anyhow!("The app has exited"))?;
+                match answer.recv().context("The app has exited")? {
+                    Ok(result) => json!({ "id": id, "result": result }),
+                    Err(error) => json!({ "id": id, "error": error }),
+                }
+            }
+            Err(e) => json!({ "id": null, "error": format!("Invalid request: {}", e) }),
+        };
+        socket.send(Message::text(response.to_string()))?;
+    }
+}
+
+fn rect_json(rect: Rect) -> Value {
+    json!({ "x": rect.x, "y": rect.y, "width": rect.width, "height": rect.height })
+}
+
+/// `id` and, for the whole tree, its descendants; a single element comes
+/// with its constraints and semantics instead
+fn element_json(tree: &ElementTree, id: ElementId, recursive: bool) -> Value {
+    let Some(element) = tree.get(id) else {
+        return Value::Null;
+    };
+    let mut value = json!({
+        "id": id.as_u64(),
+        "widget": element.widget.as_ref().map(|widget| widget.debug_name()),
+        "key": element.key.as_ref().map(|key| format!("{:?}", key)),
+        "bounds": element.bounds.map(rect_json),
+        "dirty": element.dirty,
+    });
+    if recursive {
+        value["children"] = element.children.iter().map(|child| element_json(tree, *child, true)).collect();
+    } else {
+        let constraints = element.constraints;
+        value["parent"] = json!(element.parent.map(|parent| parent.as_u64()));
+        value["children"] = element.children.iter().map(|child| json!(child.as_u64())).collect();
+        value["size"] = json!({ "width": element.size.width, "height": element.size.height });
+        value["constraints"] = json!({
+            "minWidth": constraints.min_width,
+            "maxWidth": constraints.max_width,
+            "minHeight": constraints.min_height,
+            "maxHeight": constraints.max_height,
+        });
+        value["semantics"] = json!(element.semantics.as_ref().map(|semantics| json!({
+            "label": semantics.label,
+            "role": semantics.role.map(|role| format!("{:?}", role)),
+            "value": semantics.value,
+        })));
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::Matrix;
+    use crate::layout::Size;
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_element_json_reports_layout() {
+        let mut tree = ElementTree::new();
+        let root = tree.create_element(&Text::new("root"), None, 0);
+        let child = tree.create_element(&Text::new("child"), Some(root), 0);
+        tree.set_layout(root, Matrix::identity(), Size::new(400.0, 300.0));
+        tree.set_layout(child, Matrix::translate(10.0, 20.0), Size::new(50.0, 16.0));
+
+        let json = element_json(&tree, root, true);
+        assert_eq!(json["children"][0]["id"], child.as_u64());
+        assert_eq!(json["children"][0]["bounds"]["y"], 20.0);
+        assert!(json["widget"].as_str().unwrap().ends_with("Text"));
+
+        let json = element_json(&tree, child, false);
+        assert_eq!(json["parent"], root.as_u64());
+        assert_eq!(json["size"]["width"], 50.0);
+    }
+
+    #[test]
+    fn test_handshakes_need_the_token_and_no_origin() {
+        let token = random_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, random_token());
+        let request = |uri: &str, origin: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(origin) = origin {
+                builder = builder.header("Origin", origin);
+            }
+            builder.body(()).unwrap()
+        };
+
+        let with_token = format!("/?token={}", token);
+        assert_eq!(authorize(&request(&with_token, None), &token), Ok(()));
+        assert_eq!(authorize(&request("/?token=guess", None), &token), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(authorize(&request("/", None), &token), Err(StatusCode::UNAUTHORIZED));
+        // A page in the browser, even one that learned the token
+        assert_eq!(
+            authorize(&request(&with_token, Some("https://example.com")), &token),
+            Err(StatusCode::FORBIDDEN)
+        );
+    }
+}
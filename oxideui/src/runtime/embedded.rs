@@ -0,0 +1,280 @@
+//! Running OxideUI inside a host engine
+//!
+//! `EmbeddedUi` owns the widget tree but no window or event loop. Each tick
+//! the host forwards input with `send_event` and then draws the frame
+//! either into its own OpenGL framebuffer (`render_to_gl_framebuffer`) or
+//! into pixels it uploads to a texture itself (`render_to_rgba`, for wgpu
+//! and other non-GL engines). The background defaults to transparent so the
+//! UI composites over the scene.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use skia_safe::{gpu, AlphaType, ColorType, ISize, ImageInfo, Surface};
+
+use super::widget_builder::WidgetBuilder;
+use crate::core::element::{new_shared_element_tree, SharedElementTree};
+use crate::core::event::UiEvent;
+use crate::core::render_object::{Color, Matrix, RenderObject};
+use crate::core::state_driven::release_subscriptions;
+use crate::core::{EventDispatcher, Theme, Widget};
+use crate::layout::Constraints;
+use crate::platform::window::{CursorShape, WindowController};
+use crate::render::rendering_impl::SkiaRenderer;
+use crate::render::{RgbaImage, TextCache};
+
+/// Skia state for drawing into the host's framebuffer
+struct GlTarget {
+    context: gpu::DirectContext,
+    surface: Surface,
+    fbo: u32,
+    width: u32,
+    height: u32,
+}
+
+pub struct EmbeddedUi {
+    root_widget: Box<dyn Widget>,
+    theme: Arc<Theme>,
+    window_controller: WindowController,
+    event_dispatcher: EventDispatcher,
+    element_tree: SharedElementTree,
+    /// Injected events waiting for the next frame
+    pending_events: VecDeque<UiEvent>,
+    scale_factor: f32,
+    background: Color,
+    renderer: SkiaRenderer,
+    raster: Option<Surface>,
+    gl: Option<GlTarget>,
+}
+
+// SAFETY: Skia's surfaces and GPU context aren't `Send` because two threads
+// must not use them at once. Every Skia object here is owned by this struct
+// alone and only used through `&mut self`, so moving the whole UI to another
+// thread is sound; the Skia types it shares (typefaces, images) are
+// reference counted atomically. A GL host must make its context current on
+// whichever thread calls `render_to_gl_framebuffer`.
+unsafe impl Send for EmbeddedUi {}
+
+impl EmbeddedUi {
+    pub fn new(root_widget: Box<dyn Widget>) -> Self {
+        Self {
+            root_widget,
+            theme: Arc::new(Theme::default()),
+            window_controller: WindowController::new(),
+            event_dispatcher: EventDispatcher::new(),
+            element_tree: new_shared_element_tree(),
+            pending_events: VecDeque::new(),
+            scale_factor: 1.0,
+            background: Color::TRANSPARENT,
+            renderer: SkiaRenderer::new(),
+            raster: None,
+            gl: None,
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Arc::new(theme);
+        self
+    }
+
+    /// Physical pixels per logical pixel of the target
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Arc::new(theme);
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+        TextCache::global().set_scale_factor(scale_factor);
+    }
+
+    /// Queue an input event; positions are in logical pixels of the UI
+    /// layer. Events are dispatched at the start of the next render.
+    pub fn send_event(&mut self, event: UiEvent) {
+        self.pending_events.push_back(event);
+    }
+
+    /// Cursor shape the UI wants; the host applies it to its own window
+    pub fn cursor(&self) -> CursorShape {
+        self.window_controller.cursor()
+    }
+
+    /// Handle passed to widgets as `ctx.window()`. Window commands such as
+    /// drag or minimize are ignored when embedded.
+    pub fn window_controller(&self) -> WindowController {
+        self.window_controller.clone()
+    }
+
+    /// Dispatch queued input and build the frame for a `width` x `height`
+    /// pixel target
+    fn build_frame(&mut self, width: u32, height: u32) -> RenderObject {
+        {
+            let element_tree = self.element_tree.read();
+            while let Some(event) = self.pending_events.pop_front() {
+                if matches!(event, UiEvent::PointerMove { .. }) {
+                    self.window_controller.reset_cursor_request();
+                }
+                self.event_dispatcher.dispatch_event(&event, &element_tree);
+            }
+            self.event_dispatcher.poll_long_press(&element_tree);
+        }
+        let dirty = self.event_dispatcher.take_dirty_elements();
+        if !dirty.is_empty() {
+            let mut element_tree = self.element_tree.write();
+            for id in dirty {
+                element_tree.mark_dirty(id);
+            }
+        }
+        self.window_controller.commit_cursor();
+
+        let scale = self.scale_factor;
+        let constraints = Constraints::new(0.0, width as f32 / scale, 0.0, height as f32 / scale);
+        let builder = WidgetBuilder::new(self.theme.clone())
+            .with_element_tree(self.element_tree.clone())
+            .with_window(self.window_controller.clone());
+        let root = builder.build_widget_tree(&self.root_widget, constraints);
+        let unmounted = self.element_tree.write().take_unmounted();
+        let tree_id = self.element_tree.read().id();
+        for id in &unmounted {
+            release_subscriptions(tree_id, *id);
+        }
+        self.event_dispatcher.update_focusables(&self.element_tree.read(), &unmounted);
+        if scale != 1.0 {
+            RenderObject::transform(Matrix::scale(scale, scale), root)
+        } else {
+            root
+        }
+    }
+
+    /// Render a frame into CPU memory for the host to upload
+    pub fn render_to_rgba(&mut self, width: u32, height: u32) -> Result<RgbaImage> {
+        if width == 0 || height == 0 {
+            bail!("Cannot render an empty {}x{} frame", width, height);
+        }
+        let root = self.build_frame(width, height);
+
+        let info = ImageInfo::new(ISize::new(width as i32, height as i32), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let stale = self.raster.as_ref().is_none_or(|s| s.width() != width as i32 || s.height() != height as i32);
+        if stale {
+            self.raster = Some(
+                skia_safe::surfaces::raster(&info.with_alpha_type(AlphaType::Premul), None, None)
+                    .ok_or_else(|| anyhow!("Failed to create a {}x{} surface", width, height))?,
+            );
+        }
+        let surface = self.raster.as_mut().expect("raster surface was just created");
+
+        self.renderer.backdrop_filters = false;
+        self.renderer.clear(surface.canvas(), self.background);
+        self.renderer.render(surface.canvas(), &root);
+
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        if !surface.read_pixels(&info, &mut pixels, width as usize * 4, (0, 0)) {
+            bail!("Failed to read back the embedded frame");
+        }
+        RgbaImage::new(width, height, pixels)
+    }
+
+    /// Render a frame into framebuffer `fbo` (0 for the default one) of the
+    /// host's OpenGL context, which must be current on this thread.
+    ///
+    /// To draw into a texture, attach it to an FBO and pass that. GL state is
+    /// reset before drawing, so the host can change it freely between ticks,
+    /// but should restore its own state afterwards.
+    pub fn render_to_gl_framebuffer(&mut self, fbo: u32, width: u32, height: u32) -> Result<()> {
+        if width == 0 || height == 0 {
+            bail!("Cannot render an empty {}x{} frame", width, height);
+        }
+        let root = self.build_frame(width, height);
+
+        let stale = self.gl.as_ref().is_none_or(|gl| gl.fbo != fbo || gl.width != width || gl.height != height);
+        if stale {
+            let context = match self.gl.take() {
+                Some(gl) => gl.context,
+                None => {
+                    let interface = gpu::gl::Interface::new_native().context("No current OpenGL context")?;
+                    gpu::direct_contexts::make_gl(interface, None).context("Failed to create Skia DirectContext")?
+                }
+            };
+            self.gl = Some(wrap_framebuffer(context, fbo, width, height)?);
+        }
+        let gl = self.gl.as_mut().expect("GL target was just created");
+
+        gl.context.reset(None);
+        self.renderer.backdrop_filters = true;
+        self.renderer.clear(gl.surface.canvas(), self.background);
+        self.renderer.render(gl.surface.canvas(), &root);
+        gl.context.flush_and_submit();
+        Ok(())
+    }
+}
+
+fn wrap_framebuffer(mut context: gpu::DirectContext, fbo: u32, width: u32, height: u32) -> Result<GlTarget> {
+    let fb_info = gpu::gl::FramebufferInfo {
+        fboid: fbo,
+        format: gpu::gl::Format::RGBA8.into(),
+        ..Default::default()
+    };
+    let target = gpu::backend_render_targets::make_gl((width as i32, height as i32), 0, 8, fb_info);
+    let surface = gpu::surfaces::wrap_backend_render_target(
+        &mut context,
+        &target,
+        gpu::SurfaceOrigin::BottomLeft,
+        ColorType::RGBA8888,
+        None,
+        None,
+    )
+    .context("Failed to wrap the host framebuffer")?;
+
+    Ok(GlTarget {
+        context,
+        surface,
+        fbo,
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::core::event::MouseButton;
+    use crate::core::render_object::Point;
+    use crate::widgets::basic::Container;
+    use crate::widgets::Button;
+
+    #[test]
+    fn test_injected_clicks_reach_the_built_frame() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let mut ui = EmbeddedUi::new(Box::new(Container::new().with_child(
+            Button::new("Play").with_size(120.0, 40.0).with_on_click(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        )))
+        .with_scale_factor(2.0);
+
+        ui.build_frame(800, 600);
+        let elements = ui.element_tree.read().len();
+        assert_eq!(elements, 2);
+
+        // Logical pixels, dispatched at the start of the next frame
+        let position = Point::new(20.0, 20.0);
+        ui.send_event(UiEvent::PointerDown { id: 0, position, button: MouseButton::Left });
+        ui.send_event(UiEvent::PointerUp { id: 0, position, button: MouseButton::Left });
+        ui.build_frame(800, 600);
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
+        assert_eq!(ui.element_tree.read().len(), elements, "frames reuse the elements");
+    }
+}
@@ -0,0 +1,127 @@
+//! Frame budget monitoring
+//!
+//! `Runtime::with_jank_monitor` reports every frame that takes longer than
+//! its budget, with the time spent in each phase and the elements that were
+//! dirty going into the frame, so production apps can send slow frames to
+//! their telemetry.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::ElementId;
+
+/// Time spent in each phase of a frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameTimings {
+    /// Dispatching queued input and applying theme and locale changes
+    pub events: Duration,
+    /// Building and laying out the widget tree
+    pub build: Duration,
+    /// Drawing the render tree
+    pub paint: Duration,
+    /// Handing the frame to the compositor
+    pub present: Duration,
+}
+
+impl FrameTimings {
+    pub fn total(&self) -> Duration {
+        self.events + self.build + self.paint + self.present
+    }
+
+    /// The phase that took longest, by name
+    pub fn slowest_phase(&self) -> (&'static str, Duration) {
+        [
+            ("events", self.events),
+            ("build", self.build),
+            ("paint", self.paint),
+            ("present", self.present),
+        ]
+        .into_iter()
+        .max_by_key(|(_, duration)| *duration)
+        .unwrap_or(("events", Duration::ZERO))
+    }
+}
+
+/// A frame that went over budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct JankReport {
+    /// Frames rendered before this one
+    pub frame: u64,
+    pub budget: Duration,
+    pub timings: FrameTimings,
+    /// Elements marked dirty when the frame started
+    pub dirty_elements: Vec<ElementId>,
+}
+
+impl JankReport {
+    /// How far the frame went over its budget
+    pub fn overrun(&self) -> Duration {
+        self.timings.total().saturating_sub(self.budget)
+    }
+}
+
+pub(crate) type JankCallback = Arc<dyn Fn(&JankReport) + Send + Sync>;
+
+pub(crate) struct JankMonitor {
+    budget: Duration,
+    callback: JankCallback,
+    frame: u64,
+}
+
+impl JankMonitor {
+    pub(crate) fn new(budget: Duration, callback: JankCallback) -> Self {
+        Self { budget, callback, frame: 0 }
+    }
+
+    /// Report the frame if it went over budget. `dirty_elements` were
+    /// collected before the frame built, when the dirty flags were still set.
+    pub(crate) fn record(&mut self, timings: FrameTimings, dirty_elements: Vec<ElementId>) {
+        let frame = self.frame;
+        self.frame += 1;
+        if timings.total() <= self.budget {
+            return;
+        }
+        (self.callback)(&JankReport {
+            frame,
+            budget: self.budget,
+            timings,
+            dirty_elements,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[test]
+    fn test_only_slow_frames_are_reported() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let mut monitor = JankMonitor::new(
+            Duration::from_millis(16),
+            Arc::new(move |report: &JankReport| sink.lock().push(report.clone())),
+        );
+
+        let fast = FrameTimings {
+            build: Duration::from_millis(4),
+            paint: Duration::from_millis(6),
+            ..Default::default()
+        };
+        let slow = FrameTimings {
+            build: Duration::from_millis(30),
+            paint: Duration::from_millis(5),
+            ..Default::default()
+        };
+        monitor.record(fast, Vec::new());
+        monitor.record(slow, vec![ElementId::new(7)]);
+
+        let reports = reports.lock();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].frame, 1);
+        assert_eq!(reports[0].overrun(), Duration::from_millis(19));
+        assert_eq!(reports[0].timings.slowest_phase(), ("build", Duration::from_millis(30)));
+        assert_eq!(reports[0].dirty_elements, vec![ElementId::new(7)]);
+    }
+}
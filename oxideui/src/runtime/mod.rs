@@ -1,50 +1,353 @@
-mod widget_builder;
+pub mod recorder;
+#[cfg(feature = "devtools")]
+mod devtools;
+#[cfg(feature = "accesskit")]
+mod screen_reader;
+#[cfg(feature = "skia")]
+mod embedded;
+#[cfg(feature = "skia")]
+mod offscreen;
+mod jank;
+mod pacing;
+mod proxy;
+mod tasks;
+mod timers;
+pub(crate) mod widget_builder;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::sync::Arc;
 use winit::application::ApplicationHandler;
-use winit::dpi::LogicalSize;
-use winit::event::{DeviceEvent, DeviceId, StartCause, WindowEvent, ElementState, MouseButton};
-use winit::event_loop::{ActiveEventLoop, EventLoop};
-use winit::window::{Window, WindowAttributes, WindowId};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{DeviceEvent, DeviceId, ElementState, Ime, MouseButton, MouseScrollDelta, StartCause, TouchPhase, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
+use winit::keyboard::PhysicalKey;
+use winit::window::{Theme as WinitTheme, Window, WindowId};
+#[cfg(target_os = "android")]
+use winit::platform::android::activity::AndroidApp;
 use winit_input_helper::WinitInputHelper;
 use crate::core::element::SharedElementTree;
+use crate::core::state_driven::release_subscriptions;
 use crate::core::widget::Widget;
-use crate::core::{EventDispatcher, Theme};
-use crate::layout::Constraints;
-use crate::render::{select_backend, BackendType, RenderBackend};
-use crate::theming::ThemeConfig;
+use crate::core::event::{Modifiers, UiEvent, Vector2};
+use crate::core::render_object::{Color, Matrix, Point, RenderObject};
+use crate::core::{AccessibilityManager, EventDispatcher, FocusRing, Theme};
+use crate::i18n::Localizer;
+use crate::layout::{Constraints, Direction, EdgeInsets, LayoutCache};
+use crate::platform::mobile::{self, AppLifecycle};
+use crate::platform::window::{PresentedFrame, WindowConfig, WindowController, WindowIcon};
+use crate::platform::window_state::{MonitorArea, PersistedWindowState};
+#[cfg(feature = "global-hotkeys")]
+use crate::platform::hotkeys::{GlobalHotkeys, HotkeyAction, HotkeyBinding, HotkeyId};
+#[cfg(feature = "notifications")]
+use crate::platform::notifications::{NotificationEvent, Notifications};
+#[cfg(feature = "tray")]
+use crate::platform::tray::{self, TrayConfig, TrayEvent, TrayHandle, TrayMenuEntry};
+use crate::render::{select_backend, BackendType, FontManager, RenderBackend, TextCache};
+#[cfg(feature = "skia")]
+use crate::render::RgbaImage;
+use crate::animation::{Animation, EasingCurve};
+use crate::theming::{Stylesheet, SystemColors, ThemeConfig, ThemeController, ThemeMode, ThemeRequest, WidgetStyles};
+#[cfg(feature = "devtools")]
+pub use devtools::DevTools;
+#[cfg(feature = "devtools")]
+use devtools::{DevToolsHost, DevToolsRequest};
+use jank::{JankCallback, JankMonitor};
+use pacing::FramePacer;
 use widget_builder::WidgetBuilder;
-use std::time::Instant;
+#[cfg(feature = "skia")]
+pub use embedded::EmbeddedUi;
+pub use jank::{FrameTimings, JankReport};
+pub use proxy::RuntimeProxy;
+use proxy::{Message, MessageHandlers};
+pub use tasks::UiHandle;
+#[cfg(feature = "async")]
+pub use tasks::TaskHandle;
+#[cfg(feature = "async")]
+pub(crate) use tasks::TaskSpawner;
+use tasks::UiTask;
+pub use timers::TimerHandle;
+pub(crate) use timers::TimerScheduler;
+#[cfg(feature = "skia")]
+pub use offscreen::OffscreenRenderer;
+pub use recorder::{EventRecorder, EventReplay, RecordedEntry, RecordedEvent, RecordedInput};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
+use web_time::Instant;
 use oneshot;
+
+/// Messages delivered to the event loop from other threads
+pub(crate) enum RuntimeEvent {
+    /// An event for the widget dispatcher
+    Ui(UiEvent),
+    /// Work sent back to the UI thread through a `UiHandle`
+    Run(UiTask),
+    /// A typed message from `UiHandle::send`
+    Message(Message),
+    /// The OS reduced motion preference, read on a background thread
+    ReducedMotion(bool),
+    /// Tray icon click or tray menu selection
+    #[cfg(feature = "tray")]
+    Tray(TrayEvent),
+    /// A registered global hotkey was pressed
+    #[cfg(feature = "global-hotkeys")]
+    Hotkey(HotkeyId),
+    /// The user responded to a desktop notification
+    #[cfg(feature = "notifications")]
+    Notification(NotificationEvent),
+    /// A request from a DevTools inspector
+    #[cfg(feature = "devtools")]
+    DevTools(DevToolsRequest),
+    /// A screen reader asked for the tree or for an action
+    #[cfg(feature = "accesskit")]
+    Accessibility(accesskit_winit::Event),
+}
+
+#[cfg(feature = "accesskit")]
+impl From<accesskit_winit::Event> for RuntimeEvent {
+    fn from(event: accesskit_winit::Event) -> Self {
+        RuntimeEvent::Accessibility(event)
+    }
+}
+
+/// Cloneable handle for injecting events into a running app from any thread.
+///
+/// Events are queued and dispatched at the start of the next frame.
+#[derive(Clone)]
+pub struct EventSender {
+    proxy: EventLoopProxy<RuntimeEvent>,
+}
+
+impl EventSender {
+    /// Queue an event; fails once the event loop has shut down
+    pub fn send(&self, event: UiEvent) -> Result<()> {
+        self.proxy
+            .send_event(RuntimeEvent::Ui(event))
+            .map_err(|_| anyhow::anyhow!("Event loop is closed"))
+    }
+}
+
 pub struct Runtime {
-    event_loop: Option<EventLoop<()>>,
+    event_loop: Option<EventLoop<RuntimeEvent>>,
+    proxy: EventLoopProxy<RuntimeEvent>,
     root_widget: Option<Box<dyn Widget>>,
-    title: String,
-    width: u32,
-    height: u32,
+    window_config: WindowConfig,
+    persist_id: Option<String>,
     theme_config: Option<ThemeConfig>,
+    theme_mode: ThemeMode,
+    theme_transition: Option<Duration>,
+    stylesheet: Option<Arc<Stylesheet>>,
+    widget_styles: Option<Arc<WidgetStyles>>,
+    focus_ring: Option<FocusRing>,
+    reduced_motion: Option<bool>,
+    forced_colors: Option<bool>,
+    localizer: Option<Localizer>,
+    direction: Option<Direction>,
+    parallel_build: bool,
+    max_fps: Option<u32>,
+    jank_monitor: Option<(Duration, JankCallback)>,
+    record_path: Option<PathBuf>,
+    replay_path: Option<PathBuf>,
+    #[cfg(feature = "tray")]
+    tray: Option<TrayConfig>,
+    #[cfg(feature = "global-hotkeys")]
+    hotkeys: Vec<HotkeyBinding>,
+    #[cfg(feature = "notifications")]
+    notifications: Notifications,
+    #[cfg(feature = "devtools")]
+    devtools: Option<DevTools>,
+    window_controller: WindowController,
+    lifecycle_listeners: Vec<LifecycleListener>,
+    message_handlers: MessageHandlers,
 }
 
+type LifecycleListener = Box<dyn Fn(AppLifecycle)>;
+
 impl Runtime {
     pub fn new(root_widget: Box<dyn Widget>) -> Self {
+        let event_loop = EventLoop::with_user_event().build().unwrap();
+        Self::with_event_loop(root_widget, event_loop)
+    }
+
+    /// Create the runtime from `android_main`
+    #[cfg(target_os = "android")]
+    pub fn new_android(root_widget: Box<dyn Widget>, app: AndroidApp) -> Self {
+        use winit::platform::android::EventLoopBuilderExtAndroid;
+
+        crate::platform::mobile::set_android_app(app.clone());
+        let event_loop = EventLoop::with_user_event().with_android_app(app).build().unwrap();
+        Self::with_event_loop(root_widget, event_loop)
+    }
+
+    fn with_event_loop(root_widget: Box<dyn Widget>, event_loop: EventLoop<RuntimeEvent>) -> Self {
+        let proxy = event_loop.create_proxy();
         Self {
-            event_loop: Some(EventLoop::new().unwrap()),
+            #[cfg(feature = "notifications")]
+            notifications: Notifications::new(proxy.clone()),
+            proxy,
+            event_loop: Some(event_loop),
             root_widget: Some(root_widget),
-            title: "OxideUI Application".to_string(),
-            width: 800,
-            height: 600,
+            window_config: WindowConfig::default(),
+            persist_id: None,
             theme_config: None,
+            theme_mode: ThemeMode::default(),
+            theme_transition: None,
+            stylesheet: None,
+            widget_styles: None,
+            focus_ring: Some(FocusRing::default()),
+            reduced_motion: None,
+            forced_colors: None,
+            localizer: None,
+            direction: None,
+            parallel_build: false,
+            max_fps: None,
+            jank_monitor: None,
+            record_path: None,
+            replay_path: None,
+            #[cfg(feature = "tray")]
+            tray: None,
+            #[cfg(feature = "global-hotkeys")]
+            hotkeys: Vec::new(),
+            #[cfg(feature = "devtools")]
+            devtools: None,
+            window_controller: WindowController::new(),
+            lifecycle_listeners: Vec::new(),
+            message_handlers: MessageHandlers::default(),
         }
     }
 
     pub fn with_title(mut self, title: &str) -> Self {
-        self.title = title.to_string();
+        self.window_config.title = title.to_string();
         self
     }
 
     pub fn with_size(mut self, width: u32, height: u32) -> Self {
-        self.width = width;
-        self.height = height;
+        self.window_config.width = width;
+        self.window_config.height = height;
+        self
+    }
+
+    pub fn with_min_size(mut self, width: u32, height: u32) -> Self {
+        self.window_config.min_size = Some((width, height));
+        self
+    }
+
+    pub fn with_max_size(mut self, width: u32, height: u32) -> Self {
+        self.window_config.max_size = Some((width, height));
+        self
+    }
+
+    /// Initial window position in logical pixels; the OS decides when unset
+    pub fn with_position(mut self, x: i32, y: i32) -> Self {
+        self.window_config.position = Some((x, y));
+        self
+    }
+
+    pub fn with_window_icon(mut self, icon: WindowIcon) -> Self {
+        self.window_config.icon = Some(icon);
+        self
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.window_config.resizable = resizable;
+        self
+    }
+
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.window_config.decorations = decorations;
+        self
+    }
+
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> Self {
+        self.window_config.always_on_top = always_on_top;
+        self
+    }
+
+    pub fn with_maximized(mut self, maximized: bool) -> Self {
+        self.window_config.maximized = maximized;
+        self
+    }
+
+    /// Draw the title bar yourself (see `CustomTitleBar`); turns off native decorations
+    pub fn with_custom_title_bar(mut self) -> Self {
+        self.window_config.decorations = false;
+        self
+    }
+
+    /// Transparent window; the renderer clears to transparent instead of white
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        self.window_config.transparent = transparent;
+        self
+    }
+
+    /// Call `listener` when the app moves to the background or comes back.
+    ///
+    /// Mobile OSes pause apps when switching away; desktop windows are
+    /// never paused.
+    pub fn on_lifecycle<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(AppLifecycle) + 'static,
+    {
+        self.lifecycle_listeners.push(Box::new(listener));
+        self
+    }
+
+    /// Handle messages of type `M` sent through `UiHandle::send`. Runs
+    /// on the UI thread; a frame follows each message.
+    pub fn with_message_handler<M, F>(mut self, handler: F) -> Self
+    where
+        M: std::any::Any + Send + Sync,
+        F: Fn(M) + 'static,
+    {
+        self.message_handlers.insert(handler);
+        self
+    }
+
+    /// Web only: draw into the page's `<canvas id="...">` instead of a new one
+    pub fn with_canvas(mut self, canvas_id: &str) -> Self {
+        self.window_config.canvas_id = Some(canvas_id.to_string());
+        self
+    }
+
+    /// Remember window position, size and maximized state across launches.
+    ///
+    /// `app_id` names the directory under the user's config dir.
+    pub fn with_persisted_window_state(mut self, app_id: &str) -> Self {
+        self.persist_id = Some(app_id.to_string());
+        self
+    }
+
+    /// Serve the element tree, layout, watched state and frame timings to
+    /// an inspector over a WebSocket on localhost
+    #[cfg(feature = "devtools")]
+    pub fn with_devtools(mut self, devtools: DevTools) -> Self {
+        self.devtools = Some(devtools);
+        self
+    }
+
+    /// Show an icon in the system tray
+    #[cfg(feature = "tray")]
+    pub fn with_tray(mut self, tray: TrayConfig) -> Self {
+        self.tray = Some(tray);
+        self
+    }
+
+    /// Register a system-wide hotkey such as `"Ctrl+Shift+Space"`.
+    ///
+    /// It fires even while the window is unfocused or hidden. `run` fails if
+    /// the accelerator is invalid or already taken.
+    #[cfg(feature = "global-hotkeys")]
+    pub fn with_global_hotkey(mut self, accelerator: &str, action: HotkeyAction) -> Self {
+        self.hotkeys.push(HotkeyBinding {
+            accelerator: accelerator.to_string(),
+            action,
+        });
+        self
+    }
+
+    /// Replace the whole window configuration
+    pub fn with_window_config(mut self, config: WindowConfig) -> Self {
+        self.window_config = config;
         self
     }
 
@@ -53,42 +356,290 @@ impl Runtime {
         self
     }
 
+    /// Pick the light or dark palette, or follow the OS with `ThemeMode::System`
+    pub fn with_theme_mode(mut self, mode: ThemeMode) -> Self {
+        self.theme_mode = mode;
+        self
+    }
+
+    /// Cross-fade between themes for `duration` when the theme changes at runtime
+    pub fn with_theme_transition(mut self, duration: Duration) -> Self {
+        self.theme_transition = Some(duration);
+        self
+    }
+
+    /// Style widgets by class with `stylesheet`
+    pub fn with_stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheet = Some(Arc::new(stylesheet));
+        self
+    }
+
+    /// Default `ButtonStyle`, `InputStyle` and `CardStyle` for every widget
+    /// that doesn't set its own
+    pub fn with_widget_styles(mut self, styles: WidgetStyles) -> Self {
+        self.widget_styles = Some(Arc::new(styles));
+        self
+    }
+
+    /// Style of the ring drawn around the focused widget after keyboard
+    /// navigation
+    pub fn with_focus_ring(mut self, ring: FocusRing) -> Self {
+        self.focus_ring = Some(ring);
+        self
+    }
+
+    /// Never draw a focus ring, for apps that style focus themselves
+    pub fn without_focus_ring(mut self) -> Self {
+        self.focus_ring = None;
+        self
+    }
+
+    /// Force reduced motion on or off instead of following the OS setting
+    pub fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.reduced_motion = Some(reduced_motion);
+        self
+    }
+
+    /// Force forced-colors mode on or off. It otherwise follows Windows
+    /// High Contrast; other platforms have no OS setting for it.
+    pub fn with_forced_colors(mut self, forced: bool) -> Self {
+        self.forced_colors = Some(forced);
+        self
+    }
+
+    /// Translations for `ctx.tr`; switching its locale rebuilds the UI
+    pub fn with_localizer(mut self, localizer: Localizer) -> Self {
+        self.localizer = Some(localizer);
+        self
+    }
+
+    /// Force a reading direction instead of following the locale
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Render at most `fps` frames per second. Redraws requested sooner are
+    /// coalesced into the next frame slot. Defaults to the monitor's refresh
+    /// rate, or 60 when it's unknown.
+    pub fn with_max_fps(mut self, fps: u32) -> Self {
+        self.max_fps = Some(fps.max(1));
+        self
+    }
+
+    /// Call `on_jank` for every frame that takes longer than `budget`, with
+    /// a per-phase breakdown and the elements that were dirty, e.g. to send
+    /// slow frames to telemetry. Runs on the UI thread, so keep it short.
+    pub fn with_jank_monitor<F>(mut self, budget: Duration, on_jank: F) -> Self
+    where
+        F: Fn(&JankReport) + Send + Sync + 'static,
+    {
+        self.jank_monitor = Some((budget, Arc::new(on_jank)));
+        self
+    }
+
+    /// Build sibling subtrees of large containers in parallel on a rayon
+    /// pool. Needs the `parallel` feature; worthwhile for trees with
+    /// thousands of widgets, overhead for small ones.
+    #[cfg(feature = "parallel")]
+    pub fn with_parallel_build(mut self, parallel: bool) -> Self {
+        self.parallel_build = parallel;
+        self
+    }
+
+    /// Ship a font with the app, e.g.
+    /// `.with_font("Inter", include_bytes!("../fonts/Inter-Regular.ttf"))`,
+    /// and refer to it by family name in the theme
+    pub fn with_font(self, family: &str, data: impl Into<Vec<u8>>) -> Self {
+        if let Err(e) = FontManager::register_font(family, data) {
+            eprintln!("⚠️ Ignoring font: {:#}", e);
+        }
+        self
+    }
+
+    /// Record every dispatched event and window resize to `path`
+    pub fn with_event_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_path = Some(path.into());
+        self
+    }
+
+    /// Replay a recording made with `with_event_recording`
+    pub fn with_event_replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay_path = Some(path.into());
+        self
+    }
+
+    /// Inject an event into the dispatcher; it is processed on the next frame.
+    ///
+    /// Events sent before `run` are delivered once the window is up.
+    pub fn send_event(&self, event: UiEvent) {
+        if self.proxy.send_event(RuntimeEvent::Ui(event)).is_err() {
+            eprintln!("⚠️ Dropped synthetic event: event loop is closed");
+        }
+    }
+
+    /// Handle for running closures and setting state on the UI thread from
+    /// background threads; widgets get the same handle from `ctx.ui_handle()`
+    pub fn ui_handle(&self) -> UiHandle {
+        UiHandle::new(self.proxy.clone())
+    }
+
+    /// Channel-style handle for sending events from other threads or tests
+    pub fn event_sender(&self) -> EventSender {
+        EventSender {
+            proxy: self.proxy.clone(),
+        }
+    }
+
+    /// Handle for controlling the window once it is up, from any thread
+    pub fn window_controller(&self) -> WindowController {
+        self.window_controller.clone()
+    }
+
+    /// Pixels of the last presented frame, re-rendered offscreen.
+    ///
+    /// Fails until the first frame; call on `window_controller()` from a
+    /// running app.
+    #[cfg(feature = "skia")]
+    pub fn capture_frame(&self) -> Result<RgbaImage> {
+        self.window_controller.capture_frame()
+    }
+
+    /// Handle for showing desktop notifications from any thread
+    #[cfg(feature = "notifications")]
+    pub fn notifications(&self) -> Notifications {
+        self.notifications.clone()
+    }
+
     pub async fn run(self) -> Result<()> {
         let event_loop = self.event_loop.context("Event loop was taken")?;
         let root_widget = self.root_widget.context("Root widget was taken")?;
         let (tx, rx) = oneshot::channel::<()>();
 
+        let recorder = self.record_path.map(EventRecorder::create).transpose()?;
+        let replay = self.replay_path.map(EventReplay::load).transpose()?;
+
+        #[cfg(feature = "global-hotkeys")]
+        let hotkeys = if self.hotkeys.is_empty() {
+            None
+        } else {
+            let proxy = self.proxy.clone();
+            let mut hotkeys = GlobalHotkeys::new(move |id| {
+                let _ = proxy.send_event(RuntimeEvent::Hotkey(id));
+            })?;
+            for binding in &self.hotkeys {
+                hotkeys.register(binding)?;
+                println!("⌨️ Registered global hotkey: {}", binding.accelerator);
+            }
+            Some(hotkeys)
+        };
+
+        #[cfg(feature = "devtools")]
+        let devtools = self.devtools.and_then(|devtools| {
+            DevToolsHost::start(devtools, self.proxy.clone())
+                .map_err(|e| eprintln!("⚠️ DevTools disabled: {:#}", e))
+                .ok()
+        });
+
         let mut app = OxideApp {
             window: None,
             renderer: None,
             backend_type: select_backend(),
             input: WinitInputHelper::new(),
             event_dispatcher: EventDispatcher::new(),
+            accessibility: AccessibilityManager::new(),
             element_tree: crate::core::element::new_shared_element_tree(),
             exit_tx: Some(tx),
             root_widget,
             theme_config: self.theme_config,
-            title: self.title,
-            width: self.width,
-            height: self.height,
+            theme_mode: self.theme_mode,
+            system_dark: false,
+            theme_controller: ThemeController::new(),
+            theme_transition: self.theme_transition,
+            theme_animation: None,
+            stylesheet: self.stylesheet,
+            widget_styles: self.widget_styles,
+            focus_ring: self.focus_ring,
+            forced_reduced_motion: self.reduced_motion,
+            reduced_motion: false,
+            forced_colors: self.forced_colors,
+            system_colors: None,
+            localizer: self.localizer,
+            direction: self.direction,
+            parallel_build: self.parallel_build,
+            layout_cache: LayoutCache::new(),
+            pacer: FramePacer::new(self.max_fps),
+            timers: TimerScheduler::new(),
+            jank_monitor: self
+                .jank_monitor
+                .map(|(budget, callback)| JankMonitor::new(budget, callback)),
+            window_config: self.window_config,
+            window_controller: self.window_controller,
+            lifecycle_listeners: self.lifecycle_listeners,
+            message_handlers: self.message_handlers,
+            paused: false,
+            persist_id: self.persist_id,
+            normal_geometry: None,
             theme: Arc::new(Theme::default()),
             last_frame_time: Instant::now(),
             frame_count: 0,
+            pending_events: VecDeque::new(),
+            scale_factor: 1.0,
+            cursor_position: Point::new(0.0, 0.0),
+            touches: HashMap::new(),
+            hovered_files: Vec::new(),
+            modifiers: Modifiers::default(),
+            recorder,
+            replay,
+            replay_start: Instant::now(),
+            proxy: self.proxy,
+            #[cfg(feature = "async")]
+            tasks: TaskSpawner::new()
+                .map_err(|e| eprintln!("⚠️ Background tasks disabled: {}", e))
+                .ok(),
+            #[cfg(feature = "tray")]
+            tray: self.tray,
+            #[cfg(feature = "tray")]
+            tray_handle: None,
+            #[cfg(feature = "global-hotkeys")]
+            hotkeys,
+            #[cfg(feature = "notifications")]
+            notifications: self.notifications,
+            #[cfg(feature = "devtools")]
+            devtools,
+            #[cfg(feature = "accesskit")]
+            accesskit: None,
         };
 
         println!("🎨 OxideUI Framework Starting...");
         println!("📦 Selected renderer: {:?}", app.backend_type);
         println!(
             "🪟 Window: \"{}\" ({}x{})",
-            app.title, app.width, app.height
+            app.window_config.title, app.window_config.width, app.window_config.height
         );
 
-        event_loop
-            .run_app(&mut app)
-            .context("Failed to run application event loop")?;
+        #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+        crate::assets::Assets::global().watch(UiHandle::new(app.proxy.clone()));
+
+        // The browser owns the loop: spawning returns at once and the app
+        // lives on in the page's animation frame callbacks
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::EventLoopExtWebSys;
+            drop(rx);
+            event_loop.spawn_app(app);
+            Ok(())
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            event_loop
+                .run_app(&mut app)
+                .context("Failed to run application event loop")?;
 
-        rx.await.context("Event loop shutdown channel failed")?;
-        Ok(())
+            rx.await.context("Event loop shutdown channel failed")?;
+            Ok(())
+        }
     }
 }
 
@@ -98,95 +649,213 @@ struct OxideApp {
     backend_type: BackendType,
     input: WinitInputHelper,
     event_dispatcher: EventDispatcher,
+    /// Labels and roles exported from `Semantics` widgets each frame
+    accessibility: AccessibilityManager,
     element_tree: SharedElementTree,
     exit_tx: Option<oneshot::Sender<()>>,
     root_widget: Box<dyn Widget>,
     theme_config: Option<ThemeConfig>,
-    title: String,
-    width: u32,
-    height: u32,
+    theme_mode: ThemeMode,
+    /// Last OS light/dark preference reported by the window
+    system_dark: bool,
+    theme_controller: ThemeController,
+    theme_transition: Option<Duration>,
+    /// Cross-fade in progress after a runtime theme change
+    theme_animation: Option<Animation<Theme>>,
+    stylesheet: Option<Arc<Stylesheet>>,
+    widget_styles: Option<Arc<WidgetStyles>>,
+    focus_ring: Option<FocusRing>,
+    /// Set by `Runtime::with_reduced_motion`; otherwise the OS decides
+    forced_reduced_motion: Option<bool>,
+    reduced_motion: bool,
+    /// Set by `Runtime::with_forced_colors` or the theme controller
+    forced_colors: Option<bool>,
+    /// OS palette while it forces colors
+    system_colors: Option<SystemColors>,
+    localizer: Option<Localizer>,
+    /// Set by `Runtime::with_direction`; otherwise the locale decides
+    direction: Option<Direction>,
+    parallel_build: bool,
+    /// Layouts of unchanged widgets, reused across frames
+    layout_cache: LayoutCache,
+    /// Caps the frame rate; redraws requested early wait for the next slot
+    pacer: FramePacer,
+    /// Set by `Runtime::with_jank_monitor`
+    jank_monitor: Option<JankMonitor>,
+    /// Callbacks from `ctx.after` and `ctx.every`
+    timers: TimerScheduler,
+    window_config: WindowConfig,
+    window_controller: WindowController,
+    lifecycle_listeners: Vec<LifecycleListener>,
+    message_handlers: MessageHandlers,
+    /// Suspended by the OS; the renderer is dropped until resumed
+    paused: bool,
+    /// App id for window state persistence, if enabled
+    persist_id: Option<String>,
+    /// Last un-maximized position and size, saved on exit
+    normal_geometry: Option<PersistedWindowState>,
     theme: Arc<Theme>,
     last_frame_time: Instant,
     frame_count: u64,
+    /// Injected events waiting for the next frame
+    pending_events: VecDeque<UiEvent>,
+    /// Physical pixels per logical pixel for the window's current monitor
+    scale_factor: f64,
+    /// Last cursor position, in logical pixels
+    cursor_position: Point,
+    /// Files being dragged over the window, between the first
+    /// `HoveredFile` and the drop or cancel
+    hovered_files: Vec<PathBuf>,
+    /// Last position of each finger on the screen, by touch id
+    touches: HashMap<u64, Point>,
+    /// Modifier keys currently held, from `ModifiersChanged`
+    modifiers: Modifiers,
+    recorder: Option<EventRecorder>,
+    replay: Option<EventReplay>,
+    replay_start: Instant,
+    proxy: EventLoopProxy<RuntimeEvent>,
+    /// Runs futures from `BuildContext::spawn`
+    #[cfg(feature = "async")]
+    tasks: Option<TaskSpawner>,
+    #[cfg(feature = "tray")]
+    tray: Option<TrayConfig>,
+    #[cfg(feature = "tray")]
+    tray_handle: Option<TrayHandle>,
+    #[cfg(feature = "global-hotkeys")]
+    hotkeys: Option<GlobalHotkeys>,
+    #[cfg(feature = "notifications")]
+    notifications: Notifications,
+    #[cfg(feature = "devtools")]
+    devtools: Option<DevToolsHost>,
+    #[cfg(feature = "accesskit")]
+    accesskit: Option<accesskit_winit::Adapter>,
 }
 
-impl ApplicationHandler for OxideApp {
+impl ApplicationHandler<RuntimeEvent> for OxideApp {
     fn new_events(&mut self, _event_loop: &ActiveEventLoop, _cause: StartCause) {
         self.input.step();
     }
 
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: RuntimeEvent) {
+        match event {
+            RuntimeEvent::Ui(event) => {
+                self.pending_events.push_back(event);
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            RuntimeEvent::Run(task) => {
+                task();
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            RuntimeEvent::Message(message) => {
+                if let Err(unhandled) = self.message_handlers.deliver(message) {
+                    self.pending_events.push_back(unhandled.into_event());
+                }
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            RuntimeEvent::ReducedMotion(reduced) => {
+                if self.apply_reduced_motion(reduced) {
+                    if let Some(window) = &self.window {
+                        window.request_redraw();
+                    }
+                }
+            }
+            #[cfg(feature = "tray")]
+            RuntimeEvent::Tray(event) => self.handle_tray_event(event, _event_loop),
+            #[cfg(feature = "global-hotkeys")]
+            RuntimeEvent::Hotkey(id) => self.handle_hotkey(id),
+            #[cfg(feature = "notifications")]
+            RuntimeEvent::Notification(event) => {
+                self.notifications.handle_event(event);
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            #[cfg(feature = "devtools")]
+            RuntimeEvent::DevTools(request) => {
+                if let Some(devtools) = &self.devtools {
+                    devtools.answer(request, &self.element_tree.read(), self.layout_cache.stats());
+                }
+            }
+            #[cfg(feature = "accesskit")]
+            RuntimeEvent::Accessibility(event) => self.handle_accessibility_event(event),
+        }
+    }
+
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.paused {
+            // Back from the background; the old surface is gone
+            self.paused = false;
+            if let Some(window) = self.window.clone() {
+                self.create_renderer(window, event_loop);
+            }
+            self.notify_lifecycle(AppLifecycle::Resumed);
+            return;
+        }
+
         if self.window.is_none() {
             println!(
                 "🪟 Creating window: \"{}\" ({}x{})",
-                self.title, self.width, self.height
+                self.window_config.title, self.window_config.width, self.window_config.height
             );
 
-            let window_attributes = WindowAttributes::default()
-                .with_title(&self.title)
-                .with_inner_size(LogicalSize::new(self.width, self.height))
-                .with_visible(true)
-                .with_resizable(true)
-                .with_decorations(true)
-                .with_transparent(false);
+            let mut window_attributes = self.window_config.to_attributes();
+            if let Some(state) = self.restore_window_state(event_loop) {
+                println!("🪟 Restoring window state: {}x{} at ({}, {})", state.width, state.height, state.x, state.y);
+                window_attributes = window_attributes
+                    .with_position(PhysicalPosition::new(state.x, state.y))
+                    .with_inner_size(PhysicalSize::new(state.width, state.height))
+                    .with_maximized(state.maximized);
+                self.normal_geometry = Some(state);
+            }
+
+            // Native decorations follow the app's palette; None follows the OS
+            window_attributes = window_attributes.with_theme(match self.theme_mode {
+                ThemeMode::Light => Some(WinitTheme::Light),
+                ThemeMode::Dark => Some(WinitTheme::Dark),
+                ThemeMode::System => None,
+            });
+
+            // AccessKit must be attached before the window is first shown
+            #[cfg(feature = "accesskit")]
+            let visible = std::mem::replace(&mut window_attributes.visible, false);
 
             match event_loop.create_window(window_attributes) {
                 Ok(window) => {
                     println!("✅ Window created successfully");
+                    #[cfg(feature = "accesskit")]
+                    {
+                        self.accesskit =
+                            Some(accesskit_winit::Adapter::with_event_loop_proxy(event_loop, &window, self.proxy.clone()));
+                        window.set_visible(visible);
+                    }
+                    self.system_dark = window.theme() == Some(WinitTheme::Dark);
+                    self.system_colors = crate::platform::system_forced_colors();
+                    self.refresh_theme();
+                    self.refresh_reduced_motion();
                     if let Some(config) = &self.theme_config {
-                        self.theme = Arc::new(Theme::from_config(config, false));
                         println!("🎨 Theme loaded: {}", config.font_sans);
                     }
 
+                    self.scale_factor = window.scale_factor();
+                    TextCache::global().set_scale_factor(self.scale_factor as f32);
+                    println!("🔍 Scale factor: {}", self.scale_factor);
+
                     let window_arc = Arc::new(window);
                     self.window = Some(window_arc.clone());
-
-                    // Create renderer based on backend type
-                    let renderer = match self.backend_type {
-                        BackendType::SkiaOpenGL => {
-                            #[cfg(feature = "skia-opengl")]
-                            {
-                                use crate::render::skia_opengl::SkiaOpenGLRenderer;
-                                match SkiaOpenGLRenderer::new(window_arc.clone(), event_loop) {
-                                    Ok(r) => Ok(Box::new(r) as Box<dyn RenderBackend>),
-                                    Err(e) => Err(e),
-                                }
-                            }
-                            #[cfg(not(feature = "skia-opengl"))]
-                            {
-                                // Fallback when skia-opengl feature is not enabled
-                                Err(anyhow::anyhow!("SkiaOpenGL renderer not available - skia-opengl feature not enabled"))
-                            }
-                        }
-                        BackendType::SkiaCPU => {
-                            use crate::render::skia_cpu::SkiaCPURenderer;
-                            match SkiaCPURenderer::new(window_arc) {
-                                Ok(r) => Ok(Box::new(r) as Box<dyn RenderBackend>),
-                                Err(e) => Err(e),
-                            }
-                        }
-                        BackendType::Softbuffer => {
-                            use crate::render::softbuffer::SoftbufferRenderer;
-                            match SoftbufferRenderer::new(window_arc.clone()) {
-                                Ok(r) => Ok(Box::new(r) as Box<dyn RenderBackend>),
-                                Err(e) => Err(e),
-                            }
-                        }
-                    };
-
-                    match renderer {
-                        Ok(renderer) => {
-                            println!("✅ Renderer ({}) initialized", renderer.name());
-                            self.renderer = Some(renderer);
-                            if let Some(window) = &self.window {
-                                window.request_redraw();
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("❌ Failed to create renderer: {}", e);
-                            event_loop.exit();
-                        }
+                    self.refresh_monitor_refresh_rate();
+                    #[cfg(feature = "tray")]
+                    self.install_tray();
+                    if let Some(recorder) = &mut self.recorder {
+                        recorder.restart_clock();
                     }
+
+                    self.create_renderer(window_arc, event_loop);
                 }
                 Err(e) => {
                     eprintln!("❌ Failed to create window: {}", e);
@@ -196,12 +865,25 @@ impl ApplicationHandler for OxideApp {
         }
     }
 
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        // Android destroys the native surface while in the background
+        println!("⏸️ App paused");
+        self.paused = true;
+        self.renderer = None;
+        self.save_window_state();
+        self.notify_lifecycle(AppLifecycle::Paused);
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
         _window_id: WindowId,
         event: WindowEvent,
     ) {
+        #[cfg(feature = "accesskit")]
+        if let (Some(adapter), Some(window)) = (&mut self.accesskit, &self.window) {
+            adapter.process_event(window, &event);
+        }
         if self.input.process_window_event(&event) {
             self.process_input_events();
             if let Some(window) = &self.window {
@@ -209,16 +891,31 @@ impl ApplicationHandler for OxideApp {
             }
         }
 
+        self.translate_pointer_event(&event);
+        self.translate_keyboard_event(&event);
+        self.translate_ime_event(&event);
+
         match event {
             WindowEvent::CloseRequested => {
                 println!("🛑 Close requested");
-                event_loop.exit();
+                self.close_or_hide(event_loop);
             }
             WindowEvent::RedrawRequested => {
+                if !self.pacer.begin_frame(Instant::now()) {
+                    // Rendered by `pace_frames` once the frame slot comes up
+                    return;
+                }
                 self.rebuild_and_render();
+                self.apply_window_commands(event_loop);
             }
             WindowEvent::Resized(size) => {
                 println!("📐 Window resized to: {}x{}", size.width, size.height);
+                if let Some(recorder) = &mut self.recorder {
+                    if let Err(e) = recorder.record_resize(size.width, size.height) {
+                        eprintln!("❌ Recording error: {}", e);
+                    }
+                }
+                self.track_normal_geometry();
                 if let Some(renderer) = &mut self.renderer {
                     if let Err(e) = renderer.resize(size.width, size.height) {
                         eprintln!("❌ Resize error: {}", e);
@@ -228,6 +925,47 @@ impl ApplicationHandler for OxideApp {
                     window.request_redraw();
                 }
             }
+            WindowEvent::Moved(_) => {
+                self.track_normal_geometry();
+                self.refresh_monitor_refresh_rate();
+            }
+            // Re-read whenever the user comes back, which is when they'd
+            // have changed it
+            WindowEvent::Focused(true) => {
+                let system_colors = crate::platform::system_forced_colors();
+                let colors_changed = system_colors != self.system_colors;
+                self.system_colors = system_colors;
+                if colors_changed {
+                    self.refresh_theme();
+                }
+                self.refresh_reduced_motion();
+                if colors_changed {
+                    if let Some(window) = &self.window {
+                        window.request_redraw();
+                    }
+                }
+            }
+            WindowEvent::ThemeChanged(theme) => {
+                self.system_dark = theme == WinitTheme::Dark;
+                if self.theme_mode == ThemeMode::System {
+                    println!("🌓 System theme changed: {:?}", theme);
+                    self.refresh_theme();
+                    if let Some(window) = &self.window {
+                        window.request_redraw();
+                    }
+                }
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                // Moved to a monitor with a different DPI; the following
+                // Resized event carries the new physical size
+                println!("🔍 Scale factor changed: {}", scale_factor);
+                self.scale_factor = scale_factor;
+                // Text shaped at the old DPI would be drawn blurry or misplaced
+                TextCache::global().set_scale_factor(scale_factor as f32);
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
             WindowEvent::MouseInput {
                 button: MouseButton::Left,
                 state: ElementState::Pressed,
@@ -259,6 +997,15 @@ impl ApplicationHandler for OxideApp {
             return;
         }
 
+        self.advance_replay(event_loop);
+        let long_press = self.event_dispatcher.poll_long_press(&self.element_tree.read());
+        if self.timers.fire_due(Instant::now()) || long_press.is_handled() {
+            if let Some(window) = &self.window {
+                window.request_redraw();
+            }
+        }
+        self.pace_frames(event_loop);
+
         // Request redraw for animation frames
         if let Some(window) = &self.window {
             // Check for any key press using the correct method
@@ -286,6 +1033,12 @@ impl ApplicationHandler for OxideApp {
 
     fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
         println!("👋 Application exiting...");
+        self.save_window_state();
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.flush() {
+                eprintln!("❌ Recording error: {}", e);
+            }
+        }
         if let Some(mut renderer) = self.renderer.take() {
             renderer.cleanup();
         }
@@ -324,7 +1077,706 @@ impl OxideApp {
         }
     }
 
+    /// Apply window commands queued by widgets during dispatch
+    fn apply_window_commands(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if self.window_controller.apply(window) {
+            println!("🛑 Close requested by widget");
+            self.close_or_hide(event_loop);
+        }
+    }
+
+    /// The theme for the current config, mode and OS preference
+    fn resolved_theme(&self) -> Theme {
+        let is_dark = self.theme_mode.resolve(self.system_dark);
+        let default_config;
+        let config = match &self.theme_config {
+            Some(config) => config,
+            None => {
+                default_config = ThemeConfig::default();
+                &default_config
+            }
+        };
+        if self.forced_colors.unwrap_or(self.system_colors.is_some()) {
+            let colors = self.system_colors.unwrap_or_else(|| {
+                if is_dark {
+                    SystemColors::black()
+                } else {
+                    SystemColors::white()
+                }
+            });
+            return Theme::forced_colors(config, &colors);
+        }
+        Theme::from_config(config, is_dark)
+    }
+
+    fn refresh_theme(&mut self) {
+        self.theme_animation = None;
+        self.theme = Arc::new(self.resolved_theme());
+    }
+
+    /// Apply theme changes queued by widgets, starting a cross-fade if enabled
+    fn apply_theme_requests(&mut self) {
+        let requests = self.theme_controller.take_requests();
+        if requests.is_empty() {
+            return;
+        }
+
+        for request in requests {
+            match request {
+                ThemeRequest::SetConfig(config) => self.theme_config = Some(config),
+                ThemeRequest::SetMode(mode) => self.theme_mode = mode,
+                ThemeRequest::ToggleDarkMode => {
+                    self.theme_mode = if self.theme_mode.resolve(self.system_dark) {
+                        ThemeMode::Light
+                    } else {
+                        ThemeMode::Dark
+                    };
+                }
+                ThemeRequest::SetDensity(density) => {
+                    self.theme_config.get_or_insert_with(ThemeConfig::default).density = density;
+                }
+                ThemeRequest::SetForcedColors(forced) => self.forced_colors = Some(forced),
+            }
+        }
+
+        if let Some(window) = &self.window {
+            window.set_theme(match self.theme_mode {
+                ThemeMode::Light => Some(WinitTheme::Light),
+                ThemeMode::Dark => Some(WinitTheme::Dark),
+                ThemeMode::System => None,
+            });
+        }
+
+        let target = self.resolved_theme();
+        println!("🎨 Theme changed (dark: {})", target.is_dark);
+        match self.theme_transition {
+            Some(duration) => {
+                self.theme_animation = Some(
+                    Animation::new((*self.theme).clone(), target, duration).with_curve(EasingCurve::EaseInOut),
+                );
+            }
+            None => self.theme = Arc::new(target),
+        }
+        self.element_tree.write().mark_all_dirty();
+    }
+
+    /// Step the theme cross-fade; keeps frames coming until it finishes
+    fn advance_theme_animation(&mut self) {
+        let Some(animation) = &mut self.theme_animation else {
+            return;
+        };
+        let running = animation.update();
+        self.theme = Arc::new(animation.current_value().clone());
+        if running {
+            if let Some(window) = &self.window {
+                window.request_redraw();
+            }
+        } else {
+            self.theme_animation = None;
+        }
+    }
+
+    /// Exit, or hide to the tray when the tray is set up to keep the app running
+    fn close_or_hide(&mut self, event_loop: &ActiveEventLoop) {
+        #[cfg(feature = "tray")]
+        if self.tray_handle.is_some() && self.tray.as_ref().is_some_and(|t| t.hide_on_close) {
+            if let Some(window) = &self.window {
+                println!("📥 Hiding to tray");
+                window.set_visible(false);
+                return;
+            }
+        }
+        event_loop.exit();
+    }
+
+    #[cfg(feature = "global-hotkeys")]
+    fn handle_hotkey(&mut self, id: HotkeyId) {
+        let Some(action) = self.hotkeys.as_ref().and_then(|h| h.action(id)).cloned() else {
+            return;
+        };
+        let Some(window) = &self.window else {
+            return;
+        };
+        match action {
+            HotkeyAction::ToggleWindow if window.is_visible().unwrap_or(true) && window.has_focus() => {
+                window.set_visible(false);
+            }
+            HotkeyAction::ToggleWindow | HotkeyAction::ShowWindow => {
+                window.set_visible(true);
+                window.set_minimized(false);
+                window.focus_window();
+            }
+            HotkeyAction::Callback(callback) => {
+                callback();
+                window.request_redraw();
+            }
+        }
+    }
+
+    #[cfg(feature = "tray")]
+    fn install_tray(&mut self) {
+        let Some(config) = &self.tray else {
+            return;
+        };
+        let proxy = self.proxy.clone();
+        match tray::install(config, move |event| {
+            let _ = proxy.send_event(RuntimeEvent::Tray(event));
+        }) {
+            Ok(handle) => self.tray_handle = Some(handle),
+            Err(e) => eprintln!("❌ Failed to create tray icon: {:#}", e),
+        }
+    }
+
+    #[cfg(feature = "tray")]
+    fn handle_tray_event(&mut self, event: TrayEvent, event_loop: &ActiveEventLoop) {
+        let Some(config) = &self.tray else {
+            return;
+        };
+        match event {
+            TrayEvent::Activated if config.restore_on_click => {
+                if let Some(window) = &self.window {
+                    window.set_visible(true);
+                    window.set_minimized(false);
+                    window.focus_window();
+                }
+            }
+            TrayEvent::Activated => {}
+            TrayEvent::MenuItem(index) => match config.menu.get(index) {
+                Some(TrayMenuEntry::Item { on_click, .. }) => {
+                    on_click();
+                    if let Some(window) = &self.window {
+                        window.request_redraw();
+                    }
+                }
+                Some(TrayMenuEntry::Quit { .. }) => event_loop.exit(),
+                Some(TrayMenuEntry::Separator) | None => {}
+            },
+        }
+    }
+
+    /// Load the persisted state and fit it to the monitors connected now
+    fn restore_window_state(&self, event_loop: &ActiveEventLoop) -> Option<PersistedWindowState> {
+        let app_id = self.persist_id.as_deref()?;
+        let state = match PersistedWindowState::load(app_id) {
+            Ok(state) => state?,
+            Err(e) => {
+                eprintln!("⚠️ Ignoring saved window state: {:#}", e);
+                return None;
+            }
+        };
+
+        let monitors: Vec<MonitorArea> = event_loop
+            .available_monitors()
+            .map(|monitor| MonitorArea {
+                name: monitor.name(),
+                x: monitor.position().x,
+                y: monitor.position().y,
+                width: monitor.size().width,
+                height: monitor.size().height,
+            })
+            .collect();
+        state.fit_to_monitors(&monitors)
+    }
+
+    /// Remember the windowed geometry so a maximized exit restores correctly
+    fn track_normal_geometry(&mut self) {
+        if self.persist_id.is_none() {
+            return;
+        }
+        let Some(window) = &self.window else {
+            return;
+        };
+        if window.is_maximized() || window.fullscreen().is_some() || window.is_minimized() == Some(true) {
+            return;
+        }
+        let Ok(position) = window.outer_position() else {
+            return;
+        };
+        let size = window.inner_size();
+        self.normal_geometry = Some(PersistedWindowState {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized: false,
+            monitor: window.current_monitor().and_then(|m| m.name()),
+        });
+    }
+
+    fn save_window_state(&mut self) {
+        let (Some(app_id), Some(window)) = (self.persist_id.clone(), self.window.clone()) else {
+            return;
+        };
+        self.track_normal_geometry();
+        let Some(mut state) = self.normal_geometry.clone() else {
+            return;
+        };
+        state.maximized = window.is_maximized();
+        if let Err(e) = state.save(&app_id) {
+            eprintln!("⚠️ Failed to save window state: {:#}", e);
+        }
+    }
+
+    /// Create the rendering backend for `window`
+    fn create_renderer(&mut self, window: Arc<Window>, event_loop: &ActiveEventLoop) {
+        let renderer = match self.backend_type {
+            BackendType::SkiaOpenGL => {
+                #[cfg(feature = "skia-opengl")]
+                {
+                    use crate::render::skia_opengl::SkiaOpenGLRenderer;
+                    match SkiaOpenGLRenderer::new(window.clone(), event_loop) {
+                        Ok(r) => Ok(Box::new(r) as Box<dyn RenderBackend>),
+                        Err(e) => Err(e),
+                    }
+                }
+                #[cfg(not(feature = "skia-opengl"))]
+                {
+                    // Fallback when skia-opengl feature is not enabled
+                    Err(anyhow::anyhow!("SkiaOpenGL renderer not available - skia-opengl feature not enabled"))
+                }
+            }
+            BackendType::SkiaCPU => {
+                #[cfg(feature = "skia")]
+                {
+                    use crate::render::skia_cpu::SkiaCPURenderer;
+                    match SkiaCPURenderer::new(window) {
+                        Ok(r) => Ok(Box::new(r) as Box<dyn RenderBackend>),
+                        Err(e) => Err(e),
+                    }
+                }
+                #[cfg(not(feature = "skia"))]
+                {
+                    Err(anyhow::anyhow!("SkiaCPU renderer not available - skia-cpu feature not enabled"))
+                }
+            }
+            BackendType::Softbuffer => {
+                use crate::render::softbuffer::SoftbufferRenderer;
+                match SoftbufferRenderer::new(window.clone()) {
+                    Ok(r) => Ok(Box::new(r) as Box<dyn RenderBackend>),
+                    Err(e) => Err(e),
+                }
+            }
+        };
+
+        match renderer {
+            Ok(mut renderer) => {
+                println!("✅ Renderer ({}) initialized", renderer.name());
+                renderer.set_clear_color(self.clear_color());
+                self.renderer = Some(renderer);
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to create renderer: {}", e);
+                event_loop.exit();
+            }
+        }
+    }
+
+    fn notify_lifecycle(&self, state: AppLifecycle) {
+        for listener in &self.lifecycle_listeners {
+            listener(state);
+        }
+    }
+
+    /// Queue window pointer input as `UiEvent`s in logical coordinates
+    fn translate_pointer_event(&mut self, event: &WindowEvent) {
+        let ui_event = match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                let logical = position.to_logical::<f32>(self.scale_factor);
+                let position = Point::new(logical.x, logical.y);
+                let delta = Vector2::new(position.x - self.cursor_position.x, position.y - self.cursor_position.y);
+                self.cursor_position = position;
+                // Files dragged over the window follow the pointer between
+                // drop targets
+                if !self.hovered_files.is_empty() {
+                    let paths = self.hovered_files.clone();
+                    self.pending_events.push_back(UiEvent::FileHover { position, paths });
+                }
+                UiEvent::PointerMove { id: 0, position, delta }
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let button = crate::core::event::MouseButton::from(*button);
+                let position = self.cursor_position;
+                match state {
+                    ElementState::Pressed => UiEvent::PointerDown { id: 0, position, button },
+                    ElementState::Released => UiEvent::PointerUp { id: 0, position, button },
+                }
+            }
+            WindowEvent::Touch(touch) => {
+                let logical = touch.location.to_logical::<f32>(self.scale_factor);
+                translate_touch(&mut self.touches, touch.id, touch.phase, Point::new(logical.x, logical.y))
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => Vector2::new(*x * 20.0, *y * 20.0),
+                    MouseScrollDelta::PixelDelta(pixels) => {
+                        let logical = pixels.to_logical::<f32>(self.scale_factor);
+                        Vector2::new(logical.x, logical.y)
+                    }
+                };
+                UiEvent::Scroll { position: self.cursor_position, delta }
+            }
+            // winit reports one event per file; merge them into a single drag
+            WindowEvent::HoveredFile(path) | WindowEvent::DroppedFile(path) => {
+                let dropped = matches!(event, WindowEvent::DroppedFile(_));
+                if dropped {
+                    self.hovered_files.clear();
+                } else {
+                    self.hovered_files.push(path.clone());
+                }
+                match self.pending_events.back_mut() {
+                    Some(UiEvent::FileHover { paths, .. }) if !dropped => paths.push(path.clone()),
+                    Some(UiEvent::FileDrop { paths, .. }) if dropped => paths.push(path.clone()),
+                    _ => {
+                        // The last cursor move may be from before the drag
+                        // entered the window; ask the OS where it is now
+                        let position = self.drag_position();
+                        let paths = vec![path.clone()];
+                        self.pending_events.push_back(if dropped {
+                            UiEvent::FileDrop { position, paths }
+                        } else {
+                            UiEvent::FileHover { position, paths }
+                        });
+                    }
+                }
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+                return;
+            }
+            WindowEvent::HoveredFileCancelled => {
+                self.hovered_files.clear();
+                UiEvent::FileHoverCancelled
+            }
+            WindowEvent::CursorLeft { .. } => UiEvent::PointerLeave,
+            _ => return,
+        };
+        self.pending_events.push_back(ui_event);
+        // Queued events are dispatched at the start of the next frame
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    /// Queue key presses as `KeyDown`/`KeyUp` with both the physical and the
+    /// layout-dependent key, plus `TextInput` for the characters typed
+    fn translate_keyboard_event(&mut self, event: &WindowEvent) {
+        let key_event = match event {
+            WindowEvent::ModifiersChanged(state) => {
+                self.modifiers = state.state().into();
+                self.event_dispatcher.set_modifiers(self.modifiers);
+                return;
+            }
+            WindowEvent::KeyboardInput { event, is_synthetic: false, .. } => event,
+            _ => return,
+        };
+        let PhysicalKey::Code(key) = key_event.physical_key else {
+            return;
+        };
+        let logical_key = key_event.logical_key.clone();
+        let modifiers = self.modifiers;
+
+        match key_event.state {
+            ElementState::Pressed => {
+                self.pending_events.push_back(UiEvent::KeyDown {
+                    key,
+                    logical_key,
+                    modifiers,
+                    repeat: key_event.repeat,
+                });
+                for character in typed_characters(key_event.text.as_deref(), modifiers) {
+                    self.pending_events.push_back(UiEvent::TextInput { character });
+                }
+            }
+            ElementState::Released => {
+                self.pending_events.push_back(UiEvent::KeyUp { key, logical_key, modifiers });
+            }
+        }
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    /// Feed IME composition into the shared manager and queue it for the
+    /// focused text field
+    fn translate_ime_event(&mut self, event: &WindowEvent) {
+        let WindowEvent::Ime(ime) = event else {
+            return;
+        };
+        let ui_event = {
+            let mut manager = self.window_controller.ime();
+            match ime {
+                Ime::Enabled => return,
+                Ime::Preedit(text, cursor) if text.is_empty() => {
+                    manager.cancel_composition();
+                    UiEvent::ImePreedit { text: String::new(), cursor: *cursor }
+                }
+                Ime::Preedit(text, cursor) => {
+                    manager.update_composition(text.clone(), cursor.unwrap_or((text.len(), text.len())));
+                    UiEvent::ImePreedit { text: text.clone(), cursor: *cursor }
+                }
+                Ime::Commit(text) => {
+                    manager.commit_composition();
+                    UiEvent::ImeCommit { text: text.clone() }
+                }
+                Ime::Disabled => {
+                    manager.cancel_composition();
+                    manager.set_active_input(None);
+                    UiEvent::ImePreedit { text: String::new(), cursor: None }
+                }
+            }
+        };
+        self.pending_events.push_back(ui_event);
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    /// Dispatch queued input and events injected through `Runtime::send_event`
+    fn dispatch_pending_events(&mut self) {
+        if self.pending_events.is_empty() {
+            return;
+        }
+
+        let element_tree = self.element_tree.read();
+        while let Some(event) = self.pending_events.pop_front() {
+            if let Some(recorder) = &mut self.recorder {
+                if let Err(e) = recorder.record_event(&event) {
+                    eprintln!("❌ Recording error: {}", e);
+                }
+            }
+            if matches!(event, UiEvent::PointerMove { .. }) {
+                self.window_controller.reset_cursor_request();
+            }
+            self.event_dispatcher.dispatch_event(&event, &element_tree);
+        }
+        drop(element_tree);
+
+        // Rebuilt by this frame's build, which runs next
+        let dirty = self.event_dispatcher.take_dirty_elements();
+        if !dirty.is_empty() {
+            let mut element_tree = self.element_tree.write();
+            for id in dirty {
+                element_tree.mark_dirty(id);
+            }
+        }
+    }
+
+    /// Queue replayed inputs whose time has come and wake up for the next one
+    fn advance_replay(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        // Timing starts once the window exists, matching the recording
+        let Some(window) = &self.window else {
+            self.replay_start = Instant::now();
+            return;
+        };
+
+        let due = replay.due(self.replay_start.elapsed());
+        if !due.is_empty() {
+            for input in due {
+                match input {
+                    RecordedInput::Event(event) => self.pending_events.push_back(event.to_ui_event()),
+                    RecordedInput::Resize { width, height } => {
+                        let _ = window.request_inner_size(PhysicalSize::new(width, height));
+                    }
+                }
+            }
+            window.request_redraw();
+        }
+
+        match replay.next_due() {
+            Some(next) => event_loop.set_control_flow(ControlFlow::WaitUntil(self.replay_start + next)),
+            None => {
+                println!("⏹️ Replay finished");
+                event_loop.set_control_flow(ControlFlow::Wait);
+                self.replay = None;
+            }
+        }
+    }
+
+    /// Render a frame deferred by the frame cap once its slot comes up, and
+    /// sleep until then or the next timer instead of spinning
+    fn pace_frames(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if self.pacer.take_due(Instant::now()) {
+            window.request_redraw();
+        }
+        let wake_at = [
+            self.pacer.deferred_until(),
+            self.timers.next_deadline(),
+            self.event_dispatcher.long_press_deadline(),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        match wake_at {
+            Some(at) => {
+                let wake_earlier = matches!(event_loop.control_flow(), ControlFlow::WaitUntil(other) if other < at);
+                if !wake_earlier {
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(at));
+                }
+            }
+            // A past deadline would wake the loop over and over
+            None if self.replay.is_none() => event_loop.set_control_flow(ControlFlow::Wait),
+            None => {}
+        }
+    }
+
+    /// Pace frames to the refresh rate of the monitor the window is on
+    fn refresh_monitor_refresh_rate(&mut self) {
+        let millihertz = self
+            .window
+            .as_ref()
+            .and_then(|window| window.current_monitor())
+            .and_then(|monitor| monitor.refresh_rate_millihertz());
+        self.pacer.set_monitor_refresh_rate(millihertz);
+    }
+
+    /// Re-read the reduced motion preference. The OS is asked on another
+    /// thread, as that can mean starting a process, and answers with a
+    /// `RuntimeEvent::ReducedMotion`.
+    fn refresh_reduced_motion(&mut self) {
+        if let Some(reduced) = self.forced_reduced_motion {
+            self.apply_reduced_motion(reduced);
+            return;
+        }
+        let proxy = self.proxy.clone();
+        crate::platform::query_reduced_motion(move |reduced| {
+            let _ = proxy.send_event(RuntimeEvent::ReducedMotion(reduced));
+        });
+    }
+
+    /// Use `reduced` as the reduced motion preference; true if it changed
+    fn apply_reduced_motion(&mut self, reduced: bool) -> bool {
+        let reduced = self.forced_reduced_motion.unwrap_or(reduced);
+        crate::animation::set_reduced_motion(reduced);
+        let changed = reduced != self.reduced_motion;
+        if changed {
+            println!("🐢 Reduced motion: {}", reduced);
+        }
+        self.reduced_motion = reduced;
+        changed
+    }
+
+    /// Push the built tree to a listening screen reader, if any
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_tree(&mut self) {
+        let Some(adapter) = &mut self.accesskit else {
+            return;
+        };
+        let tree = self.element_tree.read();
+        let focused = self.event_dispatcher.focused_element();
+        adapter.update_if_active(|| {
+            screen_reader::tree_update(&tree, focused, &self.window_config.title, self.scale_factor)
+        });
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn handle_accessibility_event(&mut self, event: accesskit_winit::Event) {
+        use accesskit::Action;
+        use accesskit_winit::WindowEvent as AccessKitEvent;
+        use crate::core::event::MouseButton as UiMouseButton;
+        use crate::core::FocusOrigin;
+
+        match event.window_event {
+            AccessKitEvent::InitialTreeRequested => {
+                if let Some(adapter) = &mut self.accesskit {
+                    let update = screen_reader::tree_update(
+                        &self.element_tree.read(),
+                        self.event_dispatcher.focused_element(),
+                        &self.window_config.title,
+                        self.scale_factor,
+                    );
+                    adapter.update_if_active(|| update);
+                }
+            }
+            AccessKitEvent::ActionRequested(request) => {
+                let Some(target) = screen_reader::element_id(request.target) else {
+                    return;
+                };
+                let Some(bounds) = self.element_tree.read().get(target).and_then(|element| element.bounds) else {
+                    return;
+                };
+                match request.action {
+                    Action::Focus => self.event_dispatcher.set_focus_with_origin(Some(target), FocusOrigin::Keyboard),
+                    // A click in the middle goes through the same path as the pointer
+                    Action::Click => {
+                        let position = Point::new(bounds.x + bounds.width / 2.0, bounds.y + bounds.height / 2.0);
+                        let button = UiMouseButton::Left;
+                        self.pending_events.push_back(UiEvent::PointerDown { id: 0, position, button });
+                        self.pending_events.push_back(UiEvent::PointerUp { id: 0, position, button });
+                    }
+                    _ => return,
+                }
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
+            AccessKitEvent::AccessibilityDeactivated => {}
+        }
+    }
+
+    /// Pointer position for a file drag, where the OS can tell it
+    fn drag_position(&mut self) -> Point {
+        let reported = self.window.as_deref().and_then(crate::platform::window::pointer_position);
+        if let Some(position) = reported {
+            let logical = position.to_logical::<f32>(self.scale_factor);
+            self.cursor_position = Point::new(logical.x, logical.y);
+        }
+        self.cursor_position
+    }
+
+    /// What the window shows where nothing is drawn: the theme background,
+    /// or nothing in a transparent window
+    fn clear_color(&self) -> Color {
+        if self.window_config.transparent {
+            Color::TRANSPARENT
+        } else {
+            self.theme.background
+        }
+    }
+
+    /// Ring around the focused element when focus came from the keyboard
+    fn focus_ring_render_object(&self) -> Option<RenderObject> {
+        let ring = self.focus_ring.as_ref()?;
+        if !self.event_dispatcher.focus_visible() {
+            return None;
+        }
+        let focused = self.event_dispatcher.focused_element()?;
+        let bounds = self.element_tree.read().get(focused)?.bounds?;
+        Some(ring.render(bounds, &self.theme))
+    }
+
     fn rebuild_and_render(&mut self) {
+        let _span = span!(INFO, "frame");
+        let frame_start = Instant::now();
+        // Dirty flags are cleared by the build, so note them first
+        let dirty_elements = match &self.jank_monitor {
+            Some(_) => self.element_tree.read().collect_dirty(),
+            None => Vec::new(),
+        };
+        self.dispatch_pending_events();
+        self.apply_theme_requests();
+        self.advance_theme_animation();
+        if self.localizer.as_ref().is_some_and(Localizer::take_changed) {
+            self.element_tree.write().mark_all_dirty();
+            self.layout_cache.clear();
+        }
+        // Both read `self`, so they are computed before the renderer is
+        // borrowed mutably below
+        let focus_ring = self.focus_ring_render_object();
+        // Follows theme switches, so dark themes get no white margins
+        let background = self.clear_color();
+
         if let Some(renderer) = &mut self.renderer {
             let size = if let Some(window) = &self.window {
                 window.inner_size()
@@ -332,29 +1784,210 @@ impl OxideApp {
                 return;
             };
 
+            // Widgets lay out in logical pixels; the root transform maps
+            // them onto the physical surface
+            let scale = self.scale_factor as f32;
             let constraints = Constraints::new(
-                0.0, size.width as f32,
-                0.0, size.height as f32
+                0.0, size.width as f32 / scale,
+                0.0, size.height as f32 / scale
             );
 
-            let builder = WidgetBuilder::new(self.theme.clone());
-            let root_render_obj = builder.build_widget_tree(&self.root_widget, constraints);
+            let safe_area = self.window.as_deref().map_or(EdgeInsets::zero(), mobile::safe_area);
+            let mut builder = WidgetBuilder::new(self.theme.clone())
+                .with_element_tree(self.element_tree.clone())
+                .with_window(self.window_controller.clone())
+                .with_theme_controller(self.theme_controller.clone())
+                .with_reduced_motion(self.reduced_motion)
+                .with_parallel(self.parallel_build)
+                .with_layout_cache(self.layout_cache.clone())
+                .with_ui_handle(UiHandle::new(self.proxy.clone()))
+                .with_timers(self.timers.clone())
+                .with_safe_area(EdgeInsets::only(
+                    safe_area.left / scale,
+                    safe_area.top / scale,
+                    safe_area.right / scale,
+                    safe_area.bottom / scale,
+                ));
+            if let Some(stylesheet) = &self.stylesheet {
+                builder = builder.with_stylesheet(stylesheet.clone());
+            }
+            if let Some(styles) = &self.widget_styles {
+                builder = builder.with_widget_styles(styles.clone());
+            }
+            if let Some(localizer) = &self.localizer {
+                builder = builder.with_localizer(localizer.clone());
+            }
+            if let Some(direction) = self.direction {
+                builder = builder.with_direction(direction);
+            }
+            #[cfg(feature = "async")]
+            if let Some(tasks) = &self.tasks {
+                builder = builder.with_task_spawner(tasks.clone());
+            }
+            let build_start = Instant::now();
+            self.layout_cache.begin_frame(&self.theme);
+            let mut root_render_obj = builder.build_widget_tree(&self.root_widget, constraints);
+            self.layout_cache.end_frame();
+            self.accessibility.sync_from_tree(&self.element_tree.read());
+            // Work started by elements that are gone has nobody to report to
+            let unmounted = self.element_tree.write().take_unmounted();
+            let tree_id = self.element_tree.read().id();
+            for id in &unmounted {
+                self.timers.cancel_owned_by(*id);
+                release_subscriptions(tree_id, *id);
+            }
+            self.event_dispatcher.update_focusables(&self.element_tree.read(), &unmounted);
+            self.window_controller.place_ime_caret(&self.element_tree.read());
+            #[cfg(feature = "accesskit")]
+            self.update_accesskit_tree();
+            #[cfg(feature = "async")]
+            if let Some(tasks) = &self.tasks {
+                for id in &unmounted {
+                    tasks.cancel_owned_by(*id);
+                }
+            }
+            if let Some(ring) = focus_ring {
+                root_render_obj = RenderObject::group(vec![root_render_obj, ring]);
+            }
+            if scale != 1.0 {
+                root_render_obj = RenderObject::transform(Matrix::scale(scale, scale), root_render_obj);
+            }
 
             println!("🎨 Rendering frame with constraints: {:?}", constraints);
 
-            if let Err(e) = renderer.draw_render_object(&root_render_obj, size.width, size.height) {
+            renderer.set_clear_color(background);
+            let paint_start = Instant::now();
+            let drawn = {
+                let _span = span!(INFO, "paint", width = size.width, height = size.height);
+                renderer.draw_render_object(&root_render_obj, size.width, size.height)
+            };
+            if let Err(e) = drawn {
                 eprintln!("❌ Draw error: {}", e);
                 return;
             }
 
-            if let Err(e) = renderer.present() {
+            let previous = self.window_controller.store_frame(PresentedFrame {
+                root: root_render_obj,
+                #[cfg(feature = "skia")]
+                width: size.width,
+                #[cfg(feature = "skia")]
+                height: size.height,
+                #[cfg(feature = "skia")]
+                scale_factor: scale,
+                #[cfg(feature = "skia")]
+                background,
+            });
+            // The next build reuses the old tree's allocations
+            if let Some(previous) = previous {
+                previous.recycle();
+            }
+
+            let present_start = Instant::now();
+            let presented = {
+                let _span = span!(INFO, "present");
+                renderer.present()
+            };
+            if let Err(e) = presented {
                 eprintln!("❌ Present error: {}", e);
                 return;
             }
 
+            let timings = FrameTimings {
+                events: build_start - frame_start,
+                build: paint_start - build_start,
+                paint: present_start - paint_start,
+                present: present_start.elapsed(),
+            };
+            #[cfg(feature = "devtools")]
+            if let Some(devtools) = &mut self.devtools {
+                devtools.record_frame(timings);
+            }
+            if let Some(monitor) = &mut self.jank_monitor {
+                monitor.record(timings, dirty_elements);
+            }
+
             if let Some(window) = &self.window {
                 window.pre_present_notify();
             }
         }
     }
-}
\ No newline at end of file
+}
+/// A touch as a pointer event. Each finger moves from its own last
+/// position; ids are offset by one so they never collide with the mouse.
+/// A cancelled touch (the OS took over the gesture) releases without a
+/// click.
+fn translate_touch(touches: &mut HashMap<u64, Point>, id: u64, phase: TouchPhase, position: Point) -> UiEvent {
+    let last = touches.insert(id, position).unwrap_or(position);
+    let button = crate::core::event::MouseButton::Left;
+    let event_id = id + 1;
+    match phase {
+        TouchPhase::Started => UiEvent::PointerDown { id: event_id, position, button },
+        TouchPhase::Moved => {
+            let delta = Vector2::new(position.x - last.x, position.y - last.y);
+            UiEvent::PointerMove { id: event_id, position, delta }
+        }
+        TouchPhase::Ended => {
+            touches.remove(&id);
+            UiEvent::PointerUp { id: event_id, position, button }
+        }
+        TouchPhase::Cancelled => {
+            touches.remove(&id);
+            UiEvent::PointerCancel { id: event_id, position }
+        }
+    }
+}
+
+/// The characters a key press typed, as the layout produced them. AltGr
+/// arrives as Ctrl+Alt on Windows, so Ctrl alone doesn't mean a shortcut;
+/// shortcuts produce control characters or nothing, except Cmd on macOS,
+/// which reports the plain letter.
+fn typed_characters(text: Option<&str>, modifiers: Modifiers) -> impl Iterator<Item = char> + '_ {
+    text.filter(|_| !modifiers.meta)
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_typed_with_altgr_is_kept() {
+        let altgr = Modifiers { ctrl: true, alt: true, ..Default::default() };
+        assert_eq!(typed_characters(Some("@"), altgr).collect::<String>(), "@");
+        // Ctrl+C on Windows and Linux, Cmd+C on macOS
+        let ctrl = Modifiers { ctrl: true, ..Default::default() };
+        assert_eq!(typed_characters(Some("\u{3}"), ctrl).count(), 0);
+        let cmd = Modifiers { meta: true, ..Default::default() };
+        assert_eq!(typed_characters(Some("c"), cmd).count(), 0);
+        assert_eq!(typed_characters(Some("é"), Modifiers::default()).collect::<String>(), "é");
+    }
+
+    #[test]
+    fn test_touches_move_independently_and_cancel_without_release() {
+        let mut touches = HashMap::new();
+        translate_touch(&mut touches, 0, TouchPhase::Started, Point::new(10.0, 10.0));
+        translate_touch(&mut touches, 1, TouchPhase::Started, Point::new(100.0, 100.0));
+
+        // Each finger's delta is from its own last position
+        match translate_touch(&mut touches, 0, TouchPhase::Moved, Point::new(15.0, 10.0)) {
+            UiEvent::PointerMove { id, delta, .. } => assert_eq!((id, delta.x, delta.y), (1, 5.0, 0.0)),
+            other => panic!("expected a move, got {:?}", other),
+        }
+        match translate_touch(&mut touches, 1, TouchPhase::Moved, Point::new(100.0, 90.0)) {
+            UiEvent::PointerMove { id, delta, .. } => assert_eq!((id, delta.x, delta.y), (2, 0.0, -10.0)),
+            other => panic!("expected a move, got {:?}", other),
+        }
+
+        assert!(matches!(
+            translate_touch(&mut touches, 0, TouchPhase::Cancelled, Point::new(15.0, 10.0)),
+            UiEvent::PointerCancel { id: 1, .. }
+        ));
+        assert!(matches!(
+            translate_touch(&mut touches, 1, TouchPhase::Ended, Point::new(100.0, 90.0)),
+            UiEvent::PointerUp { id: 2, .. }
+        ));
+        assert!(touches.is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+//! Rendering widgets to images without a window
+//!
+//! `OffscreenRenderer` builds a widget tree the same way the runtime does
+//! and rasterizes it on the Skia CPU path, so it works on headless servers
+//! and in doc examples:
+//!
+//! ```no_run
+//! use oxideui::runtime::OffscreenRenderer;
+//! use oxideui::{Constraints, Size, Text, Theme};
+//!
+//! let image = OffscreenRenderer::new(Theme::default())
+//!     .render(Box::new(Text::new("Hello")), Constraints::tight(Size::new(200.0, 50.0)))
+//!     .unwrap();
+//! image.save_png("hello.png").unwrap();
+//! ```
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+
+use super::widget_builder::WidgetBuilder;
+use crate::core::render_object::{Color, Matrix, RenderObject};
+use crate::core::{Theme, Widget};
+use crate::layout::Constraints;
+use crate::render::{rasterize, RgbaImage};
+use crate::theming::{Stylesheet, WidgetStyles};
+
+pub struct OffscreenRenderer {
+    theme: Arc<Theme>,
+    scale_factor: f32,
+    background: Option<Color>,
+    stylesheet: Option<Arc<Stylesheet>>,
+    widget_styles: Option<Arc<WidgetStyles>>,
+}
+
+impl OffscreenRenderer {
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            theme: Arc::new(theme),
+            scale_factor: 1.0,
+            background: None,
+            stylesheet: None,
+            widget_styles: None,
+        }
+    }
+
+    /// Physical pixels per logical pixel in the output (2.0 for retina-sized images)
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Fill color behind the widgets; defaults to the theme background
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn with_stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheet = Some(Arc::new(stylesheet));
+        self
+    }
+
+    pub fn with_widget_styles(mut self, styles: WidgetStyles) -> Self {
+        self.widget_styles = Some(Arc::new(styles));
+        self
+    }
+
+    /// Build `widget` under `constraints` and rasterize it.
+    ///
+    /// The image covers the constraints' maximum size, which must be finite.
+    pub fn render(&self, widget: Box<dyn Widget>, constraints: Constraints) -> Result<RgbaImage> {
+        if !constraints.max_width.is_finite() || !constraints.max_height.is_finite() {
+            bail!("Offscreen rendering needs bounded constraints, got {:?}", constraints);
+        }
+
+        let mut builder = WidgetBuilder::new(self.theme.clone());
+        if let Some(stylesheet) = &self.stylesheet {
+            builder = builder.with_stylesheet(stylesheet.clone());
+        }
+        if let Some(styles) = &self.widget_styles {
+            builder = builder.with_widget_styles(styles.clone());
+        }
+        let mut root = builder.build_widget_tree(&widget, constraints);
+        if self.scale_factor != 1.0 {
+            root = RenderObject::transform(Matrix::scale(self.scale_factor, self.scale_factor), root);
+        }
+
+        let width = (constraints.max_width * self.scale_factor).ceil() as u32;
+        let height = (constraints.max_height * self.scale_factor).ceil() as u32;
+        rasterize(&root, width, height, self.background.unwrap_or(self.theme.background))
+    }
+}
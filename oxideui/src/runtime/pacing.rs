@@ -0,0 +1,101 @@
+use std::time::Duration;
+use web_time::Instant;
+
+/// Frame rate used when neither the app nor the monitor says otherwise
+const DEFAULT_FPS: u32 = 60;
+
+/// Spaces frames out to a maximum rate.
+///
+/// Redraw requests arriving sooner than one frame interval after the last
+/// frame are deferred and coalesced, so a blinking cursor or a burst of
+/// pointer moves renders at most once per interval instead of as fast as
+/// the event loop spins.
+pub(crate) struct FramePacer {
+    /// Set by `Runtime::with_max_fps`; takes precedence over the monitor
+    max_fps: Option<u32>,
+    /// Refresh rate of the window's current monitor, in millihertz
+    monitor_millihertz: Option<u32>,
+    last_frame: Option<Instant>,
+    /// A redraw was requested too early and still has to be rendered
+    deferred: bool,
+}
+
+impl FramePacer {
+    pub(crate) fn new(max_fps: Option<u32>) -> Self {
+        Self {
+            max_fps,
+            monitor_millihertz: None,
+            last_frame: None,
+            deferred: false,
+        }
+    }
+
+    pub(crate) fn set_monitor_refresh_rate(&mut self, millihertz: Option<u32>) {
+        self.monitor_millihertz = millihertz.filter(|&mhz| mhz > 0);
+    }
+
+    pub(crate) fn frame_interval(&self) -> Duration {
+        match (self.max_fps, self.monitor_millihertz) {
+            (Some(fps), _) => Duration::from_secs_f64(1.0 / fps.max(1) as f64),
+            (None, Some(mhz)) => Duration::from_secs_f64(1000.0 / mhz as f64),
+            (None, None) => Duration::from_secs_f64(1.0 / DEFAULT_FPS as f64),
+        }
+    }
+
+    /// Whether a frame requested at `now` may render. Otherwise it is
+    /// deferred until `deferred_until`.
+    pub(crate) fn begin_frame(&mut self, now: Instant) -> bool {
+        if let Some(last) = self.last_frame {
+            if now < last + self.frame_interval() {
+                self.deferred = true;
+                return false;
+            }
+        }
+        self.last_frame = Some(now);
+        self.deferred = false;
+        true
+    }
+
+    /// When the deferred frame may render, if one is waiting
+    pub(crate) fn deferred_until(&self) -> Option<Instant> {
+        let last = self.last_frame?;
+        self.deferred.then(|| last + self.frame_interval())
+    }
+
+    /// Whether the deferred frame's slot has come; the caller requests a redraw
+    pub(crate) fn take_due(&mut self, now: Instant) -> bool {
+        match self.deferred_until() {
+            Some(at) if now >= at => {
+                self.deferred = false;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redraws_are_paced() {
+        let mut pacer = FramePacer::new(Some(50));
+        let start = Instant::now();
+        assert!(pacer.begin_frame(start));
+
+        // Two requests within the 20ms interval collapse into one deferred frame
+        assert!(!pacer.begin_frame(start + Duration::from_millis(5)));
+        assert!(!pacer.begin_frame(start + Duration::from_millis(8)));
+        assert_eq!(pacer.deferred_until(), Some(start + Duration::from_millis(20)));
+        assert!(!pacer.take_due(start + Duration::from_millis(19)));
+        assert!(pacer.take_due(start + Duration::from_millis(20)));
+        assert!(!pacer.take_due(start + Duration::from_millis(21)));
+        assert!(pacer.begin_frame(start + Duration::from_millis(21)));
+
+        // Without an explicit cap the monitor decides
+        let mut pacer = FramePacer::new(None);
+        pacer.set_monitor_refresh_rate(Some(120_000));
+        assert_eq!(pacer.frame_interval(), Duration::from_secs_f64(1.0 / 120.0));
+    }
+}
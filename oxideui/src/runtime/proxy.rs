@@ -0,0 +1,123 @@
+//! Typed messages from other threads
+//!
+//! ```rust,no_run
+//! use oxideui::widgets::basic::Text;
+//!
+//! struct DownloadFinished(String);
+//!
+//! let runtime = oxideui::new(Text::new("Downloading...")).with_message_handler(|done: DownloadFinished| {
+//!     println!("Saved {}", done.0);
+//! });
+//! let ui = runtime.ui_handle();
+//! std::thread::spawn(move || {
+//!     let _ = ui.send(DownloadFinished("report.pdf".into()));
+//! });
+//! // runtime.run().await
+//! ```
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+
+use crate::core::event::UiEvent;
+use crate::runtime::UiHandle;
+
+/// A typed message on its way to the UI thread
+pub(crate) struct Message {
+    type_id: TypeId,
+    name: &'static str,
+    payload: Box<dyn Any + Send + Sync>,
+}
+
+impl Message {
+    pub(crate) fn new<M: Any + Send + Sync>(message: M) -> Self {
+        Self {
+            type_id: TypeId::of::<M>(),
+            name: type_name::<M>(),
+            payload: Box::new(message),
+        }
+    }
+
+    /// `UiEvent::Custom` named after the message type, for messages without a handler
+    pub(crate) fn into_event(self) -> UiEvent {
+        UiEvent::Custom {
+            name: self.name.to_string(),
+            data: self.payload,
+        }
+    }
+}
+
+/// The handle non-UI threads send typed messages through; messages go to
+/// the handler registered for their type with `Runtime::with_message_handler`,
+/// or else to widgets as a `UiEvent::Custom` named after the type
+pub type RuntimeProxy = UiHandle;
+
+type MessageHandler = Box<dyn Fn(Box<dyn Any + Send + Sync>)>;
+
+/// Handlers registered with `Runtime::with_message_handler`, by message type
+#[derive(Default)]
+pub(crate) struct MessageHandlers {
+    handlers: HashMap<TypeId, MessageHandler>,
+}
+
+impl MessageHandlers {
+    /// Replaces any earlier handler for `M`
+    pub(crate) fn insert<M, F>(&mut self, handler: F)
+    where
+        M: Any + Send + Sync,
+        F: Fn(M) + 'static,
+    {
+        self.handlers.insert(
+            TypeId::of::<M>(),
+            Box::new(move |payload| {
+                if let Ok(message) = payload.downcast::<M>() {
+                    handler(*message);
+                }
+            }),
+        );
+    }
+
+    /// Run the handler for `message`, or hand it back if there is none
+    pub(crate) fn deliver(&self, message: Message) -> Result<(), Message> {
+        match self.handlers.get(&message.type_id) {
+            Some(handler) => {
+                handler(message.payload);
+                Ok(())
+            }
+            None => Err(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Progress(u32);
+
+    #[test]
+    fn test_messages_route_by_type() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = received.clone();
+        let mut handlers = MessageHandlers::default();
+        handlers.insert(move |progress: Progress| sink.borrow_mut().push(progress.0));
+
+        assert!(handlers.deliver(Message::new(Progress(40))).is_ok());
+        assert_eq!(*received.borrow(), vec![40]);
+
+        // Unhandled messages become custom events carrying the message,
+        // named the same for every message of a type
+        let name = |message: Message| match message.into_event() {
+            UiEvent::Custom { name, .. } => name,
+            _ => unreachable!(),
+        };
+        let unhandled = handlers.deliver(Message::new(String::from("done"))).err().unwrap();
+        let UiEvent::Custom { name: done, data } = unhandled.into_event() else {
+            unreachable!()
+        };
+        assert_eq!(data.downcast_ref::<String>().map(String::as_str), Some("done"));
+        assert_eq!(name(Message::new(String::from("later"))), done);
+        assert_ne!(name(Message::new(Progress(1))), done);
+    }
+}
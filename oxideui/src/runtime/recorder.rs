@@ -0,0 +1,335 @@
+//! Event recording and replay
+//!
+//! `EventRecorder` writes every event that reaches the dispatcher, plus
+//! window resizes, as timestamped JSON lines. `EventReplay` reads such a
+//! file back and hands the entries out in order as their time comes up, so
+//! a user-reported session can be reproduced or run as a regression test.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use web_time::Instant;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use winit::keyboard::{Key, KeyCode, NativeKey};
+
+use crate::core::event::{Modifiers, MouseButton, UiEvent, Vector2};
+use crate::core::render_object::Point;
+
+/// Serializable mirror of `UiEvent`.
+///
+/// Custom events keep their name only; their payload is not serializable
+/// and is replayed as `()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    PointerDown { id: u64, position: Point, button: MouseButton },
+    PointerUp { id: u64, position: Point, button: MouseButton },
+    PointerMove { id: u64, position: Point, delta: Vector2 },
+    PointerCancel { id: u64, position: Point },
+    Scroll { position: Point, delta: Vector2 },
+    PointerEnter,
+    PointerLeave,
+    LongPress { position: Point },
+    DoubleClick { position: Point },
+    KeyDown {
+        key: KeyCode,
+        #[serde(default = "unidentified_key")]
+        logical_key: Key,
+        modifiers: Modifiers,
+        repeat: bool,
+    },
+    KeyUp {
+        key: KeyCode,
+        #[serde(default = "unidentified_key")]
+        logical_key: Key,
+        modifiers: Modifiers,
+    },
+    TextInput { character: char },
+    ImePreedit { text: String, cursor: Option<(usize, usize)> },
+    ImeCommit { text: String },
+    Focus,
+    Blur,
+    FileHover { position: Point, paths: Vec<PathBuf> },
+    FileDrop { position: Point, paths: Vec<PathBuf> },
+    FileHoverCancelled,
+    Custom { name: String },
+}
+
+/// Recordings made before logical keys were captured replay without one
+fn unidentified_key() -> Key {
+    Key::Unidentified(NativeKey::Unidentified)
+}
+
+impl RecordedEvent {
+    pub fn from_ui_event(event: &UiEvent) -> Self {
+        match event {
+            UiEvent::PointerDown { id, position, button } => RecordedEvent::PointerDown {
+                id: *id,
+                position: *position,
+                button: *button,
+            },
+            UiEvent::PointerUp { id, position, button } => RecordedEvent::PointerUp {
+                id: *id,
+                position: *position,
+                button: *button,
+            },
+            UiEvent::PointerMove { id, position, delta } => RecordedEvent::PointerMove {
+                id: *id,
+                position: *position,
+                delta: *delta,
+            },
+            UiEvent::PointerCancel { id, position } => RecordedEvent::PointerCancel {
+                id: *id,
+                position: *position,
+            },
+            UiEvent::Scroll { position, delta } => RecordedEvent::Scroll {
+                position: *position,
+                delta: *delta,
+            },
+            UiEvent::PointerEnter => RecordedEvent::PointerEnter,
+            UiEvent::PointerLeave => RecordedEvent::PointerLeave,
+            UiEvent::LongPress { position } => RecordedEvent::LongPress { position: *position },
+            UiEvent::DoubleClick { position } => RecordedEvent::DoubleClick { position: *position },
+            UiEvent::KeyDown { key, logical_key, modifiers, repeat } => RecordedEvent::KeyDown {
+                key: *key,
+                logical_key: logical_key.clone(),
+                modifiers: *modifiers,
+                repeat: *repeat,
+            },
+            UiEvent::KeyUp { key, logical_key, modifiers } => RecordedEvent::KeyUp {
+                key: *key,
+                logical_key: logical_key.clone(),
+                modifiers: *modifiers,
+            },
+            UiEvent::TextInput { character } => RecordedEvent::TextInput { character: *character },
+            UiEvent::ImePreedit { text, cursor } => RecordedEvent::ImePreedit {
+                text: text.clone(),
+                cursor: *cursor,
+            },
+            UiEvent::ImeCommit { text } => RecordedEvent::ImeCommit { text: text.clone() },
+            UiEvent::Focus => RecordedEvent::Focus,
+            UiEvent::Blur => RecordedEvent::Blur,
+            UiEvent::FileHover { position, paths } => RecordedEvent::FileHover {
+                position: *position,
+                paths: paths.clone(),
+            },
+            UiEvent::FileDrop { position, paths } => RecordedEvent::FileDrop {
+                position: *position,
+                paths: paths.clone(),
+            },
+            UiEvent::FileHoverCancelled => RecordedEvent::FileHoverCancelled,
+            UiEvent::Custom { name, .. } => RecordedEvent::Custom { name: name.clone() },
+        }
+    }
+
+    pub fn to_ui_event(&self) -> UiEvent {
+        match self.clone() {
+            RecordedEvent::PointerDown { id, position, button } => UiEvent::PointerDown { id, position, button },
+            RecordedEvent::PointerUp { id, position, button } => UiEvent::PointerUp { id, position, button },
+            RecordedEvent::PointerMove { id, position, delta } => UiEvent::PointerMove { id, position, delta },
+            RecordedEvent::PointerCancel { id, position } => UiEvent::PointerCancel { id, position },
+            RecordedEvent::Scroll { position, delta } => UiEvent::Scroll { position, delta },
+            RecordedEvent::PointerEnter => UiEvent::PointerEnter,
+            RecordedEvent::PointerLeave => UiEvent::PointerLeave,
+            RecordedEvent::LongPress { position } => UiEvent::LongPress { position },
+            RecordedEvent::DoubleClick { position } => UiEvent::DoubleClick { position },
+            RecordedEvent::KeyDown { key, logical_key, modifiers, repeat } => UiEvent::KeyDown {
+                key,
+                logical_key,
+                modifiers,
+                repeat,
+            },
+            RecordedEvent::KeyUp { key, logical_key, modifiers } => UiEvent::KeyUp { key, logical_key, modifiers },
+            RecordedEvent::TextInput { character } => UiEvent::TextInput { character },
+            RecordedEvent::ImePreedit { text, cursor } => UiEvent::ImePreedit { text, cursor },
+            RecordedEvent::ImeCommit { text } => UiEvent::ImeCommit { text },
+            RecordedEvent::Focus => UiEvent::Focus,
+            RecordedEvent::Blur => UiEvent::Blur,
+            RecordedEvent::FileHover { position, paths } => UiEvent::FileHover { position, paths },
+            RecordedEvent::FileDrop { position, paths } => UiEvent::FileDrop { position, paths },
+            RecordedEvent::FileHoverCancelled => UiEvent::FileHoverCancelled,
+            RecordedEvent::Custom { name } => UiEvent::Custom { name, data: Box::new(()) },
+        }
+    }
+}
+
+/// One recorded input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedInput {
+    Event(RecordedEvent),
+    Resize { width: u32, height: u32 },
+}
+
+/// A recorded input and when it happened, relative to the start of recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEntry {
+    pub at_ms: u64,
+    pub input: RecordedInput,
+}
+
+/// Writes timestamped inputs as JSON lines
+pub struct EventRecorder {
+    start: Instant,
+    writer: Box<dyn Write + Send>,
+}
+
+impl EventRecorder {
+    /// Record to a file, truncating it
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).with_context(|| format!("Failed to create recording {}", path.display()))?;
+        Ok(Self::from_writer(BufWriter::new(file)))
+    }
+
+    pub fn from_writer<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            start: Instant::now(),
+            writer: Box::new(writer),
+        }
+    }
+
+    /// Reset the clock, e.g. once the window is shown
+    pub fn restart_clock(&mut self) {
+        self.start = Instant::now();
+    }
+
+    pub fn record_event(&mut self, event: &UiEvent) -> Result<()> {
+        self.record(RecordedInput::Event(RecordedEvent::from_ui_event(event)))
+    }
+
+    pub fn record_resize(&mut self, width: u32, height: u32) -> Result<()> {
+        self.record(RecordedInput::Resize { width, height })
+    }
+
+    fn record(&mut self, input: RecordedInput) -> Result<()> {
+        let entry = RecordedEntry {
+            at_ms: self.start.elapsed().as_millis() as u64,
+            input,
+        };
+        serde_json::to_writer(&mut self.writer, &entry)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().context("Failed to flush recording")
+    }
+}
+
+/// Feeds a recording back in timestamp order
+pub struct EventReplay {
+    entries: VecDeque<RecordedEntry>,
+}
+
+impl EventReplay {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).with_context(|| format!("Failed to open recording {}", path.display()))?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: RecordedEntry =
+                serde_json::from_str(&line).with_context(|| format!("Invalid recording entry on line {}", index + 1))?;
+            entries.push(entry);
+        }
+        // Stable sort keeps same-millisecond inputs in recorded order
+        entries.sort_by_key(|entry| entry.at_ms);
+        Ok(Self { entries: entries.into() })
+    }
+
+    /// Remove and return every input due at or before `elapsed`
+    pub fn due(&mut self, elapsed: Duration) -> Vec<RecordedInput> {
+        let elapsed_ms = elapsed.as_millis() as u64;
+        let mut due = Vec::new();
+        while self.entries.front().is_some_and(|entry| entry.at_ms <= elapsed_ms) {
+            if let Some(entry) = self.entries.pop_front() {
+                due.push(entry.input);
+            }
+        }
+        due
+    }
+
+    /// Time of the next pending input
+    pub fn next_due(&self) -> Option<Duration> {
+        self.entries.front().map(|entry| Duration::from_millis(entry.at_ms))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay_round_trip() {
+        let buffer = SharedBuffer::default();
+        let mut recorder = EventRecorder::from_writer(buffer.clone());
+        recorder
+            .record_event(&UiEvent::PointerDown {
+                id: 0,
+                position: Point::new(10.0, 20.0),
+                button: MouseButton::Left,
+            })
+            .unwrap();
+        recorder.record_resize(640, 480).unwrap();
+        recorder.record_event(&UiEvent::TextInput { character: 'x' }).unwrap();
+
+        let bytes = buffer.0.lock().unwrap().clone();
+        let mut replay = EventReplay::from_reader(Cursor::new(bytes)).unwrap();
+
+        let inputs = replay.due(Duration::from_secs(60));
+        assert!(replay.is_finished());
+        assert_eq!(
+            inputs,
+            vec![
+                RecordedInput::Event(RecordedEvent::PointerDown {
+                    id: 0,
+                    position: Point::new(10.0, 20.0),
+                    button: MouseButton::Left,
+                }),
+                RecordedInput::Resize { width: 640, height: 480 },
+                RecordedInput::Event(RecordedEvent::TextInput { character: 'x' }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_holds_back_future_entries() {
+        let data = r#"{"at_ms":0,"input":{"Event":"Focus"}}
+{"at_ms":500,"input":{"Resize":{"width":800,"height":600}}}"#;
+        let mut replay = EventReplay::from_reader(Cursor::new(data)).unwrap();
+
+        assert_eq!(replay.due(Duration::from_millis(100)).len(), 1);
+        assert_eq!(replay.next_due(), Some(Duration::from_millis(500)));
+        assert_eq!(replay.due(Duration::from_millis(500)).len(), 1);
+        assert!(replay.is_finished());
+    }
+}
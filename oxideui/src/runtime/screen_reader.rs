@@ -0,0 +1,132 @@
+//! Screen reader support through AccessKit
+//!
+//! With the `accesskit` feature, the element tree is exported to the
+//! platform accessibility API (UI Automation, NSAccessibility, AT-SPI)
+//! after each build. Every element becomes a node carrying the role, label,
+//! value and live region from its `Semantics`, and its layout bounds. Focus
+//! and click requests from assistive technology come back to the runtime
+//! as focus changes and clicks at the element's center.
+
+use accesskit::{Action, Live, Node, NodeId, Rect, Role, Tree, TreeUpdate};
+
+use crate::core::element::{ElementId, ElementTree};
+use crate::core::{AccessibilityRole, LiveRegion};
+
+/// The window, parent of the root element
+const WINDOW_ID: NodeId = NodeId(u64::MAX);
+
+fn node_id(id: ElementId) -> NodeId {
+    NodeId(id.as_u64())
+}
+
+/// The element an action request is aimed at
+pub(crate) fn element_id(node: NodeId) -> Option<ElementId> {
+    (node != WINDOW_ID).then(|| ElementId::new(node.0))
+}
+
+fn role(role: AccessibilityRole) -> Role {
+    match role {
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::Text => Role::Label,
+        AccessibilityRole::TextField => Role::TextInput,
+        AccessibilityRole::Image => Role::Image,
+        AccessibilityRole::Link => Role::Link,
+        AccessibilityRole::Checkbox => Role::CheckBox,
+        AccessibilityRole::RadioButton => Role::RadioButton,
+        AccessibilityRole::Slider => Role::Slider,
+        AccessibilityRole::List => Role::List,
+        AccessibilityRole::ListItem => Role::ListItem,
+        AccessibilityRole::Heading => Role::Heading,
+        AccessibilityRole::Table => Role::Table,
+    }
+}
+
+/// The whole tree as one update; `scale` maps layout bounds to the
+/// physical pixels AccessKit expects
+pub(crate) fn tree_update(tree: &ElementTree, focus: Option<ElementId>, title: &str, scale: f64) -> TreeUpdate {
+    let mut nodes = Vec::with_capacity(tree.len() + 1);
+    let mut window = Node::new(Role::Window);
+    window.set_label(title);
+    window.set_children(tree.root().map(node_id).into_iter().collect::<Vec<_>>());
+    nodes.push((WINDOW_ID, window));
+
+    for element in tree.iter() {
+        let semantics = element.semantics.clone().unwrap_or_default();
+        let mut node = Node::new(semantics.role.map_or(Role::GenericContainer, role));
+        if let Some(label) = semantics.label {
+            node.set_label(label);
+        }
+        if let Some(value) = semantics.value {
+            node.set_value(value);
+        }
+        if let Some(hint) = semantics.hint {
+            node.set_description(hint);
+        }
+        match semantics.live_region {
+            Some(LiveRegion::Polite) => node.set_live(Live::Polite),
+            Some(LiveRegion::Assertive) => node.set_live(Live::Assertive),
+            None => {}
+        }
+        if let Some(bounds) = element.bounds {
+            let (x, y) = (bounds.x as f64 * scale, bounds.y as f64 * scale);
+            node.set_bounds(Rect::new(x, y, x + bounds.width as f64 * scale, y + bounds.height as f64 * scale));
+        }
+        if element.widget.as_ref().is_some_and(|widget| widget.is_focusable()) {
+            node.add_action(Action::Focus);
+        }
+        if matches!(
+            semantics.role,
+            Some(AccessibilityRole::Button | AccessibilityRole::Link | AccessibilityRole::Checkbox | AccessibilityRole::RadioButton)
+        ) {
+            node.add_action(Action::Click);
+        }
+        node.set_children(element.children.iter().copied().map(node_id).collect::<Vec<_>>());
+        nodes.push((node_id(element.id), node));
+    }
+
+    TreeUpdate {
+        nodes,
+        tree: Some(Tree::new(WINDOW_ID)),
+        focus: focus.filter(|id| tree.get(*id).is_some()).map_or(WINDOW_ID, node_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::widget::Widget;
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use crate::runtime::widget_builder::WidgetBuilder;
+    use crate::widgets::basic::{Column, Text};
+    use crate::widgets::Button;
+
+    #[test]
+    fn test_built_elements_become_nodes() {
+        let root: Box<dyn Widget> = Box::new(
+            Column::new().with_children(vec![Box::new(Text::new("Title")), Box::new(Button::new("Save"))]),
+        );
+        let tree = new_shared_element_tree();
+        WidgetBuilder::new(Arc::new(Theme::default()))
+            .with_element_tree(tree.clone())
+            .build_widget_tree(&root, Constraints::new(0.0, 400.0, 0.0, 300.0));
+        let tree = tree.read();
+
+        let update = tree_update(&tree, None, "Editor", 2.0);
+        assert_eq!(update.nodes.len(), tree.len() + 1);
+        assert_eq!(update.focus, WINDOW_ID);
+        let (_, button) = update
+            .nodes
+            .iter()
+            .find(|(_, node)| node.role() == Role::Button)
+            .expect("the button is exported");
+        assert_eq!(button.label(), Some("Save"));
+        assert!(button.supports_action(Action::Focus) && button.supports_action(Action::Click));
+        // Bounds are in physical pixels
+        let bounds = button.bounds().unwrap();
+        assert!(bounds.y0 > 0.0 && bounds.width() > 0.0);
+    }
+}
@@ -0,0 +1,184 @@
+//! Background work that reports back to the UI thread
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "async")]
+//! # fn example(ctx: &oxideui::BuildContext, results: oxideui::core::ReactiveState<Vec<String>>) {
+//! let ui = ctx.ui_handle().expect("built by the runtime");
+//! ctx.spawn(async move {
+//!     let rows = vec!["fetched".to_string()];
+//!     // Applied on the UI thread; widgets watching `results` rebuild
+//!     let _ = ui.set(&results, rows);
+//! });
+//! # }
+//! ```
+
+use std::any::Any;
+
+use anyhow::{anyhow, Result};
+use winit::event_loop::EventLoopProxy;
+
+use crate::core::event::UiEvent;
+use crate::core::state_driven::ReactiveState;
+use crate::runtime::proxy::Message;
+use crate::runtime::RuntimeEvent;
+
+/// Closure queued for the UI thread by `UiHandle::run_on_ui`
+pub(crate) type UiTask = Box<dyn FnOnce() + Send>;
+
+/// Cloneable, `Send` handle for getting results back onto the UI thread.
+///
+/// Every method wakes the event loop and schedules a frame; they fail only
+/// once the app has shut down.
+#[derive(Clone)]
+pub struct UiHandle {
+    proxy: EventLoopProxy<RuntimeEvent>,
+}
+
+impl UiHandle {
+    pub(crate) fn new(proxy: EventLoopProxy<RuntimeEvent>) -> Self {
+        Self { proxy }
+    }
+
+    /// Run `task` on the UI thread before the next frame builds
+    pub fn run_on_ui<F: FnOnce() + Send + 'static>(&self, task: F) -> Result<()> {
+        self.proxy
+            .send_event(RuntimeEvent::Run(Box::new(task)))
+            .map_err(|_| anyhow!("Event loop is closed"))
+    }
+
+    /// Set `state` on the UI thread; widgets that read it rebuild
+    pub fn set<T: Clone + Send + Sync + 'static>(&self, state: &ReactiveState<T>, value: T) -> Result<()> {
+        let state = state.clone();
+        self.run_on_ui(move || state.set(value))
+    }
+
+    /// Dispatch `event` as if it came from the window
+    pub fn post(&self, event: UiEvent) -> Result<()> {
+        self.proxy
+            .send_event(RuntimeEvent::Ui(event))
+            .map_err(|_| anyhow!("Event loop is closed"))
+    }
+
+    /// Deliver `message` to the handler registered for its type with
+    /// `Runtime::with_message_handler`, or else to widgets as a
+    /// `UiEvent::Custom` named after the type
+    pub fn send<M: Any + Send + Sync>(&self, message: M) -> Result<()> {
+        self.proxy
+            .send_event(RuntimeEvent::Message(Message::new(message)))
+            .map_err(|_| anyhow!("Event loop is closed"))
+    }
+
+    /// Wake the event loop and render a frame
+    pub fn request_redraw(&self) -> Result<()> {
+        self.run_on_ui(|| {})
+    }
+
+    /// Dispatch a `UiEvent::Custom` carrying `data`
+    pub fn post_custom(&self, name: impl Into<String>, data: impl Any + Send + Sync) -> Result<()> {
+        self.post(UiEvent::Custom {
+            name: name.into(),
+            data: Box::new(data),
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+pub use spawner::TaskHandle;
+#[cfg(feature = "async")]
+pub(crate) use spawner::TaskSpawner;
+
+#[cfg(feature = "async")]
+mod spawner {
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::sync::Arc;
+
+    use anyhow::{Context, Result};
+    use parking_lot::Mutex;
+    use tokio::task::AbortHandle;
+
+    use crate::core::element::ElementId;
+
+    /// A spawned future; dropping the handle does not cancel it
+    #[derive(Clone)]
+    pub struct TaskHandle {
+        abort: AbortHandle,
+    }
+
+    impl TaskHandle {
+        pub fn cancel(&self) {
+            self.abort.abort();
+        }
+
+        pub fn is_finished(&self) -> bool {
+            self.abort.is_finished()
+        }
+    }
+
+    /// Tokio runtime for `BuildContext::spawn`, tracking which element
+    /// spawned each task so unmounting the element cancels its work
+    #[derive(Clone)]
+    pub(crate) struct TaskSpawner {
+        runtime: Arc<tokio::runtime::Runtime>,
+        owned: Arc<Mutex<HashMap<ElementId, Vec<AbortHandle>>>>,
+    }
+
+    impl TaskSpawner {
+        pub(crate) fn new() -> Result<Self> {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .thread_name("oxideui-task")
+                .build()
+                .context("Failed to start the task runtime")?;
+            Ok(Self {
+                runtime: Arc::new(runtime),
+                owned: Arc::new(Mutex::new(HashMap::new())),
+            })
+        }
+
+        pub(crate) fn spawn<F>(&self, owner: ElementId, future: F) -> TaskHandle
+        where
+            F: Future<Output = ()> + Send + 'static,
+        {
+            let abort = self.runtime.spawn(future).abort_handle();
+            let mut owned = self.owned.lock();
+            let tasks = owned.entry(owner).or_default();
+            tasks.retain(|task| !task.is_finished());
+            tasks.push(abort.clone());
+            TaskHandle { abort }
+        }
+
+        /// Cancel the unfinished tasks spawned while building `owner`
+        pub(crate) fn cancel_owned_by(&self, owner: ElementId) {
+            if let Some(tasks) = self.owned.lock().remove(&owner) {
+                for task in tasks {
+                    task.abort();
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::time::Duration;
+
+        #[test]
+        fn test_unmount_cancels_owned_tasks() {
+            let spawner = TaskSpawner::new().unwrap();
+            let owner = ElementId::new(3);
+            let pending = spawner.spawn(owner, std::future::pending());
+            let other = spawner.spawn(ElementId::new(4), std::future::pending());
+
+            spawner.cancel_owned_by(owner);
+            for _ in 0..100 {
+                if pending.is_finished() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            assert!(pending.is_finished());
+            assert!(!other.is_finished());
+        }
+    }
+}
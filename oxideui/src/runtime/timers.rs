@@ -0,0 +1,234 @@
+//! Timers driven by the event loop
+//!
+//! `ctx.after` and `ctx.every` register callbacks here. The runtime sleeps
+//! until the next one is due, runs it on the UI thread and schedules a
+//! frame, so clocks, auto-dismissing toasts and polling views update without
+//! a hand-rolled thread.
+
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use web_time::Instant;
+
+use crate::core::element::ElementId;
+
+type TimerCallback = Arc<dyn Fn() + Send + Sync>;
+
+/// A registered timer; cancelling it stops further callbacks
+#[derive(Clone)]
+pub struct TimerHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TimerHandle {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+struct Timer {
+    owner: ElementId,
+    /// Where `after`/`every` was called; with `owner` it identifies the
+    /// timer across rebuilds
+    site: &'static Location<'static>,
+    due: Instant,
+    /// Set for `every`
+    interval: Option<Duration>,
+    callback: TimerCallback,
+    /// One-shot timers stay registered after firing so the next build
+    /// doesn't arm them again
+    fired: bool,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct TimerScheduler {
+    timers: Arc<Mutex<Vec<Timer>>>,
+}
+
+impl TimerScheduler {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a timer, or refresh the callback of the one `owner` already
+    /// registered from `site` so it sees the latest build's captures
+    pub(crate) fn register(
+        &self,
+        owner: ElementId,
+        site: &'static Location<'static>,
+        delay: Duration,
+        interval: Option<Duration>,
+        callback: TimerCallback,
+        now: Instant,
+    ) -> TimerHandle {
+        let mut timers = self.timers.lock();
+        let existing = timers
+            .iter_mut()
+            .find(|timer| timer.owner == owner && timer.site == site && !timer.cancelled.load(Ordering::Relaxed));
+        if let Some(timer) = existing {
+            timer.callback = callback;
+            return TimerHandle {
+                cancelled: timer.cancelled.clone(),
+            };
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        timers.push(Timer {
+            owner,
+            site,
+            due: now + delay,
+            interval,
+            callback,
+            fired: false,
+            cancelled: cancelled.clone(),
+        });
+        TimerHandle { cancelled }
+    }
+
+    /// Run every callback due at `now`; true if any ran
+    pub(crate) fn fire_due(&self, now: Instant) -> bool {
+        let mut due = Vec::new();
+        {
+            let mut timers = self.timers.lock();
+            timers.retain(|timer| !timer.cancelled.load(Ordering::Relaxed));
+            for timer in timers.iter_mut().filter(|timer| !timer.fired && timer.due <= now) {
+                due.push(timer.callback.clone());
+                match timer.interval {
+                    // Missed ticks are skipped rather than run back to back
+                    Some(interval) => timer.due = (timer.due + interval).max(now + interval / 2),
+                    None => timer.fired = true,
+                }
+            }
+        }
+
+        // Callbacks may register or cancel timers, so run them unlocked
+        for callback in &due {
+            callback();
+        }
+        !due.is_empty()
+    }
+
+    /// When the runtime next has to wake up for a timer
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.timers
+            .lock()
+            .iter()
+            .filter(|timer| !timer.fired && !timer.cancelled.load(Ordering::Relaxed))
+            .map(|timer| timer.due)
+            .min()
+    }
+
+    /// Drop the timers registered while building `owner`
+    pub(crate) fn cancel_owned_by(&self, owner: ElementId) {
+        self.timers.lock().retain(|timer| timer.owner != owner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_timers_fire_and_rearm() {
+        let scheduler = TimerScheduler::new();
+        let owner = ElementId::new(1);
+        let start = Instant::now();
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let dismissed = Arc::new(AtomicUsize::new(0));
+
+        let every_site = Location::caller();
+        let tick = ticks.clone();
+        let every = move || {
+            tick.fetch_add(1, Ordering::Relaxed);
+        };
+        scheduler.register(owner, every_site, Duration::from_secs(1), Some(Duration::from_secs(1)), Arc::new(every.clone()), start);
+        let dismiss = dismissed.clone();
+        scheduler.register(
+            owner,
+            Location::caller(),
+            Duration::from_millis(500),
+            None,
+            Arc::new(move || {
+                dismiss.fetch_add(1, Ordering::Relaxed);
+            }),
+            start,
+        );
+        // A rebuild registering from the same place doesn't add a second timer
+        scheduler.register(owner, every_site, Duration::from_secs(1), Some(Duration::from_secs(1)), Arc::new(every), start);
+
+        assert_eq!(scheduler.next_deadline(), Some(start + Duration::from_millis(500)));
+        assert!(scheduler.fire_due(start + Duration::from_millis(500)));
+        assert!(!scheduler.fire_due(start + Duration::from_millis(600)));
+        assert!(scheduler.fire_due(start + Duration::from_secs(1)));
+        assert!(scheduler.fire_due(start + Duration::from_secs(2)));
+        assert_eq!(ticks.load(Ordering::Relaxed), 2);
+        assert_eq!(dismissed.load(Ordering::Relaxed), 1);
+
+        scheduler.cancel_owned_by(owner);
+        assert_eq!(scheduler.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_each_element_has_its_own_timers_until_it_unmounts() {
+        use std::any::Any;
+        use crate::core::context::BuildContext;
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::widget::{Widget, WidgetNode};
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+        use crate::runtime::widget_builder::WidgetBuilder;
+        use crate::widgets::layout_widgets::SizedBox;
+        use crate::widgets::basic::Column;
+
+        /// Dismisses itself after a while
+        #[derive(Clone)]
+        struct Toast;
+
+        impl Widget for Toast {
+            fn build(&self, ctx: &BuildContext) -> WidgetNode {
+                ctx.after(Duration::from_secs(3), || {});
+                SizedBox::shrink().build(ctx)
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn clone_box(&self) -> Box<dyn Widget> {
+                Box::new(self.clone())
+            }
+        }
+
+        let scheduler = TimerScheduler::new();
+        let tree = new_shared_element_tree();
+        let builder = WidgetBuilder::new(Arc::new(Theme::default()))
+            .with_element_tree(tree.clone())
+            .with_timers(scheduler.clone());
+        let toasts = |count: usize| -> Box<dyn Widget> {
+            Box::new(Column::new().with_children((0..count).map(|_| Box::new(Toast) as Box<dyn Widget>).collect()))
+        };
+        // What the runtime does after each build
+        let build = |root: Box<dyn Widget>| {
+            builder.build_widget_tree(&root, Constraints::loose(crate::layout::Size::new(400.0, 300.0)));
+            for id in tree.write().take_unmounted() {
+                scheduler.cancel_owned_by(id);
+            }
+            scheduler.timers.lock().len()
+        };
+
+        // Both toasts arm the timer at the same line, but each is its own
+        assert_eq!(build(toasts(2)), 2);
+        assert_eq!(build(toasts(2)), 2);
+        // The second toast is gone, and its timer with it
+        assert_eq!(build(toasts(1)), 1);
+    }
+}
@@ -1,76 +1,246 @@
+use crate::core::element::{new_shared_element_tree, SharedElementTree};
 use crate::core::{BuildContext, RenderObject, Theme, Widget, WidgetNode};
-use crate::layout::Constraints;
+use crate::i18n::Localizer;
+use crate::layout::{Constraints, Direction, EdgeInsets, LayoutCache, Size};
+use crate::platform::window::WindowController;
+use crate::runtime::{TimerScheduler, UiHandle};
+#[cfg(feature = "async")]
+use crate::runtime::TaskSpawner;
+use crate::theming::{Stylesheet, ThemeController, WidgetStyles};
 use std::sync::Arc;
 
 pub struct WidgetBuilder {
     theme: Arc<Theme>,
+    element_tree: Option<SharedElementTree>,
+    window: Option<WindowController>,
+    theme_controller: Option<ThemeController>,
+    stylesheet: Option<Arc<Stylesheet>>,
+    widget_styles: Option<Arc<WidgetStyles>>,
+    safe_area: EdgeInsets,
+    reduced_motion: bool,
+    localizer: Option<Localizer>,
+    direction: Option<Direction>,
+    parallel: bool,
+    layout_cache: Option<LayoutCache>,
+    ui_handle: Option<UiHandle>,
+    timers: Option<TimerScheduler>,
+    #[cfg(feature = "async")]
+    tasks: Option<TaskSpawner>,
 }
 
 impl WidgetBuilder {
     pub fn new(theme: Arc<Theme>) -> Self {
-        Self { theme }
+        Self {
+            theme,
+            element_tree: None,
+            window: None,
+            theme_controller: None,
+            stylesheet: None,
+            widget_styles: None,
+            safe_area: EdgeInsets::zero(),
+            reduced_motion: false,
+            localizer: None,
+            direction: None,
+            parallel: false,
+            layout_cache: None,
+            ui_handle: None,
+            timers: None,
+            #[cfg(feature = "async")]
+            tasks: None,
+        }
+    }
+
+    /// Mount elements into `tree`, e.g. the runtime's, so events can reach
+    /// them; otherwise each build gets a tree of its own
+    pub fn with_element_tree(mut self, tree: SharedElementTree) -> Self {
+        self.element_tree = Some(tree);
+        self
+    }
+
+    pub fn with_window(mut self, window: WindowController) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    pub fn with_theme_controller(mut self, controller: ThemeController) -> Self {
+        self.theme_controller = Some(controller);
+        self
+    }
+
+    pub fn with_stylesheet(mut self, stylesheet: Arc<Stylesheet>) -> Self {
+        self.stylesheet = Some(stylesheet);
+        self
+    }
+
+    pub fn with_widget_styles(mut self, styles: Arc<WidgetStyles>) -> Self {
+        self.widget_styles = Some(styles);
+        self
+    }
+
+    pub fn with_safe_area(mut self, insets: EdgeInsets) -> Self {
+        self.safe_area = insets;
+        self
+    }
+
+    pub fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.reduced_motion = reduced_motion;
+        self
+    }
+
+    pub fn with_localizer(mut self, localizer: Localizer) -> Self {
+        self.localizer = Some(localizer);
+        self
+    }
+
+    /// Override the direction implied by the localizer's locale
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Build sibling subtrees of large elements on the rayon pool.
+    /// Children are merged back in declaration order, so the result is the
+    /// same as a serial build. Has no effect without the `parallel` feature.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Reuse layouts of unchanged widgets from earlier builds
+    pub fn with_layout_cache(mut self, cache: LayoutCache) -> Self {
+        self.layout_cache = Some(cache);
+        self
+    }
+
+    pub fn with_ui_handle(mut self, handle: UiHandle) -> Self {
+        self.ui_handle = Some(handle);
+        self
+    }
+
+    pub fn with_timers(mut self, timers: TimerScheduler) -> Self {
+        self.timers = Some(timers);
+        self
+    }
+
+    #[cfg(feature = "async")]
+    pub fn with_task_spawner(mut self, tasks: TaskSpawner) -> Self {
+        self.tasks = Some(tasks);
+        self
     }
 
     /// Build the complete widget tree into render objects
     pub fn build_widget_tree(&self, root_widget: &Box<dyn Widget>, constraints: Constraints) -> RenderObject {
         println!("🎨 Building widget tree...");
+        let _span = span!(INFO, "build");
 
-        let element_tree = crate::core::element::new_shared_element_tree();
+        let element_tree = self.element_tree.clone().unwrap_or_else(new_shared_element_tree);
+        element_tree.write().begin_build();
+        let root_id = element_tree.write().mount(None, root_widget.as_ref());
 
-        let ctx = BuildContext::new(
-            crate::core::element::ElementId::new(0),
-            element_tree,
+        let mut ctx = BuildContext::new(
+            root_id,
+            element_tree.clone(),
             constraints,
             self.theme.clone(),
-        );
-
-        let widget_node = root_widget.build(&ctx);
-
-        let widget_type = match &widget_node {
-            WidgetNode::Leaf(_) => "Leaf",
-            WidgetNode::Container { children } => {
-                return RenderObject::Group {
-                    children: children.iter().map(|child| {
-                        self.build_widget_recursive(child, &ctx)
-                    }).collect(),
-                };
-            }
-            WidgetNode::None => "None",
-        };
-
-        println!("📦 Root widget type: {}", widget_type);
+        )
+        .with_safe_area(self.safe_area)
+        .with_window_size(Size::new(constraints.max_width, constraints.max_height))
+        .with_reduced_motion(self.reduced_motion);
+        if let Some(window) = &self.window {
+            ctx = ctx.with_window(window.clone());
+        }
+        if let Some(controller) = &self.theme_controller {
+            ctx = ctx.with_theme_controller(controller.clone());
+        }
+        if let Some(localizer) = &self.localizer {
+            ctx = ctx.with_localizer(localizer.clone());
+        }
+        if let Some(direction) = self.direction {
+            ctx = ctx.with_direction(direction);
+        }
+        if let Some(stylesheet) = &self.stylesheet {
+            ctx = ctx.with_stylesheet(stylesheet.clone());
+        }
+        if let Some(styles) = &self.widget_styles {
+            ctx = ctx.with_widget_styles(styles.clone());
+        }
+        if let Some(cache) = &self.layout_cache {
+            ctx = ctx.with_layout_cache(cache.clone());
+        }
+        if let Some(handle) = &self.ui_handle {
+            ctx = ctx.with_ui_handle(handle.clone());
+        }
+        if let Some(timers) = &self.timers {
+            ctx = ctx.with_timers(timers.clone());
+        }
+        #[cfg(feature = "async")]
+        if let Some(tasks) = &self.tasks {
+            ctx = ctx.with_task_spawner(tasks.clone());
+        }
+        #[cfg(feature = "parallel")]
+        {
+            ctx = ctx.with_parallel(self.parallel);
+        }
 
-        match widget_node {
+        let root = match ctx.build_element(root_widget.as_ref()) {
             WidgetNode::Leaf(render_obj) => render_obj,
-            WidgetNode::Container { children } => {
-                let mut child_objects = Vec::new();
-                for child in children {
-                    let child_obj = self.build_widget_recursive(&child, &ctx);
-                    child_objects.push(child_obj);
-                }
-                RenderObject::group(child_objects)
-            }
-            WidgetNode::None => {
+            _ => {
                 println!("⚠️ None widget node");
                 RenderObject::None
             }
-        }
+        };
+
+        let mut element_tree = element_tree.write();
+        element_tree.end_build();
+        element_tree.record_layout(&root);
+        root
     }
+}
 
-    fn build_widget_recursive(&self, widget: &Box<dyn Widget>, parent_ctx: &BuildContext) -> RenderObject {
-        let widget_node = widget.build(parent_ctx);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
-        match widget_node {
-            WidgetNode::Leaf(render_obj) => render_obj,
-            WidgetNode::Container { children } => {
-                let mut child_objects = Vec::new();
-                for child in children {
-                    let child_obj = self.build_widget_recursive(&child, parent_ctx);
-                    child_objects.push(child_obj);
-                }
-                RenderObject::group(child_objects)
-            }
-            WidgetNode::None => RenderObject::None,
+    use crate::core::event::{MouseButton, UiEvent};
+    use crate::core::render_object::Point;
+    use crate::core::EventDispatcher;
+    use crate::widgets::basic::Container;
+    use crate::widgets::Button;
+
+    #[test]
+    fn test_clicks_reach_widgets_built_into_the_runtime_tree() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let root: Box<dyn Widget> = Box::new(Container::new().with_child(
+            Button::new("Save").with_size(120.0, 40.0).with_on_click(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        ));
+        let element_tree = new_shared_element_tree();
+        let builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(element_tree.clone());
+        let constraints = Constraints::new(0.0, 400.0, 0.0, 300.0);
+
+        // What the runtime does each frame: build, then dispatch input
+        builder.build_widget_tree(&root, constraints);
+        let ids = |tree: &SharedElementTree| {
+            let mut ids: Vec<_> = tree.read().iter().map(|element| element.id).collect();
+            ids.sort_by_key(|id| id.as_u64());
+            ids
+        };
+        let first = ids(&element_tree);
+        assert_eq!(first.len(), 2);
+        builder.build_widget_tree(&root, constraints);
+        assert_eq!(ids(&element_tree), first, "rebuilding keeps the elements");
+
+        let mut dispatcher = EventDispatcher::new();
+        let position = Point::new(20.0, 20.0);
+        for event in [
+            UiEvent::PointerDown { id: 0, position, button: MouseButton::Left },
+            UiEvent::PointerUp { id: 0, position, button: MouseButton::Left },
+        ] {
+            dispatcher.dispatch_event(&event, &element_tree.read());
         }
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
     }
-}
\ No newline at end of file
+}
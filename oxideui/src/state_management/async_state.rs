@@ -0,0 +1,213 @@
+//! Asynchronously loaded state
+//!
+//! `AsyncState<T>` starts out loading and later resolves to a value or an
+//! error. Reading it while a `Suspense` boundary is building registers the
+//! state with that boundary so it can show a fallback until it settles.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::thread;
+
+use parking_lot::RwLock;
+
+use crate::core::element::ElementId;
+use crate::core::state_driven::{StateToken, StateTracker};
+
+/// Current value of an `AsyncState`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsyncValue<T> {
+    Loading,
+    Ready(T),
+    Failed(String),
+}
+
+impl<T> AsyncValue<T> {
+    pub fn is_loading(&self) -> bool {
+        matches!(self, AsyncValue::Loading)
+    }
+
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            AsyncValue::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Anything a `Suspense` boundary can wait on
+pub trait PendingState: Send + Sync {
+    fn is_pending(&self) -> bool;
+
+    /// Rebuild `element` once the state settles; false if the state can't
+    /// notify anyone, so the boundary has to poll it
+    fn subscribe(&self, _element: ElementId) -> bool {
+        false
+    }
+}
+
+thread_local! {
+    static SUSPENSE_SCOPES: RefCell<Vec<Vec<Arc<dyn PendingState>>>> = RefCell::new(Vec::new());
+}
+
+/// Pending reads collected for a `Suspense` boundary while it builds. The
+/// scope is closed when dropped, so a panicking child can't leave it open
+/// for whatever builds next.
+pub(crate) struct SuspenseScope {
+    open: bool,
+}
+
+impl SuspenseScope {
+    pub(crate) fn enter() -> Self {
+        SUSPENSE_SCOPES.with(|scopes| scopes.borrow_mut().push(Vec::new()));
+        Self { open: true }
+    }
+
+    /// Close the scope and return the states read inside it
+    pub(crate) fn exit(mut self) -> Vec<Arc<dyn PendingState>> {
+        self.open = false;
+        SUSPENSE_SCOPES.with(|scopes| scopes.borrow_mut().pop().unwrap_or_default())
+    }
+}
+
+impl Drop for SuspenseScope {
+    fn drop(&mut self) {
+        if self.open {
+            SUSPENSE_SCOPES.with(|scopes| scopes.borrow_mut().pop());
+        }
+    }
+}
+
+fn register_with_suspense(state: Arc<dyn PendingState>) {
+    SUSPENSE_SCOPES.with(|scopes| {
+        if let Some(scope) = scopes.borrow_mut().last_mut() {
+            scope.push(state);
+        }
+    });
+}
+
+struct AsyncInner<T> {
+    value: RwLock<AsyncValue<T>>,
+}
+
+
+/// State that resolves asynchronously
+pub struct AsyncState<T: Clone + Send + Sync + 'static> {
+    inner: Arc<AsyncInner<T>>,
+    token: StateToken,
+    tracker: Option<Arc<StateTracker>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> AsyncState<T> {
+    /// A state that stays loading until `resolve` or `reject` is called
+    pub fn pending() -> Self {
+        Self {
+            inner: Arc::new(AsyncInner {
+                value: RwLock::new(AsyncValue::Loading),
+            }),
+            token: StateToken::new(),
+            tracker: None,
+        }
+    }
+
+    /// Run `load` on a background thread and resolve with its result
+    pub fn spawn<F>(load: F) -> Self
+    where
+        F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+    {
+        Self::spawn_with_tracker(load, None)
+    }
+
+    /// Like `spawn`, notifying `tracker` when the load settles
+    pub fn spawn_with_tracker<F>(load: F, tracker: Option<Arc<StateTracker>>) -> Self
+    where
+        F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+    {
+        let mut state = Self::pending();
+        state.tracker = tracker;
+        let handle = state.clone();
+        thread::spawn(move || match load() {
+            Ok(value) => handle.resolve(value),
+            Err(err) => handle.reject(err.to_string()),
+        });
+        state
+    }
+
+    /// Notify this tracker when the state settles
+    pub fn with_tracker(mut self, tracker: Arc<StateTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    pub fn resolve(&self, value: T) {
+        *self.inner.value.write() = AsyncValue::Ready(value);
+        self.notify();
+    }
+
+    pub fn reject(&self, error: impl Into<String>) {
+        *self.inner.value.write() = AsyncValue::Failed(error.into());
+        self.notify();
+    }
+
+    /// Go back to loading (e.g. before a refetch)
+    pub fn reset(&self) {
+        *self.inner.value.write() = AsyncValue::Loading;
+        self.notify();
+    }
+
+    fn notify(&self) {
+        if let Some(tracker) = &self.tracker {
+            tracker.notify_change(self.token);
+        }
+    }
+
+    /// Read the current value, registering with the enclosing `Suspense`
+    pub fn read(&self) -> AsyncValue<T> {
+        let value = self.inner.value.read().clone();
+        if value.is_loading() {
+            register_with_suspense(self.as_pending());
+        }
+        value
+    }
+
+    /// Read without registering with any `Suspense` boundary
+    pub fn peek(&self) -> AsyncValue<T> {
+        self.inner.value.read().clone()
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.inner.value.read().is_loading()
+    }
+
+    /// Subscribe an element so it rebuilds when the state settles
+    pub fn subscribe(&self, element: ElementId) {
+        if let Some(tracker) = &self.tracker {
+            tracker.subscribe(element, self.token);
+        }
+    }
+
+    /// Handle usable with `Suspense::with_state`
+    pub fn as_pending(&self) -> Arc<dyn PendingState> {
+        Arc::new(self.clone())
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> PendingState for AsyncState<T> {
+    fn is_pending(&self) -> bool {
+        self.is_loading()
+    }
+
+    fn subscribe(&self, element: ElementId) -> bool {
+        AsyncState::subscribe(self, element);
+        self.tracker.is_some()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Clone for AsyncState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            token: self.token,
+            tracker: self.tracker.clone(),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Two-way bindings between form widgets and reactive state
+//!
+//! A `Binding<T>` is a getter/setter pair. Form widgets read their current
+//! value from it during build and write user edits back through it, so a
+//! `ReactiveState` can drive a widget without a separate `on_change` handler.
+
+use std::sync::Arc;
+
+use crate::core::element::ElementId;
+use crate::core::state_driven::ReactiveState;
+use crate::state_management::state::State;
+
+/// Two-way binding to a value owned elsewhere
+pub struct Binding<T: Clone + Send + Sync + 'static> {
+    getter: Arc<dyn Fn() -> T + Send + Sync>,
+    setter: Arc<dyn Fn(T) + Send + Sync>,
+    subscriber: Option<Arc<dyn Fn(ElementId) + Send + Sync>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Binding<T> {
+    /// Create a binding from custom getter and setter functions
+    pub fn new<G, S>(getter: G, setter: S) -> Self
+    where
+        G: Fn() -> T + Send + Sync + 'static,
+        S: Fn(T) + Send + Sync + 'static,
+    {
+        Self {
+            getter: Arc::new(getter),
+            setter: Arc::new(setter),
+            subscriber: None,
+        }
+    }
+
+    /// Bind to a `ReactiveState`; writes notify the state's tracker
+    pub fn from_state(state: &ReactiveState<T>) -> Self {
+        let read = state.clone();
+        let write = state.clone();
+        let subscribe = state.clone();
+        Self {
+            getter: Arc::new(move || read.get()),
+            setter: Arc::new(move |value| write.set(value)),
+            subscriber: Some(Arc::new(move |element| subscribe.subscribe(element))),
+        }
+    }
+
+    /// Bind to a listener-based `State`
+    pub fn from_simple_state(state: &State<T>) -> Self {
+        let read = state.clone();
+        let write = state.clone();
+        Self::new(move || read.get(), move |value| write.set(value))
+    }
+
+    /// A read-only binding; writes are ignored
+    pub fn constant(value: T) -> Self {
+        Self::new(move || value.clone(), |_| {})
+    }
+
+    /// Read the current value
+    pub fn get(&self) -> T {
+        (self.getter)()
+    }
+
+    /// Write a new value back to the source
+    pub fn set(&self, value: T) {
+        (self.setter)(value)
+    }
+
+    /// Read, modify and write back the value
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.get();
+        f(&mut value);
+        self.set(value);
+    }
+
+    /// Subscribe an element so it rebuilds when the source changes
+    pub fn subscribe(&self, element: ElementId) {
+        if let Some(subscriber) = &self.subscriber {
+            subscriber(element);
+        }
+    }
+
+    /// Project this binding onto a derived value
+    ///
+    /// `to` converts the source value for reading; `from` folds an edited
+    /// value back into the source.
+    pub fn map<U, F, B>(&self, to: F, from: B) -> Binding<U>
+    where
+        U: Clone + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+        B: Fn(&T, U) -> T + Send + Sync + 'static,
+    {
+        let read = self.getter.clone();
+        let source = self.getter.clone();
+        let write = self.setter.clone();
+        Binding {
+            getter: Arc::new(move || to(&read())),
+            setter: Arc::new(move |value| write(from(&source(), value))),
+            subscriber: self.subscriber.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Clone for Binding<T> {
+    fn clone(&self) -> Self {
+        Self {
+            getter: self.getter.clone(),
+            setter: self.setter.clone(),
+            subscriber: self.subscriber.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> From<ReactiveState<T>> for Binding<T> {
+    fn from(state: ReactiveState<T>) -> Self {
+        Binding::from_state(&state)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> From<&ReactiveState<T>> for Binding<T> {
+    fn from(state: &ReactiveState<T>) -> Self {
+        Binding::from_state(state)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> From<State<T>> for Binding<T> {
+    fn from(state: State<T>) -> Self {
+        Binding::from_simple_state(&state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state_driven::StateTracker;
+
+    #[test]
+    fn test_binding_writes_back_to_state() {
+        let tracker = Arc::new(StateTracker::new());
+        let state = ReactiveState::new(String::from("a"), tracker.clone());
+        state.subscribe(ElementId::new(1));
+
+        let binding = Binding::from_state(&state);
+        binding.set("b".to_string());
+
+        assert_eq!(state.get(), "b");
+        assert!(tracker.get_dirty_elements().contains(&ElementId::new(1)));
+    }
+
+    #[test]
+    fn test_mapped_binding() {
+        let state = State::new((1.0f32, true));
+        let binding = Binding::from_simple_state(&state).map(|v| v.0, |v, x| (x, v.1));
+
+        binding.set(5.0);
+        assert_eq!(binding.get(), 5.0);
+        assert_eq!(state.get(), (5.0, true));
+    }
+}
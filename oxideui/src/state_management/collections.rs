@@ -0,0 +1,446 @@
+//! Observable collections with granular change notifications
+//!
+//! `ReactiveVec<T>` records every structural edit as a `ListChange` in a
+//! versioned log. Consumers remember the last version they synced to and use
+//! `ListDiffCache` to patch only the touched children instead of rebuilding
+//! the whole list.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
+
+use parking_lot::RwLock;
+
+use crate::core::element::ElementId;
+use crate::core::state_driven::{StateToken, StateTracker};
+
+/// Maximum number of changes kept in the log before consumers must resync
+const MAX_CHANGE_LOG: usize = 256;
+
+/// Stable identity of an item, preserved across moves
+pub type ItemId = u64;
+
+/// A single structural change to a `ReactiveVec`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListChange {
+    Insert { index: usize },
+    Remove { index: usize },
+    Move { from: usize, to: usize },
+    Update { index: usize },
+    /// The whole list was replaced
+    Reset,
+}
+
+struct VecInner<T> {
+    items: Vec<(ItemId, T)>,
+    next_id: ItemId,
+    version: u64,
+    /// Changes paired with the version they produced
+    log: VecDeque<(u64, ListChange)>,
+}
+
+impl<T> VecInner<T> {
+    fn record(&mut self, change: ListChange) {
+        self.version += 1;
+        self.log.push_back((self.version, change));
+        if self.log.len() > MAX_CHANGE_LOG {
+            self.log.pop_front();
+        }
+    }
+
+    fn alloc_id(&mut self) -> ItemId {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+/// Vector that emits insert/remove/move/update changes
+pub struct ReactiveVec<T: Clone + Send + Sync + 'static> {
+    inner: Arc<RwLock<VecInner<T>>>,
+    token: StateToken,
+    tracker: Option<Arc<StateTracker>>,
+    listeners: Arc<RwLock<Vec<Box<dyn Fn(ListChange) + Send + Sync>>>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> ReactiveVec<T> {
+    pub fn new() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    pub fn from_vec(items: Vec<T>) -> Self {
+        let items: Vec<(ItemId, T)> = items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| (i as ItemId + 1, item))
+            .collect();
+        let next_id = items.len() as ItemId + 1;
+
+        Self {
+            inner: Arc::new(RwLock::new(VecInner {
+                items,
+                next_id,
+                version: 0,
+                log: VecDeque::new(),
+            })),
+            token: StateToken::new(),
+            tracker: None,
+            listeners: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Notify this tracker on every change so subscribed elements rebuild
+    pub fn with_tracker(mut self, tracker: Arc<StateTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    fn emit(&self, change: ListChange) {
+        if let Some(tracker) = &self.tracker {
+            tracker.notify_change(self.token);
+        }
+        for listener in self.listeners.read().iter() {
+            listener(change);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.read().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.inner.read().items.get(index).map(|(_, item)| item.clone())
+    }
+
+    /// Stable id of the item currently at `index`
+    pub fn id_at(&self, index: usize) -> Option<ItemId> {
+        self.inner.read().items.get(index).map(|(id, _)| *id)
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.inner.read().items.iter().map(|(_, item)| item.clone()).collect()
+    }
+
+    pub fn push(&self, item: T) {
+        let index = {
+            let mut inner = self.inner.write();
+            let id = inner.alloc_id();
+            inner.items.push((id, item));
+            let index = inner.items.len() - 1;
+            inner.record(ListChange::Insert { index });
+            index
+        };
+        self.emit(ListChange::Insert { index });
+    }
+
+    pub fn insert(&self, index: usize, item: T) {
+        {
+            let mut inner = self.inner.write();
+            let id = inner.alloc_id();
+            inner.items.insert(index, (id, item));
+            inner.record(ListChange::Insert { index });
+        }
+        self.emit(ListChange::Insert { index });
+    }
+
+    pub fn remove(&self, index: usize) -> T {
+        let item = {
+            let mut inner = self.inner.write();
+            let (_, item) = inner.items.remove(index);
+            inner.record(ListChange::Remove { index });
+            item
+        };
+        self.emit(ListChange::Remove { index });
+        item
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            None
+        } else {
+            Some(self.remove(len - 1))
+        }
+    }
+
+    /// Move the item at `from` so it ends up at `to`
+    pub fn move_item(&self, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+        {
+            let mut inner = self.inner.write();
+            let entry = inner.items.remove(from);
+            inner.items.insert(to, entry);
+            inner.record(ListChange::Move { from, to });
+        }
+        self.emit(ListChange::Move { from, to });
+    }
+
+    /// Replace the item at `index`, keeping its identity
+    pub fn set(&self, index: usize, item: T) {
+        {
+            let mut inner = self.inner.write();
+            inner.items[index].1 = item;
+            inner.record(ListChange::Update { index });
+        }
+        self.emit(ListChange::Update { index });
+    }
+
+    pub fn update<F>(&self, index: usize, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        {
+            let mut inner = self.inner.write();
+            f(&mut inner.items[index].1);
+            inner.record(ListChange::Update { index });
+        }
+        self.emit(ListChange::Update { index });
+    }
+
+    /// Replace every item; consumers rebuild from scratch
+    pub fn replace(&self, items: Vec<T>) {
+        {
+            let mut inner = self.inner.write();
+            let entries: Vec<(ItemId, T)> = items
+                .into_iter()
+                .map(|item| (inner.alloc_id(), item))
+                .collect();
+            inner.items = entries;
+            inner.record(ListChange::Reset);
+        }
+        self.emit(ListChange::Reset);
+    }
+
+    pub fn clear(&self) {
+        self.replace(Vec::new());
+    }
+
+    /// Current version; bumps on every change
+    pub fn version(&self) -> u64 {
+        self.inner.read().version
+    }
+
+    /// Changes made after `version`, or `None` if the log no longer reaches
+    /// back that far and the consumer must resync
+    pub fn changes_since(&self, version: u64) -> Option<Vec<ListChange>> {
+        let inner = self.inner.read();
+        if version == inner.version {
+            return Some(Vec::new());
+        }
+        let oldest = inner.log.front().map(|(v, _)| *v)?;
+        if version + 1 < oldest {
+            return None;
+        }
+        Some(
+            inner
+                .log
+                .iter()
+                .filter(|(v, _)| *v > version)
+                .map(|(_, change)| *change)
+                .collect(),
+        )
+    }
+
+    /// Subscribe an element so it rebuilds when the list changes
+    pub fn subscribe(&self, element: ElementId) {
+        if let Some(tracker) = &self.tracker {
+            tracker.subscribe(element, self.token);
+        }
+    }
+
+    /// Listen to individual changes
+    pub fn on_change<F>(&self, listener: F)
+    where
+        F: Fn(ListChange) + Send + Sync + 'static,
+    {
+        self.listeners.write().push(Box::new(listener));
+    }
+
+    pub fn token(&self) -> StateToken {
+        self.token
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Clone for ReactiveVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            token: self.token,
+            tracker: self.tracker.clone(),
+            listeners: self.listeners.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Default for ReactiveVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An in-flight move of a cached child
+#[derive(Debug, Clone, Copy)]
+pub struct MoveTransition {
+    pub from_index: usize,
+    pub started: Instant,
+}
+
+/// Per-consumer cache of built children, patched from a `ReactiveVec` log
+pub struct ListDiffCache<R: Clone> {
+    version: Option<u64>,
+    entries: Vec<(ItemId, Option<R>)>,
+    moves: HashMap<ItemId, MoveTransition>,
+    rebuilt: usize,
+}
+
+impl<R: Clone> ListDiffCache<R> {
+    pub fn new() -> Self {
+        Self {
+            version: None,
+            entries: Vec::new(),
+            moves: HashMap::new(),
+            rebuilt: 0,
+        }
+    }
+
+    /// Bring the cache up to date, calling `build` only for touched indices
+    pub fn sync<T, F>(&mut self, list: &ReactiveVec<T>, mut build: F)
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnMut(usize, ItemId, &T) -> R,
+    {
+        let changes = self.version.and_then(|v| list.changes_since(v));
+
+        match changes {
+            Some(changes) => {
+                for change in changes {
+                    self.apply(change, list);
+                }
+            }
+            None => {
+                self.entries.clear();
+                self.moves.clear();
+            }
+        }
+
+        let inner = list.inner.read();
+        if self.entries.len() != inner.items.len() {
+            // Full resync (first build, truncated log or reset)
+            self.entries = inner.items.iter().map(|(id, _)| (*id, None)).collect();
+        }
+
+        self.rebuilt = 0;
+        for (index, (id, entry)) in self.entries.iter_mut().enumerate() {
+            let (current_id, item) = &inner.items[index];
+            if entry.is_none() || id != current_id {
+                *id = *current_id;
+                *entry = Some(build(index, *current_id, item));
+                self.rebuilt += 1;
+            }
+        }
+
+        self.version = Some(inner.version);
+    }
+
+    fn apply<T: Clone + Send + Sync + 'static>(&mut self, change: ListChange, list: &ReactiveVec<T>) {
+        match change {
+            ListChange::Insert { index } if index <= self.entries.len() => {
+                self.entries.insert(index, (0, None));
+            }
+            ListChange::Remove { index } if index < self.entries.len() => {
+                let (id, _) = self.entries.remove(index);
+                self.moves.remove(&id);
+            }
+            ListChange::Move { from, to } if from < self.entries.len() && to < self.entries.len() => {
+                let entry = self.entries.remove(from);
+                self.moves.insert(entry.0, MoveTransition { from_index: from, started: Instant::now() });
+                self.entries.insert(to, entry);
+            }
+            ListChange::Update { index } if index < self.entries.len() => {
+                self.entries[index].1 = None;
+            }
+            _ => {
+                // Reset or an out-of-range change: drop everything
+                self.entries.clear();
+                self.entries.resize(list.len(), (0, None));
+                self.moves.clear();
+            }
+        }
+    }
+
+    /// Cached children in list order
+    pub fn children(&self) -> impl Iterator<Item = &R> {
+        self.entries.iter().filter_map(|(_, entry)| entry.as_ref())
+    }
+
+    /// Stable id of the child at `index`
+    pub fn id_at(&self, index: usize) -> Option<ItemId> {
+        self.entries.get(index).map(|(id, _)| *id)
+    }
+
+    /// Active move transition for `id`, dropping it once `duration` elapsed
+    pub fn move_transition(&mut self, id: ItemId, duration: Duration) -> Option<MoveTransition> {
+        let transition = *self.moves.get(&id)?;
+        if transition.started.elapsed() >= duration {
+            self.moves.remove(&id);
+            return None;
+        }
+        Some(transition)
+    }
+
+    pub fn has_active_moves(&self) -> bool {
+        !self.moves.is_empty()
+    }
+
+    /// Number of children rebuilt by the last `sync`
+    pub fn rebuilt_count(&self) -> usize {
+        self.rebuilt
+    }
+}
+
+impl<R: Clone> Default for ListDiffCache<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_cache_only_rebuilds_touched_items() {
+        let list = ReactiveVec::from_vec(vec!["a", "b", "c"]);
+        let mut cache: ListDiffCache<String> = ListDiffCache::new();
+
+        cache.sync(&list, |_, _, item| item.to_string());
+        assert_eq!(cache.rebuilt_count(), 3);
+
+        list.insert(1, "x");
+        list.set(3, "C");
+        cache.sync(&list, |_, _, item| item.to_string());
+        assert_eq!(cache.rebuilt_count(), 2);
+
+        list.move_item(0, 3);
+        cache.sync(&list, |_, _, item| item.to_string());
+        assert_eq!(cache.rebuilt_count(), 0);
+        assert_eq!(cache.children().cloned().collect::<Vec<_>>(), vec!["x", "b", "C", "a"]);
+    }
+
+    #[test]
+    fn test_changes_since_reports_truncation() {
+        let list = ReactiveVec::new();
+        for i in 0..(MAX_CHANGE_LOG + 10) {
+            list.push(i);
+        }
+        assert!(list.changes_since(0).is_none());
+        assert_eq!(list.changes_since(list.version()), Some(Vec::new()));
+    }
+}
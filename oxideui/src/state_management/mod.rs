@@ -5,6 +5,11 @@ pub mod derived;
 pub mod pre_effect;
 pub mod props;
 pub mod bindable;
+pub mod collections;
+pub mod async_state;
 
 
 pub use state::State;
+pub use bindable::Binding;
+pub use collections::{ListChange, ListDiffCache, ReactiveVec};
+pub use async_state::{AsyncState, AsyncValue, PendingState};
@@ -0,0 +1,123 @@
+//! High-contrast palettes and forced-colors mode
+//!
+//! `ThemeConfig::high_contrast()` is an ordinary theme with black/white
+//! palettes. Forced colors goes further, like the CSS `forced-colors` media
+//! feature: every theme color is replaced from a small set of system colors
+//! and widgets draw borders where they would otherwise rely on a fill. The
+//! runtime turns it on when Windows High Contrast is active, or when the app
+//! asks with `Runtime::with_forced_colors`.
+
+use crate::core::render_object::Color;
+use crate::theming::{ThemeColors, ThemeConfig};
+
+/// The system palette of forced-colors mode, named after the CSS system colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemColors {
+    pub canvas: Color,
+    pub canvas_text: Color,
+    /// Selected items and primary actions
+    pub highlight: Color,
+    pub highlight_text: Color,
+    /// Disabled text
+    pub gray_text: Color,
+    pub link_text: Color,
+}
+
+impl SystemColors {
+    /// Windows' "High Contrast Black" scheme
+    pub fn black() -> Self {
+        Self {
+            canvas: Color::rgb(0, 0, 0),
+            canvas_text: Color::rgb(255, 255, 255),
+            highlight: Color::rgb(26, 235, 255),
+            highlight_text: Color::rgb(0, 0, 0),
+            gray_text: Color::rgb(63, 242, 63),
+            link_text: Color::rgb(255, 255, 0),
+        }
+    }
+
+    /// Windows' "High Contrast White" scheme
+    pub fn white() -> Self {
+        Self {
+            canvas: Color::rgb(255, 255, 255),
+            canvas_text: Color::rgb(0, 0, 0),
+            highlight: Color::rgb(55, 0, 110),
+            highlight_text: Color::rgb(255, 255, 255),
+            gray_text: Color::rgb(96, 0, 0),
+            link_text: Color::rgb(0, 0, 159),
+        }
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.canvas.is_dark()
+    }
+
+    /// `base` with every color role mapped onto this palette and shadows off
+    pub fn palette(&self, base: &ThemeColors) -> ThemeColors {
+        let mut colors = base.clone();
+        for name in ThemeColors::COLOR_NAMES {
+            colors.set_color(name, self.role_color(name));
+        }
+        colors.shadow_opacity = 0.0;
+        colors
+    }
+
+    fn role_color(&self, name: &str) -> Color {
+        match name {
+            "primary" | "sidebar_primary" | "destructive" | "ring" | "sidebar_ring" | "chart_1" => self.highlight,
+            "primary_foreground" | "sidebar_primary_foreground" | "destructive_foreground" => self.highlight_text,
+            "chart_2" => self.link_text,
+            "chart_3" => self.gray_text,
+            name if name.ends_with("foreground") || name.ends_with("border") || name == "input" => self.canvas_text,
+            name if name.starts_with("chart") => self.canvas_text,
+            _ => self.canvas,
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// Black-on-white and white-on-black palettes with the default fonts and
+    /// metrics. Widgets render as usual; see `SystemColors` for forced colors.
+    pub fn high_contrast() -> Self {
+        let base = ThemeConfig::default();
+        Self {
+            light: SystemColors::white().palette(&base.light),
+            dark: SystemColors::black().palette(&base.dark),
+            ..base
+        }
+    }
+
+    /// This config's fonts and metrics with both palettes taken from `colors`
+    pub fn with_system_colors(&self, colors: &SystemColors) -> Self {
+        Self {
+            light: colors.palette(&self.light),
+            dark: colors.palette(&self.dark),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Theme;
+    use crate::theming::{ButtonStyle, ColorRole};
+
+    #[test]
+    fn test_forced_colors_theme() {
+        let colors = SystemColors::black();
+        let theme = Theme::forced_colors(&ThemeConfig::default(), &colors);
+
+        assert!(theme.forced_colors);
+        assert!(theme.is_dark);
+        assert_eq!(theme.background, colors.canvas);
+        assert_eq!(theme.muted, colors.canvas);
+        assert_eq!(theme.border, colors.canvas_text);
+        assert_eq!(theme.primary, colors.highlight);
+
+        // Buttons get an outline instead of relying on their fill
+        let button = ButtonStyle::from_theme(&theme);
+        assert!(button.border_width > 0.0);
+        assert_eq!(button.border_color.map(|c| c.normal), Some(theme.role(ColorRole::Border)));
+    }
+}
@@ -0,0 +1,228 @@
+//! Import themes from CSS custom property files
+//!
+//! Reads the `:root { --background: ...; }` / `.dark { ... }` format that
+//! shadcn/ui and Tailwind design systems export. Both the older bare HSL
+//! triplets (`--primary: 222.2 47.4% 11.2%;`) and full CSS colors
+//! (`--primary: oklch(0.205 0 0);`) are understood, as are `var(--name)`
+//! references. Palette entries the file leaves out keep their defaults.
+
+use std::collections::HashMap;
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+
+use crate::core::Color;
+use crate::theming::stylesheet::strip_comments;
+use crate::theming::{ThemeColors, ThemeConfig};
+
+/// Palette entries whose `-foreground` is derived when only the base is given
+const FOREGROUND_PAIRS: [&str; 10] = [
+    "card",
+    "popover",
+    "primary",
+    "secondary",
+    "muted",
+    "accent",
+    "destructive",
+    "sidebar",
+    "sidebar_primary",
+    "sidebar_accent",
+];
+
+type Declarations = Vec<(String, String)>;
+
+impl ThemeConfig {
+    /// Build a theme from CSS custom properties
+    pub fn from_css(source: &str) -> Result<Self> {
+        let mut light_vars = HashMap::new();
+        let mut dark_vars = HashMap::new();
+        for (selector, declarations) in parse_blocks(&strip_comments(source)) {
+            let target = if selector.contains("dark") {
+                &mut dark_vars
+            } else if selector.split(',').any(|s| s.trim() == ":root") {
+                &mut light_vars
+            } else {
+                continue;
+            };
+            for (name, value) in declarations {
+                if let Some(name) = name.strip_prefix("--") {
+                    target.insert(name.to_string(), value);
+                }
+            }
+        }
+        if light_vars.is_empty() && dark_vars.is_empty() {
+            bail!("No :root or .dark custom properties found");
+        }
+
+        let mut config = ThemeConfig::default();
+        apply_palette(&mut config.light, &light_vars, &light_vars)?;
+        apply_palette(&mut config.dark, &dark_vars, &light_vars)?;
+        config.calculate_sidebar_colors();
+
+        for (name, value) in &light_vars {
+            match name.as_str() {
+                "radius" => config.radius = parse_rem(value)?,
+                "font-sans" => config.font_sans = first_font(value).unwrap_or(config.font_sans),
+                "font-mono" => config.font_mono = first_font(value).unwrap_or(config.font_mono),
+                _ if !is_palette_name(name) => {
+                    config.css_variables.insert(name.clone(), value.clone());
+                }
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load_from_css_file(path: &str) -> Result<Self> {
+        let source = fs::read_to_string(path).with_context(|| format!("Failed to read theme file: {}", path))?;
+        Self::from_css(&source).with_context(|| format!("Failed to import CSS theme: {}", path))
+    }
+}
+
+/// Innermost `selector { name: value; ... }` blocks, in source order
+fn parse_blocks(source: &str) -> Vec<(String, Declarations)> {
+    let mut blocks = Vec::new();
+    let mut stack: Vec<(String, Declarations)> = Vec::new();
+    let mut buffer = String::new();
+
+    let flush = |buffer: &mut String, stack: &mut Vec<(String, Declarations)>| {
+        if let (Some((_, declarations)), Some((name, value))) = (stack.last_mut(), buffer.split_once(':')) {
+            declarations.push((name.trim().to_string(), value.trim().to_string()));
+        }
+        buffer.clear();
+    };
+
+    for c in source.chars() {
+        match c {
+            '{' => {
+                stack.push((buffer.trim().to_string(), Vec::new()));
+                buffer.clear();
+            }
+            ';' => flush(&mut buffer, &mut stack),
+            '}' => {
+                flush(&mut buffer, &mut stack);
+                if let Some(block) = stack.pop() {
+                    if !block.1.is_empty() {
+                        blocks.push(block);
+                    }
+                }
+            }
+            _ => buffer.push(c),
+        }
+    }
+    blocks
+}
+
+fn apply_palette(
+    palette: &mut ThemeColors,
+    vars: &HashMap<String, String>,
+    root: &HashMap<String, String>,
+) -> Result<()> {
+    for (name, value) in vars {
+        if !is_palette_name(name) {
+            continue;
+        }
+        let color = parse_css_color(value, vars, root)
+            .with_context(|| format!("Invalid value for --{}", name))?;
+        palette.set_color(&name.replace('-', "_"), color);
+    }
+
+    for base in FOREGROUND_PAIRS {
+        let css_name = base.replace('_', "-");
+        if vars.contains_key(&css_name) && !vars.contains_key(&format!("{}-foreground", css_name)) {
+            let text = palette.get_color(base).contrasting_text();
+            palette.set_color(&format!("{}_foreground", base), text);
+        }
+    }
+
+    // Let `calculate_sidebar_colors` fill in a sidebar the file doesn't define
+    if !vars.contains_key("sidebar") {
+        palette.sidebar = [0, 0, 0];
+    }
+    Ok(())
+}
+
+fn is_palette_name(name: &str) -> bool {
+    ThemeColors::COLOR_NAMES.contains(&name.replace('-', "_").as_str())
+}
+
+/// A CSS color, a bare `h s% l%` triplet, or a `var()` reference to either
+fn parse_css_color(value: &str, vars: &HashMap<String, String>, root: &HashMap<String, String>) -> Result<Color> {
+    let mut value = value.trim();
+    // Follow var() chains, with a bound in case of cycles
+    for _ in 0..8 {
+        let Some(name) = value.strip_prefix("var(--").and_then(|v| v.strip_suffix(')')) else {
+            break;
+        };
+        let name = name.split(',').next().unwrap_or(name).trim();
+        value = vars
+            .get(name)
+            .or_else(|| root.get(name))
+            .with_context(|| format!("Undefined variable --{}", name))?
+            .trim();
+    }
+
+    Color::parse(value).or_else(|err| {
+        if value.split_whitespace().count() >= 3 && !value.contains('(') {
+            Color::parse(&format!("hsl({})", value))
+        } else {
+            Err(err)
+        }
+    })
+}
+
+/// `0.5rem` or `8px` in rem
+fn parse_rem(value: &str) -> Result<f32> {
+    let value = value.trim();
+    let parsed = match value.strip_suffix("px") {
+        Some(px) => px.trim().parse::<f32>().map(|px| px / 16.0),
+        None => value.trim_end_matches("rem").trim().parse(),
+    };
+    parsed.with_context(|| format!("Invalid radius \"{}\"", value))
+}
+
+/// First concrete family of a font stack
+fn first_font(value: &str) -> Option<String> {
+    let family = value.split(',').next()?.trim().trim_matches(['\'', '"']);
+    (!family.is_empty() && !family.starts_with("var(")).then(|| family.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_shadcn_variables() {
+        let css = r#"
+            @layer base {
+              :root {
+                --background: 0 0% 100%;
+                --foreground: 222.2 84% 4.9%;
+                --primary: #2563eb;
+                --ring: var(--primary);
+                --radius: 0.75rem;
+                --font-sans: "Geist", sans-serif;
+                --brand-gradient: linear-gradient(red, blue);
+              }
+              .dark {
+                --background: oklch(0.145 0 0);
+                --primary: oklch(0.985 0 0);
+              }
+            }
+        "#;
+        let config = ThemeConfig::from_css(css).unwrap();
+
+        assert_eq!(config.light.background, [255, 255, 255]);
+        assert_eq!(config.light.primary, [0x25, 0x63, 0xeb]);
+        assert_eq!(config.light.ring, config.light.primary);
+        // Derived, since the file gives no --primary-foreground
+        assert_eq!(config.light.primary_foreground, [255, 255, 255]);
+        assert_eq!(config.dark.primary_foreground, [0, 0, 0]);
+        assert!(config.dark.background[0] < 40);
+        assert_eq!(config.radius, 0.75);
+        assert_eq!(config.font_sans, "Geist");
+        assert!(config.css_variables.contains_key("brand-gradient"));
+
+        assert!(ThemeConfig::from_css("body { color: red; }").is_err());
+    }
+}
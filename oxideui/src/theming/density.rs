@@ -0,0 +1,64 @@
+//! UI density
+//!
+//! One setting that scales default control heights, row heights and
+//! paddings across the widget set. Widgets ask `theme.density()` for sizes
+//! instead of hardcoding them; explicit sizes on a widget still win.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    Compact,
+    #[default]
+    Comfortable,
+    Spacious,
+}
+
+impl Density {
+    /// Factor applied to default paddings
+    pub fn scale(self) -> f32 {
+        match self {
+            Density::Compact => 0.75,
+            Density::Comfortable => 1.0,
+            Density::Spacious => 1.25,
+        }
+    }
+
+    /// `base` padding scaled for this density
+    pub fn padding(self, base: f32) -> f32 {
+        (base * self.scale()).round()
+    }
+
+    /// Height of buttons, inputs and dropdown triggers
+    pub fn control_height(self) -> f32 {
+        match self {
+            Density::Compact => 32.0,
+            Density::Comfortable => 40.0,
+            Density::Spacious => 48.0,
+        }
+    }
+
+    /// Height of menu and list items
+    pub fn item_height(self) -> f32 {
+        match self {
+            Density::Compact => 28.0,
+            Density::Comfortable => 32.0,
+            Density::Spacious => 40.0,
+        }
+    }
+
+    /// Height of a table body row
+    pub fn table_row_height(self) -> f32 {
+        match self {
+            Density::Compact => 32.0,
+            Density::Comfortable => 48.0,
+            Density::Spacious => 56.0,
+        }
+    }
+
+    /// Height of a table header row
+    pub fn table_header_height(self) -> f32 {
+        self.table_row_height() + 8.0
+    }
+}
@@ -1,40 +1,115 @@
+mod contrast;
+mod css_import;
 mod default_theme;
+mod density;
+mod stylesheet;
+mod theme_controller;
 mod theme_loader;
+pub mod tokens;
+mod typography;
+mod widget_styles;
 
+use serde::{Deserialize, Serialize};
+
+pub use contrast::SystemColors;
+pub use density::Density;
 pub use default_theme::{LIGHT_THEME, DARK_THEME, ColorRGB, Theme as DefaultTheme};
+pub use stylesheet::{Selector, StyleProperties, StyleState, Stylesheet};
+pub use theme_controller::{ThemeController, ThemeRequest};
+pub use tokens::{ColorRole, DesignTokens, FontSizeScale, RadiusScale, SpacingScale, TokenConfig};
 pub use theme_loader::{ThemeConfig, ThemeColors, load_theme_from_file};
+pub use typography::{TextVariant, TypeStyle, Typography};
+pub use widget_styles::{ButtonStyle, ButtonVariant, CardStyle, InputStyle, StateColors, WidgetStyles};
+
+/// Which palette of a `ThemeConfig` is in use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    /// Follow the OS light/dark preference, including live changes
+    #[default]
+    System,
+}
+
+impl ThemeMode {
+    /// Whether the dark palette applies, given the OS preference
+    pub fn resolve(self, system_dark: bool) -> bool {
+        match self {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::System => system_dark,
+        }
+    }
+}
 
 pub struct ThemeManager {
     config: ThemeConfig,
-    is_dark: bool,
+    mode: ThemeMode,
+    system_dark: bool,
 }
 
 impl ThemeManager {
     pub fn new(config: ThemeConfig, is_dark: bool) -> Self {
-        Self { config, is_dark }
+        let mode = if is_dark { ThemeMode::Dark } else { ThemeMode::Light };
+        Self { config, mode, system_dark: false }
+    }
+
+    pub fn with_mode(config: ThemeConfig, mode: ThemeMode) -> Self {
+        Self { config, mode, system_dark: false }
+    }
+
+    pub fn mode(&self) -> ThemeMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: ThemeMode) {
+        self.mode = mode;
+    }
+
+    /// Record the OS preference; only affects `ThemeMode::System`
+    pub fn set_system_dark(&mut self, dark: bool) {
+        self.system_dark = dark;
     }
 
     pub fn toggle_dark_mode(&mut self) {
-        self.is_dark = !self.is_dark;
+        self.set_dark_mode(!self.is_dark());
     }
 
     pub fn set_dark_mode(&mut self, dark: bool) {
-        self.is_dark = dark;
+        self.mode = if dark { ThemeMode::Dark } else { ThemeMode::Light };
     }
 
     pub fn is_dark(&self) -> bool {
-        self.is_dark
+        self.mode.resolve(self.system_dark)
     }
 
     pub fn get_css_variables(&self) -> String {
-        self.config.to_css_variables(self.is_dark)
+        self.config.to_css_variables(self.is_dark())
     }
 
     pub fn get_current_colors(&self) -> &ThemeColors {
-        if self.is_dark {
+        if self.is_dark() {
             &self.config.dark
         } else {
             &self.config.light
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_mode_follows_os_preference() {
+        let mut manager = ThemeManager::with_mode(ThemeConfig::default(), ThemeMode::System);
+        assert!(!manager.is_dark());
+        manager.set_system_dark(true);
+        assert!(manager.is_dark());
+
+        // An explicit choice overrides the OS
+        manager.toggle_dark_mode();
+        assert_eq!(manager.mode(), ThemeMode::Light);
+        assert!(!manager.is_dark());
+    }
+}
@@ -0,0 +1,338 @@
+//! Class-based styling
+//!
+//! A `Stylesheet` maps selectors such as `Button`, `.primary` or
+//! `Button.primary:hover` to property sets. Widgets that carry class names
+//! ask the build context to resolve their style; matching rules are applied
+//! from lowest to highest specificity, with later rules winning ties, as in
+//! CSS.
+//!
+//! ```text
+//! /* app.oxss */
+//! Button { background: #2196f3; color: white; radius: 6; }
+//! Button.danger, .error { background: #dc2626; }
+//! Button.primary:active { background: hsl(224 76% 48%); }
+//! Container.card { padding: 16px; border-color: #e5e7eb; }
+//! ```
+
+use std::fs;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::core::render_object::Color;
+
+/// Interaction states usable as `:pseudo` selectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StyleState {
+    pub hover: bool,
+    pub active: bool,
+    pub focus: bool,
+    pub disabled: bool,
+}
+
+impl StyleState {
+    fn has(&self, pseudo: &str) -> bool {
+        match pseudo {
+            "hover" => self.hover,
+            "active" | "pressed" => self.active,
+            "focus" => self.focus,
+            "disabled" => self.disabled,
+            _ => false,
+        }
+    }
+}
+
+/// A compound selector: optional widget type, classes and pseudo states
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub widget: Option<String>,
+    pub classes: Vec<String>,
+    pub states: Vec<String>,
+}
+
+impl Selector {
+    /// Parse `Button.primary:hover`, `.primary`, `Button` or `*`
+    pub fn parse(source: &str) -> Result<Self> {
+        let source = source.trim();
+        if source.is_empty() {
+            bail!("Empty selector");
+        }
+        if source.contains(char::is_whitespace) {
+            bail!("Descendant selectors are not supported: \"{}\"", source);
+        }
+
+        let mut selector = Selector {
+            widget: None,
+            classes: Vec::new(),
+            states: Vec::new(),
+        };
+        let mut rest = source;
+        let type_end = rest.find(['.', ':']).unwrap_or(rest.len());
+        let widget = &rest[..type_end];
+        if !widget.is_empty() && widget != "*" {
+            selector.widget = Some(widget.to_string());
+        }
+        rest = &rest[type_end..];
+
+        while let Some(marker) = rest.chars().next() {
+            let body = &rest[1..];
+            let end = body.find(['.', ':']).unwrap_or(body.len());
+            let name = &body[..end];
+            if name.is_empty() {
+                bail!("Invalid selector \"{}\"", source);
+            }
+            match marker {
+                '.' => selector.classes.push(name.to_string()),
+                _ => selector.states.push(name.to_string()),
+            }
+            rest = &body[end..];
+        }
+        Ok(selector)
+    }
+
+    /// CSS specificity without ids: (classes + pseudo states, type)
+    pub fn specificity(&self) -> (usize, usize) {
+        (self.classes.len() + self.states.len(), self.widget.is_some() as usize)
+    }
+
+    pub fn matches(&self, widget: &str, classes: &[String], state: StyleState) -> bool {
+        self.widget.as_deref().is_none_or(|w| w == widget)
+            && self.classes.iter().all(|c| classes.contains(c))
+            && self.states.iter().all(|s| state.has(s))
+    }
+}
+
+/// Visual properties a rule can set; unset fields fall through
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StyleProperties {
+    pub background: Option<Color>,
+    pub foreground: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_width: Option<f32>,
+    pub padding: Option<f32>,
+    pub radius: Option<f32>,
+    pub font_size: Option<f32>,
+}
+
+impl StyleProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn with_foreground(mut self, color: Color) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: Color) -> Self {
+        self.border_width = Some(width);
+        self.border_color = Some(color);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Overlay `other` on top of `self`
+    pub fn merge(&mut self, other: &StyleProperties) {
+        self.background = other.background.or(self.background);
+        self.foreground = other.foreground.or(self.foreground);
+        self.border_color = other.border_color.or(self.border_color);
+        self.border_width = other.border_width.or(self.border_width);
+        self.padding = other.padding.or(self.padding);
+        self.radius = other.radius.or(self.radius);
+        self.font_size = other.font_size.or(self.font_size);
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "background" | "background-color" => self.background = Some(parse_color(value)?),
+            "color" | "foreground" => self.foreground = Some(parse_color(value)?),
+            "border-color" => self.border_color = Some(parse_color(value)?),
+            "border-width" => self.border_width = Some(parse_length(value)?),
+            "padding" => self.padding = Some(parse_length(value)?),
+            "radius" | "border-radius" => self.radius = Some(parse_length(value)?),
+            "font-size" => self.font_size = Some(parse_length(value)?),
+            _ => bail!("Unknown style property \"{}\"", name),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StyleRule {
+    selector: Selector,
+    properties: StyleProperties,
+}
+
+/// Ordered set of style rules
+#[derive(Debug, Clone, Default)]
+pub struct Stylesheet {
+    rules: Vec<StyleRule>,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule in code; panics on a malformed selector
+    pub fn with_rule(mut self, selector: &str, properties: StyleProperties) -> Self {
+        self.add_rule(selector, properties)
+            .unwrap_or_else(|e| panic!("{}", e));
+        self
+    }
+
+    /// Add a rule for each comma-separated selector
+    pub fn add_rule(&mut self, selector: &str, properties: StyleProperties) -> Result<()> {
+        for part in selector.split(',') {
+            self.rules.push(StyleRule {
+                selector: Selector::parse(part)?,
+                properties,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self> {
+        let source = fs::read_to_string(path).with_context(|| format!("Failed to read stylesheet: {}", path))?;
+        Self::parse(&source).with_context(|| format!("Failed to parse stylesheet: {}", path))
+    }
+
+    /// Parse `selector, selector { property: value; ... }` blocks
+    pub fn parse(source: &str) -> Result<Self> {
+        let source = strip_comments(source);
+        let mut sheet = Stylesheet::new();
+        let mut rest = source.as_str();
+
+        while let Some(open) = rest.find('{') {
+            let close = rest[open..]
+                .find('}')
+                .map(|i| open + i)
+                .ok_or_else(|| anyhow!("Unclosed block after \"{}\"", rest[..open].trim()))?;
+
+            let mut properties = StyleProperties::new();
+            for declaration in rest[open + 1..close].split(';') {
+                let declaration = declaration.trim();
+                if declaration.is_empty() {
+                    continue;
+                }
+                let (name, value) = declaration
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("Expected \"property: value\", found \"{}\"", declaration))?;
+                properties.set(name.trim(), value.trim())?;
+            }
+
+            sheet.add_rule(&rest[..open], properties)?;
+            rest = &rest[close + 1..];
+        }
+
+        if !rest.trim().is_empty() {
+            bail!("Unexpected trailing content \"{}\"", rest.trim());
+        }
+        Ok(sheet)
+    }
+
+    /// Append `other`'s rules, so they win ties against ours
+    pub fn extend(&mut self, other: Stylesheet) {
+        self.rules.extend(other.rules);
+    }
+
+    /// Combined properties of every rule matching the widget
+    pub fn resolve(&self, widget: &str, classes: &[String], state: StyleState) -> StyleProperties {
+        let mut matching: Vec<(usize, &StyleRule)> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.selector.matches(widget, classes, state))
+            .collect();
+        // Stable by source order within equal specificity
+        matching.sort_by_key(|(order, rule)| (rule.selector.specificity(), *order));
+
+        let mut resolved = StyleProperties::new();
+        for (_, rule) in matching {
+            resolved.merge(&rule.properties);
+        }
+        resolved
+    }
+}
+
+pub(super) fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = rest[start + 2..].find("*/").map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_length(value: &str) -> Result<f32> {
+    value
+        .trim_end_matches("px")
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid length \"{}\"", value))
+}
+
+fn parse_color(value: &str) -> Result<Color> {
+    Color::parse(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selector() {
+        let selector = Selector::parse("Button.primary:hover").unwrap();
+        assert_eq!(selector.widget.as_deref(), Some("Button"));
+        assert_eq!(selector.classes, vec!["primary"]);
+        assert_eq!(selector.states, vec!["hover"]);
+        assert_eq!(selector.specificity(), (2, 1));
+        assert!(Selector::parse("Button .primary").is_err());
+    }
+
+    #[test]
+    fn test_resolve_by_specificity_then_order() {
+        let sheet = Stylesheet::parse(
+            "
+            /* base */
+            Button.primary:hover { background: #00f; }
+            Button { background: #fff; padding: 8px; }
+            .primary { background: #f00; }
+            Button.primary { color: #000; }
+            ",
+        )
+        .unwrap();
+        let classes = vec!["primary".to_string()];
+
+        let idle = sheet.resolve("Button", &classes, StyleState::default());
+        assert_eq!(idle.background, Some(Color::rgb(255, 0, 0)));
+        assert_eq!(idle.foreground, Some(Color::BLACK));
+        assert_eq!(idle.padding, Some(8.0));
+
+        let hovered = sheet.resolve("Button", &classes, StyleState { hover: true, ..Default::default() });
+        assert_eq!(hovered.background, Some(Color::rgb(0, 0, 255)));
+
+        let other = sheet.resolve("Container", &[], StyleState::default());
+        assert_eq!(other, StyleProperties::default());
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::theming::{Density, ThemeConfig, ThemeMode};
+
+/// Theme change queued by a widget, applied by the runtime before the next frame
+#[derive(Debug, Clone)]
+pub enum ThemeRequest {
+    SetConfig(ThemeConfig),
+    SetMode(ThemeMode),
+    ToggleDarkMode,
+    SetDensity(Density),
+    SetForcedColors(bool),
+}
+
+/// Handle widgets use to change the app theme while it runs
+#[derive(Clone, Default)]
+pub struct ThemeController {
+    requests: Arc<Mutex<Vec<ThemeRequest>>>,
+}
+
+impl ThemeController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the theme config; the current light/dark mode is kept
+    pub fn set_theme(&self, config: ThemeConfig) {
+        self.requests.lock().push(ThemeRequest::SetConfig(config));
+    }
+
+    pub fn set_mode(&self, mode: ThemeMode) {
+        self.requests.lock().push(ThemeRequest::SetMode(mode));
+    }
+
+    /// Switch between the light and dark palettes
+    pub fn toggle_dark_mode(&self) {
+        self.requests.lock().push(ThemeRequest::ToggleDarkMode);
+    }
+
+    /// Change the density of the current theme
+    pub fn set_density(&self, density: Density) {
+        self.requests.lock().push(ThemeRequest::SetDensity(density));
+    }
+
+    /// Turn forced-colors mode on or off, overriding the OS setting
+    pub fn set_forced_colors(&self, forced: bool) {
+        self.requests.lock().push(ThemeRequest::SetForcedColors(forced));
+    }
+
+    /// Requests queued since the last call, in order
+    pub(crate) fn take_requests(&self) -> Vec<ThemeRequest> {
+        std::mem::take(&mut *self.requests.lock())
+    }
+}
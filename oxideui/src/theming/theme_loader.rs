@@ -3,6 +3,9 @@ use std::fs;
 use std::collections::HashMap;
 use anyhow::{Result, Context};
 use crate::core::Color;
+use crate::theming::density::Density;
+use crate::theming::tokens::TokenConfig;
+use crate::theming::typography::Typography;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
@@ -18,6 +21,15 @@ pub struct ThemeConfig {
     pub radius: f32,
     #[serde(default)]
     pub is_dark: bool,
+    /// Design token overrides; unset tokens are derived from the palette
+    #[serde(default)]
+    pub tokens: TokenConfig,
+    /// Sizes, weights and line heights per text role
+    #[serde(default)]
+    pub typography: Typography,
+    /// Scales default control sizes, row heights and paddings
+    #[serde(default)]
+    pub density: Density,
 }
 
 fn default_font_sans() -> String {
@@ -32,73 +44,107 @@ fn default_radius() -> f32 {
     0.5
 }
 
+/// One palette. Colors are `[r, g, b]` arrays, or in a theme file any
+/// string `Color::parse` accepts, e.g. `"#2196f3"` or `"hsl(210 40% 98%)"`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeColors {
     // Background
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub background: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub foreground: [u8; 3],
 
     // Cards
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub card: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub card_foreground: [u8; 3],
 
     // Popover
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub popover: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub popover_foreground: [u8; 3],
 
     // Primary
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub primary: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub primary_foreground: [u8; 3],
 
     // Secondary
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub secondary: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub secondary_foreground: [u8; 3],
 
     // Muted
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub muted: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub muted_foreground: [u8; 3],
 
     // Accent
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub accent: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub accent_foreground: [u8; 3],
 
     // Destructive
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub destructive: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub destructive_foreground: [u8; 3],
 
     // Borders & Inputs
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub border: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub input: [u8; 3],
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub ring: [u8; 3],
 
     // Charts
     #[serde(default = "default_charts")]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub chart_1: [u8; 3],
     #[serde(default = "default_charts")]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub chart_2: [u8; 3],
     #[serde(default = "default_charts")]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub chart_3: [u8; 3],
     #[serde(default = "default_charts")]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub chart_4: [u8; 3],
     #[serde(default = "default_charts")]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub chart_5: [u8; 3],
 
     // Sidebar (Radix UI inspired)
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_foreground: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_primary: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_primary_foreground: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_accent: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_accent_foreground: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_border: [u8; 3],
     #[serde(default)]
+    #[serde(deserialize_with = "rgb::deserialize")]
     pub sidebar_ring: [u8; 3],
 
     // Shadows (like shadcn)
@@ -114,6 +160,28 @@ pub struct ThemeColors {
     pub shadow_opacity: f32,
 }
 
+mod rgb {
+    use serde::{Deserialize, Deserializer};
+
+    use crate::core::Color;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RgbValue {
+        Array([u8; 3]),
+        Css(String),
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 3], D::Error> {
+        match RgbValue::deserialize(deserializer)? {
+            RgbValue::Array(rgb) => Ok(rgb),
+            RgbValue::Css(source) => Color::parse(&source)
+                .map(|color| [color.r, color.g, color.b])
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 fn default_charts() -> [u8; 3] {
     [0, 0, 0]
 }
@@ -128,8 +196,12 @@ impl ThemeConfig {
     pub fn load_from_file(path: &str) -> Result<Self> {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read theme file: {}", path))?;
-        let mut theme: Self = serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse theme JSON: {}", path))?;
+        Self::from_json(&content).with_context(|| format!("Failed to load theme file: {}", path))
+    }
+
+    /// Parse a theme from the JSON `load_from_file` reads
+    pub fn from_json(content: &str) -> Result<Self> {
+        let mut theme: Self = serde_json::from_str(content).context("Failed to parse theme JSON")?;
 
         // Auto-calculate sidebar colors if not provided
         theme.calculate_sidebar_colors();
@@ -137,7 +209,7 @@ impl ThemeConfig {
         Ok(theme)
     }
 
-    fn calculate_sidebar_colors(&mut self) {
+    pub(super) fn calculate_sidebar_colors(&mut self) {
         // If sidebar colors aren't set, calculate them from other colors
         if self.light.sidebar == [0, 0, 0] {
             // Light sidebar is slightly darker than background
@@ -319,15 +391,59 @@ impl Default for ThemeConfig {
             font_mono: "JetBrains Mono".to_string(),
             radius: 0.5,
             is_dark: false,
+            tokens: TokenConfig::default(),
+            typography: Typography::default(),
+            density: Density::default(),
         }
     }
 }
 
+/// Load a JSON theme, or a shadcn-style CSS variable file if `path` ends in `.css`
 pub fn load_theme_from_file(path: &str) -> Result<ThemeConfig> {
-    ThemeConfig::load_from_file(path)
+    if path.ends_with(".css") {
+        ThemeConfig::load_from_css_file(path)
+    } else {
+        ThemeConfig::load_from_file(path)
+    }
 }
 
 impl ThemeColors {
+    /// Names accepted by `get_color` and `set_color`
+    pub const COLOR_NAMES: [&'static str; 32] = [
+        "background",
+        "foreground",
+        "card",
+        "card_foreground",
+        "popover",
+        "popover_foreground",
+        "primary",
+        "primary_foreground",
+        "secondary",
+        "secondary_foreground",
+        "muted",
+        "muted_foreground",
+        "accent",
+        "accent_foreground",
+        "destructive",
+        "destructive_foreground",
+        "border",
+        "input",
+        "ring",
+        "sidebar",
+        "sidebar_foreground",
+        "sidebar_primary",
+        "sidebar_primary_foreground",
+        "sidebar_accent",
+        "sidebar_accent_foreground",
+        "sidebar_border",
+        "sidebar_ring",
+        "chart_1",
+        "chart_2",
+        "chart_3",
+        "chart_4",
+        "chart_5",
+    ];
+
     pub fn get_color(&self, name: &str) -> Color {
         let rgb = match name {
             "background" => self.background,
@@ -366,4 +482,45 @@ impl ThemeColors {
         };
         Color::rgb(rgb[0], rgb[1], rgb[2])
     }
-}
\ No newline at end of file
+
+    /// Set a palette entry by field name; false if there is no such entry
+    pub fn set_color(&mut self, name: &str, color: Color) -> bool {
+        let slot = match name {
+            "background" => &mut self.background,
+            "foreground" => &mut self.foreground,
+            "card" => &mut self.card,
+            "card_foreground" => &mut self.card_foreground,
+            "popover" => &mut self.popover,
+            "popover_foreground" => &mut self.popover_foreground,
+            "primary" => &mut self.primary,
+            "primary_foreground" => &mut self.primary_foreground,
+            "secondary" => &mut self.secondary,
+            "secondary_foreground" => &mut self.secondary_foreground,
+            "muted" => &mut self.muted,
+            "muted_foreground" => &mut self.muted_foreground,
+            "accent" => &mut self.accent,
+            "accent_foreground" => &mut self.accent_foreground,
+            "destructive" => &mut self.destructive,
+            "destructive_foreground" => &mut self.destructive_foreground,
+            "border" => &mut self.border,
+            "input" => &mut self.input,
+            "ring" => &mut self.ring,
+            "sidebar" => &mut self.sidebar,
+            "sidebar_foreground" => &mut self.sidebar_foreground,
+            "sidebar_primary" => &mut self.sidebar_primary,
+            "sidebar_primary_foreground" => &mut self.sidebar_primary_foreground,
+            "sidebar_accent" => &mut self.sidebar_accent,
+            "sidebar_accent_foreground" => &mut self.sidebar_accent_foreground,
+            "sidebar_border" => &mut self.sidebar_border,
+            "sidebar_ring" => &mut self.sidebar_ring,
+            "chart_1" => &mut self.chart_1,
+            "chart_2" => &mut self.chart_2,
+            "chart_3" => &mut self.chart_3,
+            "chart_4" => &mut self.chart_4,
+            "chart_5" => &mut self.chart_5,
+            _ => return false,
+        };
+        *slot = [color.r, color.g, color.b];
+        true
+    }
+}
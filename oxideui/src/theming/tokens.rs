@@ -0,0 +1,304 @@
+//! Design tokens
+//!
+//! Named scales and semantic color roles that widgets reference instead of
+//! raw pixel values and palette entries. Tokens are derived from the theme
+//! palette, then any overrides in `ThemeConfig::tokens` are applied, so one
+//! token change restyles every widget that uses it.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::animation::Interpolate;
+use crate::core::render_object::Color;
+use crate::core::Theme;
+
+/// Spacing steps in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpacingScale {
+    pub xs: f32,
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+    pub xxl: f32,
+}
+
+impl Default for SpacingScale {
+    fn default() -> Self {
+        Self {
+            xs: 4.0,
+            sm: 8.0,
+            md: 12.0,
+            lg: 16.0,
+            xl: 24.0,
+            xxl: 32.0,
+        }
+    }
+}
+
+/// Corner radius steps in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RadiusScale {
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+    /// Pill / circle
+    pub full: f32,
+}
+
+impl RadiusScale {
+    /// shadcn-style scale around a base radius given in rem
+    pub fn from_base_rem(radius: f32) -> Self {
+        let lg = radius * 16.0;
+        Self {
+            sm: (lg - 4.0).max(0.0),
+            md: (lg - 2.0).max(0.0),
+            lg,
+            xl: lg + 4.0,
+            full: 9999.0,
+        }
+    }
+}
+
+impl Default for RadiusScale {
+    fn default() -> Self {
+        Self::from_base_rem(0.5)
+    }
+}
+
+/// Font size steps in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FontSizeScale {
+    pub xs: f32,
+    pub sm: f32,
+    pub base: f32,
+    pub lg: f32,
+    pub xl: f32,
+    pub xxl: f32,
+}
+
+impl Default for FontSizeScale {
+    fn default() -> Self {
+        Self {
+            xs: 12.0,
+            sm: 13.0,
+            base: 14.0,
+            lg: 18.0,
+            xl: 20.0,
+            xxl: 24.0,
+        }
+    }
+}
+
+/// Semantic color roles, named `group.variant` in config files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorRole {
+    Surface,
+    SurfaceHover,
+    SurfacePressed,
+    SurfaceRaised,
+    SurfaceSunken,
+    Text,
+    TextMuted,
+    TextOnAccent,
+    Border,
+    BorderFocus,
+    Accent,
+    AccentHover,
+    AccentPressed,
+    Danger,
+    DangerHover,
+    TextOnDanger,
+    Selection,
+}
+
+impl ColorRole {
+    pub const ALL: [ColorRole; 17] = [
+        ColorRole::Surface,
+        ColorRole::SurfaceHover,
+        ColorRole::SurfacePressed,
+        ColorRole::SurfaceRaised,
+        ColorRole::SurfaceSunken,
+        ColorRole::Text,
+        ColorRole::TextMuted,
+        ColorRole::TextOnAccent,
+        ColorRole::Border,
+        ColorRole::BorderFocus,
+        ColorRole::Accent,
+        ColorRole::AccentHover,
+        ColorRole::AccentPressed,
+        ColorRole::Danger,
+        ColorRole::DangerHover,
+        ColorRole::TextOnDanger,
+        ColorRole::Selection,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorRole::Surface => "surface",
+            ColorRole::SurfaceHover => "surface.hover",
+            ColorRole::SurfacePressed => "surface.pressed",
+            ColorRole::SurfaceRaised => "surface.raised",
+            ColorRole::SurfaceSunken => "surface.sunken",
+            ColorRole::Text => "text",
+            ColorRole::TextMuted => "text.muted",
+            ColorRole::TextOnAccent => "text.on-accent",
+            ColorRole::Border => "border",
+            ColorRole::BorderFocus => "border.focus",
+            ColorRole::Accent => "accent",
+            ColorRole::AccentHover => "accent.hover",
+            ColorRole::AccentPressed => "accent.pressed",
+            ColorRole::Danger => "danger",
+            ColorRole::DangerHover => "danger.hover",
+            ColorRole::TextOnDanger => "text.on-danger",
+            ColorRole::Selection => "selection",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|role| role.name() == name)
+    }
+}
+
+/// Token overrides in a theme file; anything omitted is derived
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenConfig {
+    #[serde(default)]
+    pub spacing: Option<SpacingScale>,
+    #[serde(default)]
+    pub radii: Option<RadiusScale>,
+    #[serde(default)]
+    pub font_sizes: Option<FontSizeScale>,
+    /// Role name (e.g. `"surface.hover"`) to RGB
+    #[serde(default)]
+    pub colors: HashMap<String, [u8; 3]>,
+}
+
+/// Resolved tokens for one palette (light or dark)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignTokens {
+    pub spacing: SpacingScale,
+    pub radii: RadiusScale,
+    pub font_sizes: FontSizeScale,
+    colors: HashMap<ColorRole, Color>,
+}
+
+impl DesignTokens {
+    /// Derive tokens from a theme palette, then apply `overrides`
+    pub fn derive(theme: &Theme, overrides: &TokenConfig) -> Self {
+        // Interaction shades move toward the foreground, so they read on
+        // both light and dark surfaces
+        let shade = |color: Color, amount: f32| color.mix(theme.foreground, amount);
+
+        let mut colors = HashMap::from([
+            (ColorRole::Surface, theme.background),
+            (ColorRole::SurfaceHover, shade(theme.background, 0.05)),
+            (ColorRole::SurfacePressed, shade(theme.background, 0.10)),
+            (ColorRole::SurfaceRaised, theme.card),
+            (ColorRole::SurfaceSunken, theme.muted),
+            (ColorRole::Text, theme.foreground),
+            (ColorRole::TextMuted, theme.muted_foreground),
+            (ColorRole::TextOnAccent, theme.primary_foreground),
+            (ColorRole::Border, theme.border),
+            (ColorRole::BorderFocus, theme.ring),
+            (ColorRole::Accent, theme.primary),
+            (ColorRole::AccentHover, shade(theme.primary, 0.10)),
+            (ColorRole::AccentPressed, shade(theme.primary, 0.20)),
+            (ColorRole::Danger, theme.destructive),
+            (ColorRole::DangerHover, shade(theme.destructive, 0.10)),
+            (ColorRole::TextOnDanger, theme.destructive_foreground),
+            (ColorRole::Selection, theme.primary.with_alpha(64)),
+        ]);
+        for (name, [r, g, b]) in &overrides.colors {
+            match ColorRole::from_name(name) {
+                Some(role) => {
+                    colors.insert(role, Color::rgb(*r, *g, *b));
+                }
+                None => eprintln!("⚠️ Unknown color token \"{}\"", name),
+            }
+        }
+
+        Self {
+            spacing: overrides.spacing.unwrap_or_default(),
+            radii: overrides
+                .radii
+                .unwrap_or_else(|| RadiusScale::from_base_rem(theme.radius)),
+            font_sizes: overrides.font_sizes.unwrap_or_default(),
+            colors,
+        }
+    }
+
+    pub fn color(&self, role: ColorRole) -> Color {
+        self.colors.get(&role).copied().unwrap_or(Color::TRANSPARENT)
+    }
+
+    pub fn set_color(&mut self, role: ColorRole, color: Color) {
+        self.colors.insert(role, color);
+    }
+
+    /// Scales only, no colors; used while a theme is being assembled
+    pub(crate) fn empty() -> Self {
+        Self {
+            spacing: SpacingScale::default(),
+            radii: RadiusScale::default(),
+            font_sizes: FontSizeScale::default(),
+            colors: HashMap::new(),
+        }
+    }
+}
+
+impl Interpolate for DesignTokens {
+    /// Colors blend role by role; scales switch at the midpoint
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        let snap = if t < 0.5 { self } else { other };
+        let colors = other
+            .colors
+            .iter()
+            .map(|(role, to)| {
+                let from = self.colors.get(role).unwrap_or(to);
+                (*role, from.interpolate(to, t))
+            })
+            .collect();
+        Self {
+            spacing: snap.spacing,
+            radii: snap.radii,
+            font_sizes: snap.font_sizes,
+            colors,
+        }
+    }
+}
+
+impl Default for DesignTokens {
+    fn default() -> Self {
+        Self::derive(&Theme::default(), &TokenConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theming::ThemeConfig;
+
+    #[test]
+    fn test_overrides_replace_derived_tokens() {
+        let theme = Theme::from_config(&ThemeConfig::default(), false);
+        let overrides = TokenConfig {
+            colors: HashMap::from([("surface.hover".to_string(), [1, 2, 3])]),
+            ..Default::default()
+        };
+
+        let tokens = DesignTokens::derive(&theme, &overrides);
+        assert_eq!(tokens.color(ColorRole::SurfaceHover), Color::rgb(1, 2, 3));
+        assert_eq!(tokens.color(ColorRole::Accent), theme.primary);
+        assert_eq!(tokens.radii.lg, theme.radius * 16.0);
+    }
+
+    #[test]
+    fn test_role_names_round_trip() {
+        for role in ColorRole::ALL {
+            assert_eq!(ColorRole::from_name(role.name()), Some(role));
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Typography scale
+//!
+//! Size, weight and line height for each text role. Lives in
+//! `ThemeConfig::typography`; any role a theme file omits keeps its default.
+
+use serde::{Deserialize, Serialize};
+
+/// Text roles with an entry in the typography scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextVariant {
+    H1,
+    H2,
+    H3,
+    H4,
+    H5,
+    H6,
+    Body,
+    Caption,
+    /// Rendered in the theme's monospace font
+    Code,
+}
+
+impl TextVariant {
+    /// Heading for `level` 1–6, clamped
+    pub fn heading(level: u8) -> Self {
+        match level {
+            0 | 1 => TextVariant::H1,
+            2 => TextVariant::H2,
+            3 => TextVariant::H3,
+            4 => TextVariant::H4,
+            5 => TextVariant::H5,
+            _ => TextVariant::H6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TypeStyle {
+    pub size: f32,
+    /// CSS-style weight, 100–900
+    #[serde(default = "default_weight")]
+    pub weight: u16,
+    /// Multiple of `size`
+    #[serde(default = "default_line_height")]
+    pub line_height: f32,
+}
+
+fn default_weight() -> u16 {
+    400
+}
+
+fn default_line_height() -> f32 {
+    1.5
+}
+
+impl TypeStyle {
+    pub const fn new(size: f32, weight: u16, line_height: f32) -> Self {
+        Self {
+            size,
+            weight,
+            line_height,
+        }
+    }
+
+    /// Renderers only distinguish regular and bold
+    pub fn is_bold(&self) -> bool {
+        self.weight >= 600
+    }
+
+    /// Line height in logical pixels
+    pub fn line_height_px(&self) -> f32 {
+        self.size * self.line_height
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Typography {
+    pub h1: TypeStyle,
+    pub h2: TypeStyle,
+    pub h3: TypeStyle,
+    pub h4: TypeStyle,
+    pub h5: TypeStyle,
+    pub h6: TypeStyle,
+    pub body: TypeStyle,
+    pub caption: TypeStyle,
+    pub code: TypeStyle,
+}
+
+impl Typography {
+    pub fn get(&self, variant: TextVariant) -> TypeStyle {
+        match variant {
+            TextVariant::H1 => self.h1,
+            TextVariant::H2 => self.h2,
+            TextVariant::H3 => self.h3,
+            TextVariant::H4 => self.h4,
+            TextVariant::H5 => self.h5,
+            TextVariant::H6 => self.h6,
+            TextVariant::Body => self.body,
+            TextVariant::Caption => self.caption,
+            TextVariant::Code => self.code,
+        }
+    }
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            h1: TypeStyle::new(48.0, 800, 1.1),
+            h2: TypeStyle::new(36.0, 700, 1.2),
+            h3: TypeStyle::new(30.0, 600, 1.25),
+            h4: TypeStyle::new(24.0, 500, 1.3),
+            h5: TypeStyle::new(20.0, 500, 1.4),
+            h6: TypeStyle::new(18.0, 500, 1.4),
+            body: TypeStyle::new(14.0, 400, 1.5),
+            caption: TypeStyle::new(12.0, 400, 1.4),
+            code: TypeStyle::new(13.0, 400, 1.5),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_typography_keeps_defaults() {
+        let typography: Typography = serde_json::from_str(r#"{ "h1": { "size": 40 } }"#).unwrap();
+
+        assert_eq!(typography.h1, TypeStyle::new(40.0, 400, 1.5));
+        assert_eq!(typography.body, Typography::default().body);
+        assert_eq!(typography.get(TextVariant::heading(9)), typography.h6);
+    }
+}
@@ -0,0 +1,463 @@
+//! Per-widget style bundles
+//!
+//! `ButtonStyle`, `InputStyle` and `CardStyle` gather every visual knob of a
+//! widget in one place. Set one on a widget with `with_style`, or register
+//! app-wide defaults with `Runtime::with_widget_styles`; otherwise each is
+//! derived from the theme's design tokens.
+
+use crate::core::render_object::Color;
+use crate::core::Theme;
+use crate::layout::constraints::EdgeInsets;
+use crate::theming::{ColorRole, StyleState};
+
+/// A color with optional per-state variants
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateColors {
+    pub normal: Color,
+    pub hover: Option<Color>,
+    pub pressed: Option<Color>,
+    pub focused: Option<Color>,
+    pub disabled: Option<Color>,
+}
+
+impl StateColors {
+    pub fn new(normal: Color) -> Self {
+        Self {
+            normal,
+            hover: None,
+            pressed: None,
+            focused: None,
+            disabled: None,
+        }
+    }
+
+    pub fn with_hover(mut self, color: Color) -> Self {
+        self.hover = Some(color);
+        self
+    }
+
+    pub fn with_pressed(mut self, color: Color) -> Self {
+        self.pressed = Some(color);
+        self
+    }
+
+    pub fn with_focused(mut self, color: Color) -> Self {
+        self.focused = Some(color);
+        self
+    }
+
+    pub fn with_disabled(mut self, color: Color) -> Self {
+        self.disabled = Some(color);
+        self
+    }
+
+    /// Color for `state`; disabled wins, then pressed, hover and focused
+    pub fn resolve(&self, state: StyleState) -> Color {
+        let variant = if state.disabled {
+            self.disabled
+        } else if state.active {
+            self.pressed.or(self.hover)
+        } else if state.hover {
+            self.hover
+        } else if state.focus {
+            self.focused
+        } else {
+            None
+        };
+        variant.unwrap_or(self.normal)
+    }
+}
+
+impl From<Color> for StateColors {
+    fn from(color: Color) -> Self {
+        StateColors::new(color)
+    }
+}
+
+/// Preset button looks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ButtonVariant {
+    /// Filled with the accent color, for the main action
+    #[default]
+    Primary,
+    /// Raised surface, for everything else
+    Secondary,
+    /// Transparent with a border
+    Outline,
+    /// Transparent until hovered
+    Ghost,
+    /// Filled with the danger color, for actions that delete or discard
+    Destructive,
+    /// Underlined accent text
+    Link,
+}
+
+impl ButtonVariant {
+    /// Stylesheet class added to buttons of this variant, e.g. `Button.outline`
+    pub fn class(self) -> &'static str {
+        match self {
+            ButtonVariant::Primary => "primary",
+            ButtonVariant::Secondary => "secondary",
+            ButtonVariant::Outline => "outline",
+            ButtonVariant::Ghost => "ghost",
+            ButtonVariant::Destructive => "destructive",
+            ButtonVariant::Link => "link",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ButtonStyle {
+    pub background: StateColors,
+    pub foreground: StateColors,
+    pub border_color: Option<StateColors>,
+    pub border_width: f32,
+    pub padding: EdgeInsets,
+    pub radius: f32,
+    pub font_size: f32,
+    pub bold: bool,
+    pub underline: bool,
+    pub min_width: f32,
+    pub height: f32,
+}
+
+impl ButtonStyle {
+    /// Primary button look from the theme tokens
+    pub fn from_theme(theme: &Theme) -> Self {
+        let tokens = &theme.tokens;
+        let density = theme.density();
+        Self {
+            background: StateColors::new(tokens.color(ColorRole::Accent))
+                .with_hover(tokens.color(ColorRole::AccentHover))
+                .with_pressed(tokens.color(ColorRole::AccentPressed))
+                .with_disabled(tokens.color(ColorRole::SurfaceSunken)),
+            foreground: StateColors::new(tokens.color(ColorRole::TextOnAccent))
+                .with_disabled(tokens.color(ColorRole::TextMuted)),
+            // Forced colors flattens the fill into the background
+            border_color: theme
+                .forced_colors
+                .then(|| StateColors::new(tokens.color(ColorRole::Border)).with_disabled(tokens.color(ColorRole::TextMuted))),
+            border_width: if theme.forced_colors { 1.0 } else { 0.0 },
+            padding: EdgeInsets::symmetric(
+                density.padding(tokens.spacing.lg),
+                density.padding(tokens.spacing.sm),
+            ),
+            radius: tokens.radii.md,
+            font_size: tokens.font_sizes.base,
+            bold: false,
+            underline: false,
+            min_width: 3.0 * density.control_height(),
+            height: density.control_height(),
+        }
+    }
+
+    /// Look of `variant` from the theme tokens
+    pub fn variant(theme: &Theme, variant: ButtonVariant) -> Self {
+        let tokens = &theme.tokens;
+        let base = Self::from_theme(theme);
+        let disabled_text = tokens.color(ColorRole::TextMuted);
+        let text = StateColors::new(tokens.color(ColorRole::Text)).with_disabled(disabled_text);
+        let hover_fill = StateColors::new(Color::TRANSPARENT)
+            .with_hover(tokens.color(ColorRole::SurfaceHover))
+            .with_pressed(tokens.color(ColorRole::SurfacePressed));
+        match variant {
+            ButtonVariant::Primary => base,
+            ButtonVariant::Secondary => base
+                .with_background(
+                    StateColors::new(tokens.color(ColorRole::SurfaceRaised))
+                        .with_hover(tokens.color(ColorRole::SurfaceHover))
+                        .with_pressed(tokens.color(ColorRole::SurfacePressed))
+                        .with_disabled(tokens.color(ColorRole::SurfaceSunken)),
+                )
+                .with_foreground(text),
+            ButtonVariant::Outline => base
+                .with_background(hover_fill)
+                .with_foreground(text)
+                .with_border(
+                    1.0,
+                    StateColors::new(tokens.color(ColorRole::Border))
+                        .with_focused(tokens.color(ColorRole::BorderFocus))
+                        .with_disabled(disabled_text),
+                ),
+            ButtonVariant::Ghost => base.with_background(hover_fill).with_foreground(text),
+            ButtonVariant::Destructive => base
+                .with_background(
+                    StateColors::new(tokens.color(ColorRole::Danger))
+                        .with_hover(tokens.color(ColorRole::DangerHover))
+                        .with_disabled(tokens.color(ColorRole::SurfaceSunken)),
+                )
+                .with_foreground(StateColors::new(tokens.color(ColorRole::TextOnDanger)).with_disabled(disabled_text)),
+            ButtonVariant::Link => Self {
+                background: StateColors::new(Color::TRANSPARENT),
+                foreground: StateColors::new(tokens.color(ColorRole::Accent))
+                    .with_hover(tokens.color(ColorRole::AccentHover))
+                    .with_pressed(tokens.color(ColorRole::AccentPressed))
+                    .with_disabled(disabled_text),
+                border_color: None,
+                border_width: 0.0,
+                padding: EdgeInsets::zero(),
+                underline: true,
+                min_width: 0.0,
+                ..base
+            },
+        }
+    }
+
+    pub fn with_background(mut self, background: impl Into<StateColors>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    pub fn with_foreground(mut self, foreground: impl Into<StateColors>) -> Self {
+        self.foreground = foreground.into();
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: impl Into<StateColors>) -> Self {
+        self.border_width = width;
+        self.border_color = Some(color.into());
+        self
+    }
+
+    pub fn with_padding(mut self, padding: EdgeInsets) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    pub fn with_size(mut self, min_width: f32, height: f32) -> Self {
+        self.min_width = min_width;
+        self.height = height;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputStyle {
+    pub background: StateColors,
+    pub foreground: StateColors,
+    pub placeholder: Color,
+    pub border_color: StateColors,
+    pub border_width: f32,
+    pub padding: EdgeInsets,
+    pub radius: f32,
+    pub font_size: f32,
+    pub height: f32,
+}
+
+impl InputStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        let tokens = &theme.tokens;
+        let density = theme.density();
+        Self {
+            background: StateColors::new(tokens.color(ColorRole::Surface))
+                .with_disabled(tokens.color(ColorRole::SurfaceSunken)),
+            foreground: StateColors::new(tokens.color(ColorRole::Text))
+                .with_disabled(tokens.color(ColorRole::TextMuted)),
+            placeholder: tokens.color(ColorRole::TextMuted),
+            border_color: StateColors::new(theme.input)
+                .with_hover(tokens.color(ColorRole::Border))
+                .with_focused(tokens.color(ColorRole::BorderFocus)),
+            border_width: 1.0,
+            padding: EdgeInsets::symmetric(
+                density.padding(tokens.spacing.md),
+                density.padding(tokens.spacing.sm),
+            ),
+            radius: tokens.radii.md,
+            font_size: tokens.font_sizes.base,
+            height: density.control_height(),
+        }
+    }
+
+    pub fn with_background(mut self, background: impl Into<StateColors>) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    pub fn with_foreground(mut self, foreground: impl Into<StateColors>) -> Self {
+        self.foreground = foreground.into();
+        self
+    }
+
+    pub fn with_placeholder(mut self, color: Color) -> Self {
+        self.placeholder = color;
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: impl Into<StateColors>) -> Self {
+        self.border_width = width;
+        self.border_color = color.into();
+        self
+    }
+
+    pub fn with_padding(mut self, padding: EdgeInsets) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardStyle {
+    pub background: Color,
+    pub title_color: Color,
+    pub description_color: Color,
+    pub border_color: Option<Color>,
+    pub border_width: f32,
+    pub padding: f32,
+    pub radius: f32,
+    pub title_font_size: f32,
+    pub description_font_size: f32,
+}
+
+impl CardStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        let tokens = &theme.tokens;
+        Self {
+            background: tokens.color(ColorRole::SurfaceRaised),
+            title_color: theme.card_foreground,
+            description_color: tokens.color(ColorRole::TextMuted),
+            border_color: theme.forced_colors.then(|| tokens.color(ColorRole::Border)),
+            border_width: if theme.forced_colors { 1.0 } else { 0.0 },
+            padding: theme.density().padding(tokens.spacing.lg),
+            radius: tokens.radii.lg,
+            title_font_size: tokens.font_sizes.lg,
+            description_font_size: tokens.font_sizes.base,
+        }
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn with_text_colors(mut self, title: Color, description: Color) -> Self {
+        self.title_color = title;
+        self.description_color = description;
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: Color) -> Self {
+        self.border_width = width;
+        self.border_color = Some(color);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_font_sizes(mut self, title: f32, description: f32) -> Self {
+        self.title_font_size = title;
+        self.description_font_size = description;
+        self
+    }
+}
+
+/// App-wide style defaults; widgets without their own style use these,
+/// falling back to the theme-derived look
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WidgetStyles {
+    pub button: Option<ButtonStyle>,
+    pub input: Option<InputStyle>,
+    pub card: Option<CardStyle>,
+}
+
+impl WidgetStyles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_button(mut self, style: ButtonStyle) -> Self {
+        self.button = Some(style);
+        self
+    }
+
+    pub fn with_input(mut self, style: InputStyle) -> Self {
+        self.input = Some(style);
+        self
+    }
+
+    pub fn with_card(mut self, style: CardStyle) -> Self {
+        self.card = Some(style);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_colors_priority() {
+        let colors = StateColors::new(Color::WHITE)
+            .with_hover(Color::RED)
+            .with_disabled(Color::BLACK);
+
+        assert_eq!(colors.resolve(StyleState::default()), Color::WHITE);
+        assert_eq!(colors.resolve(StyleState { hover: true, ..Default::default() }), Color::RED);
+        // No pressed color: falls back to hover
+        assert_eq!(colors.resolve(StyleState { active: true, ..Default::default() }), Color::RED);
+        assert_eq!(
+            colors.resolve(StyleState { hover: true, disabled: true, ..Default::default() }),
+            Color::BLACK
+        );
+    }
+
+    #[test]
+    fn test_button_variants() {
+        let theme = Theme::default();
+        let tokens = &theme.tokens;
+        let hover = StyleState { hover: true, ..Default::default() };
+
+        let ghost = ButtonStyle::variant(&theme, ButtonVariant::Ghost);
+        assert_eq!(ghost.background.resolve(StyleState::default()), Color::TRANSPARENT);
+        assert_eq!(ghost.background.resolve(hover), tokens.color(ColorRole::SurfaceHover));
+
+        let outline = ButtonStyle::variant(&theme, ButtonVariant::Outline);
+        assert_eq!(outline.border_width, 1.0);
+        let destructive = ButtonStyle::variant(&theme, ButtonVariant::Destructive);
+        assert_eq!(destructive.background.normal, tokens.color(ColorRole::Danger));
+        let link = ButtonStyle::variant(&theme, ButtonVariant::Link);
+        assert!(link.underline && link.min_width == 0.0);
+    }
+}
@@ -1,8 +1,13 @@
 use crate::core::context::BuildContext;
-use crate::core::context::ThemeProvider;
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::context::{InheritedTextStyle, ThemeProvider};
+use crate::core::render_object::{Color, Point, Rect, RenderObject, TextAlign, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
 use crate::layout::constraints::{EdgeInsets};
+use crate::layout::{children_hash, debug_hash, AlignItems, EdgeInsetsDirectional, FlexDirection, FlexLayout};
+use crate::render::text::{fit_text, line_height, text_width, TextOverflow};
+use crate::theming::{ColorRole, StyleState, TextVariant};
+use crate::state_management::collections::ReactiveVec;
+use crate::widgets::layout_widgets::{build_flex, ListView};
 use std::any::Any;
 
 /// Container widget - flexible box with styling
@@ -11,6 +16,8 @@ pub struct Container {
     pub height: Option<f32>,
     pub color: Option<Color>,
     pub padding: EdgeInsets,
+    /// Start/end padding; replaces `padding` and mirrors in RTL
+    pub padding_directional: Option<EdgeInsetsDirectional>,
     pub margin: EdgeInsets,
     pub border_radius: f32,
     pub border_width: f32,
@@ -18,6 +25,8 @@ pub struct Container {
     pub child: Option<WidgetChild>,
     pub tooltip: Option<String>,
     pub on_click: Option<std::sync::Arc<dyn Fn() + Send + Sync>>,
+    /// Stylesheet classes, matched by selectors like `Container.card`
+    pub classes: Vec<String>,
     key: Option<WidgetKey>,
 }
 
@@ -41,6 +50,7 @@ impl Clone for Container {
             height: self.height,
             color: self.color,
             padding: self.padding,
+            padding_directional: self.padding_directional,
             margin: self.margin,
             border_radius: self.border_radius,
             border_width: self.border_width,
@@ -48,6 +58,7 @@ impl Clone for Container {
             child: self.child.clone(),
             tooltip: self.tooltip.clone(),
             on_click: self.on_click.clone(),
+            classes: self.classes.clone(),
             key: self.key.clone(),
         }
     }
@@ -60,6 +71,7 @@ impl Container {
             height: None,
             color: None,
             padding: EdgeInsets::zero(),
+            padding_directional: None,
             margin: EdgeInsets::zero(),
             border_radius: 0.0,
             border_width: 0.0,
@@ -67,6 +79,7 @@ impl Container {
             child: None,
             tooltip: None,
             on_click: None,
+            classes: Vec::new(),
             key: None,
         }
     }
@@ -92,6 +105,13 @@ impl Container {
         self
     }
 
+    /// Padding on the reading-direction start and end, for layouts that
+    /// should mirror in RTL locales
+    pub fn with_padding_directional(mut self, start: f32, top: f32, end: f32, bottom: f32) -> Self {
+        self.padding_directional = Some(EdgeInsetsDirectional::only(start, top, end, bottom));
+        self
+    }
+
     pub fn with_margin(mut self, margin: f32) -> Self {
         self.margin = EdgeInsets::all(margin);
         self
@@ -126,6 +146,11 @@ impl Container {
         self
     }
 
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
@@ -135,8 +160,20 @@ impl Container {
 impl StatelessWidget for Container {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
-        let bg_color = self.color.unwrap_or(theme.background);
-        let border_color = self.border_color.unwrap_or(theme.border);
+        let style = ctx.resolve_style("Container", &self.classes, StyleState::default());
+        // Explicit builder values win over the stylesheet
+        let bg_color = self.color.or(style.background).unwrap_or(theme.role(ColorRole::Surface));
+        let border_color = self.border_color.or(style.border_color).unwrap_or(theme.role(ColorRole::Border));
+        let border_width = if self.border_width > 0.0 {
+            self.border_width
+        } else {
+            style.border_width.unwrap_or(0.0)
+        };
+        let padding = match (self.padding_directional, style.padding) {
+            (Some(directional), _) => directional.resolve(ctx.direction()),
+            (None, Some(padding)) if self.padding == EdgeInsets::zero() => EdgeInsets::all(padding),
+            _ => self.padding,
+        };
 
         let available_width = ctx.constraints.max_width - self.margin.horizontal();
         let available_height = ctx.constraints.max_height - self.margin.vertical();
@@ -148,19 +185,19 @@ impl StatelessWidget for Container {
 
         // Background
         render_objects.push(RenderObject::rect(
-            Rect::new(self.padding.left, self.padding.top,
-                      width - self.padding.horizontal(),
-                      height - self.padding.vertical()),
+            Rect::new(padding.left, padding.top,
+                      width - padding.horizontal(),
+                      height - padding.vertical()),
             bg_color,
         ));
 
         // Border
-        if self.border_width > 0.0 {
+        if border_width > 0.0 {
             let border_rect = Rect::new(
-                self.padding.left - self.border_width/2.0,
-                self.padding.top - self.border_width/2.0,
-                width - self.padding.horizontal() + self.border_width,
-                height - self.padding.vertical() + self.border_width
+                padding.left - border_width/2.0,
+                padding.top - border_width/2.0,
+                width - padding.horizontal() + border_width,
+                height - padding.vertical() + border_width
             );
 
             render_objects.push(RenderObject::rect(
@@ -174,14 +211,14 @@ impl StatelessWidget for Container {
             match child {
                 WidgetChild::Single(widget) => {
                     let child_constraints = ctx.constraints.deflate(EdgeInsets {
-                        left: self.padding.left + self.border_width,
-                        top: self.padding.top + self.border_width,
-                        right: self.padding.right + self.border_width,
-                        bottom: self.padding.bottom + self.border_width,
+                        left: padding.left + border_width,
+                        top: padding.top + border_width,
+                        right: padding.right + border_width,
+                        bottom: padding.bottom + border_width,
                     });
 
                     let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-                    let child_node = widget.build(&child_ctx);
+                    let child_node = child_ctx.build_child(widget.as_ref());
 
                     if let WidgetNode::Leaf(child_render) = child_node {
                         render_objects.push(child_render);
@@ -238,6 +275,14 @@ pub struct Text {
     pub content: String,
     pub style: Option<TextStyle>,
     pub color: Option<Color>,
+    /// Typography role; sets size and weight over the inherited style
+    pub variant: Option<TextVariant>,
+    /// Cut-off applied when the text is wider than its constraints; `None`
+    /// draws it in full
+    pub overflow: Option<TextOverflow>,
+    /// Wrap onto at most this many lines; unset keeps the text on one line
+    pub max_lines: Option<usize>,
+    pub align: Option<TextAlign>,
     key: Option<WidgetKey>,
 }
 
@@ -247,6 +292,10 @@ impl Clone for Text {
             content: self.content.clone(),
             style: self.style.clone(),
             color: self.color,
+            variant: self.variant,
+            overflow: self.overflow,
+            max_lines: self.max_lines,
+            align: self.align,
             key: self.key.clone(),
         }
     }
@@ -258,41 +307,261 @@ impl Text {
             content: content.into(),
             style: None,
             color: None,
+            variant: None,
+            overflow: None,
+            max_lines: None,
+            align: None,
             key: None,
         }
     }
 
+    /// Use `style` instead of the inherited text style
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
     pub fn with_color(mut self, color: Color) -> Self {
         self.color = Some(color);
         self
     }
+
+    pub fn with_variant(mut self, variant: TextVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = Some(overflow);
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
 }
 
 impl StatelessWidget for Text {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
-        let theme = ctx.theme();
-        let text_color = self.color.unwrap_or(theme.foreground);
-        let style = self.style.clone().unwrap_or(TextStyle {
-            font_family: theme.font_sans.clone(),
-            font_size: 14.0,
-            color: text_color,
-            bold: false,
-            italic: false,
-        });
-
-        WidgetNode::Leaf(RenderObject::text(
-            self.content.clone(),
+        let mut style = self.style.clone().unwrap_or_else(|| ctx.text_style());
+        if let Some(variant) = self.variant {
+            let role = ctx.theme().text_style(variant);
+            style.font_size = role.font_size;
+            style.bold = role.bold;
+            if variant == TextVariant::Code {
+                style.font_family = role.font_family;
+            }
+        }
+        if let Some(color) = self.color {
+            style.color = color;
+        }
+        if let Some(align) = self.align {
+            style.align = align;
+        } else if ctx.direction().is_rtl() && style.align == TextAlign::Left {
+            // Unaligned text hugs the start edge, which is the right in RTL
+            style.align = TextAlign::Right;
+        }
+
+        WidgetNode::Leaf(layout_text(
+            &self.content,
             style,
-            Point::new(0.0, 0.0)
+            ctx.constraints.max_width,
+            self.max_lines,
+            self.overflow,
         ))
     }
 }
 
+/// Render `content` within `max_width`, wrapped to `max_lines`, aligned per
+/// `style.align` and cut off per `overflow`. Shared by `Text` and `Label`.
+pub(crate) fn layout_text(
+    content: &str,
+    style: TextStyle,
+    max_width: f32,
+    max_lines: Option<usize>,
+    overflow: Option<TextOverflow>,
+) -> RenderObject {
+    if !max_width.is_finite() || (overflow.is_none() && style.align == TextAlign::Left) {
+        return RenderObject::text(content.to_string(), style, Point::ZERO);
+    }
+
+    let fitted = fit_text(content, &style, max_width, max_lines.unwrap_or(1), overflow.unwrap_or_default());
+    let line_h = line_height(&style);
+    let last = fitted.lines.len() - 1;
+    let line = |i: usize, text: &str, style: &TextStyle| {
+        let y = i as f32 * line_h;
+        let slack = (max_width - text_width(text, style)).max(0.0);
+        let at = |text: &str, x: f32| RenderObject::text(text.to_string(), style.clone(), Point::new(x, y));
+        match style.align {
+            TextAlign::Left => at(text, 0.0),
+            // The renderer aligns the line within the box by its real width
+            TextAlign::Center | TextAlign::Right => {
+                RenderObject::aligned_text(text.to_string(), style.clone(), Point::new(0.0, y), max_width)
+            }
+            // Word by word with the slack spread over the gaps; the last line stays left-aligned
+            TextAlign::Justify if i != last && text.contains(' ') => {
+                let words: Vec<&str> = text.split(' ').collect();
+                let gap = text_width(" ", style) + slack / (words.len() - 1) as f32;
+                let mut x = 0.0;
+                let mut runs = Vec::with_capacity(words.len());
+                for word in words {
+                    runs.push(at(word, x));
+                    x += text_width(word, style) + gap;
+                }
+                RenderObject::group(runs)
+            }
+            TextAlign::Justify => at(text, 0.0),
+        }
+    };
+
+    let mut lines: Vec<RenderObject> = fitted.lines[..last]
+        .iter()
+        .enumerate()
+        .map(|(i, text)| line(i, text, &style))
+        .collect();
+
+    let fades = overflow == Some(TextOverflow::Fade)
+        && fitted.truncated
+        && text_width(&fitted.lines[last], &style) > max_width;
+    if fades {
+        // The same line drawn in vertical bands of falling opacity
+        const BANDS: usize = 4;
+        let fade_width = (style.font_size * 3.0).min(max_width / 3.0);
+        let fade_start = max_width - fade_width;
+        let y = last as f32 * line_h;
+        lines.push(RenderObject::clip(
+            Rect::new(0.0, y, fade_start, line_h),
+            line(last, &fitted.lines[last], &style),
+        ));
+        for band in 0..BANDS {
+            let mut faded = style.clone();
+            let opacity = 1.0 - (band + 1) as f32 / (BANDS + 1) as f32;
+            faded.color = style.color.with_alpha((style.color.a as f32 * opacity) as u8);
+            let band_width = fade_width / BANDS as f32;
+            lines.push(RenderObject::clip(
+                Rect::new(fade_start + band as f32 * band_width, y, band_width, line_h),
+                line(last, &fitted.lines[last], &faded),
+            ));
+        }
+    } else {
+        lines.push(line(last, &fitted.lines[last], &style));
+    }
+
+    let block = RenderObject::group(lines);
+    match overflow {
+        Some(_) => RenderObject::clip(Rect::new(0.0, 0.0, max_width, fitted.lines.len() as f32 * line_h), block),
+        None => block,
+    }
+}
+
 impl Widget for Text {
     fn build(&self, ctx: &BuildContext) -> WidgetNode {
         self.build_stateless(ctx)
     }
 
+    fn layout_hash(&self) -> Option<u64> {
+        Some(debug_hash(&(
+            &self.content,
+            &self.style,
+            self.color,
+            self.variant,
+            self.overflow,
+            self.max_lines,
+            self.align,
+        )))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Sets the text style inherited by descendant `Text` and `Label` widgets.
+///
+/// Nested `DefaultTextStyle`s layer over each other; properties left unset
+/// here keep the ancestor's value.
+pub struct DefaultTextStyle {
+    pub style: InheritedTextStyle,
+    pub child: Box<dyn Widget>,
+    key: Option<WidgetKey>,
+}
+
+impl DefaultTextStyle {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            style: InheritedTextStyle::default(),
+            child: Box::new(child),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            style: self.style.clone(),
+            child: self.child.clone_box(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.style.font_family = Some(family.into());
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.style.font_size = Some(size);
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.style.bold = Some(bold);
+        self
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = Some(italic);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for DefaultTextStyle {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let child_ctx = ctx.clone().with_text_style(&self.style);
+        self.child.build(&child_ctx)
+    }
+
+    fn handle_event(
+        &self,
+        event: &crate::core::event::UiEvent,
+        context: &mut crate::core::event::EventContext,
+    ) -> crate::core::event::EventResult {
+        self.child.handle_event(event, context)
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -310,6 +579,10 @@ impl Widget for Text {
 pub struct Column {
     pub children: Vec<std::sync::Arc<dyn Widget>>,
     pub spacing: f32,
+    /// Horizontal placement of children narrower than the column
+    pub cross_alignment: AlignItems,
+    /// Diff-driven children from a `ReactiveVec`, built with the current spacing
+    reactive: Option<std::sync::Arc<dyn Fn(f32) -> Box<dyn Widget> + Send + Sync>>,
     key: Option<WidgetKey>,
 }
 
@@ -318,6 +591,8 @@ impl Clone for Column {
         Self {
             children: self.children.clone(),
             spacing: self.spacing,
+            cross_alignment: self.cross_alignment,
+            reactive: self.reactive.clone(),
             key: self.key.clone(),
         }
     }
@@ -328,10 +603,26 @@ impl Column {
         Self {
             children: Vec::new(),
             spacing: 0.0,
+            cross_alignment: AlignItems::FlexStart,
+            reactive: None,
             key: None,
         }
     }
 
+    /// Build children from a `ReactiveVec`; only inserted, removed or updated
+    /// items are rebuilt and moved items animate to their new slot
+    pub fn with_reactive_children<T, F>(mut self, items: ReactiveVec<T>, builder: F) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Fn(usize, &T) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        let list = ListView::new(items, builder).with_measured_items();
+        self.reactive = Some(std::sync::Arc::new(move |spacing| {
+            Box::new(list.clone().with_spacing(spacing))
+        }));
+        self
+    }
+
     pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
         self.children = children
             .into_iter()
@@ -344,37 +635,34 @@ impl Column {
         self.spacing = spacing;
         self
     }
+
+    pub fn with_cross_alignment(mut self, cross_alignment: AlignItems) -> Self {
+        self.cross_alignment = cross_alignment;
+        self
+    }
 }
 
 impl Widget for Column {
     fn build(&self, ctx: &BuildContext) -> WidgetNode {
-        let mut accumulated_height = 0.0;
-        let mut child_objects = Vec::new();
-
-        for (i, child) in self.children.iter().enumerate() {
-            let child_height = ctx.constraints.max_height - accumulated_height;
-            let child_constraints = ctx.constraints.constrain_height(child_height);
-
-            let child_ctx = ctx.child_context(
-                crate::core::element::ElementId::new(i as u64 + 1),
-                child_constraints
-            );
-
-            let child_node = child.build(&child_ctx);
+        if let Some(reactive) = &self.reactive {
+            return reactive(self.spacing).build(ctx);
+        }
 
-            if let WidgetNode::Leaf(render_obj) = child_node {
-                let transformed = RenderObject::transform(
-                    crate::core::render_object::Matrix::translate(0.0, accumulated_height),
-                    render_obj
-                );
-                child_objects.push(transformed);
+        let flex = FlexLayout {
+            direction: FlexDirection::Column,
+            align_items: self.cross_alignment,
+            gap: self.spacing,
+            ..FlexLayout::default()
+        };
+        build_flex(ctx, self.children.iter().map(|child| child.as_ref()), flex)
+    }
 
-                // Estimate height based on render object bounds
-                accumulated_height += 50.0 + self.spacing; // Rough estimate
-            }
+    fn layout_hash(&self) -> Option<u64> {
+        if self.reactive.is_some() {
+            return None;
         }
-
-        WidgetNode::Leaf(RenderObject::group(child_objects))
+        let children = children_hash(self.children.iter().map(|child| child.as_ref()))?;
+        Some(debug_hash(&(self.spacing, self.cross_alignment, children)))
     }
 
     fn key(&self) -> Option<WidgetKey> {
@@ -394,6 +682,9 @@ impl Widget for Column {
 pub struct Row {
     pub children: Vec<std::sync::Arc<dyn Widget>>,
     pub spacing: f32,
+    /// Vertical placement of children shorter than the row;
+    /// `AlignItems::Baseline` lines up their first lines of text
+    pub cross_alignment: AlignItems,
     key: Option<WidgetKey>,
 }
 
@@ -402,6 +693,7 @@ impl Clone for Row {
         Self {
             children: self.children.clone(),
             spacing: self.spacing,
+            cross_alignment: self.cross_alignment,
             key: self.key.clone(),
         }
     }
@@ -412,6 +704,7 @@ impl Row {
         Self {
             children: Vec::new(),
             spacing: 0.0,
+            cross_alignment: AlignItems::FlexStart,
             key: None,
         }
     }
@@ -428,37 +721,27 @@ impl Row {
         self.spacing = spacing;
         self
     }
+
+    pub fn with_cross_alignment(mut self, cross_alignment: AlignItems) -> Self {
+        self.cross_alignment = cross_alignment;
+        self
+    }
 }
 
 impl Widget for Row {
     fn build(&self, ctx: &BuildContext) -> WidgetNode {
-        let mut accumulated_width = 0.0;
-        let mut child_objects = Vec::new();
-
-        for (i, child) in self.children.iter().enumerate() {
-            let child_width = ctx.constraints.max_width - accumulated_width;
-            let child_constraints = ctx.constraints.constrain_width(child_width);
-
-            let child_ctx = ctx.child_context(
-                crate::core::element::ElementId::new(i as u64 + 1),
-                child_constraints
-            );
-
-            let child_node = child.build(&child_ctx);
-
-            if let WidgetNode::Leaf(render_obj) = child_node {
-                let transformed = RenderObject::transform(
-                    crate::core::render_object::Matrix::translate(accumulated_width, 0.0),
-                    render_obj
-                );
-                child_objects.push(transformed);
-
-                // Estimate width based on render object bounds
-                accumulated_width += 100.0 + self.spacing; // Rough estimate
-            }
-        }
+        let flex = FlexLayout {
+            direction: FlexDirection::Row,
+            align_items: self.cross_alignment,
+            gap: self.spacing,
+            ..FlexLayout::default()
+        };
+        build_flex(ctx, self.children.iter().map(|child| child.as_ref()), flex)
+    }
 
-        WidgetNode::Leaf(RenderObject::group(child_objects))
+    fn layout_hash(&self) -> Option<u64> {
+        let children = children_hash(self.children.iter().map(|child| child.as_ref()))?;
+        Some(debug_hash(&(self.spacing, self.cross_alignment, children)))
     }
 
     fn key(&self) -> Option<WidgetKey> {
@@ -508,12 +791,7 @@ impl Widget for Center {
         let mut child_objects = Vec::new();
 
         if let Some(child) = &self.child {
-            let child_ctx = ctx.child_context(
-                crate::core::element::ElementId::new(1),
-                ctx.constraints
-            );
-
-            let child_node = child.build(&child_ctx);
+            let child_node = ctx.build_child(child.as_ref());
 
             if let WidgetNode::Leaf(render_obj) = child_node {
                 // Center the child by translating it to the center
@@ -586,6 +864,7 @@ impl Widget for HelloWorld {
                 color: theme.primary,
                 bold: true,
                 italic: false,
+                ..Default::default()
             },
             Point::new(70.0, 80.0),
         ));
@@ -599,6 +878,7 @@ impl Widget for HelloWorld {
                 color: theme.foreground,
                 bold: false,
                 italic: false,
+                ..Default::default()
             },
             Point::new(70.0, 120.0),
         ));
@@ -0,0 +1,247 @@
+//! Clipping a subtree to a shape
+//!
+//! `ClipRRect`, `ClipOval` and `ClipPath` crop their child to a rounded
+//! rectangle, an ellipse or an SVG path, for avatars, pill buttons and
+//! custom shapes. Clicks outside the shape fall through to whatever is
+//! beneath, just as the cropped pixels do.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{ClipShape, Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::Size;
+
+/// Shared by the clip widgets: build the child and clip it to `shape`,
+/// resolved in the clip's box, which is the child's unless `size` is set
+fn build_clipped(ctx: &BuildContext, child: &dyn Widget, size: Option<Size>, shape: impl FnOnce(Rect) -> ClipShape) -> WidgetNode {
+    // Container children come back laid out as one group, so they're
+    // clipped as a whole too
+    let WidgetNode::Leaf(render_obj) = ctx.build_child(child) else {
+        return WidgetNode::None;
+    };
+    let size = size.unwrap_or_else(|| render_obj.layout_size());
+    WidgetNode::Leaf(RenderObject::clip_shape(shape(Rect::from_size(size)), render_obj))
+}
+
+/// Clips its child to a rectangle with rounded corners
+pub struct ClipRRect {
+    pub child: Box<dyn Widget>,
+    /// Clockwise from the top left
+    pub radii: [f32; 4],
+    /// Defaults to the child's size
+    pub size: Option<Size>,
+    key: Option<WidgetKey>,
+}
+
+impl ClipRRect {
+    pub fn new<W: Widget + 'static>(child: W, radius: f32) -> Self {
+        Self {
+            child: Box::new(child),
+            radii: [radius; 4],
+            size: None,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            radii: self.radii,
+            size: self.size,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_radii(mut self, top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        self.radii = [top_left, top_right, bottom_right, bottom_left];
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(Size::new(width, height));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for ClipRRect {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_clipped(ctx, self.child.as_ref(), self.size, |rect| ClipShape::RoundedRect { rect, radii: self.radii })
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Clips its child to the ellipse filling its box; a circle for square boxes
+pub struct ClipOval {
+    pub child: Box<dyn Widget>,
+    /// Defaults to the child's size
+    pub size: Option<Size>,
+    key: Option<WidgetKey>,
+}
+
+impl ClipOval {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            size: None,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            size: self.size,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(Size::new(width, height));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for ClipOval {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_clipped(ctx, self.child.as_ref(), self.size, ClipShape::Oval)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Clips its child to an SVG path, e.g. `"M0 0 L100 0 L50 80 Z"`
+pub struct ClipPath {
+    pub child: Box<dyn Widget>,
+    pub data: Arc<str>,
+    /// Extent of the path; defaults to the child's size
+    pub size: Option<Size>,
+    key: Option<WidgetKey>,
+}
+
+impl ClipPath {
+    pub fn new<W: Widget + 'static>(child: W, data: impl Into<Arc<str>>) -> Self {
+        Self {
+            child: Box::new(child),
+            data: data.into(),
+            size: None,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            data: self.data.clone(),
+            size: self.size,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(Size::new(width, height));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for ClipPath {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_clipped(ctx, self.child.as_ref(), self.size, |bounds| ClipShape::Path {
+            data: self.data.clone(),
+            bounds,
+        })
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::Point;
+
+    #[test]
+    fn test_clip_shapes_hit_test_their_outline() {
+        let pill = ClipShape::rounded(Rect::new(0.0, 0.0, 100.0, 40.0), 20.0);
+        assert!(pill.contains(Point::new(50.0, 20.0)));
+        assert!(pill.contains(Point::new(20.0, 1.0)));
+        // The square corner is cut away
+        assert!(!pill.contains(Point::new(2.0, 2.0)));
+        assert!(!pill.contains(Point::new(98.0, 38.0)));
+
+        let avatar = ClipShape::Oval(Rect::new(0.0, 0.0, 48.0, 48.0));
+        assert!(avatar.contains(Point::new(24.0, 1.0)));
+        assert!(!avatar.contains(Point::new(4.0, 4.0)));
+        assert!(!avatar.contains(Point::new(60.0, 24.0)));
+    }
+
+    #[test]
+    fn test_clips_take_the_size_of_their_child() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+        use crate::widgets::layout_widgets::SizedBox;
+
+        let avatar = ClipOval::new(SizedBox::new(48.0, 48.0));
+        let clip = |constraints: Constraints| {
+            let tree = new_shared_element_tree();
+            let id = tree.write().mount(None, &avatar);
+            let ctx = BuildContext::new(id, tree, constraints, Arc::new(Theme::default()));
+            match avatar.build(&ctx) {
+                WidgetNode::Leaf(RenderObject::ClipShape { shape, .. }) => shape.bounds(),
+                _ => panic!("the child is clipped"),
+            }
+        };
+        // Neither stretched across a wide parent nor collapsed in an
+        // unbounded one
+        assert_eq!(clip(Constraints::new(0.0, 400.0, 0.0, 300.0)), Rect::new(0.0, 0.0, 48.0, 48.0));
+        assert_eq!(clip(Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY)), Rect::new(0.0, 0.0, 48.0, 48.0));
+    }
+}
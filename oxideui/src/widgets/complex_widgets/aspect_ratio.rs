@@ -1,7 +1,11 @@
 use std::any::Any;
 use crate::core::context::BuildContext;
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, Size};
+use crate::widgets::layout_widgets::build_sized;
 
+/// Sizes its child to `ratio` (width / height), as large as the
+/// constraints allow
 pub struct AspectRatio {
     pub ratio: f32,
     pub child: Box<dyn Widget>,
@@ -29,22 +33,34 @@ impl AspectRatio {
         self.key = Some(key);
         self
     }
+
+    /// Largest size of the right shape within `constraints`
+    pub fn size(&self, constraints: &Constraints) -> Size {
+        if !(self.ratio.is_finite() && self.ratio > 0.0) {
+            return constraints.smallest();
+        }
+        let (mut width, mut height) = if constraints.has_bounded_width() {
+            (constraints.max_width, constraints.max_width / self.ratio)
+        } else if constraints.has_bounded_height() {
+            (constraints.max_height * self.ratio, constraints.max_height)
+        } else {
+            // Nothing to fill; fall back to the child's minimum
+            let width = constraints.min_width.max(constraints.min_height * self.ratio);
+            (width, width / self.ratio)
+        };
+        // Too tall for the height: fit the height instead
+        if height > constraints.max_height {
+            height = constraints.max_height;
+            width = height * self.ratio;
+        }
+        constraints.constrain(Size::new(width, height))
+    }
 }
 
 impl StatelessWidget for AspectRatio {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
-        let width = ctx.constraints.max_width;
-        let target_height = width / self.ratio;
-
-        let child_constraints = crate::layout::constraints::Constraints::new(
-            0.0,
-            width,
-            0.0,
-            target_height,
-        );
-
-        let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-        self.child.build(&child_ctx)
+        let size = self.size(&ctx.constraints);
+        build_sized(ctx, self.child.as_ref(), Constraints::tight(size), size, (0.0, 0.0))
     }
 }
 
@@ -64,4 +80,22 @@ impl Widget for AspectRatio {
     fn clone_box(&self) -> Box<dyn Widget> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_aspect_ratio_fits_the_constraints() {
+        let video = AspectRatio::new(16.0 / 9.0, Box::new(Text::new("▶")));
+        // Width-bound: full width, height follows
+        assert_eq!(video.size(&Constraints::loose(Size::new(320.0, 400.0))), Size::new(320.0, 180.0));
+        // Too tall at full width, so the height wins
+        assert_eq!(video.size(&Constraints::loose(Size::new(320.0, 90.0))), Size::new(160.0, 90.0));
+        // Only the height is known
+        let unbounded_width = Constraints::new(0.0, f32::INFINITY, 0.0, 90.0);
+        assert_eq!(video.size(&unbounded_width), Size::new(160.0, 90.0));
+    }
+}
@@ -1,8 +1,9 @@
 use std::any::Any;
 use std::sync::Arc;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::theming::{CardStyle, ColorRole};
 use crate::ThemeProvider;
 
 pub struct Card {
@@ -10,11 +11,15 @@ pub struct Card {
     pub description: Option<String>,
     pub width: Option<f32>,
     pub height: Option<f32>,
-    pub padding: f32,
+    /// Overrides the style's padding
+    pub padding: Option<f32>,
     pub variant: CardVariant,
     pub children: Vec<Box<dyn Widget>>,
     pub on_click: Option<Arc<dyn Fn() + Send + Sync>>,
     pub tooltip: Option<String>,
+    /// Visual style; when unset, the app-wide default or one derived
+    /// from the theme and `variant`
+    pub style: Option<CardStyle>,
     key: Option<WidgetKey>,
 }
 
@@ -33,11 +38,12 @@ impl Card {
             description: None,
             width: None,
             height: None,
-            padding: 16.0,
+            padding: None,
             variant: CardVariant::Default,
             children: Vec::new(),
             on_click: None,
             tooltip: None,
+            style: None,
             key: None,
         }
     }
@@ -57,6 +63,7 @@ impl Card {
                 .collect(),
             on_click: self.on_click.as_ref().map(|cb| cb.clone()),
             tooltip: self.tooltip.clone(),
+            style: self.style.clone(),
             key: self.key.clone(),
         }
     }
@@ -78,7 +85,7 @@ impl Card {
     }
 
     pub fn with_padding(mut self, padding: f32) -> Self {
-        self.padding = padding;
+        self.padding = Some(padding);
         self
     }
 
@@ -87,6 +94,11 @@ impl Card {
         self
     }
 
+    pub fn with_style(mut self, style: CardStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
     pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
         self.children = children;
         self
@@ -116,54 +128,64 @@ impl Card {
     }
 }
 
+impl Card {
+    /// Theme-derived style adjusted for `variant`
+    fn variant_style(&self, theme: &crate::core::Theme) -> CardStyle {
+        let tokens = &theme.tokens;
+        let style = CardStyle::from_theme(theme);
+        match self.variant {
+            CardVariant::Default | CardVariant::Elevated => style,
+            CardVariant::Outlined => style
+                .with_background(tokens.color(ColorRole::Surface))
+                .with_border(1.0, tokens.color(ColorRole::Border)),
+            CardVariant::Filled => style.with_background(tokens.color(ColorRole::SurfaceSunken)),
+        }
+    }
+}
+
 impl StatelessWidget for Card {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
         let width = self.width.unwrap_or(300.0);
         let height = self.height.unwrap_or(200.0);
 
-        let bg_color = match self.variant {
-            CardVariant::Default => theme.card,
-            CardVariant::Outlined => theme.background,
-            CardVariant::Elevated => theme.card,
-            CardVariant::Filled => theme.muted,
-        };
-
-        let border_color = if self.variant == CardVariant::Outlined {
-            theme.border
-        } else {
-            Color::TRANSPARENT
-        };
+        let style = self
+            .style
+            .clone()
+            .or_else(|| ctx.card_style())
+            .unwrap_or_else(|| self.variant_style(theme));
+        let padding = self.padding.unwrap_or(style.padding);
 
         let mut render_objects = Vec::new();
 
         // Card background
         render_objects.push(RenderObject::rect(
             Rect::new(0.0, 0.0, width, height),
-            bg_color,
+            style.background,
         ));
 
-        // Card border (if outlined)
-        if self.variant == CardVariant::Outlined {
+        // Card border
+        if let (true, Some(border_color)) = (style.border_width > 0.0, style.border_color) {
+            let b = style.border_width;
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, 0.0, width, 1.0),
+                Rect::new(0.0, 0.0, width, b),
                 border_color,
             ));
             render_objects.push(RenderObject::rect(
-                Rect::new(width - 1.0, 0.0, 1.0, height),
+                Rect::new(width - b, 0.0, b, height),
                 border_color,
             ));
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, height - 1.0, width, 1.0),
+                Rect::new(0.0, height - b, width, b),
                 border_color,
             ));
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, 0.0, 1.0, height),
+                Rect::new(0.0, 0.0, b, height),
                 border_color,
             ));
         }
 
-        let mut current_y = self.padding;
+        let mut current_y = padding;
 
         // Title
         if let Some(title) = &self.title {
@@ -171,14 +193,15 @@ impl StatelessWidget for Card {
                 title.clone(),
                 TextStyle {
                     font_family: theme.font_sans.clone(),
-                    font_size: 18.0,
-                    color: theme.card_foreground,
+                    font_size: style.title_font_size,
+                    color: style.title_color,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
-                Point::new(self.padding, current_y),
+                Point::new(padding, current_y),
             ));
-            current_y += 24.0;
+            current_y += style.title_font_size + theme.tokens.spacing.xs;
         }
 
         // Description
@@ -187,35 +210,36 @@ impl StatelessWidget for Card {
                 description.clone(),
                 TextStyle {
                     font_family: theme.font_sans.clone(),
-                    font_size: 14.0,
-                    color: theme.muted_foreground,
+                    font_size: style.description_font_size,
+                    color: style.description_color,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
-                Point::new(self.padding, current_y),
+                Point::new(padding, current_y),
             ));
-            current_y += 20.0;
+            current_y += style.description_font_size + theme.tokens.spacing.sm;
         }
 
         // Children
         if !self.children.is_empty() {
             let child_y = current_y;
-            let child_height = height - child_y - self.padding;
+            let child_height = height - child_y - padding;
 
             for child in &self.children {
                 let child_constraints = crate::layout::constraints::Constraints::new(
                     0.0,
-                    width - (self.padding * 2.0),
+                    width - (padding * 2.0),
                     0.0,
                     child_height,
                 );
 
                 let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-                let child_node = child.build(&child_ctx);
+                let child_node = child_ctx.build_child(child.as_ref());
 
                 if let WidgetNode::Leaf(render_obj) = child_node {
                     let offset_render_obj = RenderObject::transform(
-                        crate::core::render_object::Matrix::translate(self.padding, child_y),
+                        crate::core::render_object::Matrix::translate(padding, child_y),
                         render_obj,
                     );
                     render_objects.push(offset_render_obj);
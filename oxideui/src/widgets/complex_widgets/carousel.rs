@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::core::context::BuildContext;
 use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::widgets::keyboard_nav::{step_index, NavAxis};
 use crate::ThemeProvider;
 
 pub struct Carousel {
@@ -118,7 +119,7 @@ impl StatelessWidget for Carousel {
             );
 
             let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-            let child_node = item.build(&child_ctx);
+            let child_node = child_ctx.build_child(item.as_ref());
 
             if let WidgetNode::Leaf(render_obj) = child_node {
                 render_objects.push(render_obj);
@@ -144,6 +145,7 @@ impl StatelessWidget for Carousel {
                     color: theme.primary_foreground,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(20.0, height / 2.0 + 5.0),
             ));
@@ -162,6 +164,7 @@ impl StatelessWidget for Carousel {
                     color: theme.primary_foreground,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(width - button_size, height / 2.0 + 5.0),
             ));
@@ -206,6 +209,23 @@ impl Widget for Carousel {
         use crate::core::event::{UiEvent, MouseButton, EventResult};
 
         match event {
+            UiEvent::PointerDown { button: MouseButton::Left, .. } if context.is_at_target() => {
+                context.request_focus();
+                EventResult::Unhandled
+            }
+            UiEvent::KeyDown { key, .. } => {
+                match step_index(Some(self.current_index), *key, NavAxis::Horizontal, self.items.len(), 1) {
+                    Some(index) => {
+                        if index != self.current_index {
+                            if let Some(on_change) = &self.on_index_change {
+                                on_change(index);
+                            }
+                        }
+                        EventResult::Stopped
+                    }
+                    None => EventResult::Unhandled,
+                }
+            }
             UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
                 let width = self.width.unwrap_or(400.0);
                 let height = self.height.unwrap_or(300.0);
@@ -239,6 +259,10 @@ impl Widget for Carousel {
         }
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -182,6 +182,7 @@ impl StatelessWidget for Chart {
                                     color: theme.foreground,
                                     bold: false,
                                     italic: false,
+                                    ..Default::default()
                                 },
                                 Point::new(x + bar_width / 2.0 - 10.0, y - 15.0),
                             ));
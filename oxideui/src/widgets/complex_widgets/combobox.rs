@@ -1,8 +1,13 @@
 use std::any::Any;
 use std::sync::Arc;
+use parking_lot::RwLock;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Matrix, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::render::text::text_width;
+use crate::widgets::portal::DismissBarrier;
+use crate::widgets::element_widgets::Icon;
+use crate::widgets::keyboard_nav::ListNavigator;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -18,6 +23,10 @@ pub struct Combobox {
     pub on_change: Option<Arc<dyn Fn(usize) + Send + Sync>>,
     pub on_search: Option<Arc<dyn Fn(String) + Send + Sync>>,
     pub tooltip: Option<String>,
+    /// Keyboard highlight and open state, shared across rebuilds
+    nav: Arc<RwLock<ListNavigator>>,
+    /// Text typed into the search row
+    query: Arc<RwLock<String>>,
     key: Option<WidgetKey>,
 }
 
@@ -35,6 +44,8 @@ impl Combobox {
             on_change: None,
             on_search: None,
             tooltip: None,
+            nav: Arc::new(RwLock::new(ListNavigator::new())),
+            query: Arc::new(RwLock::new(String::new())),
             key: None,
         }
     }
@@ -95,6 +106,87 @@ impl Combobox {
         self.key = Some(key);
         self
     }
+
+    /// Whether the menu is showing, after any keyboard or click toggles
+    pub fn is_open(&self) -> bool {
+        self.nav.read().is_open(self.open)
+    }
+
+    fn select(&self, index: usize) {
+        if let Some(on_change) = &self.on_change {
+            on_change(index);
+        }
+    }
+
+    fn search(&self, edit: impl FnOnce(&mut String)) {
+        let query = {
+            let mut query = self.query.write();
+            edit(&mut query);
+            query.clone()
+        };
+        self.nav.write().set_open(true);
+        if let Some(on_search) = &self.on_search {
+            on_search(query);
+        }
+    }
+
+    /// The open menu, below the combobox box
+    fn menu_rect(&self, width: f32, height: f32, item_height: f32) -> Rect {
+        Rect::new(0.0, height, width, ((self.options.len() as f32 + 0.5) * item_height).min(250.0))
+    }
+
+    /// Arrows, Home/End and Enter/Space while focused; Escape closes the menu
+    fn handle_key(&self, key: winit::keyboard::KeyCode) -> bool {
+        use winit::keyboard::KeyCode;
+
+        let mut nav = self.nav.write();
+        let open = nav.is_open(self.open);
+        if nav.highlighted.is_none() {
+            nav.highlighted = self.selected.filter(|i| *i < self.options.len());
+        }
+        match key {
+            // Space types into the search row instead of selecting
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space
+                if open && !(key == KeyCode::Space && self.searchable) =>
+            {
+                let highlighted = nav.highlighted;
+                nav.set_open(false);
+                drop(nav);
+                if let Some(index) = highlighted {
+                    self.select(index);
+                }
+                true
+            }
+            KeyCode::Escape if open => {
+                nav.set_open(false);
+                true
+            }
+            KeyCode::Backspace if open && self.searchable => {
+                drop(nav);
+                self.search(|query| {
+                    query.pop();
+                });
+                true
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space | KeyCode::ArrowDown | KeyCode::ArrowUp
+                if !open =>
+            {
+                nav.set_open(true);
+                true
+            }
+            _ if open => nav.move_key(key, self.options.len(), 5),
+            _ => false,
+        }
+    }
+
+    /// Top of the first item, below the search row when searchable
+    fn items_top(&self, height: f32, item_height: f32) -> f32 {
+        if self.searchable {
+            height + item_height
+        } else {
+            height
+        }
+    }
 }
 
 impl StatelessWidget for Combobox {
@@ -103,6 +195,10 @@ impl StatelessWidget for Combobox {
         let width = self.width.unwrap_or(200.0);
         let height = self.height.unwrap_or(40.0);
         let item_height = 32.0;
+        let (open, highlighted) = {
+            let nav = self.nav.read();
+            (nav.is_open(self.open), nav.highlighted)
+        };
 
         let bg_color = if self.disabled {
             theme.muted
@@ -161,34 +257,33 @@ impl StatelessWidget for Combobox {
             text_color
         };
 
+        // Text and chevron swap sides in RTL
+        let direction = ctx.direction();
+        let display_style = TextStyle {
+            font_family: theme.font_sans.clone(),
+            font_size: 14.0,
+            color: display_color,
+            bold: false,
+            italic: false,
+            ..Default::default()
+        };
+        let display_x = direction.place(12.0, text_width(display_text, &display_style), width);
         render_objects.push(RenderObject::text(
             display_text.to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 14.0,
-                color: display_color,
-                bold: false,
-                italic: false,
-            },
-            Point::new(12.0, height / 2.0 + 5.0),
+            display_style,
+            Point::new(display_x, height / 2.0 + 5.0),
         ));
 
         // Combobox arrow
-        render_objects.push(RenderObject::text(
-            "▼".to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 12.0,
-                color: theme.muted_foreground,
-                bold: false,
-                italic: false,
-            },
-            Point::new(width - 24.0, height / 2.0 + 5.0),
+        render_objects.push(RenderObject::transform(
+            Matrix::translate(direction.place(width - 28.0, 16.0, width), (height - 16.0) / 2.0),
+            Icon::new("chevron-down").with_size(16.0).to_render_object(theme.muted_foreground),
         ));
 
         // Dropdown menu (if open)
-        if self.open && !self.disabled {
-            let menu_height = ((self.options.len() as f32 + 0.5) * item_height).min(250.0);
+        if open && !self.disabled {
+            let menu_start = render_objects.len();
+            let menu_height = self.menu_rect(width, height, item_height).height;
 
             // Menu background
             render_objects.push(RenderObject::rect(
@@ -225,15 +320,22 @@ impl StatelessWidget for Combobox {
                     theme.background,
                 ));
 
-                // Search placeholder
+                // Search query, or a placeholder before anything is typed
+                let query = self.query.read().clone();
+                let (search_text, search_color) = if query.is_empty() {
+                    ("Search...".to_string(), theme.muted_foreground)
+                } else {
+                    (query, theme.foreground)
+                };
                 render_objects.push(RenderObject::text(
-                    "Search...".to_string(),
+                    search_text,
                     TextStyle {
                         font_family: theme.font_sans.clone(),
                         font_size: 14.0,
-                        color: theme.muted_foreground,
+                        color: search_color,
                         bold: false,
                         italic: false,
+                        ..Default::default()
                     },
                     Point::new(12.0, current_y + search_height / 2.0 + 5.0),
                 ));
@@ -246,27 +348,39 @@ impl StatelessWidget for Combobox {
                 let item_y = current_y + (i as f32 * item_height);
                 let is_selected = self.selected == Some(i);
 
-                // Item background (hover/selected effect)
+                // Item background (selected, then keyboard highlight)
                 if is_selected {
                     render_objects.push(RenderObject::rect(
                         Rect::new(0.0, item_y, width, item_height),
                         theme.accent,
                     ));
+                } else if highlighted == Some(i) {
+                    render_objects.push(RenderObject::rect(
+                        Rect::new(0.0, item_y, width, item_height),
+                        theme.muted,
+                    ));
                 }
 
                 // Item text
+                let item_style = TextStyle {
+                    font_family: theme.font_sans.clone(),
+                    font_size: 14.0,
+                    color: if is_selected { theme.accent_foreground } else { theme.popover_foreground },
+                    bold: false,
+                    italic: false,
+                    ..Default::default()
+                };
+                let item_x = direction.place(12.0, text_width(option, &item_style), width);
                 render_objects.push(RenderObject::text(
                     option.clone(),
-                    TextStyle {
-                        font_family: theme.font_sans.clone(),
-                        font_size: 14.0,
-                        color: if is_selected { theme.accent_foreground } else { theme.popover_foreground },
-                        bold: false,
-                        italic: false,
-                    },
-                    Point::new(12.0, item_y + item_height / 2.0 + 5.0),
+                    item_style,
+                    Point::new(item_x, item_y + item_height / 2.0 + 5.0),
                 ));
             }
+
+            // Menu is portaled to the overlay layer so ScrollArea/Card clips don't cut it off
+            let menu = render_objects.split_off(menu_start);
+            render_objects.push(DismissBarrier::overlay(RenderObject::group(menu)));
         }
 
         WidgetNode::Leaf(RenderObject::group(render_objects))
@@ -278,6 +392,85 @@ impl Widget for Combobox {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &crate::core::event::UiEvent, context: &mut crate::core::event::EventContext) -> crate::core::event::EventResult {
+        use crate::core::event::{UiEvent, MouseButton, EventResult};
+
+        if self.disabled {
+            return EventResult::Unhandled;
+        }
+
+        match event {
+            UiEvent::PointerDown { button: MouseButton::Left, .. } if context.is_at_target() => {
+                context.request_focus();
+                EventResult::Unhandled
+            }
+            // Focus moving elsewhere closes the menu
+            UiEvent::Blur => {
+                self.nav.write().set_open(false);
+                EventResult::Handled
+            }
+            UiEvent::KeyDown { key, .. } => {
+                if self.handle_key(*key) {
+                    EventResult::Stopped
+                } else {
+                    EventResult::Unhandled
+                }
+            }
+            UiEvent::TextInput { character } if self.searchable => {
+                let character = *character;
+                self.search(|query| query.push(character));
+                EventResult::Stopped
+            }
+            UiEvent::TextInput { character } if !character.is_whitespace() => {
+                match self.nav.write().typeahead(*character, self.options.iter().map(String::as_str)) {
+                    Some(_) => EventResult::Stopped,
+                    None => EventResult::Unhandled,
+                }
+            }
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
+                let width = self.width.unwrap_or(200.0);
+                let height = self.height.unwrap_or(40.0);
+                let item_height = 32.0;
+
+                if Rect::new(0.0, 0.0, width, height).contains(position.x, position.y) {
+                    let mut nav = self.nav.write();
+                    let open = nav.is_open(self.open);
+                    nav.set_open(!open);
+                    return EventResult::Stopped;
+                }
+
+                if self.is_open() {
+                    let top = self.items_top(height, item_height);
+                    for i in 0..self.options.len() {
+                        let item_rect = Rect::new(0.0, top + i as f32 * item_height, width, item_height);
+                        if item_rect.contains(position.x, position.y) {
+                            {
+                                let mut nav = self.nav.write();
+                                nav.highlighted = Some(i);
+                                nav.set_open(false);
+                            }
+                            self.select(i);
+                            return EventResult::Stopped;
+                        }
+                    }
+
+                    // Clicks outside the menu land on the barrier and close it
+                    if DismissBarrier::is_dismissal(event, self.menu_rect(width, height, item_height)) {
+                        self.nav.write().set_open(false);
+                    }
+                    return EventResult::Stopped;
+                }
+
+                EventResult::Unhandled
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.disabled
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -0,0 +1,230 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::core::render_object::{Matrix, Point, Rect, RenderObject, TextStyle};
+use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{popup_window, PopupPlacement, PopupSide, Size};
+use crate::theming::Density;
+use crate::widgets::portal::DismissBarrier;
+use crate::ThemeProvider;
+
+type MenuAction = Arc<dyn Fn() + Send + Sync>;
+
+/// Opens a menu of actions where its child is right-clicked, flipping and
+/// clamping it to stay inside the window
+pub struct ContextMenu {
+    pub child: Box<dyn Widget>,
+    pub items: Vec<(String, MenuAction)>,
+    pub width: f32,
+    /// Where the menu was opened, in local coordinates; None while closed
+    open_at: Arc<RwLock<Option<Point>>>,
+    /// Where the menu was placed in the last build, for hit-testing items
+    menu: Arc<RwLock<Option<Rect>>>,
+    /// Density of the last build, for hit-testing items
+    density: Arc<RwLock<Density>>,
+    key: Option<WidgetKey>,
+}
+
+impl ContextMenu {
+    pub fn new(child: Box<dyn Widget>) -> Self {
+        Self {
+            child,
+            items: Vec::new(),
+            width: 180.0,
+            open_at: Arc::new(RwLock::new(None)),
+            menu: Arc::new(RwLock::new(None)),
+            density: Arc::new(RwLock::new(Density::default())),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            items: self.items.clone(),
+            width: self.width,
+            open_at: self.open_at.clone(),
+            menu: self.menu.clone(),
+            density: self.density.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_item<F>(mut self, label: impl Into<String>, action: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.items.push((label.into(), Arc::new(action)));
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open_at.read().is_some()
+    }
+
+    /// Open the menu at `position`, in the widget's local coordinates
+    pub fn open_at(&self, position: Point) {
+        *self.open_at.write() = Some(position);
+        *self.menu.write() = None;
+    }
+
+    pub fn close(&self) {
+        *self.open_at.write() = None;
+    }
+}
+
+impl StatelessWidget for ContextMenu {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let child = match ctx.build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(child) => child,
+            other => return other,
+        };
+        let Some(position) = *self.open_at.read() else {
+            return WidgetNode::Leaf(child);
+        };
+
+        let theme = ctx.theme();
+        let density = theme.density();
+        *self.density.write() = density;
+        let item_height = density.item_height();
+        let size = Size::new(self.width, self.items.len() as f32 * item_height);
+        // Below and after the pointer, as if it were a zero-sized anchor
+        let (menu_rect, _) = PopupPlacement::new(PopupSide::Bottom).with_offset(0.0).place(
+            Rect::new(position.x, position.y, 0.0, 0.0),
+            size,
+            popup_window(ctx),
+        );
+        *self.menu.write() = Some(menu_rect);
+
+        let mut menu = vec![
+            RenderObject::rect(Rect::from_size(size), theme.popover),
+            RenderObject::rect(Rect::new(0.0, 0.0, size.width, 1.0), theme.border),
+            RenderObject::rect(Rect::new(size.width - 1.0, 0.0, 1.0, size.height), theme.border),
+            RenderObject::rect(Rect::new(0.0, size.height - 1.0, size.width, 1.0), theme.border),
+            RenderObject::rect(Rect::new(0.0, 0.0, 1.0, size.height), theme.border),
+        ];
+        for (i, (label, _)) in self.items.iter().enumerate() {
+            menu.push(RenderObject::text(
+                label.clone(),
+                TextStyle {
+                    font_family: theme.font_sans.clone(),
+                    font_size: 14.0,
+                    color: theme.popover_foreground,
+                    ..Default::default()
+                },
+                Point::new(12.0, i as f32 * item_height + item_height / 2.0 + 5.0),
+            ));
+        }
+
+        WidgetNode::Leaf(RenderObject::group(vec![
+            child,
+            DismissBarrier::overlay(RenderObject::transform(
+                Matrix::translate(menu_rect.x, menu_rect.y),
+                RenderObject::group(menu),
+            )),
+        ]))
+    }
+}
+
+impl Widget for ContextMenu {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, _context: &mut EventContext) -> EventResult {
+        let menu = *self.menu.read();
+        match (event, menu) {
+            // Right-clicks bubble up from the child
+            (UiEvent::PointerUp { position, button: MouseButton::Right, .. }, _) => {
+                self.open_at(*position);
+                EventResult::Stopped
+            }
+            (UiEvent::PointerUp { position, button: MouseButton::Left, .. }, Some(menu)) if self.is_open() => {
+                if menu.contains(position.x, position.y) {
+                    let item_height = self.density.read().item_height();
+                    let index = ((position.y - menu.y) / item_height) as usize;
+                    self.close();
+                    if let Some((_, action)) = self.items.get(index) {
+                        action();
+                    }
+                } else if DismissBarrier::is_dismissal(event, menu) {
+                    self.close();
+                }
+                EventResult::Stopped
+            }
+            (UiEvent::KeyDown { .. }, Some(menu)) if self.is_open() && DismissBarrier::is_dismissal(event, menu) => {
+                self.close();
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::event::EventPhase;
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use crate::runtime::widget_builder::WidgetBuilder;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_menu_stays_in_the_window_and_runs_items() {
+        let copies = Arc::new(AtomicUsize::new(0));
+        let counter = copies.clone();
+        let menu = ContextMenu::new(Box::new(SizedBox::new(400.0, 300.0)))
+            .with_item("Copy", move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .with_item("Paste", || {});
+        let root: Box<dyn Widget> = Box::new(menu.clone());
+        let builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(new_shared_element_tree());
+        let window = Constraints::new(0.0, 400.0, 0.0, 300.0);
+        builder.build_widget_tree(&root, window);
+
+        // Right-clicked near the bottom right corner: the 64px tall menu
+        // opens above the pointer and is pulled in from the right edge
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::Bubbling);
+        let position = Point::new(390.0, 280.0);
+        menu.handle_event(&UiEvent::PointerUp { id: 0, position, button: MouseButton::Right }, &mut context);
+        builder.build_widget_tree(&root, window);
+        let placed = menu.menu.read().unwrap();
+        assert_eq!(placed, Rect::new(212.0, 216.0, 180.0, 64.0));
+
+        let position = Point::new(250.0, 220.0);
+        menu.handle_event(&UiEvent::PointerUp { id: 0, position, button: MouseButton::Left }, &mut context);
+        assert!(!menu.is_open());
+        assert_eq!(copies.load(Ordering::SeqCst), 1);
+    }
+}
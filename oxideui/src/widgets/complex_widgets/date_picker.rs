@@ -1,8 +1,11 @@
 use std::any::Any;
 use std::sync::Arc;
+use parking_lot::RwLock;
 use crate::core::context::BuildContext;
 use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{PopupPlacement, PopupSide, Size};
+use crate::widgets::portal::DismissBarrier;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -16,9 +19,23 @@ pub struct DatePicker {
     pub open: bool,
     pub on_change: Option<Arc<dyn Fn(String) + Send + Sync>>,
     pub tooltip: Option<String>,
+    /// Popup state and keyboard-focused day, shared across rebuilds
+    state: Arc<RwLock<PickerState>>,
     key: Option<WidgetKey>,
 }
 
+#[derive(Debug, Default)]
+struct PickerState {
+    /// Open state toggled by keyboard or click; `None` defers to the prop
+    open: Option<bool>,
+    /// Day the keyboard is on; the displayed month follows it
+    focused: Option<CalendarDate>,
+    /// First column of the grid from the last build's locale, 0 for Sunday
+    first_weekday: u32,
+    /// Where the calendar opened in the last build, for hit-testing days
+    popup: Option<Rect>,
+}
+
 impl DatePicker {
     pub fn new() -> Self {
         Self {
@@ -31,6 +48,7 @@ impl DatePicker {
             open: false,
             on_change: None,
             tooltip: None,
+            state: Arc::new(RwLock::new(PickerState::default())),
             key: None,
         }
     }
@@ -83,6 +101,94 @@ impl DatePicker {
         self.key = Some(key);
         self
     }
+
+    /// Whether the calendar is showing, after any keyboard or click toggles
+    pub fn is_open(&self) -> bool {
+        self.state.read().open.unwrap_or(self.open)
+    }
+
+    fn selected_date(&self) -> Option<CalendarDate> {
+        self.value.as_deref().and_then(|value| CalendarDate::parse(value, &self.format))
+    }
+
+    /// The focused day, else the selection, else today
+    fn focused_date(&self) -> CalendarDate {
+        self.state.read().focused.or_else(|| self.selected_date()).unwrap_or_else(CalendarDate::today)
+    }
+
+    fn set_open(&self, open: bool) {
+        let mut state = self.state.write();
+        state.open = Some(open);
+        state.focused = None;
+    }
+
+    fn select(&self, date: CalendarDate) {
+        self.set_open(false);
+        if let Some(on_change) = &self.on_change {
+            on_change(date.format(&self.format));
+        }
+    }
+
+    /// Arrows move by day and week, PageUp/PageDown by month (with Shift, by
+    /// year), Home/End to the ends of the week, Enter picks, Escape closes
+    fn handle_key(&self, key: winit::keyboard::KeyCode, shift: bool) -> bool {
+        use winit::keyboard::KeyCode;
+
+        if !self.is_open() {
+            if matches!(key, KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space | KeyCode::ArrowDown) {
+                self.set_open(true);
+                return true;
+            }
+            return false;
+        }
+
+        let date = self.focused_date();
+        let moved = match key {
+            KeyCode::ArrowLeft => date.add_days(-1),
+            KeyCode::ArrowRight => date.add_days(1),
+            KeyCode::ArrowUp => date.add_days(-7),
+            KeyCode::ArrowDown => date.add_days(7),
+            KeyCode::PageUp => date.add_months(if shift { -12 } else { -1 }),
+            KeyCode::PageDown => date.add_months(if shift { 12 } else { 1 }),
+            KeyCode::Home => date.add_days(-(self.column(date) as i64)),
+            KeyCode::End => date.add_days(6 - self.column(date) as i64),
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space => {
+                self.select(date);
+                return true;
+            }
+            KeyCode::Escape => {
+                self.set_open(false);
+                return true;
+            }
+            _ => return false,
+        };
+        self.state.write().focused = Some(moved);
+        true
+    }
+
+    /// The calendar as last placed, else below the input box
+    fn popup_rect(&self, height: f32) -> Rect {
+        self.state.read().popup.unwrap_or(Rect::new(0.0, height + 4.0, 280.0, 320.0))
+    }
+
+    /// Grid column of `date` given the locale's first day of the week
+    fn column(&self, date: CalendarDate) -> u32 {
+        (date.weekday() + 7 - self.state.read().first_weekday) % 7
+    }
+
+    /// Screen rect of each day cell in the month grid, shared by layout and hit-testing
+    fn day_cells(&self, month: CalendarDate, popup: Rect) -> impl Iterator<Item = (CalendarDate, Rect)> {
+        let cell_size = 36.0;
+        let days_start_y = popup.y + 60.0 + 25.0;
+        let first = CalendarDate { day: 1, ..month };
+        let offset = self.column(first);
+        (1..=days_in_month(month.year, month.month)).map(move |day| {
+            let slot = offset + day - 1;
+            let x = popup.x + 10.0 + (slot % 7) as f32 * cell_size;
+            let y = days_start_y + (slot / 7) as f32 * cell_size;
+            (CalendarDate { day, ..month }, Rect::new(x, y - 15.0, cell_size, cell_size))
+        })
+    }
 }
 
 impl StatelessWidget for DatePicker {
@@ -90,6 +196,8 @@ impl StatelessWidget for DatePicker {
         let theme = ctx.theme();
         let width = self.width.unwrap_or(200.0);
         let height = self.height.unwrap_or(40.0);
+        let locale = ctx.locale_format();
+        self.state.write().first_weekday = locale.first_day_of_week();
 
         let bg_color = if self.disabled {
             theme.muted
@@ -136,8 +244,12 @@ impl StatelessWidget for DatePicker {
         ));
 
         // Display value or placeholder
+        // Localized apps show the date in the locale's pattern
         let display_text = if let Some(value) = &self.value {
-            value.clone()
+            match self.selected_date() {
+                Some(date) if ctx.localizer().is_some() => date.format(locale.date_pattern()),
+                _ => value.clone(),
+            }
         } else {
             self.placeholder.clone()
         };
@@ -156,6 +268,7 @@ impl StatelessWidget for DatePicker {
                 color: display_color,
                 bold: false,
                 italic: false,
+                ..Default::default()
             },
             Point::new(12.0, height / 2.0 + 5.0),
         ));
@@ -169,16 +282,24 @@ impl StatelessWidget for DatePicker {
                 color: theme.muted_foreground,
                 bold: false,
                 italic: false,
+                ..Default::default()
             },
             Point::new(width - 30.0, height / 2.0 + 5.0),
         ));
 
         // Calendar popup (if open)
-        if self.open && !self.disabled {
-            let calendar_width = 280.0;
-            let calendar_height = 320.0;
-            let calendar_x = 0.0;
-            let calendar_y = height + 4.0;
+        if self.is_open() && !self.disabled {
+            let focused = self.focused_date();
+            let selected = self.selected_date();
+            let today = CalendarDate::today();
+
+            // Below the input, or above it near the bottom of the window
+            let placement = PopupPlacement::new(PopupSide::Bottom);
+            let (popup, _) = placement.place_for(ctx, Size::new(width, height), Size::new(280.0, 320.0));
+            self.state.write().popup = Some(popup);
+            let (calendar_x, calendar_y) = (popup.x, popup.y);
+            let (calendar_width, calendar_height) = (popup.width, popup.height);
+            let popup_start = render_objects.len();
 
             // Calendar background
             render_objects.push(RenderObject::rect(
@@ -206,13 +327,14 @@ impl StatelessWidget for DatePicker {
 
             // Calendar header (month/year)
             render_objects.push(RenderObject::text(
-                "March 2024".to_string(), // Hardcoded for example
+                format!("{} {}", MONTH_NAMES[focused.month as usize - 1], focused.year),
                 TextStyle {
                     font_family: theme.font_sans.clone(),
                     font_size: 16.0,
                     color: theme.popover_foreground,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(calendar_x + 20.0, calendar_y + 30.0),
             ));
@@ -222,7 +344,8 @@ impl StatelessWidget for DatePicker {
             let cell_size = 36.0;
             let header_start_y = calendar_y + 60.0;
 
-            for (i, day) in day_headers.iter().enumerate() {
+            let first_weekday = locale.first_day_of_week() as usize;
+            for (i, day) in day_headers.iter().cycle().skip(first_weekday).take(7).enumerate() {
                 let x = calendar_x + 10.0 + (i as f32 * cell_size);
                 render_objects.push(RenderObject::text(
                     day.to_string(),
@@ -232,39 +355,48 @@ impl StatelessWidget for DatePicker {
                         color: theme.muted_foreground,
                         bold: true,
                         italic: false,
+                        ..Default::default()
                     },
                     Point::new(x, header_start_y),
                 ));
             }
 
-            // Calendar days (example grid)
-            let days_start_y = header_start_y + 25.0;
-            for week in 0..6 {
-                for day in 0..7 {
-                    let day_number = (week * 7 + day + 1).min(31);
-                    let x = calendar_x + 10.0 + (day as f32 * cell_size);
-                    let y = days_start_y + (week as f32 * cell_size);
-
-                    let is_today = day_number == 15; // Example: today is 15th
-                    let day_color = if is_today {
-                        theme.primary
-                    } else {
-                        theme.popover_foreground
-                    };
-
-                    render_objects.push(RenderObject::text(
-                        day_number.to_string(),
-                        TextStyle {
-                            font_family: theme.font_sans.clone(),
-                            font_size: 14.0,
-                            color: day_color,
-                            bold: is_today,
-                            italic: false,
-                        },
-                        Point::new(x + 10.0, y + 10.0),
-                    ));
+            // Calendar days, with the selected day filled and the keyboard's day outlined
+            for (date, cell) in self.day_cells(focused, popup) {
+                let cell_rect = Rect::new(cell.x + 2.0, cell.y + 2.0, cell.width - 4.0, cell.height - 4.0);
+                let is_selected = selected == Some(date);
+                if is_selected {
+                    render_objects.push(RenderObject::rect(cell_rect, theme.primary));
+                } else if date == focused {
+                    render_objects.push(RenderObject::rect(cell_rect, theme.accent));
                 }
+
+                let is_today = date == today;
+                let day_color = if is_selected {
+                    theme.primary_foreground
+                } else if is_today {
+                    theme.primary
+                } else {
+                    theme.popover_foreground
+                };
+
+                render_objects.push(RenderObject::text(
+                    date.day.to_string(),
+                    TextStyle {
+                        font_family: theme.font_sans.clone(),
+                        font_size: 14.0,
+                        color: day_color,
+                        bold: is_today,
+                        italic: false,
+                        ..Default::default()
+                    },
+                    Point::new(cell.x + 10.0, cell.y + 25.0),
+                ));
             }
+
+            // Portaled to the overlay layer, over a barrier for outside clicks
+            let calendar = render_objects.split_off(popup_start);
+            render_objects.push(DismissBarrier::overlay(RenderObject::group(calendar)));
         }
 
         WidgetNode::Leaf(RenderObject::group(render_objects))
@@ -276,6 +408,65 @@ impl Widget for DatePicker {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &crate::core::event::UiEvent, context: &mut crate::core::event::EventContext) -> crate::core::event::EventResult {
+        use crate::core::event::{UiEvent, MouseButton, EventResult};
+
+        if self.disabled {
+            return EventResult::Unhandled;
+        }
+
+        match event {
+            UiEvent::PointerDown { button: MouseButton::Left, .. } if context.is_at_target() => {
+                context.request_focus();
+                EventResult::Unhandled
+            }
+            // Focus moving elsewhere closes the calendar
+            UiEvent::Blur => {
+                self.set_open(false);
+                EventResult::Handled
+            }
+            UiEvent::KeyDown { key, modifiers, .. } => {
+                if self.handle_key(*key, modifiers.shift) {
+                    EventResult::Stopped
+                } else {
+                    EventResult::Unhandled
+                }
+            }
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
+                let width = self.width.unwrap_or(200.0);
+                let height = self.height.unwrap_or(40.0);
+
+                if Rect::new(0.0, 0.0, width, height).contains(position.x, position.y) {
+                    self.set_open(!self.is_open());
+                    return EventResult::Stopped;
+                }
+
+                if self.is_open() {
+                    let picked = self
+                        .day_cells(self.focused_date(), self.popup_rect(height))
+                        .find(|(_, cell)| cell.contains(position.x, position.y));
+                    if let Some((date, _)) = picked {
+                        self.select(date);
+                        return EventResult::Stopped;
+                    }
+
+                    // Clicks outside the calendar land on the barrier and close it
+                    if DismissBarrier::is_dismissal(event, self.popup_rect(height)) {
+                        self.set_open(false);
+                    }
+                    return EventResult::Stopped;
+                }
+
+                EventResult::Unhandled
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.disabled
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -287,4 +478,133 @@ impl Widget for DatePicker {
     fn clone_box(&self) -> Box<dyn Widget> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// A day in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CalendarDate {
+    year: i32,
+    /// 1-12
+    month: u32,
+    /// 1-31
+    day: u32,
+}
+
+impl CalendarDate {
+    fn today() -> Self {
+        let secs = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or(0);
+        Self::from_days(secs.div_euclid(86_400))
+    }
+
+    /// Date `days` after 1970-01-01 (Howard Hinnant's `civil_from_days`)
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01
+    fn to_days(self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// Same day `months` later, clamped to the end of shorter months
+    fn add_months(self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+        Self { year, month, day: self.day.min(days_in_month(year, month)) }
+    }
+
+    /// 0 for Sunday through 6 for Saturday
+    fn weekday(self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.to_days() + 4).rem_euclid(7) as u32
+    }
+
+    /// Parse with the `%Y`, `%m` and `%d` fields of `format`
+    fn parse(text: &str, format: &str) -> Option<Self> {
+        let (mut year, mut month, mut day) = (None, None, None);
+        let mut rest = text;
+        let mut spec = format.chars();
+        while let Some(c) = spec.next() {
+            if c != '%' {
+                rest = rest.strip_prefix(c)?;
+                continue;
+            }
+            let field = spec.next()?;
+            let max_digits = match field {
+                'Y' => 4,
+                'm' | 'd' => 2,
+                _ => return None,
+            };
+            let len = rest.chars().take(max_digits).take_while(char::is_ascii_digit).count();
+            let value: u32 = rest[..len].parse().ok()?;
+            rest = &rest[len..];
+            match field {
+                'Y' => year = Some(value as i32),
+                'm' => month = Some(value),
+                _ => day = Some(value),
+            }
+        }
+        let date = Self { year: year?, month: month?, day: day? };
+        let valid = (1..=12).contains(&date.month) && (1..=days_in_month(date.year, date.month)).contains(&date.day);
+        (valid && rest.is_empty()).then_some(date)
+    }
+
+    fn format(self, format: &str) -> String {
+        format
+            .replace("%Y", &format!("{:04}", self.year))
+            .replace("%m", &format!("{:02}", self.month))
+            .replace("%d", &format!("{:02}", self.day))
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_date_math() {
+        let date = CalendarDate::parse("2024-03-15", "%Y-%m-%d").unwrap();
+        assert_eq!(date, CalendarDate { year: 2024, month: 3, day: 15 });
+        assert_eq!(date.weekday(), 5); // Friday
+        assert_eq!(date.add_days(17).format("%Y-%m-%d"), "2024-04-01");
+        assert_eq!(CalendarDate { year: 2024, month: 1, day: 31 }.add_months(1).day, 29);
+        assert_eq!(date.add_months(-3).format("%d/%m/%Y"), "15/12/2023");
+        assert_eq!(CalendarDate::parse("15/12/2023", "%d/%m/%Y"), Some(date.add_months(-3)));
+        assert_eq!(CalendarDate::parse("2023-02-29", "%Y-%m-%d"), None);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Color, Matrix, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::widgets::element_widgets::Icon;
 use std::any::Any;
 use std::sync::Arc;
 
@@ -144,6 +145,7 @@ impl StatelessWidget for Dialog {
                 color: theme.popover_foreground,
                 bold: true,
                 italic: false,
+                ..Default::default()
             },
             Point::new(x + 16.0, y + 20.0),
         ));
@@ -158,6 +160,7 @@ impl StatelessWidget for Dialog {
                     color: theme.muted_foreground,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(x + 16.0, y + 50.0),
             ));
@@ -175,16 +178,9 @@ impl StatelessWidget for Dialog {
             theme.destructive,
         ));
 
-        render_objects.push(RenderObject::text(
-            "×".to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 18.0,
-                color: theme.destructive_foreground,
-                bold: true,
-                italic: false,
-            },
-            Point::new(x + width - close_button_size - 4.0, y + 10.0),
+        render_objects.push(RenderObject::transform(
+            Matrix::translate(x + width - close_button_size - 4.0, y + 12.0),
+            Icon::new("x").with_size(16.0).to_render_object(theme.destructive_foreground),
         ));
 
         WidgetNode::Leaf(RenderObject::group(render_objects))
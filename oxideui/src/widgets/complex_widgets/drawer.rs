@@ -1,8 +1,9 @@
 use std::any::Any;
 use std::sync::Arc;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Color, Matrix, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::widgets::element_widgets::Icon;
 use crate::ThemeProvider;
 
 pub struct Drawer {
@@ -191,6 +192,7 @@ impl StatelessWidget for Drawer {
                     color: theme.popover_foreground,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(x + 20.0, title_y),
             ));
@@ -209,16 +211,9 @@ impl StatelessWidget for Drawer {
             theme.destructive,
         ));
 
-        render_objects.push(RenderObject::text(
-            "×".to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 18.0,
-                color: theme.destructive_foreground,
-                bold: true,
-                italic: false,
-            },
-            Point::new(close_x + 4.0, close_y + 4.0),
+        render_objects.push(RenderObject::transform(
+            Matrix::translate(close_x + 4.0, close_y + 4.0),
+            Icon::new("x").with_size(16.0).to_render_object(theme.destructive_foreground),
         ));
 
         WidgetNode::Leaf(RenderObject::group(render_objects))
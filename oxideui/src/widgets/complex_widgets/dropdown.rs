@@ -1,8 +1,16 @@
 use std::any::Any;
 use std::sync::Arc;
+use parking_lot::RwLock;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Matrix, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{PopupPlacement, PopupSide, Size};
+use crate::render::text::text_width;
+use crate::widgets::portal::DismissBarrier;
+use crate::widgets::element_widgets::Icon;
+use crate::widgets::keyboard_nav::ListNavigator;
+use crate::state_management::bindable::Binding;
+use crate::theming::Density;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -15,7 +23,14 @@ pub struct Dropdown {
     pub disabled: bool,
     pub open: bool,
     pub on_change: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    pub binding: Option<Binding<Option<usize>>>,
     pub tooltip: Option<String>,
+    /// Density of the last build, for hit-testing items
+    density: Arc<RwLock<Density>>,
+    /// Keyboard highlight and open state, shared across rebuilds
+    nav: Arc<RwLock<ListNavigator>>,
+    /// Where the menu opened in the last build, for hit-testing items
+    menu: Arc<RwLock<Option<Rect>>>,
     key: Option<WidgetKey>,
 }
 
@@ -30,7 +45,11 @@ impl Dropdown {
             disabled: false,
             open: false,
             on_change: None,
+            binding: None,
             tooltip: None,
+            density: Arc::new(RwLock::new(Density::default())),
+            nav: Arc::new(RwLock::new(ListNavigator::new())),
+            menu: Arc::new(RwLock::new(None)),
             key: None,
         }
     }
@@ -69,6 +88,12 @@ impl Dropdown {
         self
     }
 
+    /// Read the selection from, and write picks back to, a binding
+    pub fn with_binding(mut self, binding: impl Into<Binding<Option<usize>>>) -> Self {
+        self.binding = Some(binding.into());
+        self
+    }
+
     pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
         self.tooltip = Some(tooltip.into());
         self
@@ -78,14 +103,91 @@ impl Dropdown {
         self.key = Some(key);
         self
     }
+
+    /// Current selection, preferring the binding over the static value
+    pub fn current_selection(&self) -> Option<usize> {
+        let selected = match &self.binding {
+            Some(binding) => binding.get(),
+            None => self.selected,
+        };
+        selected.filter(|i| *i < self.options.len())
+    }
+
+    /// Whether the menu is showing, after any keyboard or click toggles
+    pub fn is_open(&self) -> bool {
+        self.nav.read().is_open(self.open)
+    }
+
+    fn select(&self, index: usize) {
+        if let Some(binding) = &self.binding {
+            binding.set(Some(index));
+        }
+        if let Some(on_change) = &self.on_change {
+            on_change(index);
+        }
+    }
+
+    fn menu_height(&self, item_height: f32) -> f32 {
+        (self.options.len() as f32 * item_height).min(200.0)
+    }
+
+    /// The open menu as last placed, else below the dropdown box
+    fn menu_rect(&self, width: f32, height: f32, item_height: f32) -> Rect {
+        self.menu.read().unwrap_or_else(|| Rect::new(0.0, height, width, self.menu_height(item_height)))
+    }
+
+    /// Arrows, Home/End and Enter/Space while focused; Escape closes the menu
+    fn handle_key(&self, key: winit::keyboard::KeyCode) -> bool {
+        use winit::keyboard::KeyCode;
+
+        let mut nav = self.nav.write();
+        let open = nav.is_open(self.open);
+        if nav.highlighted.is_none() {
+            nav.highlighted = self.current_selection();
+        }
+        match key {
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space if open => {
+                let highlighted = nav.highlighted;
+                nav.set_open(false);
+                drop(nav);
+                if let Some(index) = highlighted {
+                    self.select(index);
+                }
+                true
+            }
+            KeyCode::Escape if open => {
+                nav.set_open(false);
+                true
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space | KeyCode::ArrowDown | KeyCode::ArrowUp
+                if !open =>
+            {
+                nav.set_open(true);
+                true
+            }
+            _ if open => nav.move_key(key, self.options.len(), 5),
+            _ => false,
+        }
+    }
 }
 
 impl StatelessWidget for Dropdown {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
+        let density = theme.density();
+        *self.density.write() = density;
         let width = self.width.unwrap_or(200.0);
-        let height = self.height.unwrap_or(40.0);
-        let item_height = 32.0;
+        let height = self.height.unwrap_or(density.control_height());
+        let item_height = density.item_height();
+
+        if let Some(binding) = &self.binding {
+            binding.subscribe(ctx.element_id);
+        }
+        let selected = self.current_selection();
+        let (open, highlighted) = {
+            let nav = self.nav.read();
+            (nav.is_open(self.open), nav.highlighted)
+        };
 
         let bg_color = if self.disabled {
             theme.muted
@@ -132,97 +234,116 @@ impl StatelessWidget for Dropdown {
         ));
 
         // Selected value or placeholder
-        let display_text = if let Some(selected) = self.selected {
+        let display_text = if let Some(selected) = selected {
             &self.options[selected]
         } else {
             &self.placeholder
         };
 
-        let display_color = if self.selected.is_none() && !self.disabled {
+        let display_color = if selected.is_none() && !self.disabled {
             theme.muted_foreground
         } else {
             text_color
         };
 
+        // Text and chevron swap sides in RTL
+        let direction = ctx.direction();
+        let display_style = TextStyle {
+            font_family: theme.font_sans.clone(),
+            font_size: 14.0,
+            color: display_color,
+            bold: false,
+            italic: false,
+            ..Default::default()
+        };
+        let display_x = direction.place(12.0, text_width(display_text, &display_style), width);
         render_objects.push(RenderObject::text(
             display_text.to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 14.0,
-                color: display_color,
-                bold: false,
-                italic: false,
-            },
-            Point::new(12.0, height / 2.0 + 5.0),
+            display_style,
+            Point::new(display_x, height / 2.0 + 5.0),
         ));
 
         // Dropdown arrow
-        render_objects.push(RenderObject::text(
-            "▼".to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 12.0,
-                color: theme.muted_foreground,
-                bold: false,
-                italic: false,
-            },
-            Point::new(width - 24.0, height / 2.0 + 5.0),
+        render_objects.push(RenderObject::transform(
+            Matrix::translate(direction.place(width - 28.0, 16.0, width), (height - 16.0) / 2.0),
+            Icon::new("chevron-down").with_size(16.0).to_render_object(theme.muted_foreground),
         ));
 
         // Dropdown menu (if open)
-        if self.open && !self.disabled {
-            let menu_height = (self.options.len() as f32 * item_height).min(200.0);
+        if open && !self.disabled {
+            let menu_start = render_objects.len();
+            let menu_size = Size::new(width, self.menu_height(item_height));
+            let placement = PopupPlacement::new(PopupSide::Bottom).with_offset(0.0);
+            let (menu_rect, _) = placement.place_for(ctx, Size::new(width, height), menu_size);
+            *self.menu.write() = Some(menu_rect);
+            let menu_height = menu_size.height;
 
             // Menu background
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, height, width, menu_height),
+                Rect::new(0.0, 0.0, width, menu_height),
                 theme.popover,
             ));
 
             // Menu border
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, height, width, 1.0),
+                Rect::new(0.0, 0.0, width, 1.0),
                 theme.border,
             ));
             render_objects.push(RenderObject::rect(
-                Rect::new(width - 1.0, height, 1.0, menu_height),
+                Rect::new(width - 1.0, 0.0, 1.0, menu_height),
                 theme.border,
             ));
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, height + menu_height - 1.0, width, 1.0),
+                Rect::new(0.0, menu_height - 1.0, width, 1.0),
                 theme.border,
             ));
             render_objects.push(RenderObject::rect(
-                Rect::new(0.0, height, 1.0, menu_height),
+                Rect::new(0.0, 0.0, 1.0, menu_height),
                 theme.border,
             ));
 
             // Menu items
             for (i, option) in self.options.iter().enumerate() {
-                let item_y = height + (i as f32 * item_height);
-                let is_selected = self.selected == Some(i);
+                let item_y = i as f32 * item_height;
+                let is_selected = selected == Some(i);
 
-                // Item background (hover/selected effect)
+                // Item background (selected, then keyboard highlight)
                 if is_selected {
                     render_objects.push(RenderObject::rect(
                         Rect::new(0.0, item_y, width, item_height),
                         theme.accent,
                     ));
+                } else if highlighted == Some(i) {
+                    render_objects.push(RenderObject::rect(
+                        Rect::new(0.0, item_y, width, item_height),
+                        theme.muted,
+                    ));
                 }
 
                 // Item text
+                let item_style = TextStyle {
+                    font_family: theme.font_sans.clone(),
+                    font_size: 14.0,
+                    color: if is_selected { theme.accent_foreground } else { theme.popover_foreground },
+                    bold: false,
+                    italic: false,
+                    ..Default::default()
+                };
+                let item_x = direction.place(12.0, text_width(option, &item_style), width);
                 render_objects.push(RenderObject::text(
                     option.clone(),
-                    TextStyle {
-                        font_family: theme.font_sans.clone(),
-                        font_size: 14.0,
-                        color: if is_selected { theme.accent_foreground } else { theme.popover_foreground },
-                        bold: false,
-                        italic: false,
-                    },
-                    Point::new(12.0, item_y + item_height / 2.0 + 5.0),
+                    item_style,
+                    Point::new(item_x, item_y + item_height / 2.0 + 5.0),
                 ));
             }
+
+            // Menu is portaled to the overlay layer so ScrollArea/Card clips don't cut it off,
+            // below the box or above it near the bottom of the window
+            let menu = render_objects.split_off(menu_start);
+            render_objects.push(DismissBarrier::overlay(RenderObject::transform(
+                Matrix::translate(menu_rect.x, menu_rect.y),
+                RenderObject::group(menu),
+            )));
         }
 
         WidgetNode::Leaf(RenderObject::group(render_objects))
@@ -242,32 +363,72 @@ impl Widget for Dropdown {
         }
 
         match event {
+            UiEvent::PointerDown { button: MouseButton::Left, .. } if context.is_at_target() => {
+                context.request_focus();
+                EventResult::Unhandled
+            }
+            // Focus moving elsewhere closes the menu
+            UiEvent::Blur => {
+                self.nav.write().set_open(false);
+                EventResult::Handled
+            }
+            UiEvent::KeyDown { key, .. } => {
+                if self.handle_key(*key) {
+                    EventResult::Stopped
+                } else {
+                    EventResult::Unhandled
+                }
+            }
+            UiEvent::TextInput { character } if !character.is_whitespace() => {
+                let found = self.nav.write().typeahead(*character, self.options.iter().map(String::as_str));
+                match found {
+                    // A closed dropdown selects as you type, like a native select
+                    Some(index) if !self.is_open() => {
+                        self.select(index);
+                        EventResult::Stopped
+                    }
+                    Some(_) => EventResult::Stopped,
+                    None => EventResult::Unhandled,
+                }
+            }
             UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
+                let density = *self.density.read();
                 let width = self.width.unwrap_or(200.0);
-                let height = self.height.unwrap_or(40.0);
+                let height = self.height.unwrap_or(density.control_height());
 
                 // Check if clicked on main dropdown
                 let main_rect = Rect::new(0.0, 0.0, width, height);
                 if main_rect.contains(position.x, position.y) {
-                    // Toggle open state (this would need state management)
-                    println!("Dropdown clicked - would toggle open state");
+                    let mut nav = self.nav.write();
+                    let open = nav.is_open(self.open);
+                    nav.set_open(!open);
                     return EventResult::Stopped;
                 }
 
                 // Check if clicked on menu item
-                if self.open {
-                    let item_height = 32.0;
+                if self.is_open() {
+                    let item_height = density.item_height();
+                    let menu = self.menu_rect(width, height, item_height);
                     for (i, _) in self.options.iter().enumerate() {
-                        let item_y = height + (i as f32 * item_height);
-                        let item_rect = Rect::new(0.0, item_y, width, item_height);
+                        let item_y = menu.y + (i as f32 * item_height);
+                        let item_rect = Rect::new(menu.x, item_y, width, item_height);
 
                         if item_rect.contains(position.x, position.y) {
-                            if let Some(on_change) = &self.on_change {
-                                on_change(i);
+                            {
+                                let mut nav = self.nav.write();
+                                nav.highlighted = Some(i);
+                                nav.set_open(false);
                             }
+                            self.select(i);
                             return EventResult::Stopped;
                         }
                     }
+
+                    // Clicks outside the menu land on the barrier and close it
+                    if DismissBarrier::is_dismissal(event, self.menu_rect(width, height, item_height)) {
+                        self.nav.write().set_open(false);
+                    }
+                    return EventResult::Stopped;
                 }
 
                 EventResult::Unhandled
@@ -276,6 +437,10 @@ impl Widget for Dropdown {
         }
     }
 
+    fn is_focusable(&self) -> bool {
+        !self.disabled
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -287,4 +452,62 @@ impl Widget for Dropdown {
     fn clone_box(&self) -> Box<dyn Widget> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::ElementId;
+    use crate::core::event::{EventContext, EventPhase, MouseButton, UiEvent};
+
+    fn click(dropdown: &Dropdown, x: f32, y: f32) {
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        let event = UiEvent::PointerUp { id: 0, position: Point::new(x, y), button: MouseButton::Left };
+        dropdown.handle_event(&event, &mut context);
+    }
+
+    #[test]
+    fn test_outside_click_and_blur_close_the_menu() {
+        let dropdown = Dropdown::new(vec!["Red".into(), "Green".into()]).with_size(200.0, 40.0);
+        click(&dropdown, 20.0, 20.0);
+        assert!(dropdown.is_open());
+        // Picking an item closes the menu, as does a click outside it
+        click(&dropdown, 20.0, 40.0 + 2.0 * Density::default().item_height() - 1.0);
+        assert!(!dropdown.is_open());
+
+        click(&dropdown, 20.0, 20.0);
+        click(&dropdown, 500.0, 500.0);
+        assert!(!dropdown.is_open());
+
+        click(&dropdown, 20.0, 20.0);
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        dropdown.handle_event(&UiEvent::Blur, &mut context);
+        assert!(!dropdown.is_open());
+    }
+
+    #[test]
+    fn test_menu_opens_above_near_the_bottom_edge() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+        use crate::runtime::widget_builder::WidgetBuilder;
+        use crate::widgets::basic::Column;
+        use crate::widgets::layout_widgets::SizedBox;
+
+        let dropdown = Dropdown::new(vec!["Red".into(), "Green".into(), "Blue".into()])
+            .with_size(200.0, 40.0)
+            .open(true);
+        let root: Box<dyn Widget> = Box::new(Column::new().with_children(vec![
+            Box::new(SizedBox::height(500.0)),
+            Box::new(dropdown.clone()),
+        ]));
+        let builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(new_shared_element_tree());
+        let window = Constraints::new(0.0, 400.0, 0.0, 600.0);
+
+        // Not laid out yet: below the box
+        builder.build_widget_tree(&root, window);
+        assert_eq!(dropdown.menu.read().unwrap().y, 40.0);
+        // 96px of menu doesn't fit in the 52px left below the box
+        builder.build_widget_tree(&root, window);
+        assert_eq!(dropdown.menu.read().unwrap(), Rect::new(0.0, -96.0, 200.0, 96.0));
+    }
+}
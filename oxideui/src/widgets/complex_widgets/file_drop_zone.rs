@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, UiEvent};
+use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::theming::ColorRole;
+use crate::ThemeProvider;
+
+/// Area that accepts files dragged from the desktop
+pub struct FileDropZone {
+    pub width: f32,
+    pub height: f32,
+    pub label: String,
+    /// Accepted file extensions, lowercase without the dot; empty accepts all
+    pub accept: Vec<String>,
+    pub disabled: bool,
+    pub on_drop: Option<Arc<dyn Fn(Vec<PathBuf>) + Send + Sync>>,
+    hovering: Arc<RwLock<bool>>,
+    key: Option<WidgetKey>,
+}
+
+impl FileDropZone {
+    pub fn new() -> Self {
+        Self {
+            width: 320.0,
+            height: 160.0,
+            label: "Drop files here".to_string(),
+            accept: Vec::new(),
+            disabled: false,
+            on_drop: None,
+            hovering: Arc::new(RwLock::new(false)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            label: self.label.clone(),
+            accept: self.accept.clone(),
+            disabled: self.disabled,
+            on_drop: self.on_drop.clone(),
+            hovering: self.hovering.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Only accept files with these extensions, e.g. `["png", "jpg"]`
+    pub fn with_accept<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.accept = extensions
+            .into_iter()
+            .map(|ext| ext.into().trim_start_matches('.').to_lowercase())
+            .collect();
+        self
+    }
+
+    pub fn with_on_drop<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Vec<PathBuf>) + Send + Sync + 'static,
+    {
+        self.on_drop = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    fn accepts(&self, path: &Path) -> bool {
+        self.accept.is_empty()
+            || path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| self.accept.iter().any(|a| a.eq_ignore_ascii_case(ext)))
+    }
+
+    fn accepted(&self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        paths.iter().filter(|path| self.accepts(path)).cloned().collect()
+    }
+}
+
+impl Default for FileDropZone {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for FileDropZone {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let theme = ctx.theme();
+        let hovering = *self.hovering.read() && !self.disabled;
+        let border_color = theme.role(if hovering { ColorRole::BorderFocus } else { ColorRole::Border });
+        let fill = theme.role(if hovering { ColorRole::SurfaceHover } else { ColorRole::SurfaceRaised });
+        let border = if hovering { 2.0 } else { 1.0 };
+
+        let font_size = theme.tokens.font_sizes.base;
+        let text_width = self.label.chars().count() as f32 * font_size * 0.6;
+        let text_color = theme.role(if self.disabled { ColorRole::TextMuted } else { ColorRole::Text });
+
+        WidgetNode::Leaf(RenderObject::group(vec![
+            RenderObject::rect(Rect::new(0.0, 0.0, self.width, self.height), border_color),
+            RenderObject::rect(
+                Rect::new(border, border, self.width - border * 2.0, self.height - border * 2.0),
+                fill,
+            ),
+            RenderObject::text(
+                self.label.clone(),
+                TextStyle {
+                    font_family: theme.font_sans.clone(),
+                    font_size,
+                    color: text_color,
+                    bold: false,
+                    italic: false,
+                    ..Default::default()
+                },
+                Point::new((self.width - text_width) / 2.0, (self.height - font_size * 1.2) / 2.0),
+            ),
+        ]))
+    }
+
+    fn handle_event(&self, event: &UiEvent, _context: &mut EventContext) -> EventResult {
+        if self.disabled {
+            return EventResult::Unhandled;
+        }
+
+        match event {
+            UiEvent::FileHover { paths, .. } => {
+                let accepted = !self.accepted(paths).is_empty();
+                *self.hovering.write() = accepted;
+                if accepted {
+                    EventResult::Stopped
+                } else {
+                    EventResult::Unhandled
+                }
+            }
+            UiEvent::FileHoverCancelled => {
+                *self.hovering.write() = false;
+                EventResult::Handled
+            }
+            UiEvent::FileDrop { paths, .. } => {
+                *self.hovering.write() = false;
+                let accepted = self.accepted(paths);
+                if accepted.is_empty() {
+                    return EventResult::Unhandled;
+                }
+                if let Some(on_drop) = &self.on_drop {
+                    on_drop(accepted);
+                }
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_filters_by_extension() {
+        let zone = FileDropZone::new().with_accept([".PNG", "jpg"]);
+        let paths = vec![
+            PathBuf::from("/tmp/a.png"),
+            PathBuf::from("/tmp/b.JPG"),
+            PathBuf::from("/tmp/c.txt"),
+            PathBuf::from("/tmp/noext"),
+        ];
+        assert_eq!(
+            zone.accepted(&paths),
+            vec![PathBuf::from("/tmp/a.png"), PathBuf::from("/tmp/b.JPG")]
+        );
+    }
+}
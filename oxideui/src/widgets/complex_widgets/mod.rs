@@ -3,8 +3,11 @@ pub mod aspect_ratio;
 pub mod carousel;
 pub mod chart;
 pub mod combobox;
+pub mod context_menu;
 pub mod date_picker;
 pub mod drawer;
+pub mod file_drop_zone;
+pub mod page_view;
 pub mod radio_group;
 pub mod slider;
 pub mod sonner;
@@ -20,10 +23,13 @@ pub use tabs::Tabs;
 pub use dialog::Dialog;
 pub use radio_group::RadioGroup;
 pub use combobox::Combobox;
+pub use context_menu::ContextMenu;
 pub use date_picker::DatePicker;
 pub use drawer::Drawer;
+pub use file_drop_zone::FileDropZone;
 pub use aspect_ratio::AspectRatio;
 pub use carousel::Carousel;
+pub use page_view::PageView;
 pub use chart::{Chart, ChartType};
 pub use sonner::{Sonner, ToastVariant, ToastPosition};
 pub use card::{Card, CardVariant};
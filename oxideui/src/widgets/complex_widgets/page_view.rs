@@ -0,0 +1,423 @@
+//! Swipeable pages
+//!
+//! `PageView` shows one page at a time and follows horizontal drags, then
+//! snaps to the nearest page when released. A quick flick turns the page
+//! even if it was dragged less than halfway, but never more than one page
+//! at a time. Arrow keys and `animate_to_page` slide to a page as well.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use web_time::Instant;
+
+use crate::animation::{reduced_motion, EasingCurve};
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent, Vector2};
+use crate::core::render_object::{Matrix, Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, Size};
+use crate::widgets::keyboard_nav::{step_index, NavAxis};
+use crate::widgets::scrolling::{ScrollSnapController, SnapAxis, SnapPoint};
+use crate::ThemeProvider;
+
+/// How far a pointer moves before a press becomes a drag, so taps still
+/// reach the page
+const DRAG_SLOP: f32 = 8.0;
+
+/// How long a released drag keeps its speed when picking the page to
+/// snap to, in seconds
+const FLING_TIME: f32 = 0.3;
+
+struct PageDrag {
+    start_x: f32,
+    start_position: f32,
+    last_x: f32,
+    last_time: Instant,
+    /// Pages per second, positive towards later pages
+    velocity: f32,
+    dragging: bool,
+}
+
+struct PageAnimation {
+    from: f32,
+    started: Instant,
+}
+
+struct PageState {
+    /// Current page
+    page: usize,
+    /// Scroll position in pages; fractional while dragging or animating
+    position: f32,
+    page_width: f32,
+    drag: Option<PageDrag>,
+    animation: Option<PageAnimation>,
+}
+
+pub struct PageView {
+    pub pages: Vec<Box<dyn Widget>>,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    pub show_indicators: bool,
+    /// Length of the slide to a page
+    pub duration: Duration,
+    pub on_page_changed: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    state: Arc<RwLock<PageState>>,
+    key: Option<WidgetKey>,
+}
+
+impl PageView {
+    pub fn new(pages: Vec<Box<dyn Widget>>) -> Self {
+        Self {
+            pages,
+            width: None,
+            height: None,
+            show_indicators: true,
+            duration: Duration::from_millis(300),
+            on_page_changed: None,
+            state: Arc::new(RwLock::new(PageState {
+                page: 0,
+                position: 0.0,
+                page_width: 400.0,
+                drag: None,
+                animation: None,
+            })),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            pages: self.pages.iter().map(|page| page.clone_box()).collect(),
+            width: self.width,
+            height: self.height,
+            show_indicators: self.show_indicators,
+            duration: self.duration,
+            on_page_changed: self.on_page_changed.clone(),
+            state: self.state.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn initial_page(self, page: usize) -> Self {
+        {
+            let mut state = self.state.write();
+            state.page = page.min(self.pages.len().saturating_sub(1));
+            state.position = state.page as f32;
+        }
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    pub fn show_indicators(mut self, show: bool) -> Self {
+        self.show_indicators = show;
+        self
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn with_on_page_changed<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.on_page_changed = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn current_page(&self) -> usize {
+        self.state.read().page
+    }
+
+    /// Slide to `page`, starting from the next build. Clones of this view
+    /// share its state, so the app can keep one to drive it.
+    pub fn animate_to_page(&self, page: usize) {
+        let page = page.min(self.pages.len().saturating_sub(1));
+        let changed = {
+            let mut state = self.state.write();
+            state.drag = None;
+            state.animation = Some(PageAnimation {
+                from: state.position,
+                started: Instant::now(),
+            });
+            Self::set_page(&mut state, page)
+        };
+        self.notify(changed);
+    }
+
+    /// Show `page` at once
+    pub fn jump_to_page(&self, page: usize) {
+        let page = page.min(self.pages.len().saturating_sub(1));
+        let changed = {
+            let mut state = self.state.write();
+            state.drag = None;
+            state.animation = None;
+            state.position = page as f32;
+            Self::set_page(&mut state, page)
+        };
+        self.notify(changed);
+    }
+
+    /// The page, if it changed
+    fn set_page(state: &mut PageState, page: usize) -> Option<usize> {
+        (page != state.page).then(|| {
+            state.page = page;
+            page
+        })
+    }
+
+    /// Report a page change; called with the state unlocked, so the
+    /// callback can drive this view
+    fn notify(&self, changed: Option<usize>) {
+        if let (Some(page), Some(on_change)) = (changed, &self.on_page_changed) {
+            on_change(page);
+        }
+    }
+
+    /// Page a drag that started on `start` settles on when released at
+    /// `position`: the snap point nearest where its `velocity` would carry
+    /// it, no more than one page away
+    fn settle(&self, position: f32, velocity: f32, start: usize) -> usize {
+        let last = self.pages.len().saturating_sub(1);
+        let mut snap = ScrollSnapController::new(SnapAxis::Horizontal);
+        for page in start.saturating_sub(1)..=(start + 1).min(last) {
+            snap.add_snap_point(SnapPoint {
+                offset: page as f32,
+                strength: 1.0,
+            });
+        }
+        snap.snap_threshold = f32::INFINITY;
+        let projected = position + velocity * FLING_TIME;
+        snap.find_snap_point(Vector2::new(projected, 0.0))
+            .map_or(start, |point| point.x.round() as usize)
+    }
+
+    /// Position to draw at, advancing any slide to the current page
+    fn advance(&self, state: &mut PageState) -> f32 {
+        if let Some(animation) = &state.animation {
+            let t = if reduced_motion() || self.duration.is_zero() {
+                1.0
+            } else {
+                animation.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()
+            };
+            let target = state.page as f32;
+            if t >= 1.0 {
+                state.position = target;
+                state.animation = None;
+            } else {
+                state.position = animation.from + (target - animation.from) * EasingCurve::EaseOut.evaluate(t);
+            }
+        }
+        state.position
+    }
+}
+
+impl Widget for PageView {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let theme = ctx.theme();
+        let width = self.width.unwrap_or(if ctx.constraints.has_bounded_width() { ctx.constraints.max_width } else { 400.0 });
+        let height = self.height.unwrap_or(if ctx.constraints.has_bounded_height() { ctx.constraints.max_height } else { 300.0 });
+
+        let (position, page, animating) = {
+            let mut state = self.state.write();
+            state.page_width = width.max(1.0);
+            let position = self.advance(&mut state);
+            (position, state.page, state.animation.is_some())
+        };
+        if animating {
+            ctx.mark_dirty();
+        }
+
+        // Only the pages at least partly in view are built, each as an
+        // element of its own, so events reach it directly
+        let mut layers = Vec::new();
+        let visible = position.floor() as usize..=position.ceil() as usize;
+        let pages_ctx = ctx.child_context(ctx.element_id, Constraints::tight(Size::new(width, height)));
+        for (index, child) in self.pages.iter().enumerate().filter(|(i, _)| visible.contains(i)) {
+            if let WidgetNode::Leaf(render_obj) = pages_ctx.mount_item(index, child.as_ref()).build_element(child.as_ref()) {
+                let x = (index as f32 - position) * width;
+                layers.push(RenderObject::transform(Matrix::translate(x, 0.0), render_obj));
+            }
+        }
+        let mut render_objects = vec![RenderObject::clip(
+            Rect::new(0.0, 0.0, width, height),
+            RenderObject::group(layers),
+        )];
+
+        if self.show_indicators && self.pages.len() > 1 {
+            let indicator_size = 8.0;
+            let indicator_spacing = 12.0;
+            let count = self.pages.len() as f32;
+            let total_width = count * indicator_size + (count - 1.0) * indicator_spacing;
+            let start_x = (width - total_width) / 2.0;
+            for i in 0..self.pages.len() {
+                let color = if i == page { theme.primary } else { theme.muted };
+                let x = start_x + i as f32 * (indicator_size + indicator_spacing);
+                render_objects.push(RenderObject::rect(
+                    Rect::new(x, height - 20.0, indicator_size, indicator_size),
+                    color,
+                ));
+            }
+        }
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        match event {
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } => {
+                context.request_focus();
+                let mut state = self.state.write();
+                // Catch a sliding page where it is
+                state.animation = None;
+                state.drag = Some(PageDrag {
+                    start_x: position.x,
+                    start_position: state.position,
+                    last_x: position.x,
+                    last_time: Instant::now(),
+                    velocity: 0.0,
+                    dragging: false,
+                });
+                EventResult::Unhandled
+            }
+            UiEvent::PointerMove { position, .. } => {
+                let mut state = self.state.write();
+                let page_width = state.page_width;
+                let last = self.pages.len().saturating_sub(1) as f32;
+                let Some(drag) = state.drag.as_mut() else {
+                    return EventResult::Unhandled;
+                };
+                if !drag.dragging {
+                    if (position.x - drag.start_x).abs() < DRAG_SLOP {
+                        return EventResult::Unhandled;
+                    }
+                    drag.dragging = true;
+                    context.capture_pointer(context.current_target);
+                }
+                let dt = drag.last_time.elapsed().as_secs_f32();
+                if dt > 0.0 {
+                    drag.velocity = (drag.last_x - position.x) / page_width / dt;
+                }
+                drag.last_x = position.x;
+                drag.last_time = Instant::now();
+                let dragged = drag.start_position + (drag.start_x - position.x) / page_width;
+                state.position = dragged.clamp(0.0, last);
+                EventResult::Stopped
+            }
+            UiEvent::PointerUp { button: MouseButton::Left, .. } => {
+                let changed = {
+                    let mut state = self.state.write();
+                    let Some(drag) = state.drag.take() else {
+                        return EventResult::Unhandled;
+                    };
+                    if !drag.dragging {
+                        return EventResult::Unhandled;
+                    }
+                    // A drag that paused before release doesn't fling
+                    let velocity = if drag.last_time.elapsed() > Duration::from_millis(100) { 0.0 } else { drag.velocity };
+                    let page = self.settle(state.position, velocity, drag.start_position.round() as usize);
+                    state.animation = Some(PageAnimation {
+                        from: state.position,
+                        started: Instant::now(),
+                    });
+                    Self::set_page(&mut state, page)
+                };
+                self.notify(changed);
+                EventResult::Stopped
+            }
+            UiEvent::KeyDown { key, .. } => {
+                match step_index(Some(self.current_page()), *key, NavAxis::Horizontal, self.pages.len(), 1) {
+                    Some(page) => {
+                        self.animate_to_page(page);
+                        EventResult::Stopped
+                    }
+                    None => EventResult::Unhandled,
+                }
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_release_snaps_to_one_page_at_most() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+        let pages = (0..4).map(|_| Box::new(SizedBox::shrink()) as Box<dyn Widget>).collect();
+        let view = PageView::new(pages);
+        // The callback may read the view it's called from
+        let observer = view.clone();
+        let view = view.with_on_page_changed(move |page| recorded.lock().unwrap().push((page, observer.current_page())));
+
+        // Slow drags snap to the nearest page
+        assert_eq!(view.settle(1.4, 0.0, 1), 1);
+        assert_eq!(view.settle(1.6, 0.0, 1), 2);
+        // A flick turns the page from a short drag, but only by one
+        assert_eq!(view.settle(1.2, 2.0, 1), 2);
+        assert_eq!(view.settle(1.9, 20.0, 1), 2);
+        assert_eq!(view.settle(0.8, -20.0, 1), 0);
+        // Nothing past the last page
+        assert_eq!(view.settle(3.0, 5.0, 3), 3);
+
+        view.animate_to_page(2);
+        view.animate_to_page(9);
+        view.jump_to_page(3);
+        assert_eq!(view.current_page(), 3);
+        assert_eq!(*changes.lock().unwrap(), vec![(2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn test_visible_pages_are_elements() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+
+        let pages = (0..4).map(|_| Box::new(SizedBox::shrink()) as Box<dyn Widget>).collect();
+        let view = PageView::new(pages).with_size(200.0, 100.0).initial_page(2);
+        let tree = new_shared_element_tree();
+        let root = tree.write().create_element(&view, None, 0);
+        let ctx = BuildContext::new(root, tree.clone(), Constraints::loose(Size::new(200.0, 100.0)), Arc::new(Theme::default()));
+        view.build(&ctx);
+
+        let tree = tree.read();
+        let children = tree.get_children(root);
+        assert_eq!(children.len(), 1);
+        let page = tree.get(children[0]).unwrap();
+        assert_eq!(page.key, Some(WidgetKey::U64(2)));
+        assert_eq!(page.parent, Some(root));
+    }
+}
@@ -130,6 +130,7 @@ impl StatelessWidget for ProgressBar {
                     color: theme.foreground,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 crate::core::render_object::Point::new(width + 8.0, height / 2.0 + 5.0),
             ));
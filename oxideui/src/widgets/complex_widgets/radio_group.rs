@@ -131,6 +131,7 @@ impl StatelessWidget for RadioGroup {
                     color: text_color,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(current_x + radio_size + 8.0, current_y + radio_size / 2.0 + 5.0),
             ));
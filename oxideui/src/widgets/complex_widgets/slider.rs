@@ -3,6 +3,8 @@ use std::sync::Arc;
 use crate::core::context::BuildContext;
 use crate::core::render_object::{ Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::state_management::bindable::Binding;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -15,6 +17,7 @@ pub struct Slider {
     pub height: Option<f32>,
     pub disabled: bool,
     pub on_change: Option<Arc<dyn Fn(f32) + Send + Sync>>,
+    pub binding: Option<Binding<f32>>,
     pub tooltip: Option<String>,
     key: Option<WidgetKey>,
 }
@@ -30,6 +33,7 @@ impl Slider {
             height: None,
             disabled: false,
             on_change: None,
+            binding: None,
             tooltip: None,
             key: None,
         }
@@ -69,6 +73,12 @@ impl Slider {
         self
     }
 
+    /// Read the value from, and write drags back to, a binding
+    pub fn with_binding(mut self, binding: impl Into<Binding<f32>>) -> Self {
+        self.binding = Some(binding.into());
+        self
+    }
+
     pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
         self.tooltip = Some(tooltip.into());
         self
@@ -78,6 +88,38 @@ impl Slider {
         self.key = Some(key);
         self
     }
+
+    /// Current value, preferring the binding over the static value
+    pub fn current_value(&self) -> f32 {
+        match &self.binding {
+            Some(binding) => binding.get().clamp(self.min, self.max),
+            None => self.value,
+        }
+    }
+
+    /// Map a local x coordinate on the track to a stepped value
+    fn value_at(&self, x: f32) -> f32 {
+        let width = self.width.unwrap_or(200.0);
+        let thumb_size = 20.0;
+        let t = ((x - thumb_size / 2.0) / (width - thumb_size)).clamp(0.0, 1.0);
+        let mut value = self.min + t * (self.max - self.min);
+        if let Some(step) = self.step.filter(|s| *s > 0.0) {
+            value = self.min + ((value - self.min) / step).round() * step;
+        }
+        value.clamp(self.min, self.max)
+    }
+
+    fn drag_to(&self, x: f32) {
+        let value = self.value_at(x);
+        if (value - self.current_value()).abs() > f32::EPSILON {
+            if let Some(binding) = &self.binding {
+                binding.set(value);
+            }
+            if let Some(on_change) = &self.on_change {
+                on_change(value);
+            }
+        }
+    }
 }
 
 impl StatelessWidget for Slider {
@@ -86,10 +128,15 @@ impl StatelessWidget for Slider {
         let width = self.width.unwrap_or(200.0);
         let height = self.height.unwrap_or(32.0);
 
+        if let Some(binding) = &self.binding {
+            binding.subscribe(ctx.element_id);
+        }
+        let value = self.current_value();
+
         let track_height = 6.0;
         let thumb_size = 20.0;
 
-        let normalized_value = (self.value - self.min) / (self.max - self.min);
+        let normalized_value = (value - self.min) / (self.max - self.min);
         let thumb_position = normalized_value * (width - thumb_size);
 
         let track_color = if self.disabled {
@@ -132,7 +179,7 @@ impl StatelessWidget for Slider {
 
         // Value label
         if !self.disabled {
-            let value_text = format!("{:.1}", self.value);
+            let value_text = format!("{:.1}", value);
             render_objects.push(RenderObject::text(
                 value_text,
                 TextStyle {
@@ -141,6 +188,7 @@ impl StatelessWidget for Slider {
                     color: theme.foreground,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(thumb_position + thumb_size / 2.0 - 10.0, (height - thumb_size) / 2.0 - 15.0),
             ));
@@ -155,6 +203,30 @@ impl Widget for Slider {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if self.disabled || !context.is_at_target() {
+            return EventResult::Unhandled;
+        }
+
+        match event {
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } => {
+                // Keep receiving moves while dragging outside the track
+                context.capture_pointer(context.current_target);
+                self.drag_to(position.x);
+                EventResult::Stopped
+            }
+            UiEvent::PointerMove { position, .. } if context.has_pointer_capture() => {
+                self.drag_to(position.x);
+                EventResult::Stopped
+            }
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } => {
+                self.drag_to(position.x);
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -1,8 +1,9 @@
 use std::any::Any;
 use std::sync::Arc;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Color, Matrix, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::widgets::element_widgets::Icon;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -172,6 +173,7 @@ impl StatelessWidget for Sonner {
                 color: text_color,
                 bold: false,
                 italic: false,
+                ..Default::default()
             },
             Point::new(x + padding, y + padding + 5.0),
         ));
@@ -185,6 +187,7 @@ impl StatelessWidget for Sonner {
                 color: text_color,
                 bold: true,
                 italic: false,
+                ..Default::default()
             },
             Point::new(x + padding + 30.0, y + padding + 5.0),
         ));
@@ -199,6 +202,7 @@ impl StatelessWidget for Sonner {
                     color: text_color.with_alpha(180),
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(x + padding + 30.0, y + padding + 25.0),
             ));
@@ -214,16 +218,9 @@ impl StatelessWidget for Sonner {
             text_color.with_alpha(50),
         ));
 
-        render_objects.push(RenderObject::text(
-            "×".to_string(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size: 18.0,
-                color: text_color,
-                bold: true,
-                italic: false,
-            },
-            Point::new(close_x + 4.0, close_y + 4.0),
+        render_objects.push(RenderObject::transform(
+            Matrix::translate(close_x + 4.0, close_y + 4.0),
+            Icon::new("x").with_size(16.0).to_render_object(text_color),
         ));
 
         // Progress bar (showing time remaining)
@@ -113,6 +113,7 @@ impl StatelessWidget for Switch {
                     color: theme.foreground,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(width + 8.0, height / 2.0 + 5.0),
             ));
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::core::context::BuildContext;
 use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::widgets::keyboard_nav::{step_index, NavAxis};
 use crate::ThemeProvider;
 
 pub struct Tabs {
@@ -150,6 +151,7 @@ impl StatelessWidget for Tabs {
                     color: text_color,
                     bold: is_active,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(current_x + tab_padding, tab_height / 2.0 + 5.0),
             ));
@@ -199,7 +201,7 @@ impl StatelessWidget for Tabs {
             );
 
             let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-            let child_node = child.build(&child_ctx);
+            let child_node = child_ctx.build_child(child.as_ref());
 
             if let WidgetNode::Leaf(render_obj) = child_node {
                 // Offset child to content area
@@ -224,6 +226,28 @@ impl Widget for Tabs {
         use crate::core::event::{UiEvent, MouseButton, EventResult};
 
         match event {
+            UiEvent::PointerDown { button: MouseButton::Left, .. } if context.is_at_target() => {
+                context.request_focus();
+                EventResult::Unhandled
+            }
+            // Arrows follow the tab strip and activate the tab they land on
+            UiEvent::KeyDown { key, .. } => {
+                let axis = match self.orientation {
+                    TabOrientation::Horizontal => NavAxis::Horizontal,
+                    TabOrientation::Vertical => NavAxis::Vertical,
+                };
+                match step_index(Some(self.active), *key, axis, self.tabs.len(), 1) {
+                    Some(index) => {
+                        if index != self.active {
+                            if let Some(on_change) = &self.on_tab_change {
+                                on_change(index);
+                            }
+                        }
+                        EventResult::Stopped
+                    }
+                    None => EventResult::Unhandled,
+                }
+            }
             UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
                 let tab_height = 40.0;
                 let tab_padding = 16.0;
@@ -250,6 +274,10 @@ impl Widget for Tabs {
         }
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -0,0 +1,312 @@
+//! Visual effects over a subtree
+//!
+//! `Filtered` draws its child through a `RenderEffect`: blur, saturation,
+//! brightness and tint applied to the composited result rather than to
+//! each shape. The Skia backends use image filters; softbuffer draws the
+//! child unfiltered.
+//!
+//! `Opacity` fades a subtree as one layer: overlapping children blend with
+//! what's behind them, not with each other.
+//!
+//! `BackdropFilter` instead filters whatever was painted beneath its bounds
+//! and draws its child sharp on top, for frosted-glass sidebars and dialogs.
+//! Only the GPU backends blur the backdrop; CPU rendering fills the bounds
+//! with a translucent color instead.
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Color, Rect, RenderEffect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::ThemeProvider;
+
+/// Draws its child through an image filter, e.g. to gray out a disabled
+/// panel or blur content behind a modal
+pub struct Filtered {
+    pub child: Box<dyn Widget>,
+    pub effect: RenderEffect,
+    key: Option<WidgetKey>,
+}
+
+impl Filtered {
+    pub fn new<W: Widget + 'static>(child: W, effect: RenderEffect) -> Self {
+        Self {
+            child: Box::new(child),
+            effect,
+            key: None,
+        }
+    }
+
+    pub fn blur<W: Widget + 'static>(child: W, sigma: f32) -> Self {
+        Self::new(child, RenderEffect::blur(sigma))
+    }
+
+    pub fn grayscale<W: Widget + 'static>(child: W) -> Self {
+        Self::new(child, RenderEffect::grayscale())
+    }
+
+    pub fn tint<W: Widget + 'static>(child: W, color: Color) -> Self {
+        Self::new(child, RenderEffect::tint(color))
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            effect: self.effect.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for Filtered {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        match ctx.build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(render_obj) if !self.effect.is_identity() => {
+                WidgetNode::Leaf(RenderObject::effect(self.effect.clone(), render_obj))
+            }
+            other => other,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Draws its child at `alpha` opacity; animate `alpha` for fade transitions
+pub struct Opacity {
+    pub child: Box<dyn Widget>,
+    pub alpha: f32,
+    key: Option<WidgetKey>,
+}
+
+impl Opacity {
+    pub fn new<W: Widget + 'static>(child: W, alpha: f32) -> Self {
+        Self {
+            child: Box::new(child),
+            alpha: alpha.clamp(0.0, 1.0),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            alpha: self.alpha,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for Opacity {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        // Container children come back laid out as one group, so the
+        // whole subtree fades as a layer
+        match ctx.build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(render_obj) if self.alpha < 1.0 => {
+                WidgetNode::Leaf(RenderObject::opacity(self.alpha, render_obj))
+            }
+            WidgetNode::Leaf(render_obj) => WidgetNode::Leaf(render_obj),
+            _ => WidgetNode::None,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Blurs the content behind its bounds and draws its child on top
+pub struct BackdropFilter {
+    pub child: Box<dyn Widget>,
+    pub blur: f32,
+    /// Laid over the blurred backdrop; defaults to the theme background at
+    /// partial opacity
+    pub tint: Option<Color>,
+    /// Alpha of the tint when the backdrop can't be blurred, high enough
+    /// to keep the child readable over busy content
+    pub fallback_opacity: f32,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    key: Option<WidgetKey>,
+}
+
+impl BackdropFilter {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            blur: 16.0,
+            tint: None,
+            fallback_opacity: 0.9,
+            width: None,
+            height: None,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            blur: self.blur,
+            tint: self.tint,
+            fallback_opacity: self.fallback_opacity,
+            width: self.width,
+            height: self.height,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_blur(mut self, sigma: f32) -> Self {
+        self.blur = sigma.max(0.0);
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = Some(tint);
+        self
+    }
+
+    pub fn with_fallback_opacity(mut self, opacity: f32) -> Self {
+        self.fallback_opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Size of the filtered region; defaults to the child's size
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for BackdropFilter {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        // Container children come back laid out as one group
+        let child = match ctx.build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            _ => RenderObject::None,
+        };
+        let size = child.layout_size();
+        let width = self.width.unwrap_or(size.width);
+        let height = self.height.unwrap_or(size.height);
+        let tint = self.tint.unwrap_or_else(|| ctx.theme().background.with_alpha(96));
+        let filter = RenderEffect::blur(self.blur).with_tint(tint);
+        let fallback = tint.with_alpha((self.fallback_opacity * 255.0).round() as u8);
+        WidgetNode::Leaf(RenderObject::backdrop(Rect::new(0.0, 0.0, width, height), filter, fallback, child))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::Theme;
+    use crate::layout::{Constraints, Size};
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_filtered_wraps_child_in_effect() {
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::loose(Size::new(200.0, 100.0)),
+            Arc::new(Theme::default()),
+        );
+        let WidgetNode::Leaf(RenderObject::Effect { effect, .. }) = Filtered::grayscale(Text::new("Disabled")).build(&ctx) else {
+            panic!("expected an effect layer");
+        };
+
+        // Grayscale maps every color channel to the same luma
+        let matrix = effect.color_matrix().unwrap();
+        assert_eq!(matrix[0..3], matrix[5..8]);
+        assert_eq!(matrix[5..8], matrix[10..13]);
+        assert!((matrix[0] + matrix[1] + matrix[2] - 1.0).abs() < 1e-6);
+
+        // An effect that changes nothing adds no layer
+        let plain = Filtered::new(Text::new("Enabled"), RenderEffect::default()).build(&ctx);
+        assert!(!matches!(plain, WidgetNode::Leaf(RenderObject::Effect { .. })));
+    }
+
+    #[test]
+    fn test_backdrop_covers_its_child() {
+        use crate::widgets::layout_widgets::SizedBox;
+
+        let panel = BackdropFilter::new(SizedBox::new(120.0, 80.0));
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &panel);
+        let unbounded = Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY);
+        let ctx = BuildContext::new(id, tree, unbounded, Arc::new(Theme::default()));
+        let WidgetNode::Leaf(RenderObject::Backdrop { rect, fallback, child, .. }) = panel.build(&ctx) else {
+            panic!("expected a backdrop layer");
+        };
+        assert_eq!(rect, Rect::new(0.0, 0.0, 120.0, 80.0));
+        assert_eq!(child.layout_size(), Size::new(120.0, 80.0));
+        // See-through where the backdrop can't be blurred
+        assert_eq!(fallback.a, 230);
+    }
+
+    #[test]
+    fn test_opacity_fades_multi_child_layouts() {
+        use crate::widgets::basic::Column;
+
+        let column = || {
+            Column::new().with_children(vec![Box::new(Text::new("Title")), Box::new(Text::new("Subtitle"))])
+        };
+        let tree = new_shared_element_tree();
+        let faded = Opacity::new(column(), 0.4);
+        let id = tree.write().mount(None, &faded);
+        let ctx = BuildContext::new(id, tree, Constraints::new(0.0, 400.0, 0.0, 300.0), Arc::new(Theme::default()));
+
+        let WidgetNode::Leaf(RenderObject::Opacity { alpha, child }) = faded.build(&ctx) else {
+            panic!("expected an opacity layer");
+        };
+        assert_eq!(alpha, 0.4);
+        assert!(matches!(*child, RenderObject::Element { .. }));
+        // Fully opaque adds no layer
+        assert!(!matches!(Opacity::new(column(), 1.0).build(&ctx), WidgetNode::Leaf(RenderObject::Opacity { .. })));
+    }
+}
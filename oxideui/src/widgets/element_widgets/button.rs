@@ -1,13 +1,72 @@
 //! Button widget - demonstrates event handling
+//!
+//! The button tints while hovered, darkens and shrinks slightly while
+//! pressed, and draws a focus ring when focused. Disabled buttons use the
+//! style's disabled colors and ignore input. `with_ripple(true)` adds an ink
+//! ripple that spreads from the press point.
+//!
+//! `with_variant` picks a preset look, and the variant's name is added as a
+//! stylesheet class. Colors are layered: the style (or variant, or the
+//! theme's default), then stylesheet rules, then `with_color` and
+//! `with_text_color`, which always win. The fill's corners follow the
+//! style's `radius`.
+//!
+//! `color` and `text_color` became `Option<Color>` when styles were added,
+//! and an unstyled button takes the theme's primary look instead of the
+//! fixed Material blue it used to have. Code that assigned the fields
+//! directly should call `with_color` and `with_text_color`, or set
+//! `Some(color)`; `with_color(Color::from_hex(0x2196F3))` brings the old
+//! look back. Icons go before or after the label, and
+//! `Button::icon_only` makes a round button with just an icon. A loading
+//! button shows a spinner in place of its content and ignores clicks:
+//!
+//! ```rust,ignore
+//! Button::new("Delete")
+//!     .with_variant(ButtonVariant::Destructive)
+//!     .with_icon(Icon::new("trash-2"))
+//!     .loading(deleting.get())
+//! ```
 
 use std::any::Any;
 use std::sync::Arc;
+use std::f32::consts::TAU;
+use std::time::Duration;
 
+use parking_lot::RwLock;
+use web_time::Instant;
+use winit::keyboard::KeyCode;
+
+use crate::animation::{reduced_motion, EasingCurve};
 use crate::core::context::BuildContext;
 use crate::core::event::{EventContext, EventPhase, EventResult, MouseButton, UiEvent};
-use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{ClipShape, Color, Matrix, Point, Rect, RenderObject, TextDecoration, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::core::{AccessibilityRole, FocusRing, SemanticsData};
 use crate::layout::constraints::Size;
+use crate::render::text::text_width;
+use crate::theming::{ButtonStyle, ButtonVariant, StateColors, StyleState};
+use crate::widgets::element_widgets::Icon;
+use crate::widgets::layout_widgets::reserve;
+use crate::ThemeProvider;
+
+const RIPPLE_DURATION: Duration = Duration::from_millis(400);
+/// How far a pressed button shrinks
+const PRESSED_SCALE: f32 = 0.97;
+/// Space between icons and the label
+const ICON_GAP: f32 = 6.0;
+/// Time for one turn of the loading spinner
+const SPIN_PERIOD: Duration = Duration::from_millis(800);
+
+#[derive(Debug, Default)]
+struct ButtonState {
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    /// Window-space press point and time of the running ripple
+    ripple: Option<(Point, Instant)>,
+    /// When the loading spinner started turning
+    spinning_since: Option<Instant>,
+}
 
 /// Callback type for button clicks
 pub type OnClick = Arc<dyn Fn() + Send + Sync>;
@@ -16,10 +75,32 @@ pub type OnClick = Arc<dyn Fn() + Send + Sync>;
 pub struct Button {
     pub label: String,
     pub on_click: Option<OnClick>,
-    pub color: Color,
-    pub text_color: Color,
+    /// Background shorthand; overrides the style and stylesheet rules.
+    /// Was a plain `Color` before styles; None takes the style's
+    pub color: Option<Color>,
+    /// Label color shorthand; overrides the style and stylesheet rules.
+    /// Was a plain `Color` before styles; None takes the style's
+    pub text_color: Option<Color>,
+    /// Full visual style; the app-wide default when unset
+    pub style: Option<ButtonStyle>,
     pub width: Option<f32>,
     pub height: Option<f32>,
+    /// Stylesheet classes, matched by selectors like `Button.primary`
+    pub classes: Vec<String>,
+    /// Preset look, used when no `style` is set
+    pub variant: Option<ButtonVariant>,
+    /// Icon before the label
+    pub icon: Option<Icon>,
+    /// Icon after the label
+    pub trailing_icon: Option<Icon>,
+    /// Round, sized to its height; set by `icon_only`
+    pub circular: bool,
+    /// Show a spinner instead of the content and ignore clicks
+    pub loading: bool,
+    pub disabled: bool,
+    /// Draw an ink ripple from the press point
+    pub ripple: bool,
+    state: Arc<RwLock<ButtonState>>,
     key: Option<WidgetKey>,
 }
 
@@ -30,8 +111,18 @@ impl Clone for Button {
             on_click: self.on_click.clone(),
             color: self.color,
             text_color: self.text_color,
+            style: self.style.clone(),
             width: self.width,
             height: self.height,
+            classes: self.classes.clone(),
+            variant: self.variant,
+            icon: self.icon.clone(),
+            trailing_icon: self.trailing_icon.clone(),
+            circular: self.circular,
+            loading: self.loading,
+            disabled: self.disabled,
+            ripple: self.ripple,
+            state: self.state.clone(),
             key: self.key.clone(),
         }
     }
@@ -42,14 +133,31 @@ impl Button {
         Self {
             label: label.into(),
             on_click: None,
-            color: Color::from_hex(0x2196F3), // Material blue
-            text_color: Color::WHITE,
+            color: None,
+            text_color: None,
+            style: None,
             width: None,
             height: None,
+            classes: Vec::new(),
+            variant: None,
+            icon: None,
+            trailing_icon: None,
+            circular: false,
+            loading: false,
+            disabled: false,
+            ripple: false,
+            state: Arc::new(RwLock::new(ButtonState::default())),
             key: None,
         }
     }
 
+    /// Round button showing only `icon`
+    pub fn icon_only(icon: Icon) -> Self {
+        let mut button = Self::new("").with_icon(icon);
+        button.circular = true;
+        button
+    }
+
     pub fn with_on_click<F>(mut self, callback: F) -> Self
     where
         F: Fn() + Send + Sync + 'static,
@@ -59,12 +167,17 @@ impl Button {
     }
 
     pub fn with_color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.color = Some(color);
         self
     }
 
     pub fn with_text_color(mut self, color: Color) -> Self {
-        self.text_color = color;
+        self.text_color = Some(color);
+        self
+    }
+
+    pub fn with_style(mut self, style: ButtonStyle) -> Self {
+        self.style = Some(style);
         self
     }
 
@@ -74,43 +187,246 @@ impl Button {
         self
     }
 
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
+    pub fn with_variant(mut self, variant: ButtonVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+
+    pub fn with_icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn with_trailing_icon(mut self, icon: Icon) -> Self {
+        self.trailing_icon = Some(icon);
+        self
+    }
+
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn with_ripple(mut self, ripple: bool) -> Self {
+        self.ripple = ripple;
+        self
+    }
+
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
     }
-}
 
-impl StatelessWidget for Button {
-    fn build_stateless(&self, _ctx: &BuildContext) -> WidgetNode {
-        let width = self.width.unwrap_or(120.0);
-        let height = self.height.unwrap_or(40.0);
+    /// Style state for the current interaction
+    pub(crate) fn style_state(&self) -> StyleState {
+        let state = self.state.read();
+        let inert = self.disabled || self.loading;
+        StyleState {
+            hover: state.hovered && !inert,
+            active: state.pressed && !inert,
+            focus: state.focused && !self.disabled,
+            disabled: self.disabled,
+        }
+    }
 
-        let size = Size::new(width, height);
-        let rect = Rect::from_size(size);
+    fn activate(&self) {
+        if self.loading {
+            return;
+        }
+        if let Some(on_click) = &self.on_click {
+            on_click();
+        }
+    }
+
+    /// A three-quarter ring `diameter` across, turning while motion is allowed
+    fn spinner(&self, ctx: &BuildContext, diameter: f32, color: Color) -> RenderObject {
+        let stroke = (diameter / 8.0).max(1.5);
+        let (c, r) = (diameter / 2.0, (diameter - stroke) / 2.0);
+        let arc = RenderObject::stroke_path(
+            format!("M {} {} A {r} {r} 0 1 1 {c} {}", c + r, c, c - r),
+            Rect::from_size(Size::new(diameter, diameter)),
+            color,
+            stroke,
+        );
+        if reduced_motion() {
+            return arc;
+        }
+        let started = *self.state.write().spinning_since.get_or_insert_with(Instant::now);
+        let turns = started.elapsed().as_secs_f32() / SPIN_PERIOD.as_secs_f32();
+        ctx.mark_dirty();
+        RenderObject::transform(Matrix::rotate(turns.fract() * TAU).around(Point::new(c, c)), arc)
+    }
+
+    /// The ripple spreading over `rect`, or None once it has faded
+    fn ripple_layer(&self, ctx: &BuildContext, rect: Rect, color: Color) -> Option<RenderObject> {
+        let mut state = self.state.write();
+        let (press, started) = state.ripple?;
+        let t = started.elapsed().as_secs_f32() / RIPPLE_DURATION.as_secs_f32();
+        if t >= 1.0 || reduced_motion() {
+            state.ripple = None;
+            return None;
+        }
+        let origin = ctx.element_tree.read().to_local(ctx.element_id, press).unwrap_or(press);
+        // Grow to the corner farthest from the press point
+        let reach = |from: f32, start: f32, length: f32| (from - start).abs().max((start + length - from).abs());
+        let max_radius = reach(origin.x, rect.x, rect.width).hypot(reach(origin.y, rect.y, rect.height));
+        let radius = max_radius * EasingCurve::EaseOut.evaluate(t);
+        let circle = Rect::new(origin.x - radius, origin.y - radius, radius * 2.0, radius * 2.0);
+        let alpha = (0.3 * (1.0 - t) * 255.0) as u8;
+        Some(RenderObject::clip(
+            rect,
+            RenderObject::clip_shape(ClipShape::Oval(circle), RenderObject::rect(circle, color.with_alpha(alpha))),
+        ))
+    }
+}
 
-        // Create button background
-        let background = RenderObject::rect(rect, self.color);
+impl StatelessWidget for Button {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let state = self.style_state();
+        let mut button_style = self.style.clone().unwrap_or_else(|| match self.variant {
+            Some(variant) => ButtonStyle::variant(ctx.theme(), variant),
+            None => ctx.button_style(),
+        });
+        // Stylesheet rules win over the style struct
+        let mut classes = self.classes.clone();
+        if let Some(variant) = self.variant {
+            classes.push(variant.class().to_string());
+        }
+        let mut style = ctx.resolve_style("Button", &classes, state);
+        // and explicit colors win over both
+        if let Some(color) = self.color {
+            // Derive the tints from the shorthand so it still reacts
+            button_style.background = StateColors::new(color)
+                .with_hover(color.lighten(0.06))
+                .with_pressed(color.darken(0.08))
+                .with_disabled(button_style.background.resolve(StyleState { disabled: true, ..Default::default() }));
+            style.background = None;
+        }
+        if let Some(color) = self.text_color {
+            button_style.foreground.normal = color;
+            style.foreground = None;
+        }
 
-        // Create button text
+        let foreground = style.foreground.unwrap_or_else(|| button_style.foreground.resolve(state));
         let text_style = TextStyle {
             font_family: "sans-serif".to_string(),
-            font_size: 14.0,
-            color: self.text_color,
-            bold: false,
+            font_size: style.font_size.unwrap_or(button_style.font_size),
+            color: foreground,
+            bold: button_style.bold,
             italic: false,
+            decoration: if button_style.underline { TextDecoration::underline() } else { TextDecoration::NONE },
+            ..Default::default()
         };
 
-        // Center text in button (rough approximation)
-        let text_x = rect.width / 2.0 - (self.label.len() as f32 * 4.0);
-        let text_y = rect.height / 2.0 + 5.0;
-        let text = RenderObject::text(
-            self.label.clone(),
-            text_style,
-            Point::new(text_x, text_y),
-        );
+        // Icons and label side by side, centered
+        let label_width = if self.label.is_empty() { 0.0 } else { text_width(&self.label, &text_style) };
+        let parts = [
+            self.icon.as_ref().map(|icon| icon.size),
+            (label_width > 0.0).then_some(label_width),
+            self.trailing_icon.as_ref().map(|icon| icon.size),
+        ];
+        let widths: Vec<f32> = parts.into_iter().flatten().collect();
+        let content_width = widths.iter().sum::<f32>() + ICON_GAP * widths.len().saturating_sub(1) as f32;
+
+        let (width, height) = if self.circular {
+            let diameter = self.width.or(self.height).unwrap_or(button_style.height);
+            (diameter, diameter)
+        } else {
+            let fit = (content_width + button_style.padding.horizontal()).max(button_style.min_width);
+            (self.width.unwrap_or(fit), self.height.unwrap_or(button_style.height))
+        };
 
-        // Group background and text
-        WidgetNode::Leaf(RenderObject::group(vec![background, text]))
+        let size = Size::new(width, height);
+        let rect = Rect::from_size(size);
+
+        let mut render_objects = Vec::new();
+        let mut fill_rect = rect;
+        let border_width = style.border_width.unwrap_or(button_style.border_width);
+        let border_color = style
+            .border_color
+            .or_else(|| button_style.border_color.map(|colors| colors.resolve(state)));
+        if let (true, Some(border_color)) = (border_width > 0.0, border_color) {
+            render_objects.push(RenderObject::rect(rect, border_color));
+            fill_rect = Rect::new(
+                border_width,
+                border_width,
+                width - border_width * 2.0,
+                height - border_width * 2.0,
+            );
+        }
+
+        // Create button background; ghost and link buttons have none, but
+        // keep their size
+        let background = style.background.unwrap_or_else(|| button_style.background.resolve(state));
+        if background.a > 0 {
+            render_objects.push(RenderObject::rect(fill_rect, background));
+        }
+
+        if self.ripple {
+            if let Some(ripple) = self.ripple_layer(ctx, fill_rect, foreground) {
+                render_objects.push(ripple);
+                ctx.mark_dirty();
+            }
+        }
+
+        if self.loading {
+            // The spinner takes the label's place; the size stays put
+            let diameter = text_style.font_size;
+            let spinner = self.spinner(ctx, diameter, foreground);
+            let at = Matrix::translate((width - diameter) / 2.0, (height - diameter) / 2.0);
+            render_objects.push(RenderObject::transform(at, spinner));
+        } else {
+            self.state.write().spinning_since = None;
+            let mut x = (width - content_width) / 2.0;
+            let place_icon = |icon: &Icon, x: f32| {
+                let at = Matrix::translate(x, (height - icon.size) / 2.0);
+                RenderObject::transform(at, icon.to_render_object(foreground))
+            };
+            if let Some(icon) = &self.icon {
+                render_objects.push(place_icon(icon, x));
+                x += icon.size + ICON_GAP;
+            }
+            if label_width > 0.0 {
+                let text_y = rect.height / 2.0 + 5.0;
+                render_objects.push(RenderObject::text(self.label.clone(), text_style, Point::new(x, text_y)));
+                x += label_width + ICON_GAP;
+            }
+            if let Some(icon) = &self.trailing_icon {
+                render_objects.push(place_icon(icon, x));
+            }
+        }
+
+        let mut content = reserve(size, RenderObject::group(render_objects));
+        let radius = style.radius.unwrap_or(button_style.radius);
+        if self.circular {
+            content = RenderObject::clip_shape(ClipShape::Oval(rect), content);
+        } else if radius > 0.0 {
+            content = RenderObject::clip_shape(ClipShape::rounded(rect, radius), content);
+        }
+        if state.focus {
+            let mut ring = FocusRing::new();
+            if self.circular {
+                // The radius is clamped to a full circle
+                ring = ring.with_radius(height);
+            }
+            content = RenderObject::group(vec![content, ring.render(rect, ctx.theme())]);
+        }
+
+        if state.active {
+            let scale = Matrix::scale(PRESSED_SCALE, PRESSED_SCALE).around(Point::new(width / 2.0, height / 2.0));
+            return WidgetNode::Leaf(RenderObject::transform(scale, content));
+        }
+        WidgetNode::Leaf(content)
     }
 }
 
@@ -120,13 +436,51 @@ impl Widget for Button {
     }
 
     fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if self.disabled {
+            return EventResult::Unhandled;
+        }
         match event {
+            UiEvent::PointerEnter => {
+                self.state.write().hovered = true;
+                context.mark_dirty();
+                EventResult::Handled
+            }
+            UiEvent::PointerLeave => {
+                let mut state = self.state.write();
+                state.hovered = false;
+                state.pressed = false;
+                context.mark_dirty();
+                EventResult::Handled
+            }
+            UiEvent::PointerCancel { .. } => {
+                self.state.write().pressed = false;
+                context.mark_dirty();
+                EventResult::Handled
+            }
+            UiEvent::Focus => {
+                self.state.write().focused = true;
+                context.mark_dirty();
+                EventResult::Handled
+            }
+            UiEvent::Blur => {
+                self.state.write().focused = false;
+                context.mark_dirty();
+                EventResult::Handled
+            }
             UiEvent::PointerDown {
+                position,
                 button: MouseButton::Left,
                 ..
             } => {
                 // Visual feedback on mouse down
-                println!("Button '{}' pressed", self.label);
+                context.request_focus();
+                if self.loading {
+                    return EventResult::Stopped;
+                }
+                let mut state = self.state.write();
+                state.pressed = true;
+                state.ripple = Some((*position, Instant::now()));
+                context.mark_dirty();
                 EventResult::Handled // Continue propagation for hover effects
             }
             UiEvent::PointerUp {
@@ -134,18 +488,39 @@ impl Widget for Button {
                 ..
             } if context.phase == EventPhase::AtTarget => {
                 // Execute callback on release (standard button behavior)
-                println!("Button '{}' clicked!", self.label);
-
-                if let Some(on_click) = &self.on_click {
-                    on_click();
+                let was_pressed = std::mem::replace(&mut self.state.write().pressed, false);
+                if was_pressed {
+                    context.mark_dirty();
+                    self.activate();
                 }
 
                 EventResult::Stopped // Stop propagation - button consumed the click
             }
+            UiEvent::KeyDown {
+                key: KeyCode::Enter | KeyCode::Space,
+                repeat: false,
+                ..
+            } => {
+                self.activate();
+                EventResult::Stopped
+            }
             _ => EventResult::Unhandled,
         }
     }
 
+    fn is_focusable(&self) -> bool {
+        !self.disabled
+    }
+
+    fn semantics(&self) -> Option<SemanticsData> {
+        // Icon-only buttons are named by the icon's tooltip, or its name
+        let label = match &self.icon {
+            Some(icon) if self.label.is_empty() => icon.tooltip.clone().unwrap_or_else(|| icon.name.clone()),
+            _ => self.label.clone(),
+        };
+        Some(SemanticsData::new().with_role(AccessibilityRole::Button).with_label(label))
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -162,6 +537,7 @@ impl Widget for Button {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::element::ElementId;
 
     #[test]
     fn test_button_creation() {
@@ -186,4 +562,148 @@ mod tests {
 
         assert!(clicked.load(std::sync::atomic::Ordering::SeqCst));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hover_press_and_disabled_states() {
+        let clicks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let button = Button::new("Save").with_on_click(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        let down = UiEvent::PointerDown { id: 0, position: Point::new(10.0, 10.0), button: MouseButton::Left };
+        let up = UiEvent::PointerUp { id: 0, position: Point::new(10.0, 10.0), button: MouseButton::Left };
+
+        button.handle_event(&UiEvent::PointerEnter, &mut context);
+        assert!(button.style_state().hover);
+        button.handle_event(&down, &mut context);
+        assert!(button.style_state().active);
+        button.handle_event(&up, &mut context);
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Leaving while pressed cancels the click
+        button.handle_event(&down, &mut context);
+        button.handle_event(&UiEvent::PointerLeave, &mut context);
+        button.handle_event(&up, &mut context);
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(!button.style_state().hover);
+
+        let disabled = button.clone().disabled(true);
+        assert_eq!(disabled.handle_event(&down, &mut context), EventResult::Unhandled);
+        assert!(disabled.style_state().disabled && !disabled.style_state().active);
+    }
+
+    #[test]
+    fn test_loading_ignores_clicks_and_icon_only_is_round() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+
+        let clicks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let button = Button::new("Save").loading(true).with_on_click(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        let down = UiEvent::PointerDown { id: 0, position: Point::new(10.0, 10.0), button: MouseButton::Left };
+        let up = UiEvent::PointerUp { id: 0, position: Point::new(10.0, 10.0), button: MouseButton::Left };
+        button.handle_event(&down, &mut context);
+        button.handle_event(&up, &mut context);
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 400.0, 0.0, 400.0),
+            Arc::new(Theme::default()),
+        );
+        let round = Button::icon_only(Icon::new("plus")).with_variant(ButtonVariant::Ghost);
+        let WidgetNode::Leaf(render_obj) = round.build(&ctx) else {
+            panic!("button builds a leaf");
+        };
+        let size = render_obj.layout_size();
+        assert_eq!(size.width, size.height);
+    }
+
+    #[test]
+    fn test_ghost_buttons_paint_no_background() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+
+        fn painted_rects(obj: &RenderObject, out: &mut Vec<Rect>) {
+            match obj {
+                RenderObject::Rect { rect, paint } if paint.color.a > 0 => out.push(*rect),
+                RenderObject::Group { children } => children.iter().for_each(|child| painted_rects(child, out)),
+                RenderObject::Transform { child, .. } | RenderObject::ClipShape { child, .. } => painted_rects(child, out),
+                _ => {}
+            }
+        }
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 400.0, 0.0, 400.0),
+            Arc::new(Theme::default()),
+        );
+        let build = |button: Button| match button.build(&ctx) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            _ => panic!("button builds a leaf"),
+        };
+        let primary = build(Button::new("Cancel"));
+        let ghost = build(Button::new("Cancel").with_variant(ButtonVariant::Ghost));
+
+        let mut rects = Vec::new();
+        painted_rects(&ghost, &mut rects);
+        assert!(rects.is_empty(), "ghost button painted {:?}", rects);
+        assert_eq!(ghost.layout_size(), primary.layout_size());
+    }
+
+    #[test]
+    fn test_stylesheet_hover_and_explicit_colors_layer_in_order() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+        use crate::theming::Stylesheet;
+
+        fn fills(obj: &RenderObject, out: &mut Vec<Color>) {
+            match obj {
+                RenderObject::Rect { paint, .. } if paint.color.a > 0 => out.push(paint.color),
+                RenderObject::Group { children } => children.iter().for_each(|child| fills(child, out)),
+                RenderObject::Transform { child, .. } | RenderObject::ClipShape { child, .. } => fills(child, out),
+                _ => {}
+            }
+        }
+        let sheet = Stylesheet::parse("Button { background: #f00; radius: 6; } Button:hover { background: #00f; }").unwrap();
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 400.0, 0.0, 400.0),
+            Arc::new(Theme::default()),
+        )
+        .with_stylesheet(Arc::new(sheet));
+        let build = |button: &Button| match button.build(&ctx) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            _ => panic!("button builds a leaf"),
+        };
+
+        let styled = Button::new("Save");
+        let idle = build(&styled);
+        let mut colors = Vec::new();
+        fills(&idle, &mut colors);
+        assert_eq!(colors, vec![Color::rgb(255, 0, 0)]);
+        // The fill is clipped to the rule's radius
+        assert!(matches!(&idle, RenderObject::ClipShape { shape: ClipShape::RoundedRect { radii: [6.0, ..], .. }, .. }));
+
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        styled.handle_event(&UiEvent::PointerEnter, &mut context);
+        let mut colors = Vec::new();
+        fills(&build(&styled), &mut colors);
+        assert_eq!(colors, vec![Color::rgb(0, 0, 255)]);
+
+        // An explicit color beats every rule
+        let explicit = Button::new("Save").with_color(Color::rgb(0, 255, 0));
+        let mut colors = Vec::new();
+        fills(&build(&explicit), &mut colors);
+        assert_eq!(colors, vec![Color::rgb(0, 255, 0)]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::*;
 use crate::core::render_object::{Point, Rect, TextStyle};  // Use OUR Rect
+use crate::state_management::bindable::Binding;
 use std::any::Any;
 use std::sync::Arc;
 
@@ -9,6 +10,7 @@ pub struct Checkbox {
     pub label: Option<String>,
     pub disabled: bool,
     pub on_change: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    pub binding: Option<Binding<bool>>,
     pub tooltip: Option<String>,
     key: Option<WidgetKey>,
 }
@@ -20,6 +22,7 @@ impl Checkbox {
             label: None,
             disabled: false,
             on_change: None,
+            binding: None,
             tooltip: None,
             key: None,
         }
@@ -43,6 +46,12 @@ impl Checkbox {
         self
     }
 
+    /// Read the checked state from, and write toggles back to, a binding
+    pub fn with_binding(mut self, binding: impl Into<Binding<bool>>) -> Self {
+        self.binding = Some(binding.into());
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -52,14 +61,27 @@ impl Checkbox {
         self.tooltip = Some(text.into());
         self
     }
+
+    /// Current checked state, preferring the binding over the static value
+    pub fn is_checked(&self) -> bool {
+        match &self.binding {
+            Some(binding) => binding.get(),
+            None => self.checked,
+        }
+    }
 }
 
 impl StatelessWidget for Checkbox {
-    fn build_stateless(&self, _ctx: &BuildContext) -> WidgetNode {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        if let Some(binding) = &self.binding {
+            binding.subscribe(ctx.element_id);
+        }
+
+        let checked = self.is_checked();
         let size = 20.0;
         let mut render_objects = Vec::new();
 
-        let bg_color = if self.checked {
+        let bg_color = if checked {
             Color::from_hex(0xD87943)
         } else {
             Color::WHITE
@@ -72,7 +94,7 @@ impl StatelessWidget for Checkbox {
         ));
 
         // Border
-        let border_color = if self.checked {
+        let border_color = if checked {
             Color::from_hex(0xD87943)
         } else {
             Color::from_hex(0xE5E7EB)
@@ -89,7 +111,7 @@ impl StatelessWidget for Checkbox {
         }
 
         // Checkmark
-        if self.checked {
+        if checked {
             render_objects.push(RenderObject::rect(
                 Rect::new(6.0, 9.0, 8.0, 2.0),
                 Color::WHITE,
@@ -110,6 +132,7 @@ impl StatelessWidget for Checkbox {
                     color: Color::from_hex(0x111827),
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(size + 8.0, size / 2.0 + 5.0),
             ));
@@ -124,6 +147,26 @@ impl Widget for Checkbox {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if self.disabled {
+            return EventResult::Unhandled;
+        }
+
+        match event {
+            UiEvent::PointerUp { button: MouseButton::Left, .. } if context.is_at_target() => {
+                let checked = !self.is_checked();
+                if let Some(binding) = &self.binding {
+                    binding.set(checked);
+                }
+                if let Some(on_change) = &self.on_change {
+                    on_change(checked);
+                }
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -0,0 +1,406 @@
+//! Text model behind `CodeEditor`: lines, cursors and the edits between them
+//!
+//! Every editing command applies at each cursor in document order. An edit
+//! shifts the cursors after it, so they keep pointing at the same text.
+
+/// A place in the text; `column` counts characters, not bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TextPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    pub const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// One edit: `removed` at `start` was replaced with `inserted`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChange {
+    pub start: TextPosition,
+    pub removed: String,
+    pub inserted: String,
+}
+
+/// What the Tab key inserts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabHandling {
+    /// Spaces up to the next multiple of the width; Backspace in leading
+    /// whitespace removes a whole indent level
+    Spaces(usize),
+    /// A literal tab character
+    Tab,
+}
+
+impl Default for TabHandling {
+    fn default() -> Self {
+        TabHandling::Spaces(4)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorMove {
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    LineEnd,
+}
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+#[derive(Debug, Clone)]
+pub struct EditorBuffer {
+    lines: Vec<String>,
+    /// Sorted, without duplicates; the first is the primary cursor
+    cursors: Vec<TextPosition>,
+    pub tab: TabHandling,
+    /// Start new lines at the previous line's indentation
+    pub auto_indent: bool,
+}
+
+impl EditorBuffer {
+    pub fn new(text: &str) -> Self {
+        Self {
+            lines: split_lines(text),
+            cursors: vec![TextPosition::default()],
+            tab: TabHandling::default(),
+            auto_indent: true,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Replace the whole text, leaving a single cursor at the start
+    pub fn set_text(&mut self, text: &str) -> TextChange {
+        let removed = self.text();
+        self.lines = split_lines(text);
+        self.cursors = vec![TextPosition::default()];
+        TextChange {
+            start: TextPosition::default(),
+            removed,
+            inserted: text.to_string(),
+        }
+    }
+
+    pub fn cursors(&self) -> &[TextPosition] {
+        &self.cursors
+    }
+
+    pub fn primary_cursor(&self) -> TextPosition {
+        self.cursors[0]
+    }
+
+    /// Drop all cursors and place one at `position`
+    pub fn set_cursor(&mut self, position: TextPosition) {
+        self.cursors = vec![self.clamp(position)];
+    }
+
+    /// Add another cursor, e.g. on Alt+click
+    pub fn add_cursor(&mut self, position: TextPosition) {
+        let position = self.clamp(position);
+        self.cursors.push(position);
+        self.normalize_cursors();
+    }
+
+    /// Keep only the primary cursor
+    pub fn clear_secondary_cursors(&mut self) {
+        self.cursors.truncate(1);
+    }
+
+    pub fn move_cursors(&mut self, movement: CursorMove) {
+        for i in 0..self.cursors.len() {
+            let TextPosition { line, column } = self.cursors[i];
+            let line_len = self.line_len(line);
+            self.cursors[i] = match movement {
+                CursorMove::Left if column > 0 => TextPosition::new(line, column - 1),
+                CursorMove::Left if line > 0 => TextPosition::new(line - 1, self.line_len(line - 1)),
+                CursorMove::Right if column < line_len => TextPosition::new(line, column + 1),
+                CursorMove::Right if line + 1 < self.lines.len() => TextPosition::new(line + 1, 0),
+                CursorMove::Up if line > 0 => self.clamp(TextPosition::new(line - 1, column)),
+                CursorMove::Down if line + 1 < self.lines.len() => self.clamp(TextPosition::new(line + 1, column)),
+                CursorMove::LineStart => TextPosition::new(line, 0),
+                CursorMove::LineEnd => TextPosition::new(line, line_len),
+                _ => self.cursors[i],
+            };
+        }
+        self.normalize_cursors();
+    }
+
+    /// Type `text` at every cursor
+    pub fn insert(&mut self, text: &str) -> Vec<TextChange> {
+        self.edit_each(|_, cursor| (cursor, cursor, text.to_string()))
+    }
+
+    /// Break the line at every cursor, carrying the indentation over
+    pub fn newline(&mut self) -> Vec<TextChange> {
+        let auto_indent = self.auto_indent;
+        self.edit_each(|buffer, cursor| {
+            let indent = if auto_indent {
+                leading_whitespace(&buffer.lines[cursor.line])
+                    .chars()
+                    .take(cursor.column)
+                    .collect::<String>()
+            } else {
+                String::new()
+            };
+            (cursor, cursor, format!("\n{}", indent))
+        })
+    }
+
+    /// Indent at every cursor according to `tab`
+    pub fn tab(&mut self) -> Vec<TextChange> {
+        let tab = self.tab;
+        self.edit_each(|_, cursor| {
+            let inserted = match tab {
+                TabHandling::Tab => "\t".to_string(),
+                TabHandling::Spaces(width) => {
+                    let width = width.max(1);
+                    " ".repeat(width - cursor.column % width)
+                }
+            };
+            (cursor, cursor, inserted)
+        })
+    }
+
+    /// Delete the character (or indent level) before every cursor
+    pub fn backspace(&mut self) -> Vec<TextChange> {
+        let tab = self.tab;
+        self.edit_each(|buffer, cursor| {
+            let TextPosition { line, column } = cursor;
+            if column == 0 {
+                return match line {
+                    0 => (cursor, cursor, String::new()),
+                    _ => (TextPosition::new(line - 1, buffer.line_len(line - 1)), cursor, String::new()),
+                };
+            }
+            let before: String = buffer.lines[line].chars().take(column).collect();
+            let count = match tab {
+                TabHandling::Spaces(width) if width > 0 && before.chars().all(|c| c == ' ') => {
+                    (column - 1) % width + 1
+                }
+                _ => 1,
+            };
+            (TextPosition::new(line, column - count), cursor, String::new())
+        })
+    }
+
+    /// Delete the character after every cursor
+    pub fn delete(&mut self) -> Vec<TextChange> {
+        self.edit_each(|buffer, cursor| {
+            let TextPosition { line, column } = cursor;
+            let end = if column < buffer.line_len(line) {
+                TextPosition::new(line, column + 1)
+            } else if line + 1 < buffer.lines.len() {
+                TextPosition::new(line + 1, 0)
+            } else {
+                cursor
+            };
+            (cursor, end, String::new())
+        })
+    }
+
+    /// The bracket at or just before `position` and its partner
+    pub fn matching_bracket(&self, position: TextPosition) -> Option<(TextPosition, TextPosition)> {
+        let at = |p: TextPosition| self.lines.get(p.line)?.chars().nth(p.column);
+        let candidates = [
+            Some(position),
+            position.column.checked_sub(1).map(|c| TextPosition::new(position.line, c)),
+        ];
+        for start in candidates.into_iter().flatten() {
+            let Some(ch) = at(start) else { continue };
+            for (open, close) in BRACKETS {
+                if ch == open {
+                    return self.scan_forward(start, open, close).map(|end| (start, end));
+                }
+                if ch == close {
+                    return self.scan_backward(start, open, close).map(|end| (start, end));
+                }
+            }
+        }
+        None
+    }
+
+    fn scan_forward(&self, from: TextPosition, open: char, close: char) -> Option<TextPosition> {
+        let mut depth = 0usize;
+        for (line, text) in self.lines.iter().enumerate().skip(from.line) {
+            let skip = if line == from.line { from.column } else { 0 };
+            for (column, ch) in text.chars().enumerate().skip(skip) {
+                if ch == open {
+                    depth += 1;
+                } else if ch == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(TextPosition::new(line, column));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn scan_backward(&self, from: TextPosition, open: char, close: char) -> Option<TextPosition> {
+        let mut depth = 0usize;
+        for line in (0..=from.line).rev() {
+            let chars: Vec<char> = self.lines[line].chars().collect();
+            let end = if line == from.line { from.column + 1 } else { chars.len() };
+            for column in (0..end.min(chars.len())).rev() {
+                if chars[column] == close {
+                    depth += 1;
+                } else if chars[column] == open {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(TextPosition::new(line, column));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Replace a range at each cursor, in document order. `range` maps a
+    /// cursor to the `(start, end, text)` to put there; the cursor lands
+    /// after the inserted text.
+    fn edit_each<F>(&mut self, mut range: F) -> Vec<TextChange>
+    where
+        F: FnMut(&EditorBuffer, TextPosition) -> (TextPosition, TextPosition, String),
+    {
+        let mut changes = Vec::new();
+        for i in 0..self.cursors.len() {
+            let (start, end, inserted) = range(self, self.cursors[i]);
+            if start == end && inserted.is_empty() {
+                continue;
+            }
+            let (removed, new_end) = self.replace(start, end, &inserted);
+            self.cursors[i] = new_end;
+            for later in &mut self.cursors[i + 1..] {
+                *later = shift(*later, end, new_end);
+            }
+            changes.push(TextChange { start, removed, inserted });
+        }
+        self.normalize_cursors();
+        changes
+    }
+
+    /// Replace `start..end` with `text`; returns the removed text and the end
+    /// of the inserted text
+    fn replace(&mut self, start: TextPosition, end: TextPosition, text: &str) -> (String, TextPosition) {
+        let head: String = self.lines[start.line].chars().take(start.column).collect();
+        let tail: String = self.lines[end.line].chars().skip(end.column).collect();
+
+        let removed = if start.line == end.line {
+            self.lines[start.line]
+                .chars()
+                .skip(start.column)
+                .take(end.column - start.column)
+                .collect()
+        } else {
+            let mut removed: String = self.lines[start.line].chars().skip(start.column).collect();
+            for line in &self.lines[start.line + 1..end.line] {
+                removed.push('\n');
+                removed.push_str(line);
+            }
+            removed.push('\n');
+            removed.extend(self.lines[end.line].chars().take(end.column));
+            removed
+        };
+
+        let mut inserted = split_lines(text);
+        let last = inserted.len() - 1;
+        let new_end = TextPosition::new(
+            start.line + last,
+            if last == 0 { start.column } else { 0 } + inserted[last].chars().count(),
+        );
+        inserted[0] = format!("{}{}", head, inserted[0]);
+        inserted[last].push_str(&tail);
+        self.lines.splice(start.line..=end.line, inserted);
+
+        (removed, new_end)
+    }
+
+    fn line_len(&self, line: usize) -> usize {
+        self.lines.get(line).map_or(0, |l| l.chars().count())
+    }
+
+    fn clamp(&self, position: TextPosition) -> TextPosition {
+        let line = position.line.min(self.lines.len() - 1);
+        TextPosition::new(line, position.column.min(self.line_len(line)))
+    }
+
+    fn normalize_cursors(&mut self) {
+        let primary = self.cursors[0];
+        self.cursors.sort();
+        self.cursors.dedup();
+        // Keep the primary cursor first so scrolling follows it
+        if let Some(index) = self.cursors.iter().position(|c| *c == primary) {
+            self.cursors[..=index].rotate_right(1);
+        }
+    }
+}
+
+/// Where `position` ends up after text ending at `old_end` was replaced by
+/// text ending at `new_end`
+fn shift(position: TextPosition, old_end: TextPosition, new_end: TextPosition) -> TextPosition {
+    if position.line == old_end.line {
+        TextPosition::new(new_end.line, new_end.column + position.column.saturating_sub(old_end.column))
+    } else {
+        TextPosition::new(position.line + new_end.line - old_end.line, position.column)
+    }
+}
+
+fn split_lines(text: &str) -> Vec<String> {
+    text.split('\n').map(|line| line.trim_end_matches('\r').to_string()).collect()
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multi_cursor_editing() {
+        let mut buffer = EditorBuffer::new("fn a() {\nfn b() {");
+        buffer.set_cursor(TextPosition::new(0, 8));
+        buffer.add_cursor(TextPosition::new(1, 8));
+
+        buffer.newline();
+        buffer.tab();
+        buffer.insert("x");
+        assert_eq!(buffer.text(), "fn a() {\n    x\nfn b() {\n    x");
+        assert_eq!(buffer.cursors(), &[TextPosition::new(1, 5), TextPosition::new(3, 5)]);
+
+        // Backspace removes the character, then a whole indent level
+        buffer.backspace();
+        buffer.backspace();
+        assert_eq!(buffer.text(), "fn a() {\n\nfn b() {\n");
+
+        // Unclosed
+        assert_eq!(buffer.matching_bracket(TextPosition::new(0, 7)), None);
+
+        let buffer = EditorBuffer::new("f(a[0], {})");
+        assert_eq!(
+            buffer.matching_bracket(TextPosition::new(0, 1)),
+            Some((TextPosition::new(0, 1), TextPosition::new(0, 10)))
+        );
+        assert_eq!(
+            buffer.matching_bracket(TextPosition::new(0, 6)),
+            Some((TextPosition::new(0, 5), TextPosition::new(0, 3)))
+        );
+    }
+}
@@ -0,0 +1,440 @@
+//! Embeddable code editor
+//!
+//! `CodeEditor` draws the text held by a `CodeEditorController` with a
+//! line-number gutter, syntax colors, every cursor, and the bracket pair at
+//! the primary cursor. The controller owns the text and cursors, so they
+//! survive rebuilds, and reports each edit through `on_change` or the
+//! `changes()` channel.
+//!
+//! Alt+Up/Down adds a cursor above or below; Escape goes back to one.
+
+mod buffer;
+mod syntax;
+
+pub use buffer::{CursorMove, EditorBuffer, TabHandling, TextChange, TextPosition};
+pub use syntax::{KeywordHighlighter, SyntaxHighlighter, Token, TokenKind};
+
+use std::any::Any;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use parking_lot::{Mutex, RwLock};
+use winit::keyboard::KeyCode;
+
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::ThemeProvider;
+
+/// Columns a tab advances to when drawn
+const TAB_STOP: usize = 4;
+const PADDING: f32 = 8.0;
+
+type ChangeListener = Box<dyn Fn(&TextChange) + Send + Sync>;
+
+struct EditorState {
+    buffer: EditorBuffer,
+    /// First visible line; follows the primary cursor
+    scroll_line: usize,
+}
+
+/// Text, cursors and change subscribers shared by a `CodeEditor` and the app
+#[derive(Clone)]
+pub struct CodeEditorController {
+    state: Arc<RwLock<EditorState>>,
+    listeners: Arc<RwLock<Vec<ChangeListener>>>,
+    senders: Arc<Mutex<Vec<Sender<TextChange>>>>,
+}
+
+impl CodeEditorController {
+    pub fn new(text: &str) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(EditorState {
+                buffer: EditorBuffer::new(text),
+                scroll_line: 0,
+            })),
+            listeners: Arc::new(RwLock::new(Vec::new())),
+            senders: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.state.read().buffer.text()
+    }
+
+    pub fn set_text(&self, text: &str) {
+        self.edit(|buffer| vec![buffer.set_text(text)]);
+    }
+
+    pub fn cursors(&self) -> Vec<TextPosition> {
+        self.state.read().buffer.cursors().to_vec()
+    }
+
+    pub fn set_tab_handling(&self, tab: TabHandling) {
+        self.state.write().buffer.tab = tab;
+    }
+
+    pub fn set_auto_indent(&self, auto_indent: bool) {
+        self.state.write().buffer.auto_indent = auto_indent;
+    }
+
+    /// Call `listener` after every edit
+    pub fn on_change<F>(&self, listener: F)
+    where
+        F: Fn(&TextChange) + Send + Sync + 'static,
+    {
+        self.listeners.write().push(Box::new(listener));
+    }
+
+    /// A channel receiving every edit from now on, for consumers on other
+    /// threads (language servers, autosave, collaborative sync)
+    pub fn changes(&self) -> Receiver<TextChange> {
+        let (sender, receiver) = mpsc::channel();
+        self.senders.lock().push(sender);
+        receiver
+    }
+
+    /// Run `f` on the buffer and report the changes it returns
+    pub fn edit<F>(&self, f: F)
+    where
+        F: FnOnce(&mut EditorBuffer) -> Vec<TextChange>,
+    {
+        // Release the buffer before notifying, so listeners can read it
+        let changes = f(&mut self.state.write().buffer);
+        if changes.is_empty() {
+            return;
+        }
+        let listeners = self.listeners.read();
+        let mut senders = self.senders.lock();
+        for change in &changes {
+            for listener in listeners.iter() {
+                listener(change);
+            }
+            senders.retain(|sender| sender.send(change.clone()).is_ok());
+        }
+    }
+
+    fn with_buffer<R>(&self, f: impl FnOnce(&mut EditorBuffer) -> R) -> R {
+        f(&mut self.state.write().buffer)
+    }
+}
+
+#[derive(Clone)]
+pub struct CodeEditor {
+    pub controller: CodeEditorController,
+    pub highlighter: Option<Arc<dyn SyntaxHighlighter>>,
+    pub line_numbers: bool,
+    /// Visible lines
+    pub rows: u32,
+    pub width: Option<f32>,
+    pub font_size: f32,
+    /// Allow moving cursors but not editing
+    pub read_only: bool,
+    key: Option<WidgetKey>,
+}
+
+/// Sizes shared by drawing and hit-testing
+struct Metrics {
+    char_width: f32,
+    line_height: f32,
+    text_x: f32,
+}
+
+impl CodeEditor {
+    pub fn new(controller: CodeEditorController) -> Self {
+        Self {
+            controller,
+            highlighter: None,
+            line_numbers: true,
+            rows: 12,
+            width: None,
+            font_size: 13.0,
+            read_only: false,
+            key: None,
+        }
+    }
+
+    pub fn with_highlighter(mut self, highlighter: impl SyntaxHighlighter + 'static) -> Self {
+        self.highlighter = Some(Arc::new(highlighter));
+        self
+    }
+
+    pub fn without_line_numbers(mut self) -> Self {
+        self.line_numbers = false;
+        self
+    }
+
+    pub fn rows(mut self, rows: u32) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    fn metrics(&self, line_count: usize) -> Metrics {
+        let char_width = self.font_size * 0.6;
+        let gutter = if self.line_numbers {
+            line_count.to_string().len().max(2) as f32 * char_width + PADDING * 2.0
+        } else {
+            0.0
+        };
+        Metrics {
+            char_width,
+            line_height: (self.font_size * 1.5).round(),
+            text_x: gutter + PADDING,
+        }
+    }
+
+    /// Text position under a point local to the editor
+    fn position_at(&self, point: Point) -> TextPosition {
+        let state = self.controller.state.read();
+        let metrics = self.metrics(state.buffer.line_count());
+        let row = ((point.y - PADDING) / metrics.line_height).max(0.0) as usize;
+        let line = (state.scroll_line + row).min(state.buffer.line_count() - 1);
+        let visual = ((point.x - metrics.text_x) / metrics.char_width).round().max(0.0) as usize;
+        TextPosition::new(line, column_at_visual(&state.buffer.lines()[line], visual))
+    }
+}
+
+/// Drawn column of character `column`, with tabs expanded
+fn visual_column(line: &str, column: usize) -> usize {
+    line.chars()
+        .take(column)
+        .fold(0, |visual, ch| if ch == '\t' { visual + TAB_STOP - visual % TAB_STOP } else { visual + 1 })
+}
+
+/// Character column drawn at or just before `visual`
+fn column_at_visual(line: &str, visual: usize) -> usize {
+    let count = line.chars().count();
+    (0..=count).take_while(|&column| visual_column(line, column) <= visual).last().unwrap_or(0)
+}
+
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    for ch in line.chars() {
+        if ch == '\t' {
+            let width = TAB_STOP - expanded.chars().count() % TAB_STOP;
+            expanded.extend(std::iter::repeat(' ').take(width));
+        } else {
+            expanded.push(ch);
+        }
+    }
+    expanded
+}
+
+impl StatelessWidget for CodeEditor {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let theme = ctx.theme();
+        let rows = self.rows as usize;
+
+        let mut state = self.controller.state.write();
+        let primary = state.buffer.primary_cursor();
+        if primary.line < state.scroll_line {
+            state.scroll_line = primary.line;
+        } else if primary.line >= state.scroll_line + rows {
+            state.scroll_line = primary.line + 1 - rows;
+        }
+        let scroll_line = state.scroll_line;
+        let buffer = &state.buffer;
+
+        let metrics = self.metrics(buffer.line_count());
+        let width = self.width.unwrap_or(ctx.constraints.max_width.min(640.0));
+        let height = rows as f32 * metrics.line_height + PADDING * 2.0;
+        let visible = scroll_line..(scroll_line + rows).min(buffer.line_count());
+        let row_y = |line: usize| PADDING + (line - scroll_line) as f32 * metrics.line_height;
+        let column_x = |position: TextPosition| {
+            metrics.text_x + visual_column(&buffer.lines()[position.line], position.column) as f32 * metrics.char_width
+        };
+        let style = |color: Color| TextStyle {
+            font_family: theme.font_mono.clone(),
+            font_size: self.font_size,
+            color,
+            bold: false,
+            italic: false,
+            line_height: Some(1.5),
+            ..Default::default()
+        };
+
+        let mut render_objects = vec![
+            RenderObject::rect(Rect::new(0.0, 0.0, width, height), theme.card),
+            RenderObject::rect(Rect::new(0.0, 0.0, width, 1.0), theme.border),
+            RenderObject::rect(Rect::new(0.0, height - 1.0, width, 1.0), theme.border),
+            RenderObject::rect(Rect::new(0.0, 0.0, 1.0, height), theme.border),
+            RenderObject::rect(Rect::new(width - 1.0, 0.0, 1.0, height), theme.border),
+        ];
+
+        // Current line and gutter
+        if visible.contains(&primary.line) {
+            render_objects.push(RenderObject::rect(
+                Rect::new(1.0, row_y(primary.line), width - 2.0, metrics.line_height),
+                theme.accent.with_alpha(90),
+            ));
+        }
+        if self.line_numbers {
+            let gutter_width = metrics.text_x - PADDING;
+            render_objects.push(RenderObject::rect(Rect::new(1.0, 1.0, gutter_width, height - 2.0), theme.muted));
+            for line in visible.clone() {
+                let number = (line + 1).to_string();
+                let color = if line == primary.line { theme.foreground } else { theme.muted_foreground };
+                let x = gutter_width - PADDING - number.len() as f32 * metrics.char_width;
+                render_objects.push(RenderObject::text(number, style(color), Point::new(x, row_y(line))));
+            }
+        }
+
+        let mut content = Vec::new();
+
+        // Bracket pair at the primary cursor
+        if let Some((bracket, partner)) = buffer.matching_bracket(primary) {
+            for position in [bracket, partner] {
+                if visible.contains(&position.line) {
+                    content.push(RenderObject::rect(
+                        Rect::new(column_x(position), row_y(position.line), metrics.char_width, metrics.line_height),
+                        theme.ring.with_alpha(80),
+                    ));
+                }
+            }
+        }
+
+        // Highlighted text
+        for line in visible.clone() {
+            let text = &buffer.lines()[line];
+            let expanded: Vec<char> = expand_tabs(text).chars().collect();
+            let tokens = match &self.highlighter {
+                Some(highlighter) => highlighter.highlight_line(text),
+                None => vec![Token {
+                    columns: 0..text.chars().count(),
+                    kind: TokenKind::Plain,
+                }],
+            };
+            for token in tokens {
+                let start = visual_column(text, token.columns.start);
+                let end = visual_column(text, token.columns.end).min(expanded.len());
+                let run: String = expanded[start.min(end)..end].iter().collect();
+                if run.trim().is_empty() {
+                    continue;
+                }
+                content.push(RenderObject::text(
+                    run,
+                    style(token.kind.color(theme)),
+                    Point::new(metrics.text_x + start as f32 * metrics.char_width, row_y(line)),
+                ));
+            }
+        }
+
+        // Cursors
+        for cursor in buffer.cursors() {
+            if visible.contains(&cursor.line) {
+                content.push(RenderObject::rect(
+                    Rect::new(column_x(*cursor), row_y(cursor.line), 2.0, metrics.line_height),
+                    theme.foreground,
+                ));
+            }
+        }
+
+        // Long lines are cut at the border rather than drawn over it
+        render_objects.push(RenderObject::clip(
+            Rect::new(metrics.text_x - 2.0, 1.0, width - metrics.text_x + 1.0, height - 2.0),
+            RenderObject::group(content),
+        ));
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+}
+
+impl Widget for CodeEditor {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if !context.is_at_target() {
+            return EventResult::Unhandled;
+        }
+        let controller = &self.controller;
+
+        match event {
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } => {
+                let target = self.position_at(*position);
+                controller.with_buffer(|buffer| buffer.set_cursor(target));
+                EventResult::Stopped
+            }
+            UiEvent::TextInput { character } if !self.read_only && !character.is_control() => {
+                controller.edit(|buffer| buffer.insert(&character.to_string()));
+                EventResult::Stopped
+            }
+            UiEvent::KeyDown { key, modifiers, .. } => {
+                let editable = !self.read_only;
+                match key {
+                    KeyCode::Enter | KeyCode::NumpadEnter if editable => controller.edit(EditorBuffer::newline),
+                    KeyCode::Tab if editable => controller.edit(EditorBuffer::tab),
+                    KeyCode::Backspace if editable => controller.edit(EditorBuffer::backspace),
+                    KeyCode::Delete if editable => controller.edit(EditorBuffer::delete),
+                    KeyCode::ArrowUp | KeyCode::ArrowDown if modifiers.alt => {
+                        // Extend the column of cursors up or down
+                        controller.with_buffer(|buffer| {
+                            let cursors = buffer.cursors();
+                            let edge = if *key == KeyCode::ArrowUp {
+                                cursors.iter().min()
+                            } else {
+                                cursors.iter().max()
+                            };
+                            let Some(&edge) = edge else { return };
+                            let line = match key {
+                                KeyCode::ArrowUp => edge.line.checked_sub(1),
+                                _ => Some(edge.line + 1).filter(|l| *l < buffer.line_count()),
+                            };
+                            if let Some(line) = line {
+                                buffer.add_cursor(TextPosition::new(line, edge.column));
+                            }
+                        });
+                    }
+                    KeyCode::ArrowLeft => controller.with_buffer(|b| b.move_cursors(CursorMove::Left)),
+                    KeyCode::ArrowRight => controller.with_buffer(|b| b.move_cursors(CursorMove::Right)),
+                    KeyCode::ArrowUp => controller.with_buffer(|b| b.move_cursors(CursorMove::Up)),
+                    KeyCode::ArrowDown => controller.with_buffer(|b| b.move_cursors(CursorMove::Down)),
+                    KeyCode::Home => controller.with_buffer(|b| b.move_cursors(CursorMove::LineStart)),
+                    KeyCode::End => controller.with_buffer(|b| b.move_cursors(CursorMove::LineEnd)),
+                    KeyCode::Escape => controller.with_buffer(EditorBuffer::clear_secondary_cursors),
+                    _ => return EventResult::Unhandled,
+                }
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,166 @@
+//! Syntax highlighting for `CodeEditor`
+//!
+//! Highlighters work a line at a time, which keeps re-highlighting cheap
+//! while typing. `KeywordHighlighter` covers keywords, strings, numbers and
+//! line comments; implement `SyntaxHighlighter` for anything richer.
+
+use std::ops::Range;
+
+use crate::core::render_object::Color;
+use crate::core::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    String,
+    Number,
+    Comment,
+    Punctuation,
+}
+
+impl TokenKind {
+    /// Color for this kind of token in `theme`
+    pub fn color(self, theme: &Theme) -> Color {
+        match self {
+            TokenKind::Plain => theme.foreground,
+            TokenKind::Keyword => theme.primary,
+            TokenKind::String => theme.chart_2,
+            TokenKind::Number => theme.chart_1,
+            TokenKind::Comment => theme.muted_foreground,
+            TokenKind::Punctuation => theme.foreground.with_alpha(180),
+        }
+    }
+}
+
+/// A run of one token kind; `columns` counts characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub columns: Range<usize>,
+    pub kind: TokenKind,
+}
+
+pub trait SyntaxHighlighter: Send + Sync {
+    /// Tokens covering `line`; uncovered columns are drawn as plain text
+    fn highlight_line(&self, line: &str) -> Vec<Token>;
+}
+
+/// Keywords, quoted strings, numbers and line comments
+#[derive(Debug, Clone, Default)]
+pub struct KeywordHighlighter {
+    pub keywords: Vec<String>,
+    pub line_comment: Option<String>,
+    pub quotes: Vec<char>,
+}
+
+impl KeywordHighlighter {
+    pub fn new<I, S>(keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            keywords: keywords.into_iter().map(Into::into).collect(),
+            line_comment: None,
+            quotes: vec!['"'],
+        }
+    }
+
+    pub fn with_line_comment(mut self, prefix: impl Into<String>) -> Self {
+        self.line_comment = Some(prefix.into());
+        self
+    }
+
+    pub fn with_quotes(mut self, quotes: &[char]) -> Self {
+        self.quotes = quotes.to_vec();
+        self
+    }
+
+    pub fn rust() -> Self {
+        Self::new([
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "false",
+            "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+            "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+            "where", "while",
+        ])
+        .with_line_comment("//")
+    }
+
+    pub fn javascript() -> Self {
+        Self::new([
+            "async", "await", "break", "case", "catch", "class", "const", "continue", "default", "else",
+            "export", "extends", "false", "for", "function", "if", "import", "in", "let", "new", "null",
+            "return", "switch", "this", "throw", "true", "try", "typeof", "undefined", "var", "while",
+        ])
+        .with_line_comment("//")
+        .with_quotes(&['"', '\'', '`'])
+    }
+
+    pub fn python() -> Self {
+        Self::new([
+            "and", "as", "async", "await", "break", "class", "continue", "def", "elif", "else", "except",
+            "False", "finally", "for", "from", "if", "import", "in", "is", "lambda", "None", "not", "or",
+            "pass", "raise", "return", "True", "try", "while", "with", "yield",
+        ])
+        .with_line_comment("#")
+        .with_quotes(&['"', '\''])
+    }
+
+    pub fn json() -> Self {
+        Self::new(["true", "false", "null"])
+    }
+}
+
+impl SyntaxHighlighter for KeywordHighlighter {
+    fn highlight_line(&self, line: &str) -> Vec<Token> {
+        let chars: Vec<char> = line.chars().collect();
+        let comment: Option<Vec<char>> = self.line_comment.as_ref().map(|c| c.chars().collect());
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let start = i;
+            let ch = chars[i];
+            let kind = if comment.as_ref().is_some_and(|c| chars[i..].starts_with(c)) {
+                i = chars.len();
+                TokenKind::Comment
+            } else if self.quotes.contains(&ch) {
+                i += 1;
+                while i < chars.len() && chars[i] != ch {
+                    // Skip escaped characters, including escaped quotes
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(chars.len());
+                TokenKind::String
+            } else if ch.is_ascii_digit() {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                    i += 1;
+                }
+                TokenKind::Number
+            } else if ch.is_alphabetic() || ch == '_' {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if self.keywords.iter().any(|k| *k == word) {
+                    TokenKind::Keyword
+                } else {
+                    TokenKind::Plain
+                }
+            } else if ch.is_ascii_punctuation() {
+                i += 1;
+                TokenKind::Punctuation
+            } else {
+                i += 1;
+                TokenKind::Plain
+            };
+
+            // Merge neighbouring runs of the same kind to keep draw calls down
+            match tokens.last_mut() {
+                Some(Token { columns, kind: last }) if *last == kind && columns.end == start => columns.end = i,
+                _ => tokens.push(Token { columns: start..i, kind }),
+            }
+        }
+        tokens
+    }
+}
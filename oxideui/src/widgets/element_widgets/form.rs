@@ -0,0 +1,711 @@
+//! Form and field validation
+//!
+//! A `Form` owns a `FormController` that every `FormField` registers with
+//! during build. The controller tracks dirty/touched state per field, runs
+//! validators, gates submission on `is_valid()` and moves focus to the first
+//! invalid field when a submit fails.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use winit::keyboard::KeyCode;
+
+use crate::core::context::BuildContext;
+use crate::core::element::ElementId;
+use crate::core::event::{EventContext, EventResult, UiEvent};
+use crate::core::FocusManager;
+use crate::core::render_object::{Matrix, Point, RenderObject, TextStyle};
+use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::state_management::bindable::Binding;
+use crate::ThemeProvider;
+
+/// Validator returning an error message for invalid values
+pub type Validator<T> = Arc<dyn Fn(&T) -> Option<String> + Send + Sync>;
+
+/// Common validators
+pub mod validators {
+    use super::Validator;
+    use std::sync::Arc;
+
+    /// Fails on empty (or whitespace-only) strings
+    pub fn required(message: impl Into<String>) -> Validator<String> {
+        let message = message.into();
+        Arc::new(move |value: &String| {
+            if value.trim().is_empty() {
+                Some(message.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Fails when the string has fewer than `min` characters
+    pub fn min_length(min: usize, message: impl Into<String>) -> Validator<String> {
+        let message = message.into();
+        Arc::new(move |value: &String| {
+            if value.chars().count() < min {
+                Some(message.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Fails when the string has more than `max` characters
+    pub fn max_length(max: usize, message: impl Into<String>) -> Validator<String> {
+        let message = message.into();
+        Arc::new(move |value: &String| {
+            if value.chars().count() > max {
+                Some(message.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Loose email check: one `@` with a dotted domain
+    pub fn email(message: impl Into<String>) -> Validator<String> {
+        let message = message.into();
+        Arc::new(move |value: &String| {
+            let valid = match value.split_once('@') {
+                Some((local, domain)) => {
+                    !local.is_empty() && domain.contains('.') && !domain.starts_with('.') && !domain.ends_with('.')
+                }
+                None => false,
+            };
+            if valid {
+                None
+            } else {
+                Some(message.clone())
+            }
+        })
+    }
+
+    /// Fails when the value is outside `min..=max`
+    pub fn range(min: f32, max: f32, message: impl Into<String>) -> Validator<f32> {
+        let message = message.into();
+        Arc::new(move |value: &f32| {
+            if *value < min || *value > max {
+                Some(message.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Fails unless the value is true (e.g. "accept the terms")
+    pub fn must_be_true(message: impl Into<String>) -> Validator<bool> {
+        let message = message.into();
+        Arc::new(move |value: &bool| if *value { None } else { Some(message.clone()) })
+    }
+}
+
+/// Registered field entry tracked by the controller
+struct FieldEntry {
+    order: usize,
+    element: Option<ElementId>,
+    validate: Arc<dyn Fn() -> Option<String> + Send + Sync>,
+    is_dirty: Arc<dyn Fn() -> bool + Send + Sync>,
+    touched: bool,
+    error: Option<String>,
+}
+
+struct FormInner {
+    fields: HashMap<String, FieldEntry>,
+    next_order: usize,
+    submitted: bool,
+    focus_manager: Option<Arc<RwLock<FocusManager>>>,
+}
+
+/// Shared handle to a form's field state
+#[derive(Clone)]
+pub struct FormController {
+    inner: Arc<RwLock<FormInner>>,
+}
+
+impl FormController {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(FormInner {
+                fields: HashMap::new(),
+                next_order: 0,
+                submitted: false,
+                focus_manager: None,
+            })),
+        }
+    }
+
+    /// Use this focus manager to focus the first invalid field on submit
+    pub fn with_focus_manager(self, focus_manager: Arc<RwLock<FocusManager>>) -> Self {
+        self.inner.write().focus_manager = Some(focus_manager);
+        self
+    }
+
+    /// Register a field. Re-registering keeps touched state and order.
+    pub fn register<V, D>(&self, name: &str, element: Option<ElementId>, validate: V, is_dirty: D)
+    where
+        V: Fn() -> Option<String> + Send + Sync + 'static,
+        D: Fn() -> bool + Send + Sync + 'static,
+    {
+        let mut guard = self.inner.write();
+        let inner = &mut *guard;
+        let order = inner.next_order;
+        let submitted = inner.submitted;
+        match inner.fields.get_mut(name) {
+            Some(entry) => {
+                entry.element = element.or(entry.element);
+                entry.validate = Arc::new(validate);
+                entry.is_dirty = Arc::new(is_dirty);
+                if entry.touched || submitted {
+                    entry.error = (entry.validate)();
+                }
+            }
+            None => {
+                inner.fields.insert(
+                    name.to_string(),
+                    FieldEntry {
+                        order,
+                        element,
+                        validate: Arc::new(validate),
+                        is_dirty: Arc::new(is_dirty),
+                        touched: false,
+                        error: None,
+                    },
+                );
+                inner.next_order += 1;
+            }
+        }
+    }
+
+    /// Remove a field from the form
+    pub fn unregister(&self, name: &str) {
+        self.inner.write().fields.remove(name);
+    }
+
+    /// Mark a field as touched (the user has interacted with it)
+    pub fn touch(&self, name: &str) {
+        let mut inner = self.inner.write();
+        if let Some(entry) = inner.fields.get_mut(name) {
+            entry.touched = true;
+            entry.error = (entry.validate)();
+        }
+    }
+
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.inner.read().fields.get(name).map_or(false, |f| f.touched)
+    }
+
+    pub fn is_dirty(&self, name: &str) -> bool {
+        self.inner.read().fields.get(name).map_or(false, |f| (f.is_dirty)())
+    }
+
+    /// Whether any field differs from its initial value
+    pub fn is_form_dirty(&self) -> bool {
+        self.inner.read().fields.values().any(|f| (f.is_dirty)())
+    }
+
+    /// Error for a field, only once it has been touched or the form submitted
+    pub fn error(&self, name: &str) -> Option<String> {
+        let inner = self.inner.read();
+        let entry = inner.fields.get(name)?;
+        if entry.touched || inner.submitted {
+            entry.error.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Run a single field's validators, returning its error
+    pub fn validate_field(&self, name: &str) -> Option<String> {
+        let mut inner = self.inner.write();
+        let entry = inner.fields.get_mut(name)?;
+        entry.error = (entry.validate)();
+        entry.error.clone()
+    }
+
+    /// Run all validators and return whether every field passes
+    pub fn validate(&self) -> bool {
+        let mut inner = self.inner.write();
+        let mut valid = true;
+        for entry in inner.fields.values_mut() {
+            entry.error = (entry.validate)();
+            valid &= entry.error.is_none();
+        }
+        valid
+    }
+
+    /// Whether every field currently passes validation
+    pub fn is_valid(&self) -> bool {
+        self.inner.read().fields.values().all(|f| (f.validate)().is_none())
+    }
+
+    /// Names of invalid fields in registration order
+    pub fn invalid_fields(&self) -> Vec<String> {
+        let inner = self.inner.read();
+        let mut invalid: Vec<(usize, &String)> = inner
+            .fields
+            .iter()
+            .filter(|(_, f)| (f.validate)().is_some())
+            .map(|(name, f)| (f.order, name))
+            .collect();
+        invalid.sort_by_key(|(order, _)| *order);
+        invalid.into_iter().map(|(_, name)| name.clone()).collect()
+    }
+
+    /// Validate and touch every field. On failure, focuses the first invalid
+    /// field and returns its element id in `Err`.
+    pub fn submit(&self) -> Result<(), Option<ElementId>> {
+        {
+            let mut inner = self.inner.write();
+            inner.submitted = true;
+            for entry in inner.fields.values_mut() {
+                entry.touched = true;
+            }
+        }
+
+        if self.validate() {
+            return Ok(());
+        }
+
+        let inner = self.inner.read();
+        let first_invalid = inner
+            .fields
+            .values()
+            .filter(|f| f.error.is_some())
+            .min_by_key(|f| f.order)
+            .and_then(|f| f.element);
+
+        if let (Some(element), Some(focus)) = (first_invalid, &inner.focus_manager) {
+            focus.write().set_focus(Some(element));
+        }
+
+        Err(first_invalid)
+    }
+
+    /// Clear touched/submitted state and errors
+    pub fn reset(&self) {
+        let mut inner = self.inner.write();
+        inner.submitted = false;
+        for entry in inner.fields.values_mut() {
+            entry.touched = false;
+            entry.error = None;
+        }
+    }
+}
+
+impl Default for FormController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps an input widget with validation bound to a form
+pub struct FormField<T: Clone + PartialEq + Send + Sync + 'static> {
+    pub name: String,
+    pub label: Option<String>,
+    pub binding: Binding<T>,
+    pub validators: Vec<Validator<T>>,
+    pub child: Box<dyn Widget>,
+    pub controller: Option<FormController>,
+    pub error_builder: Option<Arc<dyn Fn(&str) -> Box<dyn Widget> + Send + Sync>>,
+    initial: Arc<RwLock<Option<T>>>,
+    key: Option<WidgetKey>,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> FormField<T> {
+    /// `binding` should be the same binding the child input writes to
+    pub fn new<W: Widget + 'static>(name: impl Into<String>, binding: Binding<T>, child: W) -> Self {
+        Self {
+            name: name.into(),
+            label: None,
+            binding,
+            validators: Vec::new(),
+            child: Box::new(child),
+            controller: None,
+            error_builder: None,
+            initial: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            label: self.label.clone(),
+            binding: self.binding.clone(),
+            validators: self.validators.clone(),
+            child: self.child.clone_box(),
+            controller: self.controller.clone(),
+            error_builder: self.error_builder.clone(),
+            initial: self.initial.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_validator(mut self, validator: Validator<T>) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    pub fn with_controller(mut self, controller: FormController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
+    /// Replace the default error text with a custom widget
+    pub fn with_error_slot<F>(mut self, builder: F) -> Self
+    where
+        F: Fn(&str) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        self.error_builder = Some(Arc::new(builder));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Run validators against the bound value
+    pub fn validate_value(&self) -> Option<String> {
+        let value = self.binding.get();
+        self.validators.iter().find_map(|v| v(&value))
+    }
+
+    fn register(&self, controller: &FormController, element: ElementId) {
+        {
+            let mut initial = self.initial.write();
+            if initial.is_none() {
+                *initial = Some(self.binding.get());
+            }
+        }
+
+        let binding = self.binding.clone();
+        let validators = self.validators.clone();
+        let dirty_binding = self.binding.clone();
+        let initial = self.initial.clone();
+
+        controller.register(
+            &self.name,
+            Some(element),
+            move || {
+                let value = binding.get();
+                validators.iter().find_map(|v| v(&value))
+            },
+            move || initial.read().as_ref().map_or(false, |init| *init != dirty_binding.get()),
+        );
+    }
+
+    fn label_height(&self) -> f32 {
+        if self.label.is_some() { 22.0 } else { 0.0 }
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> StatelessWidget for FormField<T> {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let theme = ctx.theme();
+        let mut render_objects = Vec::new();
+
+        if let Some(controller) = &self.controller {
+            self.register(controller, ctx.element_id);
+        }
+        self.binding.subscribe(ctx.element_id);
+
+        if let Some(label) = &self.label {
+            render_objects.push(RenderObject::text(
+                label.clone(),
+                TextStyle {
+                    font_family: theme.font_sans.clone(),
+                    font_size: 14.0,
+                    color: theme.foreground,
+                    bold: true,
+                    italic: false,
+                    ..Default::default()
+                },
+                Point::new(0.0, 14.0),
+            ));
+        }
+
+        let child_ctx = ctx.child_context(ctx.element_id, ctx.constraints);
+        if let WidgetNode::Leaf(child) = self.child.build(&child_ctx) {
+            render_objects.push(RenderObject::transform(
+                Matrix::translate(0.0, self.label_height()),
+                child,
+            ));
+        }
+
+        let error = match &self.controller {
+            Some(controller) => controller.error(&self.name),
+            None => self.validate_value(),
+        };
+
+        if let Some(error) = error {
+            let error_y = self.label_height() + 44.0;
+            match &self.error_builder {
+                Some(builder) => {
+                    if let WidgetNode::Leaf(slot) = builder(&error).build(&child_ctx) {
+                        render_objects.push(RenderObject::transform(Matrix::translate(0.0, error_y), slot));
+                    }
+                }
+                None => {
+                    render_objects.push(RenderObject::text(
+                        error,
+                        TextStyle {
+                            font_family: theme.font_sans.clone(),
+                            font_size: 12.0,
+                            color: theme.destructive,
+                            bold: false,
+                            italic: false,
+                            ..Default::default()
+                        },
+                        Point::new(0.0, error_y + 12.0),
+                    ));
+                }
+            }
+        }
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Widget for FormField<T> {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if matches!(event, UiEvent::Blur) {
+            if let Some(controller) = &self.controller {
+                controller.touch(&self.name);
+            }
+        }
+
+        let result = self.child.handle_event(event, context);
+
+        if result != EventResult::Unhandled {
+            if let Some(controller) = &self.controller {
+                if controller.is_touched(&self.name) {
+                    controller.validate_field(&self.name);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Vertical container of form fields sharing one `FormController`
+pub struct Form {
+    pub controller: FormController,
+    pub children: Vec<Box<dyn Widget>>,
+    pub spacing: f32,
+    /// Minimum height of each row; rows are otherwise as tall as their child
+    pub field_height: f32,
+    pub on_submit: Option<Arc<dyn Fn() + Send + Sync>>,
+    pub on_invalid: Option<Arc<dyn Fn(Vec<String>) + Send + Sync>>,
+    key: Option<WidgetKey>,
+}
+
+impl Form {
+    pub fn new(controller: FormController) -> Self {
+        Self {
+            controller,
+            children: Vec::new(),
+            spacing: 16.0,
+            field_height: 0.0,
+            on_submit: None,
+            on_invalid: None,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            controller: self.controller.clone(),
+            children: self.children.iter().map(|child| child.clone_box()).collect(),
+            spacing: self.spacing,
+            field_height: self.field_height,
+            on_submit: self.on_submit.clone(),
+            on_invalid: self.on_invalid.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    /// Add a field, wiring it to this form's controller
+    pub fn with_field<T: Clone + PartialEq + Send + Sync + 'static>(mut self, field: FormField<T>) -> Self {
+        self.children.push(Box::new(field.with_controller(self.controller.clone())));
+        self
+    }
+
+    /// Add a non-field child (headings, submit buttons, ...)
+    pub fn with_child<W: Widget + 'static>(mut self, child: W) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_field_height(mut self, height: f32) -> Self {
+        self.field_height = height;
+        self
+    }
+
+    /// Called when a submit passes validation
+    pub fn with_on_submit<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_submit = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called with the invalid field names when a submit fails
+    pub fn with_on_invalid<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Vec<String>) + Send + Sync + 'static,
+    {
+        self.on_invalid = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.controller.is_valid()
+    }
+
+    /// Submit the form; `on_submit` only runs when every field is valid
+    pub fn submit(&self) -> bool {
+        match self.controller.submit() {
+            Ok(()) => {
+                if let Some(on_submit) = &self.on_submit {
+                    on_submit();
+                }
+                true
+            }
+            Err(_) => {
+                if let Some(on_invalid) = &self.on_invalid {
+                    on_invalid(self.controller.invalid_fields());
+                }
+                false
+            }
+        }
+    }
+}
+
+impl StatelessWidget for Form {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let mut render_objects = Vec::new();
+        let mut y = 0.0;
+
+        // Fields are elements of their own, so input reaches them directly
+        for child_obj in ctx.build_children(&self.children) {
+            let height = child_obj.layout_size().height.max(self.field_height);
+            render_objects.push(RenderObject::transform(Matrix::translate(0.0, y), child_obj));
+            y += height + self.spacing;
+        }
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+}
+
+impl Widget for Form {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, _context: &mut EventContext) -> EventResult {
+        match event {
+            UiEvent::KeyDown { key: KeyCode::Enter | KeyCode::NumpadEnter, modifiers, .. } if !modifiers.shift => {
+                self.submit();
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_management::state::State;
+
+    #[test]
+    fn test_submit_gates_on_validation() {
+        let controller = FormController::new();
+        let email = State::new(String::new());
+        let binding = Binding::from_simple_state(&email);
+        let validator = validators::email("Invalid email");
+
+        let read = binding.clone();
+        controller.register("email", Some(ElementId::new(7)), move || validator(&read.get()), || false);
+
+        assert!(!controller.is_valid());
+        assert_eq!(controller.error("email"), None);
+        assert_eq!(controller.submit(), Err(Some(ElementId::new(7))));
+        assert_eq!(controller.error("email"), Some("Invalid email".to_string()));
+
+        email.set("me@example.com".to_string());
+        assert!(controller.submit().is_ok());
+    }
+
+    #[test]
+    fn test_rows_are_mounted_and_as_tall_as_their_child() {
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::{Constraints, Size};
+        use crate::widgets::layout_widgets::SizedBox;
+
+        let form = Form::new(FormController::new())
+            .with_child(SizedBox::height(30.0))
+            .with_child(SizedBox::height(50.0))
+            .with_child(SizedBox::height(20.0))
+            .with_spacing(10.0);
+        let tree = new_shared_element_tree();
+        let root = tree.write().create_element(&form, None, 0);
+        let ctx = BuildContext::new(root, tree.clone(), Constraints::loose(Size::new(300.0, 600.0)), Arc::new(Theme::default()));
+        let WidgetNode::Leaf(render_obj) = form.build(&ctx) else {
+            panic!("expected the laid out form");
+        };
+
+        assert_eq!(tree.read().get_children(root).len(), 3);
+        assert_eq!(render_obj.layout_size().height, 120.0);
+    }
+}
@@ -1,7 +1,8 @@
 use std::any::Any;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Color, Point, RenderObject, TextStyle};
+use crate::core::render_object::{Color, Point, RenderObject};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::theming::TextVariant;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -55,28 +56,12 @@ impl Heading {
 impl StatelessWidget for Heading {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
-        let font_sizes = [48.0, 36.0, 30.0, 24.0, 20.0, 18.0];
-        let font_weights = [true, true, true, false, false, false];
-
-        let idx = (self.level - 1) as usize;
-        let font_size = font_sizes.get(idx).copied().unwrap_or(16.0);
-        let is_bold = font_weights.get(idx).copied().unwrap_or(false);
-
-        let text_color = self.color.unwrap_or_else(|| {
+        let mut style = theme.text_style(TextVariant::heading(self.level));
+        style.color = self.color.unwrap_or_else(|| {
             Color::from_hex(if theme.is_dark { 0xFFFFFF } else { 0x000000 })
         });
 
-        WidgetNode::Leaf(RenderObject::text(
-            self.text.clone(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size,
-                color: text_color,
-                bold: is_bold,
-                italic: false,
-            },
-            Point::ZERO,
-        ))
+        WidgetNode::Leaf(RenderObject::text(self.text.clone(), style, Point::ZERO))
     }
 }
 
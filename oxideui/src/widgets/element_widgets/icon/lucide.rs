@@ -0,0 +1,306 @@
+//! Bundled icon set: path data from Lucide (https://lucide.dev), ISC License.
+//!
+//! 24×24 view box, drawn with a 2px round stroke. Circles and rounded
+//! rectangles are converted to path data.
+
+/// `(name, paths)`, sorted by name
+pub(super) const ICONS: &[(&str, &[&str])] = &[
+    (
+        "alert-circle",
+        &[
+            "M2 12a10 10 0 1 0 20 0a10 10 0 1 0 -20 0",
+            "M12 8v4",
+            "M12 16h.01",
+        ],
+    ),
+    (
+        "alert-triangle",
+        &[
+            "m21.73 18-8-14a2 2 0 0 0-3.48 0l-8 14A2 2 0 0 0 4 21h16a2 2 0 0 0 1.73-3",
+            "M12 9v4",
+            "M12 17h.01",
+        ],
+    ),
+    (
+        "arrow-down",
+        &[
+            "M12 5v14",
+            "m19 12-7 7-7-7",
+        ],
+    ),
+    (
+        "arrow-left",
+        &[
+            "m12 19-7-7 7-7",
+            "M19 12H5",
+        ],
+    ),
+    (
+        "arrow-right",
+        &[
+            "M5 12h14",
+            "m12 5 7 7-7 7",
+        ],
+    ),
+    (
+        "arrow-up",
+        &[
+            "m5 12 7-7 7 7",
+            "M12 19V5",
+        ],
+    ),
+    (
+        "bell",
+        &[
+            "M10.268 21a2 2 0 0 0 3.464 0",
+            "M3.262 15.326A1 1 0 0 0 4 17h16a1 1 0 0 0 .74-1.673C19.41 13.956 18 12.499 18 8A6 6 0 0 0 6 8c0 4.499-1.411 5.956-2.738 7.326",
+        ],
+    ),
+    (
+        "calendar",
+        &[
+            "M8 2v4",
+            "M16 2v4",
+            "M5 4h14a2 2 0 0 1 2 2v14a2 2 0 0 1 -2 2h-14a2 2 0 0 1 -2 -2v-14a2 2 0 0 1 2 -2z",
+            "M3 10h18",
+        ],
+    ),
+    ("check", &["M20 6 9 17l-5-5"]),
+    (
+        "check-circle",
+        &[
+            "M2 12a10 10 0 1 0 20 0a10 10 0 1 0 -20 0",
+            "m9 12 2 2 4-4",
+        ],
+    ),
+    ("chevron-down", &["m6 9 6 6 6-6"]),
+    ("chevron-left", &["m15 18-6-6 6-6"]),
+    ("chevron-right", &["m9 18 6-6-6-6"]),
+    ("chevron-up", &["m18 15-6-6-6 6"]),
+    ("circle", &["M2 12a10 10 0 1 0 20 0a10 10 0 1 0 -20 0"]),
+    (
+        "copy",
+        &[
+            "M10 8h10a2 2 0 0 1 2 2v10a2 2 0 0 1 -2 2h-10a2 2 0 0 1 -2 -2v-10a2 2 0 0 1 2 -2z",
+            "M4 16c-1.1 0-2-.9-2-2V4c0-1.1.9-2 2-2h10c1.1 0 2 .9 2 2",
+        ],
+    ),
+    (
+        "download",
+        &[
+            "M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4",
+            "m7 10 5 5 5-5",
+            "M12 15V3",
+        ],
+    ),
+    (
+        "edit",
+        &[
+            "M21.174 6.812a1 1 0 0 0-3.986-3.987L3.842 16.174a2 2 0 0 0-.5.83l-1.321 4.352a.5.5 0 0 0 .623.622l4.353-1.32a2 2 0 0 0 .83-.497z",
+            "m15 5 4 4",
+        ],
+    ),
+    (
+        "external-link",
+        &[
+            "M15 3h6v6",
+            "M10 14 21 3",
+            "M18 13v6a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2V8a2 2 0 0 1 2-2h6",
+        ],
+    ),
+    (
+        "eye",
+        &[
+            "M2.062 12.348a1 1 0 0 1 0-.696 10.75 10.75 0 0 1 19.876 0 1 1 0 0 1 0 .696 10.75 10.75 0 0 1-19.876 0",
+            "M9 12a3 3 0 1 0 6 0a3 3 0 1 0 -6 0",
+        ],
+    ),
+    (
+        "file",
+        &[
+            "M15 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2V7Z",
+            "M14 2v4a2 2 0 0 0 2 2h4",
+        ],
+    ),
+    (
+        "filter",
+        &[
+            "M10 20a1 1 0 0 0 .553.895l2 1A1 1 0 0 0 14 21v-7a2 2 0 0 1 .517-1.341L21.74 4.67A1 1 0 0 0 21 3H3a1 1 0 0 0-.742 1.67l7.225 7.989A2 2 0 0 1 10 14z",
+        ],
+    ),
+    (
+        "folder",
+        &[
+            "M20 20a2 2 0 0 0 2-2V8a2 2 0 0 0-2-2h-7.9a2 2 0 0 1-1.69-.9L9.6 3.9A2 2 0 0 0 7.93 3H4a2 2 0 0 0-2 2v13a2 2 0 0 0 2 2Z",
+        ],
+    ),
+    (
+        "grip-vertical",
+        &[
+            "M8 12a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M8 5a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M8 19a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M14 12a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M14 5a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M14 19a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+        ],
+    ),
+    (
+        "heart",
+        &[
+            "M19 14c1.49-1.46 3-3.21 3-5.5A5.5 5.5 0 0 0 16.5 3c-1.76 0-3 .5-4.5 2-1.5-1.5-2.74-2-4.5-2A5.5 5.5 0 0 0 2 8.5c0 2.3 1.5 4.05 3 5.5l7 7Z",
+        ],
+    ),
+    (
+        "home",
+        &[
+            "M15 21v-8a1 1 0 0 0-1-1h-4a1 1 0 0 0-1 1v8",
+            "M3 10a2 2 0 0 1 .709-1.528l7-5.999a2 2 0 0 1 2.582 0l7 5.999A2 2 0 0 1 21 10v9a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2z",
+        ],
+    ),
+    (
+        "info",
+        &[
+            "M2 12a10 10 0 1 0 20 0a10 10 0 1 0 -20 0",
+            "M12 16v-4",
+            "M12 8h.01",
+        ],
+    ),
+    ("loader", &["M21 12a9 9 0 1 1-6.219-8.56"]),
+    (
+        "lock",
+        &[
+            "M5 11h14a2 2 0 0 1 2 2v7a2 2 0 0 1 -2 2h-14a2 2 0 0 1 -2 -2v-7a2 2 0 0 1 2 -2z",
+            "M7 11V7a5 5 0 0 1 10 0v4",
+        ],
+    ),
+    (
+        "log-out",
+        &[
+            "m16 17 5-5-5-5",
+            "M21 12H9",
+            "M9 21H5a2 2 0 0 1-2-2V5a2 2 0 0 1 2-2h4",
+        ],
+    ),
+    (
+        "mail",
+        &[
+            "M4 4h16a2 2 0 0 1 2 2v12a2 2 0 0 1 -2 2h-16a2 2 0 0 1 -2 -2v-12a2 2 0 0 1 2 -2z",
+            "m22 7-8.991 5.727a2 2 0 0 1-2.009 0L2 7",
+        ],
+    ),
+    (
+        "menu",
+        &[
+            "M4 12h16",
+            "M4 6h16",
+            "M4 18h16",
+        ],
+    ),
+    ("minus", &["M5 12h14"]),
+    ("moon", &["M12 3a6 6 0 0 0 9 9 9 9 0 1 1-9-9Z"]),
+    (
+        "more-horizontal",
+        &[
+            "M11 12a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M18 12a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M4 12a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+        ],
+    ),
+    (
+        "more-vertical",
+        &[
+            "M11 12a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M11 5a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+            "M11 19a1 1 0 1 0 2 0a1 1 0 1 0 -2 0",
+        ],
+    ),
+    (
+        "plus",
+        &[
+            "M5 12h14",
+            "M12 5v14",
+        ],
+    ),
+    (
+        "refresh-cw",
+        &[
+            "M3 12a9 9 0 0 1 9-9 9.75 9.75 0 0 1 6.74 2.74L21 8",
+            "M21 3v5h-5",
+            "M21 12a9 9 0 0 1-9 9 9.75 9.75 0 0 1-6.74-2.74L3 16",
+            "M8 16H3v5",
+        ],
+    ),
+    (
+        "search",
+        &[
+            "M3 11a8 8 0 1 0 16 0a8 8 0 1 0 -16 0",
+            "m21 21-4.3-4.3",
+        ],
+    ),
+    (
+        "settings",
+        &[
+            "M12.22 2h-.44a2 2 0 0 0-2 2v.18a2 2 0 0 1-1 1.73l-.43.25a2 2 0 0 1-2 0l-.15-.08a2 2 0 0 0-2.73.73l-.22.38a2 2 0 0 0 .73 2.73l.15.1a2 2 0 0 1 1 1.72v.51a2 2 0 0 1-1 1.74l-.15.09a2 2 0 0 0-.73 2.73l.22.38a2 2 0 0 0 2.73.73l.15-.08a2 2 0 0 1 2 0l.43.25a2 2 0 0 1 1 1.73V20a2 2 0 0 0 2 2h.44a2 2 0 0 0 2-2v-.18a2 2 0 0 1 1-1.73l.43-.25a2 2 0 0 1 2 0l.15.08a2 2 0 0 0 2.73-.73l.22-.39a2 2 0 0 0-.73-2.73l-.15-.08a2 2 0 0 1-1-1.74v-.5a2 2 0 0 1 1-1.74l.15-.09a2 2 0 0 0 .73-2.73l-.22-.38a2 2 0 0 0-2.73-.73l-.15.08a2 2 0 0 1-2 0l-.43-.25a2 2 0 0 1-1-1.73V4a2 2 0 0 0-2-2z",
+            "M9 12a3 3 0 1 0 6 0a3 3 0 1 0 -6 0",
+        ],
+    ),
+    (
+        "star",
+        &[
+            "M11.525 2.295a.53.53 0 0 1 .95 0l2.31 4.679a2.123 2.123 0 0 0 1.595 1.16l5.166.756a.53.53 0 0 1 .294.904l-3.736 3.638a2.123 2.123 0 0 0-.611 1.878l.882 5.14a.53.53 0 0 1-.771.56l-4.618-2.428a2.122 2.122 0 0 0-1.973 0L6.396 21.01a.53.53 0 0 1-.77-.56l.881-5.139a2.122 2.122 0 0 0-.611-1.879L2.16 9.795a.53.53 0 0 1 .294-.906l5.165-.755a2.122 2.122 0 0 0 1.597-1.16z",
+        ],
+    ),
+    (
+        "sun",
+        &[
+            "M8 12a4 4 0 1 0 8 0a4 4 0 1 0 -8 0",
+            "M12 2v2",
+            "M12 20v2",
+            "m4.93 4.93 1.41 1.41",
+            "m17.66 17.66 1.41 1.41",
+            "M2 12h2",
+            "M20 12h2",
+            "m6.34 17.66-1.41 1.41",
+            "m19.07 4.93-1.41 1.41",
+        ],
+    ),
+    (
+        "trash",
+        &[
+            "M3 6h18",
+            "M19 6v14c0 1-1 2-2 2H7c-1 0-2-1-2-2V6",
+            "M8 6V4c0-1 1-2 2-2h4c1 0 2 1 2 2v2",
+        ],
+    ),
+    (
+        "upload",
+        &[
+            "M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4",
+            "m17 8-5-5-5 5",
+            "M12 3v12",
+        ],
+    ),
+    (
+        "user",
+        &[
+            "M19 21v-2a4 4 0 0 0-4-4H9a4 4 0 0 0-4 4v2",
+            "M8 7a4 4 0 1 0 8 0a4 4 0 1 0 -8 0",
+        ],
+    ),
+    (
+        "x",
+        &[
+            "M18 6 6 18",
+            "m6 6 12 12",
+        ],
+    ),
+    (
+        "x-circle",
+        &[
+            "M2 12a10 10 0 1 0 20 0a10 10 0 1 0 -20 0",
+            "m15 9-6 6",
+            "m9 9 6 6",
+        ],
+    ),
+];
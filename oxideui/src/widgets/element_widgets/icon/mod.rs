@@ -0,0 +1,293 @@
+//! Vector icons
+//!
+//! `Icon` draws a named icon from the registry at any size, tinted with the
+//! current text color unless given one. The bundled Lucide set is always
+//! available; apps add their own with `register_icon_pack`, and can address
+//! a pack explicitly as `"pack:name"`.
+
+mod lucide;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Color, Matrix, Paint, Rect, RenderObject};
+use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::theming::ColorRole;
+use crate::ThemeProvider;
+
+/// How an icon's paths are painted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IconStyle {
+    /// Outline with the given width in view-box units
+    Stroke(f32),
+    Fill,
+}
+
+/// One icon: SVG path data in a square view box
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconData {
+    pub view_box: f32,
+    pub paths: Vec<Arc<str>>,
+    pub style: IconStyle,
+}
+
+impl IconData {
+    pub fn new(view_box: f32, paths: &[&str], style: IconStyle) -> Self {
+        Self {
+            view_box,
+            paths: paths.iter().map(|p| Arc::from(*p)).collect(),
+            style,
+        }
+    }
+
+    /// Icon from a single-color SVG document: every `<path d="…">` in its
+    /// view box, stroked when the root sets `fill="none"`
+    pub fn from_svg(svg: &str) -> Option<Self> {
+        let root = &svg[svg.find("<svg")?..];
+        let root_tag = &root[..root.find('>')?];
+        let view_box = svg_attribute(root_tag, "viewBox")
+            .and_then(|view_box| {
+                view_box
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|part| !part.is_empty())
+                    .nth(2)?
+                    .parse()
+                    .ok()
+            })
+            .or_else(|| svg_attribute(root_tag, "width")?.parse().ok())
+            .unwrap_or(24.0);
+        let paths: Vec<Arc<str>> = root
+            .split("<path")
+            .skip(1)
+            .filter_map(|tag| svg_attribute(&tag[..tag.find('>')?], "d").map(Arc::from))
+            .collect();
+        if paths.is_empty() {
+            return None;
+        }
+
+        let style = match svg_attribute(root_tag, "fill") {
+            Some("none") => IconStyle::Stroke(
+                svg_attribute(root_tag, "stroke-width")
+                    .and_then(|width| width.parse().ok())
+                    .unwrap_or(2.0),
+            ),
+            _ => IconStyle::Fill,
+        };
+        Some(Self { view_box, paths, style })
+    }
+
+    /// Paths scaled to `size`×`size` at the origin
+    pub fn render(&self, size: f32, color: Color) -> RenderObject {
+        let bounds = Rect::new(0.0, 0.0, self.view_box, self.view_box);
+        let paint = Paint {
+            color,
+            stroke_width: match self.style {
+                IconStyle::Stroke(width) => width,
+                IconStyle::Fill => 0.0,
+            },
+            anti_alias: true,
+        };
+        let paths = self
+            .paths
+            .iter()
+            .map(|data| RenderObject::Path {
+                data: data.clone(),
+                bounds,
+                paint: paint.clone(),
+                stroke: matches!(self.style, IconStyle::Stroke(_)),
+            })
+            .collect();
+
+        let scale = size / self.view_box;
+        RenderObject::transform(Matrix::scale(scale, scale), RenderObject::group(paths))
+    }
+}
+
+/// A named collection of icons
+#[derive(Debug, Clone, Default)]
+pub struct IconPack {
+    pub name: String,
+    icons: HashMap<String, IconData>,
+}
+
+impl IconPack {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            icons: HashMap::new(),
+        }
+    }
+
+    pub fn with_icon(mut self, name: impl Into<String>, icon: IconData) -> Self {
+        self.icons.insert(name.into(), icon);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&IconData> {
+        self.icons.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.icons.keys().map(String::as_str)
+    }
+
+    /// The bundled Lucide icons
+    pub fn lucide() -> Self {
+        let mut pack = IconPack::new("lucide");
+        for (name, paths) in lucide::ICONS {
+            pack.icons
+                .insert(name.to_string(), IconData::new(24.0, paths, IconStyle::Stroke(2.0)));
+        }
+        pack
+    }
+}
+
+/// Value of `name="…"` in an SVG start tag
+fn svg_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{}=\"", name);
+    let (index, _) = tag
+        .match_indices(&pattern)
+        .find(|(index, _)| tag[..*index].ends_with(char::is_whitespace))?;
+    let value = &tag[index + pattern.len()..];
+    Some(&value[..value.find('"')?])
+}
+
+/// Packs in lookup order: most recently registered first, Lucide last
+fn registry() -> &'static RwLock<Vec<Arc<IconPack>>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Arc<IconPack>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(vec![Arc::new(IconPack::lucide())]))
+}
+
+/// Make a pack's icons available to every `Icon`; a pack with the same name
+/// is replaced
+pub fn register_icon_pack(pack: IconPack) {
+    let mut packs = registry().write();
+    packs.retain(|p| p.name != pack.name);
+    packs.insert(0, Arc::new(pack));
+}
+
+/// Find `name` or `pack:name` in the registered packs
+pub fn lookup_icon(name: &str) -> Option<IconData> {
+    let packs = registry().read();
+    match name.split_once(':') {
+        Some((pack, icon)) => packs
+            .iter()
+            .find(|p| p.name == pack)
+            .and_then(|p| p.get(icon))
+            .cloned(),
+        None => packs.iter().find_map(|p| p.get(name)).cloned(),
+    }
+}
+
+#[derive(Clone)]
+pub struct Icon {
+    pub name: String,
+    pub size: f32,
+    pub color: Option<Color>,
+    /// Theme color role used when no explicit color is set
+    pub tint: Option<ColorRole>,
+    pub tooltip: Option<String>,
+    key: Option<WidgetKey>,
+}
+
+impl Icon {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            size: 16.0,
+            color: None,
+            tint: None,
+            tooltip: None,
+            key: None,
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Tint with a theme color role, e.g. `ColorRole::TextMuted`
+    pub fn with_tint(mut self, role: ColorRole) -> Self {
+        self.tint = Some(role);
+        self
+    }
+
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Render at the origin in `color`, for widgets that draw icons inline
+    pub fn to_render_object(&self, color: Color) -> RenderObject {
+        match lookup_icon(&self.name) {
+            Some(icon) => icon.render(self.size, self.color.unwrap_or(color)),
+            None => {
+                eprintln!("⚠️ Unknown icon \"{}\"", self.name);
+                RenderObject::None
+            }
+        }
+    }
+}
+
+impl StatelessWidget for Icon {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let color = match self.tint {
+            Some(role) => ctx.theme().role(role),
+            None => ctx.text_style().color,
+        };
+        WidgetNode::Leaf(self.to_render_object(color))
+    }
+}
+
+impl Widget for Icon {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_pack_lookup() {
+        assert!(lookup_icon("check").is_some());
+        assert!(lookup_icon("lucide:chevron-down").is_some());
+        assert!(lookup_icon("no-such-icon").is_none());
+
+        let square = IconData::new(16.0, &["M0 0h16v16H0z"], IconStyle::Fill);
+        register_icon_pack(IconPack::new("test-pack").with_icon("heart", square.clone()));
+
+        // Newer packs shadow bundled names; the bundled icon stays addressable
+        assert_eq!(lookup_icon("heart"), Some(square.clone()));
+        assert_eq!(lookup_icon("test-pack:heart"), Some(square));
+        assert_eq!(lookup_icon("lucide:heart").map(|i| i.view_box), Some(24.0));
+    }
+}
@@ -1,40 +1,69 @@
 use std::any::Any;
 use std::sync::Arc;
+use crate::assets::Assets;
 use crate::core::context::BuildContext;
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
 use crate::core::render_object::{Color, Rect, RenderObject};
+use crate::state_management::async_state::AsyncValue;
+use crate::widgets::element_widgets::image_cache::ImageCache;
 use crate::ThemeProvider;
 
+pub use crate::core::render_object::ImageFit;
+
+type PlaceholderBuilder = Arc<dyn Fn(&BuildContext) -> Box<dyn Widget> + Send + Sync>;
+type ErrorBuilder = Arc<dyn Fn(&BuildContext, &str) -> Box<dyn Widget> + Send + Sync>;
+
+/// Where an `Image` loads its bytes from
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageSource {
+    File(String),
+    /// Registered with `Assets`, by logical path
+    Asset(String),
+    /// Fetched over HTTP(S); needs the `http` feature
+    Url(String),
+}
+
 #[derive(Clone)]
 pub struct Image {
-    pub path: String,
+    pub source: ImageSource,
     pub width: Option<f32>,
     pub height: Option<f32>,
     pub fit: ImageFit,
     pub alt_text: String,
     pub tooltip: Option<String>,
     pub on_click: Option<Arc<dyn Fn() + Send + Sync>>,
+    placeholder: Option<PlaceholderBuilder>,
+    error: Option<ErrorBuilder>,
     key: Option<WidgetKey>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum ImageFit {
-    Fill,
-    Contain,
-    Cover,
-    ScaleDown,
-}
-
 impl Image {
     pub fn new(path: impl Into<String>) -> Self {
+        Self::from_source(ImageSource::File(path.into()))
+    }
+
+    /// Image downloaded from `url`, shared with every other `Image` showing
+    /// it and cached on disk between runs
+    pub fn network(url: impl Into<String>) -> Self {
+        Self::from_source(ImageSource::Url(url.into()))
+    }
+
+    /// Image registered with `Assets` under `path`, e.g. `"logo.png"`
+    pub fn asset(path: impl Into<String>) -> Self {
+        Self::from_source(ImageSource::Asset(path.into()))
+    }
+
+    pub fn from_source(source: ImageSource) -> Self {
         Self {
-            path: path.into(),
+            source,
             width: None,
             height: None,
             fit: ImageFit::Contain,
             alt_text: String::new(),
             tooltip: None,
             on_click: None,
+            placeholder: None,
+            error: None,
             key: None,
         }
     }
@@ -68,19 +97,33 @@ impl Image {
         self
     }
 
+    /// Widget shown while the image loads
+    pub fn with_placeholder<F>(mut self, placeholder: F) -> Self
+    where
+        F: Fn(&BuildContext) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        self.placeholder = Some(Arc::new(placeholder));
+        self
+    }
+
+    /// Widget shown when the image can't be loaded, given the reason
+    pub fn with_error<F>(mut self, error: F) -> Self
+    where
+        F: Fn(&BuildContext, &str) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        self.error = Some(Arc::new(error));
+        self
+    }
+
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
     }
 }
 
-impl StatelessWidget for Image {
-    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
-        let width = self.width.unwrap_or(ctx.constraints.max_width);
-        let height = self.height.unwrap_or(ctx.constraints.max_height);
-
-        // For now, draw a placeholder rectangle
-        // In a real implementation, we would load and decode the image
+impl Image {
+    /// Grey box with a border and `label`, until there is a picture to show
+    fn placeholder_box(&self, ctx: &BuildContext, width: f32, height: f32, label: &str) -> RenderObject {
         let placeholder_color = Color::from_hex(0xE5E7EB);
         let border_color = Color::from_hex(0xD1D5DB);
 
@@ -110,21 +153,55 @@ impl StatelessWidget for Image {
             border_color,
         ));
 
-        // "Image" text
         let theme = ctx.theme();
         children.push(RenderObject::text(
-            "📷 Image".to_string(),
+            label.to_string(),
             crate::core::render_object::TextStyle {
                 font_family: theme.font_sans.clone(),
                 font_size: 14.0,
                 color: Color::from_hex(0x6B7280),
                 bold: false,
                 italic: true,
+                ..Default::default()
             },
             crate::core::render_object::Point::new(width / 2.0 - 30.0, height / 2.0 + 5.0),
         ));
 
-        WidgetNode::Leaf(RenderObject::group(children))
+        RenderObject::group(children)
+    }
+}
+
+impl StatelessWidget for Image {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let width = self.width.unwrap_or(ctx.constraints.max_width);
+        let height = self.height.unwrap_or(ctx.constraints.max_height);
+
+        let value = match &self.source {
+            ImageSource::File(path) => ImageCache::global().load_file(path),
+            ImageSource::Asset(path) => match Assets::global().image(path) {
+                Some(data) => AsyncValue::Ready(data),
+                None => AsyncValue::Failed(format!("No asset at \"{}\"", path)),
+            },
+            ImageSource::Url(url) => ImageCache::global().load_url(url, ctx),
+        };
+        match value {
+            AsyncValue::Ready(data) => WidgetNode::Leaf(RenderObject::Image {
+                size: crate::layout::Size::new(width, height),
+                data: Some(data),
+                fit: self.fit,
+            }),
+            AsyncValue::Loading => match &self.placeholder {
+                Some(placeholder) => ctx.build_child(placeholder(ctx).as_ref()),
+                None => WidgetNode::Leaf(self.placeholder_box(ctx, width, height, "📷 Image")),
+            },
+            AsyncValue::Failed(reason) => match &self.error {
+                Some(error) => ctx.build_child(error(ctx, &reason).as_ref()),
+                None => {
+                    let label = if self.alt_text.is_empty() { "⚠ Image" } else { self.alt_text.as_str() };
+                    WidgetNode::Leaf(self.placeholder_box(ctx, width, height, label))
+                }
+            },
+        }
     }
 }
 
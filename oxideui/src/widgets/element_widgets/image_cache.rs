@@ -0,0 +1,437 @@
+//! Loading and caching the bytes behind `Image`
+//!
+//! Files are read the first time they are shown. With the `http` feature,
+//! URLs are fetched on the task runtime: concurrent requests for one URL
+//! share a single download, responses are kept in memory and on disk, and a
+//! copy found on disk is shown straight away while it is revalidated with
+//! its ETag. Failures are remembered for a while, then retried.
+
+use std::num::NonZeroUsize;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use web_time::Instant;
+
+use crate::core::render_object::ImageData;
+use crate::state_management::async_state::AsyncValue;
+
+/// Loaded or failed sources kept in memory
+const MEMORY_CACHE_SIZE: usize = 256;
+/// How long a failed load is reported before the next build tries again
+const RETRY_FAILED_AFTER: Duration = Duration::from_secs(30);
+
+struct CacheEntry {
+    value: AsyncValue<ImageData>,
+    failed_at: Option<Instant>,
+}
+
+/// Process-wide cache shared by every `Image`
+pub struct ImageCache {
+    memory: Mutex<LruCache<String, CacheEntry>>,
+    #[cfg(feature = "http")]
+    network: network::NetworkState,
+}
+
+impl ImageCache {
+    pub fn global() -> &'static ImageCache {
+        static CACHE: OnceLock<ImageCache> = OnceLock::new();
+        CACHE.get_or_init(ImageCache::new)
+    }
+
+    fn new() -> Self {
+        Self {
+            memory: Mutex::new(LruCache::new(NonZeroUsize::new(MEMORY_CACHE_SIZE).unwrap())),
+            #[cfg(feature = "http")]
+            network: network::NetworkState::default(),
+        }
+    }
+
+    /// Forget `source` so the next build loads it again, e.g. to retry a
+    /// failed download
+    pub fn evict(&self, source: &str) {
+        self.memory.lock().pop(source);
+    }
+
+    /// Forget every loaded image; the disk cache is kept
+    pub fn clear_memory(&self) {
+        self.memory.lock().clear();
+    }
+
+    /// What `source` last loaded as, unless it failed long enough ago to
+    /// try again
+    fn cached(&self, source: &str) -> Option<AsyncValue<ImageData>> {
+        let mut memory = self.memory.lock();
+        let entry = memory.get(source)?;
+        if entry.failed_at.is_some_and(|at| at.elapsed() >= RETRY_FAILED_AFTER) {
+            memory.pop(source);
+            return None;
+        }
+        Some(entry.value.clone())
+    }
+
+    fn store(&self, source: &str, value: AsyncValue<ImageData>) {
+        let failed_at = matches!(value, AsyncValue::Failed(_)).then(Instant::now);
+        self.memory.lock().put(source.to_string(), CacheEntry { value, failed_at });
+    }
+
+    pub(crate) fn load_file(&self, path: &str) -> AsyncValue<ImageData> {
+        if let Some(value) = self.cached(path) {
+            return value;
+        }
+        let value = match std::fs::read(path) {
+            Ok(bytes) => AsyncValue::Ready(ImageData::from_encoded(bytes)),
+            Err(e) => AsyncValue::Failed(format!("Failed to read {}: {}", path, e)),
+        };
+        self.store(path, value.clone());
+        value
+    }
+
+    /// The image at `url` if it has loaded, otherwise start fetching it on
+    /// `ctx`'s task runtime and report it as loading
+    #[cfg(feature = "http")]
+    pub(crate) fn load_url(&'static self, url: &str, ctx: &crate::core::context::BuildContext) -> AsyncValue<ImageData> {
+        if let Some(value) = self.cached(url) {
+            return value;
+        }
+        if self.network.begin(url, ctx.ui_handle()) && ctx.spawn(network::fetch(self, url.to_string())).is_none() {
+            // Offscreen builds have no task runtime; the image stays loading
+            self.network.finish(url);
+        }
+        AsyncValue::Loading
+    }
+
+    #[cfg(not(feature = "http"))]
+    pub(crate) fn load_url(&'static self, url: &str, _ctx: &crate::core::context::BuildContext) -> AsyncValue<ImageData> {
+        AsyncValue::Failed(format!("Loading {} needs the `http` feature", url))
+    }
+
+    /// Where downloaded images are kept between runs; `None` keeps them in
+    /// memory only. Defaults to `oxideui/images` in the user's cache
+    /// directory, created readable by the user alone.
+    #[cfg(feature = "http")]
+    pub fn set_disk_dir(&self, dir: Option<std::path::PathBuf>) {
+        *self.network.disk_dir.lock() = dir;
+    }
+}
+
+#[cfg(feature = "http")]
+mod network {
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    use anyhow::{Context, Result};
+    use parking_lot::Mutex;
+
+    use super::ImageCache;
+    use crate::core::render_object::ImageData;
+    use crate::runtime::UiHandle;
+    use crate::state_management::async_state::AsyncValue;
+
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+    /// Largest the disk cache grows before the least recently used images
+    /// are deleted
+    const DISK_CACHE_BYTES: u64 = 128 * 1024 * 1024;
+
+    pub(super) struct NetworkState {
+        /// URLs being downloaded, with the handle to wake when they land
+        in_flight: Mutex<HashMap<String, Option<UiHandle>>>,
+        pub(super) disk_dir: Mutex<Option<PathBuf>>,
+    }
+
+    impl Default for NetworkState {
+        fn default() -> Self {
+            Self {
+                in_flight: Mutex::new(HashMap::new()),
+                disk_dir: Mutex::new(default_disk_dir()),
+            }
+        }
+    }
+
+    impl NetworkState {
+        /// Note that `url` is wanted; true if the caller should start the
+        /// download, false if one is already running
+        pub(super) fn begin(&self, url: &str, waiter: Option<UiHandle>) -> bool {
+            let mut in_flight = self.in_flight.lock();
+            match in_flight.get_mut(url) {
+                Some(existing) => {
+                    if existing.is_none() {
+                        *existing = waiter;
+                    }
+                    false
+                }
+                None => {
+                    in_flight.insert(url.to_string(), waiter);
+                    true
+                }
+            }
+        }
+
+        /// The download of `url` is over; wake the UI so it rebuilds
+        pub(super) fn finish(&self, url: &str) {
+            if let Some(Some(ui)) = self.in_flight.lock().remove(url) {
+                let _ = ui.run_on_ui(|| {});
+            }
+        }
+
+        fn wake(&self, url: &str) {
+            if let Some(Some(ui)) = self.in_flight.lock().get(url) {
+                let _ = ui.run_on_ui(|| {});
+            }
+        }
+
+        fn disk(&self) -> Option<DiskCache> {
+            self.disk_dir.lock().clone().map(|dir| DiskCache::new(dir, DISK_CACHE_BYTES))
+        }
+    }
+
+    /// The user's own cache directory: `$XDG_CACHE_HOME` or `~/.cache` on
+    /// Linux, `~/Library/Caches` on macOS, `%LOCALAPPDATA%` on Windows
+    fn default_disk_dir() -> Option<PathBuf> {
+        let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+        #[cfg(windows)]
+        let base = var("LOCALAPPDATA");
+        #[cfg(target_os = "macos")]
+        let base = var("HOME").map(|home| home.join("Library").join("Caches"));
+        #[cfg(not(any(windows, target_os = "macos")))]
+        let base = var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache")));
+        base.map(|base| base.join("oxideui").join("images"))
+    }
+
+    /// Ends the download when the task completes or is cancelled, so an
+    /// aborted fetch is retried by the next build that wants it
+    struct InFlight<'a> {
+        cache: &'a ImageCache,
+        url: &'a str,
+    }
+
+    impl Drop for InFlight<'_> {
+        fn drop(&mut self) {
+            self.cache.network.finish(self.url);
+        }
+    }
+
+    pub(super) async fn fetch(cache: &'static ImageCache, url: String) {
+        let _in_flight = InFlight { cache, url: &url };
+        let disk = cache.network.disk();
+
+        let stale = disk.as_ref().and_then(|disk| disk.read(&url));
+        if let Some(entry) = &stale {
+            cache.store(&url, AsyncValue::Ready(ImageData::from_encoded(entry.body.clone())));
+            cache.network.wake(&url);
+        }
+
+        match download(&url, stale.as_ref().and_then(|entry| entry.etag.as_deref())).await {
+            Ok(Download::NotModified) => {}
+            Ok(Download::Body { body, etag }) => {
+                if let Some(disk) = &disk {
+                    if let Err(e) = disk.write(&url, &body, etag.as_deref()) {
+                        eprintln!("⚠️ Failed to cache {}: {:#}", url, e);
+                    }
+                }
+                cache.store(&url, AsyncValue::Ready(ImageData::from_encoded(body)));
+            }
+            // Keep showing the copy from disk
+            Err(_) if stale.is_some() => {}
+            Err(e) => cache.store(&url, AsyncValue::Failed(format!("{:#}", e))),
+        }
+    }
+
+    enum Download {
+        NotModified,
+        Body { body: Vec<u8>, etag: Option<String> },
+    }
+
+    fn client() -> &'static reqwest::Client {
+        static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+        CLIENT.get_or_init(|| reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build().unwrap_or_default())
+    }
+
+    async fn download(url: &str, etag: Option<&str>) -> Result<Download> {
+        let mut request = client().get(url);
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await.with_context(|| format!("Failed to fetch {}", url))?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Download::NotModified);
+        }
+
+        let response = response.error_for_status().with_context(|| format!("Failed to fetch {}", url))?;
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.bytes().await.with_context(|| format!("Failed to download {}", url))?;
+        Ok(Download::Body { body: body.to_vec(), etag })
+    }
+
+    struct DiskEntry {
+        body: Vec<u8>,
+        etag: Option<String>,
+    }
+
+    /// One `<hash>.body` file per URL, with its ETag in `<hash>.etag`. The
+    /// bodies' modification times record when they were last used.
+    struct DiskCache {
+        dir: PathBuf,
+        max_bytes: u64,
+    }
+
+    impl DiskCache {
+        fn new(dir: PathBuf, max_bytes: u64) -> Self {
+            Self { dir, max_bytes }
+        }
+
+        fn path(&self, url: &str, extension: &str) -> PathBuf {
+            self.dir.join(format!("{:016x}.{}", fnv1a(url), extension))
+        }
+
+        fn read(&self, url: &str) -> Option<DiskEntry> {
+            let path = self.path(url, "body");
+            let body = std::fs::read(&path).ok()?;
+            let etag = std::fs::read_to_string(self.path(url, "etag")).ok();
+            if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+                let _ = file.set_modified(std::time::SystemTime::now());
+            }
+            Some(DiskEntry { body, etag })
+        }
+
+        fn write(&self, url: &str, body: &[u8], etag: Option<&str>) -> Result<()> {
+            create_private_dir(&self.dir)?;
+            write_file(&self.path(url, "body"), body)?;
+            let etag_path = self.path(url, "etag");
+            match etag {
+                Some(etag) => write_file(&etag_path, etag.as_bytes())?,
+                None => {
+                    let _ = std::fs::remove_file(etag_path);
+                }
+            }
+            self.trim();
+            Ok(())
+        }
+
+        /// Delete the least recently used images until the cache fits in
+        /// `max_bytes`
+        fn trim(&self) {
+            let Ok(entries) = std::fs::read_dir(&self.dir) else {
+                return;
+            };
+            let mut bodies: Vec<_> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|extension| extension == "body"))
+                .filter_map(|path| {
+                    let metadata = std::fs::metadata(&path).ok()?;
+                    Some((metadata.modified().ok()?, metadata.len(), path))
+                })
+                .collect();
+            bodies.sort_by(|a, b| b.0.cmp(&a.0));
+
+            let mut total = 0;
+            for (_, len, path) in bodies {
+                total += len;
+                if total > self.max_bytes {
+                    let _ = std::fs::remove_file(path.with_extension("etag"));
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+        }
+    }
+
+    /// Create `dir` readable and writable by the current user only
+    fn create_private_dir(dir: &Path) -> Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            // It may have existed already, from an older version
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+                .with_context(|| format!("Failed to restrict {}", dir.display()))
+        }
+        #[cfg(not(unix))]
+        {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))
+        }
+    }
+
+    fn write_file(path: &Path, contents: &[u8]) -> Result<()> {
+        std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Stable across runs and Rust versions, unlike `DefaultHasher`
+    fn fnv1a(text: &str) -> u64 {
+        text.bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_downloads_are_shared_and_cached_on_disk() {
+            let state = NetworkState::default();
+            let url = "https://example.com/logo.png";
+            assert!(state.begin(url, None));
+            assert!(!state.begin(url, None));
+            state.finish(url);
+            assert!(state.begin(url, None));
+
+            let dir = std::env::temp_dir().join(format!("oxideui-image-cache-test-{}", std::process::id()));
+            let disk = DiskCache::new(dir.clone(), 6);
+            assert!(disk.read(url).is_none());
+            disk.write(url, b"png", Some("\"v1\"")).unwrap();
+            let entry = disk.read(url).unwrap();
+            assert_eq!(entry.body, b"png");
+            assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                assert_eq!(std::fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+            }
+
+            // A response without an ETag drops the stale one
+            disk.write(url, b"png2", None).unwrap();
+            assert_eq!(disk.read(url).unwrap().etag, None);
+
+            // Past six bytes, the image used longest ago is deleted
+            std::thread::sleep(Duration::from_millis(20));
+            disk.write("https://example.com/icon.png", b"ico", None).unwrap();
+            assert!(disk.read(url).is_none());
+            assert!(disk.read("https://example.com/icon.png").is_some());
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failures_are_retried_after_a_while() {
+        let cache = ImageCache::new();
+        let path = std::env::temp_dir().join(format!("oxideui-image-retry-{}.png", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        assert!(matches!(cache.load_file(path_str), AsyncValue::Failed(_)));
+
+        std::fs::write(&path, b"png").unwrap();
+        // Still remembered as failed...
+        assert!(matches!(cache.load_file(path_str), AsyncValue::Failed(_)));
+        // ...until it failed long enough ago
+        cache.memory.lock().get_mut(path_str).unwrap().failed_at = Some(Instant::now() - RETRY_FAILED_AFTER);
+        assert!(matches!(cache.load_file(path_str), AsyncValue::Ready(_)));
+        let _ = std::fs::remove_file(path);
+    }
+}
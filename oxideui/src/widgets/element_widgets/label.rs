@@ -1,8 +1,9 @@
 use std::any::Any;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Color, Point, RenderObject, TextStyle};
+use crate::core::render_object::Color;
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
-use crate::ThemeProvider;
+use crate::render::text::TextOverflow;
+use crate::widgets::basic::layout_text;
 
 #[derive(Clone)]
 pub struct Label {
@@ -11,6 +12,10 @@ pub struct Label {
     pub size: Option<f32>,
     pub color: Option<Color>,
     pub tooltip: Option<String>,
+    /// Cut-off applied when the text is wider than its constraints
+    pub overflow: Option<TextOverflow>,
+    /// Wrap onto at most this many lines; unset keeps the label on one line
+    pub max_lines: Option<usize>,
     key: Option<WidgetKey>,
 }
 
@@ -22,6 +27,8 @@ impl Label {
             size: None,
             color: None,
             tooltip: None,
+            overflow: None,
+            max_lines: None,
             key: None,
         }
     }
@@ -46,6 +53,16 @@ impl Label {
         self
     }
 
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = Some(overflow);
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
@@ -54,23 +71,21 @@ impl Label {
 
 impl StatelessWidget for Label {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
-        let theme = ctx.theme();
-        let text_color = self.color.unwrap_or_else(|| {
-            Color::from_hex(if theme.is_dark { 0xEEEEEE } else { 0x111111 })
-        });
-
-        let font_size = self.size.unwrap_or(14.0);
+        let mut style = ctx.text_style();
+        if let Some(color) = self.color {
+            style.color = color;
+        }
+        if let Some(size) = self.size {
+            style.font_size = size;
+        }
+        style.bold |= self.bold;
 
-        WidgetNode::Leaf(RenderObject::text(
-            self.text.clone(),
-            TextStyle {
-                font_family: theme.font_sans.clone(),
-                font_size,
-                color: text_color,
-                bold: self.bold,
-                italic: false,
-            },
-            Point::ZERO,
+        WidgetNode::Leaf(layout_text(
+            &self.text,
+            style,
+            ctx.constraints.max_width,
+            self.max_lines,
+            self.overflow,
         ))
     }
 }
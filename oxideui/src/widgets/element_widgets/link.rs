@@ -0,0 +1,175 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventPhase, EventResult, MouseButton, UiEvent};
+use crate::core::AccessibilityRole;
+use crate::core::render_object::{Color, Point, RenderObject, TextDecoration};
+use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::platform::window::{CursorShape, WindowController};
+use crate::platform::open_url;
+use crate::ThemeProvider;
+
+type OnClick = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Underlined text that opens a URL when clicked
+pub struct Link {
+    pub text: String,
+    pub url: String,
+    /// Replaces opening the URL; receives the URL
+    pub on_click: Option<OnClick>,
+    pub color: Option<Color>,
+    pub size: Option<f32>,
+    pub underline: bool,
+    pub tooltip: Option<String>,
+    /// Set at build so `handle_event` can change the cursor
+    window: Arc<RwLock<Option<WindowController>>>,
+    key: Option<WidgetKey>,
+}
+
+impl Clone for Link {
+    fn clone(&self) -> Self {
+        Self {
+            text: self.text.clone(),
+            url: self.url.clone(),
+            on_click: self.on_click.clone(),
+            color: self.color,
+            size: self.size,
+            underline: self.underline,
+            tooltip: self.tooltip.clone(),
+            window: self.window.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl Link {
+    pub fn new(text: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            url: url.into(),
+            on_click: None,
+            color: None,
+            size: None,
+            underline: true,
+            tooltip: None,
+            window: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    /// Handle the click in-app instead of opening the URL
+    pub fn on_click<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_click = Some(Arc::new(f));
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Draw without the underline
+    pub fn without_underline(mut self) -> Self {
+        self.underline = false;
+        self
+    }
+
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Role reported to assistive technology
+    pub fn accessibility_role(&self) -> AccessibilityRole {
+        AccessibilityRole::Link
+    }
+
+    /// Name reported to assistive technology
+    pub fn accessibility_label(&self) -> &str {
+        &self.text
+    }
+
+    fn activate(&self) {
+        match &self.on_click {
+            Some(on_click) => on_click(&self.url),
+            None => {
+                if let Err(e) = open_url(&self.url) {
+                    eprintln!("⚠️ {:#}", e);
+                }
+            }
+        }
+    }
+}
+
+impl StatelessWidget for Link {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        *self.window.write() = ctx.window().cloned();
+
+        let mut style = ctx.text_style();
+        style.color = self.color.unwrap_or(ctx.theme().primary);
+        if let Some(size) = self.size {
+            style.font_size = size;
+        }
+        if self.underline {
+            style.decoration = TextDecoration::underline();
+        }
+
+        WidgetNode::Leaf(RenderObject::text(self.text.clone(), style, Point::ZERO))
+    }
+}
+
+impl Widget for Link {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if context.phase != EventPhase::AtTarget {
+            return EventResult::Unhandled;
+        }
+        match event {
+            UiEvent::PointerMove { .. } => {
+                if let Some(window) = self.window.read().as_ref() {
+                    window.set_cursor(CursorShape::Pointer);
+                }
+                EventResult::Handled
+            }
+            UiEvent::PointerUp {
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.activate();
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
@@ -1,9 +1,14 @@
 pub mod button;
 pub mod headings;
+pub mod icon;
 pub mod label;
+pub mod link;
 pub mod text_input;
 pub mod checkbox;
+pub mod code_editor;
+pub mod form;
 pub mod image;
+pub mod image_cache;
 pub mod table;
 pub mod textarea;
 pub mod tooltip;
@@ -11,11 +16,19 @@ pub mod video;
 
 pub use button::Button;
 pub use headings::{Heading, h1, h2, h3, h4, h5, h6};
+pub use icon::{Icon, IconData, IconPack, IconStyle, lookup_icon, register_icon_pack};
 pub use label::Label;
+pub use link::Link;
 pub use text_input::TextInput;
 pub use checkbox::Checkbox;
+pub use code_editor::{
+    CodeEditor, CodeEditorController, CursorMove, EditorBuffer, KeywordHighlighter, SyntaxHighlighter, TabHandling,
+    TextChange, TextPosition, Token, TokenKind,
+};
+pub use form::{Form, FormController, FormField, Validator, validators};
 pub use textarea::Textarea;
 pub use tooltip::{Tooltip, TooltipPlacement};
-pub use image::{Image, ImageFit};
-pub use table::{Table, TableColumn, TableRow, ColumnWidth, TableAlign, SortDirection};
+pub use image::{Image, ImageFit, ImageSource};
+pub use image_cache::ImageCache;
+pub use table::{Table, TableColumn, TableRow, ColumnWidth, TableAlign, SortDirection, CellEditor};
 pub use video::Video;
\ No newline at end of file
@@ -1,8 +1,21 @@
 use std::any::Any;
+use std::path::Path;
 use std::sync::Arc;
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use winit::keyboard::KeyCode;
 use crate::core::context::BuildContext;
-use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
+use crate::core::render_object::{Matrix, Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::debug_hash;
+use crate::render::text::{text_width, TextOverflow};
+use crate::theming::Density;
+use crate::widgets::basic::layout_text;
+use crate::core::{AccessibilityRole, FocusRing, LiveRegion, SemanticsData};
+use crate::platform::set_clipboard_text;
+use crate::theming::ColorRole;
+use crate::widgets::keyboard_nav::{step_index, ListNavigator, NavAxis};
+use crate::widgets::selection::SelectionController;
 use crate::ThemeProvider;
 
 #[derive(Clone)]
@@ -13,25 +26,80 @@ pub struct Table {
     pub striped: bool,
     pub hoverable: bool,
     pub bordered: bool,
+    /// Force compact rows regardless of the theme density
     pub compact: bool,
     pub sortable: bool,
+    /// Leading columns that stay in view while the rest scroll sideways
+    pub frozen_columns: usize,
     pub on_row_click: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    /// Called when a row is double-clicked outside an editable cell, or
+    /// Enter is pressed on it
+    pub on_row_activate: Option<Arc<dyn Fn(usize) + Send + Sync>>,
     pub on_sort: Option<Arc<dyn Fn(usize, SortDirection) + Send + Sync>>,
+    /// Called with the row, column and new value when an edit commits
+    pub on_cell_edited: Option<Arc<dyn Fn(usize, usize, String) + Send + Sync>>,
+    /// Density of the last build, for hit-testing rows
+    density: Arc<RwLock<Density>>,
+    /// Keyboard-highlighted row, shared across rebuilds
+    nav: Arc<RwLock<ListNavigator>>,
+    /// Focused column and scroll position, shared across rebuilds
+    cursor: Arc<RwLock<TableCursor>>,
+    /// Selected rows, updated by clicks and the arrow keys
+    pub selection: Option<SelectionController>,
     key: Option<WidgetKey>,
 }
 
+/// The focused cell is in the navigator's highlighted row and this column
+#[derive(Debug, Default)]
+struct TableCursor {
+    column: usize,
+    /// First body row in view when the rows don't all fit
+    first_row: usize,
+    /// Body rows that fit in the last build, for Page Up/Down
+    page_rows: usize,
+    /// How far the unfrozen columns are scrolled left
+    scroll_x: f32,
+    /// Width and column widths of the last build, for hit-testing
+    view_width: f32,
+    widths: Vec<f32>,
+    /// Cell being edited in place
+    editing: Option<CellEdit>,
+}
+
+#[derive(Debug)]
+struct CellEdit {
+    row: usize,
+    column: usize,
+    value: String,
+}
+
 #[derive(Clone)]
 pub struct TableColumn {
     pub label: String,
     pub width: ColumnWidth,
     pub align: TableAlign,
     pub sortable: bool,
+    /// Double-click or F2 edits this column's cells
+    pub editor: Option<CellEditor>,
+}
+
+/// How a column's cells are edited in place
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellEditor {
+    Text,
+    /// Accepts digits, a sign and a decimal point
+    Number,
+    /// One of the options, picked with the up and down arrows
+    Dropdown(Vec<String>),
 }
 
 #[derive(Clone)]
 pub struct TableRow {
     pub cells: Vec<String>,
     pub selectable: bool,
+    /// Identifies the row across sorting and filtering, so the selection
+    /// stays on it; rows without one are identified by their cells
+    pub id: Option<u64>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -66,8 +134,15 @@ impl Table {
             bordered: true,
             compact: false,
             sortable: false,
+            frozen_columns: 0,
             on_row_click: None,
+            on_row_activate: None,
             on_sort: None,
+            on_cell_edited: None,
+            density: Arc::new(RwLock::new(Density::default())),
+            nav: Arc::new(RwLock::new(ListNavigator::new())),
+            cursor: Arc::new(RwLock::new(TableCursor::default())),
+            selection: None,
             key: None,
         }
     }
@@ -107,6 +182,13 @@ impl Table {
         self
     }
 
+    /// Keep the first `count` columns in view when the table scrolls
+    /// sideways
+    pub fn with_frozen_columns(mut self, count: usize) -> Self {
+        self.frozen_columns = count;
+        self
+    }
+
     pub fn with_width(mut self, width: f32) -> Self {
         self.width = Some(width);
         self
@@ -120,6 +202,14 @@ impl Table {
         self
     }
 
+    pub fn with_on_row_activate<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.on_row_activate = Some(Arc::new(callback));
+        self
+    }
+
     pub fn with_on_sort<F>(mut self, callback: F) -> Self
     where
         F: Fn(usize, SortDirection) + Send + Sync + 'static,
@@ -128,6 +218,23 @@ impl Table {
         self
     }
 
+    /// Edits commit on Enter or when the table loses focus; the app
+    /// updates its rows with the new value
+    pub fn with_on_cell_edited<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, usize, String) + Send + Sync + 'static,
+    {
+        self.on_cell_edited = Some(Arc::new(callback));
+        self
+    }
+
+    /// Let clicks and the arrow keys select rows, with Ctrl and Shift in
+    /// multiple selection mode
+    pub fn with_selection(mut self, selection: SelectionController) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
@@ -173,20 +280,224 @@ impl Table {
 
         widths
     }
+
+    /// Focused cell as (row, column), once a row has been highlighted
+    pub fn focused_cell(&self) -> Option<(usize, usize)> {
+        let row = self.nav.read().highlighted.filter(|row| *row < self.rows.len())?;
+        Some((row, self.cursor.read().column.min(self.columns.len().saturating_sub(1))))
+    }
+
+    /// What a screen reader says for the focused cell, e.g.
+    /// "Row 2 of 10, Name: Ada"
+    fn focused_cell_announcement(&self) -> Option<String> {
+        let (row, column) = self.focused_cell()?;
+        let label = self.columns.get(column).map_or("", |column| column.label.as_str());
+        let value = self.rows[row].cells.get(column).map_or("", String::as_str);
+        Some(format!("Row {} of {}, {}: {}", row + 1, self.rows.len(), label, value))
+    }
+
+    /// The header and rows as CSV, in the order shown. Rows are whatever
+    /// the app passed in, so this follows its sorting and filtering.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header = self.columns.iter().map(|column| column.label.as_str());
+        push_csv_record(&mut csv, header);
+        for row in &self.rows {
+            push_csv_record(&mut csv, row.cells.iter().map(String::as_str));
+        }
+        csv
+    }
+
+    pub fn export_csv(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_csv()).with_context(|| format!("Failed to export table to {}", path.display()))
+    }
+
+    /// The selected rows as tab-separated lines, which spreadsheets paste
+    /// into cells, or the focused row without a selection
+    pub fn selection_tsv(&self) -> Option<String> {
+        let rows: Vec<usize> = match &self.selection {
+            Some(selection) if !selection.selected().get().is_empty() => selection.selected().get().into_iter().collect(),
+            _ => vec![self.focused_cell()?.0],
+        };
+        let lines: Vec<String> = rows
+            .into_iter()
+            .filter_map(|row| self.rows.get(row))
+            .map(|row| {
+                // Tabs and newlines would split the cell
+                let cells: Vec<String> = row.cells.iter().map(|cell| cell.replace(['\t', '\n', '\r'], " ")).collect();
+                cells.join("\t")
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    /// Cell being edited as (row, column)
+    pub fn editing_cell(&self) -> Option<(usize, usize)> {
+        self.cursor.read().editing.as_ref().map(|edit| (edit.row, edit.column))
+    }
+
+    /// Open the editor on the focused cell, if its column is editable
+    fn begin_edit(&self) -> bool {
+        let Some((row, column)) = self.focused_cell() else {
+            return false;
+        };
+        if self.columns[column].editor.is_none() {
+            return false;
+        }
+        let value = self.rows[row].cells.get(column).cloned().unwrap_or_default();
+        self.cursor.write().editing = Some(CellEdit { row, column, value });
+        true
+    }
+
+    /// Close the editor, reporting the value if it changed
+    fn commit_edit(&self) {
+        let Some(edit) = self.cursor.write().editing.take() else {
+            return;
+        };
+        let original = self.rows.get(edit.row).and_then(|row| row.cells.get(edit.column));
+        if original != Some(&edit.value) {
+            if let Some(on_cell_edited) = &self.on_cell_edited {
+                on_cell_edited(edit.row, edit.column, edit.value);
+            }
+        }
+    }
+
+    /// Keys and text while a cell is being edited
+    fn handle_edit_event(&self, event: &crate::core::event::UiEvent) -> crate::core::event::EventResult {
+        use crate::core::event::{EventResult, UiEvent};
+
+        let mut cursor = self.cursor.write();
+        let Some(edit) = cursor.editing.as_mut() else {
+            return EventResult::Unhandled;
+        };
+        let editor = self.columns.get(edit.column).and_then(|column| column.editor.clone());
+        match (event, editor) {
+            // A number cell only commits a number; "-" or "." stays open
+            (UiEvent::KeyDown { key: KeyCode::Enter | KeyCode::NumpadEnter, .. }, Some(CellEditor::Number))
+                if edit.value.parse::<f64>().is_err() => {}
+            (UiEvent::KeyDown { key: KeyCode::Enter | KeyCode::NumpadEnter, .. }, _) => {
+                drop(cursor);
+                self.commit_edit();
+            }
+            (UiEvent::KeyDown { key: KeyCode::Escape, .. }, _) => cursor.editing = None,
+            (UiEvent::KeyDown { key: key @ (KeyCode::ArrowUp | KeyCode::ArrowDown), .. }, Some(CellEditor::Dropdown(options))) => {
+                let current = options.iter().position(|option| *option == edit.value);
+                if let Some(index) = step_index(current, *key, NavAxis::Vertical, options.len(), 1) {
+                    edit.value = options[index].clone();
+                }
+            }
+            (UiEvent::KeyDown { key: KeyCode::Backspace, .. }, Some(CellEditor::Text | CellEditor::Number)) => {
+                edit.value.pop();
+            }
+            (UiEvent::TextInput { character }, Some(CellEditor::Text)) if !character.is_control() => {
+                edit.value.push(*character);
+            }
+            (UiEvent::TextInput { character }, Some(CellEditor::Number))
+                if is_number_draft(&format!("{}{}", edit.value, character)) =>
+            {
+                edit.value.push(*character);
+            }
+            (UiEvent::ImeCommit { text }, Some(CellEditor::Text)) => edit.value.push_str(text),
+            _ => return EventResult::Unhandled,
+        }
+        EventResult::Stopped
+    }
+
+    /// Scroll the focused cell into view, by the sizes of the last build
+    fn scroll_to_focus(&self) {
+        let Some((row, column)) = self.focused_cell() else {
+            return;
+        };
+        let mut cursor = self.cursor.write();
+        let page = cursor.page_rows.max(1);
+        if row < cursor.first_row {
+            cursor.first_row = row;
+        } else if row >= cursor.first_row + page {
+            cursor.first_row = row + 1 - page;
+        }
+
+        // Frozen columns are always in view
+        let frozen = self.frozen_columns.min(cursor.widths.len());
+        if (frozen..cursor.widths.len()).contains(&column) {
+            let view = cursor.view_width - cursor.widths[..frozen].iter().sum::<f32>();
+            let left: f32 = cursor.widths[frozen..column].iter().sum();
+            let right = left + cursor.widths[column];
+            if left < cursor.scroll_x {
+                cursor.scroll_x = left;
+            } else if right > cursor.scroll_x + view {
+                cursor.scroll_x = (right - view).min(left);
+            }
+        }
+    }
+
+    /// Column under `x` in the last build, allowing for the sideways scroll
+    fn column_at(&self, x: f32) -> Option<usize> {
+        let cursor = self.cursor.read();
+        let frozen = self.frozen_columns.min(cursor.widths.len());
+        let frozen_width: f32 = cursor.widths[..frozen].iter().sum();
+        let x = if x < frozen_width { x } else { x + cursor.scroll_x };
+        let mut right = 0.0;
+        cursor.widths.iter().position(|width| {
+            right += width;
+            x < right
+        })
+    }
+
+    /// Move the focused cell one column for the left and right arrows
+    fn move_column(&self, key: KeyCode) -> bool {
+        let mut cursor = self.cursor.write();
+        let last = self.columns.len().saturating_sub(1);
+        cursor.column = match key {
+            KeyCode::ArrowLeft => cursor.column.min(last).saturating_sub(1),
+            KeyCode::ArrowRight => (cursor.column + 1).min(last),
+            _ => return false,
+        };
+        true
+    }
 }
 
 impl StatelessWidget for Table {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
         let width = self.width.unwrap_or(ctx.constraints.max_width);
-        let row_height = if self.compact { 32.0 } else { 48.0 };
-        let header_height = if self.compact { 40.0 } else { 56.0 };
+        let density = if self.compact { Density::Compact } else { theme.density() };
+        *self.density.write() = density;
+        let row_height = density.table_row_height();
+        let header_height = density.table_header_height();
 
         let column_widths = self.calculate_column_widths(width);
         let mut render_objects = Vec::new();
 
+        // Columns past the frozen ones scroll sideways when they overflow
+        let frozen = self.frozen_columns.min(self.columns.len());
+        let frozen_width: f32 = column_widths[..frozen].iter().sum();
+        let max_scroll_x = (column_widths.iter().sum::<f32>() - width).max(0.0);
+
+        // Rows past the available height scroll
+        let highlighted = self.nav.read().highlighted;
+        let visible_rows = if ctx.constraints.has_bounded_height() {
+            let fit = ((ctx.constraints.max_height - header_height) / row_height).floor().max(1.0) as usize;
+            fit.min(self.rows.len())
+        } else {
+            self.rows.len()
+        };
+        let (first_row, scroll_x, focused_column) = {
+            let mut cursor = self.cursor.write();
+            cursor.page_rows = visible_rows.max(1);
+            cursor.view_width = width;
+            cursor.widths = column_widths.clone();
+            cursor.first_row = cursor.first_row.min(self.rows.len() - visible_rows);
+            cursor.scroll_x = cursor.scroll_x.clamp(0.0, max_scroll_x);
+            (
+                cursor.first_row,
+                cursor.scroll_x,
+                cursor.column.min(self.columns.len().saturating_sub(1)),
+            )
+        };
+
         // Table background
-        let total_height = header_height + (self.rows.len() as f32 * row_height);
+        let total_height = header_height + (visible_rows as f32 * row_height);
         render_objects.push(RenderObject::rect(
             Rect::new(0.0, 0.0, width, total_height),
             theme.card,
@@ -225,10 +536,15 @@ impl StatelessWidget for Table {
             theme.border,
         ));
 
+        // Frozen columns draw over the rest, which scroll sideways under them
+        let mut frozen_cells = Vec::new();
+        let mut scrolling_cells = Vec::new();
+
         // Header cells
         let mut current_x = 8.0;
         for (i, col) in self.columns.iter().enumerate() {
             let col_width = column_widths[i];
+            let cells = if i < frozen { &mut frozen_cells } else { &mut scrolling_cells };
 
             // Column text
             let x_offset = match col.align {
@@ -237,7 +553,7 @@ impl StatelessWidget for Table {
                 TableAlign::Right => current_x + col_width - col.label.len() as f32 * 7.0 - 8.0,
             };
 
-            render_objects.push(RenderObject::text(
+            cells.push(RenderObject::text(
                 col.label.clone(),
                 TextStyle {
                     font_family: theme.font_sans.clone(),
@@ -245,13 +561,14 @@ impl StatelessWidget for Table {
                     color: theme.foreground,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(x_offset.max(current_x), header_height / 2.0 + 5.0),
             ));
 
             // Sort indicator if sortable
             if self.sortable && col.sortable {
-                render_objects.push(RenderObject::text(
+                cells.push(RenderObject::text(
                     "⇅".to_string(),
                     TextStyle {
                         font_family: theme.font_sans.clone(),
@@ -259,6 +576,7 @@ impl StatelessWidget for Table {
                         color: theme.muted_foreground,
                         bold: false,
                         italic: false,
+                        ..Default::default()
                     },
                     Point::new(current_x + col_width - 20.0, header_height / 2.0 + 5.0),
                 ));
@@ -266,7 +584,7 @@ impl StatelessWidget for Table {
 
             // Vertical separator
             if self.bordered && i < self.columns.len() - 1 {
-                render_objects.push(RenderObject::rect(
+                cells.push(RenderObject::rect(
                     Rect::new(current_x + col_width, 0.0, 1.0, total_height),
                     theme.border,
                 ));
@@ -276,10 +594,24 @@ impl StatelessWidget for Table {
         }
 
         // Data rows
+        if let Some(selection) = &self.selection {
+            selection.follow_rows(&self.rows.iter().map(TableRow::identity).collect::<Vec<_>>());
+        }
+        let selected = self.selection.as_ref().map(|selection| selection.selected().get()).unwrap_or_default();
         let mut current_y = header_height;
-        for (row_idx, row) in self.rows.iter().enumerate() {
-            // Striped background
-            if self.striped && row_idx % 2 == 1 {
+        for (row_idx, row) in self.rows.iter().enumerate().skip(first_row).take(visible_rows) {
+            // Keyboard highlight, else selection, else striped background
+            if highlighted == Some(row_idx) {
+                render_objects.push(RenderObject::rect(
+                    Rect::new(0.0, current_y, width, row_height),
+                    theme.accent,
+                ));
+            } else if selected.contains(&row_idx) {
+                render_objects.push(RenderObject::rect(
+                    Rect::new(0.0, current_y, width, row_height),
+                    theme.role(ColorRole::Selection),
+                ));
+            } else if self.striped && row_idx % 2 == 1 {
                 render_objects.push(RenderObject::rect(
                     Rect::new(0.0, current_y, width, row_height),
                     theme.muted.with_alpha(50),
@@ -303,6 +635,7 @@ impl StatelessWidget for Table {
 
                 let col = &self.columns[col_idx];
                 let col_width = column_widths[col_idx];
+                let cells = if col_idx < frozen { &mut frozen_cells } else { &mut scrolling_cells };
 
                 let x_offset = match col.align {
                     TableAlign::Left => current_x,
@@ -310,16 +643,24 @@ impl StatelessWidget for Table {
                     TableAlign::Right => current_x + col_width - cell.len() as f32 * 7.0 - 8.0,
                 };
 
-                render_objects.push(RenderObject::text(
-                    cell.clone(),
-                    TextStyle {
-                        font_family: theme.font_sans.clone(),
-                        font_size: 13.0,
-                        color: theme.foreground,
-                        bold: false,
-                        italic: false,
-                    },
-                    Point::new(x_offset.max(current_x), current_y + row_height / 2.0 + 5.0),
+                // Long values end in an ellipsis instead of running into the next column
+                let x = x_offset.max(current_x);
+                cells.push(RenderObject::transform(
+                    Matrix::translate(x, current_y + row_height / 2.0 + 5.0),
+                    layout_text(
+                        cell,
+                        TextStyle {
+                            font_family: theme.font_sans.clone(),
+                            font_size: 13.0,
+                            color: theme.foreground,
+                            bold: false,
+                            italic: false,
+                            ..Default::default()
+                        },
+                        current_x + col_width - 8.0 - x,
+                        None,
+                        Some(TextOverflow::Ellipsis),
+                    ),
                 ));
 
                 current_x += col_width;
@@ -328,6 +669,68 @@ impl StatelessWidget for Table {
             current_y += row_height;
         }
 
+        // Ring inside the focused cell
+        let in_view = first_row..first_row + visible_rows;
+        if let Some(row) = highlighted.filter(|row| in_view.contains(row) && !self.columns.is_empty()) {
+            let x: f32 = column_widths[..focused_column].iter().sum();
+            let y = header_height + (row - first_row) as f32 * row_height;
+            let cell = Rect::new(x, y, column_widths[focused_column], row_height);
+            let ring = FocusRing::new().with_offset(-2.0).with_radius(2.0).render(cell, &theme);
+            if focused_column < frozen {
+                frozen_cells.push(ring);
+            } else {
+                scrolling_cells.push(ring);
+            }
+        }
+
+        // The editor covers the cell inside the focus ring, with a caret or
+        // a dropdown arrow
+        let editing = self.cursor.read().editing.as_ref().map(|edit| (edit.row, edit.column, edit.value.clone()));
+        if let Some((row, column, value)) = editing.filter(|(row, column, _)| in_view.contains(row) && *column < self.columns.len()) {
+            let x: f32 = column_widths[..column].iter().sum();
+            let y = header_height + (row - first_row) as f32 * row_height;
+            let col_width = column_widths[column];
+            let style = TextStyle {
+                font_family: theme.font_sans.clone(),
+                font_size: 13.0,
+                color: theme.foreground,
+                ..Default::default()
+            };
+            let baseline = y + row_height / 2.0 + 5.0;
+            let mut editor = vec![RenderObject::rect(
+                Rect::new(x + 2.0, y + 2.0, col_width - 4.0, row_height - 4.0),
+                theme.background,
+            )];
+            if let Some(CellEditor::Dropdown(_)) = self.columns[column].editor {
+                editor.push(RenderObject::text("▾".to_string(), style.clone(), Point::new(x + col_width - 20.0, baseline)));
+            } else {
+                let caret_x = x + 8.0 + text_width(&value, &style);
+                editor.push(RenderObject::rect(Rect::new(caret_x, y + 6.0, 1.0, row_height - 12.0), theme.foreground));
+            }
+            editor.push(RenderObject::transform(
+                Matrix::translate(x + 8.0, baseline),
+                layout_text(&value, style, col_width - 28.0, None, Some(TextOverflow::Ellipsis)),
+            ));
+            if column < frozen {
+                frozen_cells.extend(editor);
+            } else {
+                scrolling_cells.extend(editor);
+            }
+        }
+
+        // The header and body columns share one scroll, so they stay in line
+        render_objects.push(RenderObject::clip(
+            Rect::new(frozen_width, 0.0, (width - frozen_width).max(0.0), total_height),
+            RenderObject::transform(Matrix::translate(-scroll_x, 0.0), RenderObject::group(scrolling_cells)),
+        ));
+        render_objects.push(RenderObject::group(frozen_cells));
+        if frozen > 0 && scroll_x > 0.0 {
+            render_objects.push(RenderObject::rect(
+                Rect::new(frozen_width - 1.0, 0.0, 2.0, total_height),
+                theme.border,
+            ));
+        }
+
         WidgetNode::Leaf(RenderObject::group(render_objects))
     }
 }
@@ -340,34 +743,179 @@ impl Widget for Table {
     fn handle_event(&self, event: &crate::core::event::UiEvent, context: &mut crate::core::event::EventContext) -> crate::core::event::EventResult {
         use crate::core::event::{UiEvent, MouseButton, EventResult};
 
+        // While a cell is being edited, keys go to the editor. Others may
+        // be app shortcuts but don't move the focused cell.
+        if self.cursor.read().editing.is_some() {
+            match event {
+                UiEvent::KeyDown { .. } | UiEvent::TextInput { .. } | UiEvent::ImeCommit { .. } => {
+                    return self.handle_edit_event(event);
+                }
+                UiEvent::Blur => {
+                    self.commit_edit();
+                    return EventResult::Handled;
+                }
+                _ => {}
+            }
+        }
+
         match event {
+            UiEvent::PointerDown { button: MouseButton::Left, .. } if context.is_at_target() => {
+                context.request_focus();
+                EventResult::Unhandled
+            }
+            UiEvent::KeyDown { key: KeyCode::KeyC, modifiers, .. } if modifiers.ctrl || modifiers.meta => {
+                let Some(tsv) = self.selection_tsv() else {
+                    return EventResult::Unhandled;
+                };
+                if let Err(e) = set_clipboard_text(&tsv) {
+                    eprintln!("⚠️ {:#}", e);
+                }
+                EventResult::Stopped
+            }
+            UiEvent::KeyDown { key: KeyCode::F2, .. } => {
+                if self.begin_edit() {
+                    EventResult::Stopped
+                } else {
+                    EventResult::Unhandled
+                }
+            }
+            UiEvent::KeyDown { key: key @ (KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space), modifiers, .. }
+                if !(modifiers.ctrl || modifiers.meta) =>
+            {
+                let highlighted = self.nav.read().highlighted;
+                let Some(row_index) = highlighted.filter(|i| *i < self.rows.len()) else {
+                    return EventResult::Unhandled;
+                };
+                // Enter opens the row like a double-click; Space acts like
+                // a single click
+                let callback = match key {
+                    KeyCode::Space => &self.on_row_click,
+                    _ => &self.on_row_activate,
+                };
+                if let (true, Some(callback)) = (self.rows[row_index].selectable, callback) {
+                    callback(row_index);
+                }
+                self.scroll_to_focus();
+                EventResult::Stopped
+            }
+            UiEvent::KeyDown { key, modifiers, .. } => {
+                let mut nav = self.nav.write();
+                let result = match key {
+                    KeyCode::ArrowLeft | KeyCode::ArrowRight => {
+                        if nav.highlighted.is_none() {
+                            nav.highlighted = self.selection.as_ref().and_then(|s| s.lead()).or(Some(0));
+                        }
+                        self.move_column(*key);
+                        EventResult::Stopped
+                    }
+                    _ => {
+                        let page = self.cursor.read().page_rows.max(1);
+                        match &self.selection {
+                            Some(selection) if selection.handle_key(*key, *modifiers, self.rows.len(), page) => {
+                                nav.highlighted = selection.lead();
+                                EventResult::Stopped
+                            }
+                            Some(_) => EventResult::Unhandled,
+                            None if nav.move_key(*key, self.rows.len(), page) => EventResult::Stopped,
+                            None => EventResult::Unhandled,
+                        }
+                    }
+                };
+                drop(nav);
+                if result == EventResult::Stopped {
+                    self.scroll_to_focus();
+                }
+                result
+            }
+            UiEvent::Scroll { delta, .. } => {
+                let row_height = self.density.read().table_row_height();
+                let mut cursor = self.cursor.write();
+                let before = (cursor.first_row, cursor.scroll_x);
+
+                // Wheel deltas point the way the content moves. Rows scroll
+                // whole, at least one for a small trackpad delta.
+                let steps = -delta.y / row_height;
+                let rows = if delta.y == 0.0 { 0 } else { (steps.abs().round().max(1.0) * steps.signum()) as isize };
+                let last_first_row = self.rows.len().saturating_sub(cursor.page_rows);
+                cursor.first_row = cursor.first_row.saturating_add_signed(rows).min(last_first_row);
+                let max_scroll_x = (cursor.widths.iter().sum::<f32>() - cursor.view_width).max(0.0);
+                cursor.scroll_x = (cursor.scroll_x - delta.x).clamp(0.0, max_scroll_x);
+
+                // At the ends, leave the wheel to an enclosing scroll area
+                if (cursor.first_row, cursor.scroll_x) == before {
+                    EventResult::Unhandled
+                } else {
+                    EventResult::Stopped
+                }
+            }
+            // Typeahead matches the first column
+            UiEvent::TextInput { character } if !character.is_whitespace() => {
+                let labels = self.rows.iter().map(|row| row.cells.first().map_or("", String::as_str));
+                match self.nav.write().typeahead(*character, labels) {
+                    Some(_) => EventResult::Stopped,
+                    None => EventResult::Unhandled,
+                }
+            }
+            // Double-clicking a cell opens its editor, or else activates the row
+            UiEvent::DoubleClick { position } if context.is_at_target() => {
+                let density = *self.density.read();
+                if position.y <= density.table_header_height() || self.editing_cell().is_some() {
+                    return EventResult::Unhandled;
+                }
+                let row_index = self.cursor.read().first_row
+                    + ((position.y - density.table_header_height()) / density.table_row_height()) as usize;
+                if row_index >= self.rows.len() {
+                    return EventResult::Unhandled;
+                }
+                self.nav.write().highlighted = Some(row_index);
+                if let Some(column) = self.column_at(position.x) {
+                    self.cursor.write().column = column;
+                    if self.begin_edit() {
+                        return EventResult::Stopped;
+                    }
+                }
+                match &self.on_row_activate {
+                    Some(on_row_activate) if self.rows[row_index].selectable => {
+                        on_row_activate(row_index);
+                        EventResult::Stopped
+                    }
+                    _ => EventResult::Unhandled,
+                }
+            }
             UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
-                let row_height = if self.compact { 32.0 } else { 48.0 };
-                let header_height = if self.compact { 40.0 } else { 56.0 };
+                let density = *self.density.read();
+                let row_height = density.table_row_height();
+                let header_height = density.table_header_height();
 
                 // Check if clicked on header (for sorting)
                 if position.y <= header_height && self.sortable {
-                    let width = self.width.unwrap_or(800.0);
-                    let column_widths = self.calculate_column_widths(width);
-
-                    let mut current_x = 0.0;
-                    for (i, col_width) in column_widths.iter().enumerate() {
-                        if position.x >= current_x && position.x < current_x + col_width {
-                            if self.columns[i].sortable {
-                                if let Some(on_sort) = &self.on_sort {
-                                    on_sort(i, SortDirection::Ascending);
-                                }
-                                return EventResult::Stopped;
-                            }
+                    if let Some(i) = self.column_at(position.x).filter(|i| self.columns[*i].sortable) {
+                        if let Some(on_sort) = &self.on_sort {
+                            on_sort(i, SortDirection::Ascending);
                         }
-                        current_x += col_width;
+                        return EventResult::Stopped;
                     }
                 } else if position.y > header_height {
-                    // Check if clicked on row
-                    let row_index = ((position.y - header_height) / row_height) as usize;
+                    // Check if clicked on row, focusing the cell under the pointer
+                    let row_index = self.cursor.read().first_row + ((position.y - header_height) / row_height) as usize;
+                    let column = self.column_at(position.x);
+                    if self.editing_cell().is_some_and(|cell| Some(cell) != column.map(|column| (row_index, column))) {
+                        self.commit_edit();
+                    }
+                    if row_index < self.rows.len() {
+                        self.nav.write().highlighted = Some(row_index);
+                        if let Some(column) = column {
+                            self.cursor.write().column = column;
+                        }
+                    }
                     if row_index < self.rows.len() && self.rows[row_index].selectable {
+                        if let Some(selection) = &self.selection {
+                            selection.click(row_index, context.modifiers);
+                        }
                         if let Some(on_row_click) = &self.on_row_click {
                             on_row_click(row_index);
+                        }
+                        if self.selection.is_some() || self.on_row_click.is_some() {
                             return EventResult::Stopped;
                         }
                     }
@@ -379,6 +927,23 @@ impl Widget for Table {
         }
     }
 
+    /// The focused cell is a polite live region, so moving it reads the
+    /// new cell aloud
+    fn semantics(&self) -> Option<SemanticsData> {
+        let semantics = SemanticsData::new()
+            .with_role(AccessibilityRole::Table)
+            .with_label(format!("Table, {} rows", self.rows.len()))
+            .with_live_region(LiveRegion::Polite);
+        Some(match self.focused_cell_announcement() {
+            Some(cell) => semantics.with_value(cell),
+            None => semantics,
+        })
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -392,6 +957,30 @@ impl Widget for Table {
     }
 }
 
+/// Whether `draft` can still be typed into a number: an optional sign,
+/// then digits with at most one decimal point
+fn is_number_draft(draft: &str) -> bool {
+    let digits = draft.strip_prefix(['-', '+']).unwrap_or(draft);
+    digits.chars().all(|c| c.is_ascii_digit() || c == '.') && digits.matches('.').count() <= 1
+}
+
+/// Append one CSV line, quoting fields with commas, quotes or line breaks
+fn push_csv_record<'a>(csv: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push_str("\r\n");
+}
+
 impl TableColumn {
     pub fn new(label: impl Into<String>) -> Self {
         Self {
@@ -399,6 +988,7 @@ impl TableColumn {
             width: ColumnWidth::Auto,
             align: TableAlign::Left,
             sortable: false,
+            editor: None,
         }
     }
 
@@ -416,6 +1006,11 @@ impl TableColumn {
         self.sortable = sortable;
         self
     }
+
+    pub fn editable(mut self, editor: CellEditor) -> Self {
+        self.editor = Some(editor);
+        self
+    }
 }
 
 impl TableRow {
@@ -423,6 +1018,7 @@ impl TableRow {
         Self {
             cells,
             selectable: true,
+            id: None,
         }
     }
 
@@ -430,4 +1026,185 @@ impl TableRow {
         self.selectable = selectable;
         self
     }
-}
\ No newline at end of file
+
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    fn identity(&self) -> u64 {
+        self.id.unwrap_or_else(|| debug_hash(&self.cells))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::event::{EventContext, EventPhase, Modifiers, UiEvent, Vector2};
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use winit::keyboard::{Key, NamedKey};
+
+    fn press(table: &Table, key: KeyCode, named: NamedKey) {
+        let event = UiEvent::KeyDown {
+            key,
+            logical_key: Key::Named(named),
+            modifiers: Modifiers::default(),
+            repeat: false,
+        };
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        table.handle_event(&event, &mut context);
+    }
+
+    #[test]
+    fn test_focused_cell_moves_and_scrolls_into_view() {
+        let table = Table::new(vec![TableColumn::new("Name"), TableColumn::new("Role")])
+            .with_rows((0..20).map(|i| TableRow::new(vec![format!("User {i}"), "Admin".into()])).collect())
+            .compact(true);
+        let (row_height, header_height) = (Density::Compact.table_row_height(), Density::Compact.table_header_height());
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 400.0, 0.0, header_height + 5.0 * row_height),
+            Arc::new(Theme::default()),
+        );
+        table.build(&ctx);
+        assert_eq!(table.focused_cell(), None);
+
+        press(&table, KeyCode::ArrowDown, NamedKey::ArrowDown);
+        press(&table, KeyCode::ArrowRight, NamedKey::ArrowRight);
+        assert_eq!(table.focused_cell(), Some((0, 1)));
+        assert_eq!(table.focused_cell_announcement().as_deref(), Some("Row 1 of 20, Role: Admin"));
+
+        // Page Down moves a screen of rows and the next build scrolls to it
+        press(&table, KeyCode::PageDown, NamedKey::PageDown);
+        assert_eq!(table.focused_cell(), Some((5, 1)));
+        table.build(&ctx);
+        assert_eq!(table.cursor.read().first_row, 1);
+
+        press(&table, KeyCode::End, NamedKey::End);
+        press(&table, KeyCode::ArrowLeft, NamedKey::ArrowLeft);
+        assert_eq!(table.focused_cell(), Some((19, 0)));
+        table.build(&ctx);
+        assert_eq!(table.cursor.read().first_row, 15);
+    }
+
+    #[test]
+    fn test_frozen_columns_stay_while_the_rest_scroll() {
+        let columns = ["Name", "Email", "Team", "Role"]
+            .into_iter()
+            .map(|label| TableColumn::new(label).with_width(ColumnWidth::Fixed(150.0)))
+            .collect();
+        let table = Table::new(columns)
+            .with_rows(vec![TableRow::new(vec!["Ada".into(), "ada@example.com".into(), "Core".into(), "Admin".into()])])
+            .with_frozen_columns(1);
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 400.0, 0.0, f32::INFINITY),
+            Arc::new(Theme::default()),
+        );
+        table.build(&ctx);
+
+        // Focusing the last column scrolls it flush with the right edge
+        for _ in 0..4 {
+            press(&table, KeyCode::ArrowRight, NamedKey::ArrowRight);
+        }
+        assert_eq!(table.focused_cell(), Some((0, 3)));
+        assert_eq!(table.cursor.read().scroll_x, 200.0);
+        assert_eq!(table.column_at(100.0), Some(0));
+        assert_eq!(table.column_at(200.0), Some(2));
+
+        // The wheel scrolls sideways, clamped to the content
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        let wheel = UiEvent::Scroll { position: Point::new(200.0, 50.0), delta: Vector2::new(500.0, 0.0) };
+        table.handle_event(&wheel, &mut context);
+        assert_eq!(table.cursor.read().scroll_x, 0.0);
+        assert_eq!(table.column_at(200.0), Some(1));
+    }
+
+    #[test]
+    fn test_cell_editing_commits_on_enter_and_cancels_on_escape() {
+        let edits = Arc::new(RwLock::new(Vec::new()));
+        let log = edits.clone();
+        let table = Table::new(vec![
+            TableColumn::new("Name").editable(CellEditor::Text),
+            TableColumn::new("Age").editable(CellEditor::Number),
+        ])
+        .with_rows(vec![TableRow::new(vec!["Ada".into(), "36".into()])])
+        .with_on_cell_edited(move |row, column, value| log.write().push((row, column, value)));
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        let mut type_text = |text: &str| {
+            for character in text.chars() {
+                table.handle_event(&UiEvent::TextInput { character }, &mut context);
+            }
+        };
+
+        // F2 opens the focused cell; Escape drops the draft
+        press(&table, KeyCode::ArrowDown, NamedKey::ArrowDown);
+        press(&table, KeyCode::F2, NamedKey::F2);
+        assert_eq!(table.editing_cell(), Some((0, 0)));
+        type_text("x");
+        press(&table, KeyCode::Escape, NamedKey::Escape);
+        assert_eq!(table.editing_cell(), None);
+
+        // Number cells take only numeric characters
+        press(&table, KeyCode::ArrowRight, NamedKey::ArrowRight);
+        press(&table, KeyCode::F2, NamedKey::F2);
+        press(&table, KeyCode::Backspace, NamedKey::Backspace);
+        type_text("7a");
+        press(&table, KeyCode::Enter, NamedKey::Enter);
+        assert_eq!(table.editing_cell(), None);
+        assert_eq!(*edits.read(), vec![(0, 1, "37".to_string())]);
+
+        // Signs only lead, one decimal point, and only numbers commit
+        press(&table, KeyCode::F2, NamedKey::F2);
+        for _ in 0..2 {
+            press(&table, KeyCode::Backspace, NamedKey::Backspace);
+        }
+        type_text("-");
+        press(&table, KeyCode::Enter, NamedKey::Enter);
+        assert_eq!(table.editing_cell(), Some((0, 1)));
+        type_text("1.-5.+2");
+        press(&table, KeyCode::Enter, NamedKey::Enter);
+        assert_eq!(edits.read().last(), Some(&(0, 1, "-1.52".to_string())));
+    }
+
+    #[test]
+    fn test_csv_and_tsv_export() {
+        let selection = SelectionController::new(crate::widgets::selection::SelectionMode::Multiple);
+        let table = Table::new(vec![TableColumn::new("Name"), TableColumn::new("Note")])
+            .with_rows(vec![
+                TableRow::new(vec!["Ada".into(), "Says \"hi\", often".into()]),
+                TableRow::new(vec!["Grace".into(), "line\tbreak".into()]),
+            ])
+            .with_selection(selection.clone());
+        assert_eq!(table.to_csv(), "Name,Note\r\nAda,\"Says \"\"hi\"\", often\"\r\nGrace,line\tbreak\r\n");
+
+        assert_eq!(table.selection_tsv(), None);
+        selection.select_all(2);
+        assert_eq!(table.selection_tsv().as_deref(), Some("Ada\tSays \"hi\", often\nGrace\tline break"));
+    }
+
+    #[test]
+    fn test_enter_activates_and_space_clicks_the_row() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let activated = Arc::new(AtomicUsize::new(usize::MAX));
+        let clicked = Arc::new(AtomicUsize::new(usize::MAX));
+        let (on_activate, on_click) = (activated.clone(), clicked.clone());
+        let table = Table::new(vec![TableColumn::new("Name")])
+            .with_rows((0..3).map(|i| TableRow::new(vec![format!("User {i}")])).collect())
+            .with_on_row_activate(move |row| on_activate.store(row, Ordering::SeqCst))
+            .with_on_row_click(move |row| on_click.store(row, Ordering::SeqCst));
+
+        press(&table, KeyCode::ArrowDown, NamedKey::ArrowDown);
+        press(&table, KeyCode::ArrowDown, NamedKey::ArrowDown);
+        press(&table, KeyCode::Enter, NamedKey::Enter);
+        assert_eq!(activated.load(Ordering::SeqCst), 1);
+        assert_eq!(clicked.load(Ordering::SeqCst), usize::MAX);
+
+        press(&table, KeyCode::Space, NamedKey::Space);
+        assert_eq!(clicked.load(Ordering::SeqCst), 1);
+    }
+}
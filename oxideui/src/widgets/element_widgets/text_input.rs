@@ -1,7 +1,13 @@
 use crate::core::*;
-use crate::core::render_object::{Point, Rect, TextStyle};
+use crate::core::render_object::{Point, Rect, TextDecoration, TextStyle};
+use crate::render::text::text_width;
+use crate::state_management::bindable::Binding;
+use crate::platform::window::WindowController;
+use crate::theming::{InputStyle, StyleState};
+use parking_lot::RwLock;
 use std::any::Any;
 use std::sync::Arc;
+use winit::keyboard::KeyCode;
 
 #[derive(Clone)]
 pub struct TextInput {
@@ -11,7 +17,14 @@ pub struct TextInput {
     pub height: Option<f32>,
     pub disabled: bool,
     pub on_change: Option<Arc<dyn Fn(String) + Send + Sync>>,
+    pub binding: Option<Binding<String>>,
     pub tooltip: Option<String>,
+    /// Visual style; the app-wide default when unset
+    pub style: Option<InputStyle>,
+    /// Set at build so `handle_event` can show the software keyboard
+    window: Arc<RwLock<Option<WindowController>>>,
+    /// Insertion point as a byte offset into the value; None at the end
+    cursor: Arc<RwLock<Option<usize>>>,
     key: Option<WidgetKey>,
 }
 
@@ -21,10 +34,14 @@ impl TextInput {
             placeholder: placeholder.into(),
             value: String::new(),
             width: None,
-            height: Some(40.0),
+            height: None,
             disabled: false,
             on_change: None,
+            binding: None,
             tooltip: None,
+            style: None,
+            window: Arc::new(RwLock::new(None)),
+            cursor: Arc::new(RwLock::new(None)),
             key: None,
         }
     }
@@ -48,6 +65,12 @@ impl TextInput {
         self
     }
 
+    /// Read the value from, and write edits back to, a binding
+    pub fn with_binding(mut self, binding: impl Into<Binding<String>>) -> Self {
+        self.binding = Some(binding.into());
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -57,19 +80,71 @@ impl TextInput {
         self.tooltip = Some(text.into());
         self
     }
+
+    pub fn with_style(mut self, style: InputStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Current value, preferring the binding over the static value
+    pub fn current_value(&self) -> String {
+        match &self.binding {
+            Some(binding) => binding.get(),
+            None => self.value.clone(),
+        }
+    }
+
+    /// Insertion point in `value`, kept on a character boundary
+    fn cursor_in(&self, value: &str) -> usize {
+        let mut cursor = self.cursor.read().unwrap_or(value.len()).min(value.len());
+        while !value.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        cursor
+    }
+
+    fn set_cursor(&self, value: &str, cursor: usize) {
+        *self.cursor.write() = (cursor < value.len()).then_some(cursor);
+    }
+
+    /// Insert `text` at the cursor and move the cursor past it
+    fn insert(&self, text: &str) {
+        let mut value = self.current_value();
+        let cursor = self.cursor_in(&value);
+        value.insert_str(cursor, text);
+        self.set_cursor(&value, cursor + text.len());
+        self.commit(value);
+    }
+
+    fn commit(&self, value: String) {
+        if let Some(binding) = &self.binding {
+            binding.set(value.clone());
+        }
+        if let Some(on_change) = &self.on_change {
+            on_change(value);
+        }
+    }
 }
 
 impl StatelessWidget for TextInput {
-    fn build_stateless(&self, _ctx: &BuildContext) -> WidgetNode {
-        let width = self.width.unwrap_or(200.0);
-        let height = self.height.unwrap_or(40.0);
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        *self.window.write() = ctx.window().cloned();
+        if let Some(binding) = &self.binding {
+            binding.subscribe(ctx.element_id);
+        }
 
-        let bg_color = if self.disabled {
-            Color::from_hex(0xF3F4F6)
-        } else {
-            Color::WHITE
+        let style = self.style.clone().unwrap_or_else(|| ctx.input_style());
+        let state = StyleState {
+            disabled: self.disabled,
+            ..Default::default()
         };
 
+        let value = self.current_value();
+        let width = self.width.unwrap_or(200.0);
+        let height = self.height.unwrap_or(style.height);
+
+        let bg_color = style.background.resolve(state);
+
         let mut render_objects = Vec::new();
 
         // Background
@@ -79,40 +154,87 @@ impl StatelessWidget for TextInput {
         ));
 
         // Border
-        let border_color = Color::from_hex(0xE5E7EB);
-        render_objects.push(RenderObject::rect(
-            Rect::new(0.0, 0.0, width, 1.0),
-            border_color,
-        ));
-        render_objects.push(RenderObject::rect(
-            Rect::new(0.0, height - 1.0, width, 1.0),
-            border_color,
-        ));
+        let border_color = style.border_color.resolve(state);
+        let border_width = style.border_width;
+        if border_width > 0.0 {
+            render_objects.push(RenderObject::rect(
+                Rect::new(0.0, 0.0, width, border_width),
+                border_color,
+            ));
+            render_objects.push(RenderObject::rect(
+                Rect::new(0.0, height - border_width, width, border_width),
+                border_color,
+            ));
+        }
+
+        // In-progress IME composition, if this field owns it
+        let composition = ctx.window().and_then(|window| {
+            let ime = window.ime();
+            let text = ime.get_composition().filter(|text| !text.is_empty())?;
+            (ime.get_active_input() == Some(ctx.element_id)).then(|| (text.to_string(), ime.get_composition_range()))
+        });
+        let cursor = self.cursor_in(&value);
 
         // Text
-        let text = if self.value.is_empty() {
+        let text = if value.is_empty() && composition.is_none() {
             &self.placeholder
         } else {
-            &self.value
+            &value
         };
 
-        let text_color = if self.value.is_empty() {
-            Color::from_hex(0x9CA3AF)
+        let text_color = if value.is_empty() {
+            style.placeholder
         } else {
-            Color::from_hex(0x111827)
+            style.foreground.resolve(state)
         };
 
-        render_objects.push(RenderObject::text(
-            text.clone(),
-            TextStyle {
-                font_family: "Inter".to_string(),
-                font_size: 14.0,
-                color: text_color,
-                bold: false,
-                italic: false,
-            },
-            Point::new(12.0, height / 2.0 + 5.0),
-        ));
+        let text_style = TextStyle {
+            font_family: "Inter".to_string(),
+            font_size: style.font_size,
+            color: text_color,
+            bold: false,
+            italic: false,
+            ..Default::default()
+        };
+        let baseline = height / 2.0 + style.font_size / 3.0;
+        match composition {
+            None => render_objects.push(RenderObject::text(
+                text.clone(),
+                text_style.clone(),
+                Point::new(style.padding.left, baseline),
+            )),
+            // Composition text is underlined at the cursor, and the
+            // candidate window follows its own cursor
+            Some((preedit, range)) => {
+                let (before, after) = value.split_at(cursor);
+                let x = style.padding.left + text_width(before, &text_style);
+                let preedit_width = text_width(&preedit, &text_style);
+                let caret = range.map_or(preedit.len(), |(start, _)| start.min(preedit.len()));
+                let caret_x = x + text_width(preedit.get(..caret).unwrap_or(&preedit), &text_style);
+                render_objects.push(RenderObject::text(
+                    before.to_string(),
+                    text_style.clone(),
+                    Point::new(style.padding.left, baseline),
+                ));
+                render_objects.push(RenderObject::text(
+                    after.to_string(),
+                    text_style.clone(),
+                    Point::new(x + preedit_width, baseline),
+                ));
+                render_objects.push(RenderObject::text(
+                    preedit,
+                    TextStyle {
+                        color: style.foreground.resolve(state),
+                        decoration: TextDecoration::underline(),
+                        ..text_style
+                    },
+                    Point::new(x, baseline),
+                ));
+                if let Some(window) = ctx.window() {
+                    window.set_ime_caret(ctx.element_id, Rect::new(caret_x, 0.0, 1.0, height));
+                }
+            }
+        }
 
         WidgetNode::Leaf(RenderObject::group(render_objects))
     }
@@ -123,6 +245,89 @@ impl Widget for TextInput {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if self.disabled || !context.is_at_target() {
+            return EventResult::Unhandled;
+        }
+
+        match event {
+            UiEvent::ImePreedit { .. } => {
+                if let Some(window) = self.window.read().as_ref() {
+                    window.ime().set_active_input(Some(context.target));
+                }
+                EventResult::Stopped
+            }
+            UiEvent::ImeCommit { text } => {
+                self.insert(text);
+                EventResult::Stopped
+            }
+            UiEvent::PointerDown { .. } => {
+                context.request_focus();
+                EventResult::Handled
+            }
+            // Touch devices have no keyboard until a text field asks for
+            // one, and the composition goes to the focused field
+            UiEvent::Focus => {
+                if let Some(window) = self.window.read().as_ref() {
+                    window.ime().set_active_input(Some(context.target));
+                    window.show_soft_keyboard();
+                }
+                EventResult::Handled
+            }
+            UiEvent::Blur => {
+                if let Some(window) = self.window.read().as_ref() {
+                    let mut ime = window.ime();
+                    if ime.get_active_input() == Some(context.target) {
+                        ime.set_active_input(None);
+                    }
+                    drop(ime);
+                    window.hide_soft_keyboard();
+                }
+                EventResult::Handled
+            }
+            UiEvent::TextInput { character } if !character.is_control() => {
+                self.insert(character.encode_utf8(&mut [0; 4]));
+                EventResult::Stopped
+            }
+            UiEvent::KeyDown { key: KeyCode::Backspace, .. } => {
+                let mut value = self.current_value();
+                let cursor = self.cursor_in(&value);
+                if let Some((start, _)) = value[..cursor].char_indices().next_back() {
+                    value.remove(start);
+                    self.set_cursor(&value, start);
+                    self.commit(value);
+                }
+                EventResult::Stopped
+            }
+            UiEvent::KeyDown { key: key @ (KeyCode::ArrowLeft | KeyCode::ArrowRight | KeyCode::Home | KeyCode::End), .. } => {
+                let value = self.current_value();
+                let cursor = self.cursor_in(&value);
+                let cursor = match key {
+                    KeyCode::ArrowLeft => value[..cursor].char_indices().next_back().map_or(0, |(i, _)| i),
+                    KeyCode::ArrowRight => value[cursor..].chars().next().map_or(cursor, |c| cursor + c.len_utf8()),
+                    KeyCode::Home => 0,
+                    _ => value.len(),
+                };
+                self.set_cursor(&value, cursor);
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.disabled
+    }
+
+    fn semantics(&self) -> Option<SemanticsData> {
+        Some(
+            SemanticsData::new()
+                .with_role(AccessibilityRole::TextField)
+                .with_label(self.placeholder.clone())
+                .with_value(self.current_value()),
+        )
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -134,4 +339,37 @@ impl Widget for TextInput {
     fn clone_box(&self) -> Box<dyn Widget> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::ElementId;
+    use crate::core::event::{EventPhase, Modifiers};
+    use crate::state_management::state::State;
+    use winit::keyboard::{Key, NamedKey};
+
+    #[test]
+    fn test_commits_go_in_at_the_cursor() {
+        let state = State::new("helo".to_string());
+        let window = WindowController::new();
+        let input = TextInput::new("Name").with_binding(state.clone());
+        *input.window.write() = Some(window.clone());
+        let mut context = EventContext::new(ElementId::new(7), ElementId::new(7), EventPhase::AtTarget);
+
+        // Focusing the field routes the composition to it
+        input.handle_event(&UiEvent::Focus, &mut context);
+        assert_eq!(window.ime().get_active_input(), Some(ElementId::new(7)));
+
+        let left = UiEvent::KeyDown {
+            key: KeyCode::ArrowLeft,
+            logical_key: Key::Named(NamedKey::ArrowLeft),
+            modifiers: Modifiers::default(),
+            repeat: false,
+        };
+        input.handle_event(&left, &mut context);
+        input.handle_event(&UiEvent::ImeCommit { text: "l".into() }, &mut context);
+        assert_eq!(state.get(), "hello");
+        input.handle_event(&UiEvent::TextInput { character: '!' }, &mut context);
+        assert_eq!(state.get(), "hell!o");
+    }
+}
@@ -144,6 +144,7 @@ impl StatelessWidget for Textarea {
                     color: display_color,
                     bold: false,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(8.0, 12.0 + (i as f32 * 24.0)),
             ));
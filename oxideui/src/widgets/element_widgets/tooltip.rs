@@ -1,17 +1,36 @@
 use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+use parking_lot::RwLock;
 use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, UiEvent};
 use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{popup_window, PopupAlign, PopupPlacement, PopupSide, Size};
+use crate::runtime::TimerHandle;
+use crate::ThemeProvider;
 
+/// Shows `text` beside its child once the pointer has rested on it for
+/// `delay` milliseconds
 pub struct Tooltip {
     pub text: String,
     pub child: Box<dyn Widget>,
     pub placement: TooltipPlacement,
     pub delay: u32,
     pub max_width: Option<f32>,
+    /// Hover and visibility, shared across rebuilds
+    state: Arc<RwLock<TooltipState>>,
     key: Option<WidgetKey>,
 }
 
+#[derive(Default)]
+struct TooltipState {
+    hovered: bool,
+    shown: bool,
+    /// Pending show after the delay
+    timer: Option<TimerHandle>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TooltipPlacement {
     Top,
@@ -20,6 +39,17 @@ pub enum TooltipPlacement {
     Right,
 }
 
+impl From<TooltipPlacement> for PopupSide {
+    fn from(placement: TooltipPlacement) -> Self {
+        match placement {
+            TooltipPlacement::Top => PopupSide::Top,
+            TooltipPlacement::Bottom => PopupSide::Bottom,
+            TooltipPlacement::Left => PopupSide::Left,
+            TooltipPlacement::Right => PopupSide::Right,
+        }
+    }
+}
+
 impl Tooltip {
     pub fn new(text: impl Into<String>, child: Box<dyn Widget>) -> Self {
         Self {
@@ -28,6 +58,7 @@ impl Tooltip {
             placement: TooltipPlacement::Top,
             delay: 500,
             max_width: Some(200.0),
+            state: Arc::new(RwLock::new(TooltipState::default())),
             key: None,
         }
     }
@@ -39,6 +70,7 @@ impl Tooltip {
             placement: self.placement,
             delay: self.delay,
             max_width: self.max_width,
+            state: self.state.clone(),
             key: self.key.clone(),
         }
     }
@@ -66,10 +98,44 @@ impl Tooltip {
 
 impl StatelessWidget for Tooltip {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
-        // For now, just render the child
-        // Tooltip display logic would be handled by the framework
-        // based on hover state and delay
-        self.child.build(ctx)
+        let child = match ctx.build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(child) => child,
+            other => return other,
+        };
+
+        let shown = {
+            let mut state = self.state.write();
+            if !state.hovered {
+                if let Some(timer) = state.timer.take() {
+                    timer.cancel();
+                }
+            } else if !state.shown && state.timer.is_none() {
+                let shared = self.state.clone();
+                let timer = ctx.after(Duration::from_millis(self.delay as u64), move || {
+                    let mut state = shared.write();
+                    state.shown = state.hovered;
+                });
+                match timer {
+                    Some(timer) => state.timer = Some(timer),
+                    // Headless builds have no clock to wait on
+                    None => state.shown = true,
+                }
+            }
+            state.shown
+        };
+        if !shown {
+            return WidgetNode::Leaf(child);
+        }
+
+        let popup = render_tooltip(
+            &self.text,
+            Rect::from_size(child.layout_size()),
+            self.placement,
+            popup_window(ctx),
+            ctx.theme(),
+            self.max_width.unwrap_or(f32::INFINITY),
+        );
+        WidgetNode::Leaf(RenderObject::group(vec![child, RenderObject::overlay(popup)]))
     }
 }
 
@@ -78,6 +144,21 @@ impl Widget for Tooltip {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &UiEvent, _context: &mut EventContext) -> EventResult {
+        match event {
+            UiEvent::PointerEnter => self.state.write().hovered = true,
+            UiEvent::PointerLeave => {
+                let mut state = self.state.write();
+                state.hovered = false;
+                state.shown = false;
+            }
+            // Pressing hides it until the next hover
+            UiEvent::PointerDown { .. } => self.state.write().shown = false,
+            _ => {}
+        }
+        EventResult::Unhandled
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -91,11 +172,13 @@ impl Widget for Tooltip {
     }
 }
 
-// Tooltip render object builder (used by framework)
+// Tooltip render object builder (used by framework). The tooltip is centered
+// on `placement`'s side of `anchor`, flipping and clamping to stay in `window`.
 pub fn render_tooltip(
     text: &str,
-    position: Point,
+    anchor: Rect,
     placement: TooltipPlacement,
+    window: Rect,
     theme: &crate::core::Theme,
     max_width: f32,
 ) -> RenderObject {
@@ -109,13 +192,11 @@ pub fn render_tooltip(
     let tooltip_width = text_width + padding * 2.0;
     let tooltip_height = text_height + padding * 2.0;
 
-    // Calculate position based on placement
-    let (x, y) = match placement {
-        TooltipPlacement::Top => (position.x - tooltip_width / 2.0, position.y - tooltip_height - 8.0),
-        TooltipPlacement::Bottom => (position.x - tooltip_width / 2.0, position.y + 8.0),
-        TooltipPlacement::Left => (position.x - tooltip_width - 8.0, position.y - tooltip_height / 2.0),
-        TooltipPlacement::Right => (position.x + 8.0, position.y - tooltip_height / 2.0),
-    };
+    let (rect, _) = PopupPlacement::new(placement.into())
+        .with_align(PopupAlign::Center)
+        .with_offset(8.0)
+        .place(anchor, Size::new(tooltip_width, tooltip_height), window);
+    let (x, y) = (rect.x, rect.y);
 
     let mut render_objects = Vec::new();
 
@@ -152,9 +233,48 @@ pub fn render_tooltip(
             color: theme.popover_foreground,
             bold: false,
             italic: false,
+            ..Default::default()
         },
         Point::new(x + padding, y + padding + 5.0),
     ));
 
     RenderObject::group(render_objects)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::event::EventPhase;
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use crate::runtime::widget_builder::WidgetBuilder;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    fn overlay_bounds(obj: &RenderObject) -> Option<Rect> {
+        match obj {
+            RenderObject::Overlay { child } => child.bounds(),
+            RenderObject::Group { children } => children.iter().find_map(overlay_bounds),
+            RenderObject::Element { child, .. } | RenderObject::Transform { child, .. } => overlay_bounds(child),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_hover_shows_the_tooltip_inside_the_window() {
+        let tooltip = Tooltip::new("Save", Box::new(SizedBox::new(100.0, 30.0)));
+        let root: Box<dyn Widget> = Box::new(tooltip.clone());
+        let builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(new_shared_element_tree());
+        let window = Constraints::new(0.0, 400.0, 0.0, 300.0);
+        assert_eq!(overlay_bounds(&builder.build_widget_tree(&root, window)), None);
+
+        let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+        tooltip.handle_event(&UiEvent::PointerEnter, &mut context);
+        // No room above the top of the window, so it flips below
+        let shown = overlay_bounds(&builder.build_widget_tree(&root, window)).expect("tooltip is showing");
+        assert_eq!(shown.y, 38.0);
+
+        tooltip.handle_event(&UiEvent::PointerLeave, &mut context);
+        assert_eq!(overlay_bounds(&builder.build_widget_tree(&root, window)), None);
+    }
+}
@@ -99,6 +99,7 @@ impl StatelessWidget for Video {
                 color: Color::WHITE,
                 bold: false,
                 italic: false,
+                ..Default::default()
             },
             Point::new(width / 2.0 - 24.0, height / 2.0 + 16.0),
         ));
@@ -0,0 +1,331 @@
+//! Error boundaries
+//!
+//! `ErrorBoundary` builds its whole subtree inside `catch_unwind`. A panic
+//! or an `Err` from the child builder is captured and a fallback is rendered
+//! in place of the subtree, so one broken widget can't take down the app.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::element::{ElementId, SharedElementTree};
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::core::render_object::{Color, Point, Rect, RenderObject, TextStyle};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::ThemeProvider;
+
+/// An error captured by an `ErrorBoundary`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryError {
+    pub message: String,
+    /// True when the error came from a panic rather than a returned `Err`
+    pub panicked: bool,
+}
+
+impl BoundaryError {
+    fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "widget panicked during build".to_string()
+        };
+        Self { message, panicked: true }
+    }
+}
+
+impl std::fmt::Display for BoundaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+type ChildBuilder = Arc<dyn Fn(&BuildContext) -> anyhow::Result<Box<dyn Widget>> + Send + Sync>;
+type FallbackBuilder = Arc<dyn Fn(&BoundaryError) -> Box<dyn Widget> + Send + Sync>;
+
+/// Catches panics and errors from its subtree and renders a fallback
+pub struct ErrorBoundary {
+    pub builder: ChildBuilder,
+    pub fallback: Option<FallbackBuilder>,
+    pub on_error: Option<Arc<dyn Fn(&BoundaryError) + Send + Sync>>,
+    pub on_retry: Option<Arc<dyn Fn() + Send + Sync>>,
+    pub width: f32,
+    pub height: f32,
+    error: Arc<RwLock<Option<BoundaryError>>>,
+    built_child: Arc<RwLock<Option<Box<dyn Widget>>>>,
+    /// Element of the last build, so `retry` can rebuild it
+    element: Arc<RwLock<Option<(ElementId, SharedElementTree)>>>,
+    key: Option<WidgetKey>,
+}
+
+impl ErrorBoundary {
+    /// Guard an existing widget; panics in its build are caught
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        let child: Arc<dyn Widget> = Arc::new(child);
+        Self::try_build(move |_| Ok(child.clone_box()))
+    }
+
+    /// Guard a fallible builder; both `Err` results and panics are caught
+    pub fn try_build<F>(builder: F) -> Self
+    where
+        F: Fn(&BuildContext) -> anyhow::Result<Box<dyn Widget>> + Send + Sync + 'static,
+    {
+        Self {
+            builder: Arc::new(builder),
+            fallback: None,
+            on_error: None,
+            on_retry: None,
+            width: 320.0,
+            height: 96.0,
+            error: Arc::new(RwLock::new(None)),
+            built_child: Arc::new(RwLock::new(None)),
+            element: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            builder: self.builder.clone(),
+            fallback: self.fallback.clone(),
+            on_error: self.on_error.clone(),
+            on_retry: self.on_retry.clone(),
+            width: self.width,
+            height: self.height,
+            error: self.error.clone(),
+            built_child: self.built_child.clone(),
+            element: self.element.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    /// Custom fallback UI for a captured error
+    pub fn with_fallback<F>(mut self, fallback: F) -> Self
+    where
+        F: Fn(&BoundaryError) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+
+    /// Called once each time an error is captured
+    pub fn with_on_error<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&BoundaryError) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called when the user presses "Retry" in the default fallback
+    pub fn with_on_retry<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(callback));
+        self
+    }
+
+    /// Size of the default fallback panel
+    pub fn with_fallback_size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// The currently captured error, if any
+    pub fn error(&self) -> Option<BoundaryError> {
+        self.error.read().clone()
+    }
+
+    /// Clear the captured error and rebuild the subtree
+    pub fn retry(&self) {
+        *self.error.write() = None;
+        if let Some((id, tree)) = self.element.read().as_ref() {
+            tree.write().mark_dirty(*id);
+        }
+        if let Some(on_retry) = &self.on_retry {
+            on_retry();
+        }
+    }
+
+    fn capture(&self, error: BoundaryError) {
+        if let Some(on_error) = &self.on_error {
+            on_error(&error);
+        }
+        *self.built_child.write() = None;
+        *self.error.write() = Some(error);
+    }
+
+    fn retry_rect(&self) -> Rect {
+        Rect::new(16.0, self.height - 40.0, 72.0, 28.0)
+    }
+
+    fn build_fallback(&self, error: &BoundaryError, ctx: &BuildContext) -> WidgetNode {
+        if let Some(fallback) = &self.fallback {
+            return fallback(error).build(ctx);
+        }
+
+        let theme = ctx.theme();
+        let retry = self.retry_rect();
+        let text_style = |size: f32, color: Color, bold: bool| TextStyle {
+            font_family: theme.font_sans.clone(),
+            font_size: size,
+            color,
+            bold,
+            italic: false,
+            ..Default::default()
+        };
+
+        let render_objects = vec![
+            RenderObject::rect(Rect::new(0.0, 0.0, self.width, self.height), theme.destructive.with_alpha(24)),
+            RenderObject::rect(Rect::new(0.0, 0.0, 3.0, self.height), theme.destructive),
+            RenderObject::text(
+                "Something went wrong".to_string(),
+                text_style(14.0, theme.destructive, true),
+                Point::new(16.0, 24.0),
+            ),
+            RenderObject::text(error.message.clone(), text_style(12.0, theme.foreground, false), Point::new(16.0, 44.0)),
+            RenderObject::rect(retry, theme.destructive),
+            RenderObject::text(
+                "Retry".to_string(),
+                text_style(13.0, theme.destructive_foreground, true),
+                Point::new(retry.x + 18.0, retry.y + 18.0),
+            ),
+        ];
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+}
+
+impl Widget for ErrorBoundary {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        *self.element.write() = Some((ctx.element_id, ctx.element_tree.clone()));
+        if let Some(error) = self.error() {
+            return self.build_fallback(&error, ctx);
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let child = (self.builder)(ctx)?;
+            // Children are built here rather than by the caller, so their
+            // panics are caught too
+            let node = match child.build(ctx) {
+                WidgetNode::Container { children } => {
+                    WidgetNode::Leaf(RenderObject::group(ctx.build_children(&children)))
+                }
+                node => node,
+            };
+            Ok::<_, anyhow::Error>((child, node))
+        }));
+
+        match result {
+            Ok(Ok((child, node))) => {
+                *self.built_child.write() = Some(child);
+                node
+            }
+            Ok(Err(err)) => {
+                let error = BoundaryError { message: err.to_string(), panicked: false };
+                self.capture(error.clone());
+                self.build_fallback(&error, ctx)
+            }
+            Err(payload) => {
+                let error = BoundaryError::from_panic(payload);
+                self.capture(error.clone());
+                self.build_fallback(&error, ctx)
+            }
+        }
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if self.error.read().is_some() {
+            return match event {
+                UiEvent::PointerUp { position, button: MouseButton::Left, .. }
+                    if self.fallback.is_none() && self.retry_rect().contains(position.x, position.y) =>
+                {
+                    self.retry();
+                    context.mark_dirty();
+                    EventResult::Stopped
+                }
+                _ => EventResult::Unhandled,
+            };
+        }
+
+        let child = match self.built_child.read().as_ref() {
+            Some(child) => child.clone_box(),
+            None => return EventResult::Unhandled,
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| child.handle_event(event, context))) {
+            Ok(result) => result,
+            Err(payload) => {
+                self.capture(BoundaryError::from_panic(payload));
+                EventResult::Stopped
+            }
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use crate::widgets::basic::Column;
+
+    #[derive(Clone)]
+    struct Panicker;
+
+    impl Widget for Panicker {
+        fn build(&self, _ctx: &BuildContext) -> WidgetNode {
+            panic!("grandchild failed");
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_panics_below_a_container_are_caught_and_retry_rebuilds() {
+        let boundary = ErrorBoundary::new(Column::new().with_children(vec![Box::new(Panicker)]));
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &boundary);
+        let ctx = BuildContext::new(id, tree.clone(), Constraints::new(0.0, 400.0, 0.0, 300.0), Arc::new(Theme::default()));
+
+        boundary.build(&ctx);
+        let error = boundary.error().expect("the grandchild's panic is captured");
+        assert_eq!(error.message, "grandchild failed");
+        assert!(error.panicked);
+
+        tree.write().get_mut(id).unwrap().dirty = false;
+        boundary.retry();
+        assert!(boundary.error().is_none());
+        assert!(tree.read().get(id).unwrap().dirty);
+    }
+}
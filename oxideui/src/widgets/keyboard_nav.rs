@@ -0,0 +1,151 @@
+//! Keyboard navigation shared by list-like widgets
+//!
+//! Dropdown, Combobox and Table keep a `ListNavigator` behind an
+//! `Arc<RwLock<..>>` so the highlighted row, popup state and typeahead
+//! buffer survive rebuilds, the same way they keep their last density.
+
+use std::time::Duration;
+
+use web_time::Instant;
+use winit::keyboard::KeyCode;
+
+/// Typed characters within this window extend the typeahead search
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Which arrow keys move through a list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NavAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// The index `key` moves to from `current` in a list of `len` items, or
+/// `None` if the key doesn't navigate. Arrows along `axis` step by one,
+/// Home/End jump to the ends and PageUp/PageDown step by `page`.
+pub(crate) fn step_index(current: Option<usize>, key: KeyCode, axis: NavAxis, len: usize, page: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let last = len - 1;
+    let (back, forward) = match axis {
+        NavAxis::Horizontal => (KeyCode::ArrowLeft, KeyCode::ArrowRight),
+        NavAxis::Vertical => (KeyCode::ArrowUp, KeyCode::ArrowDown),
+    };
+    let next = match (key, current) {
+        (KeyCode::Home, _) => 0,
+        (KeyCode::End, _) => last,
+        // Nothing highlighted yet: the first press lands on an end
+        (k, None) if k == forward || k == KeyCode::PageDown => 0,
+        (k, None) if k == back || k == KeyCode::PageUp => last,
+        (k, Some(i)) if k == forward => (i + 1).min(last),
+        (k, Some(i)) if k == back => i.saturating_sub(1),
+        (KeyCode::PageDown, Some(i)) => (i + page.max(1)).min(last),
+        (KeyCode::PageUp, Some(i)) => i.saturating_sub(page.max(1)),
+        _ => return None,
+    };
+    Some(next)
+}
+
+/// Highlight, popup and typeahead state of a keyboard-driven list
+#[derive(Debug)]
+pub(crate) struct ListNavigator {
+    /// Row the keyboard is on, which may differ from the selection
+    pub highlighted: Option<usize>,
+    /// Open state toggled by the keyboard; `None` defers to the widget's prop
+    open: Option<bool>,
+    typeahead: String,
+    last_typed: Instant,
+}
+
+impl ListNavigator {
+    pub fn new() -> Self {
+        Self {
+            highlighted: None,
+            open: None,
+            typeahead: String::new(),
+            last_typed: Instant::now(),
+        }
+    }
+
+    pub fn is_open(&self, default: bool) -> bool {
+        self.open.unwrap_or(default)
+    }
+
+    pub fn set_open(&mut self, open: bool) {
+        self.open = Some(open);
+        self.typeahead.clear();
+    }
+
+    /// Move the highlight for a vertical list; returns whether `key` navigated
+    pub fn move_key(&mut self, key: KeyCode, len: usize, page: usize) -> bool {
+        match step_index(self.highlighted, key, NavAxis::Vertical, len, page) {
+            Some(index) => {
+                self.highlighted = Some(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add `c` to the typeahead buffer and highlight the first label starting
+    /// with it, case-insensitively. Repeating a single character cycles
+    /// through the labels sharing that initial, as native list boxes do.
+    pub fn typeahead<'a>(&mut self, c: char, labels: impl IntoIterator<Item = &'a str>) -> Option<usize> {
+        let now = Instant::now();
+        if now.duration_since(self.last_typed) > TYPEAHEAD_TIMEOUT {
+            self.typeahead.clear();
+        }
+        self.last_typed = now;
+        self.typeahead.extend(c.to_lowercase());
+
+        let labels: Vec<String> = labels.into_iter().map(str::to_lowercase).collect();
+        let mut chars = self.typeahead.chars();
+        let repeated = chars.next().filter(|first| chars.all(|c| c == *first));
+
+        let (query, start) = match repeated {
+            Some(first) => (first.to_string(), self.highlighted.map_or(0, |i| i + 1)),
+            None => (self.typeahead.clone(), self.highlighted.unwrap_or(0)),
+        };
+        let found = (0..labels.len())
+            .map(|offset| (start + offset) % labels.len())
+            .find(|&i| labels[i].starts_with(&query))?;
+        self.highlighted = Some(found);
+        Some(found)
+    }
+}
+
+impl Default for ListNavigator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_navigation() {
+        let mut nav = ListNavigator::new();
+        assert!(nav.move_key(KeyCode::ArrowDown, 5, 3));
+        assert_eq!(nav.highlighted, Some(0));
+        nav.move_key(KeyCode::PageDown, 5, 3);
+        assert_eq!(nav.highlighted, Some(3));
+        nav.move_key(KeyCode::ArrowDown, 5, 3);
+        nav.move_key(KeyCode::ArrowDown, 5, 3);
+        assert_eq!(nav.highlighted, Some(4));
+        nav.move_key(KeyCode::Home, 5, 3);
+        assert_eq!(nav.highlighted, Some(0));
+        assert!(!nav.move_key(KeyCode::ArrowLeft, 5, 3));
+
+        let labels = ["Apple", "Banana", "Blueberry", "Cherry"];
+        assert_eq!(nav.typeahead('b', labels), Some(1));
+        assert_eq!(nav.typeahead('l', labels), Some(2));
+
+        let mut nav = ListNavigator::new();
+        assert_eq!(nav.typeahead('b', labels), Some(1));
+        assert_eq!(nav.typeahead('b', labels), Some(2));
+        assert_eq!(nav.typeahead('b', labels), Some(1));
+        assert_eq!(nav.typeahead('z', labels), None);
+    }
+}
@@ -0,0 +1,309 @@
+//! Layouts from relations between children
+//!
+//! Each child of a `ConstraintLayout` has an id and declares how its edges
+//! relate to its siblings' and the layout's own, and the Cassowary solver
+//! finds positions and sizes satisfying them all. Children keep their
+//! natural size unless a constraint says otherwise.
+//!
+//! ```rust,ignore
+//! ConstraintLayout::new()
+//!     .add_child("avatar", Box::new(avatar), vec![
+//!         LayoutConstraint::equal(Edge::Left.of("avatar"), Edge::Left.of(PARENT) + 16.0),
+//!         LayoutConstraint::equal(Edge::CenterY.of("avatar"), Edge::CenterY.of(PARENT)),
+//!     ])
+//!     .add_child("name", Box::new(name), vec![
+//!         LayoutConstraint::equal(Edge::Left.of("name"), Edge::Right.of("avatar") + 8.0),
+//!         LayoutConstraint::at_most(Edge::Right.of("name"), Edge::Right.of(PARENT) - 16.0),
+//!     ])
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{debug_hash, Constraints, Expression, LayoutConstraint, LayoutSolver, Size, Strength};
+use crate::widgets::layout_widgets::reserve;
+
+/// Id children use to refer to the layout itself
+pub const PARENT: &str = "parent";
+
+/// An edge or extent of a child, to constrain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    Width,
+    Height,
+}
+
+impl Edge {
+    /// This edge of the child `id`, or of the layout for `PARENT`
+    pub fn of(self, id: &str) -> Expression {
+        let var = |name: &str| Expression::var(format!("{id}.{name}"));
+        match self {
+            Edge::Left => var("left"),
+            Edge::Top => var("top"),
+            Edge::Width => var("width"),
+            Edge::Height => var("height"),
+            Edge::Right => var("left") + var("width"),
+            Edge::Bottom => var("top") + var("height"),
+            Edge::CenterX => var("left") + var("width") * 0.5,
+            Edge::CenterY => var("top") + var("height") * 0.5,
+        }
+    }
+}
+
+/// The last solution, kept in the layout's element until its constraints
+/// or its children's sizes change
+struct Solution {
+    inputs: u64,
+    values: HashMap<String, f32>,
+}
+
+/// Places children by solving the constraints between them
+pub struct ConstraintLayout {
+    /// Children with the ids their constraints use
+    pub children: Vec<(String, Box<dyn Widget>)>,
+    pub constraints: Vec<LayoutConstraint>,
+    key: Option<WidgetKey>,
+}
+
+impl ConstraintLayout {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            constraints: Vec::new(),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            children: self.children.iter().map(|(id, child)| (id.clone(), child.clone_box())).collect(),
+            constraints: self.constraints.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    /// Add `child` as `id`, placed by `constraints`
+    pub fn add_child(mut self, id: &str, child: Box<dyn Widget>, constraints: Vec<LayoutConstraint>) -> Self {
+        self.children.push((id.to_string(), child));
+        self.constraints.extend(constraints);
+        self
+    }
+
+    pub fn add_constraint(mut self, constraint: LayoutConstraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Solve for every edge of the layout and its children, keyed by
+    /// variable name
+    fn solve(&self, constraints: &Constraints, natural: &[Size]) -> HashMap<String, f32> {
+        let mut solver = LayoutSolver::new();
+        for constraint in self.base_constraints(constraints, natural) {
+            let _ = solver.add_constraint(constraint);
+        }
+        for constraint in &self.constraints {
+            if let Err(e) = solver.add_constraint(constraint.clone()) {
+                eprintln!("⚠️ Ignoring layout constraint: {}", e);
+            }
+        }
+        let mut values = HashMap::new();
+        for id in std::iter::once(PARENT).chain(self.children.iter().map(|(id, _)| id.as_str())) {
+            for name in ["left", "top", "width", "height"] {
+                let var = format!("{id}.{name}");
+                if let Some(value) = solver.get_value(&var) {
+                    values.insert(var, value);
+                }
+            }
+        }
+        values
+    }
+
+    /// Constraints every layout starts from: the layout at the origin,
+    /// filling bounded space and wrapping its children otherwise, and each
+    /// child preferring the size it measured
+    fn base_constraints(&self, constraints: &Constraints, natural: &[Size]) -> Vec<LayoutConstraint> {
+        let mut base = vec![
+            LayoutConstraint::equal(Edge::Left.of(PARENT), 0.0),
+            LayoutConstraint::equal(Edge::Top.of(PARENT), 0.0),
+            LayoutConstraint::at_least(Edge::Width.of(PARENT), constraints.min_width),
+            LayoutConstraint::at_least(Edge::Height.of(PARENT), constraints.min_height),
+        ];
+        let axes = [
+            (Edge::Width, Edge::Right, constraints.max_width),
+            (Edge::Height, Edge::Bottom, constraints.max_height),
+        ];
+        for (extent, far_edge, max) in axes {
+            if max.is_finite() {
+                base.push(LayoutConstraint::at_most(extent.of(PARENT), max));
+                base.push(LayoutConstraint::equal(extent.of(PARENT), max).with_strength(Strength::STRONG));
+            } else {
+                base.extend(
+                    self.children
+                        .iter()
+                        .map(|(id, _)| LayoutConstraint::at_least(far_edge.of(PARENT), far_edge.of(id))),
+                );
+                base.push(LayoutConstraint::equal(extent.of(PARENT), 0.0).with_strength(Strength::WEAK));
+            }
+        }
+        for ((id, _), size) in self.children.iter().zip(natural) {
+            for (extent, natural) in [(Edge::Width, size.width), (Edge::Height, size.height)] {
+                base.push(LayoutConstraint::at_least(extent.of(id), 0.0));
+                base.push(LayoutConstraint::equal(extent.of(id), natural).with_strength(Strength::MEDIUM));
+            }
+        }
+        base
+    }
+}
+
+impl Default for ConstraintLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for ConstraintLayout {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let mounted: Vec<BuildContext> = self.children.iter().map(|(_, child)| ctx.mount_child(child.as_ref())).collect();
+        let build = |index: usize, constraints: Constraints| {
+            let child_ctx = &mounted[index];
+            match child_ctx.child_context(child_ctx.element_id, constraints).build_element(self.children[index].1.as_ref()) {
+                WidgetNode::Leaf(render_obj) => render_obj,
+                _ => RenderObject::None,
+            }
+        };
+        let built: Vec<RenderObject> = (0..self.children.len()).map(|i| build(i, ctx.constraints.loosen())).collect();
+        let natural: Vec<Size> = built.iter().map(RenderObject::layout_size).collect();
+
+        let ids: Vec<&str> = self.children.iter().map(|(id, _)| id.as_str()).collect();
+        let inputs = debug_hash(&(ctx.constraints, &natural, &ids, &self.constraints));
+        let cached = ctx.element_tree.read().get(ctx.element_id).and_then(|element| {
+            let solution = element.state.as_ref()?.downcast_ref::<Solution>()?;
+            (solution.inputs == inputs).then(|| solution.values.clone())
+        });
+        let values = cached.unwrap_or_else(|| {
+            let values = self.solve(&ctx.constraints, &natural);
+            if let Some(element) = ctx.element_tree.write().get_mut(ctx.element_id) {
+                element.state = Some(Box::new(Solution { inputs, values: values.clone() }));
+            }
+            values
+        });
+        let value = |id: &str, name: &str| values.get(&format!("{id}.{name}")).copied().unwrap_or(0.0);
+
+        let mut layers = RenderObject::child_vec(built.len());
+        for (i, ((id, _), render_obj)) in self.children.iter().zip(built).enumerate() {
+            let size = Size::new(value(id, "width").max(0.0), value(id, "height").max(0.0));
+            let render_obj = if size == natural[i] {
+                render_obj
+            } else {
+                build(i, Constraints::tight(size))
+            };
+            layers.push(RenderObject::transform(
+                Matrix::translate(value(id, "left"), value(id, "top")),
+                render_obj,
+            ));
+        }
+
+        let size = ctx.constraints.constrain(Size::new(value(PARENT, "width"), value(PARENT, "height")));
+        WidgetNode::Leaf(reserve(size, RenderObject::group(layers)))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::Theme;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_children_follow_their_relations() {
+        let layout = ConstraintLayout::new()
+            .add_child(
+                "avatar",
+                Box::new(SizedBox::square(40.0)),
+                vec![
+                    LayoutConstraint::equal(Edge::Left.of("avatar"), Edge::Left.of(PARENT) + 16.0),
+                    LayoutConstraint::equal(Edge::CenterY.of("avatar"), Edge::CenterY.of(PARENT)),
+                ],
+            )
+            .add_child(
+                "name",
+                Box::new(SizedBox::new(100.0, 20.0)),
+                vec![
+                    LayoutConstraint::equal(Edge::Left.of("name"), Edge::Right.of("avatar") + 8.0),
+                    LayoutConstraint::at_most(Edge::Right.of("name"), Edge::Left.of("badge") - 8.0),
+                    LayoutConstraint::equal(Edge::Top.of("name"), Edge::Top.of("avatar")),
+                ],
+            )
+            .add_child(
+                "badge",
+                Box::new(SizedBox::square(24.0)),
+                vec![
+                    LayoutConstraint::equal(Edge::Right.of("badge"), Edge::Right.of(PARENT) - 16.0),
+                    LayoutConstraint::equal(Edge::Bottom.of("badge"), Edge::Bottom.of("avatar")),
+                ],
+            );
+
+        let tree = new_shared_element_tree();
+        let root = tree.write().create_element(&layout, None, 0);
+        let bounds = |width: f32| {
+            let ctx = BuildContext::new(
+                root,
+                tree.clone(),
+                Constraints::loose(Size::new(width, 100.0)),
+                Arc::new(Theme::default()),
+            );
+            let WidgetNode::Leaf(render_obj) = layout.build(&ctx) else {
+                panic!("expected the laid out children");
+            };
+            assert_eq!(render_obj.layout_size(), Size::new(width, 100.0));
+            let RenderObject::Group { children } = &render_obj else {
+                panic!("expected a group");
+            };
+            let RenderObject::Group { children: placed } = &children[1] else {
+                panic!("expected the placed children");
+            };
+            placed
+                .iter()
+                .map(|child| child.bounds().map(|b| (b.x, b.y, b.width, b.height)).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // The name follows the avatar; the badge sits in the corner, level
+        // with the avatar's bottom
+        assert_eq!(
+            bounds(300.0),
+            vec![(16.0, 30.0, 40.0, 40.0), (64.0, 30.0, 100.0, 20.0), (260.0, 46.0, 24.0, 24.0)]
+        );
+        // A narrower layout is solved again rather than reusing the last
+        // solution
+        assert_eq!(bounds(250.0)[2], (210.0, 46.0, 24.0, 24.0));
+    }
+}
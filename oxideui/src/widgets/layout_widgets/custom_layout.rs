@@ -0,0 +1,163 @@
+//! Layouts defined by the app
+//!
+//! `CustomMultiChildLayout` delegates sizing and placement to a
+//! `MultiChildLayoutDelegate`, for arrangements the built-in layouts don't
+//! cover: radial menus, graph nodes, timelines. The delegate picks the
+//! constraints each child is built with, then places it knowing its size.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, Point, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, Size};
+use crate::widgets::layout_widgets::reserve;
+
+/// Sizes and positions the children of a `CustomMultiChildLayout`.
+/// Children are identified by their index among `count` children.
+pub trait MultiChildLayoutDelegate: Send + Sync + 'static {
+    /// Size of the whole layout; defaults to all the space available
+    fn size(&self, constraints: &Constraints) -> Size {
+        constraints.biggest()
+    }
+
+    /// Constraints child `index` is built with, inside a layout of `size`
+    fn measure_child(&self, _index: usize, _count: usize, size: Size) -> Constraints {
+        Constraints::loose(size)
+    }
+
+    /// Top-left corner of child `index`, which built at `child_size`
+    fn position_child(&self, index: usize, count: usize, child_size: Size, size: Size) -> Point;
+}
+
+/// Lays its children out with a user-supplied delegate
+pub struct CustomMultiChildLayout {
+    pub delegate: Arc<dyn MultiChildLayoutDelegate>,
+    pub children: Vec<Box<dyn Widget>>,
+    key: Option<WidgetKey>,
+}
+
+impl CustomMultiChildLayout {
+    pub fn new<D: MultiChildLayoutDelegate>(delegate: D) -> Self {
+        Self {
+            delegate: Arc::new(delegate),
+            children: Vec::new(),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            delegate: self.delegate.clone(),
+            children: self.children.iter().map(|c| c.clone_box()).collect(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn add_child(mut self, child: Box<dyn Widget>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for CustomMultiChildLayout {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let count = self.children.len();
+        let size = ctx.constraints.constrain(self.delegate.size(&ctx.constraints));
+
+        let mut layers = RenderObject::child_vec(count);
+        for (i, child) in self.children.iter().enumerate() {
+            let constraints = self.delegate.measure_child(i, count, size);
+            let render_obj = match ctx.child_context(ctx.element_id, constraints).build_child(child.as_ref()) {
+                WidgetNode::Leaf(render_obj) => render_obj,
+                _ => continue,
+            };
+            let child_size = constraints.constrain(render_obj.layout_size());
+            let origin = self.delegate.position_child(i, count, child_size, size);
+            layers.push(RenderObject::transform(Matrix::translate(origin.x, origin.y), render_obj));
+        }
+
+        WidgetNode::Leaf(reserve(size, RenderObject::group(layers)))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::Theme;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    /// Children evenly spaced on a circle, starting at the top
+    struct Radial {
+        radius: f32,
+    }
+
+    impl MultiChildLayoutDelegate for Radial {
+        fn position_child(&self, index: usize, count: usize, child_size: Size, size: Size) -> Point {
+            let angle = index as f32 / count as f32 * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+            Point::new(
+                size.width / 2.0 + self.radius * angle.cos() - child_size.width / 2.0,
+                size.height / 2.0 + self.radius * angle.sin() - child_size.height / 2.0,
+            )
+        }
+    }
+
+    #[test]
+    fn test_delegate_positions_children() {
+        let menu = CustomMultiChildLayout::new(Radial { radius: 80.0 })
+            .with_children((0..4).map(|_| Box::new(SizedBox::square(20.0)) as Box<dyn Widget>).collect());
+        let tree = new_shared_element_tree();
+        let root = tree.write().create_element(&menu, None, 0);
+        let ctx = BuildContext::new(
+            root,
+            tree.clone(),
+            Constraints::loose(Size::new(200.0, 200.0)),
+            Arc::new(Theme::default()),
+        );
+
+        let WidgetNode::Leaf(render_obj) = menu.build(&ctx) else {
+            panic!("expected the laid out menu");
+        };
+        assert_eq!(render_obj.layout_size(), Size::new(200.0, 200.0));
+        let RenderObject::Group { children } = &render_obj else {
+            panic!("expected a group");
+        };
+        let RenderObject::Group { children: items } = &children[1] else {
+            panic!("expected the menu items");
+        };
+        let centers: Vec<(f32, f32)> = items
+            .iter()
+            .map(|item| {
+                let bounds = item.bounds().unwrap();
+                ((bounds.x + 10.0).round(), (bounds.y + 10.0).round())
+            })
+            .collect();
+        // Top, right, bottom, left of the center
+        assert_eq!(centers, vec![(100.0, 20.0), (180.0, 100.0), (100.0, 180.0), (20.0, 100.0)]);
+        assert_eq!(tree.read().get_children(root).len(), 4);
+    }
+}
@@ -0,0 +1,241 @@
+use std::any::Any;
+use std::sync::Arc;
+use parking_lot::RwLock;
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::core::render_object::{Matrix, Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::constraints::{Axis, Constraints, Size};
+use crate::layout::{DockLayout, DockSplit};
+use crate::platform::window::{CursorShape, WindowController};
+use crate::ThemeProvider;
+
+const DIVIDER_SIZE: f32 = 4.0;
+/// Dividers are easier to grab than they look
+const DIVIDER_HIT_SIZE: f32 = 8.0;
+
+/// Split panes for a `DockLayout`: each open panel is built in its rect and
+/// dragging a divider between panels moves it. Fills its constraints.
+pub struct DockView {
+    pub layout: Arc<RwLock<DockLayout>>,
+    /// Content of each panel by id; panels without content stay empty
+    pub panels: Vec<(String, Box<dyn Widget>)>,
+    pub on_layout_change: Option<Arc<dyn Fn(&DockLayout) + Send + Sync>>,
+    size: Arc<RwLock<Size>>,
+    /// Index of the split whose divider is held
+    dragging: Arc<RwLock<Option<usize>>>,
+    window: Arc<RwLock<Option<WindowController>>>,
+    key: Option<WidgetKey>,
+}
+
+impl DockView {
+    pub fn new(layout: Arc<RwLock<DockLayout>>) -> Self {
+        Self {
+            layout,
+            panels: Vec::new(),
+            on_layout_change: None,
+            size: Arc::new(RwLock::new(Size::zero())),
+            dragging: Arc::new(RwLock::new(None)),
+            window: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    pub fn with_panel(mut self, id: impl Into<String>, content: impl Widget + 'static) -> Self {
+        self.panels.push((id.into(), Box::new(content)));
+        self
+    }
+
+    /// Called after a divider moves, e.g. to save the layout
+    pub fn with_on_layout_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&DockLayout) + Send + Sync + 'static,
+    {
+        self.on_layout_change = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            layout: self.layout.clone(),
+            panels: self.panels.iter().map(|(id, content)| (id.clone(), content.clone_box())).collect(),
+            on_layout_change: self.on_layout_change.clone(),
+            size: self.size.clone(),
+            dragging: self.dragging.clone(),
+            window: self.window.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    fn splits(&self) -> Vec<DockSplit> {
+        let size = *self.size.read();
+        self.layout.read().splits(Rect::new(0.0, 0.0, size.width, size.height))
+    }
+
+    /// The split whose divider is under (x, y); inner splits win, as their
+    /// dividers end on the outer ones
+    fn divider_at(&self, x: f32, y: f32) -> Option<(usize, DockSplit)> {
+        self.splits()
+            .into_iter()
+            .enumerate()
+            .rev()
+            .find(|(_, split)| split.divider(DIVIDER_HIT_SIZE).contains(x, y))
+    }
+
+    fn drag_to(&self, index: usize, x: f32, y: f32) {
+        let Some(split) = self.splits().get(index).copied() else {
+            return;
+        };
+        let mut layout = self.layout.write();
+        layout.set_ratio(index, split.ratio_at(x, y));
+        if let Some(on_layout_change) = &self.on_layout_change {
+            on_layout_change(&layout);
+        }
+    }
+
+    fn set_cursor(&self, split: DockSplit) {
+        if let Some(window) = self.window.read().as_ref() {
+            window.set_cursor(match split.axis {
+                Axis::Horizontal => CursorShape::ColumnResize,
+                Axis::Vertical => CursorShape::RowResize,
+            });
+        }
+    }
+}
+
+impl Widget for DockView {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let theme = ctx.theme();
+        let size = Size::new(ctx.constraints.max_width, ctx.constraints.max_height);
+        *self.size.write() = size;
+        *self.window.write() = ctx.window().cloned();
+
+        let layout = self.layout.read();
+        let bounds = Rect::new(0.0, 0.0, size.width, size.height);
+        let mut render_objects = Vec::new();
+        for (id, rect) in layout.panel_rects(bounds) {
+            let Some(index) = self.panels.iter().position(|(panel, _)| *panel == id) else {
+                continue;
+            };
+            // Keyed by position in `panels`, so panels keep their elements
+            // as others open and close
+            let content = self.panels[index].1.as_ref();
+            let panel_ctx = ctx.child_context(ctx.element_id, Constraints::tight(Size::new(rect.width, rect.height)));
+            if let WidgetNode::Leaf(obj) = panel_ctx.mount_item(index, content).build_element(content) {
+                let placed = RenderObject::transform(Matrix::translate(rect.x, rect.y), obj);
+                render_objects.push(RenderObject::clip(rect, placed));
+            }
+        }
+        for split in layout.splits(bounds) {
+            render_objects.push(RenderObject::rect(split.divider(DIVIDER_SIZE), theme.border));
+        }
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        match event {
+            // Seen while capturing, before the panel next to the divider
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } => {
+                let Some((index, _)) = self.divider_at(position.x, position.y) else {
+                    return EventResult::Unhandled;
+                };
+                *self.dragging.write() = Some(index);
+                context.capture_pointer(context.current_target);
+                EventResult::Stopped
+            }
+            UiEvent::PointerMove { position, .. } => {
+                let dragging = *self.dragging.read();
+                match dragging {
+                    Some(index) => {
+                        self.drag_to(index, position.x, position.y);
+                        context.mark_dirty();
+                        EventResult::Stopped
+                    }
+                    None => {
+                        if let Some((_, split)) = self.divider_at(position.x, position.y) {
+                            self.set_cursor(split);
+                        }
+                        EventResult::Unhandled
+                    }
+                }
+            }
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } => {
+                let Some(index) = self.dragging.write().take() else {
+                    return EventResult::Unhandled;
+                };
+                self.drag_to(index, position.x, position.y);
+                context.mark_dirty();
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::event::EventPhase;
+    use crate::core::render_object::Point;
+    use crate::core::Theme;
+    use crate::layout::DockNode;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_panels_are_built_in_their_rects_and_dividers_drag() {
+        let layout = Arc::new(RwLock::new(DockLayout::new(DockNode::split(
+            Axis::Horizontal,
+            0.5,
+            DockNode::panel("files"),
+            DockNode::panel("editor"),
+        ))));
+        layout.write().close_panel("files");
+        let view = DockView::new(layout.clone())
+            .with_panel("files", SizedBox::new(10.0, 10.0))
+            .with_panel("editor", SizedBox::new(10.0, 10.0));
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &view);
+        let ctx = BuildContext::new(id, tree.clone(), Constraints::new(0.0, 400.0, 0.0, 300.0), Arc::new(Theme::default()));
+        view.build(&ctx);
+        assert_eq!(tree.read().get_children(id).len(), 1);
+        let editor = tree.read().get_children(id)[0];
+
+        // Reopening a panel keeps the other's element
+        layout.write().open_panel("files", "editor", Axis::Horizontal).unwrap();
+        view.build(&ctx);
+        let children = tree.read().get_children(id);
+        assert_eq!(children.len(), 2);
+        assert!(children.contains(&editor));
+
+        let mut context = EventContext::new(id, id, EventPhase::Capturing);
+        let down = UiEvent::PointerDown { id: 0, position: Point::new(201.0, 100.0), button: MouseButton::Left };
+        assert_eq!(view.handle_event(&down, &mut context), EventResult::Stopped);
+        let up = UiEvent::PointerUp { id: 0, position: Point::new(100.0, 100.0), button: MouseButton::Left };
+        assert_eq!(view.handle_event(&up, &mut context), EventResult::Stopped);
+        assert_eq!(
+            layout.read().panel_rects(Rect::new(0.0, 0.0, 400.0, 300.0))[1],
+            ("files".to_string(), Rect::new(100.0, 0.0, 300.0, 300.0))
+        );
+        // Presses away from a divider reach the panels
+        assert_eq!(view.handle_event(&down, &mut context), EventResult::Unhandled);
+    }
+}
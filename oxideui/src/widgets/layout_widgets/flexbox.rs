@@ -1,6 +1,10 @@
 use std::any::Any;
 use crate::core::context::BuildContext;
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::FlexLayout;
+use crate::widgets::layout_widgets::flexible::build_flex;
+
+pub use crate::layout::{AlignItems, FlexDirection, FlexWrap, JustifyContent};
 
 pub struct Flexbox {
     pub direction: FlexDirection,
@@ -12,40 +16,6 @@ pub struct Flexbox {
     key: Option<WidgetKey>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum FlexDirection {
-    Row,
-    RowReverse,
-    Column,
-    ColumnReverse,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum JustifyContent {
-    FlexStart,
-    FlexEnd,
-    Center,
-    SpaceBetween,
-    SpaceAround,
-    SpaceEvenly,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum AlignItems {
-    FlexStart,
-    FlexEnd,
-    Center,
-    Stretch,
-    Baseline,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum FlexWrap {
-    NoWrap,
-    Wrap,
-    WrapReverse,
-}
-
 impl Flexbox {
     pub fn new() -> Self {
         Self {
@@ -113,12 +83,16 @@ impl Flexbox {
 }
 
 impl StatelessWidget for Flexbox {
-    fn build_stateless(&self, _ctx: &BuildContext) -> WidgetNode {
-        // For now, just return the children as a container
-        // In a real implementation, we would calculate flexbox layout
-        WidgetNode::Container {
-            children: self.children.iter().map(|c| c.clone_box()).collect(),
-        }
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let flex = FlexLayout {
+            direction: self.direction,
+            justify_content: self.justify,
+            align_items: self.align,
+            wrap: self.wrap,
+            gap: self.gap,
+            ..FlexLayout::default()
+        };
+        build_flex(ctx, self.children.iter().map(|c| c.as_ref()), flex)
     }
 }
 
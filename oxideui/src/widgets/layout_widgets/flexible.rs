@@ -0,0 +1,375 @@
+//! Flex children
+//!
+//! Inside a `Row`, `Column` or `Flexbox`, `Expanded` and `Flexible` take a
+//! share of the space left over after their siblings are laid out, in
+//! proportion to their flex factors. `Expanded` always fills its share;
+//! `Flexible` may be smaller. Elsewhere both just build their child.
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{AlignItems, Constraints, FlexItem, FlexLayout, LayoutEngine, LayoutNode, LayoutType, Size};
+
+/// Whether a flexible child must fill its share
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FlexFit {
+    /// Exactly its share
+    Tight,
+    /// Up to its share
+    #[default]
+    Loose,
+}
+
+/// Takes a share of the free space, up to its share when `fit` is loose
+pub struct Flexible {
+    pub child: Box<dyn Widget>,
+    pub flex: u32,
+    /// How much it gives up when siblings overflow; 0 never shrinks below
+    /// `basis`
+    pub shrink: f32,
+    /// Size before free space is shared out; defaults to zero
+    pub basis: Option<f32>,
+    pub fit: FlexFit,
+    key: Option<WidgetKey>,
+}
+
+impl Flexible {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            flex: 1,
+            shrink: 1.0,
+            basis: None,
+            fit: FlexFit::Loose,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            flex: self.flex,
+            shrink: self.shrink,
+            basis: self.basis,
+            fit: self.fit,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_flex(mut self, flex: u32) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    pub fn with_shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink.max(0.0);
+        self
+    }
+
+    pub fn with_basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis.max(0.0));
+        self
+    }
+
+    pub fn with_fit(mut self, fit: FlexFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn flex_item(&self) -> FlexItem {
+        FlexItem {
+            flex_grow: self.flex as f32,
+            flex_shrink: self.shrink,
+            flex_basis: self.basis,
+            align_self: None,
+        }
+    }
+}
+
+impl Widget for Flexible {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        ctx.build_child(self.child.as_ref())
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Fills its share of the free space
+pub struct Expanded {
+    pub child: Box<dyn Widget>,
+    pub flex: u32,
+    key: Option<WidgetKey>,
+}
+
+impl Expanded {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            flex: 1,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            flex: self.flex,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_flex(mut self, flex: u32) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for Expanded {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        ctx.build_child(self.child.as_ref())
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Flex factors and inner child of an `Expanded` or `Flexible`
+fn flex_parts(widget: &dyn Widget) -> Option<(FlexItem, FlexFit, &dyn Widget)> {
+    if let Some(expanded) = widget.as_any().downcast_ref::<Expanded>() {
+        let item = FlexItem {
+            flex_grow: expanded.flex as f32,
+            ..FlexItem::default()
+        };
+        return Some((item, FlexFit::Tight, expanded.child.as_ref()));
+    }
+    let flexible = widget.as_any().downcast_ref::<Flexible>()?;
+    Some((flexible.flex_item(), flexible.fit, flexible.child.as_ref()))
+}
+
+/// Lay `children` out with the flex engine. Other children are built
+/// first at their natural size, which they keep; flexible ones are then
+/// built to the share of the remaining space the engine gives them.
+pub(crate) fn build_flex<'a>(
+    ctx: &BuildContext,
+    children: impl IntoIterator<Item = &'a dyn Widget>,
+    flex: FlexLayout,
+) -> WidgetNode {
+    let is_row = flex.direction.is_row();
+    let loose = ctx.constraints.loosen();
+    // Each child is mounted once, so one built again at its final size
+    // stays the same element
+    let build = |child_ctx: &BuildContext, child: &dyn Widget, constraints: Constraints| {
+        match child_ctx.child_context(child_ctx.element_id, constraints).build_element(child) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            _ => RenderObject::None,
+        }
+    };
+
+    // Rigid children keep what they built at their natural size; flexible
+    // ones wait for their share
+    let mut built: Vec<(Option<RenderObject>, &dyn Widget, FlexFit, BuildContext)> = Vec::new();
+    let mut nodes = Vec::new();
+    for (i, child) in children.into_iter().enumerate() {
+        match flex_parts(child) {
+            Some((item, fit, inner)) => {
+                nodes.push(LayoutNode::sized(i as u64, Size::zero()).with_flex_item(item));
+                built.push((None, inner, fit, ctx.mount_child(inner)));
+            }
+            None => {
+                let child_ctx = ctx.mount_child(child);
+                let render_obj = build(&child_ctx, child, loose);
+                nodes.push(
+                    LayoutNode::sized(i as u64, render_obj.layout_size())
+                        .with_flex_item(FlexItem::rigid())
+                        .with_baseline(render_obj.baseline()),
+                );
+                built.push((Some(render_obj), child, FlexFit::Tight, child_ctx));
+            }
+        }
+    }
+    let natural: Vec<Size> = nodes.iter().map(|node| node.size).collect();
+
+    let mut root = LayoutNode::new(0, LayoutType::Flex, ctx.constraints)
+        .with_flex(flex)
+        .with_children(nodes);
+    LayoutEngine::new().layout(&mut root);
+
+    let mut layers = RenderObject::child_vec(built.len());
+    for (i, (node, (render_obj, child, fit, child_ctx))) in root.children.iter().zip(built).enumerate() {
+        let stretched = node.flex_item.align_self.unwrap_or(flex.align_items) == AlignItems::Stretch;
+        let render_obj = match render_obj {
+            // Laid out at the size it asked for
+            Some(render_obj) if node.size == natural[i] => render_obj,
+            // Stretched across the line: build again at the final size
+            Some(_) => build(&child_ctx, child, Constraints::tight(node.size)),
+            None => {
+                let (main, cross, max_cross) = if is_row {
+                    (node.size.width, node.size.height, loose.max_height)
+                } else {
+                    (node.size.height, node.size.width, loose.max_width)
+                };
+                let min_main = if fit == FlexFit::Tight { main } else { 0.0 };
+                let (min_cross, max_cross) = if stretched { (cross, cross) } else { (0.0, max_cross) };
+                let constraints = if is_row {
+                    Constraints::new(min_main, main, min_cross, max_cross)
+                } else {
+                    Constraints::new(min_cross, max_cross, min_main, main)
+                };
+                build(&child_ctx, child, constraints)
+            }
+        };
+        let (x, y) = node.position;
+        // RTL rows start at the right edge
+        let x = if is_row { ctx.direction().place(x, node.size.width, root.size.width) } else { x };
+        layers.push(RenderObject::transform(Matrix::translate(x, y), render_obj));
+    }
+
+    WidgetNode::Leaf(RenderObject::group(layers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::Theme;
+    use crate::layout::FlexDirection;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_free_space_is_shared_by_flex_factor() {
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::loose(Size::new(400.0, 50.0)),
+            Arc::new(Theme::default()),
+        );
+        let children: Vec<Box<dyn Widget>> = vec![
+            Box::new(SizedBox::new(100.0, 20.0)),
+            Box::new(Expanded::new(SizedBox::height(20.0))),
+            Box::new(Expanded::new(SizedBox::height(20.0)).with_flex(2)),
+        ];
+        let flex = FlexLayout {
+            gap: 10.0,
+            ..FlexLayout::default()
+        };
+        let WidgetNode::Leaf(RenderObject::Group { children: layers }) =
+            build_flex(&ctx, children.iter().map(|c| c.as_ref()), flex)
+        else {
+            panic!("expected the laid out children");
+        };
+        let bounds: Vec<(f32, f32)> = layers
+            .iter()
+            .map(|layer| layer.bounds().map(|b| (b.x, b.width)).unwrap())
+            .collect();
+        // 400 - 100 - 2 * 10 = 280 left over, split 1:2
+        let expected = [(0.0, 100.0), (110.0, 280.0 / 3.0), (110.0 + 280.0 / 3.0 + 10.0, 560.0 / 3.0)];
+        for ((x, width), (expected_x, expected_width)) in bounds.into_iter().zip(expected) {
+            assert!((x - expected_x).abs() < 1e-3 && (width - expected_width).abs() < 1e-3);
+        }
+
+        // Vertically, the same factors share the height
+        let column = FlexLayout {
+            direction: FlexDirection::Column,
+            ..FlexLayout::default()
+        };
+        let WidgetNode::Leaf(render_obj) = build_flex(&ctx, children[1..].iter().map(|c| c.as_ref()), column) else {
+            panic!("expected the laid out children");
+        };
+        assert!((render_obj.layout_size().height - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_row_aligns_mixed_font_sizes_on_their_baseline() {
+        use crate::core::render_object::TextStyle;
+        use crate::widgets::basic::{Row, Text};
+
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::loose(Size::new(400.0, 100.0)),
+            Arc::new(Theme::default()),
+        );
+        let text = |content: &str, font_size: f32| {
+            Box::new(Text::new(content).with_style(TextStyle { font_size, ..TextStyle::default() })) as Box<dyn Widget>
+        };
+        let row = Row::new()
+            .with_cross_alignment(AlignItems::Baseline)
+            .with_children(vec![text("Total", 12.0), text("$42", 24.0)]);
+
+        let WidgetNode::Leaf(RenderObject::Group { children: layers }) = row.build(&ctx) else {
+            panic!("expected the row's children");
+        };
+        let label = layers[0].baseline().unwrap();
+        let value = layers[1].baseline().unwrap();
+        assert!((label - value).abs() < 1e-4);
+        // The smaller label moved down to meet the larger value
+        assert!(layers[0].bounds().unwrap().y > 0.0);
+    }
+
+    #[test]
+    fn test_children_built_again_stay_one_element() {
+        use crate::widgets::basic::Text;
+
+        let tree = new_shared_element_tree();
+        let row = tree.write().create_element(&Text::new("row"), None, 0);
+        let ctx = BuildContext::new(
+            row,
+            tree.clone(),
+            Constraints::new(0.0, 400.0, 50.0, 50.0),
+            Arc::new(Theme::default()),
+        );
+        let children: Vec<Box<dyn Widget>> = vec![
+            Box::new(SizedBox::new(100.0, 20.0)),
+            Box::new(Expanded::new(SizedBox::height(20.0))),
+        ];
+        // Stretching builds the sized box a second time at the row's full
+        // height
+        let flex = FlexLayout {
+            align_items: AlignItems::Stretch,
+            ..FlexLayout::default()
+        };
+        let WidgetNode::Leaf(render_obj) = build_flex(&ctx, children.iter().map(|c| c.as_ref()), flex) else {
+            panic!("expected the laid out children");
+        };
+        tree.write().record_layout(&render_obj);
+
+        let tree = tree.read();
+        let mounted = tree.get_children(row);
+        assert_eq!(mounted.len(), 2);
+        let bounds: Vec<_> = mounted.iter().map(|id| tree.get(*id).unwrap().bounds.unwrap()).collect();
+        assert_eq!((bounds[0].width, bounds[0].height), (100.0, 50.0));
+        assert_eq!((bounds[1].x, bounds[1].width), (100.0, 300.0));
+    }
+}
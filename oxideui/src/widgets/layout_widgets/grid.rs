@@ -1,46 +1,125 @@
+//! Two-dimensional layout
+//!
+//! `Grid` sizes its columns and rows from `GridTrack`s, as with CSS
+//! `grid-template-columns` and `grid-template-rows`, and places children
+//! into the free cells in order, row by row. A child can span several
+//! cells, be pinned to a column and row, or claim a named area of the
+//! template:
+//!
+//! ```rust,ignore
+//! Grid::new()
+//!     .template_columns(vec![GridTrack::Fixed(200.0), GridTrack::Flex(1.0)])
+//!     .template_rows(vec![GridTrack::Auto, GridTrack::Flex(1.0)])
+//!     .template_areas(&["header header", "nav main"])
+//!     .add_to_area("header", Box::new(header))
+//!     .add_to_area("nav", Box::new(nav))
+//!     .add_to_area("main", Box::new(content))
+//!
+//! Grid::new()
+//!     .columns(4)
+//!     .add_child(Box::new(chart))
+//!     .span(2, 2)
+//!     .add_child(Box::new(badge))
+//!     .at(3, 0)
+//! ```
+
 use std::any::Any;
 use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, RenderObject};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, GridAutoFlow, GridItem, GridLayout, GridTrack, LayoutEngine, LayoutNode, LayoutType, Size};
+use crate::widgets::layout_widgets::reserve;
+
+/// Where a child of a `Grid` goes
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridPlacement {
+    /// Cells by track index; missing starts take the next free cells
+    Cells(GridItem),
+    /// A named area of the template
+    Area(String),
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        GridPlacement::Cells(GridItem::default())
+    }
+}
 
 pub struct Grid {
-    pub columns: usize,
-    pub rows: usize,
+    pub columns: Vec<GridTrack>,
+    pub rows: Vec<GridTrack>,
     pub column_gap: f32,
     pub row_gap: f32,
+    /// Named regions, one string of space-separated cell names per row;
+    /// `.` leaves a cell unnamed
+    pub areas: Vec<String>,
+    pub auto_flow: GridAutoFlow,
     pub children: Vec<Box<dyn Widget>>,
+    /// Placement of each child by index; children past its end flow into
+    /// the next free cell
+    pub placements: Vec<GridPlacement>,
     key: Option<WidgetKey>,
 }
 
 impl Grid {
     pub fn new() -> Self {
         Self {
-            columns: 1,
-            rows: 1,
+            columns: vec![GridTrack::Flex(1.0)],
+            rows: Vec::new(),
             column_gap: 0.0,
             row_gap: 0.0,
+            areas: Vec::new(),
+            auto_flow: GridAutoFlow::Row,
             children: Vec::new(),
+            placements: Vec::new(),
             key: None,
         }
     }
-    
+
     pub fn clone(&self) -> Self {
         Self {
-            columns: self.columns,
-            rows: self.rows,
+            columns: self.columns.clone(),
+            rows: self.rows.clone(),
             column_gap: self.column_gap,
             row_gap: self.row_gap,
+            areas: self.areas.clone(),
+            auto_flow: self.auto_flow,
             children: self.children.iter().map(|c| c.clone_box()).collect(),
+            placements: self.placements.clone(),
             key: self.key.clone(),
         }
     }
 
+    /// `columns` equal columns sharing the width
     pub fn columns(mut self, columns: usize) -> Self {
-        self.columns = columns.max(1);
+        self.columns = vec![GridTrack::Flex(1.0); columns.max(1)];
         self
     }
 
+    /// `rows` equal rows sharing the height
     pub fn rows(mut self, rows: usize) -> Self {
-        self.rows = rows.max(1);
+        self.rows = vec![GridTrack::Flex(1.0); rows.max(1)];
+        self
+    }
+
+    pub fn template_columns(mut self, columns: Vec<GridTrack>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Rows past the template size to their content
+    pub fn template_rows(mut self, rows: Vec<GridTrack>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn template_areas(mut self, areas: &[&str]) -> Self {
+        self.areas = areas.iter().map(|row| row.to_string()).collect();
+        self
+    }
+
+    pub fn auto_flow(mut self, auto_flow: GridAutoFlow) -> Self {
+        self.auto_flow = auto_flow;
         self
     }
 
@@ -62,27 +141,144 @@ impl Grid {
 
     pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
         self.children = children;
+        self.placements.clear();
         self
     }
 
-    pub fn add_child(mut self, child: Box<dyn Widget>) -> Self {
-        self.children.push(child);
-        self
+    pub fn add_child(self, child: Box<dyn Widget>) -> Self {
+        self.place(child, GridPlacement::default())
+    }
+
+    /// Make the last child added span `columns` by `rows` cells
+    pub fn span(self, columns: usize, rows: usize) -> Self {
+        self.update_last(|item| {
+            item.column_span = columns.max(1);
+            item.row_span = rows.max(1);
+        })
+    }
+
+    /// Pin the last child added to start at `column`, `row`
+    pub fn at(self, column: usize, row: usize) -> Self {
+        self.update_last(|item| {
+            item.column_start = Some(column);
+            item.row_start = Some(row);
+        })
+    }
+
+    /// Add `child` covering the named `area` of the template
+    pub fn add_to_area(self, area: &str, child: Box<dyn Widget>) -> Self {
+        self.place(child, GridPlacement::Area(area.to_string()))
     }
 
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
     }
+
+    fn place(mut self, child: Box<dyn Widget>, placement: GridPlacement) -> Self {
+        self.placements.resize(self.children.len(), GridPlacement::default());
+        self.children.push(child);
+        self.placements.push(placement);
+        self
+    }
+
+    /// Children in named areas keep the area's cells
+    fn update_last(mut self, update: impl FnOnce(&mut GridItem)) -> Self {
+        self.placements.resize(self.children.len(), GridPlacement::default());
+        if let Some(GridPlacement::Cells(item)) = self.placements.last_mut() {
+            update(item);
+        }
+        self
+    }
+
+    fn layout(&self) -> GridLayout {
+        GridLayout {
+            columns: self.columns.clone(),
+            rows: self.rows.clone(),
+            column_gap: self.column_gap,
+            row_gap: self.row_gap,
+            auto_flow: self.auto_flow,
+            areas: self.areas.clone(),
+        }
+    }
+
+    /// Cells claimed by child `index`; unknown areas take the next free cell
+    fn grid_item(&self, layout: &GridLayout, index: usize) -> GridItem {
+        match self.placements.get(index) {
+            Some(GridPlacement::Cells(item)) => *item,
+            Some(GridPlacement::Area(name)) => layout.area(name).unwrap_or_default(),
+            None => GridItem::default(),
+        }
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StatelessWidget for Grid {
-    fn build_stateless(&self, _ctx: &BuildContext) -> WidgetNode {
-        // For now, just return the children as a container
-        // In a real implementation, we would calculate grid layout
-        WidgetNode::Container {
-            children: self.children.iter().map(|c| c.clone_box()).collect(),
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        let mounted: Vec<BuildContext> = self.children.iter().map(|child| ctx.mount_child(child.as_ref())).collect();
+        let build = |index: usize, constraints: Constraints| {
+            let child_ctx = &mounted[index];
+            match child_ctx.child_context(child_ctx.element_id, constraints).build_element(self.children[index].as_ref()) {
+                WidgetNode::Leaf(render_obj) => render_obj,
+                _ => RenderObject::None,
+            }
+        };
+
+        // Children in content-sized tracks are measured within the tracks
+        // that are fixed; children whose cells are fixed both ways are only
+        // built once, into their cells
+        let layout = self.layout();
+        let items: Vec<GridItem> = (0..self.children.len()).map(|i| self.grid_item(&layout, i)).collect();
+        let loose = ctx.constraints.loosen();
+        let measured: Vec<Option<RenderObject>> = layout
+            .fixed_cell_sizes(&items)
+            .into_iter()
+            .enumerate()
+            .map(|(i, fixed)| match fixed {
+                (Some(_), Some(_)) => None,
+                (width, height) => Some(build(
+                    i,
+                    Constraints::new(
+                        width.unwrap_or(loose.min_width),
+                        width.unwrap_or(loose.max_width),
+                        height.unwrap_or(loose.min_height),
+                        height.unwrap_or(loose.max_height),
+                    ),
+                )),
+            })
+            .collect();
+        let nodes = measured
+            .iter()
+            .zip(&items)
+            .enumerate()
+            .map(|(i, (render_obj, item))| {
+                let size = render_obj.as_ref().map_or(Size::zero(), RenderObject::layout_size);
+                LayoutNode::sized(i as u64, size).with_grid_item(*item)
+            })
+            .collect();
+        let mut root = LayoutNode::new(0, LayoutType::Grid, ctx.constraints)
+            .with_grid(layout)
+            .with_children(nodes);
+        LayoutEngine::new().layout(&mut root);
+
+        let mut layers = RenderObject::child_vec(measured.len());
+        for (i, (node, render_obj)) in root.children.iter().zip(measured).enumerate() {
+            let render_obj = match render_obj {
+                Some(render_obj) if render_obj.layout_size() == node.size => render_obj,
+                _ => build(i, Constraints::tight(node.size)),
+            };
+            let (x, y) = node.position;
+            // RTL grids number their columns from the right
+            let x = ctx.direction().place(x, node.size.width, root.size.width);
+            layers.push(RenderObject::transform(Matrix::translate(x, y), render_obj));
         }
+
+        WidgetNode::Leaf(reserve(root.size, RenderObject::group(layers)))
     }
 }
 
@@ -102,4 +298,137 @@ impl Widget for Grid {
     fn clone_box(&self) -> Box<dyn Widget> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::Theme;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_named_areas_span_their_tracks() {
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::loose(Size::new(420.0, 300.0)),
+            Arc::new(Theme::default()),
+        );
+        let grid = Grid::new()
+            .template_columns(vec![GridTrack::Fixed(100.0), GridTrack::Flex(1.0), GridTrack::Flex(2.0)])
+            .template_rows(vec![GridTrack::Auto, GridTrack::Flex(1.0)])
+            .template_areas(&["header header header", "nav main main"])
+            .gap(10.0)
+            .add_to_area("main", Box::new(SizedBox::shrink()))
+            .add_to_area("header", Box::new(SizedBox::height(40.0)))
+            .add_to_area("nav", Box::new(SizedBox::shrink()));
+
+        let WidgetNode::Leaf(render_obj) = grid.build(&ctx) else {
+            panic!("expected the laid out grid");
+        };
+        assert_eq!(render_obj.layout_size(), Size::new(420.0, 300.0));
+        let RenderObject::Group { children } = &render_obj else {
+            panic!("expected a group");
+        };
+        let RenderObject::Group { children: cells } = &children[1] else {
+            panic!("expected the grid's cells");
+        };
+        let bounds: Vec<_> = cells
+            .iter()
+            .map(|cell| cell.bounds().map(|b| (b.x, b.y, b.width, b.height)).unwrap())
+            .collect();
+        // Columns of 100, 100 and 200 after the gaps; the header row fits
+        // its 40px child and the second row takes the rest
+        assert_eq!(
+            bounds,
+            vec![(110.0, 50.0, 310.0, 250.0), (0.0, 0.0, 420.0, 40.0), (0.0, 50.0, 100.0, 250.0)]
+        );
+    }
+
+    #[test]
+    fn test_spanning_children_cover_their_cells() {
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 300.0, 0.0, f32::INFINITY),
+            Arc::new(Theme::default()),
+        );
+        let cell = || Box::new(SizedBox::height(20.0));
+        let grid = Grid::new()
+            .columns(3)
+            .add_child(cell())
+            .at(1, 0)
+            .add_child(cell())
+            .span(2, 1)
+            .add_child(cell())
+            .add_child(cell())
+            .span(1, 2);
+
+        let WidgetNode::Leaf(RenderObject::Group { children }) = grid.build(&ctx) else {
+            panic!("expected the laid out grid");
+        };
+        let RenderObject::Group { children: cells } = &children[1] else {
+            panic!("expected the grid's cells");
+        };
+        let bounds: Vec<_> = cells
+            .iter()
+            .map(|cell| cell.bounds().map(|b| (b.x, b.y, b.width, b.height)).unwrap())
+            .collect();
+        // The wide child skips past the pinned one to the second row; the
+        // tall one shares its 20px between the two rows it spans
+        assert_eq!(
+            bounds,
+            vec![(100.0, 0.0, 100.0, 20.0), (0.0, 20.0, 200.0, 20.0), (200.0, 20.0, 100.0, 20.0), (0.0, 40.0, 100.0, 20.0)]
+        );
+    }
+
+    /// Counts its builds
+    #[derive(Clone)]
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Widget for Counted {
+        fn build(&self, ctx: &BuildContext) -> WidgetNode {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            SizedBox::shrink().build(ctx)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_children_in_fixed_cells_build_once() {
+        let tree = new_shared_element_tree();
+        let builds = Arc::new(AtomicUsize::new(0));
+        let grid = Grid::new()
+            .template_columns(vec![GridTrack::Fixed(80.0), GridTrack::Flex(1.0)])
+            .template_rows(vec![GridTrack::Fixed(30.0)])
+            .add_child(Box::new(Counted(builds.clone())))
+            .add_child(Box::new(SizedBox::shrink()));
+        let root = tree.write().create_element(&grid, None, 0);
+        let ctx = BuildContext::new(
+            root,
+            tree.clone(),
+            Constraints::loose(Size::new(200.0, 100.0)),
+            Arc::new(Theme::default()),
+        );
+
+        let WidgetNode::Leaf(RenderObject::Group { children }) = grid.build(&ctx) else {
+            panic!("expected the laid out grid");
+        };
+        let RenderObject::Group { children: cells } = &children[1] else {
+            panic!("expected the grid's cells");
+        };
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert_eq!(cells[0].bounds().unwrap().width, 80.0);
+        assert_eq!(tree.read().get_children(root).len(), 2);
+    }
+}
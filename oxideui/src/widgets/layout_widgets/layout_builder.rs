@@ -0,0 +1,104 @@
+//! Building from the available space
+//!
+//! `LayoutBuilder` calls its builder with the constraints it is laid out
+//! with, so a widget can pick a layout for the room it actually has (a
+//! sidebar beside the content when wide, stacked when narrow) rather than
+//! guessing from the window size.
+//!
+//! ```rust,ignore
+//! LayoutBuilder::new(|_ctx, constraints| {
+//!     if constraints.max_width >= 720.0 {
+//!         Box::new(Row::new().with_children(vec![nav(), content()]))
+//!     } else {
+//!         Box::new(Column::new().with_children(vec![nav(), content()]))
+//!     }
+//! })
+//! ```
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::core::context::BuildContext;
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::Constraints;
+
+type LayoutWidgetBuilder = Arc<dyn Fn(&BuildContext, Constraints) -> Box<dyn Widget> + Send + Sync>;
+
+/// Builds its child from the incoming constraints on every layout
+#[derive(Clone)]
+pub struct LayoutBuilder {
+    builder: LayoutWidgetBuilder,
+    key: Option<WidgetKey>,
+}
+
+impl LayoutBuilder {
+    pub fn new<F>(builder: F) -> Self
+    where
+        F: Fn(&BuildContext, Constraints) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        Self {
+            builder: Arc::new(builder),
+            key: None,
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for LayoutBuilder {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let child = (self.builder)(ctx, ctx.constraints);
+        ctx.build_child(child.as_ref())
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::render_object::RenderObject;
+    use crate::core::Theme;
+    use crate::layout::Size;
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_builder_sees_the_constraints_it_is_laid_out_with() {
+        let responsive = LayoutBuilder::new(|_ctx, constraints| {
+            let label = if constraints.max_width >= 720.0 { "wide" } else { "narrow" };
+            Box::new(Text::new(label))
+        });
+        let build = |width: f32| {
+            let ctx = BuildContext::new(
+                ElementId::new(0),
+                new_shared_element_tree(),
+                Constraints::loose(Size::new(width, 600.0)),
+                Arc::new(Theme::default()),
+            );
+            match responsive.build(&ctx) {
+                WidgetNode::Leaf(render_obj) => match render_obj.unmarked() {
+                    RenderObject::Text { content, .. } => content.clone(),
+                    _ => panic!("expected the built text"),
+                },
+                _ => panic!("expected the built text"),
+            }
+        };
+
+        assert_eq!(build(1024.0), "wide");
+        assert_eq!(build(400.0), "narrow");
+    }
+}
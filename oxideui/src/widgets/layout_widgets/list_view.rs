@@ -0,0 +1,384 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+
+use crate::animation::EasingCurve;
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent, Vector2};
+use crate::core::render_object::{Matrix, Point, Rect, RenderObject};
+use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::constraints::{Constraints, Size};
+use crate::state_management::collections::{ItemId, ListDiffCache, ReactiveVec};
+use crate::theming::ColorRole;
+use crate::widgets::layout_widgets::reserve;
+use crate::widgets::scrolling::{ScrollController, ScrollPhysics};
+use crate::widgets::selection::SelectionController;
+use crate::ThemeProvider;
+
+/// Vertical list driven by a `ReactiveVec`
+///
+/// Only items touched since the last build are rebuilt; moved items slide to
+/// their new slot instead of being recreated. With a height, the list
+/// scrolls within it and `StickyHeader` items pin to its top.
+pub struct ListView<T: Clone + Send + Sync + 'static> {
+    pub items: ReactiveVec<T>,
+    pub item_builder: Arc<dyn Fn(usize, &T) -> Box<dyn Widget> + Send + Sync>,
+    pub item_extent: f32,
+    pub spacing: f32,
+    pub move_duration: Duration,
+    cache: Arc<RwLock<ListDiffCache<RenderObject>>>,
+    /// Selected items, updated by clicks and the arrow keys
+    pub selection: Option<SelectionController>,
+    /// Called with the item index and pointer position on a long press,
+    /// e.g. to open a context menu
+    pub on_long_press: Option<Arc<dyn Fn(usize, Point) + Send + Sync>>,
+    /// Lay items out at their own height instead of `item_extent`
+    pub measure_items: bool,
+    /// Height of the scrolled window onto the items; None lays out all of
+    /// them, e.g. inside a `ScrollArea`
+    pub height: Option<f32>,
+    /// Top of each item in the last build and the end of the last one, for
+    /// hit-testing and moves
+    tops: Arc<RwLock<Vec<f32>>>,
+    controller: Arc<RwLock<ScrollController>>,
+    key: Option<WidgetKey>,
+}
+
+impl<T: Clone + Send + Sync + 'static> ListView<T> {
+    pub fn new<F>(items: ReactiveVec<T>, item_builder: F) -> Self
+    where
+        F: Fn(usize, &T) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        let mut controller = ScrollController::new();
+        controller.physics = ScrollPhysics::Clamping;
+        Self {
+            items,
+            item_builder: Arc::new(item_builder),
+            item_extent: 48.0,
+            spacing: 0.0,
+            move_duration: Duration::from_millis(200),
+            cache: Arc::new(RwLock::new(ListDiffCache::new())),
+            selection: None,
+            on_long_press: None,
+            measure_items: false,
+            height: None,
+            tops: Arc::new(RwLock::new(Vec::new())),
+            controller: Arc::new(RwLock::new(controller)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            item_builder: self.item_builder.clone(),
+            item_extent: self.item_extent,
+            spacing: self.spacing,
+            move_duration: self.move_duration,
+            cache: self.cache.clone(),
+            selection: self.selection.clone(),
+            on_long_press: self.on_long_press.clone(),
+            measure_items: self.measure_items,
+            height: self.height,
+            tops: self.tops.clone(),
+            controller: self.controller.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_item_extent(mut self, extent: f32) -> Self {
+        self.item_extent = extent;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Duration of the slide animation for moved items (zero disables it)
+    pub fn with_move_duration(mut self, duration: Duration) -> Self {
+        self.move_duration = duration;
+        self
+    }
+
+    /// Let clicks and the arrow keys select items, with Ctrl and Shift in
+    /// multiple selection mode
+    pub fn with_selection(mut self, selection: SelectionController) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    pub fn with_on_long_press<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, Point) + Send + Sync + 'static,
+    {
+        self.on_long_press = Some(Arc::new(callback));
+        self
+    }
+
+    /// Lay items out at their own height instead of `item_extent`
+    pub fn with_measured_items(mut self) -> Self {
+        self.measure_items = true;
+        self
+    }
+
+    /// Scroll the items within `height`
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Share scroll state with the app, to read or jump the offset
+    pub fn with_controller(mut self, controller: Arc<RwLock<ScrollController>>) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.controller.read().offset.y
+    }
+
+    /// Item under `y`, if any
+    fn index_at(&self, y: f32) -> Option<usize> {
+        let y = y + if self.height.is_some() { self.offset() } else { 0.0 };
+        let tops = self.tops.read();
+        let index = tops.partition_point(|top| *top <= y).checked_sub(1)?;
+        (index + 1 < tops.len() && index < self.items.len()).then_some(index)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> StatelessWidget for ListView<T> {
+    fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
+        self.items.subscribe(ctx.element_id);
+
+        let max_height = if self.measure_items { f32::INFINITY } else { self.item_extent };
+        let child_constraints = Constraints::new(0.0, ctx.constraints.max_width, 0.0, max_height);
+
+        // Every item is mounted, keyed by its id so its element follows it
+        // when it moves, but only touched items are built again
+        let mut mounted: HashMap<ItemId, (BuildContext, Box<dyn Widget>)> = HashMap::new();
+        for index in 0..self.items.len() {
+            let (Some(id), Some(item)) = (self.items.id_at(index), self.items.get(index)) else {
+                continue;
+            };
+            let widget = (self.item_builder)(index, &item);
+            let child_ctx = ctx.mount_item(id as usize, widget.as_ref());
+            mounted.insert(id, (child_ctx, widget));
+        }
+        let mut rebuilt = HashSet::new();
+        let mut cache = self.cache.write();
+        cache.sync(&self.items, |_, id, _| {
+            rebuilt.insert(id);
+            let Some((child_ctx, widget)) = mounted.get(&id) else {
+                return RenderObject::None;
+            };
+            match child_ctx.child_context(child_ctx.element_id, child_constraints).build_element(widget.as_ref()) {
+                WidgetNode::Leaf(render_obj) => render_obj,
+                _ => RenderObject::None,
+            }
+        });
+        for (id, (child_ctx, _)) in &mounted {
+            if !rebuilt.contains(id) {
+                ctx.element_tree.write().retain_subtree(child_ctx.element_id);
+            }
+        }
+
+        let cached: Vec<RenderObject> = cache.children().cloned().collect();
+        let extent = |child: &RenderObject| if self.measure_items { child.layout_size().height } else { self.item_extent };
+        let mut tops = Vec::with_capacity(cached.len());
+        let mut next_top = 0.0;
+        for child in &cached {
+            tops.push(next_top);
+            next_top += extent(child) + self.spacing;
+        }
+        tops.push(next_top);
+        let previous_tops = std::mem::replace(&mut *self.tops.write(), tops.clone());
+        let stride = self.item_extent + self.spacing;
+
+        let mut children = Vec::with_capacity(cached.len());
+        if let Some(selection) = &self.selection {
+            selection.follow_rows(&(0..self.items.len()).filter_map(|index| self.items.id_at(index)).collect::<Vec<_>>());
+        }
+        let selected = self.selection.as_ref().map(|selection| selection.selected().get()).unwrap_or_default();
+        let selection_color = ctx.theme().role(ColorRole::Selection);
+
+        for (index, child) in cached.into_iter().enumerate() {
+            let target_y = tops[index];
+            let y = match cache.id_at(index).and_then(|id| cache.move_transition(id, self.move_duration)) {
+                Some(transition) => {
+                    let t = transition.started.elapsed().as_secs_f32() / self.move_duration.as_secs_f32();
+                    let from_index = transition.from_index;
+                    let from_y = previous_tops.get(from_index).copied().unwrap_or(from_index as f32 * stride);
+                    from_y + (target_y - from_y) * EasingCurve::EaseOut.evaluate(t.clamp(0.0, 1.0))
+                }
+                None => target_y,
+            };
+            if selected.contains(&index) {
+                let width = if ctx.constraints.has_bounded_width() {
+                    ctx.constraints.max_width
+                } else {
+                    child.layout_size().width
+                };
+                children.push(RenderObject::rect(Rect::new(0.0, y, width, extent(&child)), selection_color));
+            }
+            children.push(RenderObject::transform(Matrix::translate(0.0, y), child));
+        }
+
+        let animating = cache.has_active_moves();
+        drop(cache);
+
+        if animating {
+            ctx.mark_dirty();
+        }
+
+        let Some(height) = self.height else {
+            return WidgetNode::Leaf(RenderObject::group(children));
+        };
+        let items = RenderObject::group(children);
+        let width = if ctx.constraints.has_bounded_width() {
+            ctx.constraints.max_width
+        } else {
+            items.layout_size().width
+        };
+        let content_height = (next_top - self.spacing).max(0.0);
+        let offset = {
+            let mut controller = self.controller.write();
+            controller.set_content_size(Vector2::new(width, content_height), Vector2::new(width, height));
+            // Items may have been removed from under the offset
+            let offset = controller.offset;
+            controller.jump_to(offset);
+            controller.offset.y
+        };
+        let size = Size::new(width, height);
+        WidgetNode::Leaf(reserve(
+            size,
+            RenderObject::viewport(Rect::from_size(size), RenderObject::transform(Matrix::translate(0.0, -offset), items)),
+        ))
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Widget for ListView<T> {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        self.build_stateless(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if let (UiEvent::Scroll { delta, .. }, Some(_)) = (event, self.height) {
+            let mut controller = self.controller.write();
+            let before = controller.offset;
+            // Wheel deltas point the way the content moves
+            controller.scroll(Vector2::new(0.0, -delta.y));
+            // At the ends, leave the wheel to an enclosing scroll area
+            return if controller.offset == before { EventResult::Unhandled } else { EventResult::Stopped };
+        }
+        if let (UiEvent::LongPress { position }, Some(on_long_press)) = (event, &self.on_long_press) {
+            return match self.index_at(position.y) {
+                Some(index) => {
+                    on_long_press(index, *position);
+                    EventResult::Stopped
+                }
+                None => EventResult::Unhandled,
+            };
+        }
+        let Some(selection) = &self.selection else {
+            return EventResult::Unhandled;
+        };
+        match event {
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } => {
+                context.request_focus();
+                if let Some(index) = self.index_at(position.y) {
+                    selection.click(index, context.modifiers);
+                    return EventResult::Handled;
+                }
+                EventResult::Unhandled
+            }
+            UiEvent::KeyDown { key, modifiers, .. } if selection.handle_key(*key, *modifiers, self.items.len(), 10) => {
+                EventResult::Stopped
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    /// Only lists with selection take keys
+    fn is_focusable(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::event::EventPhase;
+    use crate::core::Theme;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    #[test]
+    fn test_list_with_a_height_scrolls_in_a_viewport() {
+        let items = ReactiveVec::from_vec((0..10).collect::<Vec<u32>>());
+        let list = ListView::new(items, |_, _| Box::new(SizedBox::new(200.0, 48.0))).with_height(100.0);
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &list);
+        let ctx = BuildContext::new(id, tree, Constraints::new(0.0, 200.0, 0.0, 300.0), Arc::new(Theme::default()));
+        list.build(&ctx);
+
+        // Scrolling past the end stops at the last item
+        let mut context = EventContext::new(id, id, EventPhase::Bubbling);
+        let scroll = UiEvent::Scroll { position: Point::new(10.0, 10.0), delta: Vector2::new(0.0, -1000.0) };
+        assert_eq!(list.handle_event(&scroll, &mut context), EventResult::Stopped);
+        let WidgetNode::Leaf(render_obj) = list.build(&ctx) else {
+            panic!("expected a leaf");
+        };
+        assert_eq!(list.offset(), 380.0);
+        assert_eq!(render_obj.layout_size(), Size::new(200.0, 100.0));
+        let RenderObject::Group { children } = &render_obj else {
+            panic!("expected the reserved box");
+        };
+        assert!(matches!(&children[1], RenderObject::Viewport { rect, .. } if rect.height == 100.0));
+        // Clicks land on the item scrolled under the pointer
+        assert_eq!(list.index_at(10.0), Some(8));
+    }
+
+    #[test]
+    fn test_items_are_elements_and_can_be_measured() {
+        let items = ReactiveVec::from_vec(vec![20.0f32, 60.0, 30.0]);
+        let list = ListView::new(items.clone(), |_, height| Box::new(SizedBox::new(200.0, *height))).with_measured_items();
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &list);
+        let ctx = BuildContext::new(id, tree.clone(), Constraints::new(0.0, 200.0, 0.0, 300.0), Arc::new(Theme::default()));
+        list.build(&ctx);
+
+        // One element per item, under the list, none reusing an item id
+        let children = tree.read().get_children(id);
+        assert_eq!(children.len(), 3);
+        assert!(!children.contains(&id));
+        assert_eq!(children.iter().collect::<HashSet<_>>().len(), 3);
+
+        // Items sit at their own heights
+        assert_eq!(list.index_at(19.0), Some(0));
+        assert_eq!(list.index_at(21.0), Some(1));
+        assert_eq!(list.index_at(85.0), Some(2));
+        assert_eq!(list.index_at(200.0), None);
+    }
+}
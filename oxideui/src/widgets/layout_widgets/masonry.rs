@@ -0,0 +1,316 @@
+//! Staggered columns
+//!
+//! `Masonry` packs children of varying heights into equal-width columns,
+//! each going to the top of whichever column is shortest so far, like a
+//! photo wall. For large galleries, `Masonry::builder` takes each item's
+//! height up front and builds only the items near the visible window set
+//! with `with_viewport`; the rest are laid out without being built.
+//!
+//! ```rust,ignore
+//! Masonry::builder(3, photos.len(), move |i, width| width / photos[i].aspect_ratio, move |i| {
+//!     Box::new(Photo::new(photos[i].clone()))
+//! })
+//! .with_spacing(8.0)
+//! .with_viewport(scroll_offset, viewport_height)
+//! ```
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, Size};
+use crate::widgets::layout_widgets::reserve;
+
+type MasonryItemBuilder = Arc<dyn Fn(usize) -> Box<dyn Widget> + Send + Sync>;
+type MasonryItemExtent = Arc<dyn Fn(usize, f32) -> f32 + Send + Sync>;
+
+enum MasonryItems {
+    Children(Vec<Box<dyn Widget>>),
+    /// Built on demand; `extent` gives an item's height at a column width
+    Builder {
+        count: usize,
+        extent: MasonryItemExtent,
+        builder: MasonryItemBuilder,
+    },
+}
+
+pub struct Masonry {
+    items: MasonryItems,
+    pub columns: usize,
+    /// Gap between columns and between items in a column
+    pub spacing: f32,
+    /// Scroll offset and height of the visible window, for builder masonry
+    pub viewport: Option<(f32, f32)>,
+    /// How far past the visible window items are still built
+    pub cache_extent: f32,
+    key: Option<WidgetKey>,
+}
+
+impl Masonry {
+    pub fn new(columns: usize) -> Self {
+        Self {
+            items: MasonryItems::Children(Vec::new()),
+            columns: columns.max(1),
+            spacing: 0.0,
+            viewport: None,
+            cache_extent: 250.0,
+            key: None,
+        }
+    }
+
+    /// `count` items built by `builder` when near the viewport, each as
+    /// tall as `extent(index, column_width)`
+    pub fn builder<E, B>(columns: usize, count: usize, extent: E, builder: B) -> Self
+    where
+        E: Fn(usize, f32) -> f32 + Send + Sync + 'static,
+        B: Fn(usize) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        Self {
+            items: MasonryItems::Builder {
+                count,
+                extent: Arc::new(extent),
+                builder: Arc::new(builder),
+            },
+            ..Self::new(columns)
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        let items = match &self.items {
+            MasonryItems::Children(children) => MasonryItems::Children(children.iter().map(|c| c.clone_box()).collect()),
+            MasonryItems::Builder { count, extent, builder } => MasonryItems::Builder {
+                count: *count,
+                extent: extent.clone(),
+                builder: builder.clone(),
+            },
+        };
+        Self {
+            items,
+            columns: self.columns,
+            spacing: self.spacing,
+            viewport: self.viewport,
+            cache_extent: self.cache_extent,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
+        self.items = MasonryItems::Children(children);
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Build only the items within `height` of `offset`, plus the cache
+    /// extent either side
+    pub fn with_viewport(mut self, offset: f32, height: f32) -> Self {
+        self.viewport = Some((offset, height));
+        self
+    }
+
+    pub fn with_cache_extent(mut self, cache_extent: f32) -> Self {
+        self.cache_extent = cache_extent.max(0.0);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Column and top of each item of `heights`, each going to the top of
+    /// the shortest column, and the height of the tallest column
+    fn place(&self, heights: &[f32]) -> (Vec<(usize, f32)>, f32) {
+        let mut columns = vec![0.0_f32; self.columns];
+        let slots = heights
+            .iter()
+            .map(|height| {
+                let (column, top) = columns
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .unwrap_or((0, 0.0));
+                columns[column] = top + height + self.spacing;
+                (column, top)
+            })
+            .collect();
+        let height = columns.iter().copied().fold(0.0, f32::max);
+        (slots, if heights.is_empty() { 0.0 } else { height - self.spacing })
+    }
+
+    /// Whether an item from `top` to `bottom` is near enough the viewport
+    /// to build
+    fn is_visible(&self, top: f32, bottom: f32) -> bool {
+        match self.viewport {
+            Some((offset, height)) => {
+                bottom > offset - self.cache_extent && top < offset + height + self.cache_extent
+            }
+            None => true,
+        }
+    }
+}
+
+impl Widget for Masonry {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        // Columns share the width; an unbounded masonry has only its minimum
+        let width = if ctx.constraints.has_bounded_width() {
+            ctx.constraints.max_width
+        } else {
+            ctx.constraints.min_width
+        };
+        let gaps = self.spacing * (self.columns - 1) as f32;
+        let column_width = ((width - gaps) / self.columns as f32).max(0.0);
+        let column_x = |column: usize| column as f32 * (column_width + self.spacing);
+
+        let mut layers = Vec::new();
+        let content_height = match &self.items {
+            MasonryItems::Children(children) => {
+                let column = Constraints::new(column_width, column_width, 0.0, f32::INFINITY);
+                let built = ctx.child_context(ctx.element_id, column).build_children(children);
+                let heights: Vec<f32> = built.iter().map(|obj| obj.layout_size().height).collect();
+                let (slots, content_height) = self.place(&heights);
+                for (render_obj, (column, top)) in built.into_iter().zip(slots) {
+                    let x = ctx.direction().place(column_x(column), column_width, width);
+                    layers.push(RenderObject::transform(Matrix::translate(x, top), render_obj));
+                }
+                content_height
+            }
+            MasonryItems::Builder { count, extent, builder } => {
+                let heights: Vec<f32> = (0..*count).map(|i| extent(i, column_width).max(0.0)).collect();
+                let (slots, content_height) = self.place(&heights);
+                for (i, (column, top)) in slots.into_iter().enumerate() {
+                    if !self.is_visible(top, top + heights[i]) {
+                        continue;
+                    }
+                    let child = builder(i);
+                    let item_ctx = ctx.child_context(ctx.element_id, Constraints::tight(Size::new(column_width, heights[i])));
+                    let render_obj = match item_ctx.mount_item(i, child.as_ref()).build_element(child.as_ref()) {
+                        WidgetNode::Leaf(render_obj) => render_obj,
+                        _ => RenderObject::None,
+                    };
+                    let x = ctx.direction().place(column_x(column), column_width, width);
+                    layers.push(RenderObject::transform(Matrix::translate(x, top), render_obj));
+                }
+                content_height
+            }
+        };
+
+        let size = ctx.constraints.constrain(Size::new(width, content_height));
+        WidgetNode::Leaf(reserve(size, RenderObject::group(layers)))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::Theme;
+    use crate::widgets::layout_widgets::SizedBox;
+
+    fn origins(render_obj: &RenderObject) -> Vec<(f32, f32)> {
+        let RenderObject::Group { children } = render_obj else {
+            panic!("expected a group");
+        };
+        let RenderObject::Group { children: items } = &children[1] else {
+            panic!("expected the masonry's items");
+        };
+        items.iter().map(|item| item.bounds().map(|b| (b.x, b.y)).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_items_go_to_the_shortest_column() {
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 300.0, 0.0, f32::INFINITY),
+            Arc::new(Theme::default()),
+        );
+        let masonry = Masonry::new(3).with_children(
+            [100.0, 50.0, 80.0, 40.0, 60.0]
+                .into_iter()
+                .map(|height| Box::new(SizedBox::height(height)) as Box<dyn Widget>)
+                .collect(),
+        );
+        let WidgetNode::Leaf(render_obj) = masonry.build(&ctx) else {
+            panic!("expected the laid out masonry");
+        };
+        assert_eq!(
+            origins(&render_obj),
+            vec![(0.0, 0.0), (100.0, 0.0), (200.0, 0.0), (100.0, 50.0), (200.0, 80.0)]
+        );
+        assert_eq!(render_obj.layout_size(), Size::new(300.0, 140.0));
+
+        // A thousand rows of three, scrolled to the eleventh row: only the
+        // three visible rows are built, though the whole height is laid out
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = built.clone();
+        let gallery = Masonry::builder(
+            3,
+            3000,
+            |_, _| 100.0,
+            move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Box::new(SizedBox::shrink())
+            },
+        )
+        .with_viewport(1000.0, 300.0)
+        .with_cache_extent(0.0);
+        let WidgetNode::Leaf(render_obj) = gallery.build(&ctx) else {
+            panic!("expected the laid out gallery");
+        };
+        assert_eq!(built.load(Ordering::SeqCst), 9);
+        assert_eq!(origins(&render_obj)[0], (0.0, 1000.0));
+        assert_eq!(render_obj.layout_size(), Size::new(300.0, 100_000.0));
+    }
+
+    #[test]
+    fn test_built_items_keep_their_elements_while_scrolling() {
+        let tree = new_shared_element_tree();
+        let gallery = |offset: f32| {
+            Masonry::builder(3, 3000, |_, _| 100.0, |_| Box::new(SizedBox::shrink()))
+                .with_viewport(offset, 300.0)
+                .with_cache_extent(0.0)
+        };
+        // The element of item 33, the first in the twelfth row
+        let item_33 = |offset: f32| {
+            let gallery = gallery(offset);
+            tree.write().begin_build();
+            let root = tree.write().mount(None, &gallery);
+            let ctx = BuildContext::new(
+                root,
+                tree.clone(),
+                Constraints::new(0.0, 300.0, 0.0, f32::INFINITY),
+                Arc::new(Theme::default()),
+            );
+            ctx.build_element(&gallery);
+            tree.write().end_build();
+            let tree = tree.read();
+            tree.get_children(root)
+                .into_iter()
+                .find(|id| tree.get(*id).unwrap().key == Some(WidgetKey::U64(33)))
+        };
+
+        let before = item_33(1000.0).expect("the twelfth row is visible");
+        // Scrolled a row further, the rows before it are no longer built
+        assert_eq!(item_33(1100.0), Some(before));
+    }
+}
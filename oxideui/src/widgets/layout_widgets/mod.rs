@@ -1,13 +1,37 @@
 mod scaffolding;
+mod constraint_layout;
+mod custom_layout;
+mod dock_view;
 mod flexbox;
+mod flexible;
 mod grid;
+mod layout_builder;
+mod list_view;
+mod masonry;
 mod resizable;
 mod scroll_area;
 mod sidebar;
+mod sizing;
+mod stack;
+mod sticky_header;
+mod wrap;
 
 pub use scaffolding::Scaffolding;
+pub use constraint_layout::{ConstraintLayout, Edge, PARENT};
+pub use custom_layout::{CustomMultiChildLayout, MultiChildLayoutDelegate};
+pub use dock_view::DockView;
 pub use flexbox::{Flexbox, FlexDirection, JustifyContent, AlignItems, FlexWrap};
-pub use grid::Grid;
+pub use flexible::{Expanded, FlexFit, Flexible};
+pub(crate) use flexible::build_flex;
+pub use grid::{Grid, GridPlacement};
+pub use layout_builder::LayoutBuilder;
+pub use list_view::ListView;
+pub use masonry::Masonry;
 pub use resizable::{Resizable, ResizableEdges};
 pub use scroll_area::ScrollArea;
-pub use sidebar::{Sidebar, SidebarPosition};
\ No newline at end of file
+pub use sidebar::{Sidebar, SidebarPosition};
+pub use sizing::{ConstrainedBox, FractionallySizedBox, IntrinsicHeight, IntrinsicWidth, LimitedBox, SizedBox};
+pub(crate) use sizing::{build_sized, reserve};
+pub use stack::{Stack, Positioned, StackFit};
+pub use sticky_header::StickyHeader;
+pub use wrap::{Wrap, WrapAlignment, WrapCrossAlignment};
\ No newline at end of file
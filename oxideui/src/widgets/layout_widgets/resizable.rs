@@ -1,10 +1,14 @@
 use std::any::Any;
 use std::sync::Arc;
+use parking_lot::RwLock;
 use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
 use crate::core::render_object::{Rect, RenderObject};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
 use crate::ThemeProvider;
 
+const HANDLE_SIZE: f32 = 8.0;
+
 pub struct Resizable {
     pub child: Box<dyn Widget>,
     pub min_width: f32,
@@ -15,6 +19,8 @@ pub struct Resizable {
     pub height: f32,
     pub resizable: ResizableEdges,
     pub on_resize: Option<Arc<dyn Fn(f32, f32) + Send + Sync>>,
+    /// Axes (horizontal, vertical) being dragged, while a handle is held
+    drag_axes: Arc<RwLock<Option<(bool, bool)>>>,
     key: Option<WidgetKey>,
 }
 
@@ -59,6 +65,7 @@ impl Resizable {
             height: 150.0,
             resizable: ResizableEdges::all(),
             on_resize: None,
+            drag_axes: Arc::new(RwLock::new(None)),
             key: None,
         }
     }
@@ -110,15 +117,42 @@ impl Resizable {
             height: self.height,
             resizable: self.resizable,
             on_resize: self.on_resize.clone(),
+            drag_axes: self.drag_axes.clone(),
             key: self.key.clone(),
         }
     }
+
+    /// Which axes a press at (x, y) would resize, if it lands on a handle
+    fn handle_at(&self, x: f32, y: f32) -> Option<(bool, bool)> {
+        let on_right = self.resizable.right && x >= self.width - HANDLE_SIZE && x <= self.width;
+        let on_bottom = self.resizable.bottom && y >= self.height - HANDLE_SIZE && y <= self.height;
+        let mid_x = (self.width - HANDLE_SIZE) / 2.0;
+        let mid_y = (self.height - HANDLE_SIZE) / 2.0;
+
+        if on_right && on_bottom {
+            Some((true, true))
+        } else if on_right && y >= mid_y && y <= mid_y + HANDLE_SIZE {
+            Some((true, false))
+        } else if on_bottom && x >= mid_x && x <= mid_x + HANDLE_SIZE {
+            Some((false, true))
+        } else {
+            None
+        }
+    }
+
+    fn resize_to(&self, x: f32, y: f32, (horizontal, vertical): (bool, bool)) {
+        let width = if horizontal { x.clamp(self.min_width, self.max_width) } else { self.width };
+        let height = if vertical { y.clamp(self.min_height, self.max_height) } else { self.height };
+        if let Some(on_resize) = &self.on_resize {
+            on_resize(width, height);
+        }
+    }
 }
 
 impl StatelessWidget for Resizable {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
-        let handle_size = 8.0;
+        let handle_size = HANDLE_SIZE;
 
         let mut render_objects = Vec::new();
 
@@ -131,7 +165,7 @@ impl StatelessWidget for Resizable {
         );
 
         let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-        let child_node = self.child.build(&child_ctx);
+        let child_node = child_ctx.build_child(self.child.as_ref());
 
         if let WidgetNode::Leaf(render_obj) = child_node {
             render_objects.push(render_obj);
@@ -192,6 +226,37 @@ impl Widget for Resizable {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        match event {
+            // Seen while capturing, before the child under the handle
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } => {
+                let Some(axes) = self.handle_at(position.x, position.y) else {
+                    return EventResult::Unhandled;
+                };
+                // Keep resizing while the cursor runs ahead of the handle
+                *self.drag_axes.write() = Some(axes);
+                context.capture_pointer(context.current_target);
+                EventResult::Stopped
+            }
+            UiEvent::PointerMove { position, .. } if context.has_pointer_capture() => {
+                if let Some(axes) = *self.drag_axes.read() {
+                    self.resize_to(position.x, position.y, axes);
+                }
+                EventResult::Stopped
+            }
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } => {
+                match self.drag_axes.write().take() {
+                    Some(axes) => {
+                        self.resize_to(position.x, position.y, axes);
+                        EventResult::Stopped
+                    }
+                    None => EventResult::Unhandled,
+                }
+            }
+            _ => EventResult::Unhandled,
+        }
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -1,7 +1,20 @@
 use std::any::Any;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
 use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, UiEvent, Vector2};
+use crate::core::render_object::{Matrix, Rect, RenderObject};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, Size};
+use crate::theming::ColorRole;
+use crate::widgets::layout_widgets::reserve;
+use crate::widgets::scrolling::{ScrollController, ScrollPhysics};
+use crate::ThemeProvider;
 
+/// Clips its child to a window scrolled by the mouse wheel or trackpad.
+/// `StickyHeader`s inside pin to the top of the window.
 pub struct ScrollArea {
     pub child: Box<dyn Widget>,
     pub width: Option<f32>,
@@ -9,11 +22,14 @@ pub struct ScrollArea {
     pub scroll_x: bool,
     pub scroll_y: bool,
     pub scrollbar_size: f32,
+    controller: Arc<RwLock<ScrollController>>,
     key: Option<WidgetKey>,
 }
 
 impl ScrollArea {
     pub fn new(child: Box<dyn Widget>) -> Self {
+        let mut controller = ScrollController::new();
+        controller.physics = ScrollPhysics::Clamping;
         Self {
             child,
             width: None,
@@ -21,10 +37,11 @@ impl ScrollArea {
             scroll_x: false,
             scroll_y: true,
             scrollbar_size: 8.0,
+            controller: Arc::new(RwLock::new(controller)),
             key: None,
         }
     }
-    
+
     pub fn clone(&self) -> Self {
         Self {
             child: self.child.clone_box(),
@@ -33,6 +50,7 @@ impl ScrollArea {
             scroll_x: self.scroll_x,
             scroll_y: self.scroll_y,
             scrollbar_size: self.scrollbar_size,
+            controller: self.controller.clone(),
             key: self.key.clone(),
         }
     }
@@ -58,28 +76,81 @@ impl ScrollArea {
         self
     }
 
+    /// Share scroll state with the app, to read or jump the offset
+    pub fn with_controller(mut self, controller: Arc<RwLock<ScrollController>>) -> Self {
+        self.controller = controller;
+        self
+    }
+
     pub fn with_key(mut self, key: WidgetKey) -> Self {
         self.key = Some(key);
         self
     }
+
+    pub fn offset(&self) -> Vector2 {
+        self.controller.read().offset
+    }
 }
 
 impl StatelessWidget for ScrollArea {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
-        let width = self.width.unwrap_or(ctx.constraints.max_width);
-        let height = self.height.unwrap_or(ctx.constraints.max_height);
-
-        // Create a clipping area for the child
-        // In a real implementation, we would handle scrolling and scrollbars
-        let child_constraints = crate::layout::constraints::Constraints::new(
+        // Scrolled axes give the child all the room it wants
+        let child_constraints = Constraints::new(
             0.0,
-            width,
+            if self.scroll_x { f32::INFINITY } else { self.width.unwrap_or(ctx.constraints.max_width) },
             0.0,
-            height,
+            if self.scroll_y { f32::INFINITY } else { self.height.unwrap_or(ctx.constraints.max_height) },
         );
+        let render_obj = match ctx.child_context(ctx.element_id, child_constraints).build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            other => return other,
+        };
+
+        // Fill bounded space; unbounded axes wrap the content
+        let content = render_obj.layout_size();
+        let extent = |fixed: Option<f32>, max: f32, content: f32| {
+            fixed.unwrap_or(if max.is_finite() { max } else { content })
+        };
+        let size = ctx.constraints.constrain(Size::new(
+            extent(self.width, ctx.constraints.max_width, content.width),
+            extent(self.height, ctx.constraints.max_height, content.height),
+        ));
 
-        let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-        self.child.build(&child_ctx)
+        let offset = {
+            let mut controller = self.controller.write();
+            controller.set_content_size(
+                Vector2::new(content.width, content.height),
+                Vector2::new(size.width, size.height),
+            );
+            // Content may have shrunk under the offset
+            let offset = controller.offset;
+            controller.jump_to(offset);
+            controller.offset
+        };
+
+        let mut layers = vec![RenderObject::viewport(
+            Rect::from_size(size),
+            RenderObject::transform(Matrix::translate(-offset.x, -offset.y), render_obj),
+        )];
+        let thumb_color = ctx.theme().role(ColorRole::Border);
+        if self.scroll_y && content.height > size.height {
+            let length = size.height * size.height / content.height;
+            let top = offset.y / (content.height - size.height) * (size.height - length);
+            layers.push(RenderObject::rect(
+                Rect::new(size.width - self.scrollbar_size, top, self.scrollbar_size, length),
+                thumb_color,
+            ));
+        }
+        if self.scroll_x && content.width > size.width {
+            let length = size.width * size.width / content.width;
+            let left = offset.x / (content.width - size.width) * (size.width - length);
+            layers.push(RenderObject::rect(
+                Rect::new(left, size.height - self.scrollbar_size, length, self.scrollbar_size),
+                thumb_color,
+            ));
+        }
+
+        WidgetNode::Leaf(reserve(size, RenderObject::group(layers)))
     }
 }
 
@@ -88,6 +159,26 @@ impl Widget for ScrollArea {
         self.build_stateless(ctx)
     }
 
+    fn handle_event(&self, event: &UiEvent, _context: &mut EventContext) -> EventResult {
+        let UiEvent::Scroll { delta, .. } = event else {
+            return EventResult::Unhandled;
+        };
+        // Wheel deltas point the way the content moves
+        let delta = Vector2::new(
+            if self.scroll_x { -delta.x } else { 0.0 },
+            if self.scroll_y { -delta.y } else { 0.0 },
+        );
+        let mut controller = self.controller.write();
+        let before = controller.offset;
+        controller.scroll(delta);
+        // At the ends, leave the wheel to an enclosing scroll area
+        if controller.offset == before {
+            EventResult::Unhandled
+        } else {
+            EventResult::Stopped
+        }
+    }
+
     fn key(&self) -> Option<WidgetKey> {
         self.key.clone()
     }
@@ -99,4 +190,4 @@ impl Widget for ScrollArea {
     fn clone_box(&self) -> Box<dyn Widget> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::core::context::BuildContext;
 use crate::core::render_object::{Point, Rect, RenderObject, TextStyle};
 use crate::core::widget::{StatelessWidget, Widget, WidgetKey, WidgetNode};
+use crate::layout::Direction;
 use crate::ThemeProvider;
 
 pub struct Sidebar {
@@ -19,13 +20,27 @@ pub struct Sidebar {
 pub enum SidebarPosition {
     Left,
     Right,
+    /// Left in LTR locales, right in RTL ones
+    Start,
+    End,
+}
+
+impl SidebarPosition {
+    /// The physical side, `Left` or `Right`, for `direction`
+    pub fn resolve(self, direction: Direction) -> SidebarPosition {
+        match (self, direction) {
+            (SidebarPosition::Start, Direction::Ltr) | (SidebarPosition::End, Direction::Rtl) => SidebarPosition::Left,
+            (SidebarPosition::Start, Direction::Rtl) | (SidebarPosition::End, Direction::Ltr) => SidebarPosition::Right,
+            (physical, _) => physical,
+        }
+    }
 }
 
 impl Sidebar {
     pub fn new() -> Self {
         Self {
             width: 240.0,
-            position: SidebarPosition::Left,
+            position: SidebarPosition::Start,
             collapsed: false,
             collapsible: true,
             children: Vec::new(),
@@ -100,6 +115,7 @@ impl StatelessWidget for Sidebar {
     fn build_stateless(&self, ctx: &BuildContext) -> WidgetNode {
         let theme = ctx.theme();
         let actual_width = if self.collapsed { 60.0 } else { self.width };
+        let position = self.position.resolve(ctx.direction());
 
         let mut render_objects = Vec::new();
 
@@ -110,9 +126,9 @@ impl StatelessWidget for Sidebar {
         ));
 
         // Sidebar border
-        let border_side = match self.position {
-            SidebarPosition::Left => Rect::new(actual_width - 1.0, 0.0, 1.0, ctx.constraints.max_height),
+        let border_side = match position {
             SidebarPosition::Right => Rect::new(0.0, 0.0, 1.0, ctx.constraints.max_height),
+            _ => Rect::new(actual_width - 1.0, 0.0, 1.0, ctx.constraints.max_height),
         };
         render_objects.push(RenderObject::rect(
             border_side,
@@ -130,11 +146,11 @@ impl StatelessWidget for Sidebar {
                 theme.sidebar_accent,
             ));
 
-            let arrow_icon = match (self.position, self.collapsed) {
-                (SidebarPosition::Left, false) => "◀",
-                (SidebarPosition::Left, true) => "▶",
+            let arrow_icon = match (position, self.collapsed) {
                 (SidebarPosition::Right, false) => "▶",
                 (SidebarPosition::Right, true) => "◀",
+                (_, false) => "◀",
+                (_, true) => "▶",
             };
 
             render_objects.push(RenderObject::text(
@@ -145,6 +161,7 @@ impl StatelessWidget for Sidebar {
                     color: theme.sidebar_accent_foreground,
                     bold: true,
                     italic: false,
+                    ..Default::default()
                 },
                 Point::new(toggle_x + 8.0, toggle_y + 8.0),
             ));
@@ -164,7 +181,7 @@ impl StatelessWidget for Sidebar {
                 );
 
                 let child_ctx = ctx.child_context(ctx.element_id, child_constraints);
-                let child_node = child.build(&child_ctx);
+                let child_node = child_ctx.build_child(child.as_ref());
 
                 if let WidgetNode::Leaf(render_obj) = child_node {
                     let offset_render_obj = RenderObject::transform(
@@ -0,0 +1,608 @@
+//! Single-child sizing
+//!
+//! These widgets don't paint anything; they change the constraints their
+//! child is built with.
+//!
+//! - `SizedBox` gives its child (or itself, as a spacer) an exact size
+//! - `ConstrainedBox` adds min/max limits on top of the parent's
+//! - `LimitedBox` caps a size only where the parent leaves it unbounded,
+//!   e.g. inside a scroll area
+//! - `FractionallySizedBox` sizes its child to a share of the space
+//!   available, so cards scale with the window
+//! - `IntrinsicWidth` and `IntrinsicHeight` size their child to its
+//!   natural extent, e.g. so a column of buttons is as wide as the widest
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Color, Matrix, Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Alignment, Constraints, LayoutEngine, LayoutNode, Size};
+
+/// Build `child` with `constraints` and reserve `size` for it, even when it
+/// paints less (or nothing), so parents measuring bounds see the full box
+pub(crate) fn build_sized(ctx: &BuildContext, child: &dyn Widget, constraints: Constraints, size: Size, offset: (f32, f32)) -> WidgetNode {
+    let child = match ctx.child_context(ctx.element_id, constraints).build_child(child) {
+        WidgetNode::Leaf(render_obj) if offset != (0.0, 0.0) => {
+            RenderObject::transform(Matrix::translate(offset.0, offset.1), render_obj)
+        }
+        WidgetNode::Leaf(render_obj) => render_obj,
+        other => return other,
+    };
+    WidgetNode::Leaf(reserve(size, child))
+}
+
+/// `child` plus an invisible box of `size`
+pub(crate) fn reserve(size: Size, child: RenderObject) -> RenderObject {
+    RenderObject::group(vec![RenderObject::rect(Rect::from_size(size), Color::TRANSPARENT), child])
+}
+
+/// Build `child` within `constraints` and size the result to the child,
+/// kept within those constraints
+fn build_within(ctx: &BuildContext, child: Option<&dyn Widget>, constraints: Constraints) -> WidgetNode {
+    let Some(child) = child else {
+        return WidgetNode::Leaf(reserve(constraints.smallest(), RenderObject::None));
+    };
+    match ctx.child_context(ctx.element_id, constraints).build_child(child) {
+        WidgetNode::Leaf(render_obj) => {
+            let size = constraints.constrain(render_obj.layout_size());
+            WidgetNode::Leaf(reserve(size, render_obj))
+        }
+        other => other,
+    }
+}
+
+/// A box of a fixed size. Without a child it's a spacer; with one, the
+/// child is forced to that size. An unset side follows the child.
+pub struct SizedBox {
+    pub child: Option<Box<dyn Widget>>,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    key: Option<WidgetKey>,
+}
+
+impl SizedBox {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            child: None,
+            width: Some(width),
+            height: Some(height),
+            key: None,
+        }
+    }
+
+    /// A horizontal gap
+    pub fn width(width: f32) -> Self {
+        Self {
+            height: None,
+            ..Self::new(width, 0.0)
+        }
+    }
+
+    /// A vertical gap
+    pub fn height(height: f32) -> Self {
+        Self {
+            width: None,
+            ..Self::new(0.0, height)
+        }
+    }
+
+    pub fn square(side: f32) -> Self {
+        Self::new(side, side)
+    }
+
+    /// As large as the parent allows
+    pub fn expand() -> Self {
+        Self::new(f32::INFINITY, f32::INFINITY)
+    }
+
+    /// As small as the parent allows
+    pub fn shrink() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.as_ref().map(|c| c.clone_box()),
+            width: self.width,
+            height: self.height,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_child<W: Widget + 'static>(mut self, child: W) -> Self {
+        self.child = Some(Box::new(child));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Constraints for the child: the set sides made tight, within `parent`
+    pub fn child_constraints(&self, parent: &Constraints) -> Constraints {
+        // An infinite side in an unbounded parent can't be honored
+        let tight = |extent: Option<f32>, min: f32, max: f32| match extent.map(|extent| extent.clamp(min, max)) {
+            Some(extent) if extent.is_finite() => (extent, extent),
+            Some(_) => (min, min),
+            None => (min, max),
+        };
+        let (min_width, max_width) = tight(self.width, parent.min_width, parent.max_width);
+        let (min_height, max_height) = tight(self.height, parent.min_height, parent.max_height);
+        Constraints::new(min_width, max_width, min_height, max_height)
+    }
+}
+
+impl Widget for SizedBox {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_within(ctx, self.child.as_deref(), self.child_constraints(&ctx.constraints))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Adds `constraints` to those from the parent, e.g. a minimum width for
+/// a button or a maximum width for a paragraph
+pub struct ConstrainedBox {
+    pub child: Box<dyn Widget>,
+    pub constraints: Constraints,
+    key: Option<WidgetKey>,
+}
+
+impl ConstrainedBox {
+    pub fn new<W: Widget + 'static>(constraints: Constraints, child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            constraints,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            constraints: self.constraints,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for ConstrainedBox {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_within(ctx, Some(self.child.as_ref()), self.constraints.enforce(&ctx.constraints))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Caps its child's size on axes the parent leaves unbounded; bounded
+/// axes pass through untouched
+pub struct LimitedBox {
+    pub child: Box<dyn Widget>,
+    pub max_width: f32,
+    pub max_height: f32,
+    key: Option<WidgetKey>,
+}
+
+impl LimitedBox {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            max_width: f32::INFINITY,
+            max_height: f32::INFINITY,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            max_width: self.max_width,
+            max_height: self.max_height,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn with_max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn child_constraints(&self, parent: &Constraints) -> Constraints {
+        let mut child = *parent;
+        if !parent.has_bounded_width() {
+            child.max_width = self.max_width.max(parent.min_width);
+        }
+        if !parent.has_bounded_height() {
+            child.max_height = self.max_height.max(parent.min_height);
+        }
+        child
+    }
+}
+
+impl Widget for LimitedBox {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_within(ctx, Some(self.child.as_ref()), self.child_constraints(&ctx.constraints))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Which extent an intrinsic box measures
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IntrinsicAxis {
+    Width,
+    Height,
+}
+
+/// Build `child` with `axis` unbounded to measure its natural extent, then
+/// again with that extent made tight. Both passes build the same element.
+fn build_intrinsic(ctx: &BuildContext, child: &dyn Widget, axis: IntrinsicAxis) -> WidgetNode {
+    let parent = ctx.constraints;
+    let mut measuring = parent;
+    match axis {
+        IntrinsicAxis::Width => (measuring.min_width, measuring.max_width) = (0.0, f32::INFINITY),
+        IntrinsicAxis::Height => (measuring.min_height, measuring.max_height) = (0.0, f32::INFINITY),
+    }
+    let child_ctx = ctx.mount_child(child);
+    // Inside another intrinsic box's measuring pass only the size counts,
+    // so nested boxes build once instead of doubling at every level
+    if ctx.is_measuring() {
+        return child_ctx.child_context(child_ctx.element_id, measuring).build_element(child);
+    }
+    let measured = match child_ctx.measuring_context(measuring).build_element(child) {
+        WidgetNode::Leaf(render_obj) => render_obj,
+        other => return other,
+    };
+
+    // The engine resolves the measured extent against our constraints
+    let mut node = LayoutNode::sized(0, measured.layout_size());
+    node.constraints = parent;
+    let natural = LayoutEngine::new().measure_intrinsic(&node);
+    let constraints = match axis {
+        IntrinsicAxis::Width => parent.constrain_width(natural.width),
+        IntrinsicAxis::Height => parent.constrain_height(natural.height),
+    };
+    match child_ctx.child_context(child_ctx.element_id, constraints).build_element(child) {
+        WidgetNode::Leaf(render_obj) => {
+            let size = constraints.constrain(render_obj.layout_size());
+            WidgetNode::Leaf(reserve(size, render_obj))
+        }
+        other => other,
+    }
+}
+
+/// Sizes its child to the width it takes with unlimited room. Building
+/// the child twice makes this relatively expensive; avoid it in long lists.
+/// Intrinsic boxes nested inside it build only once.
+pub struct IntrinsicWidth {
+    pub child: Box<dyn Widget>,
+    key: Option<WidgetKey>,
+}
+
+impl IntrinsicWidth {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for IntrinsicWidth {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_intrinsic(ctx, self.child.as_ref(), IntrinsicAxis::Width)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Sizes its child to the height it takes with unlimited room, e.g. so
+/// stretched children of a row match the tallest
+pub struct IntrinsicHeight {
+    pub child: Box<dyn Widget>,
+    key: Option<WidgetKey>,
+}
+
+impl IntrinsicHeight {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for IntrinsicHeight {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        build_intrinsic(ctx, self.child.as_ref(), IntrinsicAxis::Height)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Fills the available space and sizes its child to a fraction of it.
+/// An unset factor, or an unbounded axis, passes the constraints through.
+pub struct FractionallySizedBox {
+    pub child: Box<dyn Widget>,
+    pub width_factor: Option<f32>,
+    pub height_factor: Option<f32>,
+    /// Where the child sits in the leftover space
+    pub alignment: Alignment,
+    key: Option<WidgetKey>,
+}
+
+impl FractionallySizedBox {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            width_factor: None,
+            height_factor: None,
+            alignment: Alignment::Center,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            width_factor: self.width_factor,
+            height_factor: self.height_factor,
+            alignment: self.alignment,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_width_factor(mut self, factor: f32) -> Self {
+        self.width_factor = Some(factor.max(0.0));
+        self
+    }
+
+    pub fn with_height_factor(mut self, factor: f32) -> Self {
+        self.height_factor = Some(factor.max(0.0));
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Constraints for the child within `constraints`
+    pub fn child_constraints(&self, constraints: &Constraints) -> Constraints {
+        let mut child = *constraints;
+        if let Some(factor) = self.width_factor.filter(|_| constraints.has_bounded_width()) {
+            let width = constraints.max_width * factor;
+            child.min_width = width;
+            child.max_width = width;
+        }
+        if let Some(factor) = self.height_factor.filter(|_| constraints.has_bounded_height()) {
+            let height = constraints.max_height * factor;
+            child.min_height = height;
+            child.max_height = height;
+        }
+        child
+    }
+}
+
+impl Widget for FractionallySizedBox {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let child_constraints = self.child_constraints(&ctx.constraints);
+        let size = ctx.constraints.biggest();
+        let offset = self.alignment.align_directional(child_constraints.biggest(), size, ctx.direction());
+        build_sized(ctx, self.child.as_ref(), child_constraints, size, offset)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::Theme;
+
+    #[test]
+    fn test_sized_boxes_reserve_their_size() {
+        let ctx = |constraints| {
+            BuildContext::new(ElementId::new(0), new_shared_element_tree(), constraints, Arc::new(Theme::default()))
+        };
+        let size_of = |node: WidgetNode| match node {
+            WidgetNode::Leaf(render_obj) => render_obj.layout_size(),
+            _ => panic!("expected a leaf"),
+        };
+        let column = ctx(Constraints::new(0.0, 300.0, 0.0, f32::INFINITY));
+
+        // A childless spacer takes its height and the parent's minimum width
+        assert_eq!(size_of(SizedBox::height(16.0).build(&column)), Size::new(0.0, 16.0));
+        // Never bigger than the parent allows
+        assert_eq!(size_of(SizedBox::new(500.0, 20.0).build(&column)), Size::new(300.0, 20.0));
+        // Expanding only works on bounded axes
+        let expanded = SizedBox::expand().child_constraints(&column.constraints);
+        assert_eq!((expanded.min_width, expanded.max_width, expanded.max_height), (300.0, 300.0, 0.0));
+
+        let min_width = ConstrainedBox::new(Constraints::new(120.0, f32::INFINITY, 0.0, f32::INFINITY), SizedBox::square(10.0));
+        assert_eq!(size_of(min_width.build(&column)), Size::new(120.0, 10.0));
+
+        // Limits apply to the unbounded height but not the bounded width
+        let limited = LimitedBox::new(SizedBox::expand()).with_max_width(100.0).with_max_height(100.0);
+        let limits = limited.child_constraints(&column.constraints);
+        assert_eq!((limits.max_width, limits.max_height), (300.0, 100.0));
+    }
+
+    #[test]
+    fn test_intrinsic_width_matches_the_widest_child() {
+        use crate::layout::AlignItems;
+        use crate::widgets::basic::Column;
+
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            // A parent that would otherwise let the column fill 300px
+            Constraints::new(0.0, 300.0, 0.0, 300.0),
+            Arc::new(Theme::default()),
+        );
+        let buttons = Column::new()
+            .with_cross_alignment(AlignItems::Stretch)
+            .with_children(vec![Box::new(SizedBox::new(50.0, 10.0)), Box::new(SizedBox::new(80.0, 10.0))]);
+
+        let WidgetNode::Leaf(render_obj) = IntrinsicWidth::new(buttons).build(&ctx) else {
+            panic!("expected a leaf");
+        };
+        assert_eq!(render_obj.layout_size().width, 80.0);
+    }
+
+    #[test]
+    fn test_nested_intrinsic_boxes_build_each_child_once_per_pass() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone)]
+        struct Counted(Arc<AtomicUsize>);
+
+        impl Widget for Counted {
+            fn build(&self, _ctx: &BuildContext) -> WidgetNode {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                WidgetNode::Leaf(RenderObject::rect(Rect::new(0.0, 0.0, 40.0, 10.0), Color::BLACK))
+            }
+            fn key(&self) -> Option<WidgetKey> {
+                None
+            }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            fn clone_box(&self) -> Box<dyn Widget> {
+                Box::new(self.clone())
+            }
+        }
+
+        let builds = Arc::new(AtomicUsize::new(0));
+        let nested = IntrinsicWidth::new(IntrinsicWidth::new(IntrinsicWidth::new(Counted(builds.clone()))));
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &nested);
+        let ctx = BuildContext::new(id, tree.clone(), Constraints::new(0.0, 300.0, 0.0, 300.0), Arc::new(Theme::default()));
+
+        let WidgetNode::Leaf(render_obj) = nested.build(&ctx) else {
+            panic!("expected a leaf");
+        };
+        assert_eq!(render_obj.layout_size().width, 40.0);
+        // Eight builds if every level built its child twice
+        assert_eq!(builds.load(Ordering::SeqCst), 4);
+        // Both passes build the same elements
+        assert_eq!(tree.read().len(), 4);
+    }
+
+    #[test]
+    fn test_fraction_resolves_against_constraints() {
+        let card = FractionallySizedBox::new(crate::widgets::basic::Text::new("Card")).with_width_factor(0.5);
+        let child = card.child_constraints(&Constraints::loose(Size::new(800.0, 600.0)));
+        assert_eq!((child.min_width, child.max_width), (400.0, 400.0));
+        // The unset axis passes through
+        assert_eq!((child.min_height, child.max_height), (0.0, 600.0));
+    }
+}
@@ -0,0 +1,383 @@
+//! Overlapping children
+//!
+//! `Stack` paints its children on top of each other, first at the bottom.
+//! Plain children are laid out within the stack's constraints and placed
+//! by its alignment; children wrapped in `Positioned` are pinned to its
+//! edges instead, and don't affect its size.
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Alignment, Constraints, Size};
+
+/// How a stack constrains its non-positioned children
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum StackFit {
+    /// Up to the stack's size
+    #[default]
+    Loose,
+    /// Exactly the stack's size
+    Expand,
+}
+
+pub struct Stack {
+    pub children: Vec<Box<dyn Widget>>,
+    /// Where non-positioned children sit; start-relative, so it mirrors in
+    /// right-to-left layouts
+    pub alignment: Alignment,
+    pub fit: StackFit,
+    key: Option<WidgetKey>,
+}
+
+impl Stack {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            alignment: Alignment::TopLeft,
+            fit: StackFit::Loose,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            children: self.children.iter().map(|c| c.clone_box()).collect(),
+            alignment: self.alignment,
+            fit: self.fit,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn add_child(mut self, child: Box<dyn Widget>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_fit(mut self, fit: StackFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Stack {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        // Mounted up front so hit-testing follows paint order; a
+        // positioned child is mounted directly, without its `Positioned`
+        let mounted: Vec<(BuildContext, &dyn Widget)> = self
+            .children
+            .iter()
+            .map(|child| {
+                let inner = child
+                    .as_any()
+                    .downcast_ref::<Positioned>()
+                    .map_or(child.as_ref(), |positioned| positioned.child.as_ref());
+                (ctx.mount_child(inner), inner)
+            })
+            .collect();
+        let build = |index: usize, constraints: Constraints| {
+            let (child_ctx, child) = &mounted[index];
+            match child_ctx.child_context(child_ctx.element_id, constraints).build_element(*child) {
+                WidgetNode::Leaf(render_obj) => render_obj,
+                _ => RenderObject::None,
+            }
+        };
+
+        // Non-positioned children first: with unbounded constraints they
+        // decide the stack's size
+        let plain_constraints = match self.fit {
+            StackFit::Loose => ctx.constraints.loosen(),
+            StackFit::Expand => Constraints::tight(ctx.constraints.biggest()),
+        };
+        let mut built: Vec<Option<RenderObject>> = self
+            .children
+            .iter()
+            .enumerate()
+            .map(|(i, child)| {
+                let positioned = child.as_any().is::<Positioned>();
+                (!positioned).then(|| build(i, plain_constraints))
+            })
+            .collect();
+
+        let content = built
+            .iter()
+            .flatten()
+            .map(RenderObject::layout_size)
+            .fold(Size::zero(), |a, b| Size::new(a.width.max(b.width), a.height.max(b.height)));
+        // Expanding stacks fill bounded axes; otherwise they wrap their content
+        let expand = |bounded: bool, max: f32, content: f32| {
+            if bounded && self.fit == StackFit::Expand { max } else { content }
+        };
+        let size = ctx.constraints.constrain(Size::new(
+            expand(ctx.constraints.has_bounded_width(), ctx.constraints.max_width, content.width),
+            expand(ctx.constraints.has_bounded_height(), ctx.constraints.max_height, content.height),
+        ));
+
+        let mut layers = RenderObject::child_vec(self.children.len());
+        for (i, child) in self.children.iter().enumerate() {
+            let (render_obj, x, y) = match (built[i].take(), child.as_any().downcast_ref::<Positioned>()) {
+                (Some(render_obj), _) => {
+                    let (x, y) = self.alignment.align_directional(render_obj.layout_size(), size, ctx.direction());
+                    (render_obj, x, y)
+                }
+                (None, Some(positioned)) => {
+                    let render_obj = build(i, positioned.constraints(size));
+                    let (x, y) = positioned.offset(render_obj.layout_size(), size);
+                    (render_obj, x, y)
+                }
+                (None, None) => continue,
+            };
+            layers.push(RenderObject::transform(Matrix::translate(x, y), render_obj));
+        }
+
+        WidgetNode::Leaf(RenderObject::group(layers))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Pins a `Stack` child to the stack's edges. Setting both `left` and
+/// `right` (or `top` and `bottom`) stretches the child between them;
+/// otherwise `width`/`height` size it, or it takes its natural size.
+pub struct Positioned {
+    pub child: Box<dyn Widget>,
+    pub left: Option<f32>,
+    pub top: Option<f32>,
+    pub right: Option<f32>,
+    pub bottom: Option<f32>,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    key: Option<WidgetKey>,
+}
+
+impl Positioned {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            left: None,
+            top: None,
+            right: None,
+            bottom: None,
+            width: None,
+            height: None,
+            key: None,
+        }
+    }
+
+    /// Stretched over the whole stack
+    pub fn fill<W: Widget + 'static>(child: W) -> Self {
+        Self::new(child).left(0.0).top(0.0).right(0.0).bottom(0.0)
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            left: self.left,
+            top: self.top,
+            right: self.right,
+            bottom: self.bottom,
+            width: self.width,
+            height: self.height,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn left(mut self, left: f32) -> Self {
+        self.left = Some(left);
+        self
+    }
+
+    pub fn top(mut self, top: f32) -> Self {
+        self.top = Some(top);
+        self
+    }
+
+    pub fn right(mut self, right: f32) -> Self {
+        self.right = Some(right);
+        self
+    }
+
+    pub fn bottom(mut self, bottom: f32) -> Self {
+        self.bottom = Some(bottom);
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Constraints for the child inside a stack of `stack` size
+    fn constraints(&self, stack: Size) -> Constraints {
+        let axis = |start: Option<f32>, end: Option<f32>, extent: Option<f32>, available: f32| match (start, end, extent) {
+            (Some(start), Some(end), _) => {
+                let extent = (available - start - end).max(0.0);
+                (extent, extent)
+            }
+            (_, _, Some(extent)) => (extent, extent),
+            _ => (0.0, available),
+        };
+        let (min_width, max_width) = axis(self.left, self.right, self.width, stack.width);
+        let (min_height, max_height) = axis(self.top, self.bottom, self.height, stack.height);
+        Constraints::new(min_width, max_width, min_height, max_height)
+    }
+
+    /// Top-left corner of a child of `size` inside a stack of `stack` size
+    fn offset(&self, size: Size, stack: Size) -> (f32, f32) {
+        let axis = |start: Option<f32>, end: Option<f32>, extent: f32, available: f32| match (start, end) {
+            (Some(start), _) => start,
+            (None, Some(end)) => available - end - extent,
+            (None, None) => 0.0,
+        };
+        let width = self.width.unwrap_or(size.width);
+        let height = self.height.unwrap_or(size.height);
+        (
+            axis(self.left, self.right, width, stack.width),
+            axis(self.top, self.bottom, height, stack.height),
+        )
+    }
+}
+
+impl Widget for Positioned {
+    /// Outside a `Stack` the position is ignored
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        ctx.build_child(self.child.as_ref())
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::render_object::{Color, Rect};
+    use crate::core::Theme;
+
+    /// A fixed-size box, standing in for any child with a natural size
+    #[derive(Clone)]
+    struct Swatch(Size);
+
+    impl Widget for Swatch {
+        fn build(&self, ctx: &BuildContext) -> WidgetNode {
+            let size = ctx.constraints.constrain(self.0);
+            WidgetNode::Leaf(RenderObject::rect(Rect::from_size(size), Color::RED))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_positioned_children_pin_to_edges() {
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::loose(Size::new(200.0, 100.0)),
+            Arc::new(Theme::default()),
+        );
+        let stack = Stack::new()
+            .with_fit(StackFit::Expand)
+            .with_alignment(Alignment::Center)
+            .with_children(vec![
+                Box::new(Swatch(Size::new(40.0, 40.0))),
+                Box::new(Positioned::new(Swatch(Size::new(20.0, 10.0))).right(5.0).bottom(5.0)),
+                Box::new(Positioned::new(Swatch(Size::new(20.0, 10.0))).left(10.0).right(10.0).top(0.0)),
+            ]);
+
+        let WidgetNode::Leaf(RenderObject::Group { children }) = stack.build(&ctx) else {
+            panic!("expected the stack's layers");
+        };
+        let bounds: Vec<Rect> = children.iter().map(|layer| layer.bounds().unwrap()).collect();
+        // Expanded to the stack's size, whatever the child asked for
+        assert_eq!(bounds[0], Rect::new(0.0, 0.0, 200.0, 100.0));
+        assert_eq!(bounds[1], Rect::new(175.0, 85.0, 20.0, 10.0));
+        // Stretched between its left and right insets
+        assert_eq!(bounds[2], Rect::new(10.0, 0.0, 180.0, 10.0));
+    }
+
+    #[test]
+    fn test_children_mount_in_paint_order() {
+        let tree = new_shared_element_tree();
+        let stack = Stack::new().with_children(vec![
+            Box::new(Positioned::new(Swatch(Size::new(20.0, 10.0))).left(0.0).top(0.0)),
+            Box::new(Swatch(Size::new(40.0, 40.0))),
+        ]);
+        let build = || {
+            tree.write().begin_build();
+            let root = tree.write().mount(None, &stack);
+            let ctx = BuildContext::new(
+                root,
+                tree.clone(),
+                Constraints::loose(Size::new(200.0, 100.0)),
+                Arc::new(Theme::default()),
+            );
+            ctx.build_element(&stack);
+            tree.write().end_build();
+            (root, tree.read().get_children(root))
+        };
+
+        let (root, first) = build();
+        assert_eq!(build(), (root, first.clone()));
+        // The positioned child, built after the plain one, still comes first
+        // and is mounted without its wrapper
+        let tree = tree.read();
+        let slots: Vec<usize> = first.iter().map(|id| tree.get(*id).unwrap().slot_index).collect();
+        assert_eq!(slots, vec![0, 1]);
+        assert!(tree.get(first[0]).unwrap().widget.as_ref().unwrap().as_any().is::<Swatch>());
+    }
+}
@@ -0,0 +1,96 @@
+//! Section headers that stay in view
+//!
+//! A `StickyHeader` lays a header out above its section's content. Inside a
+//! `ScrollArea` or a `ListView` with a height, the header pins to the top of the scroll area while any
+//! of its section is in view, and the next section's header pushes it out
+//! as the section ends. The pinning happens when painting, so scrolling
+//! doesn't relayout the sections. Headers draw over their content, so give
+//! them a background.
+//!
+//! ```rust,ignore
+//! let sections = groups
+//!     .iter()
+//!     .map(|(letter, contacts)| {
+//!         let header = Box::new(LetterHeader::new(*letter));
+//!         Box::new(StickyHeader::new(header, Box::new(ContactList::new(contacts)))) as Box<dyn Widget>
+//!     })
+//!     .collect();
+//! ScrollArea::new(Box::new(Column::new().with_children(sections)))
+//! ```
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Constraints, Size};
+use crate::widgets::layout_widgets::reserve;
+
+/// A section whose header sticks to the top of the enclosing scroll area
+pub struct StickyHeader {
+    pub header: Box<dyn Widget>,
+    pub content: Box<dyn Widget>,
+    key: Option<WidgetKey>,
+}
+
+impl StickyHeader {
+    pub fn new(header: Box<dyn Widget>, content: Box<dyn Widget>) -> Self {
+        Self {
+            header,
+            content,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            header: self.header.clone_box(),
+            content: self.content.clone_box(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for StickyHeader {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let constraints = Constraints::new(ctx.constraints.min_width, ctx.constraints.max_width, 0.0, f32::INFINITY);
+        let build = |child: &dyn Widget| match ctx.child_context(ctx.element_id, constraints).build_child(child) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            _ => RenderObject::None,
+        };
+        // The header is mounted last, as it paints over the content and is
+        // hit-tested first
+        let content = build(self.content.as_ref());
+        let header = build(self.header.as_ref());
+        let header_size = header.layout_size();
+        let content_size = content.layout_size();
+
+        let size = ctx.constraints.constrain(Size::new(
+            header_size.width.max(content_size.width),
+            header_size.height + content_size.height,
+        ));
+        // The header goes last so it paints over the content scrolling under it
+        let layers = vec![
+            RenderObject::transform(Matrix::translate(0.0, header_size.height), content),
+            RenderObject::sticky(Rect::from_size(size), header),
+        ];
+        WidgetNode::Leaf(reserve(size, RenderObject::group(layers)))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
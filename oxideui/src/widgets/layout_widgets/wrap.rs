@@ -0,0 +1,294 @@
+//! Flow layout
+//!
+//! `Wrap` places children one after another along its direction and starts
+//! a new run when the next child would overflow, like words in a paragraph.
+//! Runs stack along the cross axis, so tag and chip clouds reflow with the
+//! window width. Horizontal wraps fill from the right in RTL layouts.
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Axis, JustifyContent, Size};
+
+/// How free space is shared out along an axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum WrapAlignment {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl WrapAlignment {
+    /// Offset of the first of `count` items and the gap after each, given
+    /// `free` space left over with `spacing` between items
+    fn distribute(self, free: f32, count: usize, spacing: f32) -> (f32, f32) {
+        let justify = match self {
+            WrapAlignment::Start => JustifyContent::FlexStart,
+            WrapAlignment::End => JustifyContent::FlexEnd,
+            WrapAlignment::Center => JustifyContent::Center,
+            WrapAlignment::SpaceBetween => JustifyContent::SpaceBetween,
+            WrapAlignment::SpaceAround => JustifyContent::SpaceAround,
+            WrapAlignment::SpaceEvenly => JustifyContent::SpaceEvenly,
+        };
+        justify.distribute(free, count, spacing)
+    }
+}
+
+/// Where children shorter than their run sit across it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum WrapCrossAlignment {
+    #[default]
+    Start,
+    End,
+    Center,
+}
+
+/// Children of one line, by index into the wrap's children
+struct Run {
+    start: usize,
+    end: usize,
+    main: f32,
+    cross: f32,
+}
+
+pub struct Wrap {
+    pub children: Vec<Box<dyn Widget>>,
+    pub direction: Axis,
+    /// Gap between children in a run
+    pub spacing: f32,
+    /// Gap between runs
+    pub run_spacing: f32,
+    /// Placement of children within each run
+    pub alignment: WrapAlignment,
+    /// Placement of the runs within the wrap
+    pub run_alignment: WrapAlignment,
+    pub cross_alignment: WrapCrossAlignment,
+    key: Option<WidgetKey>,
+}
+
+impl Wrap {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            direction: Axis::Horizontal,
+            spacing: 0.0,
+            run_spacing: 0.0,
+            alignment: WrapAlignment::Start,
+            run_alignment: WrapAlignment::Start,
+            cross_alignment: WrapCrossAlignment::Start,
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            children: self.children.iter().map(|c| c.clone_box()).collect(),
+            direction: self.direction,
+            spacing: self.spacing,
+            run_spacing: self.run_spacing,
+            alignment: self.alignment,
+            run_alignment: self.run_alignment,
+            cross_alignment: self.cross_alignment,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn add_child(mut self, child: Box<dyn Widget>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_direction(mut self, direction: Axis) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_run_spacing(mut self, run_spacing: f32) -> Self {
+        self.run_spacing = run_spacing;
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: WrapAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_run_alignment(mut self, run_alignment: WrapAlignment) -> Self {
+        self.run_alignment = run_alignment;
+        self
+    }
+
+    pub fn with_cross_alignment(mut self, cross_alignment: WrapCrossAlignment) -> Self {
+        self.cross_alignment = cross_alignment;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// `size` as (main, cross) extents
+    fn split(&self, size: Size) -> (f32, f32) {
+        match self.direction {
+            Axis::Horizontal => (size.width, size.height),
+            Axis::Vertical => (size.height, size.width),
+        }
+    }
+}
+
+impl Default for Wrap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Wrap {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let child_constraints = ctx.constraints.loosen();
+        let built = ctx.child_context(ctx.element_id, child_constraints).build_children(&self.children);
+        let sizes: Vec<(f32, f32)> = built.iter().map(|obj| self.split(obj.layout_size())).collect();
+
+        // Unbounded wraps never break
+        let (max_main, _) = self.split(Size::new(ctx.constraints.max_width, ctx.constraints.max_height));
+        let mut runs: Vec<Run> = Vec::new();
+        for (i, &(main, cross)) in sizes.iter().enumerate() {
+            match runs.last_mut() {
+                Some(run) if run.main + self.spacing + main <= max_main => {
+                    run.end = i + 1;
+                    run.main += self.spacing + main;
+                    run.cross = run.cross.max(cross);
+                }
+                _ => runs.push(Run { start: i, end: i + 1, main, cross }),
+            }
+        }
+
+        let content_main = runs.iter().map(|run| run.main).fold(0.0, f32::max);
+        let content_cross = runs.iter().map(|run| run.cross).sum::<f32>()
+            + self.run_spacing * runs.len().saturating_sub(1) as f32;
+        let size = ctx.constraints.constrain(match self.direction {
+            Axis::Horizontal => Size::new(content_main, content_cross),
+            Axis::Vertical => Size::new(content_cross, content_main),
+        });
+        let (container_main, container_cross) = self.split(size);
+
+        let mut layers = RenderObject::child_vec(built.len());
+        let mut built = built.into_iter();
+        let (mut cross_offset, run_gap) =
+            self.run_alignment.distribute(container_cross - content_cross, runs.len(), self.run_spacing);
+        for run in &runs {
+            let (mut main_offset, gap) =
+                self.alignment.distribute(container_main - run.main, run.end - run.start, self.spacing);
+            for &(main, cross) in &sizes[run.start..run.end] {
+                let render_obj = built.next().unwrap_or(RenderObject::None);
+                let within = match self.cross_alignment {
+                    WrapCrossAlignment::Start => 0.0,
+                    WrapCrossAlignment::End => run.cross - cross,
+                    WrapCrossAlignment::Center => (run.cross - cross) / 2.0,
+                };
+                let (x, y) = match self.direction {
+                    Axis::Horizontal => {
+                        (ctx.direction().place(main_offset, main, container_main), cross_offset + within)
+                    }
+                    Axis::Vertical => {
+                        (ctx.direction().place(cross_offset + within, cross, container_cross), main_offset)
+                    }
+                };
+                layers.push(RenderObject::transform(Matrix::translate(x, y), render_obj));
+                main_offset += main + gap;
+            }
+            cross_offset += run.cross + run_gap;
+        }
+
+        WidgetNode::Leaf(RenderObject::group(layers))
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::render_object::{Color, Rect};
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+
+    #[derive(Clone)]
+    struct Chip;
+
+    impl Widget for Chip {
+        fn build(&self, _ctx: &BuildContext) -> WidgetNode {
+            WidgetNode::Leaf(RenderObject::rect(Rect::new(0.0, 0.0, 40.0, 20.0), Color::BLUE))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_wrap_breaks_runs_when_the_line_is_full() {
+        let tree = new_shared_element_tree();
+        let root = tree.write().create_element(&Wrap::new(), None, 0);
+        let ctx = BuildContext::new(
+            root,
+            tree.clone(),
+            Constraints::loose(Size::new(100.0, 400.0)),
+            Arc::new(Theme::default()),
+        );
+        let wrap = Wrap::new()
+            .with_spacing(10.0)
+            .with_run_spacing(5.0)
+            .with_alignment(WrapAlignment::Center)
+            .with_children((0..5).map(|_| Box::new(Chip) as Box<dyn Widget>).collect());
+
+        let WidgetNode::Leaf(render_obj) = wrap.build(&ctx) else {
+            panic!("expected the wrap's children");
+        };
+        let RenderObject::Group { children } = &render_obj else {
+            panic!("expected a group");
+        };
+        let origins: Vec<(f32, f32)> = children
+            .iter()
+            .map(|child| child.bounds().map(|b| (b.x, b.y)).unwrap())
+            .collect();
+        // Two 40px chips and a 10px gap fill 90 of the 100px; the third wraps
+        assert_eq!(origins, vec![(0.0, 0.0), (50.0, 0.0), (0.0, 25.0), (50.0, 25.0), (25.0, 50.0)]);
+        // Shrink-wrapped to the widest run
+        assert_eq!(render_obj.layout_size(), Size::new(90.0, 70.0));
+        // Each chip is an element of its own under the wrap
+        assert_eq!(tree.read().get_children(root).len(), 5);
+    }
+}
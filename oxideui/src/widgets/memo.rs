@@ -0,0 +1,208 @@
+//! Memoized subtrees
+//!
+//! `Memo` rebuilds its child only when its dependencies, constraints or theme
+//! change. The cache is kept on the memo's element, so a parent that recreates
+//! the `Memo` on every build still hits it, and it goes away with the element.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+
+use crate::core::context::{BuildContext, Theme};
+use crate::core::render_object::RenderObject;
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::constraints::Constraints;
+
+/// Cached output of a memoized build
+struct MemoSlot {
+    deps: Box<dyn Any + Send + Sync>,
+    constraints: Constraints,
+    theme: Arc<Theme>,
+    /// Invalidations of the memo's key when it was built
+    generation: u64,
+    output: RenderObject,
+}
+
+type SharedSlot = Arc<RwLock<Option<MemoSlot>>>;
+
+/// Times each key was invalidated; caches built before the latest
+/// invalidation are stale
+fn generations() -> &'static RwLock<HashMap<WidgetKey, u64>> {
+    static GENERATIONS: OnceLock<RwLock<HashMap<WidgetKey, u64>>> = OnceLock::new();
+    GENERATIONS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn generation(key: Option<&WidgetKey>) -> u64 {
+    key.and_then(|key| generations().read().get(key).copied()).unwrap_or(0)
+}
+
+/// Skips rebuilding `builder`'s subtree while `deps` are unchanged
+pub struct Memo<D: PartialEq + Clone + Send + Sync + 'static> {
+    pub deps: D,
+    pub builder: Arc<dyn Fn(&D, &BuildContext) -> Box<dyn Widget> + Send + Sync>,
+    /// Invalidations of `key` when the memo was created
+    generation: u64,
+    key: Option<WidgetKey>,
+}
+
+impl<D: PartialEq + Clone + Send + Sync + 'static> Memo<D> {
+    pub fn new<F>(deps: D, builder: F) -> Self
+    where
+        F: Fn(&D, &BuildContext) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        Self {
+            deps,
+            builder: Arc::new(builder),
+            generation: 0,
+            key: None,
+        }
+    }
+
+    /// Key the memo so it keeps its element, and cache, among siblings
+    /// that move, and so `invalidate` can reach it
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.generation = generation(Some(&key));
+        self.key = Some(key);
+        self
+    }
+
+    /// Drop the cached output of every memo with `key`
+    pub fn invalidate(key: &WidgetKey) {
+        *generations().write().entry(key.clone()).or_default() += 1;
+    }
+
+    /// The cache on the element being built, created on first use
+    fn slot(ctx: &BuildContext) -> SharedSlot {
+        let mut tree = ctx.element_tree.write();
+        let Some(element) = tree.get_mut(ctx.element_id) else {
+            return SharedSlot::default();
+        };
+        if let Some(slot) = element.state.as_ref().and_then(|state| state.downcast_ref::<SharedSlot>()) {
+            return slot.clone();
+        }
+        let slot = SharedSlot::default();
+        element.state = Some(Box::new(slot.clone()));
+        slot
+    }
+
+    fn is_cached_for(slot: &MemoSlot, deps: &D, generation: u64, ctx: &BuildContext) -> bool {
+        slot.constraints == ctx.constraints
+            && slot.generation == generation
+            && Arc::ptr_eq(&slot.theme, &ctx.theme)
+            && slot.deps.downcast_ref::<D>() == Some(deps)
+    }
+}
+
+impl<D: PartialEq + Clone + Send + Sync + 'static> Clone for Memo<D> {
+    fn clone(&self) -> Self {
+        Self {
+            deps: self.deps.clone(),
+            builder: self.builder.clone(),
+            generation: self.generation,
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl<D: PartialEq + Clone + Send + Sync + 'static> Widget for Memo<D> {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let slot = Self::slot(ctx);
+        let generation = generation(self.key.as_ref());
+        if let Some(cached) = slot.read().as_ref() {
+            if Self::is_cached_for(cached, &self.deps, generation, ctx) {
+                // The output still points at the child's elements
+                ctx.element_tree.write().retain_subtree(ctx.element_id);
+                return WidgetNode::Leaf(cached.output.clone());
+            }
+        }
+
+        let child = (self.builder)(&self.deps, ctx);
+        let output = match child.build(ctx) {
+            WidgetNode::Leaf(render_obj) => render_obj,
+            // Containers can't be cached as render output; pass them through
+            other => return other,
+        };
+
+        *slot.write() = Some(MemoSlot {
+            deps: Box::new(self.deps.clone()),
+            constraints: ctx.constraints,
+            theme: ctx.theme.clone(),
+            generation,
+            output: output.clone(),
+        });
+
+        WidgetNode::Leaf(output)
+    }
+
+    fn should_rebuild(&self, old: &dyn Widget) -> bool {
+        match old.as_any().downcast_ref::<Memo<D>>() {
+            Some(old) => old.deps != self.deps || old.generation != self.generation,
+            None => true,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::runtime::widget_builder::WidgetBuilder;
+    use crate::widgets::basic::{Column, Text};
+
+    #[test]
+    fn test_unchanged_memos_keep_their_elements_and_cache_per_tree() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let key = WidgetKey::string("memo-test-trees");
+        let root = || -> Box<dyn Widget> {
+            let counter = builds.clone();
+            let memo = Memo::new(1u32, move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Box::new(Column::new().with_children(vec![Box::new(Text::new("a")), Box::new(Text::new("b"))]))
+            })
+            .with_key(key.clone());
+            Box::new(Column::new().with_children(vec![Box::new(memo)]))
+        };
+        let window = Constraints::new(0.0, 400.0, 0.0, 300.0);
+        let tree = new_shared_element_tree();
+        let builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(tree.clone());
+
+        builder.build_widget_tree(&root(), window);
+        let elements = tree.read().len();
+        builder.build_widget_tree(&root(), window);
+        // Skipped, but the subtree's elements are still there to be hit
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert_eq!(tree.read().len(), elements);
+
+        // A memo with the same key in another tree has its own cache, and
+        // unmounting it leaves this one alone
+        let other = new_shared_element_tree();
+        let other_builder = WidgetBuilder::new(Arc::new(Theme::default())).with_element_tree(other.clone());
+        other_builder.build_widget_tree(&root(), window);
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        let empty: Box<dyn Widget> = Box::new(Column::new());
+        other_builder.build_widget_tree(&empty, window);
+        builder.build_widget_tree(&root(), window);
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        assert_eq!(tree.read().len(), elements);
+
+        Memo::<u32>::invalidate(&key);
+        builder.build_widget_tree(&root(), window);
+        assert_eq!(builds.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,13 +1,34 @@
 pub mod basic;
+pub mod clip;
 pub mod complex_layout_widgets;
 pub mod complex_widgets;
+pub mod effects;
 pub mod element_widgets;
+pub mod error_boundary;
 pub mod layout_widgets;
+pub mod memo;
+pub mod portal;
+pub(crate) mod keyboard_nav;
 pub(crate) mod scrolling;
+pub mod selection;
+pub mod semantics;
+pub mod suspense;
+pub mod title_bar;
+pub mod transform;
 
-pub use basic::Container;
+pub use basic::{Container, DefaultTextStyle};
+pub use clip::{ClipOval, ClipPath, ClipRRect};
 pub use complex_layout_widgets::*;
 pub use complex_widgets::*;
+pub use effects::{BackdropFilter, Filtered, Opacity};
 pub use element_widgets::*;
+pub use error_boundary::{BoundaryError, ErrorBoundary};
 pub use layout_widgets::*;
+pub use memo::Memo;
+pub use portal::{DismissBarrier, Portal};
+pub use selection::{SelectionController, SelectionMode};
+pub use semantics::Semantics;
+pub use suspense::Suspense;
+pub use title_bar::{CustomTitleBar, WindowFrame};
+pub use transform::Transform;
 pub use crate::widgets::scrolling::{ScrollController, ScrollPhysics, ClipManager};
@@ -0,0 +1,250 @@
+//! Portals into the overlay layer
+//!
+//! A `Portal` builds its child in place - with the same context, state and
+//! element - but its output is drawn in the overlay pass, above all other
+//! content and outside any ancestor clip (ScrollArea, Card bounds, ...).
+//!
+//! A `DismissBarrier` is a portal for popups: it lays a transparent hit
+//! region over the whole window beneath its child, so a click outside the
+//! popup reaches the barrier instead of the content underneath, and calls
+//! `on_dismiss` for that click or for Escape.
+//!
+//! ```rust,ignore
+//! if menu_open.get() {
+//!     let open = menu_open.clone();
+//!     children.push(Box::new(DismissBarrier::new(menu).with_on_dismiss(move || open.set(false))));
+//! }
+//! ```
+
+use std::any::Any;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use winit::keyboard::KeyCode;
+
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::core::render_object::{Color, Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+
+/// Renders its child into the overlay root
+pub struct Portal {
+    pub child: Box<dyn Widget>,
+    key: Option<WidgetKey>,
+}
+
+impl Portal {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for Portal {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        match build_popup(self.child.as_ref(), ctx) {
+            Some(render_obj) => WidgetNode::Leaf(RenderObject::overlay(render_obj)),
+            None => WidgetNode::None,
+        }
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        self.child.handle_event(event, context)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// The child's output, with a container's children built here so they end
+/// up in the overlay too rather than inline under the caller
+fn build_popup(child: &dyn Widget, ctx: &BuildContext) -> Option<RenderObject> {
+    match child.build(ctx) {
+        WidgetNode::Leaf(render_obj) => Some(render_obj),
+        WidgetNode::Container { children } => Some(RenderObject::group(ctx.build_children(&children))),
+        WidgetNode::None => None,
+    }
+}
+
+/// Shows its child as a popup in the overlay, dismissed by a click
+/// outside it or Escape
+pub struct DismissBarrier {
+    pub child: Box<dyn Widget>,
+    pub on_dismiss: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// The child's bounds in the last build, to tell outside clicks
+    popup: Arc<RwLock<Option<Rect>>>,
+    key: Option<WidgetKey>,
+}
+
+impl DismissBarrier {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            on_dismiss: None,
+            popup: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            on_dismiss: self.on_dismiss.clone(),
+            popup: self.popup.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_on_dismiss<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_dismiss = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// `popup` in the overlay above a transparent region covering the
+    /// window, for widgets that draw their own popups. The region only
+    /// widens the element's bounds for hit-testing; no backend paints it.
+    pub fn overlay(popup: RenderObject) -> RenderObject {
+        const EXTENT: f32 = 100_000.0;
+        let barrier = RenderObject::rect(Rect::new(-EXTENT, -EXTENT, 2.0 * EXTENT, 2.0 * EXTENT), Color::TRANSPARENT);
+        RenderObject::overlay(RenderObject::group(vec![barrier, popup]))
+    }
+
+    /// Whether `event` dismisses a popup covering `popup`: a click released
+    /// outside it, or Escape
+    pub fn is_dismissal(event: &UiEvent, popup: Rect) -> bool {
+        match event {
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } => !popup.contains(position.x, position.y),
+            UiEvent::KeyDown { key: KeyCode::Escape, .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl Widget for DismissBarrier {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        match build_popup(self.child.as_ref(), ctx) {
+            Some(render_obj) => {
+                *self.popup.write() = render_obj.bounds();
+                WidgetNode::Leaf(Self::overlay(render_obj))
+            }
+            None => WidgetNode::None,
+        }
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        let result = self.child.handle_event(event, context);
+        if result != EventResult::Unhandled {
+            return result;
+        }
+        let popup = self.popup.read().unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0));
+        if !Self::is_dismissal(event, popup) {
+            return result;
+        }
+        if let Some(on_dismiss) = &self.on_dismiss {
+            on_dismiss();
+        }
+        EventResult::Stopped
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::core::element::{new_shared_element_tree, ElementId};
+    use crate::core::event::EventPhase;
+    use crate::core::render_object::Point;
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use crate::widgets::layout_widgets::SizedBox;
+    use crate::widgets::Semantics;
+
+    #[test]
+    fn test_barrier_dismisses_on_outside_click() {
+        let dismissed = Arc::new(AtomicUsize::new(0));
+        let counter = dismissed.clone();
+        let barrier = DismissBarrier::new(SizedBox::new(100.0, 50.0)).with_on_dismiss(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let ctx = BuildContext::new(
+            ElementId::new(0),
+            new_shared_element_tree(),
+            Constraints::new(0.0, 400.0, 0.0, 400.0),
+            Arc::new(Theme::default()),
+        );
+        let WidgetNode::Leaf(RenderObject::Overlay { .. }) = barrier.build(&ctx) else {
+            panic!("expected the popup in the overlay");
+        };
+
+        let release = |x: f32, y: f32| {
+            let mut context = EventContext::new(ElementId::new(0), ElementId::new(0), EventPhase::AtTarget);
+            let event = UiEvent::PointerUp { id: 0, position: Point::new(x, y), button: MouseButton::Left };
+            barrier.handle_event(&event, &mut context)
+        };
+        assert_eq!(release(50.0, 25.0), EventResult::Unhandled);
+        assert_eq!(dismissed.load(Ordering::SeqCst), 0);
+        assert_eq!(release(300.0, 25.0), EventResult::Stopped);
+        assert_eq!(dismissed.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_container_children_are_built_into_the_overlay() {
+        let tree = new_shared_element_tree();
+        let ctx = BuildContext::new(ElementId::new(0), tree.clone(), Constraints::new(0.0, 400.0, 0.0, 400.0), Arc::new(Theme::default()));
+        // Semantics builds as a Container holding its child
+        let popup = || Semantics::new(SizedBox::new(100.0, 50.0)).with_label("Menu");
+
+        let WidgetNode::Leaf(RenderObject::Overlay { .. }) = Portal::new(popup()).build(&ctx) else {
+            panic!("expected the popup in the overlay");
+        };
+        let barrier = DismissBarrier::new(popup());
+        let WidgetNode::Leaf(RenderObject::Overlay { .. }) = barrier.build(&ctx) else {
+            panic!("expected the popup in the overlay");
+        };
+        assert!(barrier.popup.read().is_some_and(|popup| popup.width == 100.0));
+        assert!(!tree.read().is_empty());
+    }
+}
@@ -1,9 +1,11 @@
 // File: ./oxideui/src/widgets/scrolling.rs
 //! Advanced scrolling and clipping with momentum and snap points
 
-use std::time::{Duration, Instant};
+use std::time::Duration;
+use web_time::Instant;
 use crate::core::render_object::{Point, Rect};
 use crate::core::event::Vector2;
+use crate::animation::reduced_motion;
 
 /// Scroll physics for natural scrolling behavior
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -59,9 +61,9 @@ impl ScrollController {
         self.last_update = Instant::now();
     }
 
-    /// Apply momentum scrolling
+    /// Apply momentum scrolling; off under reduced motion
     pub fn update_momentum(&mut self, dt: f32) {
-        if !self.momentum_enabled || self.velocity.x.abs() < 0.1 && self.velocity.y.abs() < 0.1 {
+        if !self.momentum_enabled || reduced_motion() || self.velocity.x.abs() < 0.1 && self.velocity.y.abs() < 0.1 {
             self.velocity = Vector2::ZERO;
             self.is_scrolling = false;
             return;
@@ -0,0 +1,254 @@
+//! Row selection shared by lists and tables
+//!
+//! A `SelectionController` follows the desktop conventions: a click selects
+//! one row, Ctrl+click (Cmd on macOS) toggles a row, and Shift+click selects
+//! the range from the last row clicked. The arrow keys move the selection,
+//! extending it with Shift, or move only the keyboard focus with Ctrl so
+//! Ctrl+Space can pick rows one by one. Ctrl+A selects everything.
+//!
+//! The selection is kept by index, but follows its rows when they are
+//! sorted, filtered or replaced: lists and tables report their rows' ids on
+//! every build, and rows that are gone are deselected.
+//!
+//! Clones share their state, so the app keeps one to read the selection
+//! while ListView or Table updates it:
+//!
+//! ```rust,ignore
+//! let selection = SelectionController::new(SelectionMode::Multiple);
+//! selection.selected().subscribe(|rows| println!("{} selected", rows.len()));
+//! Table::new(columns).with_rows(rows).with_selection(selection.clone())
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use winit::keyboard::KeyCode;
+
+use crate::core::event::Modifiers;
+use crate::state_management::State;
+use crate::widgets::keyboard_nav::{step_index, NavAxis};
+
+/// How many rows can be selected at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    Single,
+    Multiple,
+}
+
+#[derive(Debug, Default)]
+struct SelectionCursor {
+    /// Where Shift ranges start: the last row clicked or moved to without Shift
+    anchor: Option<usize>,
+    /// Row the keyboard is on
+    lead: Option<usize>,
+    /// Ids of the rows, in order, when they were last reported
+    rows: Vec<u64>,
+}
+
+/// Selected rows of a list or table, by index
+#[derive(Clone)]
+pub struct SelectionController {
+    pub mode: SelectionMode,
+    selected: State<BTreeSet<usize>>,
+    cursor: Arc<RwLock<SelectionCursor>>,
+}
+
+impl SelectionController {
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            selected: State::new(BTreeSet::new()),
+            cursor: Arc::new(RwLock::new(SelectionCursor::default())),
+        }
+    }
+
+    /// The selected rows, notifying subscribers when they change
+    pub fn selected(&self) -> State<BTreeSet<usize>> {
+        self.selected.clone()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.get().contains(&index)
+    }
+
+    /// Row with keyboard focus
+    pub fn lead(&self) -> Option<usize> {
+        self.cursor.read().lead
+    }
+
+    pub fn select(&self, index: usize) {
+        self.replace([index].into());
+        let mut cursor = self.cursor.write();
+        cursor.anchor = Some(index);
+        cursor.lead = Some(index);
+    }
+
+    pub fn select_all(&self, len: usize) {
+        if self.mode == SelectionMode::Multiple {
+            self.replace((0..len).collect());
+        }
+    }
+
+    pub fn clear(&self) {
+        self.replace(BTreeSet::new());
+    }
+
+    /// Update the selection for a click on row `index`
+    pub fn click(&self, index: usize, modifiers: Modifiers) {
+        let toggle = modifiers.ctrl || modifiers.meta;
+        if self.mode == SelectionMode::Single || !(toggle || modifiers.shift) {
+            self.select(index);
+            return;
+        }
+        let mut cursor = self.cursor.write();
+        if modifiers.shift {
+            let anchor = cursor.anchor.unwrap_or(index);
+            // Ctrl+Shift adds the range to what was already selected
+            let mut selected = if toggle { self.selected.get() } else { BTreeSet::new() };
+            selected.extend(anchor.min(index)..=anchor.max(index));
+            cursor.lead = Some(index);
+            drop(cursor);
+            self.replace(selected);
+        } else {
+            let mut selected = self.selected.get();
+            if !selected.remove(&index) {
+                selected.insert(index);
+            }
+            cursor.anchor = Some(index);
+            cursor.lead = Some(index);
+            drop(cursor);
+            self.replace(selected);
+        }
+    }
+
+    /// Move or extend the selection for `key` in a vertical list of `len`
+    /// rows, `page` rows to a screen. Returns whether the key was used.
+    pub fn handle_key(&self, key: KeyCode, modifiers: Modifiers, len: usize, page: usize) -> bool {
+        let multiple = self.mode == SelectionMode::Multiple;
+        let toggle = modifiers.ctrl || modifiers.meta;
+        match key {
+            KeyCode::KeyA if toggle && multiple => {
+                self.select_all(len);
+                return true;
+            }
+            KeyCode::Space if toggle && multiple => {
+                let Some(lead) = self.lead().filter(|lead| *lead < len) else {
+                    return false;
+                };
+                self.click(lead, modifiers);
+                return true;
+            }
+            _ => {}
+        }
+
+        let Some(index) = step_index(self.lead(), key, NavAxis::Vertical, len, page) else {
+            return false;
+        };
+        if multiple && modifiers.shift {
+            self.click(index, Modifiers { ctrl: false, meta: false, ..modifiers });
+        } else if multiple && toggle {
+            self.cursor.write().lead = Some(index);
+        } else {
+            self.select(index);
+        }
+        true
+    }
+
+    /// Report the ids of the rows in their current order. When they
+    /// changed since the last report, the selection moves with its rows
+    /// and rows that are gone are deselected.
+    pub fn follow_rows(&self, rows: &[u64]) {
+        let mut cursor = self.cursor.write();
+        if cursor.rows == rows {
+            return;
+        }
+        let previous = std::mem::replace(&mut cursor.rows, rows.to_vec());
+        // Indices chosen before the rows were first reported stand
+        if previous.is_empty() {
+            return;
+        }
+        // Later duplicates don't take over the first row with an id
+        let index_of: HashMap<u64, usize> = rows.iter().enumerate().rev().map(|(index, id)| (*id, index)).collect();
+        let remap = |index: usize| previous.get(index).and_then(|id| index_of.get(id).copied());
+        cursor.anchor = cursor.anchor.and_then(remap);
+        cursor.lead = cursor.lead.and_then(remap);
+        drop(cursor);
+        self.replace(self.selected.get().into_iter().filter_map(remap).collect());
+    }
+
+    fn replace(&self, selected: BTreeSet<usize>) {
+        if selected != self.selected.get() {
+            self.selected.set(selected);
+        }
+    }
+}
+
+impl Default for SelectionController {
+    fn default() -> Self {
+        Self::new(SelectionMode::Single)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(selection: &SelectionController) -> Vec<usize> {
+        selection.selected().get().into_iter().collect()
+    }
+
+    #[test]
+    fn test_click_and_keyboard_selection() {
+        let ctrl = Modifiers { ctrl: true, ..Default::default() };
+        let shift = Modifiers { shift: true, ..Default::default() };
+        let selection = SelectionController::new(SelectionMode::Multiple);
+
+        selection.click(2, Modifiers::default());
+        selection.click(5, ctrl);
+        assert_eq!(selected(&selection), vec![2, 5]);
+        // The range runs from the row last clicked without Shift
+        selection.click(3, shift);
+        assert_eq!(selected(&selection), vec![3, 4, 5]);
+        selection.click(2, ctrl);
+        selection.click(4, ctrl);
+        assert_eq!(selected(&selection), vec![2, 3, 5]);
+
+        // Shift+arrows extend from the anchor; Ctrl moves without selecting
+        selection.select(4);
+        assert!(selection.handle_key(KeyCode::ArrowDown, shift, 10, 5));
+        assert!(selection.handle_key(KeyCode::ArrowDown, shift, 10, 5));
+        assert_eq!(selected(&selection), vec![4, 5, 6]);
+        selection.handle_key(KeyCode::ArrowDown, ctrl, 10, 5);
+        selection.handle_key(KeyCode::ArrowDown, ctrl, 10, 5);
+        selection.handle_key(KeyCode::Space, ctrl, 10, 5);
+        assert_eq!(selected(&selection), vec![4, 5, 6, 8]);
+        assert!(!selection.handle_key(KeyCode::ArrowLeft, shift, 10, 5));
+
+        // Single selection ignores the modifiers
+        let single = SelectionController::new(SelectionMode::Single);
+        single.click(1, Modifiers::default());
+        single.click(3, shift);
+        assert_eq!(selected(&single), vec![3]);
+        single.handle_key(KeyCode::ArrowUp, shift, 10, 5);
+        assert_eq!(selected(&single), vec![2]);
+    }
+
+    #[test]
+    fn test_selection_follows_sorted_and_filtered_rows() {
+        let selection = SelectionController::new(SelectionMode::Multiple);
+        selection.follow_rows(&[10, 11, 12, 13]);
+        selection.click(1, Modifiers::default());
+        selection.click(3, Modifiers { ctrl: true, ..Default::default() });
+
+        // Reversed: rows 11 and 13 are now at 2 and 0
+        selection.follow_rows(&[13, 12, 11, 10]);
+        assert_eq!(selected(&selection), vec![0, 2]);
+        assert_eq!(selection.lead(), Some(0));
+
+        // Filtered: 13 is gone, 11 moved up
+        selection.follow_rows(&[12, 11]);
+        assert_eq!(selected(&selection), vec![1]);
+        assert_eq!(selection.lead(), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! Accessibility annotations for a subtree
+//!
+//! ```rust,no_run
+//! use oxideui::core::AccessibilityRole;
+//! use oxideui::widgets::basic::Text;
+//! use oxideui::widgets::Semantics;
+//!
+//! let status = Semantics::new(Text::new("3 new messages"))
+//!     .with_role(AccessibilityRole::Text)
+//!     .polite();
+//! ```
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::{AccessibilityRole, LiveRegion, SemanticsData};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+
+/// Describes its child to assistive technology; draws nothing itself
+pub struct Semantics {
+    pub child: Box<dyn Widget>,
+    pub data: SemanticsData,
+    key: Option<WidgetKey>,
+}
+
+impl Semantics {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            data: SemanticsData::new(),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            data: self.data.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.data.label = Some(label.into());
+        self
+    }
+
+    pub fn with_role(mut self, role: AccessibilityRole) -> Self {
+        self.data.role = Some(role);
+        self
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.data.value = Some(value.into());
+        self
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.data.hint = Some(hint.into());
+        self
+    }
+
+    /// Announce changes when the user is idle
+    pub fn polite(mut self) -> Self {
+        self.data.live_region = Some(LiveRegion::Polite);
+        self
+    }
+
+    /// Announce changes immediately, for errors and alerts
+    pub fn assertive(mut self) -> Self {
+        self.data.live_region = Some(LiveRegion::Assertive);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl Widget for Semantics {
+    fn build(&self, _ctx: &BuildContext) -> WidgetNode {
+        WidgetNode::Container {
+            children: vec![self.child.clone_box()],
+        }
+    }
+
+    fn semantics(&self) -> Option<SemanticsData> {
+        Some(self.data.clone())
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::ElementTree;
+    use crate::core::AccessibilityManager;
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_semantics_exported_from_tree() {
+        let mut tree = ElementTree::new();
+        let status = Semantics::new(Text::new("Saved")).with_label("Status").with_value("Saved").polite();
+        let id = tree.create_element(&status, None, 0);
+
+        let mut a11y = AccessibilityManager::new();
+        a11y.sync_from_tree(&tree);
+        assert_eq!(a11y.get_label(id), Some("Status"));
+        assert_eq!(a11y.get_value(id), Some("Saved"));
+        assert_eq!(a11y.get_live_region(id), Some(LiveRegion::Polite));
+
+        // Updating the widget updates what the manager exports
+        tree.get_mut(id).unwrap().semantics = status.with_value("Saving...").semantics();
+        a11y.sync_from_tree(&tree);
+        assert_eq!(a11y.get_value(id), Some("Saving..."));
+
+        tree.remove_element(id);
+        a11y.sync_from_tree(&tree);
+        assert_eq!(a11y.get_label(id), None);
+    }
+}
@@ -0,0 +1,224 @@
+//! Suspense boundaries
+//!
+//! `Suspense` builds its content, collecting every `AsyncState` read while
+//! it does. If any of them is still loading, the fallback is shown instead
+//! and the boundary rebuilds when they settle; states without a tracker to
+//! notify it are polled.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Rect, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::runtime::TimerHandle;
+use crate::state_management::async_state::{PendingState, SuspenseScope};
+use crate::ThemeProvider;
+
+/// How often states that can't notify the boundary are checked
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shows a fallback while descendant async state is loading
+pub struct Suspense {
+    pub content: Arc<dyn Fn(&BuildContext) -> Box<dyn Widget> + Send + Sync>,
+    pub fallback: Option<Arc<dyn Fn(&BuildContext) -> Box<dyn Widget> + Send + Sync>>,
+    /// Keep the fallback visible at least this long once shown
+    pub min_display: Option<Duration>,
+    pub states: Vec<Arc<dyn PendingState>>,
+    fallback_since: Arc<RwLock<Option<Instant>>>,
+    polling: Arc<RwLock<Option<TimerHandle>>>,
+    key: Option<WidgetKey>,
+}
+
+impl Suspense {
+    pub fn new<F>(content: F) -> Self
+    where
+        F: Fn(&BuildContext) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        Self {
+            content: Arc::new(content),
+            fallback: None,
+            min_display: None,
+            states: Vec::new(),
+            fallback_since: Arc::new(RwLock::new(None)),
+            polling: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            content: self.content.clone(),
+            fallback: self.fallback.clone(),
+            min_display: self.min_display,
+            states: self.states.clone(),
+            fallback_since: self.fallback_since.clone(),
+            polling: self.polling.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    /// Widget shown while loading; defaults to a skeleton block
+    pub fn with_fallback<F>(mut self, fallback: F) -> Self
+    where
+        F: Fn(&BuildContext) -> Box<dyn Widget> + Send + Sync + 'static,
+    {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+
+    /// Avoid flicker by keeping the fallback up for at least `duration`
+    pub fn with_min_display(mut self, duration: Duration) -> Self {
+        self.min_display = Some(duration);
+        self
+    }
+
+    /// Also wait on a state that isn't read during the content build
+    pub fn with_state(mut self, state: Arc<dyn PendingState>) -> Self {
+        self.states.push(state);
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Rebuild every `POLL_INTERVAL` until `stop_polling`
+    fn poll(&self, ctx: &BuildContext) {
+        *self.polling.write() = ctx.every(POLL_INTERVAL, || {});
+    }
+
+    fn stop_polling(&self) {
+        if let Some(timer) = self.polling.write().take() {
+            timer.cancel();
+        }
+    }
+
+    fn build_fallback(&self, ctx: &BuildContext) -> WidgetNode {
+        if let Some(fallback) = &self.fallback {
+            return fallback(ctx).build(ctx);
+        }
+
+        // Skeleton: three muted bars
+        let theme = ctx.theme();
+        let width = ctx.constraints.max_width.min(320.0);
+        let bars = [1.0, 0.8, 0.6]
+            .iter()
+            .enumerate()
+            .map(|(i, fraction)| {
+                RenderObject::rect(Rect::new(0.0, i as f32 * 24.0, width * fraction, 14.0), theme.muted)
+            })
+            .collect();
+
+        WidgetNode::Leaf(RenderObject::group(bars))
+    }
+}
+
+impl Widget for Suspense {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let scope = SuspenseScope::enter();
+        let content = (self.content)(ctx);
+        // Children are built here rather than by the caller, so their reads
+        // land in this boundary's scope
+        let node = match content.build(ctx) {
+            WidgetNode::Container { children } => WidgetNode::Leaf(RenderObject::group(ctx.build_children(&children))),
+            node => node,
+        };
+        let read_states = scope.exit();
+
+        let pending: Vec<_> = read_states.iter().chain(self.states.iter()).filter(|s| s.is_pending()).collect();
+        if !pending.is_empty() {
+            self.fallback_since.write().get_or_insert_with(Instant::now);
+            let notified = pending.iter().all(|state| state.subscribe(ctx.element_id));
+            if notified {
+                self.stop_polling();
+            } else {
+                self.poll(ctx);
+            }
+            return self.build_fallback(ctx);
+        }
+
+        let shown_at = *self.fallback_since.read();
+        if let (Some(shown_at), Some(min_display)) = (shown_at, self.min_display) {
+            if shown_at.elapsed() < min_display {
+                self.poll(ctx);
+                return self.build_fallback(ctx);
+            }
+        }
+
+        self.stop_polling();
+        *self.fallback_since.write() = None;
+        node
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::state_driven::StateTracker;
+    use crate::core::Theme;
+    use crate::layout::Constraints;
+    use crate::state_management::AsyncState;
+    use crate::widgets::basic::Column;
+
+    #[derive(Clone)]
+    struct Reader(AsyncState<i32>);
+
+    impl Widget for Reader {
+        fn build(&self, _ctx: &BuildContext) -> WidgetNode {
+            self.0.read();
+            WidgetNode::Leaf(RenderObject::None)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn Widget> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_nested_reads_suspend_until_the_state_settles() {
+        let tracker = Arc::new(StateTracker::new());
+        let state = AsyncState::<i32>::pending().with_tracker(tracker.clone());
+        let reader = state.clone();
+        // The read happens in a grandchild, built from a Container
+        let suspense =
+            Suspense::new(move |_| Box::new(Column::new().with_children(vec![Box::new(Reader(reader.clone()))])));
+
+        let tree = new_shared_element_tree();
+        let id = tree.write().mount(None, &suspense);
+        tree.write().get_mut(id).unwrap().dirty = false;
+        let ctx = BuildContext::new(id, tree.clone(), Constraints::new(0.0, 400.0, 0.0, 300.0), Arc::new(Theme::default()));
+
+        suspense.build(&ctx);
+        assert!(suspense.fallback_since.read().is_some());
+        // No rebuild is asked for until the state settles
+        assert!(!tree.read().get(id).unwrap().dirty);
+        state.resolve(1);
+        assert!(tracker.get_dirty_elements().contains(&id));
+
+        suspense.build(&ctx);
+        assert!(suspense.fallback_since.read().is_none());
+    }
+}
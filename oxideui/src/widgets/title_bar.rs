@@ -0,0 +1,389 @@
+//! Client-side window decorations
+//!
+//! Run with `Runtime::with_custom_title_bar()` to turn off the native frame,
+//! then put a `CustomTitleBar` at the top of the UI and wrap everything in a
+//! `WindowFrame` to get edge resizing back. Moving and resizing go through
+//! the native move/resize loops, so OS snap assist keeps working.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
+
+use parking_lot::RwLock;
+
+use crate::core::context::BuildContext;
+use crate::core::event::{EventContext, EventResult, MouseButton, UiEvent};
+use crate::core::render_object::{Color, Matrix, Point, Rect, RenderObject, TextStyle};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::constraints::{Constraints, Size};
+use crate::platform::window::{ResizeEdge, WindowController};
+use crate::render::text::text_width;
+use crate::ThemeProvider;
+
+const BUTTON_WIDTH: f32 = 46.0;
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TitleBarButton {
+    Minimize,
+    Maximize,
+    Close,
+}
+
+/// Draggable title bar with minimize / maximize / close buttons
+pub struct CustomTitleBar {
+    pub title: String,
+    pub height: f32,
+    pub show_minimize: bool,
+    pub show_maximize: bool,
+    pub show_close: bool,
+    /// Extra content drawn after the title (menus, tabs, ...)
+    pub leading: Option<Box<dyn Widget>>,
+    window: Arc<RwLock<Option<WindowController>>>,
+    width: Arc<RwLock<f32>>,
+    last_press: Arc<RwLock<Option<Instant>>>,
+    key: Option<WidgetKey>,
+}
+
+impl CustomTitleBar {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            height: 36.0,
+            show_minimize: true,
+            show_maximize: true,
+            show_close: true,
+            leading: None,
+            window: Arc::new(RwLock::new(None)),
+            width: Arc::new(RwLock::new(0.0)),
+            last_press: Arc::new(RwLock::new(None)),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            title: self.title.clone(),
+            height: self.height,
+            show_minimize: self.show_minimize,
+            show_maximize: self.show_maximize,
+            show_close: self.show_close,
+            leading: self.leading.as_ref().map(|w| w.clone_box()),
+            window: self.window.clone(),
+            width: self.width.clone(),
+            last_press: self.last_press.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Choose which caption buttons are shown
+    pub fn with_buttons(mut self, minimize: bool, maximize: bool, close: bool) -> Self {
+        self.show_minimize = minimize;
+        self.show_maximize = maximize;
+        self.show_close = close;
+        self
+    }
+
+    pub fn with_leading<W: Widget + 'static>(mut self, widget: W) -> Self {
+        self.leading = Some(Box::new(widget));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Caption buttons laid out right to left from the bar's right edge
+    fn buttons(&self, width: f32) -> Vec<(TitleBarButton, Rect)> {
+        let mut buttons = Vec::new();
+        let mut x = width;
+        for (button, shown) in [
+            (TitleBarButton::Close, self.show_close),
+            (TitleBarButton::Maximize, self.show_maximize),
+            (TitleBarButton::Minimize, self.show_minimize),
+        ] {
+            if shown {
+                x -= BUTTON_WIDTH;
+                buttons.push((button, Rect::new(x, 0.0, BUTTON_WIDTH, self.height)));
+            }
+        }
+        buttons
+    }
+
+    fn button_at(&self, position: Point) -> Option<TitleBarButton> {
+        self.buttons(*self.width.read())
+            .into_iter()
+            .find(|(_, rect)| rect.contains(position.x, position.y))
+            .map(|(button, _)| button)
+    }
+}
+
+impl Widget for CustomTitleBar {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let theme = ctx.theme();
+        let width = ctx.constraints.max_width;
+        *self.width.write() = width;
+        *self.window.write() = ctx.window().cloned();
+        let maximized = ctx.window().is_some_and(|w| w.is_maximized());
+
+        let title_style = TextStyle {
+            font_family: theme.font_sans.clone(),
+            font_size: 13.0,
+            color: theme.foreground,
+            bold: true,
+            italic: false,
+            ..Default::default()
+        };
+        let mut render_objects = vec![
+            RenderObject::rect(Rect::new(0.0, 0.0, width, self.height), theme.card),
+            RenderObject::rect(Rect::new(0.0, self.height - 1.0, width, 1.0), theme.border),
+            RenderObject::text(self.title.clone(), title_style.clone(), Point::new(12.0, (self.height - 16.0) / 2.0)),
+        ];
+
+        if let Some(leading) = &self.leading {
+            // Between the title and the caption buttons, as a child element
+            // so it gets its own events
+            let x = 24.0 + text_width(&self.title, &title_style);
+            let buttons_left = self.buttons(width).last().map_or(width, |(_, rect)| rect.x);
+            let constraints = Constraints::new(0.0, (buttons_left - x).max(0.0), 0.0, self.height);
+            match ctx.child_context(ctx.element_id, constraints).build_child(leading.as_ref()) {
+                WidgetNode::Leaf(obj) => render_objects.push(RenderObject::transform(Matrix::translate(x, 0.0), obj)),
+                WidgetNode::Container { .. } | WidgetNode::None => {}
+            }
+        }
+
+        let glyph = |rect: &Rect, w: f32, h: f32, color: Color| {
+            RenderObject::rect(
+                Rect::new(rect.x + (rect.width - w) / 2.0, rect.y + (rect.height - h) / 2.0, w, h),
+                color,
+            )
+        };
+
+        for (button, rect) in self.buttons(width) {
+            match button {
+                TitleBarButton::Minimize => render_objects.push(glyph(&rect, 10.0, 1.0, theme.foreground)),
+                TitleBarButton::Maximize => {
+                    // Outlined square; inset when maximized to hint "restore"
+                    let size = if maximized { 8.0 } else { 10.0 };
+                    render_objects.push(glyph(&rect, size, size, theme.foreground));
+                    render_objects.push(glyph(&rect, size - 2.0, size - 2.0, theme.card));
+                }
+                TitleBarButton::Close => render_objects.push(RenderObject::text(
+                    "×".to_string(),
+                    TextStyle {
+                        font_family: theme.font_sans.clone(),
+                        font_size: 16.0,
+                        color: theme.foreground,
+                        bold: false,
+                        italic: false,
+                        ..Default::default()
+                    },
+                    Point::new(rect.x + (rect.width - 9.0) / 2.0, (self.height - 19.0) / 2.0),
+                )),
+            }
+        }
+
+        WidgetNode::Leaf(RenderObject::group(render_objects))
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        let window = self.window.read().clone();
+        let Some(window) = window else {
+            return EventResult::Unhandled;
+        };
+
+        match event {
+            UiEvent::PointerDown { position, button: MouseButton::Left, .. } if position.y <= self.height => {
+                if self.button_at(*position).is_some() {
+                    // Buttons act on release
+                    return EventResult::Stopped;
+                }
+
+                let now = Instant::now();
+                let double_click = self.last_press.read().is_some_and(|t| now.duration_since(t) < DOUBLE_CLICK);
+                if double_click {
+                    *self.last_press.write() = None;
+                    window.toggle_maximize();
+                } else {
+                    *self.last_press.write() = Some(now);
+                    window.start_drag();
+                }
+                EventResult::Stopped
+            }
+            UiEvent::PointerUp { position, button: MouseButton::Left, .. } if context.is_at_target() => {
+                match self.button_at(*position) {
+                    Some(TitleBarButton::Minimize) => window.minimize(),
+                    Some(TitleBarButton::Maximize) => window.toggle_maximize(),
+                    Some(TitleBarButton::Close) => window.close(),
+                    None => return EventResult::Unhandled,
+                }
+                EventResult::Stopped
+            }
+            // The leading widget is an element of its own and sees its
+            // events first
+            _ => EventResult::Unhandled,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+/// Wraps the window content and adds resize zones along the window edges
+pub struct WindowFrame {
+    pub child: Box<dyn Widget>,
+    /// Width of the grab zone along each edge
+    pub border: f32,
+    window: Arc<RwLock<Option<WindowController>>>,
+    size: Arc<RwLock<Size>>,
+    key: Option<WidgetKey>,
+}
+
+impl WindowFrame {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            border: 6.0,
+            window: Arc::new(RwLock::new(None)),
+            size: Arc::new(RwLock::new(Size::zero())),
+            key: None,
+        }
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            border: self.border,
+            window: self.window.clone(),
+            size: self.size.clone(),
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_border(mut self, border: f32) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    fn edge_at(&self, position: Point) -> Option<ResizeEdge> {
+        let size = *self.size.read();
+        let north = position.y <= self.border;
+        let south = position.y >= size.height - self.border;
+        let west = position.x <= self.border;
+        let east = position.x >= size.width - self.border;
+
+        match (north, south, west, east) {
+            (true, _, true, _) => Some(ResizeEdge::NorthWest),
+            (true, _, _, true) => Some(ResizeEdge::NorthEast),
+            (_, true, true, _) => Some(ResizeEdge::SouthWest),
+            (_, true, _, true) => Some(ResizeEdge::SouthEast),
+            (true, ..) => Some(ResizeEdge::North),
+            (_, true, ..) => Some(ResizeEdge::South),
+            (_, _, true, _) => Some(ResizeEdge::West),
+            (.., true) => Some(ResizeEdge::East),
+            _ => None,
+        }
+    }
+}
+
+impl Widget for WindowFrame {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        *self.size.write() = Size::new(ctx.constraints.max_width, ctx.constraints.max_height);
+        *self.window.write() = ctx.window().cloned();
+        self.child.build(ctx)
+    }
+
+    fn handle_event(&self, event: &UiEvent, context: &mut EventContext) -> EventResult {
+        if let UiEvent::PointerDown { position, button: MouseButton::Left, .. } = event {
+            let window = self.window.read().clone();
+            // A maximized window has no edges to drag
+            if let Some(window) = window.filter(|w| !w.is_maximized()) {
+                if let Some(edge) = self.edge_at(*position) {
+                    window.start_resize(edge);
+                    return EventResult::Stopped;
+                }
+            }
+        }
+        self.child.handle_event(event, context)
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::element::new_shared_element_tree;
+    use crate::core::Theme;
+    use crate::runtime::widget_builder::WidgetBuilder;
+    use crate::widgets::layout_widgets::SizedBox;
+    use crate::widgets::Semantics;
+
+    #[test]
+    fn test_leading_content_is_built_after_the_title() {
+        // Semantics builds as a Container holding its child
+        let bar = CustomTitleBar::new("Notes").with_leading(Semantics::new(SizedBox::new(80.0, 20.0)).with_label("Menu"));
+        let root: Box<dyn Widget> = Box::new(bar);
+        let tree = new_shared_element_tree();
+        WidgetBuilder::new(Arc::new(Theme::default()))
+            .with_element_tree(tree.clone())
+            .build_widget_tree(&root, Constraints::new(0.0, 400.0, 0.0, 300.0));
+
+        let tree = tree.read();
+        let sized = tree
+            .iter()
+            .find(|element| element.widget.as_ref().is_some_and(|widget| widget.as_any().is::<SizedBox>()))
+            .expect("the leading content is mounted");
+        let bounds = sized.bounds.expect("and laid out");
+        assert!(bounds.x >= 24.0 + text_width("Notes", &TextStyle { font_size: 13.0, ..Default::default() }));
+        assert_eq!(bounds.width, 80.0);
+    }
+
+    #[test]
+    fn test_caption_buttons_and_resize_edges() {
+        let bar = CustomTitleBar::new("Notes").with_buttons(false, true, true);
+        let buttons: Vec<_> = bar.buttons(400.0).into_iter().map(|(button, rect)| (button, rect.x)).collect();
+        assert_eq!(buttons, vec![(TitleBarButton::Close, 354.0), (TitleBarButton::Maximize, 308.0)]);
+        *bar.width.write() = 400.0;
+        assert_eq!(bar.button_at(Point::new(390.0, 10.0)), Some(TitleBarButton::Close));
+        assert_eq!(bar.button_at(Point::new(200.0, 10.0)), None);
+
+        let frame = WindowFrame::new(SizedBox::new(10.0, 10.0));
+        *frame.size.write() = Size::new(400.0, 300.0);
+        assert_eq!(frame.edge_at(Point::new(2.0, 2.0)), Some(ResizeEdge::NorthWest));
+        assert_eq!(frame.edge_at(Point::new(398.0, 150.0)), Some(ResizeEdge::East));
+        assert_eq!(frame.edge_at(Point::new(200.0, 297.0)), Some(ResizeEdge::South));
+        assert_eq!(frame.edge_at(Point::new(200.0, 150.0)), None);
+    }
+}
@@ -0,0 +1,215 @@
+//! Rotating, scaling and skewing a subtree
+//!
+//! `Transform` paints its child through a matrix built around a chosen
+//! origin (the center by default). The child's element is laid out through
+//! the same matrix, so a rotated button is hit-tested where it is drawn.
+
+use std::any::Any;
+
+use crate::core::context::BuildContext;
+use crate::core::render_object::{Matrix, Point, RenderObject};
+use crate::core::widget::{Widget, WidgetKey, WidgetNode};
+use crate::layout::{Alignment, Size};
+
+/// Paints its child rotated, scaled, skewed and translated
+pub struct Transform {
+    pub child: Box<dyn Widget>,
+    /// Clockwise on screen, in radians
+    pub rotation: f32,
+    pub scale: (f32, f32),
+    /// Shear angles in radians
+    pub skew: (f32, f32),
+    /// Applied after the other transforms, in logical pixels
+    pub translation: (f32, f32),
+    /// Point of the child's box that stays put
+    pub origin: Alignment,
+    /// Overrides `origin` with a point in the child's coordinates
+    pub origin_point: Option<Point>,
+    /// Box the origin is resolved in; defaults to the incoming constraints
+    pub size: Option<Size>,
+    key: Option<WidgetKey>,
+}
+
+impl Transform {
+    pub fn new<W: Widget + 'static>(child: W) -> Self {
+        Self {
+            child: Box::new(child),
+            rotation: 0.0,
+            scale: (1.0, 1.0),
+            skew: (0.0, 0.0),
+            translation: (0.0, 0.0),
+            origin: Alignment::Center,
+            origin_point: None,
+            size: None,
+            key: None,
+        }
+    }
+
+    pub fn rotate<W: Widget + 'static>(child: W, radians: f32) -> Self {
+        Self::new(child).with_rotation(radians)
+    }
+
+    pub fn scale<W: Widget + 'static>(child: W, scale: f32) -> Self {
+        Self::new(child).with_scale(scale, scale)
+    }
+
+    pub fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone_box(),
+            rotation: self.rotation,
+            scale: self.scale,
+            skew: self.skew,
+            translation: self.translation,
+            origin: self.origin,
+            origin_point: self.origin_point,
+            size: self.size,
+            key: self.key.clone(),
+        }
+    }
+
+    pub fn with_rotation(mut self, radians: f32) -> Self {
+        self.rotation = radians;
+        self
+    }
+
+    pub fn with_rotation_degrees(self, degrees: f32) -> Self {
+        self.with_rotation(degrees.to_radians())
+    }
+
+    pub fn with_scale(mut self, x: f32, y: f32) -> Self {
+        self.scale = (x, y);
+        self
+    }
+
+    pub fn with_skew(mut self, x: f32, y: f32) -> Self {
+        self.skew = (x, y);
+        self
+    }
+
+    pub fn with_translation(mut self, x: f32, y: f32) -> Self {
+        self.translation = (x, y);
+        self
+    }
+
+    pub fn with_origin(mut self, origin: Alignment) -> Self {
+        self.origin = origin;
+        self.origin_point = None;
+        self
+    }
+
+    pub fn with_origin_point(mut self, x: f32, y: f32) -> Self {
+        self.origin_point = Some(Point::new(x, y));
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(Size::new(width, height));
+        self
+    }
+
+    pub fn with_key(mut self, key: WidgetKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Child coordinates to parent coordinates, for a child laid out in `size`
+    pub fn matrix(&self, size: Size) -> Matrix {
+        let origin = self.origin_point.unwrap_or_else(|| {
+            let (x, y) = self.origin.align(Size::zero(), size);
+            Point::new(x, y)
+        });
+        let local = Matrix::rotate(self.rotation)
+            .concat(&Matrix::skew(self.skew.0, self.skew.1))
+            .concat(&Matrix::scale(self.scale.0, self.scale.1));
+        Matrix::translate(self.translation.0, self.translation.1).concat(&local.around(origin))
+    }
+
+    fn box_size(&self, ctx: &BuildContext) -> Size {
+        self.size.unwrap_or_else(|| {
+            let finite = |extent: f32| if extent.is_finite() { extent } else { 0.0 };
+            Size::new(finite(ctx.constraints.max_width), finite(ctx.constraints.max_height))
+        })
+    }
+}
+
+impl Widget for Transform {
+    fn build(&self, ctx: &BuildContext) -> WidgetNode {
+        let matrix = self.matrix(self.box_size(ctx));
+        match ctx.build_child(self.child.as_ref()) {
+            WidgetNode::Leaf(render_obj) => WidgetNode::Leaf(RenderObject::transform(matrix, render_obj)),
+            other => other,
+        }
+    }
+
+    fn key(&self) -> Option<WidgetKey> {
+        self.key.clone()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::render_object::Rect;
+    use crate::widgets::basic::Text;
+
+    #[test]
+    fn test_rotation_around_center_maps_back_for_hit_testing() {
+        let rotated = Transform::rotate(Text::new("Spin"), std::f32::consts::FRAC_PI_2);
+        let matrix = rotated.matrix(Size::new(100.0, 40.0));
+
+        // A quarter turn about the center keeps the center fixed...
+        let center = matrix.map_point(Point::new(50.0, 20.0));
+        assert!((center.x - 50.0).abs() < 1e-4 && (center.y - 20.0).abs() < 1e-4);
+
+        // ...and stands the box on its end
+        let bounds = matrix.map_rect(Rect::new(0.0, 0.0, 100.0, 40.0));
+        assert!((bounds.width - 40.0).abs() < 1e-3 && (bounds.height - 100.0).abs() < 1e-3);
+
+        // A click near the drawn top end lands near the child's left end
+        let local = matrix.invert().unwrap().map_point(Point::new(50.0, -25.0));
+        assert!((local.x - 5.0).abs() < 1e-3 && (local.y - 20.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_children_are_mapped_from_window_space_below_other_content() {
+        use std::sync::Arc;
+
+        use crate::core::element::new_shared_element_tree;
+        use crate::core::Theme;
+        use crate::layout::Constraints;
+        use crate::runtime::widget_builder::WidgetBuilder;
+        use crate::widgets::basic::Column;
+        use crate::widgets::layout_widgets::SizedBox;
+
+        // Doubled about its center, 100px down the window
+        let root: Box<dyn Widget> = Box::new(Column::new().with_children(vec![
+            Box::new(SizedBox::new(400.0, 100.0)),
+            Box::new(Transform::scale(SizedBox::new(50.0, 50.0), 2.0).with_size(50.0, 50.0)),
+        ]));
+        let tree = new_shared_element_tree();
+        WidgetBuilder::new(Arc::new(Theme::default()))
+            .with_element_tree(tree.clone())
+            .build_widget_tree(&root, Constraints::new(0.0, 400.0, 0.0, 300.0));
+        let tree = tree.read();
+
+        let transform = tree.iter().find(|element| {
+            element.widget.as_ref().is_some_and(|widget| widget.as_any().is::<Transform>())
+        });
+        let transform = transform.expect("the transform is mounted");
+        assert_eq!(transform.transform.map_point(Point::new(0.0, 0.0)), Point::new(0.0, 100.0));
+
+        // The window point over the child's drawn top left corner is its
+        // own top left, and the drawn center stays the center
+        let child = transform.children[0];
+        assert_eq!(tree.to_local(child, Point::new(-25.0, 75.0)), Some(Point::new(0.0, 0.0)));
+        assert_eq!(tree.to_local(child, Point::new(25.0, 125.0)), Some(Point::new(25.0, 25.0)));
+    }
+}
@@ -41,16 +41,22 @@ impl Widget for ComprehensiveDemo {
                                                         Box::new(Text::new(format!("Click count: {}", self.click_count.load(Ordering::SeqCst))).with_color(theme.foreground)),
                                                         spacer(32.0),
 
-                                                        // Basic Widgets Section
-                                                        self.build_basic_section(ctx),
+                                                        // Basic Widgets Section (static, memoized)
+                                                        Box::new(
+                                                            Memo::new((), |_, ctx| Self::build_basic_section(ctx))
+                                                                .with_key(WidgetKey::string("demo-basic-section"))
+                                                        ),
                                                         spacer(32.0),
 
                                                         // Interactive Section
                                                         self.build_interactive_section(ctx),
                                                         spacer(32.0),
 
-                                                        // Layout Section
-                                                        self.build_layout_section(ctx),
+                                                        // Layout Section (static, memoized)
+                                                        Box::new(
+                                                            Memo::new((), |_, ctx| Self::build_layout_section(ctx))
+                                                                .with_key(WidgetKey::string("demo-layout-section"))
+                                                        ),
                                                     ])
                                                 )
                                         )
@@ -79,7 +85,7 @@ impl Widget for ComprehensiveDemo {
 }
 
 impl ComprehensiveDemo {
-    fn build_basic_section(&self, ctx: &BuildContext) -> Box<dyn Widget> {
+    fn build_basic_section(ctx: &BuildContext) -> Box<dyn Widget> {
         let theme = ctx.theme();
         Box::new(
             Card::new()
@@ -196,7 +202,7 @@ impl ComprehensiveDemo {
         )
     }
 
-    fn build_layout_section(&self, ctx: &BuildContext) -> Box<dyn Widget> {
+    fn build_layout_section(ctx: &BuildContext) -> Box<dyn Widget> {
         let theme = ctx.theme();
 
         Box::new(